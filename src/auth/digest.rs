@@ -0,0 +1,491 @@
+//! Digest HTTP authentication implementation.
+//!
+//! This module answers RFC 7616 Digest challenges. A request carrying
+//! `# @digest` credentials is first sent without an Authorization header;
+//! when the server replies with a `WWW-Authenticate: Digest ...` challenge,
+//! the Authorization header is computed from it and the request is sent once
+//! more. MD5 and SHA-256 (and their `-sess` variants) are supported with
+//! `qop=auth` or without a qop.
+
+use crate::auth::AuthError;
+use crate::models::headers::Headers;
+use crate::models::request::HttpRequest;
+use crate::models::response::HttpResponse;
+use md5::Md5;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// Hash algorithms a Digest challenge can ask for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DigestAlgorithm {
+    /// MD5, the default when the challenge names none
+    Md5,
+    /// MD5 with the session key mixed in
+    Md5Sess,
+    /// SHA-256
+    Sha256,
+    /// SHA-256 with the session key mixed in
+    Sha256Sess,
+}
+
+impl DigestAlgorithm {
+    /// Parses an `algorithm` parameter (case-insensitive).
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_ascii_uppercase().as_str() {
+            "MD5" => Some(DigestAlgorithm::Md5),
+            "MD5-SESS" => Some(DigestAlgorithm::Md5Sess),
+            "SHA-256" => Some(DigestAlgorithm::Sha256),
+            "SHA-256-SESS" => Some(DigestAlgorithm::Sha256Sess),
+            _ => None,
+        }
+    }
+
+    /// Returns the name used in the Authorization header.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DigestAlgorithm::Md5 => "MD5",
+            DigestAlgorithm::Md5Sess => "MD5-sess",
+            DigestAlgorithm::Sha256 => "SHA-256",
+            DigestAlgorithm::Sha256Sess => "SHA-256-sess",
+        }
+    }
+
+    /// Returns `true` for the `-sess` variants.
+    fn is_session(&self) -> bool {
+        matches!(self, DigestAlgorithm::Md5Sess | DigestAlgorithm::Sha256Sess)
+    }
+
+    /// Hashes `data` and returns the lowercase hex digest.
+    fn hash(&self, data: &str) -> String {
+        let digest = match self {
+            DigestAlgorithm::Md5 | DigestAlgorithm::Md5Sess => Md5::digest(data).to_vec(),
+            DigestAlgorithm::Sha256 | DigestAlgorithm::Sha256Sess => Sha256::digest(data).to_vec(),
+        };
+        digest.iter().map(|byte| format!("{:02x}", byte)).collect()
+    }
+}
+
+/// A parsed `WWW-Authenticate: Digest ...` challenge.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DigestChallenge {
+    /// Protection space the credentials apply to
+    pub realm: String,
+    /// Server nonce
+    pub nonce: String,
+    /// Opaque value to echo back, if the server sent one
+    pub opaque: Option<String>,
+    /// Hash algorithm to use
+    pub algorithm: DigestAlgorithm,
+    /// Whether the server offered `qop=auth`; `false` for legacy challenges without a qop
+    pub qop_auth: bool,
+}
+
+impl DigestChallenge {
+    /// Parses a `WWW-Authenticate` header value.
+    ///
+    /// The value may list several challenges (e.g., `Basic` and `Digest`);
+    /// the first Digest challenge is used.
+    ///
+    /// # Arguments
+    ///
+    /// * `header` - The `WWW-Authenticate` header value
+    ///
+    /// # Returns
+    ///
+    /// `Ok(DigestChallenge)`, or `Err(AuthError)` if there is no Digest
+    /// challenge, it lacks a realm or nonce, or it asks for an unsupported
+    /// algorithm or qop.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rest_client::auth::digest::{DigestAlgorithm, DigestChallenge};
+    ///
+    /// let challenge = DigestChallenge::parse(
+    ///     r#"Digest realm="api", qop="auth", algorithm=SHA-256, nonce="abc123""#,
+    /// )
+    /// .unwrap();
+    /// assert_eq!(challenge.realm, "api");
+    /// assert_eq!(challenge.algorithm, DigestAlgorithm::Sha256);
+    /// assert!(challenge.qop_auth);
+    /// ```
+    pub fn parse(header: &str) -> Result<Self, AuthError> {
+        let params = find_digest_params(header).ok_or_else(|| {
+            AuthError::UnsupportedScheme(format!("no Digest challenge in '{}'", header))
+        })?;
+        let params = parse_auth_params(params);
+        let param = |name: &str| {
+            params
+                .iter()
+                .find(|(key, _)| key.eq_ignore_ascii_case(name))
+                .map(|(_, value)| value.clone())
+        };
+
+        let realm = param("realm")
+            .ok_or_else(|| AuthError::InvalidFormat("Digest challenge has no realm".to_string()))?;
+        let nonce = param("nonce")
+            .ok_or_else(|| AuthError::InvalidFormat("Digest challenge has no nonce".to_string()))?;
+        let algorithm = match param("algorithm") {
+            Some(name) => DigestAlgorithm::parse(&name).ok_or_else(|| {
+                AuthError::UnsupportedScheme(format!("Digest algorithm {}", name))
+            })?,
+            None => DigestAlgorithm::Md5,
+        };
+        let qop_auth = match param("qop") {
+            Some(qop) => {
+                if !qop.split(',').any(|option| option.trim() == "auth") {
+                    return Err(AuthError::UnsupportedScheme(format!("Digest qop={}", qop)));
+                }
+                true
+            }
+            None => false,
+        };
+
+        Ok(DigestChallenge {
+            realm,
+            nonce,
+            opaque: param("opaque"),
+            algorithm,
+            qop_auth,
+        })
+    }
+
+    /// Finds the Digest challenge among a response's `WWW-Authenticate` headers.
+    ///
+    /// # Returns
+    ///
+    /// `None` if no header has a Digest challenge, otherwise the result of
+    /// parsing the first one.
+    pub fn from_headers(headers: &Headers) -> Option<Result<Self, AuthError>> {
+        headers
+            .get_all("WWW-Authenticate")
+            .find(|value| find_digest_params(value).is_some())
+            .map(|value| DigestChallenge::parse(value))
+    }
+}
+
+/// Credentials for Digest authentication, set with `# @digest user pass`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DigestAuth {
+    /// The username for authentication
+    pub username: String,
+    /// The password for authentication
+    pub password: String,
+}
+
+impl DigestAuth {
+    /// Creates Digest credentials.
+    pub fn new(username: String, password: String) -> Self {
+        Self { username, password }
+    }
+
+    /// Computes the `response` hash for a challenge.
+    ///
+    /// # Arguments
+    ///
+    /// * `challenge` - The server's challenge
+    /// * `method` - The request method (e.g., "GET")
+    /// * `uri` - The request target (path and query)
+    /// * `nonce_count` - How many times the nonce has been used, starting at 1
+    /// * `cnonce` - Client nonce
+    ///
+    /// # Returns
+    ///
+    /// The lowercase hex digest to send as `response`.
+    pub fn response(
+        &self,
+        challenge: &DigestChallenge,
+        method: &str,
+        uri: &str,
+        nonce_count: u32,
+        cnonce: &str,
+    ) -> String {
+        let algorithm = challenge.algorithm;
+        let mut ha1 = algorithm.hash(&format!(
+            "{}:{}:{}",
+            self.username, challenge.realm, self.password
+        ));
+        if algorithm.is_session() {
+            ha1 = algorithm.hash(&format!("{}:{}:{}", ha1, challenge.nonce, cnonce));
+        }
+        let ha2 = algorithm.hash(&format!("{}:{}", method, uri));
+
+        if challenge.qop_auth {
+            algorithm.hash(&format!(
+                "{}:{}:{:08x}:{}:auth:{}",
+                ha1, challenge.nonce, nonce_count, cnonce, ha2
+            ))
+        } else {
+            algorithm.hash(&format!("{}:{}:{}", ha1, challenge.nonce, ha2))
+        }
+    }
+
+    /// Builds the Authorization header value answering a challenge.
+    ///
+    /// # Arguments
+    ///
+    /// * `challenge` - The server's challenge
+    /// * `method` - The request method (e.g., "GET")
+    /// * `uri` - The request target (path and query)
+    /// * `nonce_count` - How many times the nonce has been used, starting at 1
+    /// * `cnonce` - Client nonce
+    ///
+    /// # Returns
+    ///
+    /// A `String` in the format `Digest username="...", realm="...", ...`
+    pub fn authorization(
+        &self,
+        challenge: &DigestChallenge,
+        method: &str,
+        uri: &str,
+        nonce_count: u32,
+        cnonce: &str,
+    ) -> String {
+        let response = self.response(challenge, method, uri, nonce_count, cnonce);
+
+        let mut header = format!(
+            "Digest username=\"{}\", realm=\"{}\", uri=\"{}\", algorithm={}, nonce=\"{}\"",
+            quote(&self.username),
+            quote(&challenge.realm),
+            quote(uri),
+            challenge.algorithm.as_str(),
+            quote(&challenge.nonce)
+        );
+        if challenge.qop_auth {
+            header.push_str(&format!(
+                ", nc={:08x}, cnonce=\"{}\", qop=auth",
+                nonce_count,
+                quote(cnonce)
+            ));
+        }
+        header.push_str(&format!(", response=\"{}\"", response));
+        if let Some(opaque) = &challenge.opaque {
+            header.push_str(&format!(", opaque=\"{}\"", quote(opaque)));
+        }
+        header
+    }
+}
+
+/// Prepares the retry of a request that was answered with a Digest challenge.
+///
+/// Only requests with `# @digest` credentials and no Authorization header of
+/// their own are retried, so a rejected retry is never retried again.
+///
+/// # Arguments
+///
+/// * `request` - The request that was sent
+/// * `response` - The response it received
+///
+/// # Returns
+///
+/// `Ok(Some(HttpRequest))` with the Authorization header added,
+/// `Ok(None)` if the request should not be retried, or `Err(AuthError)` if
+/// the challenge can't be answered.
+pub fn authorize_challenge(
+    request: &HttpRequest,
+    response: &HttpResponse,
+) -> Result<Option<HttpRequest>, AuthError> {
+    let Some(credentials) = &request.digest_auth else {
+        return Ok(None);
+    };
+    if request.headers.contains_key("Authorization") {
+        return Ok(None);
+    }
+    let Some(challenge) = DigestChallenge::from_headers(&response.headers) else {
+        return Ok(None);
+    };
+    let challenge = challenge?;
+
+    let url = url::Url::parse(&request.url)
+        .map_err(|e| AuthError::InvalidFormat(format!("Invalid URL '{}': {}", request.url, e)))?;
+    let uri = &url[url::Position::BeforePath..url::Position::AfterQuery];
+    let cnonce: String = rand::random::<[u8; 16]>()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect();
+
+    let mut authorized = request.clone();
+    authorized.headers.insert(
+        "Authorization".to_string(),
+        credentials.authorization(&challenge, request.method.as_str(), uri, 1, &cnonce),
+    );
+    Ok(Some(authorized))
+}
+
+/// Returns the parameters following the `Digest` scheme name in a header value.
+fn find_digest_params(header: &str) -> Option<&str> {
+    let lower = header.to_ascii_lowercase();
+    let mut offset = 0;
+    while let Some(index) = lower[offset..].find("digest") {
+        let start = offset + index;
+        let end = start + "digest".len();
+        let at_scheme_start =
+            lower[..start].trim_end().is_empty() || lower[..start].trim_end().ends_with(',');
+        let followed_by_space = lower[end..].starts_with(char::is_whitespace);
+        if at_scheme_start && followed_by_space {
+            return Some(&header[end..]);
+        }
+        offset = end;
+    }
+    None
+}
+
+/// Parses comma-separated `name=value` and `name="value"` parameters.
+///
+/// Parsing stops at a token without `=`, which starts the next challenge.
+fn parse_auth_params(params: &str) -> Vec<(String, String)> {
+    let chars: Vec<char> = params.chars().collect();
+    let mut parsed = Vec::new();
+    let mut i = 0;
+
+    loop {
+        while i < chars.len() && (chars[i].is_whitespace() || chars[i] == ',') {
+            i += 1;
+        }
+        let start = i;
+        while i < chars.len() && chars[i] != '=' && chars[i] != ',' && !chars[i].is_whitespace() {
+            i += 1;
+        }
+        if start == i || chars.get(i) != Some(&'=') {
+            break;
+        }
+        let name: String = chars[start..i].iter().collect();
+        i += 1;
+
+        let mut value = String::new();
+        if chars.get(i) == Some(&'"') {
+            i += 1;
+            while i < chars.len() && chars[i] != '"' {
+                if chars[i] == '\\' && i + 1 < chars.len() {
+                    i += 1;
+                }
+                value.push(chars[i]);
+                i += 1;
+            }
+            i += 1;
+        } else {
+            while i < chars.len() && chars[i] != ',' && !chars[i].is_whitespace() {
+                value.push(chars[i]);
+                i += 1;
+            }
+        }
+        parsed.push((name, value));
+    }
+
+    parsed
+}
+
+/// Escapes a value for use inside a quoted string.
+fn quote(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::request::HttpMethod;
+
+    /// The challenge from the RFC 7616 section 3.9.1 example.
+    fn rfc_challenge(algorithm: &str) -> String {
+        format!(
+            "Digest realm=\"http-auth@example.org\", qop=\"auth, auth-int\", \
+             algorithm={}, nonce=\"7ypf/xlj9XXwfDPEoM4URrv/xwf94BcCAzFZH4GiTo0v\", \
+             opaque=\"FQhe/qaU925kfnzjCev0ciny7QMkPqMAFRtzCUYo5tdS\"",
+            algorithm
+        )
+    }
+
+    const RFC_CNONCE: &str = "f2/wE4q74E6zIJEtWaHKaf5wv/H5QzzpXusqGemxURZJ";
+
+    fn rfc_credentials() -> DigestAuth {
+        DigestAuth::new("Mufasa".to_string(), "Circle of Life".to_string())
+    }
+
+    #[test]
+    fn test_response_matches_rfc_example_md5() {
+        let challenge = DigestChallenge::parse(&rfc_challenge("MD5")).unwrap();
+
+        assert_eq!(
+            rfc_credentials().response(&challenge, "GET", "/dir/index.html", 1, RFC_CNONCE),
+            "8ca523f5e9506fed4657c9700eebdbec"
+        );
+    }
+
+    #[test]
+    fn test_response_matches_rfc_example_sha256() {
+        let challenge = DigestChallenge::parse(&rfc_challenge("SHA-256")).unwrap();
+
+        assert_eq!(
+            rfc_credentials().response(&challenge, "GET", "/dir/index.html", 1, RFC_CNONCE),
+            "753927fa0e85d155564e2e272a28d1802ca10daf4496794697cf8db5856cb6c1"
+        );
+    }
+
+    #[test]
+    fn test_authorization_header() {
+        let challenge = DigestChallenge::parse(&rfc_challenge("MD5")).unwrap();
+
+        assert_eq!(
+            rfc_credentials().authorization(&challenge, "GET", "/dir/index.html", 1, RFC_CNONCE),
+            "Digest username=\"Mufasa\", realm=\"http-auth@example.org\", \
+             uri=\"/dir/index.html\", algorithm=MD5, \
+             nonce=\"7ypf/xlj9XXwfDPEoM4URrv/xwf94BcCAzFZH4GiTo0v\", nc=00000001, \
+             cnonce=\"f2/wE4q74E6zIJEtWaHKaf5wv/H5QzzpXusqGemxURZJ\", qop=auth, \
+             response=\"8ca523f5e9506fed4657c9700eebdbec\", \
+             opaque=\"FQhe/qaU925kfnzjCev0ciny7QMkPqMAFRtzCUYo5tdS\""
+        );
+    }
+
+    #[test]
+    fn test_parse_challenge_variants() {
+        let challenge =
+            DigestChallenge::parse(r#"Basic realm="x", Digest realm="a, b", nonce=n1"#).unwrap();
+        assert_eq!(challenge.realm, "a, b");
+        assert_eq!(challenge.nonce, "n1");
+        assert_eq!(challenge.algorithm, DigestAlgorithm::Md5);
+        assert!(!challenge.qop_auth);
+        assert_eq!(challenge.opaque, None);
+
+        assert!(matches!(
+            DigestChallenge::parse(r#"Basic realm="x""#),
+            Err(AuthError::UnsupportedScheme(_))
+        ));
+        assert!(matches!(
+            DigestChallenge::parse(r#"Digest realm="x""#),
+            Err(AuthError::InvalidFormat(_))
+        ));
+        assert!(matches!(
+            DigestChallenge::parse(r#"Digest realm="x", nonce="n", qop="auth-int""#),
+            Err(AuthError::UnsupportedScheme(_))
+        ));
+        assert!(matches!(
+            DigestChallenge::parse(r#"Digest realm="x", nonce="n", algorithm=SHA-512-256"#),
+            Err(AuthError::UnsupportedScheme(_))
+        ));
+    }
+
+    #[test]
+    fn test_authorize_challenge() {
+        let mut request = HttpRequest::new(
+            "digest-1".to_string(),
+            HttpMethod::GET,
+            "https://example.org/dir/index.html?page=2".to_string(),
+        );
+        let mut response = HttpResponse::new(401, "Unauthorized".to_string());
+        response.add_header("WWW-Authenticate".to_string(), rfc_challenge("MD5"));
+
+        // Without credentials the 401 is returned as-is
+        assert!(authorize_challenge(&request, &response).unwrap().is_none());
+
+        request.digest_auth = Some(rfc_credentials());
+        let authorized = authorize_challenge(&request, &response).unwrap().unwrap();
+        let header = authorized.headers.get("Authorization").unwrap();
+        assert!(header.starts_with("Digest username=\"Mufasa\""));
+        assert!(header.contains("uri=\"/dir/index.html?page=2\""));
+
+        // An already authorized request is not retried again
+        assert!(authorize_challenge(&authorized, &response)
+            .unwrap()
+            .is_none());
+    }
+}
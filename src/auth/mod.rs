@@ -1,11 +1,14 @@
 //! HTTP authentication module.
 //!
 //! This module provides authentication handlers for HTTP requests, supporting
-//! Basic and Bearer authentication schemes. Authentication can be detected
-//! from Authorization headers or special comment directives in .http files.
+//! Basic, Bearer, Digest, and API key authentication schemes, plus AWS SigV4
+//! signing. Authentication can be detected from Authorization headers or
+//! special comment directives in .http files.
 
 pub mod basic;
 pub mod bearer;
+pub mod digest;
+pub mod sigv4;
 
 use crate::models::request::HttpRequest;
 use std::fmt;
@@ -17,10 +20,27 @@ pub enum AuthScheme {
     Basic { username: String, password: String },
     /// Bearer token authentication (RFC 6750)
     Bearer { token: String },
+    /// HTTP Digest authentication (RFC 7616), answered when the server challenges
+    Digest { username: String, password: String },
+    /// API key sent as a query parameter or a header
+    ApiKey {
+        name: String,
+        value: String,
+        location: ApiKeyLocation,
+    },
     /// No authentication
     None,
 }
 
+/// Where an API key is placed in the request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApiKeyLocation {
+    /// Appended to the URL's query string
+    Query,
+    /// Sent as a request header
+    Header,
+}
+
 /// Errors that can occur during authentication processing.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum AuthError {
@@ -123,7 +143,49 @@ pub fn detect_auth_scheme(request: &HttpRequest) -> AuthScheme {
 /// ```
 pub fn apply_authentication(request: &mut HttpRequest) -> Result<(), AuthError> {
     let auth_scheme = detect_auth_scheme(request);
+    apply_auth_scheme(request, auth_scheme)
+}
 
+/// Applies a given authentication scheme to an HTTP request.
+///
+/// Basic and Bearer credentials are written to the Authorization header, and
+/// an API key is added as a query parameter or header depending on its
+/// location.
+///
+/// # Arguments
+///
+/// * `request` - A mutable reference to the HTTP request
+/// * `auth_scheme` - The scheme to apply
+///
+/// # Returns
+///
+/// `Ok(())` if authentication was applied successfully, or an `AuthError` if
+/// something went wrong.
+///
+/// # Examples
+///
+/// ```
+/// use rest_client::models::request::{HttpRequest, HttpMethod};
+/// use rest_client::auth::{apply_auth_scheme, ApiKeyLocation, AuthScheme};
+///
+/// let mut request = HttpRequest::new(
+///     "test-id".to_string(),
+///     HttpMethod::GET,
+///     "https://api.example.com/data?page=2".to_string()
+/// );
+///
+/// let scheme = AuthScheme::ApiKey {
+///     name: "api_key".to_string(),
+///     value: "secret".to_string(),
+///     location: ApiKeyLocation::Query,
+/// };
+/// apply_auth_scheme(&mut request, scheme).unwrap();
+/// assert_eq!(request.url, "https://api.example.com/data?page=2&api_key=secret");
+/// ```
+pub fn apply_auth_scheme(
+    request: &mut HttpRequest,
+    auth_scheme: AuthScheme,
+) -> Result<(), AuthError> {
     match auth_scheme {
         AuthScheme::Basic { username, password } => {
             // Re-encode to ensure proper formatting
@@ -141,8 +203,24 @@ pub fn apply_authentication(request: &mut HttpRequest) -> Result<(), AuthError>
             update_auth_header(request, auth_value);
             Ok(())
         }
-        AuthScheme::None => {
-            // No authentication needed
+        AuthScheme::ApiKey {
+            name,
+            value,
+            location,
+        } => {
+            match location {
+                ApiKeyLocation::Query => {
+                    request.url = append_query_param(&request.url, &name, &value);
+                }
+                ApiKeyLocation::Header => {
+                    request.headers.insert(name, value);
+                }
+            }
+            Ok(())
+        }
+        AuthScheme::Digest { .. } | AuthScheme::None => {
+            // Digest is answered by the executor once the server challenges;
+            // no authentication needed otherwise
             Ok(())
         }
     }
@@ -164,11 +242,46 @@ fn update_auth_header(request: &mut HttpRequest, auth_value: String) {
         .insert("Authorization".to_string(), auth_value);
 }
 
+/// Appends a `name=value` pair to a URL's query string.
+///
+/// The name and value are percent-encoded. Uses `?` or `&` depending on
+/// whether the URL already has a query string, and keeps any `#fragment` at
+/// the end.
+///
+/// # Examples
+///
+/// ```
+/// use rest_client::auth::append_query_param;
+///
+/// assert_eq!(
+///     append_query_param("https://api.example.com/data?page=2", "key", "a&b c"),
+///     "https://api.example.com/data?page=2&key=a%26b+c"
+/// );
+/// ```
+pub fn append_query_param(url: &str, name: &str, value: &str) -> String {
+    let (base, fragment) = match url.find('#') {
+        Some(index) => url.split_at(index),
+        None => (url, ""),
+    };
+
+    let separator = match base.find('?') {
+        None => "?",
+        Some(index) if index + 1 == base.len() || base.ends_with('&') => "",
+        Some(_) => "&",
+    };
+
+    let name: String = url::form_urlencoded::byte_serialize(name.as_bytes()).collect();
+    let value: String = url::form_urlencoded::byte_serialize(value.as_bytes()).collect();
+    format!("{}{}{}={}{}", base, separator, name, value, fragment)
+}
+
 /// Parses authentication from a comment directive.
 ///
 /// Supports the following formats:
 /// - `# @basic username password` - Basic authentication
 /// - `# @bearer token` - Bearer token authentication
+/// - `# @digest username password` - Digest authentication
+/// - `# @apikey query|header name value` - API key authentication
 ///
 /// # Arguments
 ///
@@ -227,6 +340,42 @@ pub fn parse_auth_comment(comment: &str) -> AuthScheme {
         }
     }
 
+    // Check for @digest directive
+    if let Some(rest) = content.strip_prefix("@digest") {
+        let rest = rest.trim();
+        let parts: Vec<&str> = rest.splitn(2, char::is_whitespace).collect();
+
+        if parts.len() == 2 {
+            return AuthScheme::Digest {
+                username: parts[0].to_string(),
+                password: parts[1].trim().to_string(),
+            };
+        }
+    }
+
+    // Check for @apikey directive
+    if let Some(rest) = content.strip_prefix("@apikey") {
+        let parts: Vec<&str> = rest.trim().splitn(3, char::is_whitespace).collect();
+
+        if parts.len() == 3 {
+            let location = if parts[0].eq_ignore_ascii_case("query") {
+                Some(ApiKeyLocation::Query)
+            } else if parts[0].eq_ignore_ascii_case("header") {
+                Some(ApiKeyLocation::Header)
+            } else {
+                None
+            };
+
+            if let Some(location) = location {
+                return AuthScheme::ApiKey {
+                    name: parts[1].to_string(),
+                    value: parts[2].trim().to_string(),
+                    location,
+                };
+            }
+        }
+    }
+
     AuthScheme::None
 }
 
@@ -367,7 +516,8 @@ mod tests {
         // Update should remove old and add new
         update_auth_header(&mut request, "Bearer new".to_string());
 
-        assert!(!request.headers.contains_key("authorization"));
+        assert!(!request.headers.keys().any(|k| k == "authorization"));
+        assert_eq!(request.headers.len(), 1);
         assert_eq!(
             request.headers.get("Authorization"),
             Some(&"Bearer new".to_string())
@@ -421,6 +571,116 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_auth_comment_digest() {
+        let scheme = parse_auth_comment("# @digest admin s3cret pass");
+        assert_eq!(
+            scheme,
+            AuthScheme::Digest {
+                username: "admin".to_string(),
+                password: "s3cret pass".to_string(),
+            }
+        );
+
+        // Missing password
+        assert_eq!(parse_auth_comment("# @digest admin"), AuthScheme::None);
+    }
+
+    #[test]
+    fn test_parse_auth_comment_apikey() {
+        assert_eq!(
+            parse_auth_comment("# @apikey query api_key {{KEY}}"),
+            AuthScheme::ApiKey {
+                name: "api_key".to_string(),
+                value: "{{KEY}}".to_string(),
+                location: ApiKeyLocation::Query,
+            }
+        );
+        assert_eq!(
+            parse_auth_comment("# @apikey Header X-API-Key abc123"),
+            AuthScheme::ApiKey {
+                name: "X-API-Key".to_string(),
+                value: "abc123".to_string(),
+                location: ApiKeyLocation::Header,
+            }
+        );
+
+        // Unknown location or missing value
+        assert_eq!(
+            parse_auth_comment("# @apikey cookie key abc"),
+            AuthScheme::None
+        );
+        assert_eq!(parse_auth_comment("# @apikey query key"), AuthScheme::None);
+    }
+
+    #[test]
+    fn test_apply_auth_scheme_apikey_query() {
+        let scheme = AuthScheme::ApiKey {
+            name: "api_key".to_string(),
+            value: "abc123".to_string(),
+            location: ApiKeyLocation::Query,
+        };
+
+        let mut request = HttpRequest::new(
+            "test".to_string(),
+            HttpMethod::GET,
+            "https://api.example.com/data".to_string(),
+        );
+        apply_auth_scheme(&mut request, scheme.clone()).unwrap();
+        assert_eq!(request.url, "https://api.example.com/data?api_key=abc123");
+        assert!(!request.headers.contains_key("api_key"));
+
+        let mut request = HttpRequest::new(
+            "test".to_string(),
+            HttpMethod::GET,
+            "https://api.example.com/data?page=2#results".to_string(),
+        );
+        apply_auth_scheme(&mut request, scheme).unwrap();
+        assert_eq!(
+            request.url,
+            "https://api.example.com/data?page=2&api_key=abc123#results"
+        );
+
+        let mut request = HttpRequest::new(
+            "test".to_string(),
+            HttpMethod::GET,
+            "https://api.example.com/data".to_string(),
+        );
+        let scheme = AuthScheme::ApiKey {
+            name: "api key".to_string(),
+            value: "a+b/c=&d".to_string(),
+            location: ApiKeyLocation::Query,
+        };
+        apply_auth_scheme(&mut request, scheme).unwrap();
+        assert_eq!(
+            request.url,
+            "https://api.example.com/data?api+key=a%2Bb%2Fc%3D%26d"
+        );
+    }
+
+    #[test]
+    fn test_apply_auth_scheme_apikey_header() {
+        let mut request = HttpRequest::new(
+            "test".to_string(),
+            HttpMethod::GET,
+            "https://api.example.com/data".to_string(),
+        );
+        request.add_header("x-api-key".to_string(), "old".to_string());
+
+        let scheme = AuthScheme::ApiKey {
+            name: "X-API-Key".to_string(),
+            value: "abc123".to_string(),
+            location: ApiKeyLocation::Header,
+        };
+        apply_auth_scheme(&mut request, scheme).unwrap();
+
+        assert_eq!(request.url, "https://api.example.com/data");
+        assert_eq!(
+            request.headers.get_all("X-API-Key").collect::<Vec<_>>(),
+            vec!["abc123"]
+        );
+    }
+
     #[test]
     fn test_parse_auth_comment_with_double_slash() {
         let scheme = parse_auth_comment("// @basic user pass");
@@ -0,0 +1,265 @@
+//! Go code generation for HTTP requests.
+//!
+//! This module provides a code generator for Go's standard library
+//! `net/http` client.
+
+use crate::models::request::HttpRequest;
+
+/// Generates Go code using the standard library net/http package.
+///
+/// Creates a self-contained `main` package that builds the request with
+/// `http.NewRequest`, sets headers with `req.Header.Set`, sends the body from
+/// a `bytes.NewBuffer`, and prints the response returned by `client.Do`. No
+/// external dependencies are required.
+///
+/// # Arguments
+///
+/// * `request` - The HTTP request to generate code for
+///
+/// # Returns
+///
+/// A string containing the generated Go code with comments
+pub fn generate_net_http_code(request: &HttpRequest) -> String {
+    let mut code = String::new();
+
+    // Add header comment
+    code.push_str(&format!(
+        "// Generated net/http code for {} request\n",
+        request.method.as_str()
+    ));
+    code.push_str("// This code uses Go's standard library (no external dependencies)\n\n");
+
+    code.push_str("package main\n\n");
+
+    // Only import what the snippet uses, as Go rejects unused imports
+    let mut imports = Vec::new();
+    if request.body.is_some() {
+        imports.push("bytes");
+    }
+    imports.extend(["encoding/json", "fmt", "io", "net/http"]);
+    if request.body.is_none() && request.body_file.is_some() {
+        imports.push("os");
+    }
+    imports.push("time");
+
+    code.push_str("import (\n");
+    for import in imports {
+        code.push_str(&format!("\t\"{}\"\n", import));
+    }
+    code.push_str(")\n\n");
+
+    code.push_str("func main() {\n");
+
+    // Add body if present
+    let body_arg = if let Some(body) = &request.body {
+        let body = if is_json_content_type(request) {
+            format_json(body)
+        } else {
+            body.clone()
+        };
+        code.push_str("\t// Request body\n");
+        code.push_str(&format!(
+            "\tbody := bytes.NewBuffer([]byte({}))\n\n",
+            go_string_literal(&body)
+        ));
+        "body"
+    } else if let Some(path) = &request.body_file {
+        code.push_str("\t// Request body streamed from a file\n");
+        code.push_str(&format!(
+            "\tbody, err := os.Open({})\n",
+            escape_go_string(&path.display().to_string())
+        ));
+        code.push_str("\tif err != nil {\n");
+        code.push_str("\t\tpanic(err)\n");
+        code.push_str("\t}\n");
+        code.push_str("\tdefer body.Close()\n\n");
+        "body"
+    } else {
+        "nil"
+    };
+
+    // Create the request
+    code.push_str(&format!(
+        "\t// Configure the {} request\n",
+        request.method.as_str()
+    ));
+    code.push_str(&format!(
+        "\treq, err {}= http.NewRequest({}, {}, {})\n",
+        if request.body.is_none() && request.body_file.is_some() {
+            ""
+        } else {
+            ":"
+        },
+        escape_go_string(request.method.as_str()),
+        escape_go_string(&request.url),
+        body_arg
+    ));
+    code.push_str("\tif err != nil {\n");
+    code.push_str("\t\tpanic(err)\n");
+    code.push_str("\t}\n");
+
+    // Add headers; repeated names are added rather than replaced
+    let mut seen: Vec<&str> = Vec::new();
+    for (key, value) in &request.headers {
+        let call = if seen.iter().any(|name| name.eq_ignore_ascii_case(key)) {
+            "Add"
+        } else {
+            "Set"
+        };
+        seen.push(key);
+        code.push_str(&format!(
+            "\treq.Header.{}({}, {})\n",
+            call,
+            escape_go_string(key),
+            escape_go_string(value)
+        ));
+    }
+    code.push('\n');
+
+    // Send the request
+    code.push_str(&format!(
+        "\t// Send the {} request\n",
+        request.method.as_str()
+    ));
+    code.push_str("\tclient := &http.Client{Timeout: 30 * time.Second} // 30 second timeout\n");
+    code.push_str("\tresp, err := client.Do(req)\n");
+    code.push_str("\tif err != nil {\n");
+    code.push_str("\t\tpanic(err)\n");
+    code.push_str("\t}\n");
+    code.push_str("\tdefer resp.Body.Close()\n\n");
+
+    // Read the response
+    code.push_str("\trespBody, err := io.ReadAll(resp.Body)\n");
+    code.push_str("\tif err != nil {\n");
+    code.push_str("\t\tpanic(err)\n");
+    code.push_str("\t}\n\n");
+
+    code.push_str("\tfmt.Println(\"Status Code:\", resp.StatusCode)\n");
+    code.push_str("\tfmt.Println(\"Headers:\", resp.Header)\n\n");
+
+    code.push_str("\t// Try to parse as JSON, otherwise print the text\n");
+    code.push_str("\tvar data interface{}\n");
+    code.push_str("\tif err := json.Unmarshal(respBody, &data); err == nil {\n");
+    code.push_str("\t\tpretty, _ := json.MarshalIndent(data, \"\", \"  \")\n");
+    code.push_str("\t\tfmt.Println(\"Response (JSON):\")\n");
+    code.push_str("\t\tfmt.Println(string(pretty))\n");
+    code.push_str("\t} else {\n");
+    code.push_str("\t\tfmt.Println(\"Response (Text):\")\n");
+    code.push_str("\t\tfmt.Println(string(respBody))\n");
+    code.push_str("\t}\n");
+
+    code.push_str("}\n");
+
+    code
+}
+
+/// Escapes a string as an interpreted Go string literal, including the quotes.
+fn escape_go_string(s: &str) -> String {
+    let mut escaped = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if c.is_control() => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+/// Quotes a body for Go, preferring a raw (backtick) string so multi-line
+/// bodies stay readable.
+///
+/// Falls back to an interpreted string when the body contains a backtick or
+/// a carriage return, which raw strings can't represent.
+fn go_string_literal(s: &str) -> String {
+    if s.contains('`') || s.contains('\r') {
+        escape_go_string(s)
+    } else {
+        format!("`{}`", s)
+    }
+}
+
+/// Pretty-prints a JSON body, leaving it unchanged if it isn't valid JSON.
+fn format_json(json: &str) -> String {
+    serde_json::from_str::<serde_json::Value>(json)
+        .ok()
+        .and_then(|value| serde_json::to_string_pretty(&value).ok())
+        .unwrap_or_else(|| json.to_string())
+}
+
+/// Checks if the request has a JSON content type.
+fn is_json_content_type(request: &HttpRequest) -> bool {
+    request
+        .content_type()
+        .map(|ct| ct.to_lowercase().contains("json"))
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::request::HttpMethod;
+
+    #[test]
+    fn test_escape_go_string() {
+        assert_eq!(escape_go_string("hello"), "\"hello\"");
+        assert_eq!(escape_go_string("say \"hi\""), "\"say \\\"hi\\\"\"");
+        assert_eq!(escape_go_string("a\\b\nc"), "\"a\\\\b\\nc\"");
+        assert_eq!(escape_go_string("\u{1}"), "\"\\u0001\"");
+    }
+
+    #[test]
+    fn test_go_string_literal() {
+        assert_eq!(go_string_literal("{\n  \"a\": 1\n}"), "`{\n  \"a\": 1\n}`");
+        assert_eq!(go_string_literal("a`b"), "\"a`b\"");
+    }
+
+    #[test]
+    fn test_generate_net_http_code_simple_get() {
+        let request = HttpRequest::new(
+            "test".to_string(),
+            HttpMethod::GET,
+            "https://api.example.com/users".to_string(),
+        );
+
+        let code = generate_net_http_code(&request);
+
+        assert!(code.contains("package main"));
+        assert!(code.contains("\"net/http\""));
+        assert!(!code.contains("\"bytes\""));
+        assert!(code.contains(
+            "req, err := http.NewRequest(\"GET\", \"https://api.example.com/users\", nil)"
+        ));
+        assert!(code.contains("resp, err := client.Do(req)"));
+    }
+
+    #[test]
+    fn test_generate_net_http_code_post_with_json() {
+        let mut request = HttpRequest::new(
+            "test".to_string(),
+            HttpMethod::POST,
+            "https://api.example.com/users".to_string(),
+        );
+        request.add_header("Content-Type".to_string(), "application/json".to_string());
+        request.add_header("Accept".to_string(), "application/json".to_string());
+        request.set_body(r#"{"name": "Alice", "age": 30}"#.to_string());
+
+        let code = generate_net_http_code(&request);
+
+        assert!(code.contains("\"bytes\""));
+        assert!(code.contains(
+            "body := bytes.NewBuffer([]byte(`{\n  \"age\": 30,\n  \"name\": \"Alice\"\n}`))"
+        ));
+        assert!(code.contains(
+            "req, err := http.NewRequest(\"POST\", \"https://api.example.com/users\", body)"
+        ));
+        assert!(code.contains("req.Header.Set(\"Content-Type\", \"application/json\")"));
+        assert!(code.contains("req.Header.Set(\"Accept\", \"application/json\")"));
+        assert!(code.contains("client.Do(req)"));
+    }
+}
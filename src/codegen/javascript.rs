@@ -31,6 +31,11 @@ pub fn generate_fetch_code(request: &HttpRequest) -> String {
     ));
     code.push_str("// This code uses the modern fetch API (browser/Node.js 18+)\n\n");
 
+    // Large body files are streamed from disk
+    if request.body_file.is_some() {
+        code.push_str("const fs = require('fs');\n\n");
+    }
+
     // Start the async function
     code.push_str("async function makeRequest() {\n");
     code.push_str("  try {\n");
@@ -68,6 +73,13 @@ pub fn generate_fetch_code(request: &HttpRequest) -> String {
             code.push_str(&format!("'{}',\n", escaped_body));
         }
     }
+    if let Some(path) = &request.body_file {
+        code.push_str(&format!(
+            "      body: fs.createReadStream('{}'),\n",
+            escape_js_string(&path.display().to_string())
+        ));
+        code.push_str("      duplex: 'half', // Required by Node.js to stream a request body\n");
+    }
 
     code.push_str("    };\n\n");
 
@@ -143,6 +155,9 @@ pub fn generate_axios_code(request: &HttpRequest) -> String {
 
     // Import axios
     code.push_str("const axios = require('axios');\n");
+    if request.body_file.is_some() {
+        code.push_str("const fs = require('fs');\n");
+    }
     code.push_str("// Or for ES modules: import axios from 'axios';\n\n");
 
     // Start the async function
@@ -185,6 +200,12 @@ pub fn generate_axios_code(request: &HttpRequest) -> String {
             code.push_str(&format!("'{}',\n", escaped_body));
         }
     }
+    if let Some(path) = &request.body_file {
+        code.push_str(&format!(
+            "      data: fs.createReadStream('{}'),\n",
+            escape_js_string(&path.display().to_string())
+        ));
+    }
 
     // Add timeout
     code.push_str("      timeout: 30000, // 30 second timeout\n");
@@ -387,6 +408,25 @@ mod tests {
         assert!(code.contains("custom-value"));
     }
 
+    #[test]
+    fn test_body_file_is_streamed_not_inlined() {
+        let mut request = HttpRequest::new(
+            "test".to_string(),
+            HttpMethod::PUT,
+            "https://api.example.com/uploads".to_string(),
+        );
+        request.body_file = Some(std::path::PathBuf::from("/data/dump.bin"));
+
+        let fetch = generate_fetch_code(&request);
+        assert!(fetch.contains("const fs = require('fs');"));
+        assert!(fetch.contains("body: fs.createReadStream('/data/dump.bin'),"));
+        assert!(fetch.contains("duplex: 'half'"));
+
+        let axios = generate_axios_code(&request);
+        assert!(axios.contains("const fs = require('fs');"));
+        assert!(axios.contains("data: fs.createReadStream('/data/dump.bin'),"));
+    }
+
     #[test]
     fn test_is_json_content_type() {
         let mut request = HttpRequest::new(
@@ -5,10 +5,13 @@
 //! multiple languages and libraries, allowing users to convert their .http
 //! files into runnable code snippets.
 
+pub mod go;
 pub mod javascript;
 pub mod python;
+pub mod rust;
 pub mod ui;
 
+use crate::curl::generator::generate_curl_command;
 use crate::models::request::HttpRequest;
 use std::fmt;
 
@@ -19,8 +22,12 @@ pub enum Language {
     JavaScript,
     /// Python
     Python,
-    /// Rust (future support)
+    /// Rust
     Rust,
+    /// Go
+    Go,
+    /// cURL command line
+    Curl,
 }
 
 impl Language {
@@ -30,12 +37,20 @@ impl Language {
             Language::JavaScript => "JavaScript",
             Language::Python => "Python",
             Language::Rust => "Rust",
+            Language::Go => "Go",
+            Language::Curl => "cURL",
         }
     }
 
     /// Returns all available languages.
     pub fn all() -> Vec<Language> {
-        vec![Language::JavaScript, Language::Python]
+        vec![
+            Language::JavaScript,
+            Language::Python,
+            Language::Rust,
+            Language::Go,
+            Language::Curl,
+        ]
     }
 
     /// Returns the default library for this language.
@@ -44,6 +59,8 @@ impl Language {
             Language::JavaScript => Library::Fetch,
             Language::Python => Library::Requests,
             Language::Rust => Library::Reqwest,
+            Language::Go => Library::NetHttp,
+            Language::Curl => Library::Curl,
         }
     }
 
@@ -53,6 +70,8 @@ impl Language {
             Language::JavaScript => vec![Library::Fetch, Library::Axios],
             Language::Python => vec![Library::Requests, Library::Urllib],
             Language::Rust => vec![Library::Reqwest],
+            Language::Go => vec![Library::NetHttp],
+            Language::Curl => vec![Library::Curl],
         }
     }
 }
@@ -74,8 +93,12 @@ pub enum Library {
     Requests,
     /// Python urllib (standard library)
     Urllib,
-    /// Rust reqwest library (future)
+    /// Rust reqwest library
     Reqwest,
+    /// Go net/http (standard library)
+    NetHttp,
+    /// curl command-line tool
+    Curl,
 }
 
 impl Library {
@@ -87,6 +110,8 @@ impl Library {
             Library::Requests => "requests",
             Library::Urllib => "urllib",
             Library::Reqwest => "reqwest",
+            Library::NetHttp => "net/http",
+            Library::Curl => "curl",
         }
     }
 
@@ -96,6 +121,8 @@ impl Library {
             Library::Fetch | Library::Axios => Language::JavaScript,
             Library::Requests | Library::Urllib => Language::Python,
             Library::Reqwest => Language::Rust,
+            Library::NetHttp => Language::Go,
+            Library::Curl => Language::Curl,
         }
     }
 
@@ -107,6 +134,8 @@ impl Library {
             Library::Requests => "Simple and elegant HTTP library",
             Library::Urllib => "Python standard library (no dependencies)",
             Library::Reqwest => "Ergonomic async HTTP client",
+            Library::NetHttp => "Go standard library (no dependencies)",
+            Library::Curl => "Command-line HTTP client",
         }
     }
 }
@@ -168,7 +197,9 @@ impl std::error::Error for CodeGenError {}
 ///
 /// # Returns
 ///
-/// A `Result` containing the generated code string or a `CodeGenError`
+/// A `Result` containing the generated code string or a `CodeGenError`. The
+/// configured `outputNewline` style is applied by the display layer, except for
+/// cURL, whose line continuations are already written in that style
 ///
 /// # Examples
 ///
@@ -212,19 +243,23 @@ pub fn generate_code(
     }
 
     // Dispatch to the appropriate generator
-    match (language, lib) {
-        (Language::JavaScript, Library::Fetch) => Ok(javascript::generate_fetch_code(request)),
-        (Language::JavaScript, Library::Axios) => Ok(javascript::generate_axios_code(request)),
-        (Language::Python, Library::Requests) => Ok(python::generate_requests_code(request)),
-        (Language::Python, Library::Urllib) => Ok(python::generate_urllib_code(request)),
-        (Language::Rust, Library::Reqwest) => Err(CodeGenError::UnsupportedLanguage(
-            "Rust support coming soon".to_string(),
-        )),
-        _ => Err(CodeGenError::IncompatibleLibrary {
-            language: language.as_str().to_string(),
-            library: lib.as_str().to_string(),
-        }),
-    }
+    let code = match (language, lib) {
+        (Language::JavaScript, Library::Fetch) => javascript::generate_fetch_code(request),
+        (Language::JavaScript, Library::Axios) => javascript::generate_axios_code(request),
+        (Language::Python, Library::Requests) => python::generate_requests_code(request),
+        (Language::Python, Library::Urllib) => python::generate_urllib_code(request),
+        (Language::Rust, Library::Reqwest) => rust::generate_reqwest_code(request),
+        (Language::Go, Library::NetHttp) => go::generate_net_http_code(request),
+        (Language::Curl, Library::Curl) => generate_curl_command(request),
+        _ => {
+            return Err(CodeGenError::IncompatibleLibrary {
+                language: language.as_str().to_string(),
+                library: lib.as_str().to_string(),
+            })
+        }
+    };
+
+    Ok(code)
 }
 
 #[cfg(test)]
@@ -237,6 +272,8 @@ mod tests {
         assert_eq!(Language::JavaScript.as_str(), "JavaScript");
         assert_eq!(Language::Python.as_str(), "Python");
         assert_eq!(Language::Rust.as_str(), "Rust");
+        assert_eq!(Language::Go.as_str(), "Go");
+        assert_eq!(Language::Curl.as_str(), "cURL");
     }
 
     #[test]
@@ -244,6 +281,8 @@ mod tests {
         assert_eq!(Language::JavaScript.default_library(), Library::Fetch);
         assert_eq!(Language::Python.default_library(), Library::Requests);
         assert_eq!(Language::Rust.default_library(), Library::Reqwest);
+        assert_eq!(Language::Go.default_library(), Library::NetHttp);
+        assert_eq!(Language::Curl.default_library(), Library::Curl);
     }
 
     #[test]
@@ -361,19 +400,44 @@ mod tests {
     }
 
     #[test]
-    fn test_generate_code_rust_not_supported() {
+    fn test_generate_code_rust() {
+        let request = HttpRequest::new(
+            "test".to_string(),
+            HttpMethod::DELETE,
+            "https://example.com/items/1".to_string(),
+        );
+
+        let code = generate_code(&request, Language::Rust, None).unwrap();
+        assert!(code.contains("reqwest::Client::new()"));
+        assert!(code.contains(".delete(\"https://example.com/items/1\")"));
+    }
+
+    #[test]
+    fn test_generate_code_go() {
         let request = HttpRequest::new(
             "test".to_string(),
             HttpMethod::GET,
-            "https://example.com".to_string(),
+            "https://example.com/items".to_string(),
         );
 
-        let result = generate_code(&request, Language::Rust, None);
-        assert!(result.is_err());
-        assert!(matches!(
-            result.unwrap_err(),
-            CodeGenError::UnsupportedLanguage(_)
-        ));
+        let code = generate_code(&request, Language::Go, None).unwrap();
+        assert!(code.contains("http.NewRequest(\"GET\", \"https://example.com/items\", nil)"));
+        assert!(code.contains("client.Do(req)"));
+    }
+
+    #[test]
+    fn test_generate_code_curl() {
+        let mut request = HttpRequest::new(
+            "test".to_string(),
+            HttpMethod::POST,
+            "https://example.com/items".to_string(),
+        );
+        request.set_body(r#"{"name":"widget"}"#.to_string());
+
+        let code = generate_code(&request, Language::Curl, None).unwrap();
+        assert!(code.starts_with("curl"));
+        assert!(code.contains("-X POST"));
+        assert!(code.contains("https://example.com/items"));
     }
 
     #[test]
@@ -86,6 +86,14 @@ pub fn generate_requests_code(request: &HttpRequest) -> String {
             code.push_str(&format!("        data = '{}'\n", escaped_body));
         }
     }
+    if let Some(path) = &request.body_file {
+        code.push('\n');
+        code.push_str("        # Request body streamed from a file\n");
+        code.push_str(&format!(
+            "        data = open('{}', 'rb')\n",
+            escape_python_string(&path.display().to_string())
+        ));
+    }
 
     code.push_str("\n");
 
@@ -104,6 +112,8 @@ pub fn generate_requests_code(request: &HttpRequest) -> String {
         } else {
             code.push_str("            data=data,\n");
         }
+    } else if request.body_file.is_some() {
+        code.push_str("            data=data,\n");
     }
 
     code.push_str("            timeout=30  # 30 second timeout\n");
@@ -219,6 +229,13 @@ pub fn generate_urllib_code(request: &HttpRequest) -> String {
             code.push_str(&format!("        data = '{}'\n", escaped_body));
             code.push_str("        data = data.encode('utf-8')\n");
         }
+    } else if let Some(path) = &request.body_file {
+        code.push('\n');
+        code.push_str("        # Request body streamed from a file\n");
+        code.push_str(&format!(
+            "        data = open('{}', 'rb')\n",
+            escape_python_string(&path.display().to_string())
+        ));
     } else {
         code.push_str("        data = None\n");
     }
@@ -454,6 +471,26 @@ mod tests {
         assert!(code.contains("application/json"));
     }
 
+    #[test]
+    fn test_body_file_is_streamed_not_inlined() {
+        let mut request = HttpRequest::new(
+            "test".to_string(),
+            HttpMethod::PUT,
+            "https://api.example.com/uploads".to_string(),
+        );
+        request.add_header("Content-Type".to_string(), "application/json".to_string());
+        request.body_file = Some(std::path::PathBuf::from("/data/dump.json"));
+
+        let code = generate_requests_code(&request);
+        assert!(code.contains("data = open('/data/dump.json', 'rb')"));
+        assert!(code.contains("data=data,"));
+        assert!(!code.contains("json=data"));
+
+        let code = generate_urllib_code(&request);
+        assert!(code.contains("data = open('/data/dump.json', 'rb')"));
+        assert!(!code.contains("data = None"));
+    }
+
     #[test]
     fn test_is_json_content_type() {
         let mut request = HttpRequest::new(
@@ -0,0 +1,228 @@
+//! Rust code generation for HTTP requests.
+//!
+//! This module provides a code generator for the reqwest HTTP client, run on
+//! the tokio async runtime.
+
+use crate::models::request::{HttpMethod, HttpRequest};
+
+/// Generates Rust code using the reqwest library.
+///
+/// Creates a runnable async `main` that builds the request with a
+/// `reqwest::Client`, sets its method, headers, and body, and prints the
+/// response. JSON bodies are sent with `.json()` so they are checked at
+/// compile time; other bodies are sent as-is with `.body()`.
+///
+/// # Arguments
+///
+/// * `request` - The HTTP request to generate code for
+///
+/// # Returns
+///
+/// A string containing the generated Rust code with comments
+pub fn generate_reqwest_code(request: &HttpRequest) -> String {
+    let json_body = request
+        .body
+        .as_deref()
+        .filter(|_| is_json_content_type(request))
+        .and_then(format_json_macro);
+
+    let mut code = String::new();
+
+    // Add header comment
+    code.push_str(&format!(
+        "// Generated reqwest code for {} request\n",
+        request.method.as_str()
+    ));
+    code.push_str("// This code uses the reqwest library on the tokio runtime\n");
+    code.push_str("// Add to Cargo.toml:\n");
+    code.push_str("//   reqwest = { version = \"0.12\", features = [\"json\"] }\n");
+    code.push_str("//   serde_json = \"1\"\n");
+    code.push_str("//   tokio = { version = \"1\", features = [\"full\"] }\n\n");
+
+    code.push_str("use std::time::Duration;\n\n");
+
+    // Start the main function
+    code.push_str("#[tokio::main]\n");
+    code.push_str("async fn main() -> Result<(), Box<dyn std::error::Error>> {\n");
+    code.push_str("    let client = reqwest::Client::new();\n\n");
+
+    // Build the request
+    code.push_str(&format!(
+        "    // Send the {} request\n",
+        request.method.as_str()
+    ));
+    code.push_str("    let response = client\n");
+    code.push_str(&format!(
+        "        {}\n",
+        method_call(request.method, &escape_rust_string(&request.url))
+    ));
+
+    // Add headers if present
+    for (key, value) in &request.headers {
+        code.push_str(&format!(
+            "        .header({}, {})\n",
+            escape_rust_string(key),
+            escape_rust_string(value)
+        ));
+    }
+
+    // Add body if present
+    if let Some(json) = &json_body {
+        code.push_str(&format!("        .json(&serde_json::json!({}))\n", json));
+    } else if let Some(body) = &request.body {
+        code.push_str(&format!("        .body({})\n", escape_rust_string(body)));
+    } else if let Some(path) = &request.body_file {
+        code.push_str(&format!(
+            "        .body(tokio::fs::read({}).await?)\n",
+            escape_rust_string(&path.display().to_string())
+        ));
+    }
+
+    code.push_str("        .timeout(Duration::from_secs(30)) // 30 second timeout\n");
+    code.push_str("        .send()\n");
+    code.push_str("        .await?;\n\n");
+
+    // Print the response
+    code.push_str("    println!(\"Status Code: {}\", response.status());\n");
+    code.push_str("    println!(\"Headers: {:#?}\", response.headers());\n\n");
+
+    code.push_str("    // Try to parse as JSON, otherwise print the text\n");
+    code.push_str("    let text = response.text().await?;\n");
+    code.push_str("    match serde_json::from_str::<serde_json::Value>(&text) {\n");
+    code.push_str("        Ok(data) => {\n");
+    code.push_str("            println!(\"Response (JSON):\");\n");
+    code.push_str("            println!(\"{}\", serde_json::to_string_pretty(&data)?);\n");
+    code.push_str("        }\n");
+    code.push_str("        Err(_) => {\n");
+    code.push_str("            println!(\"Response (Text):\");\n");
+    code.push_str("            println!(\"{}\", text);\n");
+    code.push_str("        }\n");
+    code.push_str("    }\n\n");
+
+    code.push_str("    Ok(())\n");
+    code.push_str("}\n");
+
+    code
+}
+
+/// Returns the `Client` call that starts a request with the given method.
+///
+/// Methods without a shorthand on `reqwest::Client` go through `.request()`.
+fn method_call(method: HttpMethod, url: &str) -> String {
+    match method {
+        HttpMethod::GET => format!(".get({})", url),
+        HttpMethod::POST => format!(".post({})", url),
+        HttpMethod::PUT => format!(".put({})", url),
+        HttpMethod::PATCH => format!(".patch({})", url),
+        HttpMethod::DELETE => format!(".delete({})", url),
+        HttpMethod::HEAD => format!(".head({})", url),
+        HttpMethod::OPTIONS | HttpMethod::TRACE | HttpMethod::CONNECT => {
+            format!(".request(reqwest::Method::{}, {})", method.as_str(), url)
+        }
+    }
+}
+
+/// Escapes a string as a Rust string literal, including the quotes.
+fn escape_rust_string(s: &str) -> String {
+    format!("{:?}", s)
+}
+
+/// Formats a JSON body for the `serde_json::json!` macro.
+///
+/// Returns `None` if the body isn't valid JSON, so it can be sent as a plain
+/// string instead. Nested lines are indented to sit inside the request chain.
+fn format_json_macro(json: &str) -> Option<String> {
+    let value = serde_json::from_str::<serde_json::Value>(json).ok()?;
+    let formatted = serde_json::to_string_pretty(&value).ok()?;
+    Some(formatted.replace('\n', "\n        "))
+}
+
+/// Checks if the request has a JSON content type.
+fn is_json_content_type(request: &HttpRequest) -> bool {
+    request
+        .content_type()
+        .map(|ct| ct.to_lowercase().contains("json"))
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape_rust_string() {
+        assert_eq!(escape_rust_string("hello"), "\"hello\"");
+        assert_eq!(escape_rust_string("say \"hi\""), "\"say \\\"hi\\\"\"");
+        assert_eq!(escape_rust_string("a\\b\nc"), "\"a\\\\b\\nc\"");
+    }
+
+    #[test]
+    fn test_generate_reqwest_code_simple_get() {
+        let request = HttpRequest::new(
+            "test".to_string(),
+            HttpMethod::GET,
+            "https://api.example.com/users".to_string(),
+        );
+
+        let code = generate_reqwest_code(&request);
+
+        assert!(code.contains("#[tokio::main]"));
+        assert!(code.contains("async fn main()"));
+        assert!(code.contains("reqwest::Client::new()"));
+        assert!(code.contains(".get(\"https://api.example.com/users\")"));
+        assert!(code.contains(".send()"));
+        assert!(!code.contains(".body("));
+    }
+
+    #[test]
+    fn test_generate_reqwest_code_post_with_json() {
+        let mut request = HttpRequest::new(
+            "test".to_string(),
+            HttpMethod::POST,
+            "https://api.example.com/users".to_string(),
+        );
+        request.add_header("Content-Type".to_string(), "application/json".to_string());
+        request.set_body(r#"{"name": "Alice", "age": 30}"#.to_string());
+
+        let code = generate_reqwest_code(&request);
+
+        assert!(code.contains("reqwest::Client::new()"));
+        assert!(code.contains(".post(\"https://api.example.com/users\")"));
+        assert!(code.contains(".header(\"Content-Type\", \"application/json\")"));
+        assert!(code.contains(".json(&serde_json::json!({"));
+        assert!(code.contains("\"name\": \"Alice\""));
+        assert!(!code.contains(".body("));
+    }
+
+    #[test]
+    fn test_generate_reqwest_code_text_body() {
+        let mut request = HttpRequest::new(
+            "test".to_string(),
+            HttpMethod::PUT,
+            "https://api.example.com/notes/1".to_string(),
+        );
+        request.add_header("Content-Type".to_string(), "text/plain".to_string());
+        request.set_body("line one\nline \"two\"".to_string());
+
+        let code = generate_reqwest_code(&request);
+
+        assert!(code.contains(".put(\"https://api.example.com/notes/1\")"));
+        assert!(code.contains(".body(\"line one\\nline \\\"two\\\"\")"));
+        assert!(!code.contains(".json("));
+    }
+
+    #[test]
+    fn test_generate_reqwest_code_method_without_shorthand() {
+        let request = HttpRequest::new(
+            "test".to_string(),
+            HttpMethod::OPTIONS,
+            "https://api.example.com/users".to_string(),
+        );
+
+        let code = generate_reqwest_code(&request);
+
+        assert!(
+            code.contains(".request(reqwest::Method::OPTIONS, \"https://api.example.com/users\")")
+        );
+    }
+}
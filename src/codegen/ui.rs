@@ -5,6 +5,7 @@
 //! for display in Zed.
 
 use crate::codegen::{generate_code, Language, Library};
+use crate::config::get_config;
 use crate::models::request::HttpRequest;
 
 /// Result of a code generation command.
@@ -65,7 +66,8 @@ impl CodeGenerationResult {
 
     /// Formats the result for display in Zed.
     ///
-    /// Returns a string that includes the generated code with metadata header.
+    /// Returns a string that includes the generated code with metadata header,
+    /// with line endings in the configured `outputNewline` style.
     pub fn to_display_string(&self) -> String {
         if !self.success {
             return format!("❌ Code Generation Failed\n\n{}", self.message);
@@ -124,17 +126,39 @@ impl CodeGenerationResult {
                 }
                 _ => {}
             },
-            _ => {}
+            Language::Rust => {
+                output.push_str("# - Add reqwest, serde_json, and tokio to Cargo.toml\n");
+                output.push_str("# - Copy this code into src/main.rs\n");
+                output.push_str("# - Run with: cargo run\n");
+            }
+            Language::Go => {
+                output.push_str("# - No installation required (standard library)\n");
+                output.push_str("# - Copy this code into a main.go file\n");
+                output.push_str("# - Run with: go run main.go\n");
+            }
+            Language::Curl => {
+                output.push_str("# - Paste this command into a terminal\n");
+            }
         }
 
         output.push_str("#\n");
         output.push_str("# Code copied to clipboard! ✓\n");
         output.push_str("\n");
 
+        let newline = get_config().output_newline;
+
+        // cURL continuations are already styled; newlines inside quoted
+        // bodies must be kept as-is
+        if *language == Language::Curl {
+            let mut output = newline.apply(&output);
+            output.push_str(code);
+            return output;
+        }
+
         // The actual generated code
         output.push_str(code);
 
-        output
+        newline.apply(&output)
     }
 }
 
@@ -204,6 +228,9 @@ pub fn list_available_languages() -> String {
     output.push_str("  /generate-code javascript axios  # Uses axios\n");
     output.push_str("  /generate-code python            # Uses requests (default)\n");
     output.push_str("  /generate-code python urllib     # Uses urllib\n");
+    output.push_str("  /generate-code rust              # Uses reqwest (default)\n");
+    output.push_str("  /generate-code go                # Uses net/http (default)\n");
+    output.push_str("  /generate-code curl              # cURL command\n");
 
     output
 }
@@ -229,9 +256,11 @@ pub fn parse_generation_options(args: &[String]) -> Result<(Language, Option<Lib
         "javascript" | "js" => Language::JavaScript,
         "python" | "py" => Language::Python,
         "rust" | "rs" => Language::Rust,
+        "go" | "golang" => Language::Go,
+        "curl" => Language::Curl,
         _ => {
             return Err(format!(
-                "Unknown language '{}'. Available: javascript, python",
+                "Unknown language '{}'. Available: javascript, python, rust, go, curl",
                 args[0]
             ))
         }
@@ -246,6 +275,8 @@ pub fn parse_generation_options(args: &[String]) -> Result<(Language, Option<Lib
             "requests" => Library::Requests,
             "urllib" => Library::Urllib,
             "reqwest" => Library::Reqwest,
+            "net/http" | "nethttp" => Library::NetHttp,
+            "curl" => Library::Curl,
             _ => {
                 return Err(format!(
                     "Unknown library '{}' for {}. Available: {}",
@@ -292,6 +323,7 @@ mod tests {
     fn create_test_request() -> HttpRequest {
         HttpRequest {
             id: "test-request-1".to_string(),
+            name: None,
             method: HttpMethod::GET,
             url: "https://api.example.com/users".to_string(),
             http_version: None,
@@ -299,6 +331,22 @@ mod tests {
                 .into_iter()
                 .collect(),
             body: None,
+            raw_body: false,
+            body_encoding: None,
+            timeout_override: None,
+            retries_override: None,
+            no_cookies: false,
+            filter: None,
+            pipe: None,
+            body_file: None,
+            conditional_headers: Vec::new(),
+            captures: Vec::new(),
+            digest_auth: None,
+            aws_sigv4: None,
+            multipart: None,
+            form_fields: Vec::new(),
+            query_api_key: None,
+            api_key_header: None,
             file_path: std::path::PathBuf::from("test.http"),
             line_number: 1,
         }
@@ -393,6 +441,30 @@ mod tests {
         assert!(display.contains("GET https://api.example.com/users"));
     }
 
+    #[test]
+    #[serial_test::serial]
+    fn test_display_string_applies_crlf_once() {
+        use crate::config::{reset_config, update_config, NewlineStyle};
+
+        update_config(|config| config.output_newline = NewlineStyle::Crlf);
+
+        let request = create_test_request();
+        let display =
+            generate_code_command(&request, Language::JavaScript, None).to_display_string();
+        assert!(display.contains("# Request: GET https://api.example.com/users\r\n"));
+        assert!(!display.replace("\r\n", "").contains('\n'));
+        assert!(!display.contains("\r\r\n"));
+
+        let mut request = create_test_request();
+        request.method = HttpMethod::POST;
+        request.body = Some("line one\nline two".to_string());
+        let display = generate_code_command(&request, Language::Curl, None).to_display_string();
+        assert!(display.contains("# - Paste this command into a terminal\r\n"));
+        assert!(display.contains("line one\nline two"));
+
+        reset_config();
+    }
+
     #[test]
     fn test_list_available_languages() {
         let list = list_available_languages();
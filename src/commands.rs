@@ -6,24 +6,56 @@
 
 use crate::codegen::ui::{generate_code_command, parse_generation_options, CodeGenerationResult};
 use crate::codegen::Language;
+use crate::config::get_config;
 use crate::curl::ui::{copy_as_curl_command, paste_curl_command, CopyCurlResult, PasteCurlResult};
-use crate::environment::{load_environments, EnvironmentSession};
+use crate::environment::{
+    init_environment_file, load_environments, EnvError, EnvironmentSession, Environments,
+};
+use crate::executor::batch::{format_batch_summary, run_batch, BatchResult};
 use crate::executor::{
-    cancel_most_recent_request, execute_request, get_active_request_count, get_active_request_ids,
-    ExecutionConfig,
+    cancel_most_recent_request, execute_request, execute_request_with_cancellation,
+    get_active_request_count, get_active_request_ids, ExecutionConfig, RequestError,
+};
+use crate::formatter::fixture::{compare_with_fixture, parse_ignore_fields_directives};
+use crate::formatter::json::redact_json;
+use crate::formatter::{format_response, format_response_with_filter, FormattedResponse};
+use crate::graphql::introspection::{
+    cache_schema, cached_schema, introspection_errors, introspection_request,
+    parse_introspection_response, INTROSPECTION_QUERY,
 };
-use crate::formatter::{format_response, FormattedResponse};
+use crate::graphql::parser::parse_graphql_request;
 use crate::history::{
-    clear_history, format_history_entry, get_recent_entries, load_history, search_history,
-    sort_by_timestamp_desc, HistoryEntry,
+    clear_history, format_history_compact, format_history_entry, get_recent_entries, import_har,
+    load_history, search_history, sort_by_timestamp_desc, HistoryEntry,
 };
 use crate::models::request::HttpRequest;
-use crate::parser::parse_request;
+use crate::parser::{
+    parse_file, parse_file_with_positions, parse_request, request_index_at_cursor,
+};
 use crate::ui::response_actions::{
-    copy_response, fold_response, save_response, toggle_raw_view, CopyOption, CopyResponseResult,
+    copy_response, copy_response_with_limit, fold_response, fold_response_keys, save_response,
+    suggest_filename, toggle_raw_view, CopyOption, CopyResponseResult, FoldMode,
     FoldResponseResult, SaveOption, SaveResponseResult,
 };
-use std::path::PathBuf;
+use crate::variables::substitution::{
+    analyze_variables, find_variable_references, substitute_request, VariableContext,
+};
+use crate::variables::{run_imports, ChainError, RequestChain, VarError};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::path::{Path, PathBuf};
+
+/// Regex for the `###` separators, `@name` and `@capture` directives, and
+/// `@variable = value` definitions that decide which variables a file defines.
+///
+/// Matched anywhere rather than line by line, since slash command arguments
+/// arrive with their line breaks turned into spaces.
+static FILE_DEFINITION_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?:^|\s)(?:(###)|@name\s+([A-Za-z_][\w-]*)|@capture\s+([A-Za-z_]\w*)\s*=|@([A-Za-z_][\w.-]*)\s*=)",
+    )
+    .unwrap()
+});
 
 /// Error types for command execution.
 #[derive(Debug)]
@@ -88,6 +120,56 @@ pub struct EnvironmentSwitchResult {
     pub available_environments: Vec<String>,
 }
 
+/// Result of a reload environments command.
+#[derive(Debug)]
+pub struct ReloadEnvironmentsResult {
+    /// Freshly loaded session that replaces the cached one.
+    pub session: EnvironmentSession,
+
+    /// Message describing the result (for notifications).
+    pub message: String,
+
+    /// Name of the environment that is active after the reload.
+    pub active_environment: Option<String>,
+
+    /// Names of all environments found in the reloaded file, sorted.
+    pub available_environments: Vec<String>,
+}
+
+/// Result of a scaffold environment command.
+#[derive(Debug)]
+pub struct ScaffoldEnvironmentResult {
+    /// Environment-dependent variables referenced in the file, sorted.
+    pub variables: Vec<String>,
+
+    /// The `.http-client-env.json` skeleton, or `None` if the file uses no
+    /// environment variables.
+    pub skeleton: Option<String>,
+
+    /// Message with the skeleton and where to save it.
+    pub message: String,
+}
+
+/// Result of a check variables command.
+#[derive(Debug)]
+pub struct VariableCheckResult {
+    /// Environment-dependent variables referenced in the file, sorted.
+    pub variables: Vec<String>,
+
+    /// Missing variables per environment, sorted by environment name.
+    pub missing: Vec<(String, Vec<String>)>,
+
+    /// Markdown report with the environment × variable matrix.
+    pub message: String,
+}
+
+impl VariableCheckResult {
+    /// Returns true if every variable resolves in every environment.
+    pub fn all_resolved(&self) -> bool {
+        self.missing.iter().all(|(_, missing)| missing.is_empty())
+    }
+}
+
 /// Result of a send request command.
 #[derive(Debug)]
 pub struct CommandResult {
@@ -238,7 +320,8 @@ pub fn switch_environment_command(
             let environments = load_environments(workspace_path).map_err(|e| {
                 format!(
                     "No environment configuration found.\n\n\
-                    To use environments, create a `.http-client-env.json` file in your workspace:\n\n\
+                    Run /init-environment to create a starter file, or create a\n\
+                    `.http-client-env.json` file in your workspace:\n\n\
                     {{\n  \"$shared\": {{\n    \"apiVersion\": \"v1\"\n  }},\n  \
                     \"dev\": {{\n    \"baseUrl\": \"http://localhost:3000\"\n  }},\n  \
                     \"production\": {{\n    \"baseUrl\": \"https://api.example.com\"\n  }},\n  \
@@ -303,6 +386,33 @@ pub fn switch_environment_command(
     }
 }
 
+/// Creates a starter `.http-client-env.json` in the workspace root.
+///
+/// # Arguments
+///
+/// * `workspace_path` - Workspace root to write the file to
+///
+/// # Returns
+///
+/// `Ok(String)` confirming where the file was written, or `Err(String)` if
+/// the workspace already has an environment file or it could not be written.
+pub fn init_environment_command(workspace_path: &Path) -> Result<String, String> {
+    match init_environment_file(workspace_path) {
+        Ok(path) => Ok(format!(
+            "✓ Created {}\n\n\
+            It defines `dev` and `production` environments with `dev` active, plus\n\
+            `$shared` variables available in both. Edit the values, then use\n\
+            /switch-environment to change the active environment.",
+            path.display()
+        )),
+        Err(EnvError::AlreadyExists(path)) => Err(format!(
+            "{} already exists and was not overwritten.",
+            path.display()
+        )),
+        Err(e) => Err(format!("Failed to create environment file: {}", e)),
+    }
+}
+
 /// Finds the boundaries of a request block around the cursor position.
 ///
 /// # Arguments
@@ -436,7 +546,7 @@ pub async fn send_request_command(
         .map_err(|e| CommandError::ExecutionError(e.to_string()))?;
 
     // Step 4: Format the response
-    let formatted = format_response(&response);
+    let formatted = format_response_with_filter(&response, request.filter.as_deref());
 
     // Step 5: Create the result
     let success = response.is_success();
@@ -572,15 +682,16 @@ pub fn rerun_from_history_command(
     let config = ExecutionConfig::default();
 
     // Execute the request
-    let response = execute_request(&entry.request, &config)
+    let request = entry.to_request();
+    let response = execute_request(&request, &config)
         .map_err(|e| format!("Failed to re-execute request: {}", e))?;
 
     // Format the response
-    let formatted_response = format_response(&response);
+    let formatted_response = format_response_with_filter(&response, request.filter.as_deref());
 
     let command_result = CommandResult {
         formatted_response: formatted_response.to_display_string(),
-        request: entry.request.clone(),
+        request,
         success: response.is_success(),
         status_message: format!(
             "Re-executed request: {} {} - {}",
@@ -602,6 +713,702 @@ pub fn rerun_from_history_command(
     })
 }
 
+/// Explains the most recent request failure with likely fixes.
+///
+/// # Arguments
+///
+/// * `error` - The error from the last failed request, if any
+///
+/// # Returns
+///
+/// The error, a plain-language explanation, and a numbered list of things to
+/// try, or a note that there is nothing to explain.
+///
+/// # Examples
+///
+/// ```
+/// use rest_client::commands::explain_error_command;
+/// use rest_client::executor::RequestError;
+///
+/// let text = explain_error_command(Some(&RequestError::Timeout));
+/// assert!(text.starts_with("Request timed out"));
+/// ```
+pub fn explain_error_command(error: Option<&RequestError>) -> String {
+    let Some(error) = error else {
+        return "No failed request to explain. /explain-error describes the last error from /send-request."
+            .to_string();
+    };
+
+    let explanation = error.explanation();
+    let mut output = format!(
+        "{}\nCategory: {}\n\nWhat happened:\n  {}\n\nThings to try:\n",
+        error,
+        error.category(),
+        explanation.summary
+    );
+    for (i, fix) in explanation.fixes.iter().enumerate() {
+        output.push_str(&format!("  {}. {}\n", i + 1, fix));
+    }
+
+    output
+}
+
+/// Imports a browser-exported `.har` file into request history.
+///
+/// # Arguments
+///
+/// * `har_path` - Path of the HAR file, relative to the workspace unless absolute
+/// * `workspace_path` - Workspace root used for resolving relative paths
+///
+/// # Returns
+///
+/// `Ok(String)` summarizing the imported and skipped requests, or
+/// `Err(String)` if the file can't be read, isn't valid HAR, or history can't be written.
+pub fn import_history_command(har_path: &str, workspace_path: &Path) -> Result<String, String> {
+    let har_path = har_path.trim();
+    let resolved_path = workspace_path.join(har_path);
+    let json = std::fs::read_to_string(&resolved_path)
+        .map_err(|e| format!("Failed to read {}: {}", resolved_path.display(), e))?;
+
+    let import = import_har(&json).map_err(|e| format!("Failed to import {}: {}", har_path, e))?;
+    let skipped = if import.skipped > 0 {
+        format!(
+            " ({} non-HTTP or unsupported entr{} skipped)",
+            import.skipped,
+            if import.skipped == 1 { "y" } else { "ies" }
+        )
+    } else {
+        String::new()
+    };
+    if import.entries.is_empty() {
+        return Ok(format!("No HTTP requests found in {}{}", har_path, skipped));
+    }
+
+    let mut output = format!(
+        "✓ Imported {} request(s) from {} into history{}\n\n",
+        import.entries.len(),
+        har_path,
+        skipped
+    );
+    for entry in &import.entries {
+        output.push_str(&format!(
+            "{}  {}\n",
+            &entry.id[..8],
+            format_history_compact(entry)
+        ));
+    }
+
+    Ok(output)
+}
+
+/// Re-runs the nth most recent request from history.
+///
+/// # Arguments
+///
+/// * `index` - Position in recent history, where 1 is the most recent request
+///
+/// # Returns
+///
+/// `Ok(String)` with the fresh response, preceded by a warning if sensitive
+/// headers were removed when the entry was saved. `Err(String)` if history
+/// can't be loaded, the index is out of range, or the request fails.
+pub fn history_run_command(index: usize) -> Result<String, String> {
+    if index == 0 {
+        return Err("History indexes start at 1 (the most recent request)".to_string());
+    }
+
+    let entries = load_history().map_err(|e| format!("Failed to load history: {}", e))?;
+    let recent = get_recent_entries(index, &entries);
+    let Some(entry) = recent.get(index - 1).cloned() else {
+        return Err(format!(
+            "No history entry #{}: history has {} entries",
+            index,
+            recent.len()
+        ));
+    };
+
+    let warning = sanitized_headers_warning(&entry);
+    let result = rerun_from_history_command(entry, None)?;
+    let command_result = result
+        .command_result
+        .ok_or("Re-executed request returned no result")?;
+
+    let mut output = format!("#{} {}\n", index, command_result.status_message);
+    if let Some(warning) = warning {
+        output.push_str(&warning);
+        output.push('\n');
+    }
+    output.push('\n');
+    output.push_str(&command_result.formatted_response);
+
+    Ok(output)
+}
+
+/// Warns that headers removed when an entry was saved weren't re-sent.
+fn sanitized_headers_warning(entry: &HistoryEntry) -> Option<String> {
+    if entry.sanitized_headers.is_empty() {
+        return None;
+    }
+
+    let (verb, subject, object) = if entry.sanitized_headers.len() == 1 {
+        ("was", "it", "it")
+    } else {
+        ("were", "they", "them")
+    };
+
+    Some(format!(
+        "⚠ {} {} not stored in history, so {} {} not sent. \
+         Add {} back if the endpoint needs authentication.",
+        entry.sanitized_headers.join(", "),
+        verb,
+        subject,
+        verb,
+        object
+    ))
+}
+
+/// Reloads environments from the workspace, discarding the cached session.
+///
+/// The previously active environment is kept active if it still exists in
+/// the reloaded file; otherwise the file's own `active` setting applies.
+///
+/// # Arguments
+///
+/// * `workspace_path` - Path to the workspace for locating environment files
+/// * `previous_active` - Name of the environment active before the reload
+///
+/// # Returns
+///
+/// `Ok(ReloadEnvironmentsResult)` with the new session, or `Err(String)` if the
+/// environment file could not be read or parsed.
+pub fn reload_environments_command(
+    workspace_path: &Path,
+    previous_active: Option<String>,
+) -> Result<ReloadEnvironmentsResult, String> {
+    let environments = load_environments(workspace_path)
+        .map_err(|e| format!("Failed to reload environments: {}", e))?;
+
+    let session = EnvironmentSession::new(environments);
+    if let Some(name) = &previous_active {
+        // Ignore failure: the environment may have been removed from the file
+        let _ = session.set_active_environment(name);
+    }
+
+    let mut available = session.list_environment_names();
+    available.sort();
+    let active = session.get_active_environment_name();
+
+    let message = if available.is_empty() {
+        "Reloaded environments: none defined in configuration file.".to_string()
+    } else {
+        let mut msg = format!("Reloaded {} environment(s):\n", available.len());
+        for name in &available {
+            let is_active = active.as_deref() == Some(name.as_str());
+            let indicator = if is_active { " (active)" } else { "" };
+            msg.push_str(&format!("  {}{}\n", name, indicator));
+        }
+        if let Some(previous) = &previous_active {
+            if active.as_deref() != Some(previous.as_str()) {
+                msg.push_str(&format!(
+                    "\nPreviously active environment '{}' no longer exists.",
+                    previous
+                ));
+            }
+        }
+        msg
+    };
+
+    Ok(ReloadEnvironmentsResult {
+        session,
+        message,
+        active_environment: active,
+        available_environments: available,
+    })
+}
+
+/// Checks that every `{{variable}}` used in a file resolves in each environment.
+///
+/// Collects all variable references in the file and, for every environment,
+/// reports those that are neither defined in that environment nor in the shared
+/// variables. System variables (`{{$guid}}`), file variables defined with
+/// `@name = value`, `@capture` targets (including `{{login.token}}`), and
+/// response references
+/// (`{{login.response.$.token}}`) are skipped since they don't depend on the
+/// environment.
+///
+/// # Arguments
+///
+/// * `file_content` - Full content of the `.http` file
+/// * `environments` - Loaded environments to check against
+///
+/// # Returns
+///
+/// A `VariableCheckResult` with the missing variables and a formatted matrix.
+pub fn check_variables_command(
+    file_content: &str,
+    environments: &Environments,
+) -> VariableCheckResult {
+    let variables = environment_variable_references(file_content);
+
+    let mut env_names = environments.list_environments();
+    env_names.sort();
+
+    let missing: Vec<(String, Vec<String>)> = env_names
+        .iter()
+        .map(|env_name| {
+            let env = environments.get_environment(env_name);
+            let missing_vars = variables
+                .iter()
+                .filter(|var| {
+                    !env.is_some_and(|e| e.contains(var)) && !environments.shared.contains_key(*var)
+                })
+                .cloned()
+                .collect();
+            (env_name.clone(), missing_vars)
+        })
+        .collect();
+
+    let message = format_variable_check(&variables, &missing);
+
+    VariableCheckResult {
+        variables,
+        missing,
+        message,
+    }
+}
+
+/// Builds a `.http-client-env.json` skeleton from the variables a file uses.
+///
+/// Every variable the file needs from an environment is listed with an
+/// empty value under `$shared` and a `dev` environment. Variables are found
+/// the same way as for `/check-variables`, so system variables, file
+/// variables, `@capture` targets, and response references are skipped.
+///
+/// # Arguments
+///
+/// * `file_content` - Full content of the `.http` file
+///
+/// # Returns
+///
+/// A `ScaffoldEnvironmentResult` with the variables and the skeleton.
+pub fn scaffold_environment_command(file_content: &str) -> ScaffoldEnvironmentResult {
+    let variables = environment_variable_references(file_content);
+    if variables.is_empty() {
+        return ScaffoldEnvironmentResult {
+            variables,
+            skeleton: None,
+            message: "No environment variables referenced in this file.".to_string(),
+        };
+    }
+
+    let placeholders: serde_json::Map<String, serde_json::Value> = variables
+        .iter()
+        .map(|name| (name.clone(), serde_json::Value::String(String::new())))
+        .collect();
+    let skeleton = serde_json::to_string_pretty(&serde_json::json!({
+        "$shared": placeholders,
+        "dev": placeholders,
+    }))
+    .unwrap_or_default();
+
+    let message = format!(
+        "Environment file skeleton for {} variable(s): {}\n\n\
+        Save it as .http-client-env.json in the workspace root (or merge it into\n\
+        the existing file) and fill in the values. Values under `dev` override\n\
+        `$shared`, so remove whichever copy you don't need.\n\n```json\n{}\n```",
+        variables.len(),
+        variables.join(", "),
+        skeleton
+    );
+
+    ScaffoldEnvironmentResult {
+        variables,
+        skeleton: Some(skeleton),
+        message,
+    }
+}
+
+/// Returns the variables a file references that must come from an environment.
+///
+/// Each reference is run through [`analyze_variables`] against the
+/// variables the file defines itself: file variables defined with
+/// `@name = value` and `@capture` targets (including `{{login.token}}`).
+/// Those that don't resolve are returned, sorted. System variables
+/// (`{{$guid}}`) and response references (`{{login.response.$.token}}`) are
+/// skipped.
+fn environment_variable_references(file_content: &str) -> Vec<String> {
+    let mut context = VariableContext::new(PathBuf::new());
+    let mut request_name = None;
+    for captures in FILE_DEFINITION_REGEX.captures_iter(file_content) {
+        if captures.get(1).is_some() {
+            request_name = None;
+        } else if let Some(name) = captures.get(2) {
+            request_name = Some(name.as_str().to_string());
+        } else if let Some(variable) = captures.get(3) {
+            if let Some(name) = &request_name {
+                context
+                    .file_variables
+                    .insert(format!("{}.{}", name, variable.as_str()), String::new());
+            }
+            context
+                .file_variables
+                .insert(variable.as_str().to_string(), String::new());
+        } else if let Some(variable) = captures.get(4) {
+            context
+                .file_variables
+                .insert(variable.as_str().to_string(), String::new());
+        }
+    }
+
+    let mut variables: Vec<String> = find_variable_references(file_content)
+        .into_iter()
+        .filter(|name| !name.starts_with('$'))
+        .filter(|name| !name.contains(".response."))
+        .filter(|name| {
+            let reference = format!("{{{{{}}}}}", name);
+            matches!(
+                analyze_variables(&reference, &context),
+                Err(VarError::UndefinedVariable(_))
+            )
+        })
+        .collect();
+    variables.sort();
+    variables
+}
+
+/// Formats the variable check as a markdown matrix plus a per-environment summary.
+fn format_variable_check(variables: &[String], missing: &[(String, Vec<String>)]) -> String {
+    if missing.is_empty() {
+        return "No environments defined in configuration file.".to_string();
+    }
+
+    if variables.is_empty() {
+        return "No environment variables referenced in this file.".to_string();
+    }
+
+    let mut output = format!(
+        "Variable check: {} variable(s) across {} environment(s)\n\n",
+        variables.len(),
+        missing.len()
+    );
+
+    output.push_str("| Variable |");
+    for (env_name, _) in missing {
+        output.push_str(&format!(" {} |", env_name));
+    }
+    output.push_str("\n|---|");
+    output.push_str(&"---|".repeat(missing.len()));
+    output.push('\n');
+
+    for var in variables {
+        output.push_str(&format!("| {} |", var));
+        for (_, missing_vars) in missing {
+            let mark = if missing_vars.contains(var) {
+                "✗"
+            } else {
+                "✓"
+            };
+            output.push_str(&format!(" {} |", mark));
+        }
+        output.push('\n');
+    }
+
+    output.push_str("\nMissing by environment:\n");
+    for (env_name, missing_vars) in missing {
+        if missing_vars.is_empty() {
+            output.push_str(&format!("  {}: all resolved\n", env_name));
+        } else {
+            output.push_str(&format!("  {}: {}\n", env_name, missing_vars.join(", ")));
+        }
+    }
+
+    output
+}
+
+/// Resolves `{{variable}}` references in a request's URL, headers, and body.
+///
+/// Variables are looked up in the active environment of `session` (falling back
+/// to shared variables) and in the system variables. Because resolution happens
+/// on every call, dynamic values such as `{{$timestamp}}` or `{{$guid}}` are
+/// regenerated each time the same request is resolved.
+///
+/// # Arguments
+///
+/// * `request` - The request as parsed from the `.http` file
+/// * `session` - Optional environment session providing the active environment
+/// * `workspace_path` - Workspace root used for resolving relative paths
+///
+/// # Returns
+///
+/// `Ok(HttpRequest)` with all variables substituted, or `Err(VarError)` if a
+/// variable could not be resolved.
+pub fn resolve_request_variables(
+    request: &HttpRequest,
+    session: Option<&EnvironmentSession>,
+    workspace_path: &Path,
+) -> Result<HttpRequest, VarError> {
+    substitute_request(request, &session_variable_context(session, workspace_path))
+}
+
+/// Builds a variable context from the active environment of `session`, with
+/// shared variables as the fallback.
+fn session_variable_context(
+    session: Option<&EnvironmentSession>,
+    workspace_path: &Path,
+) -> VariableContext {
+    let (environment, shared_variables) = match session {
+        Some(session) => (
+            session.get_active_environment(),
+            session
+                .get_environments()
+                .map(|envs| envs.shared)
+                .unwrap_or_default(),
+        ),
+        None => (None, Default::default()),
+    };
+    VariableContext::with_environment(workspace_path.to_path_buf(), environment, shared_variables)
+}
+
+/// Re-executes the most recently sent request.
+///
+/// The request is re-resolved against the current active environment before
+/// execution, so environment switches and dynamic system variables made since
+/// the original send are picked up.
+///
+/// # Arguments
+///
+/// * `last_request` - The last request sent in this session, as originally parsed
+/// * `current_session` - Optional current environment session for variable resolution
+/// * `workspace_path` - Workspace root used for resolving relative paths
+///
+/// # Returns
+///
+/// `Ok(CommandResult)` with the formatted response, or `Err(String)` if no
+/// request has been sent yet or resolution/execution fails.
+///
+/// # Example
+///
+/// ```no_run
+/// use rest_client::commands::resend_last_request_command;
+/// use rest_client::models::{HttpMethod, HttpRequest};
+/// use std::path::Path;
+///
+/// let last = HttpRequest::new(
+///     "req-1".to_string(),
+///     HttpMethod::GET,
+///     "https://httpbin.org/get".to_string(),
+/// );
+/// let result = resend_last_request_command(Some(last), None, Path::new(".")).unwrap();
+/// println!("{}", result.status_message);
+/// ```
+pub fn resend_last_request_command(
+    last_request: Option<HttpRequest>,
+    current_session: Option<EnvironmentSession>,
+    workspace_path: &Path,
+) -> Result<CommandResult, String> {
+    let last_request = last_request.ok_or_else(|| {
+        "No request has been sent in this session yet. Use /send-request first.".to_string()
+    })?;
+
+    let request =
+        resolve_request_variables(&last_request, current_session.as_ref(), workspace_path)
+            .map_err(|e| format!("Failed to resolve variables: {}", e))?;
+
+    let config = ExecutionConfig::default();
+    let response = execute_request(&request, &config)
+        .map_err(|e| format!("Failed to resend request: {}", e))?;
+
+    let formatted = format_response_with_filter(&response, request.filter.as_deref());
+
+    Ok(CommandResult {
+        formatted_response: formatted.to_display_string(),
+        success: response.is_success(),
+        status_message: format!(
+            "Resent request: {} {} ({})",
+            request.method, request.url, response.status_code
+        ),
+        request,
+    })
+}
+
+/// Sends every request in a file concurrently and summarizes the results.
+///
+/// Each request is resolved against the current environment and sent through
+/// the cancellable executor, at most `runAllConcurrency` at a time where
+/// threads are available and one at a time in the WASM extension. Requests
+/// that fail to resolve or send are listed in the summary with their error
+/// rather than stopping the run, and cancelling a running request cancels
+/// those not yet sent. Files imported with `@use` (relative to the workspace
+/// root) are run first, one request at a time, and their captured values are
+/// available to every request in the file.
+///
+/// # Arguments
+///
+/// * `file_content` - The content of the `.http` file
+/// * `session` - Optional environment session for variable resolution
+/// * `workspace_path` - Workspace root used for resolving relative paths
+///
+/// # Returns
+///
+/// `Ok(String)` with a table of method, URL, status, and duration per
+/// request, or `Err(String)` if the file can't be parsed, has no requests, or
+/// an import fails.
+pub fn run_all_command(
+    file_content: &str,
+    session: Option<&EnvironmentSession>,
+    workspace_path: &Path,
+) -> Result<String, String> {
+    let requests = parse_file(file_content, &PathBuf::from("slash-command"))
+        .map_err(|e| format!("Failed to parse file: {}", e))?;
+    if requests.is_empty() {
+        return Err("No requests found in the file".to_string());
+    }
+
+    // Files imported with `@use` run first; their captures feed every request
+    let context = session_variable_context(session, workspace_path);
+    let config = ExecutionConfig::default();
+    let execute = |request: &HttpRequest| {
+        execute_request_with_cancellation(request, &config).map(|(response, _)| response)
+    };
+    let mut chain = RequestChain::new();
+    run_imports(file_content, workspace_path, &mut chain, &context, &execute)
+        .map_err(|e| e.to_string())?;
+
+    let resolved: Vec<Result<HttpRequest, String>> = requests
+        .iter()
+        .map(|request| {
+            chain
+                .resolve(request, &context)
+                .map_err(|e| format!("Failed to resolve variables: {}", e))
+        })
+        .collect();
+    let sendable: Vec<HttpRequest> = resolved
+        .iter()
+        .filter_map(|request| request.as_ref().ok().cloned())
+        .collect();
+
+    let mut sent = run_batch(&sendable, get_config().run_all_concurrency, execute).into_iter();
+
+    // Put requests that couldn't be resolved back in file order
+    let results: Vec<BatchResult> = requests
+        .iter()
+        .zip(resolved)
+        .filter_map(|(request, resolved)| match resolved {
+            Ok(_) => sent.next(),
+            Err(e) => Some(BatchResult {
+                method: request.method,
+                url: request.url.clone(),
+                result: Err(RequestError::BuildError(e)),
+            }),
+        })
+        .collect();
+
+    Ok(format_batch_summary(&results))
+}
+
+/// Sends the request at the cursor and every request after it, in order.
+///
+/// Requests run one at a time as a chain, so `# @capture` values and
+/// `{{name.response.<path>}}` references from earlier requests in the run
+/// resolve in later ones. Requests above the cursor are not run; a reference
+/// to something only they would capture is reported as not yet captured. A
+/// request that fails is listed with its error and the run continues. Files
+/// imported with `@use` are run first, as for `/run-all`.
+///
+/// # Arguments
+///
+/// * `editor_text` - Complete text content of the editor
+/// * `cursor_position` - Byte offset of the cursor in the text
+/// * `session` - Optional environment session providing the active environment
+/// * `workspace_path` - Workspace root used for resolving relative paths
+///
+/// # Returns
+///
+/// `Ok(String)` with a summary of the requests that ran, or `Err(String)` if
+/// the file can't be parsed, the cursor isn't inside a request, or an import
+/// fails.
+pub fn run_from_here_command(
+    editor_text: &str,
+    cursor_position: usize,
+    session: Option<&EnvironmentSession>,
+    workspace_path: &Path,
+) -> Result<String, String> {
+    let requests = parse_file_with_positions(editor_text, &PathBuf::from("slash-command"))
+        .map_err(|e| format!("Failed to parse file: {}", e))?;
+    let start = request_index_at_cursor(&requests, cursor_position)
+        .ok_or_else(|| CommandError::NoRequestFound.to_string())?;
+    let (skipped, remaining) = requests.split_at(start);
+
+    let context = session_variable_context(session, workspace_path);
+    let config = ExecutionConfig::default();
+    let execute = |request: &HttpRequest| {
+        execute_request_with_cancellation(request, &config).map(|(response, _)| response)
+    };
+
+    // Files imported with `@use` run first, as they would for the whole file
+    let mut chain = RequestChain::new();
+    run_imports(editor_text, workspace_path, &mut chain, &context, &execute)
+        .map_err(|e| e.to_string())?;
+
+    let results: Vec<BatchResult> = remaining
+        .iter()
+        .map(|(request, _)| {
+            let result = chain
+                .execute(request.name.as_deref(), request, &context, execute)
+                .map_err(|e| match e {
+                    ChainError::Variable(err) => RequestError::BuildError(
+                        not_yet_captured(&err, skipped)
+                            .unwrap_or_else(|| format!("Failed to resolve variables: {}", err)),
+                    ),
+                    ChainError::Execution(err) => err,
+                });
+
+            BatchResult {
+                method: request.method,
+                url: request.url.clone(),
+                result,
+            }
+        })
+        .collect();
+
+    Ok(format!(
+        "Running from request {} of {}\n\n{}",
+        start + 1,
+        requests.len(),
+        format_batch_summary(&results)
+    ))
+}
+
+/// Explains an undefined variable that a request above the cursor would
+/// have captured, had it been run.
+///
+/// Covers bare `{{variable}}` captures as well as `{{name.variable}}` and
+/// `{{name.response.<path>}}` references to a skipped named request.
+fn not_yet_captured(
+    error: &VarError,
+    skipped: &[(HttpRequest, std::ops::Range<usize>)],
+) -> Option<String> {
+    let VarError::UndefinedVariable(reference) = error else {
+        return None;
+    };
+    // Response references carry an explanation after the reference itself
+    let reference = reference.split_whitespace().next().unwrap_or(reference);
+    let request_name = reference.split('.').next().unwrap_or(reference);
+
+    let (source, _) = skipped.iter().rev().find(|(request, _)| {
+        request.name.as_deref() == Some(request_name)
+            || request
+                .captures
+                .iter()
+                .any(|capture| capture.variable_name == reference)
+    })?;
+
+    Some(format!(
+        "'{{{{{}}}}}' is not yet captured: it comes from the request at line {}, above the cursor, which was not run",
+        reference, source.line_number
+    ))
+}
+
 /// Clears all history entries after confirmation.
 ///
 /// Deletes the entire history file, removing all stored request/response pairs.
@@ -838,6 +1645,23 @@ pub fn generate_code_from_cursor(
 ///     url: "https://api.example.com/users".to_string(),
 ///     headers: Default::default(),
 ///     body: None,
+///     raw_body: false,
+///     body_encoding: None,
+///     timeout_override: None,
+///     retries_override: None,
+///     no_cookies: false,
+///     filter: None,
+///     pipe: None,
+///     body_file: None,
+///     conditional_headers: Vec::new(),
+///     captures: Vec::new(),
+///     digest_auth: None,
+///     aws_sigv4: None,
+///     multipart: None,
+///     form_fields: Vec::new(),
+///     query_api_key: None,
+///     api_key_header: None,
+///     name: None,
 ///     file_path: PathBuf::from("test.http"),
 ///     line_number: 1,
 /// };
@@ -939,7 +1763,219 @@ pub fn copy_as_curl_from_cursor(
     // Generate cURL command
     let result = copy_as_curl_command(&request);
 
-    Ok(result)
+    Ok(result)
+}
+
+/// Builds a GraphQL `.http` request block from an endpoint and a query.
+///
+/// The query is validated with the GraphQL parser first, so syntax errors are
+/// reported before anything is scaffolded. The block POSTs the query (and any
+/// variables JSON after it) as the body with `Content-Type: application/json`;
+/// the executor wraps it in a `{"query": ..., "variables": ...}` payload when
+/// sent. A shorthand query such as `{ users { id } }` is prefixed with
+/// `query` so it is recognized as GraphQL. If the endpoint's schema was
+/// introspected with `/graphql-introspect --run` in this session, top-level
+/// fields the schema doesn't define are reported too.
+///
+/// # Arguments
+///
+/// * `endpoint` - The GraphQL endpoint URL
+/// * `query` - The selected GraphQL query, optionally followed by variables
+///
+/// # Returns
+///
+/// `Ok(String)` with the request block, or `Err(String)` if the endpoint or
+/// query is invalid.
+///
+/// # Examples
+///
+/// ```
+/// use rest_client::commands::graphql_scaffold_command;
+///
+/// let block = graphql_scaffold_command("https://api.example.com/graphql", "{ me { id } }").unwrap();
+/// assert_eq!(
+///     block,
+///     "POST https://api.example.com/graphql\nContent-Type: application/json\n\nquery { me { id } }\n"
+/// );
+/// ```
+pub fn graphql_scaffold_command(endpoint: &str, query: &str) -> Result<String, String> {
+    let endpoint = endpoint.trim();
+    if !endpoint.starts_with("http://") && !endpoint.starts_with("https://") {
+        return Err(format!(
+            "Invalid GraphQL endpoint '{}'. URL must start with http:// or https://",
+            endpoint
+        ));
+    }
+
+    let query = query.trim();
+    parse_graphql_request(query).map_err(|e| format!("Invalid GraphQL query: {}", e))?;
+
+    if let Some(schema) = cached_schema(endpoint) {
+        let unknown = schema.unknown_root_fields(query);
+        if !unknown.is_empty() {
+            return Err(format!(
+                "Unknown field(s) in the introspected schema of {}: {}. Run /graphql-introspect {} --run again if the schema changed",
+                endpoint,
+                unknown.join(", "),
+                endpoint
+            ));
+        }
+    }
+
+    let body = if query.starts_with('{') {
+        format!("query {}", query)
+    } else {
+        query.to_string()
+    };
+
+    Ok(format!(
+        "POST {}\nContent-Type: application/json\n\n{}\n",
+        endpoint, body
+    ))
+}
+
+/// Generates, and optionally runs, the GraphQL introspection query for an endpoint.
+///
+/// Without `run`, returns a request block containing the full introspection
+/// query, ready to be pasted into a `.http` file. With `run`, the query is
+/// sent to the endpoint and the schema is summarized as a list of types with
+/// their fields and kinds. The schema is also cached for the endpoint so it
+/// can be reused without querying the server again.
+///
+/// # Arguments
+///
+/// * `endpoint` - The GraphQL endpoint URL
+/// * `run` - Whether to execute the query and summarize the schema
+///
+/// # Returns
+///
+/// `Ok(String)` with the request block or schema summary, or `Err(String)` if
+/// the endpoint is invalid, the request fails, or the server returns errors.
+pub fn graphql_introspect_command(endpoint: &str, run: bool) -> Result<String, String> {
+    let endpoint = endpoint.trim();
+    let block = graphql_scaffold_command(endpoint, INTROSPECTION_QUERY)?;
+    if !run {
+        return Ok(block);
+    }
+
+    let request = introspection_request(endpoint);
+    let response = execute_request(&request, &ExecutionConfig::default())
+        .map_err(|e| format!("Failed to run introspection query: {}", e))?;
+
+    let body = response
+        .body_as_string()
+        .map_err(|e| format!("Introspection response is not valid UTF-8: {}", e))?;
+
+    if !response.is_success() {
+        return Err(introspection_errors(&body).unwrap_or_else(|| {
+            format!(
+                "Introspection query failed with status {} {}",
+                response.status_code, response.status_text
+            )
+        }));
+    }
+
+    let schema = parse_introspection_response(&body)?;
+    let summary = schema.to_display_string();
+    cache_schema(endpoint, schema);
+
+    Ok(summary)
+}
+
+/// Sends a request and compares its response body against a saved fixture.
+///
+/// The fixture is read before the request is sent, so a missing fixture
+/// doesn't cost a round trip. Fields named in the request's
+/// `# @ignore-fields` directives are blanked on both sides before comparing
+/// JSON bodies.
+///
+/// # Arguments
+///
+/// * `request_text` - The request block to send
+/// * `fixture_path` - Path of the fixture, relative to the workspace unless absolute
+/// * `session` - Optional environment session for variable resolution
+/// * `workspace_path` - Workspace root used for resolving relative paths
+///
+/// # Returns
+///
+/// `Ok(String)` with a pass message, or a fail message followed by a diff of
+/// the fixture (`-`) against the response (`+`). `Err(String)` if the fixture
+/// can't be read, or the request can't be parsed, resolved, or sent.
+pub fn assert_matches_command(
+    request_text: &str,
+    fixture_path: &str,
+    session: Option<&EnvironmentSession>,
+    workspace_path: &Path,
+) -> Result<String, String> {
+    let fixture_path = fixture_path.trim();
+    let resolved_path = workspace_path.join(fixture_path);
+    let fixture = std::fs::read_to_string(&resolved_path)
+        .map_err(|e| format!("Failed to read fixture {}: {}", resolved_path.display(), e))?;
+
+    let indexed_lines: Vec<(usize, &str)> = request_text.lines().enumerate().collect();
+    let parsed = parse_request(&indexed_lines, 0, &PathBuf::from("slash-command"))
+        .map_err(|e| format!("Failed to parse request: {}", e))?;
+    let ignore_fields = parse_ignore_fields_directives(&indexed_lines);
+
+    let request = resolve_request_variables(&parsed, session, workspace_path)
+        .map_err(|e| format!("Failed to resolve variables: {}", e))?;
+    let response = execute_request(&request, &ExecutionConfig::default())
+        .map_err(|e| format!("Failed to execute request: {}", e))?;
+    let body = response
+        .body_as_string()
+        .map_err(|e| format!("Response body is not valid UTF-8: {}", e))?;
+
+    let summary = format!(
+        "{} {} ({} {})",
+        request.method, request.url, response.status_code, response.status_text
+    );
+    match compare_with_fixture(&body, &fixture, &ignore_fields)
+        .map_err(|e| format!("Invalid @ignore-fields entry: {}", e))?
+    {
+        None => Ok(format!(
+            "✓ PASS: response matches {}\n{}\n",
+            fixture_path, summary
+        )),
+        Some(diff) => Ok(format!(
+            "✗ FAIL: response differs from {}\n{}\n\n--- {}\n+++ response\n{}",
+            fixture_path, summary, fixture_path, diff
+        )),
+    }
+}
+
+/// Redacts fields in a JSON response body so it can be shared.
+///
+/// # Arguments
+///
+/// * `fields` - Comma-separated dotted paths of the fields to redact
+/// * `json` - The JSON body
+///
+/// # Returns
+///
+/// `Ok(String)` with the pretty-printed JSON, each field replaced with
+/// `"[REDACTED]"`, or `Err(String)` if no fields are given or the body isn't
+/// valid JSON.
+///
+/// # Examples
+///
+/// ```
+/// use rest_client::commands::redact_command;
+///
+/// let redacted = redact_command("token", r#"{"token":"abc"}"#).unwrap();
+/// assert_eq!(redacted, "{\n  \"token\": \"[REDACTED]\"\n}");
+/// ```
+pub fn redact_command(fields: &str, json: &str) -> Result<String, String> {
+    let fields: Vec<String> = fields
+        .split(',')
+        .map(str::trim)
+        .filter(|field| !field.is_empty())
+        .map(str::to_string)
+        .collect();
+    if fields.is_empty() {
+        return Err("No fields provided. Usage: /redact <field1,field2,...>".to_string());
+    }
+
+    redact_json(json, &fields).map_err(|e| format!("Failed to redact response: {}", e))
 }
 
 #[cfg(test)]
@@ -1203,6 +2239,129 @@ Content-Type: application/json
         assert!(result.message.contains("active"));
     }
 
+    #[test]
+    fn test_reload_environments_preserves_active() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            temp_dir.path().join(".http-client-env.json"),
+            r#"{"dev": {"baseUrl": "http://localhost"}, "staging": {"baseUrl": "http://staging"}, "active": "dev"}"#,
+        )
+        .unwrap();
+
+        let result =
+            reload_environments_command(temp_dir.path(), Some("staging".to_string())).unwrap();
+
+        assert_eq!(result.available_environments, vec!["dev", "staging"]);
+        assert_eq!(result.active_environment, Some("staging".to_string()));
+        assert_eq!(
+            result.session.get_variable("baseUrl"),
+            Some("http://staging".to_string())
+        );
+        assert!(result.message.contains("staging (active)"));
+    }
+
+    #[test]
+    fn test_reload_environments_previous_active_removed() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            temp_dir.path().join(".http-client-env.json"),
+            r#"{"dev": {"baseUrl": "http://localhost"}, "active": "dev"}"#,
+        )
+        .unwrap();
+
+        let result =
+            reload_environments_command(temp_dir.path(), Some("production".to_string())).unwrap();
+
+        assert_eq!(result.active_environment, Some("dev".to_string()));
+        assert!(result.message.contains("'production' no longer exists"));
+    }
+
+    #[test]
+    fn test_check_variables_reports_missing_per_environment() {
+        use crate::environment::Environment;
+
+        let mut envs = Environments::new();
+        let mut dev = Environment::new("dev");
+        dev.set("baseUrl", "http://localhost");
+        dev.set("apiKey", "dev-key");
+        envs.add_environment(dev);
+        let mut prod = Environment::new("prod");
+        prod.set("baseUrl", "https://api.example.com");
+        envs.add_environment(prod);
+        envs.set_shared("version", "v1");
+
+        let content = "@page = 1\n\
+            # @name login\n\
+            # @capture token = $.token\n\
+            GET {{baseUrl}}/{{version}}/users?page={{page}}&id={{$guid}}\n\
+            Authorization: Bearer {{apiKey}}\n\
+            X-Token: {{token}}\n\
+            X-Login-Token: {{login.token}}\n\
+            X-Ref: {{login.response.$.id}}\n";
+
+        let result = check_variables_command(content, &envs);
+
+        assert_eq!(result.variables, vec!["apiKey", "baseUrl", "version"]);
+        assert_eq!(
+            result.missing,
+            vec![
+                ("dev".to_string(), vec![]),
+                ("prod".to_string(), vec!["apiKey".to_string()]),
+            ]
+        );
+        assert!(!result.all_resolved());
+        assert!(result.message.contains("| Variable | dev | prod |"));
+        assert!(result.message.contains("| apiKey | ✓ | ✗ |"));
+        assert!(result.message.contains("prod: apiKey"));
+    }
+
+    #[test]
+    fn test_scaffold_environment_lists_environment_variables() {
+        let content = "@apiUrl = {{baseUrl}}/v1\n\
+            # @name login\n\
+            # @capture token = $.token\n\
+            POST {{apiUrl}}/login?ts={{$timestamp}}&id={{$guid}}\n\
+            Authorization: Basic {{base64({{user}}:{{password}})}}\n\
+            X-Token: {{login.token}}\n\
+            X-Ref: {{login.response.$.id}}\n";
+
+        let result = scaffold_environment_command(content);
+
+        assert_eq!(result.variables, vec!["baseUrl", "password", "user"]);
+
+        // Slash command arguments arrive split on whitespace, losing the line breaks
+        let joined = content.split_whitespace().collect::<Vec<_>>().join(" ");
+        assert_eq!(
+            scaffold_environment_command(&joined).variables,
+            vec!["baseUrl", "password", "user"]
+        );
+        let skeleton: serde_json::Value =
+            serde_json::from_str(result.skeleton.as_deref().unwrap()).unwrap();
+        let expected = serde_json::json!({"baseUrl": "", "password": "", "user": ""});
+        assert_eq!(skeleton["$shared"], expected);
+        assert_eq!(skeleton["dev"], expected);
+        assert!(result.message.contains("```json"));
+    }
+
+    #[test]
+    fn test_scaffold_environment_without_environment_variables() {
+        let result = scaffold_environment_command(
+            "@id = 1\nGET https://example.com/{{id}}?t={{$timestamp}}",
+        );
+
+        assert!(result.variables.is_empty());
+        assert!(result.skeleton.is_none());
+        assert!(result.message.contains("No environment variables"));
+    }
+
+    #[test]
+    fn test_check_variables_without_environments() {
+        let result = check_variables_command("GET {{baseUrl}}/users", &Environments::new());
+
+        assert!(result.missing.is_empty());
+        assert!(result.message.contains("No environments defined"));
+    }
+
     #[test]
     fn test_switch_environment_successful_switch() {
         use crate::environment::{Environment, Environments};
@@ -1490,6 +2649,90 @@ Content-Type: application/json
         assert_eq!(rerun_result.entry.id, entry.id);
     }
 
+    #[test]
+    fn test_resolve_request_variables_uses_active_environment() {
+        use crate::environment::{Environment, Environments};
+
+        let mut envs = Environments::new();
+        envs.set_shared("token", "shared-token");
+        let mut dev = Environment::new("dev");
+        dev.set("host", "dev.example.com");
+        envs.add_environment(dev);
+        envs.set_active("dev");
+        let session = EnvironmentSession::new(envs);
+
+        let mut request = HttpRequest::new(
+            "test".to_string(),
+            HttpMethod::POST,
+            "https://{{host}}/users".to_string(),
+        );
+        request.add_header("Authorization".to_string(), "Bearer {{token}}".to_string());
+        request.set_body(r#"{"sent": "{{$timestamp}}"}"#.to_string());
+
+        let resolved =
+            resolve_request_variables(&request, Some(&session), Path::new("/test")).unwrap();
+
+        assert_eq!(resolved.url, "https://dev.example.com/users");
+        assert_eq!(
+            resolved.headers.get("Authorization"),
+            Some(&"Bearer shared-token".to_string())
+        );
+        assert!(!resolved.body.unwrap().contains("{{"));
+        // The original request keeps its templates for later re-resolution
+        assert_eq!(request.url, "https://{{host}}/users");
+    }
+
+    #[test]
+    fn test_resend_last_request_without_previous_request() {
+        let result = resend_last_request_command(None, None, Path::new("/test"));
+        assert!(result.is_err());
+        assert!(result.unwrap_err().contains("No request has been sent"));
+    }
+
+    #[test]
+    fn test_resend_last_request_undefined_variable() {
+        let request = HttpRequest::new(
+            "test".to_string(),
+            HttpMethod::GET,
+            "https://{{missingHost}}/users".to_string(),
+        );
+
+        let result = resend_last_request_command(Some(request), None, Path::new("/test"));
+        assert!(result.is_err());
+        assert!(result.unwrap_err().contains("missingHost"));
+    }
+
+    #[test]
+    fn test_init_environment_command() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+
+        let message = init_environment_command(temp_dir.path()).unwrap();
+        assert!(message.contains(".http-client-env.json"));
+
+        let error = init_environment_command(temp_dir.path()).unwrap_err();
+        assert!(error.contains("already exists and was not overwritten"));
+    }
+
+    #[test]
+    fn test_run_all_without_requests() {
+        let result = run_all_command("", None, Path::new("/test"));
+        assert_eq!(result.unwrap_err(), "No requests found in the file");
+    }
+
+    #[test]
+    fn test_run_all_reports_unresolved_requests() {
+        let summary = run_all_command(
+            "GET https://{{missingHost}}/users\n",
+            None,
+            Path::new("/test"),
+        )
+        .unwrap();
+
+        assert!(summary.starts_with("Ran 1 request: 0 succeeded, 1 failed"));
+        assert!(summary.contains("GET     https://{{missingHost}}/users"));
+        assert!(summary.contains("missingHost"));
+    }
+
     #[test]
     #[serial_test::serial]
     fn test_clear_history_command_without_confirmation() {
@@ -1883,16 +3126,33 @@ Content-Type: application/json
     #[test]
     fn test_save_response_command() {
         use crate::formatter::{ContentType, ResponseMetadata};
-        use std::collections::HashMap;
+        use crate::models::Headers;
         use std::time::Duration;
 
         let request = HttpRequest {
             id: "test-123".to_string(),
+            name: None,
             method: crate::models::request::HttpMethod::GET,
             url: "https://api.example.com/users".to_string(),
             http_version: Some("HTTP/1.1".to_string()),
-            headers: HashMap::new(),
+            headers: Headers::new(),
             body: None,
+            raw_body: false,
+            body_encoding: None,
+            timeout_override: None,
+            retries_override: None,
+            no_cookies: false,
+            filter: None,
+            pipe: None,
+            body_file: None,
+            conditional_headers: Vec::new(),
+            captures: Vec::new(),
+            digest_auth: None,
+            aws_sigv4: None,
+            multipart: None,
+            form_fields: Vec::new(),
+            query_api_key: None,
+            api_key_header: None,
             line_number: 0,
             file_path: PathBuf::from("test.http"),
         };
@@ -1912,9 +3172,19 @@ Content-Type: application/json
                 is_success: true,
                 is_truncated: false,
                 timing_breakdown: "Total: 100ms".to_string(),
+                rate_limit: None,
+                warnings: Vec::new(),
+                final_url: None,
+                throughput_bps: None,
+                chunked_lines: None,
+                original_encoding: None,
+                compressed_size: None,
+                section_sizes: None,
             },
             highlight_info: None,
             is_formatted: true,
+            filter: None,
+            filter_warning: None,
         };
 
         let result = save_response_command(&response, &request, SaveOption::BodyOnly);
@@ -1942,9 +3212,19 @@ Content-Type: application/json
                 is_success: true,
                 is_truncated: false,
                 timing_breakdown: "Total: 100ms".to_string(),
+                rate_limit: None,
+                warnings: Vec::new(),
+                final_url: None,
+                throughput_bps: None,
+                chunked_lines: None,
+                original_encoding: None,
+                compressed_size: None,
+                section_sizes: None,
             },
             highlight_info: None,
             is_formatted: true,
+            filter: None,
+            filter_warning: None,
         };
 
         let result = copy_response_command(&response, CopyOption::Body);
@@ -1952,6 +3232,87 @@ Content-Type: application/json
         assert_eq!(result.content, r#"{"test": "data"}"#);
     }
 
+    #[test]
+    fn test_find_history_entry_by_prefix() {
+        use crate::models::{HttpMethod, HttpResponse};
+
+        let request = HttpRequest::new(
+            "test".to_string(),
+            HttpMethod::GET,
+            "https://api.example.com/users".to_string(),
+        );
+        let mut first = HistoryEntry::new(request.clone(), HttpResponse::new(200, "OK".into()));
+        first.id = "abc-123".to_string();
+        let mut second = HistoryEntry::new(request, HttpResponse::new(200, "OK".into()));
+        second.id = "abd-456".to_string();
+        let entries = vec![first, second];
+
+        assert_eq!(
+            find_history_entry("abc-123", &entries).unwrap().id,
+            "abc-123"
+        );
+        assert_eq!(find_history_entry("abd", &entries).unwrap().id, "abd-456");
+        assert!(find_history_entry("ab", &entries)
+            .unwrap_err()
+            .contains("ambiguous"));
+        assert!(find_history_entry("xyz", &entries)
+            .unwrap_err()
+            .contains("No history entry"));
+    }
+
+    #[test]
+    fn test_copy_history_response_body() {
+        use crate::models::{HttpMethod, HttpResponse};
+
+        let request = HttpRequest::new(
+            "test".to_string(),
+            HttpMethod::GET,
+            "https://api.example.com/users".to_string(),
+        );
+        let mut response = HttpResponse::new(200, "OK".to_string());
+        response.add_header("Content-Type".to_string(), "application/json".to_string());
+        response.set_body(br#"{"id":1}"#.to_vec());
+        let entry = HistoryEntry::new(request, response);
+
+        let raw = copy_history_response_body(&entry, true, 1024);
+        assert!(raw.success);
+        assert_eq!(raw.content, r#"{"id":1}"#);
+
+        let formatted = copy_history_response_body(&entry, false, 1024);
+        assert!(formatted.content.contains("\"id\": 1"));
+
+        let too_large = copy_history_response_body(&entry, true, 4);
+        assert!(!too_large.success);
+        assert!(too_large.content.is_empty());
+        assert!(too_large
+            .message
+            .contains("Suggested file: get-users-response.json"));
+    }
+
+    #[test]
+    fn test_fold_history_response() {
+        use crate::models::{HttpMethod, HttpResponse};
+
+        let request = HttpRequest::new(
+            "test".to_string(),
+            HttpMethod::GET,
+            "https://api.example.com/users".to_string(),
+        );
+        let mut response = HttpResponse::new(200, "OK".to_string());
+        response.add_header("Content-Type".to_string(), "application/json".to_string());
+        response.set_body(br#"{"data":{"id":1},"items":[1,2]}"#.to_vec());
+        let entry = HistoryEntry::new(request, response);
+
+        let keys = fold_history_response(&entry, FoldMode::TopLevelKeys);
+        assert!(keys
+            .folded_response
+            .ends_with("{\n  data: {…}\n  items: [2]\n}"));
+
+        let sections = fold_history_response(&entry, FoldMode::Sections);
+        assert_eq!(sections.mode, FoldMode::Sections);
+        assert!(!sections.is_folded);
+    }
+
     #[test]
     fn test_toggle_raw_view_command() {
         use crate::formatter::{ContentType, ResponseMetadata};
@@ -1972,15 +3333,243 @@ Content-Type: application/json
                 is_success: true,
                 is_truncated: false,
                 timing_breakdown: "Total: 100ms".to_string(),
+                rate_limit: None,
+                warnings: Vec::new(),
+                final_url: None,
+                throughput_bps: None,
+                chunked_lines: None,
+                original_encoding: None,
+                compressed_size: None,
+                section_sizes: None,
             },
             highlight_info: None,
             is_formatted: true,
+            filter: None,
+            filter_warning: None,
         };
 
         let toggled = toggle_raw_view_command(&response);
         assert!(!toggled.is_formatted);
         assert_eq!(toggled.raw_body, r#"{"test":"data"}"#);
     }
+
+    #[test]
+    fn test_graphql_scaffold_command() {
+        let query = "query GetUser($id: ID!) {\n  user(id: $id) { name }\n}\n\n{\"id\": \"1\"}";
+
+        let block = graphql_scaffold_command("https://api.example.com/graphql", query).unwrap();
+
+        assert_eq!(
+            block,
+            format!(
+                "POST https://api.example.com/graphql\nContent-Type: application/json\n\n{}\n",
+                query
+            )
+        );
+
+        // The scaffolded block parses back into a request
+        let lines: Vec<(usize, &str)> = block.lines().enumerate().collect();
+        let request = parse_request(&lines, 0, &PathBuf::from("test.http")).unwrap();
+        assert_eq!(request.method, HttpMethod::POST);
+        assert_eq!(request.body.as_deref(), Some(query));
+    }
+
+    #[test]
+    fn test_graphql_scaffold_command_rejects_invalid_input() {
+        let err = graphql_scaffold_command("https://api.example.com/graphql", "query { user {")
+            .unwrap_err();
+        assert!(err.starts_with("Invalid GraphQL query"), "{}", err);
+
+        let err = graphql_scaffold_command("api.example.com/graphql", "{ me { id } }").unwrap_err();
+        assert!(err.contains("http://"), "{}", err);
+    }
+
+    #[test]
+    fn test_graphql_scaffold_command_checks_cached_schema() {
+        let endpoint = "https://scaffold-schema.example.com/graphql";
+        let schema = parse_introspection_response(
+            r#"{"data": {"__schema": {"queryType": {"name": "Query"}, "types": [
+                {"kind": "OBJECT", "name": "Query", "fields": [
+                    {"name": "me", "type": {"kind": "OBJECT", "name": "User", "ofType": null}}
+                ]}
+            ]}}}"#,
+        )
+        .unwrap();
+        cache_schema(endpoint, schema);
+
+        assert!(graphql_scaffold_command(endpoint, "{ me { id } }").is_ok());
+
+        let err = graphql_scaffold_command(endpoint, "{ me { id } posts { id } }").unwrap_err();
+        assert!(
+            err.contains(&format!("schema of {}: posts", endpoint)),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_sanitized_headers_warning() {
+        use crate::models::HttpResponse;
+
+        let request = HttpRequest::new(
+            "id".to_string(),
+            HttpMethod::GET,
+            "https://api.example.com".to_string(),
+        );
+        let mut entry = HistoryEntry::new(request, HttpResponse::new(200, "OK".to_string()));
+        assert_eq!(sanitized_headers_warning(&entry), None);
+
+        entry.sanitized_headers = vec!["Authorization".to_string()];
+        assert_eq!(
+            sanitized_headers_warning(&entry).unwrap(),
+            "⚠ Authorization was not stored in history, so it was not sent. \
+             Add it back if the endpoint needs authentication."
+        );
+
+        entry.sanitized_headers.push("Cookie".to_string());
+        assert!(sanitized_headers_warning(&entry).unwrap().starts_with(
+            "⚠ Authorization, Cookie were not stored in history, so they were not sent."
+        ));
+    }
+
+    #[test]
+    fn test_history_run_command_rejects_zero() {
+        assert!(history_run_command(0).unwrap_err().contains("start at 1"));
+    }
+
+    #[test]
+    fn test_explain_error_command() {
+        let text = explain_error_command(Some(&RequestError::NetworkError(
+            "dns error: failed to lookup address information".to_string(),
+        )));
+        assert!(text.starts_with("Network error: dns error"));
+        assert!(text.contains("\nCategory: DNS\n"));
+        assert!(text.contains("What happened:\n  The host name couldn't be resolved"));
+        assert!(text.contains("Things to try:\n  1. Check the host name in the URL for typos\n"));
+
+        assert!(explain_error_command(None).starts_with("No failed request to explain"));
+    }
+
+    #[test]
+    fn test_import_history_command_without_entries() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            temp_dir.path().join("empty.har"),
+            r#"{"log": {"entries": []}}"#,
+        )
+        .unwrap();
+        std::fs::write(temp_dir.path().join("bad.har"), "<html>").unwrap();
+        std::fs::write(
+            temp_dir.path().join("data.har"),
+            r#"{"log": {"entries": [{
+                "request": { "method": "GET", "url": "data:text/plain,hi", "headers": [] },
+                "response": { "status": 200, "statusText": "OK", "headers": [] }
+            }]}}"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            import_history_command("empty.har", temp_dir.path()).unwrap(),
+            "No HTTP requests found in empty.har"
+        );
+        assert_eq!(
+            import_history_command("data.har", temp_dir.path()).unwrap(),
+            "No HTTP requests found in data.har (1 non-HTTP or unsupported entry skipped)"
+        );
+        assert!(import_history_command("bad.har", temp_dir.path())
+            .unwrap_err()
+            .starts_with("Failed to import bad.har"));
+        assert!(import_history_command("missing.har", temp_dir.path())
+            .unwrap_err()
+            .starts_with("Failed to read"));
+    }
+
+    #[test]
+    fn test_graphql_introspect_command_without_run() {
+        let block = graphql_introspect_command("https://api.example.com/graphql", false).unwrap();
+
+        assert!(block.starts_with("POST https://api.example.com/graphql\n"));
+        assert!(block.contains(INTROSPECTION_QUERY));
+
+        assert!(graphql_introspect_command("api.example.com/graphql", false).is_err());
+    }
+
+    #[test]
+    fn test_run_from_here_command_not_yet_captured() {
+        let editor_text = "# @name login\nPOST http://127.0.0.1:9/login\n# @capture token = $.token\n\n###\n\nGET http://127.0.0.1:9/me\nAuthorization: Bearer {{token}}\n\n###\n\nGET http://127.0.0.1:9/me?session={{login.response.$.id}}\n";
+        let cursor = editor_text.find("GET").unwrap();
+
+        let summary =
+            run_from_here_command(editor_text, cursor, None, Path::new("/workspace")).unwrap();
+
+        assert!(summary
+            .starts_with("Running from request 2 of 3\n\nRan 2 requests: 0 succeeded, 2 failed\n"));
+        assert!(summary.contains(
+            "'{{token}}' is not yet captured: it comes from the request at line 2, above the cursor, which was not run"
+        ));
+        assert!(summary.contains("'{{login.response.$.id}}' is not yet captured"));
+    }
+
+    #[test]
+    fn test_run_all_command_import_cycle() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            temp_dir.path().join("auth.http"),
+            "@use ./auth.http\nPOST http://127.0.0.1:9/login\n",
+        )
+        .unwrap();
+
+        let result = run_all_command(
+            "@use ./auth.http\n\nGET http://127.0.0.1:9/me",
+            None,
+            temp_dir.path(),
+        );
+
+        let error = result.unwrap_err();
+        assert!(error.starts_with("Import cycle: "), "{}", error);
+        assert!(error.ends_with("auth.http"));
+    }
+
+    #[test]
+    fn test_run_from_here_command_cursor_on_delimiter() {
+        let editor_text = "GET http://127.0.0.1:9/a\n###\nGET http://127.0.0.1:9/b\n";
+        let cursor = editor_text.find("###").unwrap() + 1;
+
+        let result = run_from_here_command(editor_text, cursor, None, Path::new("/workspace"));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_assert_matches_command_missing_fixture() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let error = assert_matches_command(
+            "GET https://api.example.com/users",
+            "fixtures/users.json",
+            None,
+            dir.path(),
+        )
+        .unwrap_err();
+
+        assert!(error.starts_with("Failed to read fixture"));
+        assert!(error.contains("users.json"));
+    }
+
+    #[test]
+    fn test_redact_command() {
+        let redacted = redact_command(
+            "user.email, sessions.token",
+            r#"{"user":{"name":"alice","email":"a@example.com"},"sessions":[{"token":"t1"},{"token":"t2"}]}"#,
+        )
+        .unwrap();
+
+        assert!(!redacted.contains("a@example.com"));
+        assert!(!redacted.contains("t1") && !redacted.contains("t2"));
+        assert!(redacted.contains("\"name\": \"alice\""));
+
+        assert!(redact_command(" , ", "{}").is_err());
+        assert!(redact_command("token", "not json").is_err());
+    }
 }
 
 /// Save a response to a file
@@ -2046,6 +3635,87 @@ pub fn copy_response_command(
     copy_response(response, option)
 }
 
+/// Copy the response body of a history entry to clipboard
+///
+/// Looks up the entry by ID (a unique prefix of the ID is enough) and copies
+/// its response body, formatted or raw. Bodies larger than the configured
+/// `maxClipboardSize` are not copied; the result suggests a file to save the
+/// body to instead.
+///
+/// # Arguments
+///
+/// * `entry_id` - ID, or unique ID prefix, of the history entry
+/// * `raw` - Copy the raw body instead of the formatted one
+///
+/// # Returns
+///
+/// `Ok(CopyResponseResult)` with the body, or `Err(String)` if the history
+/// could not be loaded or no single entry matches the ID
+///
+/// # Examples
+///
+/// ```no_run
+/// use rest_client::commands::copy_response_body_command;
+///
+/// let result = copy_response_body_command("3f2a", false).unwrap();
+/// println!("{}", result.message);
+/// ```
+pub fn copy_response_body_command(entry_id: &str, raw: bool) -> Result<CopyResponseResult, String> {
+    let entries = load_history().map_err(|e| format!("Failed to load history: {}", e))?;
+    let entry = find_history_entry(entry_id, &entries)?;
+
+    Ok(copy_history_response_body(
+        entry,
+        raw,
+        get_config().max_clipboard_size,
+    ))
+}
+
+/// Finds the history entry whose ID is, or starts with, `entry_id`.
+fn find_history_entry<'a>(
+    entry_id: &str,
+    entries: &'a [HistoryEntry],
+) -> Result<&'a HistoryEntry, String> {
+    if let Some(entry) = entries.iter().find(|entry| entry.id == entry_id) {
+        return Ok(entry);
+    }
+
+    let mut matches = entries
+        .iter()
+        .filter(|entry| !entry_id.is_empty() && entry.id.starts_with(entry_id));
+
+    match (matches.next(), matches.next()) {
+        (Some(entry), None) => Ok(entry),
+        (Some(_), Some(_)) => Err(format!(
+            "History ID '{}' is ambiguous; use more characters",
+            entry_id
+        )),
+        (None, _) => Err(format!("No history entry found with ID '{}'", entry_id)),
+    }
+}
+
+/// Copies a history entry's response body, suggesting a file when it is too large.
+fn copy_history_response_body(
+    entry: &HistoryEntry,
+    raw: bool,
+    max_size: usize,
+) -> CopyResponseResult {
+    let mut response = format_response(&entry.response);
+    if raw && response.is_formatted {
+        response = toggle_raw_view(&response);
+    }
+
+    let mut result = copy_response_with_limit(&response, CopyOption::Body, max_size);
+    if !result.success {
+        let path = suggest_filename(&entry.request, &response.content_type);
+        result
+            .message
+            .push_str(&format!(" Suggested file: {}", path.display()));
+    }
+
+    result
+}
+
 /// Toggle between formatted and raw view of a response
 ///
 /// Switches the response display between formatted (pretty-printed) and raw (exact bytes).
@@ -2099,3 +3769,48 @@ pub fn fold_response_command(
 ) -> FoldResponseResult {
     fold_response(response, fold_threshold)
 }
+
+/// Fold the response of a history entry
+///
+/// Looks up the entry by ID (a unique prefix of the ID is enough) and folds
+/// its response body with the given mode. `FoldMode::Sections` collapses
+/// sections longer than 10 lines.
+///
+/// # Arguments
+///
+/// * `entry_id` - ID, or unique ID prefix, of the history entry
+/// * `mode` - How to fold the response
+///
+/// # Returns
+///
+/// `Ok(FoldResponseResult)` with the folded response, or `Err(String)` if the
+/// history could not be loaded or no single entry matches the ID
+///
+/// # Examples
+///
+/// ```no_run
+/// use rest_client::commands::fold_history_response_command;
+/// use rest_client::ui::response_actions::FoldMode;
+///
+/// let result = fold_history_response_command("3f2a", FoldMode::TopLevelKeys).unwrap();
+/// println!("{}", result.folded_response);
+/// ```
+pub fn fold_history_response_command(
+    entry_id: &str,
+    mode: FoldMode,
+) -> Result<FoldResponseResult, String> {
+    let entries = load_history().map_err(|e| format!("Failed to load history: {}", e))?;
+    let entry = find_history_entry(entry_id, &entries)?;
+
+    Ok(fold_history_response(entry, mode))
+}
+
+/// Folds a history entry's response with the given mode.
+fn fold_history_response(entry: &HistoryEntry, mode: FoldMode) -> FoldResponseResult {
+    let response = format_response(&entry.response);
+
+    match mode {
+        FoldMode::Sections => fold_response(&response, 10),
+        FoldMode::TopLevelKeys => fold_response_keys(&response),
+    }
+}
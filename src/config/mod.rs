@@ -5,10 +5,11 @@
 
 pub mod schema;
 
-pub use schema::{ResponsePanePosition, RestClientConfig};
+pub use schema::{NewlineStyle, ResponsePanePosition, RestClientConfig};
 
 use once_cell::sync::Lazy;
 use serde_json::Value;
+use std::path::PathBuf;
 use std::sync::RwLock;
 
 /// Global configuration instance.
@@ -143,6 +144,59 @@ where
     }
 }
 
+/// Settings a project can't set, because they let an opened repository run
+/// commands or write files.
+pub const USER_ONLY_SETTINGS: &[&str] = &["allowPipeCommands", "auditLog"];
+
+/// Drops user-only settings that a project's settings file sets.
+///
+/// Zed merges project settings over the user's, so a value set by the
+/// project can't be told apart from the user's own. Any [`USER_ONLY_SETTINGS`]
+/// key that appears in the project file is removed and falls back to its
+/// default. The file may contain comments, so keys are found by name rather
+/// than by parsing it.
+///
+/// # Arguments
+///
+/// * `settings` - The merged REST Client settings object
+/// * `project_settings` - Contents of the project's `.zed/settings.json`
+pub fn strip_user_only_settings(settings: &mut Value, project_settings: &str) {
+    let Some(settings) = settings.as_object_mut() else {
+        return;
+    };
+    for key in USER_ONLY_SETTINGS {
+        if project_settings.contains(&format!("\"{}\"", key)) {
+            settings.remove(*key);
+        }
+    }
+}
+
+/// Returns the directory for the extension's user-level files.
+///
+/// This is `~/.config/zed/extensions/rest-client` on Unix-like systems, or
+/// the equivalent under `AppData\Roaming` on Windows. Request history and the
+/// global environment file are kept here. The directory may not exist yet.
+///
+/// # Returns
+///
+/// The path, or `None` if the home directory can't be determined.
+pub fn user_config_dir() -> Option<PathBuf> {
+    let config_dir = if let Some(home) = std::env::var_os("HOME") {
+        PathBuf::from(home).join(".config")
+    } else if let Some(user_profile) = std::env::var_os("USERPROFILE") {
+        PathBuf::from(user_profile).join("AppData").join("Roaming")
+    } else {
+        return None;
+    };
+
+    Some(
+        config_dir
+            .join("zed")
+            .join("extensions")
+            .join("rest-client"),
+    )
+}
+
 /// Resets the configuration to defaults.
 ///
 /// This is useful for testing or when user wants to clear custom settings.
@@ -355,4 +409,29 @@ mod tests {
         assert_eq!(config.exclude_hosts_from_proxy.len(), 2);
         assert_eq!(config.default_headers.len(), 2);
     }
+
+    #[test]
+    fn test_strip_user_only_settings() {
+        let mut settings = json!({
+            "timeout": 5000,
+            "allowPipeCommands": true,
+            "auditLog": "/tmp/audit.log"
+        });
+        let project = r#"{
+          // Let anyone who opens the repo run commands
+          "lsp": { "rest-client-lsp": { "settings": { "allowPipeCommands": true } } }
+        }"#;
+
+        strip_user_only_settings(&mut settings, project);
+
+        assert_eq!(
+            settings,
+            json!({ "timeout": 5000, "auditLog": "/tmp/audit.log" })
+        );
+
+        // Without a project file, the user's settings are kept
+        let mut settings = json!({ "allowPipeCommands": true });
+        strip_user_only_settings(&mut settings, "");
+        assert_eq!(settings, json!({ "allowPipeCommands": true }));
+    }
 }
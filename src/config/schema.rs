@@ -5,6 +5,7 @@
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::path::PathBuf;
 
 /// Main configuration structure for the REST Client extension.
 ///
@@ -37,6 +38,83 @@ pub struct RestClientConfig {
     #[serde(default = "default_max_redirects")]
     pub max_redirects: u32,
 
+    /// Whether 307 and 308 redirects resend the request with its method and body.
+    ///
+    /// 301, 302, and 303 redirects are always followed with GET. When
+    /// disabled, 307 and 308 redirects are too. Defaults to true.
+    #[serde(default = "default_preserve_method_on_redirect")]
+    pub preserve_method_on_redirect: bool,
+
+    /// Maximum length of a request URL in characters.
+    ///
+    /// Requests whose URL (after variable substitution) exceeds this length are
+    /// rejected before sending, since many servers refuse very long URLs.
+    /// Defaults to 8192.
+    ///
+    /// Must be > 0.
+    #[serde(default = "default_max_url_length")]
+    pub max_url_length: usize,
+
+    /// Whether to percent-encode unsafe characters in resolved URLs.
+    ///
+    /// When enabled, characters such as spaces in the path or query (typically
+    /// introduced by variable substitution) are percent-encoded before sending.
+    /// Reserved delimiters and existing `%XX` escapes are preserved.
+    /// Defaults to true.
+    #[serde(default = "default_encode_url")]
+    pub encode_url: bool,
+
+    /// Whether `:name` path segments in URLs are treated as variables.
+    ///
+    /// When enabled, `/users/:id` resolves `id` like `{{id}}`. Ports such as
+    /// `localhost:8080` are never treated as parameters. Defaults to false.
+    #[serde(default)]
+    pub enable_colon_params: bool,
+
+    /// Whether to decompress gzip, deflate, and brotli response bodies.
+    ///
+    /// When disabled, compressed bodies are shown as received, in the
+    /// binary hex preview. Defaults to true.
+    #[serde(default = "default_decompress_responses")]
+    pub decompress_responses: bool,
+
+    /// Whether to block requests to internal addresses.
+    ///
+    /// When enabled, requests to loopback, link-local, and cloud metadata
+    /// addresses (e.g., `localhost`, `169.254.169.254`) are rejected, guarding
+    /// shared environments against server-side request forgery. Defaults to
+    /// false.
+    #[serde(default)]
+    pub block_internal_addresses: bool,
+
+    /// Hosts that may be requested even when internal addresses are blocked.
+    ///
+    /// Entries are host names or IP addresses (e.g., "localhost",
+    /// "127.0.0.1"), for intentional local testing. Defaults to empty array.
+    #[serde(default)]
+    pub internal_address_allowlist: Vec<String>,
+
+    /// Number of times to retry a request that fails with a network error.
+    ///
+    /// A `# @retries` directive overrides this per request. Defaults to 0
+    /// (no retries).
+    #[serde(default)]
+    pub max_retries: u32,
+
+    /// Delay before the first retry in milliseconds.
+    ///
+    /// The delay doubles after each failed attempt. Defaults to 500.
+    #[serde(default = "default_retry_backoff_ms")]
+    pub retry_backoff_ms: u64,
+
+    /// Longest `Retry-After` wait honored before retrying, in milliseconds.
+    ///
+    /// A 429 or 503 response with a `Retry-After` header is retried after
+    /// the delay the server asks for, capped at this value, instead of the
+    /// backoff delay. Defaults to 60000 (1 minute).
+    #[serde(default = "default_max_retry_after_ms")]
+    pub max_retry_after_ms: u64,
+
     /// Whether to validate SSL/TLS certificates.
     ///
     /// When enabled, requests to HTTPS endpoints will fail if the certificate
@@ -62,6 +140,28 @@ pub struct RestClientConfig {
     #[serde(default = "default_history_limit")]
     pub history_limit: usize,
 
+    /// Maximum number of response tabs to keep open.
+    ///
+    /// When a new response would exceed this limit, the oldest tab is closed.
+    /// Defaults to 10.
+    ///
+    /// Must be > 0.
+    #[serde(default = "default_max_response_tabs")]
+    pub max_response_tabs: usize,
+
+    /// Whether response tabs show only a curated set of headers.
+    ///
+    /// Only Content-Type, Content-Length, and `interesting_headers` are
+    /// listed, with a note saying how many were hidden. Defaults to false.
+    #[serde(default)]
+    pub compact_headers: bool,
+
+    /// Extra headers shown when `compact_headers` is enabled.
+    ///
+    /// Names are matched case-insensitively. Defaults to empty array.
+    #[serde(default)]
+    pub interesting_headers: Vec<String>,
+
     /// Whether to preview responses in a new tab instead of a pane.
     ///
     /// When enabled, responses will open in a new editor tab rather than
@@ -77,10 +177,26 @@ pub struct RestClientConfig {
     #[serde(default = "default_environment_file")]
     pub environment_file: String,
 
+    /// Whether to merge the user-level environment file into the workspace's.
+    ///
+    /// The user-level file (`http-client.env.json` in the extension's config
+    /// directory) holds variables shared by every project, such as personal
+    /// tokens. Workspace values take precedence. Defaults to true.
+    #[serde(default = "default_use_global_environment")]
+    pub use_global_environment: bool,
+
+    /// Proxy to send requests through (e.g., "http://proxy.corp.example.com:8080").
+    ///
+    /// When unset, the `HTTPS_PROXY` and `HTTP_PROXY` environment variables
+    /// are used. Defaults to none.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proxy_url: Option<String>,
+
     /// List of hostnames to exclude from proxy settings.
     ///
     /// Even if system proxy is configured, requests to these hosts will bypass
-    /// the proxy. Defaults to empty array.
+    /// the proxy. Patterns may contain `*` wildcards (e.g., "*.internal.com").
+    /// Defaults to empty array.
     #[serde(default = "default_exclude_hosts_from_proxy")]
     pub exclude_hosts_from_proxy: Vec<String>,
 
@@ -90,6 +206,96 @@ pub struct RestClientConfig {
     /// request-specific headers. Defaults to User-Agent header only.
     #[serde(default = "default_headers")]
     pub default_headers: HashMap<String, String>,
+
+    /// Default `Accept-Language` header value (e.g., "fr-FR").
+    ///
+    /// Sent with requests that don't set `Accept-Language` themselves, and used
+    /// as the value of `{{$locale}}`. A header set in the request always wins.
+    /// Defaults to none.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_accept_language: Option<String>,
+
+    /// Prefix that marks a directive as disabled (e.g., `# !@capture ...`).
+    ///
+    /// A disabled directive is ignored by the parser but kept in the file, so
+    /// it can be re-enabled by removing the prefix. Defaults to "!".
+    #[serde(default = "default_disabled_directive_prefix")]
+    pub disabled_directive_prefix: String,
+
+    /// Maximum size in bytes of a response body copied to the clipboard.
+    ///
+    /// Larger bodies are not copied; saving them to a file is suggested
+    /// instead. Defaults to 1048576 (1 MB).
+    ///
+    /// Must be > 0.
+    #[serde(default = "default_max_clipboard_size")]
+    pub max_clipboard_size: usize,
+
+    /// Whether to collapse large JSON arrays in formatted responses.
+    ///
+    /// Arrays longer than `collapse_array_threshold` show only their first
+    /// and last few elements with a `... (N more items)` marker. Only the
+    /// display is affected; the raw body is kept intact. Defaults to false.
+    #[serde(default)]
+    pub collapse_large_arrays: bool,
+
+    /// Number of elements above which a JSON array is collapsed.
+    ///
+    /// Only used when `collapse_large_arrays` is enabled. Defaults to 100.
+    ///
+    /// Must be > 0.
+    #[serde(default = "default_collapse_array_threshold")]
+    pub collapse_array_threshold: usize,
+
+    /// Whether to show how many bytes the headers and the body take up.
+    ///
+    /// Adds a line such as `Headers: 412 B · Body: 4.10 KB (9% headers)`
+    /// below the total size, to help spot header bloat like large cookies.
+    /// Defaults to false.
+    #[serde(default)]
+    pub show_section_sizes: bool,
+
+    /// Whether `# @pipe <command>` directives may run shell commands.
+    ///
+    /// When enabled, the response body of a request with `# @pipe` is passed
+    /// to the command on stdin and its output is shown instead. Only the
+    /// language server can run commands. Defaults to false, since opening a
+    /// shared .http file should never run arbitrary commands.
+    #[serde(default)]
+    pub allow_pipe_commands: bool,
+
+    /// Maximum number of requests `/run-all` sends at the same time.
+    ///
+    /// Ignored on `wasm32` targets, where requests are sent one at a time.
+    /// Defaults to 4.
+    ///
+    /// Must be > 0.
+    #[serde(default = "default_run_all_concurrency")]
+    pub run_all_concurrency: usize,
+
+    /// Size in bytes above which a `< path` request body is streamed from
+    /// disk instead of being read into memory.
+    ///
+    /// Defaults to 10 MB.
+    ///
+    /// Must be > 0.
+    #[serde(default = "default_large_body_threshold")]
+    pub large_body_threshold: u64,
+
+    /// File to append every sent request and its response to, for auditing.
+    ///
+    /// Each entry holds the request and response (headers and bodies) with
+    /// timestamps; sensitive headers are redacted. Unlike history, the log
+    /// is never pruned. Defaults to none (no audit log).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub audit_log: Option<PathBuf>,
+
+    /// Line ending used in generated code, cURL commands, and formatted
+    /// responses.
+    ///
+    /// Defaults to `lf`; set to `crlf` for Windows line endings.
+    #[serde(default = "default_output_newline")]
+    pub output_newline: NewlineStyle,
 }
 
 /// Position of the response pane relative to the request file.
@@ -104,19 +310,84 @@ pub enum ResponsePanePosition {
     Tab,
 }
 
+/// Line ending used in text the extension generates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NewlineStyle {
+    /// Unix line endings (`\n`).
+    Lf,
+    /// Windows line endings (`\r\n`).
+    Crlf,
+}
+
+impl NewlineStyle {
+    /// Returns the line ending as a string.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NewlineStyle::Lf => "\n",
+            NewlineStyle::Crlf => "\r\n",
+        }
+    }
+
+    /// Converts the line endings of `text` to this style.
+    ///
+    /// Text that already uses `\r\n` is left as-is when converting to CRLF,
+    /// so line endings are never doubled. `Lf` returns the text unchanged.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - Text whose lines end in `\n`
+    ///
+    /// # Returns
+    ///
+    /// The text with every line ending in this style.
+    pub fn apply(&self, text: &str) -> String {
+        match self {
+            NewlineStyle::Lf => text.to_string(),
+            NewlineStyle::Crlf => text.replace("\r\n", "\n").replace('\n', "\r\n"),
+        }
+    }
+}
+
 impl Default for RestClientConfig {
     fn default() -> Self {
         Self {
             timeout: default_timeout(),
             follow_redirects: default_follow_redirects(),
             max_redirects: default_max_redirects(),
+            preserve_method_on_redirect: default_preserve_method_on_redirect(),
+            max_url_length: default_max_url_length(),
+            encode_url: default_encode_url(),
+            enable_colon_params: false,
+            decompress_responses: default_decompress_responses(),
+            block_internal_addresses: false,
+            internal_address_allowlist: Vec::new(),
+            max_retries: 0,
+            retry_backoff_ms: default_retry_backoff_ms(),
+            max_retry_after_ms: default_max_retry_after_ms(),
             validate_ssl: default_validate_ssl(),
             response_pane: default_response_pane(),
             history_limit: default_history_limit(),
+            max_response_tabs: default_max_response_tabs(),
+            compact_headers: false,
+            interesting_headers: Vec::new(),
             preview_response_in_tab: default_preview_response_in_tab(),
             environment_file: default_environment_file(),
+            use_global_environment: default_use_global_environment(),
+            proxy_url: None,
             exclude_hosts_from_proxy: default_exclude_hosts_from_proxy(),
             default_headers: default_headers(),
+            default_accept_language: None,
+            disabled_directive_prefix: default_disabled_directive_prefix(),
+            max_clipboard_size: default_max_clipboard_size(),
+            collapse_large_arrays: false,
+            collapse_array_threshold: default_collapse_array_threshold(),
+            show_section_sizes: false,
+            allow_pipe_commands: false,
+            run_all_concurrency: default_run_all_concurrency(),
+            large_body_threshold: default_large_body_threshold(),
+            audit_log: None,
+            output_newline: default_output_newline(),
         }
     }
 }
@@ -138,7 +409,54 @@ impl RestClientConfig {
             return Err("historyLimit must be greater than 0".to_string());
         }
 
+        // Validate max response tabs
+        if self.max_response_tabs == 0 {
+            return Err("maxResponseTabs must be greater than 0".to_string());
+        }
+
+        // Validate max URL length
+        if self.max_url_length == 0 {
+            return Err("maxUrlLength must be greater than 0".to_string());
+        }
+
+        // Validate disabled directive prefix
+        let prefix = &self.disabled_directive_prefix;
+        if prefix.is_empty() || prefix.contains('@') || prefix.contains(char::is_whitespace) {
+            return Err(
+                "disabledDirectivePrefix must be non-empty and contain no whitespace or '@'"
+                    .to_string(),
+            );
+        }
+
+        // Validate max clipboard size
+        if self.max_clipboard_size == 0 {
+            return Err("maxClipboardSize must be greater than 0".to_string());
+        }
+
+        // Validate collapse array threshold
+        if self.collapse_array_threshold == 0 {
+            return Err("collapseArrayThreshold must be greater than 0".to_string());
+        }
+
+        // Validate run-all concurrency
+        if self.run_all_concurrency == 0 {
+            return Err("runAllConcurrency must be greater than 0".to_string());
+        }
+
+        // Validate large body threshold
+        if self.large_body_threshold == 0 {
+            return Err("largeBodyThreshold must be greater than 0".to_string());
+        }
+
+        // Validate proxy URL
+        if let Some(proxy_url) = &self.proxy_url {
+            if !proxy_url.starts_with("http://") && !proxy_url.starts_with("https://") {
+                return Err("proxyUrl must start with http:// or https://".to_string());
+            }
+        }
+
         // max_redirects can be 0 (no redirects), so no validation needed
+        // max_retries and retry_backoff_ms can be 0 as well
 
         Ok(())
     }
@@ -177,13 +495,39 @@ impl RestClientConfig {
             timeout: other.timeout,
             follow_redirects: other.follow_redirects,
             max_redirects: other.max_redirects,
+            preserve_method_on_redirect: other.preserve_method_on_redirect,
+            max_url_length: other.max_url_length,
+            encode_url: other.encode_url,
+            enable_colon_params: other.enable_colon_params,
+            decompress_responses: other.decompress_responses,
+            block_internal_addresses: other.block_internal_addresses,
+            internal_address_allowlist: other.internal_address_allowlist.clone(),
+            max_retries: other.max_retries,
+            retry_backoff_ms: other.retry_backoff_ms,
+            max_retry_after_ms: other.max_retry_after_ms,
             validate_ssl: other.validate_ssl,
             response_pane: other.response_pane,
             history_limit: other.history_limit,
+            max_response_tabs: other.max_response_tabs,
+            compact_headers: other.compact_headers,
+            interesting_headers: other.interesting_headers.clone(),
             preview_response_in_tab: other.preview_response_in_tab,
             environment_file: other.environment_file.clone(),
+            use_global_environment: other.use_global_environment,
+            proxy_url: other.proxy_url.clone(),
             exclude_hosts_from_proxy: other.exclude_hosts_from_proxy.clone(),
             default_headers: other.default_headers.clone(),
+            default_accept_language: other.default_accept_language.clone(),
+            disabled_directive_prefix: other.disabled_directive_prefix.clone(),
+            max_clipboard_size: other.max_clipboard_size,
+            collapse_large_arrays: other.collapse_large_arrays,
+            collapse_array_threshold: other.collapse_array_threshold,
+            show_section_sizes: other.show_section_sizes,
+            allow_pipe_commands: other.allow_pipe_commands,
+            run_all_concurrency: other.run_all_concurrency,
+            large_body_threshold: other.large_body_threshold,
+            audit_log: other.audit_log.clone(),
+            output_newline: other.output_newline,
         }
     }
 }
@@ -202,6 +546,50 @@ fn default_max_redirects() -> u32 {
     10
 }
 
+fn default_preserve_method_on_redirect() -> bool {
+    true
+}
+
+fn default_max_url_length() -> usize {
+    8192
+}
+
+fn default_retry_backoff_ms() -> u64 {
+    500
+}
+
+fn default_max_retry_after_ms() -> u64 {
+    60000
+}
+
+fn default_disabled_directive_prefix() -> String {
+    "!".to_string()
+}
+
+fn default_max_clipboard_size() -> usize {
+    1024 * 1024 // 1 MB
+}
+
+fn default_collapse_array_threshold() -> usize {
+    100
+}
+
+fn default_run_all_concurrency() -> usize {
+    4
+}
+
+fn default_large_body_threshold() -> u64 {
+    10 * 1024 * 1024 // 10 MB
+}
+
+fn default_encode_url() -> bool {
+    true
+}
+
+fn default_decompress_responses() -> bool {
+    true
+}
+
 fn default_validate_ssl() -> bool {
     true
 }
@@ -210,10 +598,18 @@ fn default_response_pane() -> ResponsePanePosition {
     ResponsePanePosition::Right
 }
 
+fn default_output_newline() -> NewlineStyle {
+    NewlineStyle::Lf
+}
+
 fn default_history_limit() -> usize {
     1000
 }
 
+fn default_max_response_tabs() -> usize {
+    10
+}
+
 fn default_preview_response_in_tab() -> bool {
     false
 }
@@ -222,6 +618,10 @@ fn default_environment_file() -> String {
     ".http-client-env.json".to_string()
 }
 
+fn default_use_global_environment() -> bool {
+    true
+}
+
 fn default_exclude_hosts_from_proxy() -> Vec<String> {
     Vec::new()
 }
@@ -242,12 +642,36 @@ mod tests {
         assert_eq!(config.timeout, 30000);
         assert_eq!(config.follow_redirects, true);
         assert_eq!(config.max_redirects, 10);
+        assert!(config.preserve_method_on_redirect);
+        assert_eq!(config.max_url_length, 8192);
+        assert_eq!(config.encode_url, true);
+        assert_eq!(config.enable_colon_params, false);
+        assert_eq!(config.decompress_responses, true);
+        assert_eq!(config.block_internal_addresses, false);
+        assert!(config.internal_address_allowlist.is_empty());
+        assert_eq!(config.max_retries, 0);
+        assert_eq!(config.retry_backoff_ms, 500);
+        assert_eq!(config.max_retry_after_ms, 60000);
         assert_eq!(config.validate_ssl, true);
         assert_eq!(config.response_pane, ResponsePanePosition::Right);
         assert_eq!(config.history_limit, 1000);
+        assert_eq!(config.max_response_tabs, 10);
         assert_eq!(config.preview_response_in_tab, false);
         assert_eq!(config.environment_file, ".http-client-env.json");
+        assert!(config.use_global_environment);
+        assert_eq!(config.proxy_url, None);
         assert_eq!(config.exclude_hosts_from_proxy.len(), 0);
+        assert_eq!(config.default_accept_language, None);
+        assert_eq!(config.disabled_directive_prefix, "!");
+        assert_eq!(config.max_clipboard_size, 1_048_576);
+        assert_eq!(config.collapse_large_arrays, false);
+        assert_eq!(config.collapse_array_threshold, 100);
+        assert!(!config.show_section_sizes);
+        assert!(!config.allow_pipe_commands);
+        assert_eq!(config.run_all_concurrency, 4);
+        assert_eq!(config.large_body_threshold, 10_485_760);
+        assert!(config.audit_log.is_none());
+        assert_eq!(config.output_newline, NewlineStyle::Lf);
         assert_eq!(config.default_headers.len(), 1);
         assert_eq!(
             config.default_headers.get("User-Agent"),
@@ -283,6 +707,91 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_config_validation_zero_max_response_tabs() {
+        let mut config = RestClientConfig::default();
+        config.max_response_tabs = 0;
+        assert_eq!(
+            config.validate().unwrap_err(),
+            "maxResponseTabs must be greater than 0"
+        );
+    }
+
+    #[test]
+    fn test_config_validation_zero_max_url_length() {
+        let mut config = RestClientConfig::default();
+        config.max_url_length = 0;
+        assert_eq!(
+            config.validate().unwrap_err(),
+            "maxUrlLength must be greater than 0"
+        );
+    }
+
+    #[test]
+    fn test_config_validation_zero_max_clipboard_size() {
+        let mut config = RestClientConfig::default();
+        config.max_clipboard_size = 0;
+        assert_eq!(
+            config.validate().unwrap_err(),
+            "maxClipboardSize must be greater than 0"
+        );
+    }
+
+    #[test]
+    fn test_config_validation_zero_collapse_array_threshold() {
+        let mut config = RestClientConfig::default();
+        config.collapse_array_threshold = 0;
+        assert_eq!(
+            config.validate().unwrap_err(),
+            "collapseArrayThreshold must be greater than 0"
+        );
+    }
+
+    #[test]
+    fn test_config_validation_zero_run_all_concurrency() {
+        let mut config = RestClientConfig::default();
+        config.run_all_concurrency = 0;
+        assert_eq!(
+            config.validate().unwrap_err(),
+            "runAllConcurrency must be greater than 0"
+        );
+    }
+
+    #[test]
+    fn test_config_validation_zero_large_body_threshold() {
+        let mut config = RestClientConfig::default();
+        config.large_body_threshold = 0;
+        assert_eq!(
+            config.validate().unwrap_err(),
+            "largeBodyThreshold must be greater than 0"
+        );
+    }
+
+    #[test]
+    fn test_config_validation_proxy_url() {
+        let mut config = RestClientConfig::default();
+        config.proxy_url = Some("http://proxy.example.com:8080".to_string());
+        assert!(config.validate().is_ok());
+
+        config.proxy_url = Some("proxy.example.com:8080".to_string());
+        assert_eq!(
+            config.validate().unwrap_err(),
+            "proxyUrl must start with http:// or https://"
+        );
+    }
+
+    #[test]
+    fn test_config_validation_disabled_directive_prefix() {
+        let mut config = RestClientConfig::default();
+        config.disabled_directive_prefix = "off".to_string();
+        assert!(config.validate().is_ok());
+
+        for prefix in ["", " ", "!@"] {
+            config.disabled_directive_prefix = prefix.to_string();
+            assert!(config.validate().is_err());
+        }
+    }
+
     #[test]
     fn test_config_validation_zero_redirects_allowed() {
         let mut config = RestClientConfig::default();
@@ -290,6 +799,17 @@ mod tests {
         assert!(config.validate().is_ok());
     }
 
+    #[test]
+    fn test_newline_style_apply() {
+        assert_eq!(NewlineStyle::Lf.apply("a\nb\n"), "a\nb\n");
+        assert_eq!(NewlineStyle::Crlf.apply("a\nb\n"), "a\r\nb\r\n");
+        assert_eq!(NewlineStyle::Crlf.apply("a\r\nb\n"), "a\r\nb\r\n");
+
+        let config: RestClientConfig =
+            serde_json::from_str(r#"{"outputNewline": "crlf"}"#).unwrap();
+        assert_eq!(config.output_newline, NewlineStyle::Crlf);
+    }
+
     #[test]
     fn test_timeout_duration() {
         let config = RestClientConfig {
@@ -393,6 +913,14 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_default_accept_language() {
+        let json = r#"{"defaultAcceptLanguage": "fr-FR"}"#;
+
+        let config: RestClientConfig = serde_json::from_str(json).unwrap();
+        assert_eq!(config.default_accept_language, Some("fr-FR".to_string()));
+    }
+
     #[test]
     fn test_exclude_hosts_from_proxy() {
         let json = r#"{
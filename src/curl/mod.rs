@@ -82,10 +82,10 @@ pub mod ui;
 pub use generator::{
     generate_curl_command, generate_curl_command_compact, generate_curl_with_options, CurlOptions,
 };
-pub use parser::{parse_curl_command, ParseError};
+pub use parser::{parse_curl_command, parse_curl_command_with_warnings, ParseError};
 pub use ui::{
-    copy_as_curl_command, paste_curl_command, validate_curl_command, CopyCurlResult,
-    PasteCurlResult,
+    copy_as_curl_command, copy_as_curl_command_with_options, paste_curl_command,
+    validate_curl_command, CopyCurlResult, PasteCurlResult,
 };
 
 #[cfg(test)]
@@ -3,8 +3,11 @@
 //! This module provides functionality to parse cURL commands into HttpRequest structures.
 //! Supports common cURL flags including headers, methods, bodies, and authentication.
 
+use crate::models::headers::Headers;
+use crate::models::multipart::{MultipartBody, MultipartPart};
 use crate::models::request::{HttpMethod, HttpRequest};
-use std::collections::HashMap;
+use crate::parser::split_file_part_type;
+use crate::variables::substitution::url_encode;
 use std::path::PathBuf;
 
 /// Errors that can occur during cURL parsing.
@@ -67,6 +70,25 @@ impl std::error::Error for ParseError {}
 /// assert_eq!(request.url, "https://api.example.com/users");
 /// ```
 pub fn parse_curl_command(curl_str: &str) -> Result<HttpRequest, ParseError> {
+    parse_curl_command_with_warnings(curl_str).map(|(request, _)| request)
+}
+
+/// Parses a cURL command string, also returning what couldn't be imported.
+///
+/// Flags that don't map onto the request (such as `-o` or a cookie file
+/// passed to `-b`) are ignored, and each one is described in the warnings.
+///
+/// # Arguments
+///
+/// * `curl_str` - The cURL command string to parse
+///
+/// # Returns
+///
+/// `Result<(HttpRequest, Vec<String>), ParseError>` - The parsed request and
+/// any warnings, or an error
+pub fn parse_curl_command_with_warnings(
+    curl_str: &str,
+) -> Result<(HttpRequest, Vec<String>), ParseError> {
     let trimmed = curl_str.trim();
 
     if trimmed.is_empty() {
@@ -149,13 +171,17 @@ fn tokenize(input: &str) -> Result<Vec<String>, ParseError> {
     Ok(tokens)
 }
 
-/// Parses tokens into an HttpRequest.
-fn parse_tokens(tokens: &[String]) -> Result<HttpRequest, ParseError> {
-    let mut method = HttpMethod::GET; // Default method
+/// Parses tokens into an HttpRequest and the warnings for ignored flags.
+fn parse_tokens(tokens: &[String]) -> Result<(HttpRequest, Vec<String>), ParseError> {
+    let mut explicit_method: Option<HttpMethod> = None;
+    let mut head = false;
+    let mut get = false;
     let mut url: Option<String> = None;
-    let mut headers: HashMap<String, String> = HashMap::new();
+    let mut headers = Headers::new();
     let mut body: Option<String> = None;
+    let mut parts: Vec<MultipartPart> = Vec::new();
     let mut unsupported_flags: Vec<String> = Vec::new();
+    let mut warnings: Vec<String> = Vec::new();
 
     let mut i = 0;
 
@@ -179,10 +205,16 @@ fn parse_tokens(tokens: &[String]) -> Result<HttpRequest, ParseError> {
                         ));
                     }
                     let method_str = &tokens[i];
-                    method = HttpMethod::from_str(method_str)
-                        .ok_or_else(|| ParseError::InvalidMethod(method_str.to_string()))?;
+                    explicit_method = Some(
+                        HttpMethod::from_str(method_str)
+                            .ok_or_else(|| ParseError::InvalidMethod(method_str.to_string()))?,
+                    );
                 }
 
+                // Flags that change the default method
+                "-I" | "--head" => head = true,
+                "-G" | "--get" => get = true,
+
                 // Header flags
                 "-H" | "--header" => {
                     i += 1;
@@ -210,16 +242,63 @@ fn parse_tokens(tokens: &[String]) -> Result<HttpRequest, ParseError> {
                         body = Some(data.clone());
 
                         // Auto-detect JSON and set Content-Type if not already set
-                        if data.trim().starts_with('{') || data.trim().starts_with('[') {
+                        if (data.trim().starts_with('{') || data.trim().starts_with('['))
+                            && !headers.contains_key("Content-Type")
+                        {
                             headers
-                                .entry("Content-Type".to_string())
-                                .or_insert_with(|| "application/json".to_string());
+                                .insert("Content-Type".to_string(), "application/json".to_string());
                         }
                     }
+                }
+
+                // URL-encoded data flag
+                "--data-urlencode" => {
+                    i += 1;
+                    if i >= tokens.len() {
+                        return Err(ParseError::ParseError(
+                            "Missing data after --data-urlencode".to_string(),
+                        ));
+                    }
+                    let data = url_encode_data(&tokens[i])?;
+
+                    // Concatenated with other data flags like -d
+                    body = Some(match body.take() {
+                        Some(existing_body) => format!("{}&{}", existing_body, data),
+                        None => data,
+                    });
+                }
+
+                // Multipart form flags
+                "-F" | "--form" => {
+                    i += 1;
+                    if i >= tokens.len() {
+                        return Err(ParseError::ParseError(
+                            "Missing form field after -F".to_string(),
+                        ));
+                    }
+                    parts.push(parse_form_part(&tokens[i])?);
+                }
 
-                    // Set method to POST if still GET and body is present
-                    if method == HttpMethod::GET {
-                        method = HttpMethod::POST;
+                // Cookie flag: an inline `name=value` string, or a cookie file
+                "-b" | "--cookie" => {
+                    i += 1;
+                    if i >= tokens.len() {
+                        return Err(ParseError::ParseError(
+                            "Missing cookies after -b".to_string(),
+                        ));
+                    }
+                    let cookies = tokens[i].trim();
+                    if cookies.contains('=') {
+                        let cookie = match headers.get("Cookie") {
+                            Some(existing) => format!("{}; {}", existing, cookies),
+                            None => cookies.to_string(),
+                        };
+                        headers.insert("Cookie".to_string(), cookie);
+                    } else {
+                        warnings.push(format!(
+                            "Cookie file '{}' is not supported; its cookies were not imported",
+                            cookies
+                        ));
                     }
                 }
 
@@ -273,35 +352,141 @@ fn parse_tokens(tokens: &[String]) -> Result<HttpRequest, ParseError> {
     }
 
     // Validate we found a URL
-    let url = url.ok_or(ParseError::MissingUrl)?;
+    let mut url = url.ok_or(ParseError::MissingUrl)?;
+
+    // Without -X, cURL picks the method from the other flags: -I sends a
+    // HEAD, -G a GET, and data on its own implies a POST
+    let method = match explicit_method {
+        Some(method) => method,
+        None if head => HttpMethod::HEAD,
+        None if get || (body.is_none() && parts.is_empty()) => HttpMethod::GET,
+        None => HttpMethod::POST,
+    };
+
+    // With -G the data is sent as the query string instead of a body
+    if get {
+        if let Some(data) = body.take() {
+            let separator = if url.contains('?') { '&' } else { '?' };
+            url = format!("{}{}{}", url, separator, data);
+        }
+    }
+
+    // -F sends a multipart/form-data body; the boundary is added when sent
+    let multipart = if parts.is_empty() {
+        None
+    } else {
+        if !headers.contains_key("Content-Type") {
+            headers.insert(
+                "Content-Type".to_string(),
+                "multipart/form-data".to_string(),
+            );
+        }
+        Some(MultipartBody { parts })
+    };
 
     // Create the request
     let request = HttpRequest {
         id: uuid::Uuid::new_v4().to_string(),
+        name: None,
         method,
         url,
         http_version: Some("HTTP/1.1".to_string()),
         headers,
         body,
+        raw_body: false,
+        body_encoding: None,
+        timeout_override: None,
+        retries_override: None,
+        no_cookies: false,
+        filter: None,
+        pipe: None,
+        body_file: None,
+        conditional_headers: Vec::new(),
+        captures: Vec::new(),
+        digest_auth: None,
+        aws_sigv4: None,
+        multipart,
+        form_fields: Vec::new(),
+        query_api_key: None,
+        api_key_header: None,
         line_number: 0,
         file_path: PathBuf::new(),
     };
 
-    Ok(request)
+    warnings.extend(
+        unsupported_flags
+            .into_iter()
+            .map(|flag| ParseError::UnsupportedFlag(flag).to_string()),
+    );
+
+    Ok((request, warnings))
 }
 
 /// Parses a header string in the format "Name: Value".
-fn parse_header(header_str: &str, headers: &mut HashMap<String, String>) -> Result<(), ParseError> {
+fn parse_header(header_str: &str, headers: &mut Headers) -> Result<(), ParseError> {
     if let Some(colon_pos) = header_str.find(':') {
         let name = header_str[..colon_pos].trim().to_string();
         let value = header_str[colon_pos + 1..].trim().to_string();
-        headers.insert(name, value);
+        headers.append(name, value);
         Ok(())
     } else {
         Err(ParseError::InvalidHeader(header_str.to_string()))
     }
 }
 
+/// Percent-encodes a `--data-urlencode` value.
+///
+/// Supports cURL's forms: `content` and `=content` encode the content,
+/// `name=content` encodes only the content, and `@file` or `name@file`
+/// encode the contents of a file. The first `=` or `@` decides the form.
+fn url_encode_data(data: &str) -> Result<String, ParseError> {
+    let (name, content) = match data.find(['=', '@']) {
+        Some(pos) if data[pos..].starts_with('@') => {
+            let path = &data[pos + 1..];
+            let content = std::fs::read_to_string(path).map_err(|e| {
+                ParseError::ParseError(format!(
+                    "Failed to read --data-urlencode file '{}': {}",
+                    path, e
+                ))
+            })?;
+            (&data[..pos], content)
+        }
+        Some(pos) => (&data[..pos], data[pos + 1..].to_string()),
+        None => ("", data.to_string()),
+    };
+
+    if name.is_empty() {
+        Ok(url_encode(&content))
+    } else {
+        Ok(format!("{}={}", name, url_encode(&content)))
+    }
+}
+
+/// Parses a `-F` form field: `name=value` for a text field, or
+/// `name=@path` for a file, optionally followed by `;type=<content type>`.
+fn parse_form_part(form: &str) -> Result<MultipartPart, ParseError> {
+    let (name, value) = form
+        .split_once('=')
+        .ok_or_else(|| ParseError::ParseError(format!("Invalid form field: {}", form)))?;
+
+    let part = match value.strip_prefix('@') {
+        Some(file) => {
+            let (path, content_type) = split_file_part_type(file);
+            MultipartPart::File {
+                name: name.to_string(),
+                path: PathBuf::from(path),
+                content_type,
+            }
+        }
+        None => MultipartPart::Field {
+            name: name.to_string(),
+            value: value.to_string(),
+        },
+    };
+
+    Ok(part)
+}
+
 /// Base64 encodes a string (for Basic authentication).
 fn base64_encode(input: &str) -> String {
     use base64::{engine::general_purpose, Engine as _};
@@ -388,6 +573,186 @@ mod tests {
         assert_eq!(result.body, Some("name=John&age=30".to_string()));
     }
 
+    #[test]
+    fn test_data_without_method_is_post() {
+        let result = parse_curl_command("curl --data name=John https://api.example.com").unwrap();
+        assert_eq!(result.method, HttpMethod::POST);
+
+        let result = parse_curl_command("curl https://api.example.com").unwrap();
+        assert_eq!(result.method, HttpMethod::GET);
+    }
+
+    #[test]
+    fn test_explicit_method_wins_over_data() {
+        let result =
+            parse_curl_command("curl -X GET -d name=John https://api.example.com").unwrap();
+        assert_eq!(result.method, HttpMethod::GET);
+        assert_eq!(result.body, Some("name=John".to_string()));
+
+        let result =
+            parse_curl_command("curl -d name=John -X PUT https://api.example.com").unwrap();
+        assert_eq!(result.method, HttpMethod::PUT);
+    }
+
+    #[test]
+    fn test_get_flag_moves_data_to_query() {
+        let curl = "curl -G -d q=rust https://api.example.com/search?page=2";
+        let result = parse_curl_command(curl).unwrap();
+
+        assert_eq!(result.method, HttpMethod::GET);
+        assert_eq!(result.url, "https://api.example.com/search?page=2&q=rust");
+        assert_eq!(result.body, None);
+    }
+
+    #[test]
+    fn test_form_fields() {
+        let curl = "curl -F title=Hello --form 'photo=@./photo.png;type=image/webp' https://api.example.com/upload";
+        let result = parse_curl_command(curl).unwrap();
+
+        assert_eq!(result.method, HttpMethod::POST);
+        assert_eq!(
+            result.headers.get("Content-Type"),
+            Some(&"multipart/form-data".to_string())
+        );
+        assert_eq!(result.body, None);
+        assert_eq!(
+            result.multipart.unwrap().parts,
+            vec![
+                MultipartPart::Field {
+                    name: "title".to_string(),
+                    value: "Hello".to_string(),
+                },
+                MultipartPart::File {
+                    name: "photo".to_string(),
+                    path: PathBuf::from("./photo.png"),
+                    content_type: Some("image/webp".to_string()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_form_fields_round_trip() {
+        let curl = "curl -F title=Hello -F file=@report.pdf https://api.example.com/upload";
+        let request = parse_curl_command(curl).unwrap();
+
+        let regenerated = crate::curl::generator::generate_curl_command(&request);
+        assert!(regenerated.contains("'title=Hello'"));
+        assert!(regenerated.contains("'file=@report.pdf'"));
+
+        let reparsed = parse_curl_command(&regenerated).unwrap();
+        assert_eq!(reparsed.multipart, request.multipart);
+    }
+
+    #[test]
+    fn test_get_flag_with_multiple_data_flags() {
+        let curl =
+            "curl --get -d q=rust --data page=2 -d sort=stars https://api.example.com/search";
+        let result = parse_curl_command(curl).unwrap();
+
+        assert_eq!(result.method, HttpMethod::GET);
+        assert_eq!(
+            result.url,
+            "https://api.example.com/search?q=rust&page=2&sort=stars"
+        );
+        assert_eq!(result.body, None);
+    }
+
+    #[test]
+    fn test_data_urlencode() {
+        let curl = "curl --data-urlencode 'q=a b&c' https://api.example.com/search";
+        let result = parse_curl_command(curl).unwrap();
+
+        assert_eq!(result.method, HttpMethod::POST);
+        assert_eq!(result.body, Some("q=a%20b%26c".to_string()));
+    }
+
+    #[test]
+    fn test_data_urlencode_forms() {
+        assert_eq!(url_encode_data("a b&c").unwrap(), "a%20b%26c");
+        assert_eq!(url_encode_data("=a b&c").unwrap(), "a%20b%26c");
+        assert_eq!(url_encode_data("q=x=y z").unwrap(), "q=x%3Dy%20z");
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("query.txt");
+        std::fs::write(&path, "hello world & more").unwrap();
+        assert_eq!(
+            url_encode_data(&format!("q@{}", path.display())).unwrap(),
+            "q=hello%20world%20%26%20more"
+        );
+        assert_eq!(
+            url_encode_data(&format!("@{}", path.display())).unwrap(),
+            "hello%20world%20%26%20more"
+        );
+        assert!(url_encode_data("@/nonexistent/query.txt").is_err());
+    }
+
+    #[test]
+    fn test_data_urlencode_concatenated_with_data() {
+        let curl = "curl -d page=2 --data-urlencode 'q=a b' --data-urlencode 'tag=c&d' https://api.example.com/search";
+        let result = parse_curl_command(curl).unwrap();
+
+        assert_eq!(result.body, Some("page=2&q=a%20b&tag=c%26d".to_string()));
+    }
+
+    #[test]
+    fn test_cookie_flag() {
+        let result = parse_curl_command("curl -b session=abc https://api.example.com").unwrap();
+        assert_eq!(
+            result.headers.get("Cookie"),
+            Some(&"session=abc".to_string())
+        );
+    }
+
+    #[test]
+    fn test_cookie_flag_multiple_cookies() {
+        let curl = r#"curl -b "session=abc; theme=dark" --cookie lang=en https://api.example.com"#;
+        let result = parse_curl_command(curl).unwrap();
+
+        assert_eq!(
+            result.headers.get("Cookie"),
+            Some(&"session=abc; theme=dark; lang=en".to_string())
+        );
+    }
+
+    #[test]
+    fn test_cookie_file_ignored() {
+        let (result, warnings) =
+            parse_curl_command_with_warnings("curl -b cookies.txt https://api.example.com")
+                .unwrap();
+
+        assert!(result.headers.get("Cookie").is_none());
+        assert_eq!(result.url, "https://api.example.com");
+        assert_eq!(
+            warnings,
+            vec!["Cookie file 'cookies.txt' is not supported; its cookies were not imported"]
+        );
+    }
+
+    #[test]
+    fn test_unsupported_flags_warned() {
+        let (_, warnings) =
+            parse_curl_command_with_warnings("curl -o out.json --retry https://api.example.com -k")
+                .unwrap();
+
+        assert_eq!(
+            warnings,
+            vec![
+                "Unsupported cURL flag: -o (will be ignored)",
+                "Unsupported cURL flag: --retry (will be ignored)",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_head_flag() {
+        for flag in ["-I", "--head"] {
+            let curl = format!("curl {} https://api.example.com", flag);
+            let result = parse_curl_command(&curl).unwrap();
+            assert_eq!(result.method, HttpMethod::HEAD);
+        }
+    }
+
     #[test]
     fn test_empty_input() {
         let result = parse_curl_command("");
@@ -7,7 +7,10 @@
 //! These functions are designed to integrate with Zed's slash command system
 //! and provide user-friendly feedback with preview, validation, and formatting.
 
-use crate::curl::{generate_curl_command, parse_curl_command};
+use crate::curl::{
+    generate_curl_with_options, parse_curl_command, parse_curl_command_with_warnings, CurlOptions,
+};
+use crate::models::multipart::MultipartPart;
 use crate::models::HttpRequest;
 
 /// Result of a cURL paste operation
@@ -151,15 +154,15 @@ pub fn paste_curl_command(curl_text: &str) -> PasteCurlResult {
     }
 
     // Parse the cURL command
-    let request = match parse_curl_command(trimmed) {
-        Ok(req) => req,
+    let (request, warnings) = match parse_curl_command_with_warnings(trimmed) {
+        Ok(parsed) => parsed,
         Err(e) => {
             return PasteCurlResult::failure(format!("Failed to parse cURL command: {}", e));
         }
     };
 
     // Format as HTTP request with nice spacing and comments
-    let formatted = format_request_from_curl(&request);
+    let formatted = format_request_from_curl(&request, &warnings);
 
     PasteCurlResult::success(request, formatted)
 }
@@ -179,13 +182,30 @@ pub fn paste_curl_command(curl_text: &str) -> PasteCurlResult {
 ///
 /// A `CopyCurlResult` containing the cURL command or error
 pub fn copy_as_curl_command(request: &HttpRequest) -> CopyCurlResult {
+    copy_as_curl_command_with_options(request, &CurlOptions::default())
+}
+
+/// Generate a cURL command from an HTTP request with formatting options
+///
+/// # Arguments
+///
+/// * `request` - The HTTP request to convert
+/// * `options` - Formatting options, e.g. `get` to emit a GET's query as `-G` data
+///
+/// # Returns
+///
+/// A `CopyCurlResult` containing the cURL command or error
+pub fn copy_as_curl_command_with_options(
+    request: &HttpRequest,
+    options: &CurlOptions,
+) -> CopyCurlResult {
     // Validate request has minimum required fields
     if request.url.is_empty() {
         return CopyCurlResult::failure("Request has no URL".to_string());
     }
 
     // Generate the cURL command
-    let curl_command = generate_curl_command(request);
+    let curl_command = generate_curl_with_options(request, options);
 
     CopyCurlResult::success(curl_command)
 }
@@ -194,14 +214,18 @@ pub fn copy_as_curl_command(request: &HttpRequest) -> CopyCurlResult {
 ///
 /// Adds:
 /// - Source comment indicating it came from cURL
+/// - A `# Warning:` comment for each part of the command that was ignored
 /// - Proper spacing between method/URL and headers
 /// - Blank line before body
 /// - Clean formatting
-fn format_request_from_curl(request: &HttpRequest) -> String {
+fn format_request_from_curl(request: &HttpRequest, warnings: &[String]) -> String {
     let mut output = String::new();
 
     // Add source comment
     output.push_str("# Generated from cURL command\n");
+    for warning in warnings {
+        output.push_str(&format!("# Warning: {}\n", warning));
+    }
 
     // Add method and URL
     output.push_str(&format!("{} {}\n", request.method, request.url));
@@ -222,6 +246,29 @@ fn format_request_from_curl(request: &HttpRequest) -> String {
         }
     }
 
+    // Add multipart parts in .http syntax
+    if let Some(multipart) = &request.multipart {
+        output.push('\n');
+        for part in &multipart.parts {
+            match part {
+                MultipartPart::Field { name, value } => {
+                    output.push_str(&format!("--field name={}\n{}\n", name, value));
+                }
+                MultipartPart::File {
+                    name,
+                    path,
+                    content_type,
+                } => {
+                    output.push_str(&format!("--file {}={}", name, path.display()));
+                    if let Some(content_type) = content_type {
+                        output.push_str(&format!(";type={}", content_type));
+                    }
+                    output.push('\n');
+                }
+            }
+        }
+    }
+
     output
 }
 
@@ -299,6 +346,37 @@ mod tests {
         assert!(result.formatted_request.contains(r#"{"name":"John"}"#));
     }
 
+    #[test]
+    fn test_paste_curl_with_form_fields() {
+        let curl = "curl -F title=Hello -F 'photo=@./photo.png;type=image/png' https://api.example.com/upload";
+        let result = paste_curl_command(curl);
+
+        assert!(result.success);
+        assert!(result
+            .formatted_request
+            .contains("POST https://api.example.com/upload"));
+        assert!(result
+            .formatted_request
+            .contains("Content-Type: multipart/form-data"));
+        assert!(result
+            .formatted_request
+            .contains("--field name=title\nHello\n--file photo=./photo.png;type=image/png\n"));
+    }
+
+    #[test]
+    fn test_paste_curl_reports_ignored_flags() {
+        let curl = "curl -b cookies.txt -o out.json https://api.example.com/users";
+        let result = paste_curl_command(curl);
+
+        assert!(result.success);
+        assert!(result.formatted_request.starts_with(
+            "# Generated from cURL command\n\
+             # Warning: Cookie file 'cookies.txt' is not supported; its cookies were not imported\n\
+             # Warning: Unsupported cURL flag: -o (will be ignored)\n\
+             GET https://api.example.com/users\n"
+        ));
+    }
+
     #[test]
     fn test_paste_curl_empty_content() {
         let result = paste_curl_command("");
@@ -339,6 +417,26 @@ mod tests {
             .contains("https://api.example.com/users"));
     }
 
+    #[test]
+    fn test_copy_as_curl_with_get_option() {
+        let request = HttpRequest::new(
+            "test-get".to_string(),
+            crate::models::HttpMethod::GET,
+            "https://api.example.com/search?q=rust".to_string(),
+        );
+        let options = CurlOptions {
+            get: true,
+            ..Default::default()
+        };
+
+        let result = copy_as_curl_command_with_options(&request, &options);
+        assert!(result.success);
+        assert_eq!(
+            result.curl_command,
+            "curl -G -d 'q=rust' https://api.example.com/search"
+        );
+    }
+
     #[test]
     fn test_copy_as_curl_with_headers() {
         let mut request = HttpRequest::new(
@@ -410,7 +508,7 @@ mod tests {
         request.add_header("Accept".to_string(), "application/json".to_string());
         request.set_body("test body".to_string());
 
-        let formatted = format_request_from_curl(&request);
+        let formatted = format_request_from_curl(&request, &[]);
 
         assert!(formatted.contains("# Generated from cURL"));
         assert!(formatted.contains("POST https://api.example.com/test"));
@@ -3,12 +3,18 @@
 //! This module handles loading environment configuration files from the workspace.
 //! It searches for .http-client-env.json or http-client.env.json files starting
 //! from the workspace root and traversing up to 3 parent directories.
+//!
+//! A user-level `http-client.env.json` in the extension's config directory is
+//! merged underneath the workspace file, so variables such as personal tokens
+//! can be shared by every project without being committed. Workspace values
+//! take precedence over user-level ones.
 
 use super::models::{Environment, Environments};
+use crate::config::{get_config, user_config_dir};
 use serde_json;
 use std::collections::HashMap;
 use std::fs;
-use std::io;
+use std::io::{self, Write};
 use std::path::{Path, PathBuf};
 
 /// Errors that can occur during environment loading
@@ -25,6 +31,9 @@ pub enum EnvError {
 
     /// IO error occurred while reading file
     IoError(String),
+
+    /// An environment file already exists where a new one would be written
+    AlreadyExists(PathBuf),
 }
 
 impl std::fmt::Display for EnvError {
@@ -37,6 +46,9 @@ impl std::fmt::Display for EnvError {
             EnvError::ParseError(msg) => write!(f, "Failed to parse environment file: {}", msg),
             EnvError::InvalidFormat(msg) => write!(f, "Invalid environment format: {}", msg),
             EnvError::IoError(msg) => write!(f, "IO error: {}", msg),
+            EnvError::AlreadyExists(path) => {
+                write!(f, "Environment file already exists: {}", path.display())
+            }
         }
     }
 }
@@ -61,11 +73,30 @@ const ENV_FILE_NAMES: &[&str] = &[".http-client-env.json", "http-client.env.json
 /// Maximum number of parent directories to search
 const MAX_PARENT_SEARCH_DEPTH: usize = 3;
 
+/// Starter environment file written by `init_environment_file`
+pub const STARTER_ENVIRONMENT_FILE: &str = r#"{
+  "$shared": {
+    "apiVersion": "v1"
+  },
+  "dev": {
+    "baseUrl": "http://localhost:3000",
+    "apiKey": "dev-api-key"
+  },
+  "production": {
+    "baseUrl": "https://api.example.com",
+    "apiKey": "replace-with-production-key"
+  },
+  "active": "dev"
+}
+"#;
+
 /// Loads environment configuration from workspace
 ///
 /// Searches for environment files starting from the workspace path and
-/// traversing up to 3 parent directories. Returns an empty Environments
-/// struct if no file is found (graceful fallback).
+/// traversing up to 3 parent directories, and merges the user-level
+/// environment file underneath it unless `useGlobalEnvironment` is disabled.
+/// Returns an empty Environments struct if neither file is found (graceful
+/// fallback).
 ///
 /// # Arguments
 ///
@@ -76,17 +107,69 @@ const MAX_PARENT_SEARCH_DEPTH: usize = 3;
 /// * `Ok(Environments)` - Loaded environments or empty if file not found
 /// * `Err(EnvError)` - If file exists but parsing failed
 pub fn load_environments(workspace_path: &Path) -> Result<Environments, EnvError> {
-    // Search for environment file
-    let env_file = match find_environment_file(workspace_path) {
-        Some(path) => path,
-        None => {
-            // Gracefully return empty environments if file not found
-            return Ok(Environments::new());
-        }
+    let global_file = if get_config().use_global_environment {
+        global_environment_file()
+    } else {
+        None
     };
 
+    load_environments_with_global(workspace_path, global_file.as_deref())
+}
+
+/// Loads workspace environments merged over a user-level environment file
+///
+/// Variables in the workspace file override those of the same name in the
+/// global file, both in `$shared` and within each environment. Environments
+/// defined only in the global file are kept. The workspace's `active`
+/// environment wins over the global one.
+///
+/// # Arguments
+///
+/// * `workspace_path` - The root workspace directory to start searching from
+/// * `global_file` - The user-level environment file, if any; a path that
+///   doesn't exist is ignored
+///
+/// # Returns
+///
+/// * `Ok(Environments)` - Merged environments, or empty if neither file exists
+/// * `Err(EnvError)` - If either file exists but parsing failed
+pub fn load_environments_with_global(
+    workspace_path: &Path,
+    global_file: Option<&Path>,
+) -> Result<Environments, EnvError> {
+    let workspace = find_environment_file(workspace_path)
+        .map(|path| read_environment_file(&path))
+        .transpose()?;
+    let global = global_file
+        .filter(|path| path.is_file())
+        .map(read_environment_file)
+        .transpose()?;
+
+    Ok(match (global, workspace) {
+        (Some(global), Some(workspace)) => merge_environments(global, workspace),
+        (Some(environments), None) | (None, Some(environments)) => environments,
+        // Gracefully return empty environments if no file is found
+        (None, None) => Environments::new(),
+    })
+}
+
+/// Returns the path of the user-level environment file
+///
+/// The file lives next to the request history, in [`user_config_dir`]:
+/// `~/.config/zed/extensions/rest-client/http-client.env.json` on Unix-like
+/// systems, or the equivalent under `AppData\Roaming` on Windows.
+///
+/// # Returns
+///
+/// The path, or `None` if the home directory can't be determined.
+pub fn global_environment_file() -> Option<PathBuf> {
+    user_config_dir().map(|dir| dir.join("http-client.env.json"))
+}
+
+/// Reads and parses a single environment file
+fn read_environment_file(path: &Path) -> Result<Environments, EnvError> {
     // Read file content
-    let content = fs::read_to_string(&env_file)?;
+    let content = fs::read_to_string(path)?;
 
     // Parse JSON into raw structure
     let raw: serde_json::Value = serde_json::from_str(&content)?;
@@ -95,6 +178,199 @@ pub fn load_environments(workspace_path: &Path) -> Result<Environments, EnvError
     parse_environment_file(raw)
 }
 
+/// Where a variable is declared in an environment file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VariableLocation {
+    /// The environment file declaring the variable
+    pub path: PathBuf,
+    /// Byte offset of the key's opening quote
+    pub offset: usize,
+    /// Zero-based line of the key
+    pub line: usize,
+    /// Zero-based byte column of the key's opening quote
+    pub character: usize,
+}
+
+impl VariableLocation {
+    /// Creates a location from a byte offset into the file content
+    fn at_offset(path: PathBuf, content: &str, offset: usize) -> Self {
+        let before = &content[..offset];
+        let line_start = before.rfind('\n').map_or(0, |pos| pos + 1);
+
+        Self {
+            path,
+            offset,
+            line: before.matches('\n').count(),
+            character: offset - line_start,
+        }
+    }
+}
+
+/// Finds where a variable is declared in the environment files
+///
+/// Follows the same precedence as variable resolution: the environment's
+/// own variables before `$shared`, and the workspace file before the
+/// user-level file.
+///
+/// # Arguments
+///
+/// * `workspace_path` - The root workspace directory to start searching from
+/// * `name` - The variable name
+/// * `environment` - The active environment, if any
+///
+/// # Returns
+///
+/// The location of the declaration, or `None` if no environment file
+/// declares the variable.
+pub fn find_variable_definition(
+    workspace_path: &Path,
+    name: &str,
+    environment: Option<&str>,
+) -> Option<VariableLocation> {
+    let global_file = if get_config().use_global_environment {
+        global_environment_file()
+    } else {
+        None
+    };
+
+    find_variable_definition_internal(workspace_path, global_file.as_deref(), name, environment)
+}
+
+/// Finds where a variable is declared, with the user-level file given
+/// explicitly; a `global_file` that doesn't exist is skipped.
+fn find_variable_definition_internal(
+    workspace_path: &Path,
+    global_file: Option<&Path>,
+    name: &str,
+    environment: Option<&str>,
+) -> Option<VariableLocation> {
+    let global_file = global_file
+        .filter(|path| path.is_file())
+        .map(Path::to_path_buf);
+    let files: Vec<(PathBuf, String)> = find_environment_file(workspace_path)
+        .into_iter()
+        .chain(global_file)
+        .filter_map(|path| {
+            fs::read_to_string(&path)
+                .ok()
+                .map(|content| (path, content))
+        })
+        .collect();
+
+    let sections = environment.into_iter().chain(["$shared", "shared"]);
+    for section in sections {
+        for (path, content) in &files {
+            if let Some(offset) = find_key_offset(content, section, name) {
+                return Some(VariableLocation::at_offset(path.clone(), content, offset));
+            }
+        }
+    }
+
+    None
+}
+
+/// Returns the byte offset of `key` inside the top-level `section` object
+///
+/// The JSON is scanned rather than deserialized so that key positions are
+/// kept; the file is assumed to be valid, since it was loaded already.
+fn find_key_offset(content: &str, section: &str, key: &str) -> Option<usize> {
+    let bytes = content.as_bytes();
+    let mut depth = 0usize;
+    let mut current_section: Option<String> = None;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'{' | b'[' => depth += 1,
+            b'}' | b']' => depth = depth.saturating_sub(1),
+            b'"' => {
+                let start = i;
+                i += 1;
+                while i < bytes.len() && bytes[i] != b'"' {
+                    i += if bytes[i] == b'\\' { 2 } else { 1 };
+                }
+                let end = (i + 1).min(bytes.len());
+
+                let is_key = content[end..].trim_start().starts_with(':');
+                if is_key {
+                    let name: String = serde_json::from_str(&content[start..end]).ok()?;
+                    match depth {
+                        1 => current_section = Some(name),
+                        2 if current_section.as_deref() == Some(section) && name == key => {
+                            return Some(start);
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+
+    None
+}
+
+/// Merges workspace environments over global ones, workspace values winning
+fn merge_environments(global: Environments, workspace: Environments) -> Environments {
+    let mut merged = global;
+    merged.shared.extend(workspace.shared);
+
+    for (name, environment) in workspace.environments {
+        merged
+            .environments
+            .entry(name.clone())
+            .or_insert_with(|| Environment::new(name))
+            .variables
+            .extend(environment.variables);
+    }
+
+    merged.active = workspace.active.or(merged.active);
+    merged
+}
+
+/// Writes a starter `.http-client-env.json` to the workspace root
+///
+/// The file defines `$shared` variables, `dev` and `production`
+/// environments, and makes `dev` active. An existing environment file in the
+/// workspace root (under either supported name) is never overwritten.
+///
+/// # Arguments
+///
+/// * `workspace_path` - The root workspace directory
+///
+/// # Returns
+///
+/// * `Ok(PathBuf)` - Path of the file that was written
+/// * `Err(EnvError::AlreadyExists)` - If the workspace already has an environment file
+/// * `Err(EnvError::IoError)` - If the file could not be written
+pub fn init_environment_file(workspace_path: &Path) -> Result<PathBuf, EnvError> {
+    if let Some(existing) = ENV_FILE_NAMES
+        .iter()
+        .map(|filename| workspace_path.join(filename))
+        .find(|candidate| candidate.exists())
+    {
+        return Err(EnvError::AlreadyExists(existing));
+    }
+
+    // `create_new` fails instead of overwriting a file created since the check
+    let env_file = workspace_path.join(ENV_FILE_NAMES[0]);
+    let mut file = match fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&env_file)
+    {
+        Ok(file) => file,
+        Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {
+            return Err(EnvError::AlreadyExists(env_file));
+        }
+        Err(e) => return Err(e.into()),
+    };
+    file.write_all(STARTER_ENVIRONMENT_FILE.as_bytes())?;
+
+    Ok(env_file)
+}
+
 /// Finds the environment file by searching workspace and parent directories
 fn find_environment_file(workspace_path: &Path) -> Option<PathBuf> {
     let mut current_path = workspace_path.to_path_buf();
@@ -257,7 +533,7 @@ mod tests {
     #[test]
     fn test_load_environments_file_not_found() {
         let temp_dir = TempDir::new().unwrap();
-        let result = load_environments(temp_dir.path()).unwrap();
+        let result = load_environments_with_global(temp_dir.path(), None).unwrap();
 
         // Should return empty environments gracefully
         assert!(result.is_empty());
@@ -265,6 +541,153 @@ mod tests {
         assert!(result.active.is_none());
     }
 
+    #[test]
+    fn test_init_environment_file() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let path = init_environment_file(temp_dir.path()).unwrap();
+        assert_eq!(path, temp_dir.path().join(".http-client-env.json"));
+
+        // The starter file loads with dev active
+        let envs = load_environments_with_global(temp_dir.path(), None).unwrap();
+        assert_eq!(envs.active.as_deref(), Some("dev"));
+        assert!(envs.environments.contains_key("production"));
+        assert_eq!(envs.shared.get("apiVersion").unwrap(), "v1");
+    }
+
+    #[test]
+    fn test_init_environment_file_refuses_to_overwrite() {
+        let temp_dir = TempDir::new().unwrap();
+        let existing = create_temp_env_file(temp_dir.path(), "http-client.env.json", "{}");
+
+        assert_eq!(
+            init_environment_file(temp_dir.path()).unwrap_err(),
+            EnvError::AlreadyExists(existing.clone())
+        );
+        assert_eq!(fs::read_to_string(&existing).unwrap(), "{}");
+        assert!(!temp_dir.path().join(".http-client-env.json").exists());
+    }
+
+    #[test]
+    fn test_global_environment_merged_under_workspace() {
+        let workspace_dir = TempDir::new().unwrap();
+        let global_dir = TempDir::new().unwrap();
+        create_temp_env_file(
+            workspace_dir.path(),
+            ".http-client-env.json",
+            r#"{
+                "$shared": { "apiVersion": "v2" },
+                "dev": { "baseUrl": "http://localhost:3000" },
+                "active": "dev"
+            }"#,
+        );
+        let global_file = create_temp_env_file(
+            global_dir.path(),
+            "http-client.env.json",
+            r#"{
+                "$shared": { "apiVersion": "v1", "apiToken": "personal-token" },
+                "dev": { "baseUrl": "http://dev.example.com", "user": "me" },
+                "sandbox": { "baseUrl": "https://sandbox.example.com" }
+            }"#,
+        );
+
+        let envs = load_environments_with_global(workspace_dir.path(), Some(&global_file)).unwrap();
+
+        // Workspace values win; global-only values fill the gaps
+        assert_eq!(envs.shared.get("apiVersion").unwrap(), "v2");
+        assert_eq!(envs.shared.get("apiToken").unwrap(), "personal-token");
+        let dev = envs.get_environment("dev").unwrap();
+        assert_eq!(dev.get("baseUrl").unwrap(), "http://localhost:3000");
+        assert_eq!(dev.get("user").unwrap(), "me");
+        assert!(envs.get_environment("sandbox").is_some());
+        assert_eq!(envs.active.as_deref(), Some("dev"));
+
+        // Without the global file only the workspace is loaded
+        let envs = load_environments_with_global(workspace_dir.path(), None).unwrap();
+        assert!(envs.shared.get("apiToken").is_none());
+        assert!(envs.get_environment("sandbox").is_none());
+    }
+
+    #[test]
+    fn test_find_variable_definition_offsets() {
+        let dir = TempDir::new().unwrap();
+        let content = "{\n  \"$shared\": {\n    \"baseUrl\": \"https://api.example.com\",\n    \"token\": \"shared\"\n  },\n  \"dev\": {\n    \"baseUrl\": \"http://localhost:3000\",\n    \"note\": \"a \\\"quoted\\\": value\"\n  }\n}\n";
+        let path = create_temp_env_file(dir.path(), ".http-client-env.json", content);
+
+        // The active environment's declaration wins over `$shared`
+        let location =
+            find_variable_definition_internal(dir.path(), None, "baseUrl", Some("dev")).unwrap();
+        assert_eq!(location.path, path);
+        assert_eq!(location.offset, content.rfind("\"baseUrl\"").unwrap());
+        assert_eq!((location.line, location.character), (6, 4));
+
+        // Without an active environment, `$shared` is used
+        let location =
+            find_variable_definition_internal(dir.path(), None, "baseUrl", None).unwrap();
+        assert_eq!(location.offset, content.find("\"baseUrl\"").unwrap());
+        assert_eq!((location.line, location.character), (2, 4));
+
+        let location =
+            find_variable_definition_internal(dir.path(), None, "token", Some("dev")).unwrap();
+        assert_eq!(location.line, 3);
+
+        // Keys inside string values and undeclared names aren't matched
+        assert!(
+            find_variable_definition_internal(dir.path(), None, "quoted", Some("dev")).is_none()
+        );
+        assert!(
+            find_variable_definition_internal(dir.path(), None, "missing", Some("dev")).is_none()
+        );
+    }
+
+    #[test]
+    fn test_find_variable_definition_in_global_file() {
+        let workspace_dir = TempDir::new().unwrap();
+        let global_dir = TempDir::new().unwrap();
+        create_temp_env_file(
+            workspace_dir.path(),
+            ".http-client-env.json",
+            r#"{"dev": {"baseUrl": "http://localhost:3000"}}"#,
+        );
+        let global_file = create_temp_env_file(
+            global_dir.path(),
+            "http-client.env.json",
+            r#"{"dev": {"baseUrl": "https://global.example.com", "apiKey": "global"}}"#,
+        );
+        let find = |name| {
+            find_variable_definition_internal(
+                workspace_dir.path(),
+                Some(&global_file),
+                name,
+                Some("dev"),
+            )
+            .unwrap()
+        };
+
+        // The workspace file wins; the global file fills the gaps
+        assert_ne!(find("baseUrl").path, global_file);
+        assert_eq!(find("apiKey").path, global_file);
+    }
+
+    #[test]
+    fn test_global_environment_without_workspace_file() {
+        let workspace_dir = TempDir::new().unwrap();
+        let global_dir = TempDir::new().unwrap();
+        let global_file = create_temp_env_file(
+            global_dir.path(),
+            "http-client.env.json",
+            r#"{ "$shared": { "apiToken": "personal-token" } }"#,
+        );
+
+        let envs = load_environments_with_global(workspace_dir.path(), Some(&global_file)).unwrap();
+        assert_eq!(envs.shared.get("apiToken").unwrap(), "personal-token");
+
+        // A missing global file is ignored
+        let missing = global_dir.path().join("missing.json");
+        let envs = load_environments_with_global(workspace_dir.path(), Some(&missing)).unwrap();
+        assert!(envs.shared.is_empty());
+    }
+
     #[test]
     fn test_load_environments_simple() {
         let temp_dir = TempDir::new().unwrap();
@@ -281,7 +704,7 @@ mod tests {
 
         create_temp_env_file(temp_dir.path(), ".http-client-env.json", content);
 
-        let envs = load_environments(temp_dir.path()).unwrap();
+        let envs = load_environments_with_global(temp_dir.path(), None).unwrap();
 
         assert_eq!(envs.len(), 2);
         assert!(envs.has_environment("dev"));
@@ -307,7 +730,7 @@ mod tests {
 
         create_temp_env_file(temp_dir.path(), ".http-client-env.json", content);
 
-        let envs = load_environments(temp_dir.path()).unwrap();
+        let envs = load_environments_with_global(temp_dir.path(), None).unwrap();
 
         assert_eq!(envs.shared.len(), 2);
         assert_eq!(envs.shared.get("contentType").unwrap(), "application/json");
@@ -329,7 +752,7 @@ mod tests {
 
         create_temp_env_file(temp_dir.path(), ".http-client-env.json", content);
 
-        let envs = load_environments(temp_dir.path()).unwrap();
+        let envs = load_environments_with_global(temp_dir.path(), None).unwrap();
 
         assert_eq!(envs.active.as_ref().unwrap(), "dev");
         assert_eq!(
@@ -349,7 +772,7 @@ mod tests {
 
         create_temp_env_file(temp_dir.path(), "http-client.env.json", content);
 
-        let envs = load_environments(temp_dir.path()).unwrap();
+        let envs = load_environments_with_global(temp_dir.path(), None).unwrap();
 
         assert_eq!(envs.len(), 1);
         assert!(envs.has_environment("dev"));
@@ -365,7 +788,7 @@ mod tests {
         create_temp_env_file(temp_dir.path(), ".http-client-env.json", content);
 
         // Load from subdirectory should find parent file
-        let envs = load_environments(&sub_dir).unwrap();
+        let envs = load_environments_with_global(&sub_dir, None).unwrap();
 
         assert_eq!(envs.len(), 1);
         assert!(envs.has_environment("dev"));
@@ -388,12 +811,12 @@ mod tests {
 
         // Should find file within MAX_PARENT_SEARCH_DEPTH (3)
         let level3 = temp_dir.path().join("level0/level1/level2");
-        let envs = load_environments(&level3).unwrap();
+        let envs = load_environments_with_global(&level3, None).unwrap();
         assert_eq!(envs.len(), 1);
 
         // Should NOT find file beyond MAX_PARENT_SEARCH_DEPTH
         let level5 = temp_dir.path().join("level0/level1/level2/level3/level4");
-        let envs = load_environments(&level5).unwrap();
+        let envs = load_environments_with_global(&level5, None).unwrap();
         assert_eq!(envs.len(), 0); // Empty, file not found
     }
 
@@ -404,7 +827,7 @@ mod tests {
 
         create_temp_env_file(temp_dir.path(), ".http-client-env.json", content);
 
-        let result = load_environments(temp_dir.path());
+        let result = load_environments_with_global(temp_dir.path(), None);
         assert!(matches!(result, Err(EnvError::ParseError(_))));
     }
 
@@ -415,7 +838,7 @@ mod tests {
 
         create_temp_env_file(temp_dir.path(), ".http-client-env.json", content);
 
-        let result = load_environments(temp_dir.path());
+        let result = load_environments_with_global(temp_dir.path(), None);
         assert!(matches!(result, Err(EnvError::InvalidFormat(_))));
     }
 
@@ -430,7 +853,7 @@ mod tests {
 
         create_temp_env_file(temp_dir.path(), ".http-client-env.json", content);
 
-        let result = load_environments(temp_dir.path());
+        let result = load_environments_with_global(temp_dir.path(), None);
         assert!(matches!(result, Err(EnvError::InvalidFormat(_))));
     }
 
@@ -446,7 +869,7 @@ mod tests {
 
         create_temp_env_file(temp_dir.path(), ".http-client-env.json", content);
 
-        let result = load_environments(temp_dir.path());
+        let result = load_environments_with_global(temp_dir.path(), None);
         assert!(matches!(result, Err(EnvError::InvalidFormat(_))));
     }
 
@@ -464,7 +887,7 @@ mod tests {
 
         create_temp_env_file(temp_dir.path(), ".http-client-env.json", content);
 
-        let envs = load_environments(temp_dir.path()).unwrap();
+        let envs = load_environments_with_global(temp_dir.path(), None).unwrap();
         let dev = envs.get_environment("dev").unwrap();
 
         assert_eq!(dev.get("stringVar").unwrap(), "hello");
@@ -507,7 +930,7 @@ mod tests {
 
         create_temp_env_file(temp_dir.path(), ".http-client-env.json", content);
 
-        let envs = load_environments(temp_dir.path()).unwrap();
+        let envs = load_environments_with_global(temp_dir.path(), None).unwrap();
 
         assert_eq!(envs.shared.len(), 1);
         assert_eq!(envs.shared.get("version").unwrap(), "v1");
@@ -526,7 +949,7 @@ mod tests {
 
         create_temp_env_file(temp_dir.path(), ".http-client-env.json", content);
 
-        let envs = load_environments(temp_dir.path()).unwrap();
+        let envs = load_environments_with_global(temp_dir.path(), None).unwrap();
         let dev = envs.get_environment("dev").unwrap();
 
         // Variables are stored as-is with {{}} syntax
@@ -30,7 +30,10 @@ pub mod models;
 use std::sync::{Arc, RwLock};
 
 // Re-export public types for convenience
-pub use loader::{load_environments, EnvError};
+pub use loader::{
+    find_variable_definition, global_environment_file, init_environment_file, load_environments,
+    load_environments_with_global, EnvError, VariableLocation,
+};
 pub use models::{Environment, Environments};
 
 /// Session manager for environment variables
@@ -0,0 +1,207 @@
+//! Protection against requests to internal addresses (SSRF).
+//!
+//! In shared environments a request file, or a variable substituted into
+//! one, can point at loopback, link-local, or cloud metadata addresses such
+//! as `169.254.169.254`. When `blockInternalAddresses` is enabled, requests
+//! whose host is, or resolves to, one of these addresses are rejected before
+//! they are sent. Hosts in `internalAddressAllowlist` are always allowed, for
+//! intentional local testing.
+
+use super::RequestError;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, ToSocketAddrs};
+use url::{Host, Url};
+
+/// Host names that always refer to cloud metadata services.
+const METADATA_HOSTS: [&str; 2] = ["metadata.google.internal", "metadata.azure.internal"];
+
+/// AWS instance metadata service IPv6 address (`fd00:ec2::254`).
+const AWS_METADATA_IPV6: Ipv6Addr = Ipv6Addr::new(0xfd00, 0x0ec2, 0, 0, 0, 0, 0, 0x0254);
+
+/// Rejects URLs whose host is an internal address, unless allowlisted.
+///
+/// The host is checked as written, then resolved and every resolved address
+/// is checked too, so a name pointing at `127.0.0.1` is also blocked. If the
+/// host cannot be resolved (e.g., DNS is unavailable), only the host as
+/// written is checked.
+///
+/// # Arguments
+///
+/// * `url` - The URL about to be requested
+/// * `allowlist` - Host names or IP addresses that may be requested anyway
+///
+/// # Returns
+///
+/// `Ok(())` if the request may be sent, or `Err(RequestError::BlockedAddress)`
+/// if the host is an internal address.
+///
+/// # Examples
+///
+/// ```
+/// use rest_client::executor::address_guard::check_internal_address;
+///
+/// assert!(check_internal_address("http://169.254.169.254/latest/meta-data", &[]).is_err());
+/// assert!(check_internal_address("http://localhost:3000/api", &["localhost".to_string()]).is_ok());
+/// ```
+pub fn check_internal_address(url: &str, allowlist: &[String]) -> Result<(), RequestError> {
+    let parsed = Url::parse(url).map_err(|e| RequestError::InvalidUrl(e.to_string()))?;
+    let Some(host) = parsed.host() else {
+        return Ok(());
+    };
+
+    let host_str = host.to_string();
+    let bare_host = host_str.trim_start_matches('[').trim_end_matches(']');
+    if allowlist
+        .iter()
+        .any(|allowed| allowed.eq_ignore_ascii_case(bare_host) || allowed == &host_str)
+    {
+        return Ok(());
+    }
+
+    let blocked = |reason: &str| {
+        Err(RequestError::BlockedAddress(format!(
+            "{} is {}",
+            host_str, reason
+        )))
+    };
+
+    match host {
+        Host::Ipv4(ip) => {
+            if let Some(reason) = internal_address_reason(IpAddr::V4(ip)) {
+                return blocked(reason);
+            }
+        }
+        Host::Ipv6(ip) => {
+            if let Some(reason) = internal_address_reason(IpAddr::V6(ip)) {
+                return blocked(reason);
+            }
+        }
+        Host::Domain(domain) => {
+            if let Some(reason) = internal_host_reason(domain) {
+                return blocked(reason);
+            }
+
+            let port = parsed.port_or_known_default().unwrap_or(80);
+            if let Ok(addresses) = (domain, port).to_socket_addrs() {
+                for address in addresses {
+                    if let Some(reason) = internal_address_reason(address.ip()) {
+                        return blocked(&format!("resolved to {} ({})", address.ip(), reason));
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Describes why a host name is internal, or returns `None` if it isn't.
+fn internal_host_reason(domain: &str) -> Option<&'static str> {
+    let domain = domain.trim_end_matches('.').to_ascii_lowercase();
+
+    if domain == "localhost" || domain.ends_with(".localhost") {
+        Some("a loopback address")
+    } else if METADATA_HOSTS.contains(&domain.as_str()) {
+        Some("a cloud metadata address")
+    } else {
+        None
+    }
+}
+
+/// Describes why an IP address is internal, or returns `None` if it isn't.
+fn internal_address_reason(ip: IpAddr) -> Option<&'static str> {
+    match ip {
+        IpAddr::V4(ip) => internal_ipv4_reason(ip),
+        IpAddr::V6(ip) => {
+            if let Some(ipv4) = ip.to_ipv4_mapped() {
+                return internal_ipv4_reason(ipv4);
+            }
+
+            if ip == AWS_METADATA_IPV6 {
+                Some("a cloud metadata address")
+            } else if ip.is_loopback() {
+                Some("a loopback address")
+            } else if ip.is_unspecified() {
+                Some("an unspecified address")
+            } else if (ip.segments()[0] & 0xffc0) == 0xfe80 {
+                Some("a link-local address")
+            } else {
+                None
+            }
+        }
+    }
+}
+
+/// Describes why an IPv4 address is internal, or returns `None` if it isn't.
+fn internal_ipv4_reason(ip: Ipv4Addr) -> Option<&'static str> {
+    if ip == Ipv4Addr::new(169, 254, 169, 254) {
+        Some("a cloud metadata address")
+    } else if ip.is_loopback() {
+        Some("a loopback address")
+    } else if ip.is_link_local() {
+        Some("a link-local address")
+    } else if ip.is_unspecified() {
+        Some("an unspecified address")
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_blocked(url: &str) {
+        match check_internal_address(url, &[]) {
+            Err(RequestError::BlockedAddress(_)) => {}
+            other => panic!("Expected {} to be blocked, got {:?}", url, other),
+        }
+    }
+
+    #[test]
+    fn test_blocks_metadata_and_link_local() {
+        assert_blocked("http://169.254.169.254/latest/meta-data/");
+        assert_blocked("http://169.254.10.1/");
+        assert_blocked("http://metadata.google.internal/computeMetadata/v1/");
+        assert_blocked("http://[fd00:ec2::254]/latest/meta-data/");
+        assert_blocked("http://[fe80::1]/");
+    }
+
+    #[test]
+    fn test_blocks_localhost_variants() {
+        assert_blocked("http://localhost:8080/");
+        assert_blocked("http://LOCALHOST./");
+        assert_blocked("http://api.localhost/");
+        assert_blocked("http://127.0.0.1/");
+        assert_blocked("http://127.1.2.3/");
+        assert_blocked("http://0.0.0.0/");
+        assert_blocked("http://[::1]/");
+        assert_blocked("http://[::ffff:127.0.0.1]/");
+        // Alternative IPv4 notations are normalized by the URL parser
+        assert_blocked("http://2852039166/");
+        assert_blocked("http://0x7f.1/");
+    }
+
+    #[test]
+    fn test_allows_public_addresses() {
+        assert!(check_internal_address("https://93.184.216.34/", &[]).is_ok());
+        assert!(check_internal_address("https://[2606:4700::1111]/", &[]).is_ok());
+    }
+
+    #[test]
+    fn test_allowlist() {
+        let allowlist = vec!["localhost".to_string(), "::1".to_string()];
+
+        assert!(check_internal_address("http://LocalHost:3000/", &allowlist).is_ok());
+        assert!(check_internal_address("http://[::1]:3000/", &allowlist).is_ok());
+        assert!(check_internal_address("http://127.0.0.1:3000/", &allowlist).is_err());
+    }
+
+    #[test]
+    fn test_blocked_error_message() {
+        let err = check_internal_address("http://169.254.169.254/", &[]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Blocked request to internal address: 169.254.169.254 is a cloud metadata address. Add the host to internalAddressAllowlist to allow it"
+        );
+    }
+}
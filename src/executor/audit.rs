@@ -0,0 +1,359 @@
+//! Audit log of raw requests and responses.
+//!
+//! When `auditLog` is configured, every executed request is appended to that
+//! file together with its response (or the error it failed with), each part
+//! stamped with the time it happened. Unlike history, the log is plain text,
+//! never pruned, and records failed requests too. Sensitive headers are
+//! redacted using the same list as history, as are keys added with
+//! `# @apikey`, whether sent as a header or a query parameter.
+
+use crate::executor::error::RequestError;
+use crate::history::models::SENSITIVE_HEADERS;
+use crate::models::headers::Headers;
+use crate::models::request::HttpRequest;
+use crate::models::response::HttpResponse;
+use chrono::{DateTime, Duration, SecondsFormat, Utc};
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+use std::sync::Mutex;
+
+/// Replacement shown for the value of a sensitive header.
+const REDACTED: &str = "[REDACTED]";
+
+/// Serializes appends from requests sent at the same time, so entries from
+/// concurrent sends never interleave.
+static AUDIT_LOG_LOCK: Mutex<()> = Mutex::new(());
+
+/// Formats a request and its outcome as an audit log entry.
+///
+/// The entry has a `>>>` block with the request as sent and a `<<<` block
+/// with the response or error. The request is stamped with the time it was
+/// sent (`now` minus the response duration) and the response with `now`.
+///
+/// # Arguments
+///
+/// * `request` - The request as it was sent
+/// * `result` - The response received, or the error the request failed with
+/// * `now` - The time the request completed
+///
+/// # Returns
+///
+/// The entry text, ending with a blank line.
+pub fn format_audit_entry(
+    request: &HttpRequest,
+    result: &Result<HttpResponse, RequestError>,
+    now: DateTime<Utc>,
+) -> String {
+    let sent_at = match result {
+        Ok(response) => Duration::from_std(response.duration)
+            .ok()
+            .and_then(|elapsed| now.checked_sub_signed(elapsed))
+            .unwrap_or(now),
+        Err(_) => now,
+    };
+
+    let url = match &request.query_api_key {
+        Some((name, _)) => redact_query_param(&request.url, name),
+        None => request.url.clone(),
+    };
+
+    let mut entry = format!(">>> {}\n", timestamp(sent_at));
+    entry.push_str(&format!(
+        "{} {} {}\n",
+        request.method.as_str(),
+        url,
+        request.http_version.as_deref().unwrap_or("HTTP/1.1")
+    ));
+    push_headers(
+        &mut entry,
+        &request.headers,
+        request.api_key_header.as_deref(),
+    );
+    entry.push('\n');
+    if let Some(path) = &request.body_file {
+        entry.push_str(&format!("[body streamed from {}]\n", path.display()));
+    } else if request.multipart.is_some() {
+        entry.push_str("[multipart/form-data body]\n");
+    } else if let Some(body) = &request.body {
+        push_body(&mut entry, body);
+    }
+
+    match result {
+        Ok(response) => {
+            entry.push_str(&format!(
+                "<<< {} ({} ms)\n",
+                timestamp(now),
+                response.duration.as_millis()
+            ));
+            entry.push_str(&format!(
+                "{} {}\n",
+                response.status_code, response.status_text
+            ));
+            push_headers(&mut entry, &response.headers, None);
+            entry.push('\n');
+            match std::str::from_utf8(&response.body) {
+                Ok(body) => push_body(&mut entry, body),
+                Err(_) => {
+                    entry.push_str(&format!("[binary body, {} bytes]\n", response.body.len()))
+                }
+            }
+        }
+        Err(error) => {
+            entry.push_str(&format!("<<< {} (failed)\n", timestamp(now)));
+            entry.push_str(&format!("{}\n", error));
+        }
+    }
+
+    entry.push('\n');
+    entry
+}
+
+/// Appends a request and its outcome to the audit log.
+///
+/// The file is created if needed. Each entry is written with a single
+/// append while holding a process-wide lock, so entries from concurrent
+/// sends stay whole.
+///
+/// # Arguments
+///
+/// * `path` - Path of the audit log file
+/// * `request` - The request as it was sent
+/// * `result` - The response received, or the error the request failed with
+///
+/// # Returns
+///
+/// `Ok(())` once the entry is written, or the I/O error that prevented it.
+pub fn append_audit_entry(
+    path: &Path,
+    request: &HttpRequest,
+    result: &Result<HttpResponse, RequestError>,
+) -> std::io::Result<()> {
+    let entry = format_audit_entry(request, result, Utc::now());
+
+    let _guard = AUDIT_LOG_LOCK
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    file.write_all(entry.as_bytes())
+}
+
+/// Appends a request and its outcome to the audit log, reporting a failed
+/// write with the outcome.
+///
+/// A response gets the failure as a warning, which is shown with it. A
+/// failed request has no response to carry it, so it is printed to stderr,
+/// which ends up in Zed's log.
+///
+/// # Arguments
+///
+/// * `path` - Path of the audit log file
+/// * `request` - The request as it was sent
+/// * `result` - The response received, or the error the request failed with
+pub fn record_audit_entry(
+    path: &Path,
+    request: &HttpRequest,
+    result: &mut Result<HttpResponse, RequestError>,
+) {
+    if let Err(e) = append_audit_entry(path, request, result) {
+        let warning = format!("Failed to write audit log '{}': {}", path.display(), e);
+        match result {
+            Ok(response) => response.warnings.push(warning),
+            Err(_) => eprintln!("Warning: {}", warning),
+        }
+    }
+}
+
+/// Writes headers one per line, redacting sensitive values and the
+/// `# @apikey` header, if any.
+fn push_headers(entry: &mut String, headers: &Headers, api_key_header: Option<&str>) {
+    for (name, value) in headers.iter() {
+        let is_api_key = api_key_header.is_some_and(|key| name.eq_ignore_ascii_case(key));
+        let value = if is_sensitive(name) || is_api_key {
+            REDACTED
+        } else {
+            value.as_str()
+        };
+        entry.push_str(&format!("{}: {}\n", name, value));
+    }
+}
+
+/// Writes a body, making sure it ends with a newline.
+fn push_body(entry: &mut String, body: &str) {
+    if body.is_empty() {
+        return;
+    }
+    entry.push_str(body);
+    if !body.ends_with('\n') {
+        entry.push('\n');
+    }
+}
+
+/// Checks whether a header is in the sensitive list.
+fn is_sensitive(name: &str) -> bool {
+    SENSITIVE_HEADERS
+        .iter()
+        .any(|sensitive| name.eq_ignore_ascii_case(sensitive))
+}
+
+/// Replaces the value of every `name` query parameter in `url`.
+fn redact_query_param(url: &str, name: &str) -> String {
+    let Some((base, rest)) = url.split_once('?') else {
+        return url.to_string();
+    };
+    let (query, fragment) = match rest.split_once('#') {
+        Some((query, fragment)) => (query, Some(fragment)),
+        None => (rest, None),
+    };
+
+    let query = query
+        .split('&')
+        .map(|pair| {
+            // Compare the decoded name, since it was percent-encoded when added
+            let matches = url::form_urlencoded::parse(pair.as_bytes())
+                .next()
+                .is_some_and(|(key, _)| key == name);
+            match pair.split_once('=') {
+                Some((key, _)) if matches => format!("{}={}", key, REDACTED),
+                _ => pair.to_string(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("&");
+
+    match fragment {
+        Some(fragment) => format!("{}?{}#{}", base, query, fragment),
+        None => format!("{}?{}", base, query),
+    }
+}
+
+/// Formats a timestamp for the log (RFC 3339, millisecond precision).
+fn timestamp(time: DateTime<Utc>) -> String {
+    time.to_rfc3339_opts(SecondsFormat::Millis, true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::request::HttpMethod;
+    use chrono::TimeZone;
+
+    fn sample_request() -> HttpRequest {
+        let mut request = HttpRequest::new(
+            "audit-1".to_string(),
+            HttpMethod::POST,
+            "https://api.example.com/login".to_string(),
+        );
+        request.add_header("Content-Type".to_string(), "application/json".to_string());
+        request.add_header("Authorization".to_string(), "Bearer secret".to_string());
+        request.set_body(r#"{"user":"alice"}"#.to_string());
+        request
+    }
+
+    fn sample_response() -> HttpResponse {
+        let mut response = HttpResponse::new(200, "OK".to_string());
+        response.add_header("Set-Cookie".to_string(), "session=abc".to_string());
+        response.add_header("Content-Type".to_string(), "text/plain".to_string());
+        response.set_body(b"welcome".to_vec());
+        response.duration = std::time::Duration::from_millis(250);
+        response
+    }
+
+    #[test]
+    fn test_format_audit_entry() {
+        let now = Utc.with_ymd_and_hms(2024, 5, 1, 12, 0, 0).unwrap();
+        let entry = format_audit_entry(&sample_request(), &Ok(sample_response()), now);
+
+        assert_eq!(
+            entry,
+            ">>> 2024-05-01T11:59:59.750Z\n\
+             POST https://api.example.com/login HTTP/1.1\n\
+             Content-Type: application/json\n\
+             Authorization: [REDACTED]\n\
+             \n\
+             {\"user\":\"alice\"}\n\
+             <<< 2024-05-01T12:00:00.000Z (250 ms)\n\
+             200 OK\n\
+             Set-Cookie: [REDACTED]\n\
+             Content-Type: text/plain\n\
+             \n\
+             welcome\n\
+             \n"
+        );
+        assert!(!entry.contains("secret"));
+    }
+
+    #[test]
+    fn test_format_audit_entry_redacts_api_keys() {
+        let now = Utc.with_ymd_and_hms(2024, 5, 1, 12, 0, 0).unwrap();
+        let mut request = HttpRequest::new(
+            "audit-2".to_string(),
+            HttpMethod::GET,
+            "https://api.example.com/items?page=2&api_key=s3cret#top".to_string(),
+        );
+        request.query_api_key = Some(("api_key".to_string(), "{{KEY}}".to_string()));
+        request.add_header("X-API-Key".to_string(), "h3ader".to_string());
+        request.api_key_header = Some("x-api-key".to_string());
+
+        let entry = format_audit_entry(&request, &Err(RequestError::Timeout), now);
+
+        assert!(entry.contains(
+            "GET https://api.example.com/items?page=2&api_key=[REDACTED]#top HTTP/1.1\n"
+        ));
+        assert!(entry.contains("X-API-Key: [REDACTED]\n"));
+        assert!(!entry.contains("s3cret"));
+        assert!(!entry.contains("h3ader"));
+    }
+
+    #[test]
+    fn test_format_audit_entry_for_error() {
+        let now = Utc.with_ymd_and_hms(2024, 5, 1, 12, 0, 0).unwrap();
+        let entry = format_audit_entry(&sample_request(), &Err(RequestError::Timeout), now);
+
+        assert!(entry.starts_with(">>> 2024-05-01T12:00:00.000Z\n"));
+        assert!(entry.contains("<<< 2024-05-01T12:00:00.000Z (failed)\n"));
+        assert!(entry.contains(&format!("{}\n", RequestError::Timeout)));
+    }
+
+    #[test]
+    fn test_record_audit_entry_warns_on_failed_write() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("missing").join("audit.log");
+
+        let mut result = Ok(sample_response());
+        record_audit_entry(&path, &sample_request(), &mut result);
+
+        let warnings = result.unwrap().warnings;
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].starts_with("Failed to write audit log"));
+    }
+
+    #[test]
+    fn test_concurrent_appends_keep_entries_whole() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("audit.log");
+
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let path = path.clone();
+                std::thread::spawn(move || {
+                    for _ in 0..10 {
+                        append_audit_entry(&path, &sample_request(), &Ok(sample_response()))
+                            .unwrap();
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        let log = std::fs::read_to_string(&path).unwrap();
+        let entries: Vec<&str> = log.split("\n>>> ").collect();
+        assert_eq!(entries.len(), 80);
+        for entry in entries {
+            assert!(entry.contains("POST https://api.example.com/login HTTP/1.1\n"));
+            assert!(entry.trim_end().ends_with("welcome"));
+        }
+    }
+}
@@ -0,0 +1,475 @@
+//! Batch runner for sending every request in a file.
+//!
+//! Requests are sent concurrently, up to a concurrency limit, and every
+//! outcome is collected so one failing request doesn't stop the rest. The
+//! WASM extension has no threads, so there they are sent one at a time; the
+//! language server sends them with the native client through
+//! [`run_batch_native`]. The results keep the order of the requests in the
+//! file and are summarized as a table of method, URL, status, and duration.
+
+use super::{parse_http_url, ErrorCategory, RequestError};
+use crate::models::request::{HttpMethod, HttpRequest};
+use crate::models::response::HttpResponse;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+/// The outcome of one request in a batch.
+#[derive(Debug)]
+pub struct BatchResult {
+    /// The request's HTTP method
+    pub method: HttpMethod,
+
+    /// The request's URL
+    pub url: String,
+
+    /// The response, or the error that prevented one
+    pub result: Result<HttpResponse, RequestError>,
+}
+
+impl BatchResult {
+    /// Returns `true` if the request got a response with a non-error status.
+    pub fn is_success(&self) -> bool {
+        matches!(&self.result, Ok(response) if response.status_code < 400)
+    }
+}
+
+/// Sends every request, at most `concurrency` at a time.
+///
+/// Once a request comes back cancelled, requests that haven't started yet
+/// are not sent and are reported as cancelled too, so cancelling any running
+/// request stops the whole batch. On `wasm32` targets, which have no threads,
+/// `concurrency` is ignored and the requests are sent one after another.
+///
+/// # Arguments
+///
+/// * `requests` - The requests to send
+/// * `concurrency` - Maximum number of requests in flight (at least 1 is used)
+/// * `execute` - Sends a single request
+///
+/// # Returns
+///
+/// One result per request, in the same order as `requests`.
+pub fn run_batch<F>(requests: &[HttpRequest], concurrency: usize, execute: F) -> Vec<BatchResult>
+where
+    F: Fn(&HttpRequest) -> Result<HttpResponse, RequestError> + Sync,
+{
+    let next = AtomicUsize::new(0);
+    let cancelled = AtomicBool::new(false);
+    let results: Mutex<Vec<Option<Result<HttpResponse, RequestError>>>> =
+        Mutex::new(requests.iter().map(|_| None).collect());
+
+    let worker = || loop {
+        let index = next.fetch_add(1, Ordering::SeqCst);
+        let Some(request) = requests.get(index) else {
+            break;
+        };
+
+        let result = if cancelled.load(Ordering::SeqCst) {
+            Err(RequestError::Cancelled)
+        } else {
+            execute(request)
+        };
+        if matches!(&result, Err(e) if is_cancelled(e)) {
+            cancelled.store(true, Ordering::SeqCst);
+        }
+
+        results.lock().unwrap()[index] = Some(result);
+    };
+
+    // wasm32-wasip1 can't spawn threads
+    #[cfg(target_arch = "wasm32")]
+    {
+        let _ = concurrency;
+        worker();
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    std::thread::scope(|scope| {
+        // The current thread works too, so a failed spawn only lowers the
+        // concurrency instead of leaving requests unsent
+        let workers = concurrency.max(1).min(requests.len());
+        for _ in 1..workers {
+            if std::thread::Builder::new()
+                .spawn_scoped(scope, worker)
+                .is_err()
+            {
+                break;
+            }
+        }
+        worker();
+    });
+
+    requests
+        .iter()
+        .zip(results.into_inner().unwrap())
+        .map(|(request, result)| BatchResult {
+            method: request.method,
+            url: request.url.clone(),
+            result: result.unwrap_or(Err(RequestError::Cancelled)),
+        })
+        .collect()
+}
+
+/// Sends every request with the native HTTP client, at most `concurrency` at
+/// a time.
+///
+/// Dropping the returned future cancels the batch: requests in flight are
+/// aborted and requests that haven't started yet are never sent.
+///
+/// # Arguments
+///
+/// * `requests` - The requests to send
+/// * `concurrency` - Maximum number of requests in flight (at least 1 is used)
+///
+/// # Returns
+///
+/// One result per request, in the same order as `requests`.
+#[cfg(feature = "lsp")]
+pub async fn run_batch_native(requests: Vec<HttpRequest>, concurrency: usize) -> Vec<BatchResult> {
+    use std::sync::Arc;
+    use tokio::sync::Semaphore;
+    use tokio::task::JoinSet;
+
+    let permits = Arc::new(Semaphore::new(concurrency.max(1)));
+    let mut results: Vec<Option<Result<HttpResponse, RequestError>>> =
+        requests.iter().map(|_| None).collect();
+
+    // The set aborts its tasks when dropped, which is what cancels the batch
+    let mut tasks = JoinSet::new();
+    for (index, request) in requests.iter().cloned().enumerate() {
+        let permits = Arc::clone(&permits);
+        tasks.spawn(async move {
+            let _permit = permits.acquire_owned().await;
+            (index, super::native::execute_request_native(&request).await)
+        });
+    }
+    while let Some(joined) = tasks.join_next().await {
+        if let Ok((index, result)) = joined {
+            results[index] = Some(result);
+        }
+    }
+
+    requests
+        .into_iter()
+        .zip(results)
+        .map(|(request, result)| BatchResult {
+            method: request.method,
+            url: request.url,
+            result: result.unwrap_or(Err(RequestError::Cancelled)),
+        })
+        .collect()
+}
+
+/// Returns `true` if `error` means the request was cancelled.
+fn is_cancelled(error: &RequestError) -> bool {
+    error.category() == ErrorCategory::Cancelled
+}
+
+/// Formats batch results as a summary grouped by host.
+///
+/// Results are grouped by their URL's host (and port, when not the default),
+/// in the order each host first appears. Every group shows its request
+/// count, successes, and average duration, followed by a table of its
+/// requests. Columns are aligned across all groups.
+///
+/// # Arguments
+///
+/// * `results` - The results of `run_batch`
+///
+/// # Returns
+///
+/// A summary line followed by one table per host, such as:
+///
+/// ```text
+/// Ran 3 requests: 2 succeeded, 1 failed
+///
+/// api.example.com: 2 requests, 2 succeeded, avg 100ms
+/// Method  URL                              Status         Duration
+/// GET     https://api.example.com/users    200 OK         120ms
+/// GET     https://api.example.com/users/1  200 OK         80ms
+///
+/// auth.example.com: 1 request, 0 succeeded, avg -
+/// Method  URL                              Status         Duration
+/// POST    https://auth.example.com/login   Network error  -
+/// ```
+pub fn format_batch_summary(results: &[BatchResult]) -> String {
+    let succeeded = results.iter().filter(|result| result.is_success()).count();
+    let mut output = format!(
+        "Ran {}: {} succeeded, {} failed\n",
+        pluralize_requests(results.len()),
+        succeeded,
+        results.len() - succeeded
+    );
+
+    let rows: Vec<[String; 4]> = results
+        .iter()
+        .map(|result| {
+            let (status, duration) = match &result.result {
+                Ok(response) => (
+                    format!("{} {}", response.status_code, response.status_text),
+                    format_duration(response.duration),
+                ),
+                Err(e) => (format!("Error: {}", e), "-".to_string()),
+            };
+            [
+                result.method.as_str().to_string(),
+                result.url.clone(),
+                status,
+                duration,
+            ]
+        })
+        .collect();
+
+    let header = ["Method", "URL", "Status", "Duration"].map(String::from);
+    let mut widths = header.clone().map(|cell| cell.chars().count());
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+    let format_row = |row: &[String; 4]| {
+        let line = row
+            .iter()
+            .zip(widths)
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect::<Vec<_>>()
+            .join("  ");
+        format!("{}\n", line.trim_end())
+    };
+
+    for (host, indices) in group_by_host(results) {
+        let group_succeeded = indices
+            .iter()
+            .filter(|&&index| results[index].is_success())
+            .count();
+        let durations: Vec<std::time::Duration> = indices
+            .iter()
+            .filter_map(|&index| results[index].result.as_ref().ok())
+            .map(|response| response.duration)
+            .collect();
+        let average = if durations.is_empty() {
+            "-".to_string()
+        } else {
+            format_duration(durations.iter().sum::<std::time::Duration>() / durations.len() as u32)
+        };
+
+        output.push_str(&format!(
+            "\n{}: {}, {} succeeded, avg {}\n",
+            host,
+            pluralize_requests(indices.len()),
+            group_succeeded,
+            average
+        ));
+        output.push_str(&format_row(&header));
+        for index in indices {
+            output.push_str(&format_row(&rows[index]));
+        }
+    }
+
+    output
+}
+
+/// Groups result indices by request host, in order of first appearance.
+fn group_by_host(results: &[BatchResult]) -> Vec<(String, Vec<usize>)> {
+    let mut groups: Vec<(String, Vec<usize>)> = Vec::new();
+    for (index, result) in results.iter().enumerate() {
+        let host = request_host(&result.url);
+        match groups.iter_mut().find(|(existing, _)| *existing == host) {
+            Some((_, indices)) => indices.push(index),
+            None => groups.push((host, vec![index])),
+        }
+    }
+    groups
+}
+
+/// Returns the host of `url`, with the port if it isn't the scheme's default.
+///
+/// URLs that don't parse are grouped under "(invalid URL)".
+fn request_host(url: &str) -> String {
+    parse_http_url(url)
+        .ok()
+        .and_then(|parsed| {
+            let host = parsed.host_str()?.to_string();
+            Some(match parsed.port() {
+                Some(port) => format!("{}:{}", host, port),
+                None => host,
+            })
+        })
+        .unwrap_or_else(|| "(invalid URL)".to_string())
+}
+
+/// Formats a request count like "1 request" or "3 requests".
+fn pluralize_requests(count: usize) -> String {
+    format!("{} request{}", count, if count == 1 { "" } else { "s" })
+}
+
+/// Formats a duration like "567ms" or "1.234s".
+fn format_duration(duration: std::time::Duration) -> String {
+    if duration.as_millis() < 1000 {
+        format!("{}ms", duration.as_millis())
+    } else {
+        format!("{:.3}s", duration.as_secs_f64())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn request(method: HttpMethod, url: &str) -> HttpRequest {
+        HttpRequest::new("test".to_string(), method, url.to_string())
+    }
+
+    fn respond(request: &HttpRequest) -> Result<HttpResponse, RequestError> {
+        if request.url.contains("down") {
+            return Err(RequestError::NetworkError("connection refused".to_string()));
+        }
+        let mut response = HttpResponse::new(200, "OK".to_string());
+        response.duration = Duration::from_millis(120);
+        Ok(response)
+    }
+
+    #[test]
+    fn test_run_batch_keeps_order_and_collects_failures() {
+        let requests = vec![
+            request(HttpMethod::GET, "https://api.example.com/users"),
+            request(HttpMethod::POST, "https://down.example.com/login"),
+            request(HttpMethod::DELETE, "https://api.example.com/users/1"),
+        ];
+
+        let results = run_batch(&requests, 2, respond);
+
+        let urls: Vec<&str> = results.iter().map(|result| result.url.as_str()).collect();
+        assert_eq!(
+            urls,
+            vec![
+                "https://api.example.com/users",
+                "https://down.example.com/login",
+                "https://api.example.com/users/1",
+            ]
+        );
+        assert!(results[0].is_success());
+        assert!(!results[1].is_success());
+        assert!(results[2].is_success());
+    }
+
+    #[test]
+    fn test_run_batch_respects_concurrency_limit() {
+        let requests: Vec<HttpRequest> = (0..8)
+            .map(|i| request(HttpMethod::GET, &format!("https://api.example.com/{}", i)))
+            .collect();
+        let in_flight = AtomicUsize::new(0);
+        let peak = AtomicUsize::new(0);
+
+        let results = run_batch(&requests, 3, |request| {
+            let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            peak.fetch_max(now, Ordering::SeqCst);
+            std::thread::sleep(Duration::from_millis(20));
+            in_flight.fetch_sub(1, Ordering::SeqCst);
+            respond(request)
+        });
+
+        assert_eq!(results.len(), 8);
+        assert!(peak.load(Ordering::SeqCst) <= 3);
+        assert!(peak.load(Ordering::SeqCst) > 1);
+    }
+
+    #[test]
+    fn test_run_batch_stops_after_cancellation() {
+        let requests: Vec<HttpRequest> = (0..4)
+            .map(|i| request(HttpMethod::GET, &format!("https://api.example.com/{}", i)))
+            .collect();
+        let sent = AtomicUsize::new(0);
+
+        let results = run_batch(&requests, 1, |_| {
+            sent.fetch_add(1, Ordering::SeqCst);
+            Err(RequestError::Cancelled)
+        });
+
+        assert_eq!(sent.load(Ordering::SeqCst), 1);
+        assert!(results
+            .iter()
+            .all(|result| matches!(&result.result, Err(e) if is_cancelled(e))));
+    }
+
+    #[cfg(feature = "lsp")]
+    #[tokio::test]
+    async fn test_run_batch_native_respects_concurrency_limit() {
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(wiremock::matchers::path("/slow"))
+            .respond_with(ResponseTemplate::new(200).set_delay(Duration::from_millis(200)))
+            .mount(&server)
+            .await;
+        let mut requests: Vec<HttpRequest> = (0..4)
+            .map(|_| request(HttpMethod::GET, &format!("{}/slow", server.uri())))
+            .collect();
+        requests.push(request(HttpMethod::GET, "not a url"));
+
+        // Four slow requests two at a time take at least two delays
+        let started = std::time::Instant::now();
+        let results = run_batch_native(requests, 2).await;
+
+        assert!(started.elapsed() >= Duration::from_millis(400));
+        assert_eq!(results.len(), 5);
+        assert!(results[..4].iter().all(BatchResult::is_success));
+        assert_eq!(results[4].url, "not a url");
+        assert!(results[4].result.is_err());
+    }
+
+    #[test]
+    fn test_format_batch_summary() {
+        let requests = vec![
+            request(HttpMethod::GET, "https://api.example.com/users"),
+            request(HttpMethod::POST, "https://down.example.com/login"),
+        ];
+        let results = run_batch(&requests, 4, respond);
+
+        assert_eq!(
+            format_batch_summary(&results),
+            "Ran 2 requests: 1 succeeded, 1 failed\n\
+             \n\
+             api.example.com: 1 request, 1 succeeded, avg 120ms\n\
+             Method  URL                             Status                                    Duration\n\
+             GET     https://api.example.com/users   200 OK                                    120ms\n\
+             \n\
+             down.example.com: 1 request, 0 succeeded, avg -\n\
+             Method  URL                             Status                                    Duration\n\
+             POST    https://down.example.com/login  Error: Network error: connection refused  -\n"
+        );
+    }
+
+    #[test]
+    fn test_format_batch_summary_groups_by_host() {
+        let requests = vec![
+            request(HttpMethod::GET, "https://api.example.com/users"),
+            request(HttpMethod::GET, "http://localhost:3000/health"),
+            request(HttpMethod::DELETE, "https://api.example.com:443/users/1"),
+            request(HttpMethod::GET, "{{baseUrl}}/users"),
+        ];
+        let results = run_batch(&requests, 1, |request| {
+            let mut response = respond(request)?;
+            if request.method == HttpMethod::DELETE {
+                response.duration = Duration::from_millis(80);
+            }
+            Ok(response)
+        });
+
+        let summary = format_batch_summary(&results);
+        let group_lines: Vec<&str> = summary
+            .lines()
+            .filter(|line| line.contains(" succeeded, avg "))
+            .collect();
+
+        assert_eq!(
+            group_lines,
+            vec![
+                "api.example.com: 2 requests, 2 succeeded, avg 100ms",
+                "localhost:3000: 1 request, 1 succeeded, avg 120ms",
+                "(invalid URL): 1 request, 1 succeeded, avg 120ms",
+            ]
+        );
+    }
+}
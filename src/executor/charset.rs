@@ -0,0 +1,186 @@
+//! Request body charset conversion.
+//!
+//! Request bodies are authored as UTF-8. A `# @encoding <charset>` directive
+//! converts the body to another charset (e.g., `iso-8859-1` for legacy
+//! services) before it is sent, and declares that charset on the
+//! `Content-Type` header.
+//!
+//! # Examples
+//!
+//! ```http
+//! # @encoding iso-8859-1
+//! POST https://legacy.example.com/orders
+//! Content-Type: text/plain
+//!
+//! Crème brûlée
+//! ```
+
+use super::RequestError;
+use crate::models::headers::Headers;
+use encoding_rs::Encoding;
+
+/// Encodes a UTF-8 body in the given charset.
+///
+/// # Arguments
+///
+/// * `body` - The request body
+/// * `charset` - A charset label such as "iso-8859-1" or "shift_jis"
+///
+/// # Returns
+///
+/// `Ok(Vec<u8>)` with the encoded body, or `Err(RequestError::BodyEncodingError)`
+/// if the charset is unknown, cannot be encoded to, or cannot represent
+/// every character of the body.
+///
+/// # Examples
+///
+/// ```
+/// use rest_client::executor::charset::encode_body;
+///
+/// assert_eq!(encode_body("café", "iso-8859-1").unwrap(), b"caf\xe9");
+/// assert!(encode_body("日本", "iso-8859-1").is_err());
+/// ```
+pub fn encode_body(body: &str, charset: &str) -> Result<Vec<u8>, RequestError> {
+    let encoding = Encoding::for_label(charset.as_bytes())
+        .ok_or_else(|| RequestError::BodyEncodingError(format!("Unknown charset '{}'", charset)))?;
+
+    // UTF-16 and the replacement encoding can only be decoded, not encoded
+    if encoding.output_encoding() != encoding {
+        return Err(RequestError::BodyEncodingError(format!(
+            "Encoding request bodies as '{}' is not supported",
+            charset
+        )));
+    }
+
+    if let Some(c) = body.chars().find(|c| !is_representable(*c, encoding)) {
+        return Err(RequestError::BodyEncodingError(format!(
+            "Character '{}' (U+{:04X}) cannot be encoded as '{}'",
+            c, c as u32, charset
+        )));
+    }
+
+    let (bytes, _, _) = encoding.encode(body);
+    Ok(bytes.into_owned())
+}
+
+/// Returns `true` if `c` can be encoded in `encoding` without loss.
+fn is_representable(c: char, encoding: &'static Encoding) -> bool {
+    let mut buffer = [0u8; 4];
+    let (_, _, had_errors) = encoding.encode(c.encode_utf8(&mut buffer));
+    !had_errors
+}
+
+/// Sets the `charset` parameter of the `Content-Type` header.
+///
+/// Any existing `charset` parameter is replaced. Requests without a
+/// `Content-Type` header are left unchanged.
+///
+/// # Arguments
+///
+/// * `headers` - The request headers
+/// * `charset` - The charset the body is encoded in
+pub fn set_content_type_charset(headers: &mut Headers, charset: &str) {
+    let Some(content_type) = headers.get("Content-Type") else {
+        return;
+    };
+
+    let mut parts: Vec<&str> = content_type
+        .split(';')
+        .map(str::trim)
+        .filter(|part| {
+            !part
+                .split('=')
+                .next()
+                .is_some_and(|name| name.trim().eq_ignore_ascii_case("charset"))
+        })
+        .collect();
+    let charset_param = format!("charset={}", charset);
+    parts.push(&charset_param);
+
+    let value = parts.join("; ");
+    headers.insert("Content-Type".to_string(), value);
+}
+
+/// Converts a request body for sending, honoring its `# @encoding` charset.
+///
+/// Without a charset the body is sent as UTF-8. With one, the body is encoded
+/// in that charset and the charset is declared on `Content-Type`.
+///
+/// # Arguments
+///
+/// * `body` - The request body
+/// * `charset` - The charset from `# @encoding`, if any
+/// * `headers` - The request headers, updated with the charset
+///
+/// # Returns
+///
+/// `Ok(Vec<u8>)` with the bytes to send, or `Err(RequestError)` if the body
+/// cannot be encoded.
+pub fn encode_request_body(
+    body: &str,
+    charset: Option<&str>,
+    headers: &mut Headers,
+) -> Result<Vec<u8>, RequestError> {
+    let Some(charset) = charset else {
+        return Ok(body.as_bytes().to_vec());
+    };
+
+    let bytes = encode_body(body, charset)?;
+    set_content_type_charset(headers, charset);
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_body_accented_characters() {
+        let bytes = encode_body("Crème brûlée à côté", "iso-8859-1").unwrap();
+        assert_eq!(bytes, b"Cr\xe8me br\xfbl\xe9e \xe0 c\xf4t\xe9");
+        assert_eq!(bytes.len(), "Crème brûlée à côté".chars().count());
+    }
+
+    #[test]
+    fn test_encode_body_unrepresentable_character() {
+        match encode_body("price: 10 ☃", "iso-8859-1") {
+            Err(RequestError::BodyEncodingError(msg)) => {
+                assert!(msg.contains("U+2603"), "{}", msg);
+            }
+            other => panic!("Expected BodyEncodingError, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_encode_body_unknown_or_decode_only_charset() {
+        assert!(encode_body("abc", "not-a-charset").is_err());
+        assert!(encode_body("abc", "utf-16le").is_err());
+    }
+
+    #[test]
+    fn test_encode_request_body_sets_charset() {
+        let mut headers = Headers::new();
+        headers.append(
+            "content-type".to_string(),
+            "text/plain; charset=utf-8; format=flowed".to_string(),
+        );
+
+        let bytes = encode_request_body("é", Some("iso-8859-1"), &mut headers).unwrap();
+
+        assert_eq!(bytes, vec![0xe9]);
+        assert_eq!(
+            headers["Content-Type"],
+            "text/plain; format=flowed; charset=iso-8859-1"
+        );
+    }
+
+    #[test]
+    fn test_encode_request_body_without_charset() {
+        let mut headers = Headers::new();
+
+        let bytes = encode_request_body("é", None, &mut headers).unwrap();
+
+        assert_eq!(bytes, "é".as_bytes());
+        assert!(headers.is_empty());
+    }
+}
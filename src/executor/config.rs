@@ -4,7 +4,9 @@
 //! including timeout settings and other execution parameters.
 
 use crate::config::get_config;
+use crate::models::request::HttpRequest;
 use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
 
 /// Configuration for HTTP request execution.
 ///
@@ -17,6 +19,93 @@ pub struct ExecutionConfig {
     /// Maximum time to wait for a complete response (including connection,
     /// headers, and body download). Defaults to 30 seconds.
     pub timeout_secs: u64,
+
+    /// Maximum URL length in characters.
+    ///
+    /// Requests with longer URLs are rejected before sending.
+    /// Defaults to 8192.
+    #[serde(default = "default_max_url_length")]
+    pub max_url_length: usize,
+
+    /// Whether to percent-encode unsafe characters in the URL before sending.
+    ///
+    /// Defaults to true.
+    #[serde(default = "default_encode_url")]
+    pub encode_url: bool,
+
+    /// Whether to decompress gzip, deflate, and brotli response bodies.
+    ///
+    /// Defaults to true.
+    #[serde(default = "default_decompress_responses")]
+    pub decompress_responses: bool,
+
+    /// `Accept-Language` value added to requests that don't set one.
+    #[serde(default)]
+    pub default_accept_language: Option<String>,
+
+    /// Whether to reject requests to loopback, link-local, and cloud
+    /// metadata addresses.
+    ///
+    /// Defaults to false.
+    #[serde(default)]
+    pub block_internal_addresses: bool,
+
+    /// Hosts allowed even when internal addresses are blocked.
+    #[serde(default)]
+    pub internal_address_allowlist: Vec<String>,
+
+    /// Number of times to retry a request that fails with a network error.
+    ///
+    /// Defaults to 0 (no retries).
+    #[serde(default)]
+    pub max_retries: u32,
+
+    /// Delay before the first retry in milliseconds.
+    ///
+    /// The delay doubles after each attempt. Defaults to 500.
+    #[serde(default = "default_retry_backoff_ms")]
+    pub retry_backoff_ms: u64,
+
+    /// Longest `Retry-After` wait honored before retrying, in milliseconds.
+    ///
+    /// Defaults to 60000.
+    #[serde(default = "default_max_retry_after_ms")]
+    pub max_retry_after_ms: u64,
+
+    /// File that sent requests and their responses are appended to.
+    ///
+    /// Defaults to none (no audit log).
+    #[serde(default)]
+    pub audit_log: Option<PathBuf>,
+}
+
+/// Default maximum URL length, matching `RestClientConfig`.
+const DEFAULT_MAX_URL_LENGTH: usize = 8192;
+
+fn default_max_url_length() -> usize {
+    DEFAULT_MAX_URL_LENGTH
+}
+
+fn default_encode_url() -> bool {
+    true
+}
+
+fn default_decompress_responses() -> bool {
+    true
+}
+
+/// Default base retry delay, matching `RestClientConfig`.
+const DEFAULT_RETRY_BACKOFF_MS: u64 = 500;
+
+fn default_retry_backoff_ms() -> u64 {
+    DEFAULT_RETRY_BACKOFF_MS
+}
+
+/// Default `Retry-After` cap, matching `RestClientConfig`.
+const DEFAULT_MAX_RETRY_AFTER_MS: u64 = 60000;
+
+fn default_max_retry_after_ms() -> u64 {
+    DEFAULT_MAX_RETRY_AFTER_MS
 }
 
 impl ExecutionConfig {
@@ -30,7 +119,19 @@ impl ExecutionConfig {
     ///
     /// A new `ExecutionConfig` instance.
     pub fn new(timeout_secs: u64) -> Self {
-        Self { timeout_secs }
+        Self {
+            timeout_secs,
+            max_url_length: DEFAULT_MAX_URL_LENGTH,
+            encode_url: default_encode_url(),
+            decompress_responses: default_decompress_responses(),
+            default_accept_language: None,
+            block_internal_addresses: false,
+            internal_address_allowlist: Vec::new(),
+            max_retries: 0,
+            retry_backoff_ms: DEFAULT_RETRY_BACKOFF_MS,
+            max_retry_after_ms: DEFAULT_MAX_RETRY_AFTER_MS,
+            audit_log: None,
+        }
     }
 
     /// Returns the timeout as a `std::time::Duration`.
@@ -41,6 +142,100 @@ impl ExecutionConfig {
     pub fn timeout_duration(&self) -> std::time::Duration {
         std::time::Duration::from_secs(self.timeout_secs)
     }
+
+    /// Returns the timeout to use for a request.
+    ///
+    /// A `# @timeout` directive on the request (in milliseconds) takes
+    /// precedence over the configured timeout.
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - The request about to be executed
+    ///
+    /// # Returns
+    ///
+    /// Duration representing the effective timeout.
+    pub fn timeout_for(&self, request: &HttpRequest) -> std::time::Duration {
+        request
+            .timeout_override
+            .map(std::time::Duration::from_millis)
+            .unwrap_or_else(|| self.timeout_duration())
+    }
+
+    /// Returns the number of retries to allow for a request.
+    ///
+    /// A `# @retries` directive on the request takes precedence over the
+    /// configured `max_retries`.
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - The request about to be executed
+    pub fn retries_for(&self, request: &HttpRequest) -> u32 {
+        request.retries_override.unwrap_or(self.max_retries)
+    }
+
+    /// Returns how long to wait before retrying after a failed attempt.
+    ///
+    /// The delay is `retry_backoff_ms * 2^attempt`, saturating on overflow.
+    ///
+    /// # Arguments
+    ///
+    /// * `attempt` - The zero-based index of the attempt that failed
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rest_client::executor::ExecutionConfig;
+    /// use std::time::Duration;
+    ///
+    /// let mut config = ExecutionConfig::new(30);
+    /// config.retry_backoff_ms = 200;
+    /// assert_eq!(config.retry_backoff(0), Duration::from_millis(200));
+    /// assert_eq!(config.retry_backoff(2), Duration::from_millis(800));
+    /// ```
+    pub fn retry_backoff(&self, attempt: u32) -> std::time::Duration {
+        let factor = 2u64.saturating_pow(attempt);
+        std::time::Duration::from_millis(self.retry_backoff_ms.saturating_mul(factor))
+    }
+
+    /// Returns how long to wait before retrying, honoring `Retry-After`.
+    ///
+    /// A wait requested by the server is used as-is, capped at
+    /// `max_retry_after_ms`; otherwise the backoff delay applies.
+    ///
+    /// # Arguments
+    ///
+    /// * `attempt` - The zero-based index of the attempt that failed
+    /// * `retry_after` - The wait requested by the response's `Retry-After`
+    ///   header, if any
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rest_client::executor::ExecutionConfig;
+    /// use std::time::Duration;
+    ///
+    /// let mut config = ExecutionConfig::new(30);
+    /// config.max_retry_after_ms = 10_000;
+    /// assert_eq!(
+    ///     config.retry_delay(0, Some(Duration::from_secs(3))),
+    ///     Duration::from_secs(3)
+    /// );
+    /// assert_eq!(
+    ///     config.retry_delay(0, Some(Duration::from_secs(120))),
+    ///     Duration::from_secs(10)
+    /// );
+    /// ```
+    pub fn retry_delay(
+        &self,
+        attempt: u32,
+        retry_after: Option<std::time::Duration>,
+    ) -> std::time::Duration {
+        match retry_after {
+            Some(wait) => wait.min(std::time::Duration::from_millis(self.max_retry_after_ms)),
+            None => self.retry_backoff(attempt),
+        }
+    }
 }
 
 impl Default for ExecutionConfig {
@@ -51,6 +246,16 @@ impl Default for ExecutionConfig {
         let global_config = get_config();
         Self {
             timeout_secs: global_config.timeout_secs(),
+            max_url_length: global_config.max_url_length,
+            encode_url: global_config.encode_url,
+            decompress_responses: global_config.decompress_responses,
+            default_accept_language: global_config.default_accept_language,
+            block_internal_addresses: global_config.block_internal_addresses,
+            internal_address_allowlist: global_config.internal_address_allowlist,
+            max_retries: global_config.max_retries,
+            retry_backoff_ms: global_config.retry_backoff_ms,
+            max_retry_after_ms: global_config.max_retry_after_ms,
+            audit_log: global_config.audit_log,
         }
     }
 }
@@ -65,6 +270,16 @@ impl ExecutionConfig {
         let global_config = get_config();
         Self {
             timeout_secs: global_config.timeout_secs(),
+            max_url_length: global_config.max_url_length,
+            encode_url: global_config.encode_url,
+            decompress_responses: global_config.decompress_responses,
+            default_accept_language: global_config.default_accept_language,
+            block_internal_addresses: global_config.block_internal_addresses,
+            internal_address_allowlist: global_config.internal_address_allowlist,
+            max_retries: global_config.max_retries,
+            retry_backoff_ms: global_config.retry_backoff_ms,
+            max_retry_after_ms: global_config.max_retry_after_ms,
+            audit_log: global_config.audit_log,
         }
     }
 }
@@ -83,6 +298,8 @@ mod tests {
     fn test_execution_config_default() {
         let config = ExecutionConfig::default();
         assert_eq!(config.timeout_secs, 30);
+        assert_eq!(config.max_url_length, 8192);
+        assert!(config.encode_url);
     }
 
     #[test]
@@ -94,6 +311,91 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_timeout_for_prefers_request_override() {
+        use crate::models::request::HttpMethod;
+
+        let config = ExecutionConfig::new(30);
+        let mut request = HttpRequest::new(
+            "test".to_string(),
+            HttpMethod::GET,
+            "https://api.example.com/slow".to_string(),
+        );
+        assert_eq!(
+            config.timeout_for(&request),
+            std::time::Duration::from_secs(30)
+        );
+
+        request.timeout_override = Some(60000);
+        assert_eq!(
+            config.timeout_for(&request),
+            std::time::Duration::from_millis(60000)
+        );
+    }
+
+    #[test]
+    fn test_retry_backoff_doubles() {
+        let mut config = ExecutionConfig::new(30);
+        config.retry_backoff_ms = 100;
+
+        let delays: Vec<u128> = (0..4)
+            .map(|attempt| config.retry_backoff(attempt).as_millis())
+            .collect();
+        assert_eq!(delays, vec![100, 200, 400, 800]);
+    }
+
+    #[test]
+    fn test_retry_backoff_saturates() {
+        let mut config = ExecutionConfig::new(30);
+        config.retry_backoff_ms = u64::MAX / 2;
+
+        assert_eq!(
+            config.retry_backoff(64),
+            std::time::Duration::from_millis(u64::MAX)
+        );
+
+        config.retry_backoff_ms = 0;
+        assert_eq!(config.retry_backoff(5), std::time::Duration::ZERO);
+    }
+
+    #[test]
+    fn test_retry_delay_honors_capped_retry_after() {
+        let mut config = ExecutionConfig::new(30);
+        config.retry_backoff_ms = 100;
+        config.max_retry_after_ms = 5000;
+
+        // Without Retry-After the backoff applies
+        assert_eq!(
+            config.retry_delay(1, None),
+            std::time::Duration::from_millis(200)
+        );
+        assert_eq!(
+            config.retry_delay(1, Some(std::time::Duration::from_secs(2))),
+            std::time::Duration::from_secs(2)
+        );
+        assert_eq!(
+            config.retry_delay(1, Some(std::time::Duration::from_secs(3600))),
+            std::time::Duration::from_secs(5)
+        );
+    }
+
+    #[test]
+    fn test_retries_for_prefers_request_override() {
+        use crate::models::request::HttpMethod;
+
+        let mut config = ExecutionConfig::new(30);
+        config.max_retries = 2;
+        let mut request = HttpRequest::new(
+            "test".to_string(),
+            HttpMethod::GET,
+            "https://api.example.com/flaky".to_string(),
+        );
+        assert_eq!(config.retries_for(&request), 2);
+
+        request.retries_override = Some(0);
+        assert_eq!(config.retries_for(&request), 0);
+    }
+
     #[test]
     fn test_serialization() {
         let config = ExecutionConfig::new(120);
@@ -0,0 +1,445 @@
+//! Session cookie jar.
+//!
+//! Cookies set by a response (`Set-Cookie`) are stored and sent back as a
+//! `Cookie` header on later requests to matching URLs, so a login request
+//! can be followed by authenticated requests without copying the session
+//! cookie by hand. Cookies live for the lifetime of the language server
+//! process; a request with `# @no-cookies` neither sends nor stores them.
+//!
+//! Matching follows RFC 6265: the `Domain`, `Path`, `Secure`, `Expires`, and
+//! `Max-Age` attributes are honored. Other attributes are ignored.
+
+use crate::models::headers::Headers;
+use chrono::{DateTime, Duration, NaiveDateTime, Utc};
+use once_cell::sync::Lazy;
+use std::sync::Mutex;
+use url::{Host, Url};
+
+/// The cookie jar shared by every request sent in this session.
+static SESSION_JAR: Lazy<Mutex<CookieJar>> = Lazy::new(|| Mutex::new(CookieJar::new()));
+
+/// Returns the cookie jar shared by every request in the session.
+pub fn session_jar() -> &'static Mutex<CookieJar> {
+    &SESSION_JAR
+}
+
+/// A cookie stored from a `Set-Cookie` response header.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cookie {
+    /// Cookie name
+    pub name: String,
+
+    /// Cookie value
+    pub value: String,
+
+    /// Domain the cookie is sent to (lowercase, without a leading dot)
+    pub domain: String,
+
+    /// Whether the cookie is only sent to `domain` itself, not its subdomains.
+    ///
+    /// Set when the cookie has no `Domain` attribute.
+    pub host_only: bool,
+
+    /// Path prefix the cookie is sent to
+    pub path: String,
+
+    /// Whether the cookie is only sent over HTTPS
+    pub secure: bool,
+
+    /// When the cookie expires, or `None` for a session cookie
+    pub expires: Option<DateTime<Utc>>,
+}
+
+impl Cookie {
+    /// Parses a `Set-Cookie` header value received from `url`.
+    ///
+    /// # Arguments
+    ///
+    /// * `set_cookie` - The `Set-Cookie` header value
+    /// * `url` - The URL of the response that set the cookie
+    /// * `now` - The current time, used to resolve `Max-Age`
+    ///
+    /// # Returns
+    ///
+    /// The cookie, or `None` if the header is malformed or its `Domain`
+    /// does not match the URL's host, is a top-level domain, or names
+    /// anything but the address itself on an IP host.
+    pub fn parse(set_cookie: &str, url: &Url, now: DateTime<Utc>) -> Option<Cookie> {
+        let host = url.host_str()?.to_ascii_lowercase();
+        let is_ip = !matches!(url.host(), Some(Host::Domain(_)));
+        let mut attributes = set_cookie.split(';');
+
+        let (name, value) = attributes.next()?.split_once('=')?;
+        let name = name.trim();
+        if name.is_empty() {
+            return None;
+        }
+
+        let mut cookie = Cookie {
+            name: name.to_string(),
+            value: value.trim().trim_matches('"').to_string(),
+            domain: host.clone(),
+            host_only: true,
+            path: default_path(url),
+            secure: false,
+            expires: None,
+        };
+        let mut max_age = None;
+
+        for attribute in attributes {
+            let (key, value) = match attribute.split_once('=') {
+                Some((key, value)) => (key.trim(), value.trim()),
+                None => (attribute.trim(), ""),
+            };
+
+            match key.to_ascii_lowercase().as_str() {
+                "domain" if !value.is_empty() => {
+                    let domain = value.trim_start_matches('.').to_ascii_lowercase();
+                    // An IP address has no parent domain, so `Domain` may only
+                    // name the address itself and the cookie stays host-only
+                    if is_ip {
+                        if domain == host || domain == host.trim_matches(['[', ']']) {
+                            continue;
+                        }
+                        return None;
+                    }
+                    if !domain_matches(&host, &domain) {
+                        return None;
+                    }
+                    // A Domain without an internal dot (`com`) would reach every
+                    // site under it; on a dotless host such as `localhost` it
+                    // only names the host itself, so the cookie stays host-only
+                    if !domain.contains('.') {
+                        if domain == host {
+                            continue;
+                        }
+                        return None;
+                    }
+                    cookie.domain = domain;
+                    cookie.host_only = false;
+                }
+                "path" if value.starts_with('/') => cookie.path = value.to_string(),
+                "secure" => cookie.secure = true,
+                "expires" => {
+                    if let Some(expires) = parse_cookie_date(value) {
+                        cookie.expires = Some(expires);
+                    }
+                }
+                "max-age" => {
+                    if let Ok(seconds) = value.parse::<i64>() {
+                        max_age = Some(seconds);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        // Max-Age takes precedence over Expires
+        if let Some(seconds) = max_age {
+            cookie.expires = Some(if seconds <= 0 {
+                DateTime::<Utc>::MIN_UTC
+            } else {
+                now + Duration::seconds(seconds)
+            });
+        }
+
+        Some(cookie)
+    }
+
+    /// Returns `true` if the cookie has expired at `now`.
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires.is_some_and(|expires| expires <= now)
+    }
+
+    /// Returns `true` if the cookie should be sent with a request to `url`.
+    pub fn matches(&self, url: &Url, now: DateTime<Utc>) -> bool {
+        let Some(host) = url.host_str().map(str::to_ascii_lowercase) else {
+            return false;
+        };
+
+        let domain_ok = if self.host_only {
+            host == self.domain
+        } else {
+            domain_matches(&host, &self.domain)
+        };
+
+        domain_ok
+            && path_matches(url.path(), &self.path)
+            && (!self.secure || url.scheme() == "https")
+            && !self.is_expired(now)
+    }
+}
+
+/// Cookies collected from responses, sent back on matching requests.
+#[derive(Debug, Clone, Default)]
+pub struct CookieJar {
+    cookies: Vec<Cookie>,
+}
+
+impl CookieJar {
+    /// Creates an empty cookie jar.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stores the cookies set by a response.
+    ///
+    /// A cookie replaces any stored cookie with the same name, domain, and
+    /// path. An expired cookie removes the stored one instead, which is how
+    /// servers log a session out.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The URL of the response
+    /// * `headers` - The response headers
+    pub fn store_response_cookies(&mut self, url: &str, headers: &Headers) {
+        let Ok(url) = Url::parse(url) else {
+            return;
+        };
+        let now = Utc::now();
+
+        for set_cookie in headers.get_all("Set-Cookie") {
+            if let Some(cookie) = Cookie::parse(set_cookie, &url, now) {
+                self.insert(cookie, now);
+            }
+        }
+    }
+
+    /// Stores a cookie, replacing or removing the one it supersedes.
+    fn insert(&mut self, cookie: Cookie, now: DateTime<Utc>) {
+        self.cookies.retain(|existing| {
+            !(existing.name == cookie.name
+                && existing.domain == cookie.domain
+                && existing.path == cookie.path)
+        });
+
+        if !cookie.is_expired(now) {
+            self.cookies.push(cookie);
+        }
+    }
+
+    /// Builds the `Cookie` header value for a request to `url`.
+    ///
+    /// Cookies with longer paths are listed first.
+    ///
+    /// # Returns
+    ///
+    /// `Some(String)` such as `"session=abc; theme=dark"`, or `None` if no
+    /// stored cookie matches the URL.
+    pub fn cookie_header(&self, url: &str) -> Option<String> {
+        let url = Url::parse(url).ok()?;
+        let now = Utc::now();
+
+        let mut matching: Vec<&Cookie> = self
+            .cookies
+            .iter()
+            .filter(|cookie| cookie.matches(&url, now))
+            .collect();
+        if matching.is_empty() {
+            return None;
+        }
+        matching.sort_by_key(|cookie| std::cmp::Reverse(cookie.path.len()));
+
+        Some(
+            matching
+                .iter()
+                .map(|cookie| format!("{}={}", cookie.name, cookie.value))
+                .collect::<Vec<_>>()
+                .join("; "),
+        )
+    }
+
+    /// Returns the stored cookies.
+    pub fn cookies(&self) -> &[Cookie] {
+        &self.cookies
+    }
+
+    /// Returns the number of stored cookies.
+    pub fn len(&self) -> usize {
+        self.cookies.len()
+    }
+
+    /// Returns `true` if no cookies are stored.
+    pub fn is_empty(&self) -> bool {
+        self.cookies.is_empty()
+    }
+
+    /// Removes every stored cookie.
+    pub fn clear(&mut self) {
+        self.cookies.clear();
+    }
+}
+
+/// Returns `true` if `host` is `domain` or a subdomain of it.
+fn domain_matches(host: &str, domain: &str) -> bool {
+    host == domain || (host.ends_with(domain) && host[..host.len() - domain.len()].ends_with('.'))
+}
+
+/// Returns `true` if `request_path` is within `cookie_path`.
+fn path_matches(request_path: &str, cookie_path: &str) -> bool {
+    request_path == cookie_path
+        || (request_path.starts_with(cookie_path)
+            && (cookie_path.ends_with('/') || request_path[cookie_path.len()..].starts_with('/')))
+}
+
+/// Returns the default cookie path for a URL: its path up to the last `/`.
+fn default_path(url: &Url) -> String {
+    match url.path().rfind('/') {
+        Some(0) | None => "/".to_string(),
+        Some(index) => url.path()[..index].to_string(),
+    }
+}
+
+/// Parses an `Expires` date such as `Wed, 21 Oct 2026 07:28:00 GMT`.
+fn parse_cookie_date(value: &str) -> Option<DateTime<Utc>> {
+    if let Ok(date) = DateTime::parse_from_rfc2822(value) {
+        return Some(date.with_timezone(&Utc));
+    }
+
+    // Older servers separate the date with dashes: `Wed, 21-Oct-2026 ...`
+    NaiveDateTime::parse_from_str(value, "%a, %d-%b-%Y %H:%M:%S GMT")
+        .ok()
+        .map(|date| date.and_utc())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn jar_with(url: &str, set_cookies: &[&str]) -> CookieJar {
+        let mut headers = Headers::new();
+        for set_cookie in set_cookies {
+            headers.append("Set-Cookie".to_string(), set_cookie.to_string());
+        }
+
+        let mut jar = CookieJar::new();
+        jar.store_response_cookies(url, &headers);
+        jar
+    }
+
+    #[test]
+    fn test_login_cookie_is_sent_on_next_request() {
+        let jar = jar_with(
+            "https://api.example.com/auth/login",
+            &["session=abc123; Path=/; HttpOnly", "theme=dark"],
+        );
+
+        assert_eq!(
+            jar.cookie_header("https://api.example.com/users"),
+            Some("session=abc123".to_string())
+        );
+        assert_eq!(
+            jar.cookie_header("https://api.example.com/auth/me"),
+            Some("theme=dark; session=abc123".to_string())
+        );
+        assert_eq!(jar.cookie_header("https://other.example.com/users"), None);
+    }
+
+    #[test]
+    fn test_domain_attribute() {
+        let jar = jar_with(
+            "https://login.example.com/",
+            &["sso=1; Domain=.example.com"],
+        );
+
+        assert!(jar.cookie_header("https://api.example.com/").is_some());
+        assert!(jar.cookie_header("https://example.com/").is_some());
+        assert!(jar.cookie_header("https://badexample.com/").is_none());
+
+        // A Domain the response host doesn't belong to is rejected
+        let jar = jar_with("https://api.example.com/", &["evil=1; Domain=other.com"]);
+        assert!(jar.is_empty());
+
+        // So is a top-level domain
+        let jar = jar_with("https://api.example.com/", &["evil=1; Domain=.com"]);
+        assert!(jar.is_empty());
+
+        // On a dotless host the Domain names the host itself
+        let jar = jar_with("http://localhost:3000/", &["dev=1; Domain=localhost"]);
+        assert_eq!(jar.len(), 1);
+        assert!(jar.cookies()[0].host_only);
+    }
+
+    #[test]
+    fn test_domain_attribute_on_ip_host() {
+        // A suffix of an IP address is not a parent domain
+        let jar = jar_with("http://192.168.0.1/", &["evil=1; Domain=0.1"]);
+        assert!(jar.is_empty());
+        let jar = jar_with("http://192.168.0.1/", &["evil=1; Domain=168.0.1"]);
+        assert!(jar.is_empty());
+
+        // Naming the address itself keeps the cookie on that host
+        let jar = jar_with("http://192.168.0.1/", &["ok=1; Domain=192.168.0.1"]);
+        assert_eq!(jar.len(), 1);
+        assert!(jar.cookies()[0].host_only);
+        assert!(jar.cookie_header("http://192.168.0.1/").is_some());
+        assert!(jar.cookie_header("http://10.168.0.1/").is_none());
+
+        let jar = jar_with("http://[::1]:8080/", &["ok=1; Domain=::1"]);
+        assert_eq!(jar.len(), 1);
+        assert!(jar.cookies()[0].host_only);
+    }
+
+    #[test]
+    fn test_path_and_secure_attributes() {
+        let jar = jar_with(
+            "https://api.example.com/",
+            &["admin=1; Path=/admin", "token=x; Secure"],
+        );
+
+        assert_eq!(
+            jar.cookie_header("https://api.example.com/admin/users"),
+            Some("admin=1; token=x".to_string())
+        );
+        assert_eq!(
+            jar.cookie_header("https://api.example.com/administrator"),
+            Some("token=x".to_string())
+        );
+        assert_eq!(
+            jar.cookie_header("http://api.example.com/admin"),
+            Some("admin=1".to_string())
+        );
+    }
+
+    #[test]
+    fn test_expiry() {
+        let mut jar = jar_with(
+            "https://api.example.com/",
+            &[
+                "old=1; Expires=Wed, 21 Oct 2015 07:28:00 GMT",
+                "legacy=1; Expires=Fri, 01-Jan-2100 00:00:00 GMT",
+                "session=abc",
+            ],
+        );
+        assert_eq!(jar.len(), 2);
+        assert!(jar
+            .cookies()
+            .iter()
+            .any(|cookie| cookie.name == "legacy" && cookie.expires.is_some()));
+
+        // Max-Age=0 deletes a stored cookie, e.g. on logout
+        let mut headers = Headers::new();
+        headers.append("Set-Cookie".to_string(), "session=; Max-Age=0".to_string());
+        jar.store_response_cookies("https://api.example.com/logout", &headers);
+
+        assert_eq!(
+            jar.cookie_header("https://api.example.com/"),
+            Some("legacy=1".to_string())
+        );
+    }
+
+    #[test]
+    fn test_cookie_replaced_by_same_name() {
+        let mut jar = jar_with("https://api.example.com/", &["session=old"]);
+
+        let mut headers = Headers::new();
+        headers.append("Set-Cookie".to_string(), "session=new".to_string());
+        jar.store_response_cookies("https://api.example.com/refresh", &headers);
+
+        assert_eq!(jar.len(), 1);
+        assert_eq!(
+            jar.cookie_header("https://api.example.com/"),
+            Some("session=new".to_string())
+        );
+    }
+}
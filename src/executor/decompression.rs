@@ -0,0 +1,198 @@
+//! Response body decompression.
+//!
+//! Zed's HTTP client returns response bodies exactly as they were sent, so a
+//! server answering with `Content-Encoding: gzip` would otherwise be shown as
+//! binary data. This module inflates gzip, deflate, and brotli bodies before
+//! they are formatted, and removes the `Content-Encoding` header once the
+//! body has been decoded.
+
+use super::RequestError;
+use crate::models::response::HttpResponse;
+use flate2::read::{DeflateDecoder, GzDecoder, ZlibDecoder};
+use std::io::Read;
+
+/// Buffer size used by the brotli decoder.
+const BROTLI_BUFFER_SIZE: usize = 4096;
+
+/// Decodes a body compressed with a single content coding.
+///
+/// # Arguments
+///
+/// * `body` - The compressed body
+/// * `encoding` - A content coding such as "gzip", "deflate", or "br"
+///
+/// # Returns
+///
+/// `Ok(Some(Vec<u8>))` with the decoded body, `Ok(None)` if the coding is
+/// `identity` or not supported, or `Err(RequestError::DecompressionError)` if
+/// the body is not valid data in that coding.
+///
+/// # Examples
+///
+/// ```
+/// use rest_client::executor::decompression::decode_body;
+///
+/// assert_eq!(decode_body(b"plain", "identity").unwrap(), None);
+/// assert!(decode_body(b"not gzip", "gzip").is_err());
+/// ```
+pub fn decode_body(body: &[u8], encoding: &str) -> Result<Option<Vec<u8>>, RequestError> {
+    let encoding = encoding.trim().to_ascii_lowercase();
+    let mut decoded = Vec::new();
+
+    let result = match encoding.as_str() {
+        "gzip" | "x-gzip" => GzDecoder::new(body).read_to_end(&mut decoded),
+        // "deflate" is meant to be zlib-wrapped, but some servers send raw deflate
+        "deflate" => ZlibDecoder::new(body)
+            .read_to_end(&mut decoded)
+            .or_else(|_| {
+                decoded.clear();
+                DeflateDecoder::new(body).read_to_end(&mut decoded)
+            }),
+        "br" => brotli_decompressor::Decompressor::new(body, BROTLI_BUFFER_SIZE)
+            .read_to_end(&mut decoded),
+        _ => return Ok(None),
+    };
+
+    result.map_err(|e| {
+        RequestError::DecompressionError(format!("Invalid {} response body: {}", encoding, e))
+    })?;
+    Ok(Some(decoded))
+}
+
+/// Decompresses a response body according to its `Content-Encoding` header.
+///
+/// Codings listed in the header are undone in reverse order. If every coding
+/// is decoded, the body is replaced and `Content-Encoding` is removed; the
+/// header's value and the size of the body as received are kept in
+/// `original_encoding` and `compressed_size`. A response without the header,
+/// with `identity`, or with a coding that isn't supported is left unchanged.
+///
+/// # Arguments
+///
+/// * `response` - The response to decompress in place
+///
+/// # Returns
+///
+/// `Ok(())` on success, or `Err(RequestError::DecompressionError)` if the body
+/// does not match its declared encoding.
+pub fn decompress_response(response: &mut HttpResponse) -> Result<(), RequestError> {
+    let Some(content_encoding) = response.headers.get("Content-Encoding") else {
+        return Ok(());
+    };
+
+    let encodings: Vec<String> = content_encoding
+        .split(',')
+        .map(|encoding| encoding.trim().to_string())
+        .filter(|encoding| !encoding.is_empty() && !encoding.eq_ignore_ascii_case("identity"))
+        .collect();
+
+    let mut body = response.body.clone();
+    for encoding in encodings.iter().rev() {
+        match decode_body(&body, encoding)? {
+            Some(decoded) => body = decoded,
+            // Leave bodies with unknown codings as they were received
+            None => return Ok(()),
+        }
+    }
+
+    if !encodings.is_empty() {
+        response.original_encoding = Some(encodings.join(", "));
+        response.compressed_size = Some(response.body.len());
+    }
+    response.body = body;
+    response.headers.remove("Content-Encoding");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::formatter::format_response;
+    use flate2::write::{GzEncoder, ZlibEncoder};
+    use flate2::Compression;
+    use std::io::Write;
+
+    const JSON: &str = r#"{"id":1,"name":"Ada"}"#;
+
+    fn gzip(data: &[u8]) -> Vec<u8> {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(data).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    fn compressed_response(body: Vec<u8>, encoding: &str) -> HttpResponse {
+        let mut response = HttpResponse::new(200, "OK".to_string());
+        response
+            .headers
+            .insert("Content-Type".to_string(), "application/json".to_string());
+        response
+            .headers
+            .insert("content-encoding".to_string(), encoding.to_string());
+        response.body = body;
+        response
+    }
+
+    #[test]
+    fn test_gzip_json_formats_as_pretty_json() {
+        let mut response = compressed_response(gzip(JSON.as_bytes()), "gzip");
+
+        decompress_response(&mut response).unwrap();
+
+        assert_eq!(response.body, JSON.as_bytes());
+        assert!(!response.headers.contains_key("Content-Encoding"));
+        assert_eq!(response.original_encoding.as_deref(), Some("gzip"));
+        assert_eq!(response.compressed_size, Some(gzip(JSON.as_bytes()).len()));
+
+        let formatted = format_response(&response);
+        assert!(formatted.is_formatted);
+        assert_eq!(
+            formatted.formatted_body,
+            "{\n  \"id\": 1,\n  \"name\": \"Ada\"\n}"
+        );
+    }
+
+    #[test]
+    fn test_deflate_and_brotli() {
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(JSON.as_bytes()).unwrap();
+        let mut response = compressed_response(encoder.finish().unwrap(), "deflate");
+        decompress_response(&mut response).unwrap();
+        assert_eq!(response.body, JSON.as_bytes());
+
+        let mut compressed = Vec::new();
+        brotli::BrotliCompress(&mut JSON.as_bytes(), &mut compressed, &Default::default()).unwrap();
+        let mut response = compressed_response(compressed, "br");
+        decompress_response(&mut response).unwrap();
+        assert_eq!(response.body, JSON.as_bytes());
+    }
+
+    #[test]
+    fn test_identity_and_unknown_encodings_are_unchanged() {
+        let mut response = compressed_response(JSON.as_bytes().to_vec(), "identity");
+        decompress_response(&mut response).unwrap();
+        assert_eq!(response.body, JSON.as_bytes());
+        assert_eq!(response.original_encoding, None);
+
+        let mut response = compressed_response(b"zstd data".to_vec(), "zstd");
+        decompress_response(&mut response).unwrap();
+        assert_eq!(response.body, b"zstd data");
+        assert!(response.headers.contains_key("Content-Encoding"));
+
+        let mut response = HttpResponse::new(200, "OK".to_string());
+        response.body = JSON.as_bytes().to_vec();
+        decompress_response(&mut response).unwrap();
+        assert_eq!(response.body, JSON.as_bytes());
+    }
+
+    #[test]
+    fn test_invalid_gzip_body() {
+        let mut response = compressed_response(JSON.as_bytes().to_vec(), "gzip");
+
+        match decompress_response(&mut response) {
+            Err(RequestError::DecompressionError(msg)) => {
+                assert!(msg.starts_with("Invalid gzip response body"), "{}", msg);
+            }
+            other => panic!("Expected DecompressionError, got {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,155 @@
+//! Percent-encoding of resolved request URLs.
+//!
+//! Variables are substituted into URLs as plain text, so a value such as
+//! `John Doe` produces a URL that servers reject. This module encodes the
+//! characters that are never valid in a URL while leaving reserved delimiters
+//! and already-encoded `%XX` sequences untouched, so encoding is idempotent.
+//! Because delimiters are kept, a value containing `&`, `=`, or `#` still
+//! splits the query; wrap such values in `{{urlEncode(...)}}`.
+
+/// Characters that may appear unencoded in a URL path, query, or fragment.
+///
+/// Includes the RFC 3986 unreserved set and all reserved delimiters.
+fn is_url_safe(c: char) -> bool {
+    c.is_ascii_alphanumeric() || "-._~:/?#[]@!$&'()*+,;=".contains(c)
+}
+
+/// Percent-encodes unsafe characters in the path, query, and fragment of a URL.
+///
+/// The scheme and authority are left as-is. Reserved delimiters such as `/`,
+/// `?`, `&`, and `=` are preserved, and existing `%XX` escapes are not
+/// encoded again. A `%` that does not start a valid escape is encoded as `%25`.
+///
+/// # Arguments
+///
+/// * `url` - The URL after variable substitution
+///
+/// # Returns
+///
+/// The URL with unsafe characters percent-encoded as UTF-8 bytes.
+///
+/// # Examples
+///
+/// ```
+/// use rest_client::executor::encoding::encode_url;
+///
+/// assert_eq!(
+///     encode_url("https://api.example.com/users/John Doe?q=a b"),
+///     "https://api.example.com/users/John%20Doe?q=a%20b"
+/// );
+/// assert_eq!(
+///     encode_url("https://api.example.com/search?q=a%20b"),
+///     "https://api.example.com/search?q=a%20b"
+/// );
+/// ```
+pub fn encode_url(url: &str) -> String {
+    let rest_start = match url.find("://") {
+        Some(scheme_end) => {
+            let authority_start = scheme_end + 3;
+            url[authority_start..]
+                .find(['/', '?', '#'])
+                .map(|i| authority_start + i)
+                .unwrap_or(url.len())
+        }
+        None => 0,
+    };
+
+    let (prefix, rest) = url.split_at(rest_start);
+    let mut encoded = String::with_capacity(url.len());
+    encoded.push_str(prefix);
+
+    let bytes = rest.as_bytes();
+    for (i, c) in rest.char_indices() {
+        if c == '%' {
+            let is_escape = bytes.len() > i + 2
+                && bytes[i + 1].is_ascii_hexdigit()
+                && bytes[i + 2].is_ascii_hexdigit();
+            if is_escape {
+                encoded.push('%');
+            } else {
+                encoded.push_str("%25");
+            }
+        } else if is_url_safe(c) {
+            encoded.push(c);
+        } else {
+            let mut buf = [0u8; 4];
+            for byte in c.encode_utf8(&mut buf).bytes() {
+                encoded.push_str(&format!("%{:02X}", byte));
+            }
+        }
+    }
+
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::variables::substitution::{substitute_variables, VariableContext};
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_encode_variable_with_space() {
+        let mut context = VariableContext::new(PathBuf::from("/workspace"));
+        context
+            .file_variables
+            .insert("name".to_string(), "Tom Jerry".to_string());
+
+        let url = substitute_variables("https://example.com/shows/{{name}}?q={{name}}", &context)
+            .unwrap();
+
+        assert_eq!(
+            encode_url(&url),
+            "https://example.com/shows/Tom%20Jerry?q=Tom%20Jerry"
+        );
+    }
+
+    #[test]
+    fn test_encode_url_encoded_variable_with_ampersand() {
+        let mut context = VariableContext::new(PathBuf::from("/workspace"));
+        context
+            .file_variables
+            .insert("name".to_string(), "Tom & Jerry".to_string());
+
+        let url = substitute_variables(
+            "https://example.com/search?q={{urlEncode({{name}})}}&page=1",
+            &context,
+        )
+        .unwrap();
+
+        assert_eq!(
+            encode_url(&url),
+            "https://example.com/search?q=Tom%20%26%20Jerry&page=1"
+        );
+    }
+
+    #[test]
+    fn test_encode_preserves_existing_escapes_and_delimiters() {
+        let url = "https://example.com/a%2Fb/c?x=1&y=%E2%9C%93#frag";
+        assert_eq!(encode_url(url), url);
+        assert_eq!(
+            encode_url(&encode_url("https://example.com/a b")),
+            "https://example.com/a%20b"
+        );
+    }
+
+    #[test]
+    fn test_encode_stray_percent_and_unicode() {
+        assert_eq!(
+            encode_url("https://example.com/100%?name=José"),
+            "https://example.com/100%25?name=Jos%C3%A9"
+        );
+    }
+
+    #[test]
+    fn test_encode_leaves_authority_untouched() {
+        assert_eq!(
+            encode_url("https://user:pa ss@example.com:8080"),
+            "https://user:pa ss@example.com:8080"
+        );
+        assert_eq!(
+            encode_url("http://localhost:3000/path with space"),
+            "http://localhost:3000/path%20with%20space"
+        );
+    }
+}
@@ -3,6 +3,7 @@
 //! This module defines error types that can occur during HTTP request execution,
 //! including network errors, timeouts, and protocol issues.
 
+use crate::models::request::InvalidHeaderError;
 use std::fmt;
 
 /// Errors that can occur during HTTP request execution.
@@ -54,6 +55,39 @@ pub enum RequestError {
     ///
     /// The requested HTTP method is not supported by the Zed HTTP client.
     UnsupportedMethod(String),
+
+    /// URL exceeds the configured maximum length.
+    ///
+    /// Contains the length of the rejected URL. Usually caused by variable
+    /// expansion producing a very long query string.
+    UrlTooLong(usize),
+
+    /// Invalid header name or value.
+    ///
+    /// Header values containing CR/LF or other control characters are
+    /// rejected to prevent header injection.
+    InvalidHeader(String),
+
+    /// The request body could not be encoded in its `# @encoding` charset.
+    ///
+    /// The charset is unknown, or the body contains characters it cannot
+    /// represent.
+    BodyEncodingError(String),
+
+    /// Request to an internal address blocked by `blockInternalAddresses`.
+    ///
+    /// Loopback, link-local, and cloud metadata addresses are rejected to
+    /// guard against server-side request forgery.
+    BlockedAddress(String),
+
+    /// The response body could not be decompressed.
+    ///
+    /// The server declared a `Content-Encoding` but the body is not valid
+    /// data in that encoding.
+    DecompressionError(String),
+
+    /// The request was cancelled before it finished.
+    Cancelled,
 }
 
 impl fmt::Display for RequestError {
@@ -71,12 +105,344 @@ impl fmt::Display for RequestError {
             RequestError::UnsupportedMethod(msg) => {
                 write!(f, "Unsupported HTTP method: {}", msg)
             }
+            RequestError::UrlTooLong(len) => write!(
+                f,
+                "URL too long ({} characters). Consider moving query parameters into the request body",
+                len
+            ),
+            RequestError::InvalidHeader(msg) => write!(f, "Invalid header: {}", msg),
+            RequestError::BodyEncodingError(msg) => write!(f, "Body encoding error: {}", msg),
+            RequestError::BlockedAddress(msg) => write!(
+                f,
+                "Blocked request to internal address: {}. Add the host to internalAddressAllowlist to allow it",
+                msg
+            ),
+            RequestError::DecompressionError(msg) => {
+                write!(f, "Response decompression error: {}", msg)
+            }
+            RequestError::Cancelled => write!(f, "Request cancelled"),
         }
     }
 }
 
 impl std::error::Error for RequestError {}
 
+impl From<InvalidHeaderError> for RequestError {
+    fn from(err: InvalidHeaderError) -> Self {
+        RequestError::InvalidHeader(err.0)
+    }
+}
+
+/// Broad class of a request failure.
+///
+/// Lets callers decide how to react to an error (retry it, explain it,
+/// report it) without matching on its message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorCategory {
+    /// The host name couldn't be resolved
+    Dns,
+
+    /// The TLS connection couldn't be set up
+    Tls,
+
+    /// The connection was refused, reset, or otherwise failed
+    Connect,
+
+    /// The request took longer than its timeout
+    Timeout,
+
+    /// The server's reply was malformed or couldn't be decoded
+    Protocol,
+
+    /// The request couldn't be built or was rejected before sending
+    Build,
+
+    /// The request was cancelled before it finished
+    Cancelled,
+}
+
+impl ErrorCategory {
+    /// Returns `true` if errors in this category are worth retrying.
+    ///
+    /// Only connection failures and timeouts are retried; the others fail
+    /// the same way on every attempt.
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, ErrorCategory::Connect | ErrorCategory::Timeout)
+    }
+}
+
+impl fmt::Display for ErrorCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            ErrorCategory::Dns => "DNS",
+            ErrorCategory::Tls => "TLS",
+            ErrorCategory::Connect => "Connection",
+            ErrorCategory::Timeout => "Timeout",
+            ErrorCategory::Protocol => "Protocol",
+            ErrorCategory::Build => "Request build",
+            ErrorCategory::Cancelled => "Cancelled",
+        };
+        write!(f, "{}", label)
+    }
+}
+
+/// A plain-language explanation of a request failure.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ErrorExplanation {
+    /// What most likely went wrong
+    pub summary: &'static str,
+
+    /// Things to check or change, most likely first
+    pub fixes: Vec<&'static str>,
+}
+
+impl ErrorExplanation {
+    fn new(summary: &'static str, fixes: Vec<&'static str>) -> Self {
+        Self { summary, fixes }
+    }
+}
+
+impl RequestError {
+    /// Classifies the error.
+    ///
+    /// Network errors are split into DNS and connection failures by their
+    /// message; everything else is classified by variant.
+    ///
+    /// # Returns
+    ///
+    /// The `ErrorCategory` this error belongs to.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rest_client::executor::{ErrorCategory, RequestError};
+    ///
+    /// let error = RequestError::NetworkError("dns error: no such host".to_string());
+    /// assert_eq!(error.category(), ErrorCategory::Dns);
+    /// assert_eq!(RequestError::Timeout.category(), ErrorCategory::Timeout);
+    /// ```
+    pub fn category(&self) -> ErrorCategory {
+        match self {
+            RequestError::NetworkError(msg) if is_dns_failure(&msg.to_lowercase()) => {
+                ErrorCategory::Dns
+            }
+            RequestError::NetworkError(_) => ErrorCategory::Connect,
+            RequestError::Timeout => ErrorCategory::Timeout,
+            RequestError::TlsError(_) => ErrorCategory::Tls,
+            RequestError::ProtocolError(_) | RequestError::DecompressionError(_) => {
+                ErrorCategory::Protocol
+            }
+            RequestError::Cancelled => ErrorCategory::Cancelled,
+            RequestError::InvalidUrl(_)
+            | RequestError::BuildError(_)
+            | RequestError::UnsupportedProtocol(_)
+            | RequestError::UnsupportedMethod(_)
+            | RequestError::UrlTooLong(_)
+            | RequestError::InvalidHeader(_)
+            | RequestError::BodyEncodingError(_)
+            | RequestError::BlockedAddress(_) => ErrorCategory::Build,
+        }
+    }
+
+    /// Returns `true` if the request is worth sending again after this error.
+    pub fn is_retryable(&self) -> bool {
+        self.category().is_retryable()
+    }
+
+    /// Explains the error for someone who hasn't seen it before.
+    ///
+    /// Network errors are narrowed down by their category, so a DNS failure
+    /// and a refused connection get different advice. TLS errors are split
+    /// into certificate and handshake problems by their message.
+    ///
+    /// # Returns
+    ///
+    /// An `ErrorExplanation` with a summary and likely fixes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rest_client::executor::RequestError;
+    ///
+    /// let explanation = RequestError::Timeout.explanation();
+    /// assert!(explanation.summary.contains("did not respond"));
+    /// ```
+    pub fn explanation(&self) -> ErrorExplanation {
+        match self {
+            RequestError::NetworkError(msg) => match self.category() {
+                ErrorCategory::Dns => ErrorExplanation::new(
+                    "The host name couldn't be resolved to an address (DNS failure).",
+                    vec![
+                        "Check the host name in the URL for typos",
+                        "Check your internet connection or VPN",
+                        "Run /check-variables to make sure {{baseUrl}}-style variables resolved",
+                    ],
+                ),
+                _ => explain_connect_error(&msg.to_lowercase()),
+            },
+            RequestError::Timeout => ErrorExplanation::new(
+                "The server did not respond before the timeout ran out.",
+                vec![
+                    "Check that the server is running and reachable from this machine",
+                    "Raise the `timeout` setting, or add `# @timeout <milliseconds>` above a slow request",
+                    "For flaky endpoints, add `# @retries <count>` to retry automatically",
+                ],
+            ),
+            RequestError::InvalidUrl(_) => ErrorExplanation::new(
+                "The URL could not be parsed.",
+                vec![
+                    "Make sure the URL starts with http:// or https://",
+                    "Check for spaces or unescaped characters in the URL",
+                    "Run /check-variables to find {{variables}} that didn't resolve",
+                ],
+            ),
+            RequestError::TlsError(msg) => explain_tls_error(&msg.to_lowercase()),
+            RequestError::ProtocolError(_) => ErrorExplanation::new(
+                "The server's reply wasn't valid HTTP.",
+                vec![
+                    "Check that the port serves HTTP and not another protocol (a database, SSH, ...)",
+                    "Try http:// instead of https://, or the other way round",
+                    "If you're going through a proxy, check `proxyUrl`",
+                ],
+            ),
+            RequestError::Cancelled => ErrorExplanation::new(
+                "The request was cancelled before it finished.",
+                vec!["Send the request again with /send-request"],
+            ),
+            RequestError::BuildError(_) => ErrorExplanation::new(
+                "The request couldn't be built from the .http file.",
+                vec![
+                    "Check the request line, headers, and body for typos",
+                    "Make sure files referenced with `< path` exist",
+                ],
+            ),
+            RequestError::UnsupportedProtocol(_) => ErrorExplanation::new(
+                "Only http:// and https:// URLs can be sent.",
+                vec!["Change the URL scheme to http:// or https://"],
+            ),
+            RequestError::UnsupportedMethod(_) => ErrorExplanation::new(
+                "The HTTP client can't send this method.",
+                vec!["Use GET, POST, PUT, DELETE, PATCH, HEAD, or OPTIONS"],
+            ),
+            RequestError::UrlTooLong(_) => ErrorExplanation::new(
+                "The URL is longer than `maxUrlLength` allows, usually because a variable expanded to a long query string.",
+                vec![
+                    "Move large query parameters into the request body",
+                    "Raise the `maxUrlLength` setting if the server accepts long URLs",
+                ],
+            ),
+            RequestError::InvalidHeader(_) => ErrorExplanation::new(
+                "A header name or value contains characters HTTP doesn't allow, such as a line break.",
+                vec![
+                    "Check for variables that resolve to multi-line values",
+                    "Make sure header names contain no spaces",
+                ],
+            ),
+            RequestError::BodyEncodingError(_) => ErrorExplanation::new(
+                "The body couldn't be encoded in the charset from `# @encoding`.",
+                vec![
+                    "Check the charset name, e.g. `utf-8`, `iso-8859-1`, `shift_jis`",
+                    "Remove characters the charset can't represent, or remove the directive",
+                ],
+            ),
+            RequestError::BlockedAddress(_) => ErrorExplanation::new(
+                "The request targets a loopback, link-local, or metadata address, and `blockInternalAddresses` is on.",
+                vec![
+                    "Add the host to `internalAddressAllowlist` if you meant to call it",
+                    "Turn off `blockInternalAddresses` for local development",
+                ],
+            ),
+            RequestError::DecompressionError(_) => ErrorExplanation::new(
+                "The server said the body was compressed, but it couldn't be decompressed.",
+                vec![
+                    "Check the server's `Content-Encoding` header matches what it actually sends",
+                    "Send `Accept-Encoding: identity` to ask for an uncompressed body",
+                ],
+            ),
+        }
+    }
+}
+
+/// Returns `true` if a (lowercased) network error message is a DNS failure.
+fn is_dns_failure(msg: &str) -> bool {
+    let dns_markers = [
+        "dns",
+        "resolve",
+        "lookup",
+        "no such host",
+        "name or service not known",
+        "nodename nor servname",
+    ];
+
+    dns_markers.iter().any(|marker| msg.contains(marker))
+}
+
+/// Picks advice for a connection error based on its (lowercased) message.
+fn explain_connect_error(msg: &str) -> ErrorExplanation {
+    if msg.contains("refused") {
+        ErrorExplanation::new(
+            "The host was reached, but nothing is listening on that port.",
+            vec![
+                "Make sure the server is running",
+                "Check the port number in the URL",
+                "For local servers, try 127.0.0.1 instead of localhost (or the other way round)",
+            ],
+        )
+    } else if msg.contains("reset") || msg.contains("broken pipe") || msg.contains("closed") {
+        ErrorExplanation::new(
+            "The server closed the connection before sending a response.",
+            vec![
+                "Check the server logs for a crash or rejected request",
+                "Try http:// instead of https://, or the other way round",
+                "Check for a proxy or firewall that drops the connection",
+            ],
+        )
+    } else {
+        ErrorExplanation::new(
+            "The request couldn't reach the server.",
+            vec![
+                "Check the URL and your network connection",
+                "If you need a proxy, set `proxyUrl`",
+                "Try the same URL with curl to see if the problem is specific to the editor",
+            ],
+        )
+    }
+}
+
+/// Picks advice for a TLS error based on its (lowercased) message.
+fn explain_tls_error(msg: &str) -> ErrorExplanation {
+    let certificate_markers = [
+        "certificate",
+        "self signed",
+        "self-signed",
+        "issuer",
+        "expired",
+    ];
+
+    if certificate_markers
+        .iter()
+        .any(|marker| msg.contains(marker))
+    {
+        ErrorExplanation::new(
+            "The server's certificate wasn't trusted.",
+            vec![
+                "Check the certificate hasn't expired and matches the host name",
+                "For local or test servers with self-signed certificates, set `validateSSL` to false",
+                "Make sure intermediate certificates are installed on the server",
+            ],
+        )
+    } else {
+        ErrorExplanation::new(
+            "The secure (TLS) connection couldn't be set up.",
+            vec![
+                "Check the server supports HTTPS on this port; try http:// if it doesn't",
+                "Check the server allows a modern TLS version (1.2 or later)",
+                "For local or test servers, try setting `validateSSL` to false",
+            ],
+        )
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -97,6 +463,126 @@ mod tests {
 
         let tls_err = RequestError::TlsError("Certificate invalid".to_string());
         assert_eq!(format!("{}", tls_err), "TLS/SSL error: Certificate invalid");
+
+        let url_err = RequestError::UrlTooLong(9000);
+        assert!(format!("{}", url_err).starts_with("URL too long (9000 characters)"));
+
+        let encoding_err = RequestError::BodyEncodingError("Unknown charset 'x'".to_string());
+        assert_eq!(
+            format!("{}", encoding_err),
+            "Body encoding error: Unknown charset 'x'"
+        );
+    }
+
+    #[test]
+    fn test_explanation_network_errors() {
+        let dns = RequestError::NetworkError("failed to lookup address information".to_string());
+        assert!(dns.explanation().summary.contains("DNS"));
+
+        let refused = RequestError::NetworkError("Connection refused (os error 61)".to_string());
+        assert!(refused
+            .explanation()
+            .summary
+            .contains("nothing is listening"));
+
+        let other = RequestError::NetworkError("unknown".to_string());
+        assert_eq!(
+            other.explanation().summary,
+            "The request couldn't reach the server."
+        );
+    }
+
+    #[test]
+    fn test_explanation_tls_errors() {
+        let cert = RequestError::TlsError("invalid peer certificate: UnknownIssuer".to_string());
+        let explanation = cert.explanation();
+        assert!(explanation.summary.contains("certificate"));
+        assert!(explanation
+            .fixes
+            .iter()
+            .any(|fix| fix.contains("validateSSL")));
+
+        let handshake = RequestError::TlsError("handshake failure".to_string());
+        assert!(handshake.explanation().summary.contains("TLS"));
+    }
+
+    #[test]
+    fn test_explanation_every_variant_has_fixes() {
+        let errors = [
+            RequestError::NetworkError(String::new()),
+            RequestError::Timeout,
+            RequestError::InvalidUrl(String::new()),
+            RequestError::TlsError(String::new()),
+            RequestError::ProtocolError(String::new()),
+            RequestError::BuildError(String::new()),
+            RequestError::UnsupportedProtocol(String::new()),
+            RequestError::UnsupportedMethod(String::new()),
+            RequestError::UrlTooLong(0),
+            RequestError::InvalidHeader(String::new()),
+            RequestError::BodyEncodingError(String::new()),
+            RequestError::BlockedAddress(String::new()),
+            RequestError::DecompressionError(String::new()),
+            RequestError::Cancelled,
+        ];
+
+        for error in errors {
+            assert!(!error.explanation().fixes.is_empty(), "{:?}", error);
+        }
+        assert!(RequestError::Timeout
+            .explanation()
+            .fixes
+            .iter()
+            .any(|fix| fix.contains("@timeout")));
+    }
+
+    #[test]
+    fn test_category_network_errors() {
+        let dns = RequestError::NetworkError("failed to lookup address information".to_string());
+        assert_eq!(dns.category(), ErrorCategory::Dns);
+
+        let refused = RequestError::NetworkError("Connection refused (os error 61)".to_string());
+        assert_eq!(refused.category(), ErrorCategory::Connect);
+        assert!(refused.is_retryable());
+        assert!(!dns.is_retryable());
+    }
+
+    #[test]
+    fn test_category_by_variant() {
+        assert_eq!(RequestError::Timeout.category(), ErrorCategory::Timeout);
+        assert_eq!(
+            RequestError::TlsError(String::new()).category(),
+            ErrorCategory::Tls
+        );
+        assert_eq!(
+            RequestError::DecompressionError(String::new()).category(),
+            ErrorCategory::Protocol
+        );
+        assert_eq!(
+            RequestError::InvalidHeader(String::new()).category(),
+            ErrorCategory::Build
+        );
+        assert_eq!(
+            RequestError::BuildError("bad body".to_string()).category(),
+            ErrorCategory::Build
+        );
+        assert_eq!(RequestError::Cancelled.category(), ErrorCategory::Cancelled);
+        assert!(!RequestError::Cancelled.is_retryable());
+        assert!(RequestError::Timeout.is_retryable());
+    }
+
+    #[test]
+    fn test_from_invalid_header_error() {
+        let err = RequestError::from(InvalidHeaderError("bad name".to_string()));
+        assert!(matches!(&err, RequestError::InvalidHeader(msg) if msg == "bad name"));
+        assert_eq!(err.to_string(), "Invalid header: bad name");
+    }
+
+    #[test]
+    fn test_explanation_cancelled() {
+        assert!(RequestError::Cancelled
+            .explanation()
+            .summary
+            .contains("cancelled"));
     }
 
     #[test]
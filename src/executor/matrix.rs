@@ -0,0 +1,296 @@
+//! Matrix runner for content negotiation testing.
+//!
+//! A `# @matrix Header: value1, value2` directive sends the same request once
+//! per listed value, replacing the named header each time. Several directives
+//! are combined, so every combination of values is sent. Each response is
+//! labeled with the header values that produced it.
+//!
+//! # Examples
+//!
+//! ```http
+//! # @matrix Accept: application/json, application/xml
+//! GET https://api.example.com/users/1
+//! ```
+
+use super::RequestError;
+use crate::models::request::HttpRequest;
+use crate::models::response::HttpResponse;
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+/// Cached regex pattern for the `# @matrix Header: v1, v2` directive.
+static MATRIX_DIRECTIVE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^(?:#|//)\s*@matrix\s+([^:\s]+)\s*:\s*(.+?)\s*$")
+        .expect("Failed to compile matrix directive regex")
+});
+
+/// A header and the values to send it with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MatrixDirective {
+    /// Header name to vary (e.g., "Accept")
+    pub header: String,
+
+    /// Values to send, one execution each
+    pub values: Vec<String>,
+}
+
+/// A single expanded request in a matrix.
+#[derive(Debug, Clone)]
+pub struct MatrixRun {
+    /// The varied headers, e.g. "Accept: application/xml"
+    pub label: String,
+
+    /// The request with the varied headers applied
+    pub request: HttpRequest,
+}
+
+/// The outcome of one matrix execution.
+#[derive(Debug)]
+pub struct MatrixResult {
+    /// The varied headers, e.g. "Accept: application/xml"
+    pub label: String,
+
+    /// The response, or the error that prevented one
+    pub result: Result<HttpResponse, RequestError>,
+}
+
+/// Parses a `# @matrix` directive from a single line.
+///
+/// # Arguments
+///
+/// * `line` - A line from an .http file
+///
+/// # Returns
+///
+/// `Some(MatrixDirective)` if the line is a matrix directive with at least one
+/// value, `None` otherwise.
+///
+/// # Examples
+///
+/// ```
+/// use rest_client::executor::matrix::parse_matrix_directive;
+///
+/// let directive = parse_matrix_directive("# @matrix Accept: application/json, application/xml").unwrap();
+/// assert_eq!(directive.header, "Accept");
+/// assert_eq!(directive.values, vec!["application/json", "application/xml"]);
+/// ```
+pub fn parse_matrix_directive(line: &str) -> Option<MatrixDirective> {
+    let captures = MATRIX_DIRECTIVE_REGEX.captures(line.trim())?;
+
+    let values: Vec<String> = captures[2]
+        .split(',')
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+        .collect();
+
+    if values.is_empty() {
+        return None;
+    }
+
+    Some(MatrixDirective {
+        header: captures[1].to_string(),
+        values,
+    })
+}
+
+/// Collects the matrix directives of a request block.
+///
+/// Only lines before the body are considered, so a body line that happens to
+/// look like a directive is not treated as one.
+///
+/// # Arguments
+///
+/// * `lines` - Vector of (line_number, line_content) tuples for one request
+///
+/// # Returns
+///
+/// The directives in the order they appear.
+pub fn parse_matrix_directives(lines: &[(usize, &str)]) -> Vec<MatrixDirective> {
+    let mut directives = Vec::new();
+    let mut past_request_line = false;
+
+    for (_, line) in lines {
+        let trimmed = line.trim();
+
+        if let Some(directive) = parse_matrix_directive(trimmed) {
+            directives.push(directive);
+            continue;
+        }
+
+        let is_comment = trimmed.starts_with('#') || trimmed.starts_with("//");
+        if trimmed.is_empty() && past_request_line {
+            break;
+        }
+        if !trimmed.is_empty() && !is_comment {
+            past_request_line = true;
+        }
+    }
+
+    directives
+}
+
+/// Expands a request into one request per combination of matrix values.
+///
+/// Existing headers with the same name (compared case-insensitively) are
+/// replaced. With no directives, the request is returned unchanged with an
+/// empty label.
+///
+/// # Arguments
+///
+/// * `request` - The request to expand
+/// * `directives` - The matrix directives to apply
+///
+/// # Returns
+///
+/// The expanded runs, in directive and value order.
+pub fn expand_matrix(request: &HttpRequest, directives: &[MatrixDirective]) -> Vec<MatrixRun> {
+    let mut runs = vec![MatrixRun {
+        label: String::new(),
+        request: request.clone(),
+    }];
+
+    for directive in directives {
+        runs = runs
+            .into_iter()
+            .flat_map(|run| {
+                directive.values.iter().map(move |value| {
+                    let mut request = run.request.clone();
+                    request
+                        .headers
+                        .retain(|name, _| !name.eq_ignore_ascii_case(&directive.header));
+                    request.add_header(directive.header.clone(), value.clone());
+
+                    let varied = format!("{}: {}", directive.header, value);
+                    let label = if run.label.is_empty() {
+                        varied
+                    } else {
+                        format!("{}; {}", run.label, varied)
+                    };
+
+                    MatrixRun { label, request }
+                })
+            })
+            .collect();
+    }
+
+    runs
+}
+
+/// Executes every combination of a request's matrix values.
+///
+/// A failed execution is recorded in its result and does not stop the
+/// remaining runs.
+///
+/// # Arguments
+///
+/// * `request` - The resolved request to send
+/// * `directives` - The matrix directives to apply
+/// * `execute` - Executes a single request
+///
+/// # Returns
+///
+/// One result per expanded request, in the same order as [`expand_matrix`].
+pub fn run_matrix<F>(
+    request: &HttpRequest,
+    directives: &[MatrixDirective],
+    mut execute: F,
+) -> Vec<MatrixResult>
+where
+    F: FnMut(&HttpRequest) -> Result<HttpResponse, RequestError>,
+{
+    expand_matrix(request, directives)
+        .into_iter()
+        .map(|run| MatrixResult {
+            result: execute(&run.request),
+            label: run.label,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::request::HttpMethod;
+
+    fn request() -> HttpRequest {
+        HttpRequest::new(
+            "test".to_string(),
+            HttpMethod::GET,
+            "https://api.example.com/users/1".to_string(),
+        )
+    }
+
+    #[test]
+    fn test_parse_matrix_directive() {
+        let directive = parse_matrix_directive("// @matrix Accept-Language: en, fr ,").unwrap();
+        assert_eq!(directive.header, "Accept-Language");
+        assert_eq!(directive.values, vec!["en", "fr"]);
+
+        assert!(parse_matrix_directive("# @matrix Accept:").is_none());
+        assert!(parse_matrix_directive("# @matrix Accept application/json").is_none());
+        assert!(parse_matrix_directive("Accept: application/json").is_none());
+    }
+
+    #[test]
+    fn test_parse_matrix_directives_stops_at_body() {
+        let lines = vec![
+            (1, "# @matrix Accept: application/json, application/xml"),
+            (2, "POST https://api.example.com/users"),
+            (3, "// @matrix Accept-Language: en, fr"),
+            (4, ""),
+            (5, "# @matrix X-Ignored: a, b"),
+        ];
+
+        let directives = parse_matrix_directives(&lines);
+        assert_eq!(directives.len(), 2);
+        assert_eq!(directives[1].header, "Accept-Language");
+    }
+
+    #[test]
+    fn test_expand_matrix_replaces_header() {
+        let mut request = request();
+        request.add_header("accept".to_string(), "*/*".to_string());
+        let directives = vec![MatrixDirective {
+            header: "Accept".to_string(),
+            values: vec![
+                "application/json".to_string(),
+                "application/xml".to_string(),
+            ],
+        }];
+
+        let runs = expand_matrix(&request, &directives);
+
+        assert_eq!(runs.len(), 2);
+        assert_eq!(runs[0].label, "Accept: application/json");
+        assert_eq!(runs[1].request.headers.len(), 1);
+        assert_eq!(runs[1].request.headers["Accept"], "application/xml");
+    }
+
+    #[test]
+    fn test_run_matrix_combines_directives() {
+        let directives = vec![
+            parse_matrix_directive("# @matrix Accept: application/json, application/xml").unwrap(),
+            parse_matrix_directive("# @matrix Accept-Language: en, fr").unwrap(),
+        ];
+
+        let results = run_matrix(&request(), &directives, |request| {
+            if request.headers["Accept-Language"] == "fr" {
+                return Err(RequestError::Timeout);
+            }
+            Ok(HttpResponse::new(200, "OK".to_string()))
+        });
+
+        let labels: Vec<&str> = results.iter().map(|r| r.label.as_str()).collect();
+        assert_eq!(
+            labels,
+            vec![
+                "Accept: application/json; Accept-Language: en",
+                "Accept: application/json; Accept-Language: fr",
+                "Accept: application/xml; Accept-Language: en",
+                "Accept: application/xml; Accept-Language: fr",
+            ]
+        );
+        assert!(results[0].result.is_ok());
+        assert!(results[1].result.is_err());
+    }
+}
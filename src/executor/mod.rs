@@ -9,26 +9,44 @@
 //! affects the REST client's ability to distinguish between different HTTP
 //! response codes (200 OK vs 404 Not Found, etc.).
 
+pub mod address_guard;
+pub mod audit;
+pub mod batch;
 pub mod cancellation;
+pub mod charset;
 pub mod config;
+pub mod cookies;
+pub mod decompression;
+pub mod encoding;
 pub mod error;
+pub mod matrix;
+pub mod proxy;
+pub mod redirect;
+pub mod retry;
 pub mod timing;
 
 // Native HTTP executor for LSP server (non-WASM)
 #[cfg(feature = "lsp")]
 pub mod native;
 
+// Piping responses through shell commands, which WASM can't spawn
+#[cfg(feature = "lsp")]
+pub mod pipe;
+
 pub use cancellation::{CancelError, RequestHandle, RequestTracker, SharedRequestTracker};
 pub use config::ExecutionConfig;
-pub use error::RequestError;
+pub use error::{ErrorCategory, ErrorExplanation, RequestError};
 pub use timing::{format_timing_breakdown, format_timing_compact, TimingCheckpoints};
 
 #[cfg(feature = "lsp")]
-pub use native::execute_request_native;
+pub use native::{execute_request_native, execute_request_native_with_progress};
 
+use crate::auth::digest;
 use crate::graphql::parser::{is_graphql_request, parse_graphql_request};
+use crate::models::headers::Headers;
 use crate::models::request::{HttpMethod, HttpRequest};
 use crate::models::response::HttpResponse;
+use std::borrow::Cow;
 use std::sync::{Arc, Mutex};
 use zed_extension_api::http_client::{self, HttpMethod as ZedHttpMethod};
 
@@ -130,7 +148,7 @@ pub fn get_active_request_ids() -> Vec<String> {
 /// # Arguments
 ///
 /// * `request` - The HTTP request to execute
-/// * `config` - Execution configuration (currently unused due to API limitations)
+/// * `config` - Execution configuration
 ///
 /// # Returns
 ///
@@ -158,9 +176,9 @@ pub fn get_active_request_ids() -> Vec<String> {
 /// ```
 pub fn execute_request(
     request: &HttpRequest,
-    _config: &ExecutionConfig,
+    config: &ExecutionConfig,
 ) -> Result<HttpResponse, RequestError> {
-    execute_request_internal(request, _config, None)
+    execute_request_internal(request, config, None)
 }
 
 /// Executes an HTTP request with cancellation support.
@@ -225,15 +243,55 @@ pub fn execute_request_with_cancellation(
 }
 
 /// Internal implementation of execute_request with optional cancellation support.
+///
+/// A request with `# @digest` credentials that is answered with a Digest
+/// challenge is sent once more with the computed Authorization header.
 fn execute_request_internal(
     request: &HttpRequest,
-    _config: &ExecutionConfig,
+    config: &ExecutionConfig,
+    cancelled_flag: Option<Arc<Mutex<bool>>>,
+) -> Result<HttpResponse, RequestError> {
+    let response = send_and_audit(request, config, cancelled_flag.clone())?;
+
+    match digest::authorize_challenge(request, &response) {
+        Ok(Some(authorized)) => send_and_audit(&authorized, config, cancelled_flag),
+        Ok(None) => Ok(response),
+        Err(e) => {
+            eprintln!("Warning: Failed to answer Digest challenge: {}", e);
+            Ok(response)
+        }
+    }
+}
+
+/// Sends a request, appending it and its outcome to the audit log if one
+/// is configured.
+fn send_and_audit(
+    request: &HttpRequest,
+    config: &ExecutionConfig,
+    cancelled_flag: Option<Arc<Mutex<bool>>>,
+) -> Result<HttpResponse, RequestError> {
+    let mut sent = None;
+    let mut result = send_request(request, config, cancelled_flag, &mut sent);
+
+    if let Some(path) = &config.audit_log {
+        audit::record_audit_entry(path, sent.as_ref().unwrap_or(request), &mut result);
+    }
+
+    result
+}
+
+/// Sends a request, recording in `sent` the request as it went out (final
+/// URL, headers, and body) once it has been assembled.
+fn send_request(
+    request: &HttpRequest,
+    config: &ExecutionConfig,
     cancelled_flag: Option<Arc<Mutex<bool>>>,
+    sent: &mut Option<HttpRequest>,
 ) -> Result<HttpResponse, RequestError> {
     // Check if request was cancelled before starting
     if let Some(ref flag) = cancelled_flag {
         if *flag.lock().unwrap() {
-            return Err(RequestError::BuildError("Request cancelled".to_string()));
+            return Err(RequestError::Cancelled);
         }
     }
 
@@ -241,13 +299,42 @@ fn execute_request_internal(
     let is_https = request.url.starts_with("https://");
     let mut timing_checkpoints = TimingCheckpoints::new(is_https);
 
+    // Percent-encode unsafe characters introduced by variable substitution
+    let url = if config.encode_url {
+        encoding::encode_url(&request.url)
+    } else {
+        request.url.clone()
+    };
+
     // Validate URL and check protocol
-    validate_url(&request.url)?;
+    validate_url_length(&url, config.max_url_length)?;
+    validate_url(&url)?;
+
+    // Reject loopback, link-local, and metadata addresses if configured
+    if config.block_internal_addresses {
+        address_guard::check_internal_address(&url, &config.internal_address_allowlist)?;
+    }
+
+    // Reject malformed headers (e.g., CR/LF from substituted variables)
+    request.validate()?;
+
+    // Zed's HTTP client takes the whole body at once, so body files of any
+    // size are read
+    let request = &*load_body_file(request, u64::MAX)?;
+
+    // Zed's HTTP client takes a single byte body, so multipart parts are
+    // only assembled by the language server's executor
+    if request.multipart.is_some() {
+        return Err(RequestError::BuildError(
+            "Multipart bodies are only supported when sending through the language server"
+                .to_string(),
+        ));
+    }
 
     // Check cancellation again
     if let Some(ref flag) = cancelled_flag {
         if *flag.lock().unwrap() {
-            return Err(RequestError::BuildError("Request cancelled".to_string()));
+            return Err(RequestError::Cancelled);
         }
     }
 
@@ -262,6 +349,54 @@ fn execute_request_internal(
     } else {
         (request.body.clone(), request.headers.clone())
     };
+    let mut processed_headers = processed_headers;
+    apply_default_accept_language(
+        &mut processed_headers,
+        config.default_accept_language.as_deref(),
+    );
+
+    // Encode the body in its `# @encoding` charset (use processed body for GraphQL)
+    let body_bytes = processed_body
+        .as_deref()
+        .map(|body| {
+            charset::encode_request_body(
+                body,
+                request.body_encoding.as_deref(),
+                &mut processed_headers,
+            )
+        })
+        .transpose()?;
+
+    // Files that aren't valid UTF-8 are sent byte for byte
+    let body_bytes = match &request.body_file {
+        Some(path) => Some(std::fs::read(path).map_err(|e| {
+            RequestError::BuildError(format!(
+                "Failed to read body file '{}': {}",
+                path.display(),
+                e
+            ))
+        })?),
+        None => body_bytes,
+    };
+
+    // `# @aws` signs the request as it will go out
+    if let Some(aws) = &request.aws_sigv4 {
+        aws.sign(
+            request.method.as_str(),
+            &url,
+            &mut processed_headers,
+            Some(body_bytes.as_deref().unwrap_or_default()),
+            chrono::Utc::now(),
+        )
+        .map_err(|e| RequestError::BuildError(format!("Failed to sign request: {}", e)))?;
+    }
+
+    *sent = Some(HttpRequest {
+        url: url.clone(),
+        headers: processed_headers.clone(),
+        body: processed_body.clone(),
+        ..request.clone()
+    });
 
     // Convert our HttpMethod to Zed's HttpMethod
     let method = match request.method {
@@ -288,24 +423,22 @@ fn execute_request_internal(
     timing_checkpoints.mark_client_start();
 
     // Build the request using Zed's HTTP client API
-    let mut req_builder = http_client::HttpRequest::builder()
-        .method(method)
-        .url(&request.url);
+    let mut req_builder = http_client::HttpRequest::builder().method(method).url(&url);
 
     // Add headers (use processed headers for GraphQL)
     for (name, value) in &processed_headers {
         req_builder = req_builder.header(name, value);
     }
 
-    // Add body if present (use processed body for GraphQL)
-    if let Some(body) = &processed_body {
-        req_builder = req_builder.body(body.as_bytes().to_vec());
+    // Add body if present
+    if let Some(body) = body_bytes {
+        req_builder = req_builder.body(body);
     }
 
     // Check cancellation before building
     if let Some(ref flag) = cancelled_flag {
         if *flag.lock().unwrap() {
-            return Err(RequestError::BuildError("Request cancelled".to_string()));
+            return Err(RequestError::Cancelled);
         }
     }
 
@@ -317,17 +450,37 @@ fn execute_request_internal(
     // Check cancellation before executing
     if let Some(ref flag) = cancelled_flag {
         if *flag.lock().unwrap() {
-            return Err(RequestError::BuildError("Request cancelled".to_string()));
+            return Err(RequestError::Cancelled);
         }
     }
 
-    // Mark when request is about to be sent
-    timing_checkpoints.mark_request_sent();
+    // Execute the request, retrying connection failures with exponential backoff
+    let max_retries = config.retries_for(request);
+    let mut attempt = 0;
+    let response = loop {
+        // Mark when request is about to be sent
+        timing_checkpoints.mark_request_sent();
+
+        match http_request.fetch().map_err(RequestError::NetworkError) {
+            Ok(response) => break response,
+            Err(e) if attempt < max_retries && e.is_retryable() => {
+                std::thread::sleep(config.retry_backoff(attempt));
+                attempt += 1;
+            }
+            Err(e) => return Err(e),
+        }
 
-    // Execute the request
-    let response = http_request
-        .fetch()
-        .map_err(|e| RequestError::NetworkError(e))?;
+        // Check cancellation before retrying
+        if let Some(ref flag) = cancelled_flag {
+            if *flag.lock().unwrap() {
+                return Err(RequestError::Cancelled);
+            }
+        }
+
+        // Only the final attempt is timed
+        timing_checkpoints = TimingCheckpoints::new(is_https);
+        timing_checkpoints.mark_client_start();
+    };
 
     // Mark when first byte received (response arrived)
     timing_checkpoints.mark_first_byte_received();
@@ -335,7 +488,7 @@ fn execute_request_internal(
     // Check cancellation after execution
     if let Some(ref flag) = cancelled_flag {
         if *flag.lock().unwrap() {
-            return Err(RequestError::BuildError("Request cancelled".to_string()));
+            return Err(RequestError::Cancelled);
         }
     }
 
@@ -346,6 +499,15 @@ fn execute_request_internal(
     let timing = timing_checkpoints.to_request_timing();
     let total_duration = timing.total();
 
+    // Zed's HTTP client can't abort an in-flight request, so an explicit
+    // `# @timeout` is enforced once it completes. The configured timeout
+    // isn't: that would throw away a slow but successful response.
+    if let Some(timeout_ms) = request.timeout_override {
+        if total_duration > std::time::Duration::from_millis(timeout_ms) {
+            return Err(RequestError::Timeout);
+        }
+    }
+
     // KNOWN LIMITATION: Zed's WASM HTTP client API does not return HTTP status codes
     // The zed_extension_api::http_client module only provides headers and body.
     // As a result, we cannot distinguish between 200 OK, 201 Created, 204 No Content, etc.
@@ -361,28 +523,25 @@ fn execute_request_internal(
     let status_text = "OK (assumed - Zed API limitation)".to_string();
 
     // Extract headers from response
-    let mut headers = std::collections::HashMap::new();
+    let mut headers = Headers::new();
     for (name, value) in &response.headers {
-        headers.insert(name.clone(), value.clone());
+        headers.append(name.clone(), value.clone());
     }
 
     // Get response body
     let body_bytes = response.body.clone();
 
-    // Calculate response size (headers + body)
-    let headers_size: usize = headers
-        .iter()
-        .map(|(k, v)| k.len() + v.len() + 4) // +4 for ": " and "\r\n"
-        .sum();
-    let total_size = headers_size + body_bytes.len();
-
     // Build and return the HttpResponse
     let mut http_response = HttpResponse::new(status_code, status_text);
     http_response.headers = headers;
-    http_response.body = body_bytes;
+    http_response.set_body(body_bytes);
     http_response.duration = total_duration;
     http_response.timing = timing;
-    http_response.size = total_size;
+
+    // Zed's HTTP client doesn't decode compressed bodies
+    if config.decompress_responses {
+        decompression::decompress_response(&mut http_response)?;
+    }
 
     Ok(http_response)
 }
@@ -404,8 +563,8 @@ fn execute_request_internal(
 /// A tuple of (processed_body, processed_headers) ready for HTTP transport
 fn process_graphql_request(
     body: &str,
-    headers: &std::collections::HashMap<String, String>,
-) -> Result<(Option<String>, std::collections::HashMap<String, String>), RequestError> {
+    headers: &Headers,
+) -> Result<(Option<String>, Headers), RequestError> {
     // Parse the GraphQL request
     let graphql_request = parse_graphql_request(body)
         .map_err(|e| RequestError::BuildError(format!("GraphQL parsing error: {}", e)))?;
@@ -438,6 +597,20 @@ fn process_graphql_request(
 ///
 /// `Ok(())` if the URL is valid, or `Err(RequestError)` if invalid.
 fn validate_url(url: &str) -> Result<(), RequestError> {
+    parse_http_url(url).map(|_| ())
+}
+
+/// Parses an HTTP or HTTPS URL.
+///
+/// # Arguments
+///
+/// * `url` - The URL string to parse
+///
+/// # Returns
+///
+/// `Ok(Url)` with the parsed URL, or `Err(RequestError)` if it is malformed
+/// or uses a protocol other than HTTP or HTTPS.
+pub(crate) fn parse_http_url(url: &str) -> Result<url::Url, RequestError> {
     // Parse the URL to ensure it's well-formed
     let parsed = url::Url::parse(url).map_err(|e| RequestError::InvalidUrl(e.to_string()))?;
 
@@ -450,6 +623,85 @@ fn validate_url(url: &str) -> Result<(), RequestError> {
         )));
     }
 
+    Ok(parsed)
+}
+
+/// Adds the configured `Accept-Language` header unless the request sets one.
+///
+/// A user-set `Accept-Language` (in any letter case) is never overwritten.
+pub fn apply_default_accept_language(headers: &mut Headers, default_language: Option<&str>) {
+    let Some(language) = default_language else {
+        return;
+    };
+
+    if !headers
+        .keys()
+        .any(|name| name.eq_ignore_ascii_case("accept-language"))
+    {
+        headers.insert("Accept-Language".to_string(), language.to_string());
+    }
+}
+
+/// Reads a request's body file when the request is sent.
+///
+/// The parser only records the path of a `< path` body. A file of at most
+/// `threshold` bytes that is valid UTF-8 is read into `body`, so it is
+/// encoded and recorded like an inline body. Larger files and files that
+/// aren't valid UTF-8 stay in `body_file` and are sent from disk.
+///
+/// # Arguments
+///
+/// * `request` - The request about to be sent
+/// * `threshold` - Size in bytes above which the file is left on disk
+///
+/// # Returns
+///
+/// The request with its body read, or `Err(RequestError::BuildError)` if the
+/// file can't be read.
+pub(crate) fn load_body_file(
+    request: &HttpRequest,
+    threshold: u64,
+) -> Result<Cow<'_, HttpRequest>, RequestError> {
+    let Some(path) = &request.body_file else {
+        return Ok(Cow::Borrowed(request));
+    };
+    let read_error = |e: std::io::Error| {
+        RequestError::BuildError(format!(
+            "Failed to read body file '{}': {}",
+            path.display(),
+            e
+        ))
+    };
+
+    if std::fs::metadata(path).map_err(read_error)?.len() > threshold {
+        return Ok(Cow::Borrowed(request));
+    }
+    match String::from_utf8(std::fs::read(path).map_err(read_error)?) {
+        Ok(body) => Ok(Cow::Owned(HttpRequest {
+            body: Some(body),
+            body_file: None,
+            ..request.clone()
+        })),
+        Err(_) => Ok(Cow::Borrowed(request)),
+    }
+}
+
+/// Rejects URLs longer than `max_length` characters before they are sent.
+///
+/// # Arguments
+///
+/// * `url` - The fully substituted URL
+/// * `max_length` - Maximum allowed length in characters
+///
+/// # Returns
+///
+/// `Ok(())` if the URL fits, or `Err(RequestError::UrlTooLong)` otherwise.
+pub fn validate_url_length(url: &str, max_length: usize) -> Result<(), RequestError> {
+    let len = url.chars().count();
+    if len > max_length {
+        return Err(RequestError::UrlTooLong(len));
+    }
+
     Ok(())
 }
 
@@ -457,6 +709,40 @@ fn validate_url(url: &str) -> Result<(), RequestError> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_load_body_file() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("user.json"), r#"{"name": "Ada"}"#).unwrap();
+        std::fs::write(dir.path().join("image.png"), [0x89, b'P', b'N', b'G', 0xff]).unwrap();
+        let request = |file: &str| {
+            let mut request = HttpRequest::new(
+                "test".to_string(),
+                HttpMethod::POST,
+                "https://api.example.com/upload".to_string(),
+            );
+            request.body_file = Some(dir.path().join(file));
+            request.raw_body = true;
+            request
+        };
+
+        let text = request("user.json");
+        let loaded = load_body_file(&text, 64).unwrap();
+        assert_eq!(loaded.body.as_deref(), Some(r#"{"name": "Ada"}"#));
+        assert_eq!(loaded.body_file, None);
+
+        // Files over the threshold and binary files stay on disk
+        for (file, threshold) in [("user.json", 8), ("image.png", 64)] {
+            let request = request(file);
+            let loaded = load_body_file(&request, threshold).unwrap();
+            assert_eq!(loaded.body, None);
+            assert_eq!(loaded.body_file, Some(dir.path().join(file)));
+        }
+
+        // A missing file only fails once the request is sent
+        let err = load_body_file(&request("missing.json"), 64).unwrap_err();
+        assert!(err.to_string().contains("missing.json"));
+    }
+
     #[test]
     fn test_validate_url_valid_http() {
         assert!(validate_url("http://example.com").is_ok());
@@ -489,6 +775,50 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_validate_url_length_within_limit() {
+        assert!(validate_url_length("https://example.com/users", 8192).is_ok());
+        assert!(validate_url_length("https://example.com", 19).is_ok());
+    }
+
+    #[test]
+    fn test_validate_url_length_after_substitution() {
+        use crate::variables::substitution::{substitute_variables, VariableContext};
+
+        let mut context = VariableContext::new(std::path::PathBuf::from("/workspace"));
+        context
+            .file_variables
+            .insert("ids".to_string(), "1,".repeat(5000));
+
+        let url = substitute_variables("https://example.com/users?ids={{ids}}", &context).unwrap();
+        match validate_url_length(&url, 8192) {
+            Err(RequestError::UrlTooLong(len)) => assert_eq!(len, url.len()),
+            other => panic!("Expected UrlTooLong error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_apply_default_accept_language() {
+        let mut headers = Headers::new();
+        apply_default_accept_language(&mut headers, Some("fr-FR"));
+        assert_eq!(headers.get("Accept-Language"), Some(&"fr-FR".to_string()));
+
+        let mut headers = Headers::new();
+        apply_default_accept_language(&mut headers, None);
+        assert!(headers.is_empty());
+    }
+
+    #[test]
+    fn test_apply_default_accept_language_keeps_user_value() {
+        let mut headers = Headers::new();
+        headers.insert("accept-language".to_string(), "de-DE".to_string());
+
+        apply_default_accept_language(&mut headers, Some("fr-FR"));
+
+        assert_eq!(headers.len(), 1);
+        assert_eq!(headers.get("accept-language"), Some(&"de-DE".to_string()));
+    }
+
     #[test]
     fn test_global_tracker_functions() {
         // Test getting active count (should work even with no requests)
@@ -5,68 +5,384 @@
 //!
 //! This is separate from the WASM executor which uses zed_extension_api::http_client.
 
+use crate::auth::digest::authorize_challenge;
+use crate::config::get_config;
+use crate::executor::address_guard::check_internal_address;
+use crate::executor::audit::record_audit_entry;
+use crate::executor::charset::encode_request_body;
+use crate::executor::cookies::session_jar;
+use crate::executor::encoding::encode_url;
 use crate::executor::error::RequestError;
+use crate::executor::proxy::resolve_proxy;
+use crate::executor::redirect::{
+    is_redirect_status, is_same_origin, redirect_method, resolve_location, BODY_HEADERS,
+    SENSITIVE_HEADERS,
+};
+use crate::executor::retry::{is_retryable_status, retry_after};
 use crate::executor::timing::TimingCheckpoints;
+use crate::executor::{
+    apply_default_accept_language, load_body_file, validate_url_length, ExecutionConfig,
+};
+use crate::models::headers::Headers;
+use crate::models::multipart::{content_type_for_path, MultipartBody, MultipartPart};
 use crate::models::request::{HttpMethod, HttpRequest};
-use crate::models::response::HttpResponse;
+use crate::models::response::{is_chunked, HttpResponse};
+use chrono::Utc;
+use reqwest::multipart::{Form, Part};
 use std::time::Instant;
 
 /// Execute an HTTP request using reqwest (native client)
 ///
 /// This function is only available when the "lsp" feature is enabled,
-/// as it uses reqwest which doesn't compile to WASM.
+/// as it uses reqwest which doesn't compile to WASM. The request and its
+/// outcome are appended to the audit log, if one is configured. A request
+/// with `# @digest` credentials that is answered with a Digest challenge is
+/// sent once more with the computed Authorization header.
 pub async fn execute_request_native(request: &HttpRequest) -> Result<HttpResponse, RequestError> {
+    execute_request_native_with_progress(request, &mut |_| {}).await
+}
+
+/// Execute an HTTP request using reqwest, reporting progress as it streams in
+///
+/// Behaves like [`execute_request_native`], but a `Transfer-Encoding: chunked`
+/// response body is read incrementally and each complete line is passed to
+/// `on_line` (without its line terminator) as soon as it arrives. The returned
+/// response still carries the full body.
+///
+/// # Arguments
+///
+/// * `request` - The HTTP request to execute
+/// * `on_line` - Called with each line of a chunked response body
+pub async fn execute_request_native_with_progress(
+    request: &HttpRequest,
+    on_line: &mut (dyn FnMut(&str) + Send),
+) -> Result<HttpResponse, RequestError> {
+    let response = send_and_audit_native(request, on_line).await?;
+
+    match authorize_challenge(request, &response) {
+        Ok(Some(authorized)) => send_and_audit_native(&authorized, on_line).await,
+        Ok(None) => Ok(response),
+        Err(e) => {
+            eprintln!("Warning: Failed to answer Digest challenge: {}", e);
+            Ok(response)
+        }
+    }
+}
+
+/// Sends a request with reqwest, appending it and its outcome to the audit
+/// log if one is configured.
+async fn send_and_audit_native(
+    request: &HttpRequest,
+    on_line: &mut (dyn FnMut(&str) + Send),
+) -> Result<HttpResponse, RequestError> {
+    let mut sent = None;
+    let mut result = send_request_native(request, &mut sent, on_line).await;
+
+    if let Some(path) = &get_config().audit_log {
+        record_audit_entry(path, sent.as_ref().unwrap_or(request), &mut result);
+    }
+
+    result
+}
+
+/// Sends a request with reqwest, recording in `sent` the request as it went
+/// out (final URL and headers) once it has been assembled. Lines of a chunked
+/// response body are passed to `on_line` as they arrive.
+async fn send_request_native(
+    request: &HttpRequest,
+    sent: &mut Option<HttpRequest>,
+    on_line: &mut (dyn FnMut(&str) + Send),
+) -> Result<HttpResponse, RequestError> {
     let start_time = Instant::now();
     let is_https = request.url.starts_with("https://");
     let mut timing_checkpoints = TimingCheckpoints::new(is_https);
 
-    // Convert our HttpMethod to reqwest's Method
-    let method = match request.method {
-        HttpMethod::GET => reqwest::Method::GET,
-        HttpMethod::POST => reqwest::Method::POST,
-        HttpMethod::PUT => reqwest::Method::PUT,
-        HttpMethod::DELETE => reqwest::Method::DELETE,
-        HttpMethod::PATCH => reqwest::Method::PATCH,
-        HttpMethod::HEAD => reqwest::Method::HEAD,
-        HttpMethod::OPTIONS => reqwest::Method::OPTIONS,
-        HttpMethod::TRACE => reqwest::Method::TRACE,
-        HttpMethod::CONNECT => reqwest::Method::CONNECT,
+    let config = get_config();
+    let url = if config.encode_url {
+        encode_url(&request.url)
+    } else {
+        request.url.clone()
     };
 
+    // Reject over-long URLs before building the request
+    validate_url_length(&url, config.max_url_length)?;
+    request.validate()?;
+
+    // Small text files are read; larger and binary ones are streamed below
+    let request = &*load_body_file(request, config.large_body_threshold)?;
+
+    // Reject loopback, link-local, and metadata addresses if configured
+    if config.block_internal_addresses {
+        check_internal_address(&url, &config.internal_address_allowlist)?;
+    }
+
     // Mark client start
     timing_checkpoints.mark_client_start();
 
     // Build the request
-    let client = reqwest::Client::builder()
-        .timeout(std::time::Duration::from_secs(30))
+    let timeout = request
+        .timeout_override
+        .map(std::time::Duration::from_millis)
+        .unwrap_or_else(|| config.timeout_duration());
+    let mut client_builder = reqwest::Client::builder().timeout(timeout);
+
+    // Route through `proxyUrl` or the environment's proxy, except for hosts
+    // in `excludeHostsFromProxy`. Checked per URL, so redirects are covered.
+    let proxy_url = config.proxy_url.clone();
+    let exclude_hosts = config.exclude_hosts_from_proxy.clone();
+    client_builder = client_builder
+        .no_proxy()
+        .proxy(reqwest::Proxy::custom(move |url| {
+            resolve_proxy(url, proxy_url.as_deref(), &exclude_hosts, |name| {
+                std::env::var(name).ok()
+            })
+        }));
+
+    // Redirects are followed below rather than by reqwest, so the method and
+    // body for each hop follow `preserveMethodOnRedirect`
+    client_builder = client_builder.redirect(reqwest::redirect::Policy::none());
+
+    // reqwest decodes compressed bodies itself unless told not to
+    if !config.decompress_responses {
+        client_builder = client_builder.no_gzip().no_brotli().no_deflate();
+    }
+
+    let client = client_builder
         .build()
         .map_err(|e| RequestError::BuildError(e.to_string()))?;
 
-    let mut req_builder = client.request(method, &request.url);
+    let mut req_builder = client.request(to_reqwest_method(request.method), &url);
+
+    // Add headers, plus the default Accept-Language if the request has none
+    let mut headers = request.headers.clone();
+    apply_default_accept_language(&mut headers, config.default_accept_language.as_deref());
+
+    // Send cookies stored from earlier responses, unless the request sets
+    // its own Cookie header or opts out with `# @no-cookies`
+    let user_cookie = headers.contains_key("Cookie");
+    if !request.no_cookies && !user_cookie {
+        if let Some(cookie) = session_jar().lock().unwrap().cookie_header(&url) {
+            headers.insert("Cookie".to_string(), cookie);
+        }
+    }
+
+    // reqwest sets the multipart Content-Type, including the boundary
+    if request.multipart.is_some() {
+        headers.retain(|name, _| !name.eq_ignore_ascii_case("Content-Type"));
+    }
 
-    // Add headers
-    for (name, value) in &request.headers {
+    // Encode the body in its `# @encoding` charset, if any
+    let body = request
+        .body
+        .as_deref()
+        .map(|body| encode_request_body(body, request.body_encoding.as_deref(), &mut headers))
+        .transpose()?;
+
+    // A large or binary `< path` body is streamed from disk rather than read
+    // into memory
+    let body_file = match &request.body_file {
+        Some(path) => {
+            let read_error = |e: std::io::Error| {
+                RequestError::BuildError(format!(
+                    "Failed to read body file '{}': {}",
+                    path.display(),
+                    e
+                ))
+            };
+            let file = tokio::fs::File::open(path).await.map_err(read_error)?;
+            let len = file.metadata().await.map_err(read_error)?.len();
+            if !headers
+                .keys()
+                .any(|name| name.eq_ignore_ascii_case("Content-Length"))
+            {
+                headers.insert("Content-Length".to_string(), len.to_string());
+            }
+            Some(file)
+        }
+        None => None,
+    };
+
+    // `# @aws` signs the request as it will go out. Streamed and multipart
+    // bodies can't be hashed up front, so they are sent as an unsigned payload.
+    if let Some(aws) = &request.aws_sigv4 {
+        let payload = if body_file.is_some() || request.multipart.is_some() {
+            None
+        } else {
+            Some(body.as_deref().unwrap_or_default())
+        };
+        aws.sign(
+            request.method.as_str(),
+            &url,
+            &mut headers,
+            payload,
+            Utc::now(),
+        )
+        .map_err(|e| RequestError::BuildError(format!("Failed to sign request: {}", e)))?;
+    }
+
+    *sent = Some(HttpRequest {
+        url: url.clone(),
+        headers: headers.clone(),
+        ..request.clone()
+    });
+
+    for (name, value) in &headers {
         req_builder = req_builder.header(name, value);
     }
 
+    // Kept to resend on a redirect that preserves the method
+    let resend_body = body.clone();
+
     // Add body if present
-    if let Some(body) = &request.body {
-        req_builder = req_builder.body(body.clone());
+    if let Some(body) = body {
+        req_builder = req_builder.body(body);
+    }
+    if let Some(file) = body_file {
+        req_builder = req_builder.body(file);
+    }
+
+    // Add multipart parts if present
+    if let Some(multipart) = &request.multipart {
+        req_builder = req_builder.multipart(build_multipart_form(multipart)?);
     }
 
-    // Mark request sent
-    timing_checkpoints.mark_request_sent();
+    // Execute the request, retrying connection failures, timeouts, and 429/503 responses.
+    // A `Retry-After` on those responses sets the wait, up to the configured cap.
+    let retry_config = ExecutionConfig::from_global_config();
+    let max_retries = retry_config.retries_for(request);
+    let mut attempt = 0;
+    let response = loop {
+        // Mark request sent
+        timing_checkpoints.mark_request_sent();
 
-    // Execute the request
-    let response = req_builder.send().await.map_err(|e| {
-        if e.is_timeout() {
-            RequestError::Timeout
-        } else if e.is_connect() {
-            RequestError::NetworkError(format!("Connection failed: {}", e))
+        // Multipart and streamed bodies can't be cloned, so those requests
+        // are sent once
+        let retry_builder = if attempt < max_retries {
+            req_builder.try_clone()
         } else {
-            RequestError::NetworkError(e.to_string())
+            None
+        };
+        let result = req_builder.send().await;
+        let Some(next_builder) = retry_builder else {
+            break result;
+        };
+
+        let delay = match &result {
+            Ok(response) if is_retryable_status(response.status().as_u16()) => {
+                let wait = retry_after(
+                    response.status().as_u16(),
+                    &collect_headers(response),
+                    Utc::now(),
+                );
+                retry_config.retry_delay(attempt, wait)
+            }
+            Ok(_) => break result,
+            Err(e) if !to_request_error(e).is_retryable() => break result,
+            Err(_) => retry_config.retry_backoff(attempt),
+        };
+        tokio::time::sleep(delay).await;
+
+        req_builder = next_builder;
+        attempt += 1;
+
+        // Only the final attempt is timed
+        timing_checkpoints = TimingCheckpoints::new(is_https);
+        timing_checkpoints.mark_client_start();
+    };
+    let mut response = response.map_err(|e| to_request_error(&e))?;
+
+    // Follow redirects as configured, recording the URLs visited. A redirect
+    // must not lead to an internal address either.
+    let mut redirect_chain = Vec::new();
+    let mut hop_url = url.clone();
+    let mut hop_method = request.method;
+    let mut hop_headers = headers;
+    let mut hop_body = resend_body;
+    let mut warnings = Vec::new();
+    while config.follow_redirects && is_redirect_status(response.status().as_u16()) {
+        let Some(location) = response
+            .headers()
+            .get(reqwest::header::LOCATION)
+            .and_then(|value| value.to_str().ok())
+        else {
+            break;
+        };
+
+        // The chain holds the original URL plus every redirect followed so far
+        let followed = redirect_chain.len().saturating_sub(1);
+        if followed >= config.max_redirects as usize {
+            return Err(RequestError::ProtocolError(format!(
+                "Too many redirects (more than {})",
+                config.max_redirects
+            )));
+        }
+
+        let status_code = response.status().as_u16();
+        let next_url = resolve_location(&hop_url, location)?;
+        if config.block_internal_addresses {
+            check_internal_address(&next_url, &config.internal_address_allowlist)?;
+        }
+
+        let next_method =
+            redirect_method(status_code, hop_method, config.preserve_method_on_redirect);
+        if next_method == hop_method && (request.body_file.is_some() || request.multipart.is_some())
+        {
+            // Streamed and multipart bodies can't be sent twice
+            warnings.push(format!(
+                "Not following {} redirect to {}: the request body can't be resent",
+                status_code, next_url
+            ));
+            break;
+        }
+        if next_method != hop_method {
+            hop_body = None;
+            hop_headers.retain(|name, _| {
+                !BODY_HEADERS
+                    .iter()
+                    .any(|header| name.eq_ignore_ascii_case(header))
+            });
+        }
+
+        // Credentials only go to the origin they were meant for
+        if !is_same_origin(&hop_url, &next_url) {
+            hop_headers.retain(|name, _| {
+                !SENSITIVE_HEADERS
+                    .iter()
+                    .any(|header| name.eq_ignore_ascii_case(header))
+            });
         }
-    })?;
+
+        if !request.no_cookies {
+            let mut jar = session_jar().lock().unwrap();
+            jar.store_response_cookies(&hop_url, &collect_headers(&response));
+            // Rebuild the jar's Cookie header for every hop, so cookies set
+            // by the redirect itself are sent on. A Cookie the user wrote
+            // is kept for as long as it stays on its origin.
+            if !user_cookie || !hop_headers.contains_key("Cookie") {
+                hop_headers.remove("Cookie");
+                if let Some(cookie) = jar.cookie_header(&next_url) {
+                    hop_headers.insert("Cookie".to_string(), cookie);
+                }
+            }
+        }
+
+        if redirect_chain.is_empty() {
+            redirect_chain.push(hop_url.clone());
+        }
+        redirect_chain.push(next_url.clone());
+
+        let mut hop_builder = client.request(to_reqwest_method(next_method), &next_url);
+        for (name, value) in &hop_headers {
+            hop_builder = hop_builder.header(name, value);
+        }
+        if let Some(body) = &hop_body {
+            hop_builder = hop_builder.body(body.clone());
+        }
+        response = hop_builder.send().await.map_err(|e| to_request_error(&e))?;
+
+        hop_url = next_url;
+        hop_method = next_method;
+    }
 
     // Mark first byte received
     timing_checkpoints.mark_first_byte_received();
@@ -80,19 +396,26 @@ pub async fn execute_request_native(request: &HttpRequest) -> Result<HttpRespons
         .to_string();
 
     // Extract headers
-    let mut response_headers = std::collections::HashMap::new();
-    for (name, value) in response.headers() {
-        if let Ok(value_str) = value.to_str() {
-            response_headers.insert(name.as_str().to_string(), value_str.to_string());
-        }
+    let response_headers = collect_headers(&response);
+
+    // Remember cookies for later requests in this session
+    if !request.no_cookies {
+        session_jar()
+            .lock()
+            .unwrap()
+            .store_response_cookies(response.url().as_str(), &response_headers);
     }
 
-    // Read response body
-    let body = response
-        .bytes()
-        .await
-        .map_err(|e| RequestError::NetworkError(e.to_string()))?
-        .to_vec();
+    // Read response body, streaming chunked bodies line by line
+    let body = if is_chunked(&response_headers) {
+        read_body_lines(response, on_line).await?
+    } else {
+        response
+            .bytes()
+            .await
+            .map_err(|e| RequestError::NetworkError(e.to_string()))?
+            .to_vec()
+    };
 
     // Mark response complete
     timing_checkpoints.mark_response_complete();
@@ -100,12 +423,13 @@ pub async fn execute_request_native(request: &HttpRequest) -> Result<HttpRespons
     // Convert timing checkpoints to RequestTiming
     let timing = timing_checkpoints.to_request_timing();
     let total_duration = timing.total();
+    let final_url = redirect_chain.last().cloned();
     let size = body.len()
         + response_headers
             .iter()
             .fold(0, |acc, (k, v)| acc + k.len() + v.len());
 
-    Ok(HttpResponse {
+    let mut response = HttpResponse {
         status_code,
         status_text,
         headers: response_headers,
@@ -113,23 +437,205 @@ pub async fn execute_request_native(request: &HttpRequest) -> Result<HttpRespons
         duration: total_duration,
         timing,
         size,
-    })
+        redirect_chain,
+        final_url,
+        original_encoding: None,
+        compressed_size: None,
+        warnings,
+        throughput_bps: None,
+    };
+    response.throughput_bps = response.download_throughput();
+
+    Ok(response)
+}
+
+/// Reads a response body chunk by chunk, passing each complete line to
+/// `on_line` as soon as it has arrived.
+async fn read_body_lines(
+    mut response: reqwest::Response,
+    on_line: &mut (dyn FnMut(&str) + Send),
+) -> Result<Vec<u8>, RequestError> {
+    let mut body = Vec::new();
+    let mut lines = LineBuffer::default();
+
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .map_err(|e| RequestError::NetworkError(e.to_string()))?
+    {
+        body.extend_from_slice(&chunk);
+        lines.push(&chunk, on_line);
+    }
+    lines.finish(on_line);
+
+    Ok(body)
+}
+
+/// Collects streamed body bytes and hands back complete lines, holding on to
+/// a partial line until the rest of it arrives.
+#[derive(Debug, Default)]
+struct LineBuffer {
+    pending: Vec<u8>,
+}
+
+impl LineBuffer {
+    /// Appends `bytes`, passing every line they complete to `on_line`.
+    fn push(&mut self, bytes: &[u8], on_line: &mut (dyn FnMut(&str) + Send)) {
+        self.pending.extend_from_slice(bytes);
+        while let Some(end) = self.pending.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = self.pending.drain(..=end).collect();
+            let text = String::from_utf8_lossy(&line);
+            on_line(text.trim_end_matches(['\n', '\r']));
+        }
+    }
+
+    /// Passes any trailing line that wasn't newline-terminated to `on_line`.
+    fn finish(&mut self, on_line: &mut (dyn FnMut(&str) + Send)) {
+        if !self.pending.is_empty() {
+            let line = std::mem::take(&mut self.pending);
+            on_line(String::from_utf8_lossy(&line).trim_end_matches('\r'));
+        }
+    }
+}
+
+/// Converts our `HttpMethod` to reqwest's `Method`.
+fn to_reqwest_method(method: HttpMethod) -> reqwest::Method {
+    match method {
+        HttpMethod::GET => reqwest::Method::GET,
+        HttpMethod::POST => reqwest::Method::POST,
+        HttpMethod::PUT => reqwest::Method::PUT,
+        HttpMethod::DELETE => reqwest::Method::DELETE,
+        HttpMethod::PATCH => reqwest::Method::PATCH,
+        HttpMethod::HEAD => reqwest::Method::HEAD,
+        HttpMethod::OPTIONS => reqwest::Method::OPTIONS,
+        HttpMethod::TRACE => reqwest::Method::TRACE,
+        HttpMethod::CONNECT => reqwest::Method::CONNECT,
+    }
+}
+
+/// Converts a reqwest error into a `RequestError`.
+///
+/// The message includes the error's source chain, so the underlying cause
+/// (e.g. a DNS failure) is visible to `RequestError::category`.
+fn to_request_error(error: &reqwest::Error) -> RequestError {
+    if error.is_timeout() {
+        return RequestError::Timeout;
+    }
+
+    let mut message = error.to_string();
+    let mut source = std::error::Error::source(error);
+    while let Some(cause) = source {
+        message.push_str(&format!(": {}", cause));
+        source = cause.source();
+    }
+
+    if error.is_connect() {
+        RequestError::NetworkError(format!("Connection failed: {}", message))
+    } else {
+        RequestError::NetworkError(message)
+    }
+}
+
+/// Copies a response's headers, skipping values that aren't valid UTF-8.
+fn collect_headers(response: &reqwest::Response) -> Headers {
+    let mut headers = Headers::new();
+    for (name, value) in response.headers() {
+        if let Ok(value_str) = value.to_str() {
+            headers.append(name.as_str().to_string(), value_str.to_string());
+        }
+    }
+    headers
+}
+
+/// Assembles a reqwest multipart form from the request's parts.
+///
+/// File parts are read from disk and sent with their file name and their
+/// `;type=` content type, or one inferred from the extension.
+fn build_multipart_form(multipart: &MultipartBody) -> Result<Form, RequestError> {
+    let mut form = Form::new();
+
+    for part in &multipart.parts {
+        form = match part {
+            MultipartPart::Field { name, value } => form.text(name.clone(), value.clone()),
+            MultipartPart::File {
+                name,
+                path,
+                content_type,
+            } => {
+                let bytes = std::fs::read(path).map_err(|e| {
+                    RequestError::BuildError(format!(
+                        "Failed to read multipart file '{}': {}",
+                        path.display(),
+                        e
+                    ))
+                })?;
+                let mut file_part = Part::bytes(bytes)
+                    .mime_str(
+                        content_type
+                            .as_deref()
+                            .unwrap_or_else(|| content_type_for_path(path)),
+                    )
+                    .map_err(|e| RequestError::BuildError(e.to_string()))?;
+                if let Some(file_name) = path.file_name() {
+                    file_part = file_part.file_name(file_name.to_string_lossy().into_owned());
+                }
+                form.part(name.clone(), file_part)
+            }
+        };
+    }
+
+    Ok(form)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::models::request::HttpRequest;
-    use std::collections::HashMap;
+    use crate::models::Headers;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_line_buffer_splits_across_chunks() {
+        let mut lines = Vec::new();
+        let mut on_line = |line: &str| lines.push(line.to_string());
+        let mut buffer = LineBuffer::default();
+
+        buffer.push(b"first\r\nsec", &mut on_line);
+        buffer.push(b"ond\n\nthi", &mut on_line);
+        buffer.push(b"rd", &mut on_line);
+        buffer.finish(&mut on_line);
+
+        assert_eq!(lines, vec!["first", "second", "", "third"]);
+    }
 
     #[tokio::test]
     async fn test_simple_get_request() {
         let request = HttpRequest {
+            id: "test".to_string(),
             method: HttpMethod::GET,
             url: "https://httpbin.org/get".to_string(),
-            headers: HashMap::new(),
+            headers: Headers::new(),
             body: None,
+            raw_body: false,
+            body_encoding: None,
+            timeout_override: None,
+            retries_override: None,
+            no_cookies: false,
+            filter: None,
+            pipe: None,
+            body_file: None,
+            conditional_headers: Vec::new(),
+            captures: Vec::new(),
+            digest_auth: None,
+            aws_sigv4: None,
+            multipart: None,
+            form_fields: Vec::new(),
+            query_api_key: None,
+            api_key_header: None,
+            name: None,
+            http_version: None,
             line_number: 0,
+            file_path: PathBuf::new(),
         };
 
         let result = execute_request_native(&request).await;
@@ -141,16 +647,36 @@ mod tests {
 
     #[tokio::test]
     async fn test_request_with_headers() {
-        let mut headers = HashMap::new();
+        let mut headers = Headers::new();
         headers.insert("User-Agent".to_string(), "RestClient/1.0".to_string());
         headers.insert("Accept".to_string(), "application/json".to_string());
 
         let request = HttpRequest {
+            id: "test".to_string(),
             method: HttpMethod::GET,
             url: "https://httpbin.org/headers".to_string(),
             headers,
             body: None,
+            raw_body: false,
+            body_encoding: None,
+            timeout_override: None,
+            retries_override: None,
+            no_cookies: false,
+            filter: None,
+            pipe: None,
+            body_file: None,
+            conditional_headers: Vec::new(),
+            captures: Vec::new(),
+            digest_auth: None,
+            aws_sigv4: None,
+            multipart: None,
+            form_fields: Vec::new(),
+            query_api_key: None,
+            api_key_header: None,
+            name: None,
+            http_version: None,
             line_number: 0,
+            file_path: PathBuf::new(),
         };
 
         let result = execute_request_native(&request).await;
@@ -162,17 +688,37 @@ mod tests {
 
     #[tokio::test]
     async fn test_post_request_with_body() {
-        let mut headers = HashMap::new();
+        let mut headers = Headers::new();
         headers.insert("Content-Type".to_string(), "application/json".to_string());
 
         let body = r#"{"name": "test", "value": 123}"#.to_string();
 
         let request = HttpRequest {
+            id: "test".to_string(),
             method: HttpMethod::POST,
             url: "https://httpbin.org/post".to_string(),
             headers,
             body: Some(body),
+            raw_body: false,
+            body_encoding: None,
+            timeout_override: None,
+            retries_override: None,
+            no_cookies: false,
+            filter: None,
+            pipe: None,
+            body_file: None,
+            conditional_headers: Vec::new(),
+            captures: Vec::new(),
+            digest_auth: None,
+            aws_sigv4: None,
+            multipart: None,
+            form_fields: Vec::new(),
+            query_api_key: None,
+            api_key_header: None,
+            name: None,
+            http_version: None,
             line_number: 0,
+            file_path: PathBuf::new(),
         };
 
         let result = execute_request_native(&request).await;
@@ -185,11 +731,31 @@ mod tests {
     #[tokio::test]
     async fn test_invalid_url() {
         let request = HttpRequest {
+            id: "test".to_string(),
             method: HttpMethod::GET,
             url: "not-a-valid-url".to_string(),
-            headers: HashMap::new(),
+            headers: Headers::new(),
             body: None,
+            raw_body: false,
+            body_encoding: None,
+            timeout_override: None,
+            retries_override: None,
+            no_cookies: false,
+            filter: None,
+            pipe: None,
+            body_file: None,
+            conditional_headers: Vec::new(),
+            captures: Vec::new(),
+            digest_auth: None,
+            aws_sigv4: None,
+            multipart: None,
+            form_fields: Vec::new(),
+            query_api_key: None,
+            api_key_header: None,
+            name: None,
+            http_version: None,
             line_number: 0,
+            file_path: PathBuf::new(),
         };
 
         let result = execute_request_native(&request).await;
@@ -199,11 +765,31 @@ mod tests {
     #[tokio::test]
     async fn test_404_response() {
         let request = HttpRequest {
+            id: "test".to_string(),
             method: HttpMethod::GET,
             url: "https://httpbin.org/status/404".to_string(),
-            headers: HashMap::new(),
+            headers: Headers::new(),
             body: None,
+            raw_body: false,
+            body_encoding: None,
+            timeout_override: None,
+            retries_override: None,
+            no_cookies: false,
+            filter: None,
+            pipe: None,
+            body_file: None,
+            conditional_headers: Vec::new(),
+            captures: Vec::new(),
+            digest_auth: None,
+            aws_sigv4: None,
+            multipart: None,
+            form_fields: Vec::new(),
+            query_api_key: None,
+            api_key_header: None,
+            name: None,
+            http_version: None,
             line_number: 0,
+            file_path: PathBuf::new(),
         };
 
         let result = execute_request_native(&request).await;
@@ -212,4 +798,361 @@ mod tests {
         let response = result.unwrap();
         assert_eq!(response.status_code, 404);
     }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_redirect_chain() {
+        use wiremock::matchers::path;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        for (from, to) in [("/start", "/middle"), ("/middle", "/end")] {
+            Mock::given(path(from))
+                .respond_with(ResponseTemplate::new(302).insert_header("Location", to))
+                .mount(&server)
+                .await;
+        }
+        Mock::given(path("/end"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+
+        let request = HttpRequest::new(
+            "test".to_string(),
+            HttpMethod::GET,
+            format!("{}/start", server.uri()),
+        );
+        let response = execute_request_native(&request).await.unwrap();
+
+        assert_eq!(response.status_code, 200);
+        assert_eq!(
+            response.redirect_chain,
+            vec![
+                format!("{}/start", server.uri()),
+                format!("{}/middle", server.uri()),
+                format!("{}/end", server.uri()),
+            ]
+        );
+        assert_eq!(response.final_url, Some(format!("{}/end", server.uri())));
+    }
+
+    async fn mount_redirects(server: &wiremock::MockServer, hops: usize) {
+        use wiremock::matchers::path;
+        use wiremock::{Mock, ResponseTemplate};
+
+        for hop in 0..hops {
+            Mock::given(path(format!("/hop{}", hop)))
+                .respond_with(
+                    ResponseTemplate::new(302)
+                        .insert_header("Location", format!("/hop{}", hop + 1)),
+                )
+                .mount(server)
+                .await;
+        }
+        Mock::given(path(format!("/hop{}", hops)))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(server)
+            .await;
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_max_redirects_zero_rejects_any_redirect() {
+        use crate::config::{reset_config, update_config};
+
+        let server = wiremock::MockServer::start().await;
+        mount_redirects(&server, 1).await;
+        update_config(|config| config.max_redirects = 0);
+
+        let request = HttpRequest::new(
+            "test".to_string(),
+            HttpMethod::GET,
+            format!("{}/hop0", server.uri()),
+        );
+        let result = execute_request_native(&request).await;
+        reset_config();
+
+        assert!(matches!(result, Err(RequestError::ProtocolError(_))));
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_max_redirects_allows_exactly_the_limit() {
+        use crate::config::{reset_config, update_config};
+
+        let server = wiremock::MockServer::start().await;
+        mount_redirects(&server, 3).await;
+        update_config(|config| config.max_redirects = 3);
+
+        let request = HttpRequest::new(
+            "test".to_string(),
+            HttpMethod::GET,
+            format!("{}/hop0", server.uri()),
+        );
+        let within_limit = execute_request_native(&request).await;
+        update_config(|config| config.max_redirects = 2);
+        let over_limit = execute_request_native(&request).await;
+        reset_config();
+
+        let response = within_limit.unwrap();
+        assert_eq!(response.status_code, 200);
+        assert_eq!(response.redirect_chain.len(), 4);
+        assert!(matches!(over_limit, Err(RequestError::ProtocolError(_))));
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_redirect_relative_locations() {
+        use wiremock::matchers::path;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        for (from, status, to) in [
+            ("/api/v1/start", 301, "next"),
+            ("/api/v1/next", 302, "../v2/next"),
+            ("/api/v2/next", 308, "/end"),
+        ] {
+            Mock::given(path(from))
+                .respond_with(ResponseTemplate::new(status).insert_header("Location", to))
+                .mount(&server)
+                .await;
+        }
+        Mock::given(path("/end"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+
+        let request = HttpRequest::new(
+            "test".to_string(),
+            HttpMethod::GET,
+            format!("{}/api/v1/start", server.uri()),
+        );
+        let response = execute_request_native(&request).await.unwrap();
+
+        assert_eq!(response.status_code, 200);
+        assert_eq!(
+            response.redirect_chain,
+            vec![
+                format!("{}/api/v1/start", server.uri()),
+                format!("{}/api/v1/next", server.uri()),
+                format!("{}/api/v2/next", server.uri()),
+                format!("{}/end", server.uri()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_redirect_method_by_status() {
+        use wiremock::matchers::{body_string, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        for (from, status) in [
+            ("/301", 301),
+            ("/302", 302),
+            ("/303", 303),
+            ("/307", 307),
+            ("/308", 308),
+        ] {
+            Mock::given(path(from))
+                .respond_with(ResponseTemplate::new(status).insert_header("Location", "target"))
+                .mount(&server)
+                .await;
+        }
+        Mock::given(method("GET"))
+            .and(path("/target"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("get"))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/target"))
+            .and(body_string("payload"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("post"))
+            .mount(&server)
+            .await;
+
+        for (status, expected) in [
+            (301, "get"),
+            (302, "get"),
+            (303, "get"),
+            (307, "post"),
+            (308, "post"),
+        ] {
+            let mut request = HttpRequest::new(
+                "test".to_string(),
+                HttpMethod::POST,
+                format!("{}/{}", server.uri(), status),
+            );
+            request.body = Some("payload".to_string());
+
+            let response = execute_request_native(&request).await.unwrap();
+            assert_eq!(response.status_code, 200, "{} redirect", status);
+            assert_eq!(response.body, expected.as_bytes(), "{} redirect", status);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_session_cookies() {
+        use wiremock::matchers::{header, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(path("/cookie-test/login"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("Set-Cookie", "session=abc123; Path=/cookie-test"),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(path("/cookie-test/me"))
+            .and(header("Cookie", "session=abc123"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+
+        let request = |path: &str| {
+            HttpRequest::new(
+                "test".to_string(),
+                HttpMethod::GET,
+                format!("{}/cookie-test/{}", server.uri(), path),
+            )
+        };
+        execute_request_native(&request("login")).await.unwrap();
+
+        let response = execute_request_native(&request("me")).await.unwrap();
+        assert_eq!(response.status_code, 200);
+
+        // `# @no-cookies` leaves the stored cookie out
+        let mut no_cookies = request("me");
+        no_cookies.no_cookies = true;
+        let response = execute_request_native(&no_cookies).await.unwrap();
+        assert_eq!(response.status_code, 404);
+    }
+
+    #[tokio::test]
+    async fn test_retry_after_on_503() {
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(wiremock::matchers::path("/busy"))
+            .respond_with(ResponseTemplate::new(503).insert_header("Retry-After", "1"))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(wiremock::matchers::path("/busy"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+
+        let mut request = HttpRequest::new(
+            "test".to_string(),
+            HttpMethod::GET,
+            format!("{}/busy", server.uri()),
+        );
+
+        // The retry waits for the Retry-After second, not the 500ms backoff
+        request.retries_override = Some(1);
+        let started = Instant::now();
+        let response = execute_request_native(&request).await.unwrap();
+        assert_eq!(response.status_code, 200);
+        assert!(started.elapsed() >= std::time::Duration::from_secs(1));
+    }
+
+    #[tokio::test]
+    async fn test_streams_large_body_file() {
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("PUT"))
+            .respond_with(ResponseTemplate::new(201))
+            .mount(&server)
+            .await;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("upload.bin");
+        let contents: Vec<u8> = (0..=255u8).cycle().take(100_000).collect();
+        std::fs::write(&path, &contents).unwrap();
+
+        let mut request = HttpRequest::new(
+            "test".to_string(),
+            HttpMethod::PUT,
+            format!("{}/upload", server.uri()),
+        );
+        request.body_file = Some(path);
+        request.raw_body = true;
+        let response = execute_request_native(&request).await.unwrap();
+        assert_eq!(response.status_code, 201);
+
+        let received = &server.received_requests().await.unwrap()[0];
+        assert_eq!(received.headers["content-length"], "100000");
+        assert_eq!(received.body, contents);
+    }
+
+    /// Sends a multipart request to a mock server and returns the received
+    /// Content-Type and body.
+    async fn send_multipart(parts: Vec<MultipartPart>) -> (String, String) {
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+
+        let mut request = HttpRequest::new(
+            "test".to_string(),
+            HttpMethod::POST,
+            format!("{}/upload", server.uri()),
+        );
+        request.add_header(
+            "Content-Type".to_string(),
+            "multipart/form-data".to_string(),
+        );
+        request.multipart = Some(MultipartBody { parts });
+        execute_request_native(&request).await.unwrap();
+
+        let received = &server.received_requests().await.unwrap()[0];
+        let content_type = received.headers["content-type"].to_str().unwrap();
+        (
+            content_type.to_string(),
+            String::from_utf8_lossy(&received.body).into_owned(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_multipart_text_fields() {
+        let (content_type, body) = send_multipart(vec![MultipartPart::Field {
+            name: "title".to_string(),
+            value: "Hello".to_string(),
+        }])
+        .await;
+
+        let boundary = content_type
+            .strip_prefix("multipart/form-data; boundary=")
+            .expect("Content-Type should carry the boundary");
+        assert!(body.starts_with(&format!("--{}\r\n", boundary)));
+        assert!(body.contains("Content-Disposition: form-data; name=\"title\"\r\n\r\nHello\r\n"));
+        assert!(body.ends_with(&format!("--{}--\r\n", boundary)));
+    }
+
+    #[tokio::test]
+    async fn test_multipart_file_part() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("photo.png");
+        std::fs::write(&path, b"fake png").unwrap();
+
+        let (_, body) = send_multipart(vec![MultipartPart::File {
+            name: "file".to_string(),
+            path,
+            content_type: None,
+        }])
+        .await;
+
+        assert!(body.contains(
+            "Content-Disposition: form-data; name=\"file\"; filename=\"photo.png\"\r\nContent-Type: image/png\r\n\r\nfake png\r\n"
+        ));
+    }
 }
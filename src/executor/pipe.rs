@@ -0,0 +1,193 @@
+//! Piping response bodies through shell commands.
+//!
+//! A `# @pipe <command>` directive passes the response body to a shell
+//! command on stdin and shows the command's output instead of the body.
+//! Spawning processes is only possible outside WASM, so this is used by the
+//! language server, and only when `allowPipeCommands` is enabled.
+//!
+//! # Examples
+//!
+//! ```http
+//! # @pipe jq '.data | length'
+//! GET https://api.example.com/items
+//! ```
+
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+
+/// Errors that can occur when piping a response through a command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PipeError {
+    /// The request has a `# @pipe` command but `allowPipeCommands` is off.
+    Disabled(String),
+
+    /// The shell could not be started.
+    SpawnFailed(String),
+
+    /// Waiting for the command or reading its output failed.
+    Io(String),
+
+    /// The command didn't finish within the request timeout and was killed.
+    Timeout(Duration),
+
+    /// The command exited unsuccessfully.
+    CommandFailed {
+        /// Exit code, or `None` if the command was terminated by a signal.
+        code: Option<i32>,
+        /// What the command wrote to stderr.
+        stderr: String,
+    },
+}
+
+impl std::fmt::Display for PipeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PipeError::Disabled(command) => write!(
+                f,
+                "Not running `{}`: set allowPipeCommands to true to enable # @pipe",
+                command
+            ),
+            PipeError::SpawnFailed(msg) => write!(f, "Failed to start pipe command: {}", msg),
+            PipeError::Io(msg) => write!(f, "Failed to run pipe command: {}", msg),
+            PipeError::Timeout(timeout) => write!(
+                f,
+                "Pipe command did not finish within {}ms and was stopped",
+                timeout.as_millis()
+            ),
+            PipeError::CommandFailed { code, stderr } => {
+                match code {
+                    Some(code) => write!(f, "Pipe command exited with status {}", code)?,
+                    None => write!(f, "Pipe command was terminated")?,
+                }
+                if !stderr.trim().is_empty() {
+                    write!(f, ":\n{}", stderr.trim_end())?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for PipeError {}
+
+/// Runs a shell command with `input` on stdin and returns its output.
+///
+/// The command is run with `sh -c` (`cmd /C` on Windows), so quoting and
+/// pipelines work as they do in a terminal. A command still running after
+/// `timeout` is killed.
+///
+/// # Arguments
+///
+/// * `command` - The command line from the `# @pipe` directive
+/// * `input` - The response body
+/// * `timeout` - How long the command may run
+///
+/// # Returns
+///
+/// `Ok(String)` with the command's stdout, or `Err(PipeError)` with its
+/// stderr if it exits unsuccessfully.
+pub async fn run_pipe_command(
+    command: &str,
+    input: &[u8],
+    timeout: Duration,
+) -> Result<String, PipeError> {
+    let mut child = shell_command(command)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| PipeError::SpawnFailed(e.to_string()))?;
+
+    // Write stdin while reading the output, so a command that streams its
+    // output can't fill the pipe and block on a large body
+    let stdin = child.stdin.take();
+    let write_input = async move {
+        if let Some(mut stdin) = stdin {
+            // A command that doesn't read all of its input closes the pipe early
+            let _ = stdin.write_all(input).await;
+        }
+    };
+    let run = async {
+        let ((), output) = tokio::join!(write_input, child.wait_with_output());
+        output
+    };
+    // Dropping the timed-out future drops the child, which kills it
+    let output = tokio::time::timeout(timeout, run)
+        .await
+        .map_err(|_| PipeError::Timeout(timeout))?
+        .map_err(|e| PipeError::Io(e.to_string()))?;
+
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    } else {
+        Err(PipeError::CommandFailed {
+            code: output.status.code(),
+            stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+        })
+    }
+}
+
+/// Builds the platform shell invocation for a command line.
+fn shell_command(command: &str) -> Command {
+    if cfg!(windows) {
+        let mut shell = Command::new("cmd");
+        shell.arg("/C").arg(command);
+        shell
+    } else {
+        let mut shell = Command::new("sh");
+        shell.arg("-c").arg(command);
+        shell
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    const TIMEOUT: Duration = Duration::from_secs(10);
+
+    #[tokio::test]
+    async fn test_run_pipe_command_transforms_body() {
+        let output = run_pipe_command("tr a-z A-Z", b"{\"status\": \"ok\"}", TIMEOUT)
+            .await
+            .unwrap();
+        assert_eq!(output, "{\"STATUS\": \"OK\"}");
+
+        // Large bodies are streamed through without blocking
+        let body = vec![b'x'; 1 << 20];
+        let output = run_pipe_command("cat", &body, TIMEOUT).await.unwrap();
+        assert_eq!(output.len(), body.len());
+    }
+
+    #[tokio::test]
+    async fn test_run_pipe_command_failure_captures_stderr() {
+        let error = run_pipe_command("echo 'parse error: bad input' >&2; exit 3", b"{}", TIMEOUT)
+            .await
+            .unwrap_err();
+        assert_eq!(
+            error,
+            PipeError::CommandFailed {
+                code: Some(3),
+                stderr: "parse error: bad input\n".to_string(),
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            "Pipe command exited with status 3:\nparse error: bad input"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_run_pipe_command_times_out() {
+        let timeout = Duration::from_millis(100);
+        let error = run_pipe_command("sleep 5", b"", timeout).await.unwrap_err();
+        assert_eq!(error, PipeError::Timeout(timeout));
+        assert_eq!(
+            error.to_string(),
+            "Pipe command did not finish within 100ms and was stopped"
+        );
+    }
+}
@@ -0,0 +1,233 @@
+//! Proxy selection for outgoing requests.
+//!
+//! Requests are sent through the proxy set in `proxyUrl`, or else the one in
+//! the `HTTPS_PROXY`/`HTTP_PROXY` environment variables for the request's
+//! scheme. Hosts matching a pattern in `excludeHostsFromProxy` (or, for
+//! proxies taken from the environment, in `NO_PROXY`) are contacted
+//! directly. Patterns may use `*` as a wildcard, e.g. `*.internal.com`.
+//! A `NO_PROXY` entry without a wildcard, such as `example.com` or
+//! `.example.com`, matches that domain and all of its subdomains.
+
+use url::Url;
+
+/// Returns `true` if `host` matches a glob `pattern`.
+///
+/// `*` matches any run of characters, including dots; everything else is
+/// compared case-insensitively.
+///
+/// # Examples
+///
+/// ```
+/// use rest_client::executor::proxy::host_matches_pattern;
+///
+/// assert!(host_matches_pattern("api.internal.com", "*.internal.com"));
+/// assert!(host_matches_pattern("192.168.1.20", "192.168.*"));
+/// assert!(!host_matches_pattern("internal.com.evil.org", "*.internal.com"));
+/// ```
+pub fn host_matches_pattern(host: &str, pattern: &str) -> bool {
+    let host = host.to_ascii_lowercase();
+    let pattern = pattern.trim().to_ascii_lowercase();
+    let (host, pattern) = (host.as_bytes(), pattern.as_bytes());
+
+    // Greedy wildcard matching, backtracking to the last `*` on a mismatch
+    let (mut h, mut p) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while h < host.len() {
+        if p < pattern.len() && pattern[p] == b'*' {
+            backtrack = Some((p, h));
+            p += 1;
+        } else if p < pattern.len() && pattern[p] == host[h] {
+            h += 1;
+            p += 1;
+        } else if let Some((star, matched)) = backtrack {
+            p = star + 1;
+            h = matched + 1;
+            backtrack = Some((star, matched + 1));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == b'*')
+}
+
+/// Returns `true` if `host` matches any of the exclude `patterns`.
+pub fn is_excluded_from_proxy(host: &str, patterns: &[String]) -> bool {
+    patterns
+        .iter()
+        .any(|pattern| host_matches_pattern(host, pattern))
+}
+
+/// Chooses the proxy for a request, if any.
+///
+/// # Arguments
+///
+/// * `url` - The URL being requested
+/// * `proxy_url` - The configured `proxyUrl`, which takes precedence
+/// * `exclude` - Host patterns from `excludeHostsFromProxy`
+/// * `env` - Looks up an environment variable
+///
+/// # Returns
+///
+/// `Some(String)` with the proxy URL, or `None` to connect directly because
+/// no proxy is set or the host is excluded.
+pub fn resolve_proxy(
+    url: &Url,
+    proxy_url: Option<&str>,
+    exclude: &[String],
+    env: impl Fn(&str) -> Option<String>,
+) -> Option<String> {
+    let host = url
+        .host_str()?
+        .trim_start_matches('[')
+        .trim_end_matches(']');
+    if is_excluded_from_proxy(host, exclude) {
+        return None;
+    }
+
+    if let Some(proxy_url) = proxy_url {
+        return Some(proxy_url.to_string());
+    }
+
+    let var = |name: &str| {
+        env(name)
+            .or_else(|| env(&name.to_ascii_lowercase()))
+            .filter(|value| !value.trim().is_empty())
+    };
+    let proxy = match url.scheme() {
+        "https" => var("HTTPS_PROXY"),
+        "http" => var("HTTP_PROXY"),
+        _ => None,
+    }?;
+
+    if let Some(no_proxy) = var("NO_PROXY") {
+        // Plain entries match on a label boundary, so `example.com` covers
+        // `api.example.com` but not `badexample.com`
+        let no_proxy: Vec<String> = no_proxy
+            .split(',')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .flat_map(|entry| {
+                if entry.contains('*') {
+                    return vec![entry.to_string()];
+                }
+                let domain = entry.trim_start_matches('.');
+                vec![domain.to_string(), format!("*.{}", domain)]
+            })
+            .collect();
+        if is_excluded_from_proxy(host, &no_proxy) {
+            return None;
+        }
+    }
+
+    Some(proxy)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn env(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect();
+        move |name| vars.get(name).cloned()
+    }
+
+    fn url(url: &str) -> Url {
+        Url::parse(url).unwrap()
+    }
+
+    #[test]
+    fn test_host_matches_pattern() {
+        assert!(host_matches_pattern("localhost", "localhost"));
+        assert!(host_matches_pattern("LocalHost", "localhost"));
+        assert!(host_matches_pattern("api.internal.com", "*.internal.com"));
+        assert!(host_matches_pattern("a.b.internal.com", "*.internal.com"));
+        assert!(host_matches_pattern("10.0.0.7", "10.0.0.*"));
+        assert!(host_matches_pattern("anything", "*"));
+
+        assert!(!host_matches_pattern("internal.com", "*.internal.com"));
+        assert!(!host_matches_pattern(
+            "api.internal.com.evil.org",
+            "*.internal.com"
+        ));
+        assert!(!host_matches_pattern("notlocalhost", "localhost"));
+        assert!(!host_matches_pattern("10.0.1.7", "10.0.0.*"));
+    }
+
+    #[test]
+    fn test_configured_proxy_and_excludes() {
+        let exclude = vec!["localhost".to_string(), "*.internal.com".to_string()];
+        let proxy = Some("http://proxy.corp:8080");
+
+        assert_eq!(
+            resolve_proxy(&url("https://api.example.com/"), proxy, &exclude, env(&[])),
+            Some("http://proxy.corp:8080".to_string())
+        );
+        assert_eq!(
+            resolve_proxy(&url("http://localhost:3000/"), proxy, &exclude, env(&[])),
+            None
+        );
+        assert_eq!(
+            resolve_proxy(&url("https://git.internal.com/"), proxy, &exclude, env(&[])),
+            None
+        );
+    }
+
+    #[test]
+    fn test_proxy_from_environment() {
+        let vars = [
+            ("HTTPS_PROXY", "http://secure-proxy:3128"),
+            ("http_proxy", "http://plain-proxy:3128"),
+            ("NO_PROXY", "localhost, .corp.example.com"),
+        ];
+
+        assert_eq!(
+            resolve_proxy(&url("https://api.example.com/"), None, &[], env(&vars)),
+            Some("http://secure-proxy:3128".to_string())
+        );
+        assert_eq!(
+            resolve_proxy(&url("http://api.example.com/"), None, &[], env(&vars)),
+            Some("http://plain-proxy:3128".to_string())
+        );
+        assert_eq!(
+            resolve_proxy(
+                &url("https://wiki.corp.example.com/"),
+                None,
+                &[],
+                env(&vars)
+            ),
+            None
+        );
+        assert_eq!(
+            resolve_proxy(&url("https://api.example.com/"), None, &[], env(&[])),
+            None
+        );
+    }
+
+    #[test]
+    fn test_no_proxy_matches_subdomains_on_label_boundary() {
+        let vars = [
+            ("HTTPS_PROXY", "http://secure-proxy:3128"),
+            ("NO_PROXY", "example.com,.internal.org"),
+        ];
+        let proxied = |address: &str| resolve_proxy(&url(address), None, &[], env(&vars));
+
+        assert_eq!(proxied("https://example.com/"), None);
+        assert_eq!(proxied("https://api.example.com/"), None);
+        assert_eq!(proxied("https://internal.org/"), None);
+        assert_eq!(proxied("https://git.internal.org/"), None);
+        assert_eq!(
+            proxied("https://badexample.com/"),
+            Some("http://secure-proxy:3128".to_string())
+        );
+        assert_eq!(
+            proxied("https://example.com.evil.org/"),
+            Some("http://secure-proxy:3128".to_string())
+        );
+    }
+}
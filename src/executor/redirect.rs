@@ -0,0 +1,212 @@
+//! Redirect handling for the native executor.
+//!
+//! `Location` values are resolved against the URL of the response that sent
+//! them, so relative paths (`next`, `../up`), absolute paths (`/login`),
+//! scheme-relative URLs (`//host/path`), and absolute URLs all lead where the
+//! server meant. 301, 302, and 303 redirects are followed with GET; 307 and
+//! 308 redirects keep the method and body unless `preserveMethodOnRedirect`
+//! is turned off.
+
+use super::{parse_http_url, RequestError};
+use crate::models::request::HttpMethod;
+
+/// Headers describing a request body, dropped when a redirect drops the body.
+pub const BODY_HEADERS: [&str; 4] = [
+    "Content-Type",
+    "Content-Length",
+    "Content-Encoding",
+    "Transfer-Encoding",
+];
+
+/// Headers carrying credentials, dropped when a redirect leaves the origin.
+pub const SENSITIVE_HEADERS: [&str; 3] = ["Authorization", "Proxy-Authorization", "Cookie"];
+
+/// Returns `true` if a response with this status is a redirect to follow.
+pub fn is_redirect_status(status_code: u16) -> bool {
+    matches!(status_code, 301 | 302 | 303 | 307 | 308)
+}
+
+/// Resolves a `Location` header value against the URL that returned it.
+///
+/// # Arguments
+///
+/// * `current_url` - The URL of the redirect response
+/// * `location` - The response's `Location` header value
+///
+/// # Returns
+///
+/// The absolute URL to request next, or an error if the result isn't a valid
+/// HTTP or HTTPS URL.
+///
+/// # Examples
+///
+/// ```
+/// use rest_client::executor::redirect::resolve_location;
+///
+/// let current = "https://api.example.com/v1/users?page=2";
+/// assert_eq!(
+///     resolve_location(current, "active").unwrap(),
+///     "https://api.example.com/v1/active"
+/// );
+/// assert_eq!(
+///     resolve_location(current, "/login").unwrap(),
+///     "https://api.example.com/login"
+/// );
+/// assert_eq!(
+///     resolve_location(current, "https://auth.example.com/").unwrap(),
+///     "https://auth.example.com/"
+/// );
+/// ```
+pub fn resolve_location(current_url: &str, location: &str) -> Result<String, RequestError> {
+    let base = parse_http_url(current_url)?;
+    let next = base.join(location.trim()).map_err(|e| {
+        RequestError::InvalidUrl(format!("Redirect to '{}': {}", location.trim(), e))
+    })?;
+
+    parse_http_url(next.as_str()).map(String::from)
+}
+
+/// Returns the method to use for the request a redirect leads to.
+///
+/// 301, 302, and 303 redirects are followed with GET, except that a HEAD
+/// request stays HEAD. 307 and 308 redirects keep the method when
+/// `preserve_method` is set, and are treated like 303 otherwise.
+///
+/// # Arguments
+///
+/// * `status_code` - The redirect status
+/// * `method` - The method of the request that was redirected
+/// * `preserve_method` - The `preserveMethodOnRedirect` setting
+///
+/// # Examples
+///
+/// ```
+/// use rest_client::executor::redirect::redirect_method;
+/// use rest_client::models::request::HttpMethod;
+///
+/// assert_eq!(redirect_method(303, HttpMethod::POST, true), HttpMethod::GET);
+/// assert_eq!(redirect_method(307, HttpMethod::POST, true), HttpMethod::POST);
+/// assert_eq!(redirect_method(307, HttpMethod::POST, false), HttpMethod::GET);
+/// ```
+pub fn redirect_method(status_code: u16, method: HttpMethod, preserve_method: bool) -> HttpMethod {
+    match status_code {
+        307 | 308 if preserve_method => method,
+        _ if method == HttpMethod::HEAD => HttpMethod::HEAD,
+        _ => HttpMethod::GET,
+    }
+}
+
+/// Returns `true` if two URLs have the same scheme, host, and port.
+///
+/// Credentials are only sent on to a redirect target with the same origin.
+pub fn is_same_origin(a: &str, b: &str) -> bool {
+    match (url::Url::parse(a), url::Url::parse(b)) {
+        (Ok(a), Ok(b)) => a.origin() == b.origin(),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CURRENT: &str = "https://api.example.com/v1/users/42?expand=true";
+
+    #[test]
+    fn test_resolve_relative_locations() {
+        assert_eq!(
+            resolve_location(CURRENT, "profile").unwrap(),
+            "https://api.example.com/v1/users/profile"
+        );
+        assert_eq!(
+            resolve_location(CURRENT, "./43").unwrap(),
+            "https://api.example.com/v1/users/43"
+        );
+        assert_eq!(
+            resolve_location(CURRENT, "../teams").unwrap(),
+            "https://api.example.com/v1/teams"
+        );
+        assert_eq!(
+            resolve_location(CURRENT, "?expand=false").unwrap(),
+            "https://api.example.com/v1/users/42?expand=false"
+        );
+    }
+
+    #[test]
+    fn test_resolve_absolute_path_and_url_locations() {
+        assert_eq!(
+            resolve_location(CURRENT, " /v2/users/42 ").unwrap(),
+            "https://api.example.com/v2/users/42"
+        );
+        assert_eq!(
+            resolve_location(CURRENT, "//cdn.example.com/users/42").unwrap(),
+            "https://cdn.example.com/users/42"
+        );
+        assert_eq!(
+            resolve_location(CURRENT, "http://legacy.example.com:8080/u/42").unwrap(),
+            "http://legacy.example.com:8080/u/42"
+        );
+    }
+
+    #[test]
+    fn test_resolve_location_rejects_other_protocols() {
+        assert!(matches!(
+            resolve_location(CURRENT, "ftp://files.example.com/42"),
+            Err(RequestError::UnsupportedProtocol(_))
+        ));
+        assert!(matches!(
+            resolve_location(CURRENT, "http://[::1"),
+            Err(RequestError::InvalidUrl(_))
+        ));
+    }
+
+    #[test]
+    fn test_redirect_method_for_each_status() {
+        for status in [301, 302, 303] {
+            assert_eq!(
+                redirect_method(status, HttpMethod::POST, true),
+                HttpMethod::GET
+            );
+            assert_eq!(
+                redirect_method(status, HttpMethod::DELETE, true),
+                HttpMethod::GET
+            );
+            assert_eq!(
+                redirect_method(status, HttpMethod::HEAD, true),
+                HttpMethod::HEAD
+            );
+        }
+        for status in [307, 308] {
+            assert_eq!(
+                redirect_method(status, HttpMethod::POST, true),
+                HttpMethod::POST
+            );
+            assert_eq!(
+                redirect_method(status, HttpMethod::PUT, true),
+                HttpMethod::PUT
+            );
+            assert_eq!(
+                redirect_method(status, HttpMethod::PUT, false),
+                HttpMethod::GET
+            );
+        }
+    }
+
+    #[test]
+    fn test_is_redirect_status() {
+        for status in [301, 302, 303, 307, 308] {
+            assert!(is_redirect_status(status));
+        }
+        for status in [200, 300, 304, 305, 400] {
+            assert!(!is_redirect_status(status));
+        }
+    }
+
+    #[test]
+    fn test_is_same_origin() {
+        assert!(is_same_origin(CURRENT, "https://api.example.com/other"));
+        assert!(!is_same_origin(CURRENT, "http://api.example.com/v1"));
+        assert!(!is_same_origin(CURRENT, "https://api.example.com:8443/v1"));
+        assert!(!is_same_origin(CURRENT, "https://auth.example.com/"));
+    }
+}
@@ -0,0 +1,106 @@
+//! Retry decisions for responses that ask the client to back off.
+//!
+//! A 429 (Too Many Requests) or 503 (Service Unavailable) response is
+//! retried like a network error. When it carries a `Retry-After` header, the
+//! retry waits as long as the server asks (capped by `maxRetryAfterMs`)
+//! instead of the backoff delay.
+
+use crate::formatter::rate_limit::parse_retry_after;
+use crate::models::headers::Headers;
+use chrono::{DateTime, Utc};
+use std::time::Duration;
+
+/// Returns `true` if a response with this status should be retried.
+pub fn is_retryable_status(status_code: u16) -> bool {
+    matches!(status_code, 429 | 503)
+}
+
+/// Returns the wait a retryable response asks for in `Retry-After`.
+///
+/// # Arguments
+///
+/// * `status_code` - The response status code
+/// * `headers` - The response headers
+/// * `now` - The time the response was received, for HTTP-date values
+///
+/// # Returns
+///
+/// `Some(Duration)` for a 429 or 503 response with a valid `Retry-After`
+/// header (delta-seconds or an HTTP date), `None` otherwise.
+///
+/// # Examples
+///
+/// ```
+/// use chrono::Utc;
+/// use rest_client::executor::retry::retry_after;
+/// use rest_client::models::Headers;
+/// use std::time::Duration;
+///
+/// let mut headers = Headers::new();
+/// headers.insert("Retry-After".to_string(), "5".to_string());
+///
+/// assert_eq!(retry_after(429, &headers, Utc::now()), Some(Duration::from_secs(5)));
+/// assert_eq!(retry_after(500, &headers, Utc::now()), None);
+/// ```
+pub fn retry_after(status_code: u16, headers: &Headers, now: DateTime<Utc>) -> Option<Duration> {
+    if !is_retryable_status(status_code) {
+        return None;
+    }
+
+    headers
+        .get("Retry-After")
+        .and_then(|value| parse_retry_after(value, now))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(retry_after: &str) -> Headers {
+        let mut headers = Headers::new();
+        headers.insert("Retry-After".to_string(), retry_after.to_string());
+        headers
+    }
+
+    fn now() -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2026-10-16T14:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_retry_after_delta_seconds() {
+        assert_eq!(
+            retry_after(429, &headers("30"), now()),
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(
+            retry_after(503, &headers(" 0 "), now()),
+            Some(Duration::ZERO)
+        );
+    }
+
+    #[test]
+    fn test_retry_after_http_date() {
+        assert_eq!(
+            retry_after(503, &headers("Fri, 16 Oct 2026 14:01:30 GMT"), now()),
+            Some(Duration::from_secs(90))
+        );
+
+        // A date that has already passed means retry right away
+        assert_eq!(
+            retry_after(429, &headers("Fri, 16 Oct 2026 13:59:00 GMT"), now()),
+            Some(Duration::ZERO)
+        );
+    }
+
+    #[test]
+    fn test_retry_after_ignored() {
+        // Only 429 and 503 responses are retried
+        assert_eq!(retry_after(500, &headers("30"), now()), None);
+        assert_eq!(retry_after(200, &headers("30"), now()), None);
+
+        assert_eq!(retry_after(429, &headers("later"), now()), None);
+        assert_eq!(retry_after(429, &Headers::new(), now()), None);
+    }
+}
@@ -3,8 +3,10 @@
 //! This module provides functionality to detect and classify HTTP response content types,
 //! enabling appropriate formatting for different data formats.
 
+use crate::formatter::csv::looks_like_csv;
+use crate::formatter::json::looks_like_ndjson;
+use crate::models::Headers;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
 
 /// Content type classification for HTTP responses.
 ///
@@ -14,8 +16,16 @@ use std::collections::HashMap;
 pub enum ContentType {
     /// JSON data (application/json)
     Json,
+    /// Newline-delimited JSON, one value per line (application/x-ndjson)
+    NdJson,
+    /// RFC 7807 problem details (application/problem+json)
+    ProblemJson,
     /// XML data (application/xml, text/xml)
     Xml,
+    /// Comma-separated values (text/csv)
+    Csv,
+    /// YAML data (application/yaml, text/yaml)
+    Yaml,
     /// HTML content (text/html)
     Html,
     /// Plain text (text/plain)
@@ -31,7 +41,11 @@ impl ContentType {
     pub fn as_str(&self) -> &'static str {
         match self {
             ContentType::Json => "JSON",
+            ContentType::NdJson => "NDJSON",
+            ContentType::ProblemJson => "Problem JSON",
             ContentType::Xml => "XML",
+            ContentType::Csv => "CSV",
+            ContentType::Yaml => "YAML",
             ContentType::Html => "HTML",
             ContentType::PlainText => "Plain Text",
             ContentType::Binary => "Binary",
@@ -43,9 +57,21 @@ impl ContentType {
     pub fn is_textual(&self) -> bool {
         matches!(
             self,
-            ContentType::Json | ContentType::Xml | ContentType::Html | ContentType::PlainText
+            ContentType::Json
+                | ContentType::NdJson
+                | ContentType::ProblemJson
+                | ContentType::Xml
+                | ContentType::Csv
+                | ContentType::Yaml
+                | ContentType::Html
+                | ContentType::PlainText
         )
     }
+
+    /// Checks if the content type is JSON-based (plain JSON or problem details).
+    pub fn is_json(&self) -> bool {
+        matches!(self, ContentType::Json | ContentType::ProblemJson)
+    }
 }
 
 impl std::fmt::Display for ContentType {
@@ -71,16 +97,16 @@ impl std::fmt::Display for ContentType {
 /// # Examples
 ///
 /// ```
-/// use std::collections::HashMap;
+/// use rest_client::models::Headers;
 /// use rest_client::formatter::content_type::detect_content_type;
 ///
-/// let mut headers = HashMap::new();
+/// let mut headers = Headers::new();
 /// headers.insert("Content-Type".to_string(), "application/json".to_string());
 /// let body = br#"{"key": "value"}"#;
 ///
 /// let content_type = detect_content_type(&headers, body);
 /// ```
-pub fn detect_content_type(headers: &HashMap<String, String>, body: &[u8]) -> ContentType {
+pub fn detect_content_type(headers: &Headers, body: &[u8]) -> ContentType {
     // First, check the Content-Type header
     if let Some(content_type_header) = find_content_type_header(headers) {
         let content_type_lower = content_type_header.to_lowercase();
@@ -93,12 +119,23 @@ pub fn detect_content_type(headers: &HashMap<String, String>, body: &[u8]) -> Co
             .trim();
 
         // Match against known content types
-        if mime_type.contains("json") {
+        if mime_type == "application/problem+json" {
+            return ContentType::ProblemJson;
+        } else if mime_type.contains("ndjson")
+            || mime_type.contains("jsonl")
+            || mime_type.contains("json-seq")
+        {
+            return ContentType::NdJson;
+        } else if mime_type.contains("json") {
             return ContentType::Json;
         } else if mime_type.contains("xml") {
             return ContentType::Xml;
         } else if mime_type.contains("html") {
             return ContentType::Html;
+        } else if mime_type.contains("csv") {
+            return ContentType::Csv;
+        } else if mime_type.contains("yaml") || mime_type.contains("yml") {
+            return ContentType::Yaml;
         } else if mime_type.starts_with("text/") {
             return ContentType::PlainText;
         } else if mime_type.starts_with("image/") {
@@ -127,7 +164,7 @@ pub fn detect_content_type(headers: &HashMap<String, String>, body: &[u8]) -> Co
 /// # Returns
 ///
 /// `Some(&str)` with the content type value, or `None` if not found.
-fn find_content_type_header(headers: &HashMap<String, String>) -> Option<&str> {
+fn find_content_type_header(headers: &Headers) -> Option<&str> {
     headers
         .iter()
         .find(|(k, _)| k.eq_ignore_ascii_case("content-type"))
@@ -151,6 +188,12 @@ fn inspect_body_content(body: &[u8]) -> ContentType {
     if let Ok(text) = std::str::from_utf8(body) {
         let trimmed = text.trim();
 
+        // One JSON value per line, checked before the JSON markers since
+        // `{...}\n{...}` also starts with `{` and ends with `}`
+        if looks_like_ndjson(trimmed) {
+            return ContentType::NdJson;
+        }
+
         // Check for JSON markers
         if (trimmed.starts_with('{') && trimmed.ends_with('}'))
             || (trimmed.starts_with('[') && trimmed.ends_with(']'))
@@ -176,6 +219,18 @@ fn inspect_body_content(body: &[u8]) -> ContentType {
             return ContentType::Xml;
         }
 
+        // A leading YAML document marker
+        if let Some(rest) = trimmed.strip_prefix("---") {
+            if rest.is_empty() || rest.starts_with(char::is_whitespace) {
+                return ContentType::Yaml;
+            }
+        }
+
+        // Consistent comma-separated columns on every line
+        if looks_like_csv(trimmed) {
+            return ContentType::Csv;
+        }
+
         // If it's valid UTF-8 and doesn't match specific formats, treat as plain text
         return ContentType::PlainText;
     }
@@ -249,7 +304,11 @@ mod tests {
     #[test]
     fn test_content_type_as_str() {
         assert_eq!(ContentType::Json.as_str(), "JSON");
+        assert_eq!(ContentType::ProblemJson.as_str(), "Problem JSON");
         assert_eq!(ContentType::Xml.as_str(), "XML");
+        assert_eq!(ContentType::NdJson.as_str(), "NDJSON");
+        assert_eq!(ContentType::Csv.as_str(), "CSV");
+        assert_eq!(ContentType::Yaml.as_str(), "YAML");
         assert_eq!(ContentType::Html.as_str(), "HTML");
         assert_eq!(ContentType::PlainText.as_str(), "Plain Text");
         assert_eq!(ContentType::Binary.as_str(), "Binary");
@@ -259,7 +318,11 @@ mod tests {
     #[test]
     fn test_content_type_is_textual() {
         assert!(ContentType::Json.is_textual());
+        assert!(ContentType::ProblemJson.is_textual());
         assert!(ContentType::Xml.is_textual());
+        assert!(ContentType::NdJson.is_textual());
+        assert!(ContentType::Csv.is_textual());
+        assert!(ContentType::Yaml.is_textual());
         assert!(ContentType::Html.is_textual());
         assert!(ContentType::PlainText.is_textual());
         assert!(!ContentType::Binary.is_textual());
@@ -268,7 +331,7 @@ mod tests {
 
     #[test]
     fn test_detect_content_type_from_header_json() {
-        let mut headers = HashMap::new();
+        let mut headers = Headers::new();
         headers.insert("Content-Type".to_string(), "application/json".to_string());
         let body = b"{}";
 
@@ -277,7 +340,7 @@ mod tests {
 
     #[test]
     fn test_detect_content_type_from_header_json_with_charset() {
-        let mut headers = HashMap::new();
+        let mut headers = Headers::new();
         headers.insert(
             "Content-Type".to_string(),
             "application/json; charset=utf-8".to_string(),
@@ -287,9 +350,25 @@ mod tests {
         assert_eq!(detect_content_type(&headers, body), ContentType::Json);
     }
 
+    #[test]
+    fn test_detect_content_type_from_header_problem_json() {
+        let mut headers = Headers::new();
+        headers.insert(
+            "Content-Type".to_string(),
+            "application/problem+json; charset=utf-8".to_string(),
+        );
+        let body = br#"{"title": "Not Found", "status": 404}"#;
+
+        assert_eq!(
+            detect_content_type(&headers, body),
+            ContentType::ProblemJson
+        );
+        assert!(ContentType::ProblemJson.is_json());
+    }
+
     #[test]
     fn test_detect_content_type_from_header_xml() {
-        let mut headers = HashMap::new();
+        let mut headers = Headers::new();
         headers.insert("Content-Type".to_string(), "application/xml".to_string());
         let body = b"<root></root>";
 
@@ -298,16 +377,57 @@ mod tests {
 
     #[test]
     fn test_detect_content_type_from_header_html() {
-        let mut headers = HashMap::new();
+        let mut headers = Headers::new();
         headers.insert("Content-Type".to_string(), "text/html".to_string());
         let body = b"<html></html>";
 
         assert_eq!(detect_content_type(&headers, body), ContentType::Html);
     }
 
+    #[test]
+    fn test_detect_content_type_from_header_csv() {
+        let mut headers = Headers::new();
+        headers.insert(
+            "Content-Type".to_string(),
+            "text/csv; charset=utf-8".to_string(),
+        );
+        let body = b"id,name\n1,Alice";
+
+        assert_eq!(detect_content_type(&headers, body), ContentType::Csv);
+    }
+
+    #[test]
+    fn test_detect_content_type_from_header_ndjson() {
+        let body = b"{\"id\":1}";
+        for mime in [
+            "application/x-ndjson",
+            "application/jsonl; charset=utf-8",
+            "application/json-seq",
+        ] {
+            let mut headers = Headers::new();
+            headers.insert("Content-Type".to_string(), mime.to_string());
+
+            assert_eq!(detect_content_type(&headers, body), ContentType::NdJson);
+        }
+    }
+
+    #[test]
+    fn test_detect_content_type_from_header_yaml() {
+        let mut headers = Headers::new();
+        headers.insert("Content-Type".to_string(), "application/x-yaml".to_string());
+        let body = b"name: api";
+
+        assert_eq!(detect_content_type(&headers, body), ContentType::Yaml);
+
+        let mut headers = Headers::new();
+        headers.insert("Content-Type".to_string(), "text/yaml".to_string());
+
+        assert_eq!(detect_content_type(&headers, body), ContentType::Yaml);
+    }
+
     #[test]
     fn test_detect_content_type_from_header_plain_text() {
-        let mut headers = HashMap::new();
+        let mut headers = Headers::new();
         headers.insert("Content-Type".to_string(), "text/plain".to_string());
         let body = b"Hello, World!";
 
@@ -316,7 +436,7 @@ mod tests {
 
     #[test]
     fn test_detect_content_type_from_header_image() {
-        let mut headers = HashMap::new();
+        let mut headers = Headers::new();
         headers.insert("Content-Type".to_string(), "image/png".to_string());
         let body = b"\x89PNG\r\n\x1a\n";
 
@@ -325,7 +445,7 @@ mod tests {
 
     #[test]
     fn test_detect_content_type_from_header_binary() {
-        let mut headers = HashMap::new();
+        let mut headers = Headers::new();
         headers.insert(
             "Content-Type".to_string(),
             "application/octet-stream".to_string(),
@@ -337,7 +457,7 @@ mod tests {
 
     #[test]
     fn test_detect_content_type_case_insensitive_header() {
-        let mut headers = HashMap::new();
+        let mut headers = Headers::new();
         headers.insert("content-type".to_string(), "application/json".to_string());
         let body = b"{}";
 
@@ -386,6 +506,33 @@ mod tests {
         assert_eq!(inspect_body_content(body), ContentType::PlainText);
     }
 
+    #[test]
+    fn test_inspect_body_csv() {
+        let body = b"id,name,email\n1,Alice,alice@example.com\n2,Bob,bob@example.com\n";
+        assert_eq!(inspect_body_content(body), ContentType::Csv);
+    }
+
+    #[test]
+    fn test_inspect_body_ndjson() {
+        let body = b"{\"id\":1,\"name\":\"a\"}\n{\"id\":2,\"name\":\"b\"}\n";
+        assert_eq!(inspect_body_content(body), ContentType::NdJson);
+
+        let body = b"{\n  \"id\": 1\n}";
+        assert_eq!(inspect_body_content(body), ContentType::Json);
+    }
+
+    #[test]
+    fn test_inspect_body_yaml() {
+        let body = b"---\nname: api\nversion: 2\n";
+        assert_eq!(inspect_body_content(body), ContentType::Yaml);
+
+        let body = b"--- # service config\nname: api\n";
+        assert_eq!(inspect_body_content(body), ContentType::Yaml);
+
+        let body = b"----- BEGIN SECTION -----";
+        assert_eq!(inspect_body_content(body), ContentType::PlainText);
+    }
+
     #[test]
     fn test_inspect_body_binary() {
         let body = b"\x00\x01\x02\x03\xFF\xFE\xFD";
@@ -430,7 +577,7 @@ mod tests {
 
     #[test]
     fn test_detect_content_type_empty_body() {
-        let headers = HashMap::new();
+        let headers = Headers::new();
         let body = b"";
         assert_eq!(detect_content_type(&headers, body), ContentType::PlainText);
     }
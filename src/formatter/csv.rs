@@ -0,0 +1,271 @@
+//! CSV formatting as an aligned table.
+//!
+//! This module parses CSV responses (RFC 4180: quoted fields, doubled quotes,
+//! and commas or line breaks inside quotes) and renders them as a table with
+//! aligned columns and a separator under the header row.
+
+use crate::formatter::FormatError;
+
+/// Maximum CSV size to format (10MB).
+///
+/// Responses larger than this will not be formatted to avoid performance issues.
+const MAX_CSV_FORMAT_SIZE: usize = 10 * 1024 * 1024; // 10MB
+
+/// Separator between table columns.
+const COLUMN_SEPARATOR: &str = " | ";
+
+/// Formats CSV as a table with aligned columns.
+///
+/// The first row is treated as the header and underlined. Rows with fewer
+/// fields than the widest row leave the remaining columns empty. Line breaks
+/// inside a field are shown as `\n` so each record stays on one line.
+///
+/// # Arguments
+///
+/// * `csv` - CSV text to format
+///
+/// # Returns
+///
+/// `Ok(String)` with the table, or `Err(FormatError)` if:
+/// - The CSV is empty or has an unterminated quoted field
+/// - The CSV exceeds the maximum size limit
+///
+/// # Examples
+///
+/// ```
+/// use rest_client::formatter::csv::format_csv_pretty;
+///
+/// let table = format_csv_pretty("id,name\n1,Alice\n2,Bob\n").unwrap();
+/// assert_eq!(table, "id | name\n---+------\n1  | Alice\n2  | Bob\n");
+/// ```
+pub fn format_csv_pretty(csv: &str) -> Result<String, FormatError> {
+    if csv.len() > MAX_CSV_FORMAT_SIZE {
+        return Err(FormatError::ResponseTooLarge(csv.len()));
+    }
+
+    let rows = parse_csv(csv)?;
+    if rows.is_empty() {
+        return Err(FormatError::CsvError("Empty CSV content".to_string()));
+    }
+
+    let rows: Vec<Vec<String>> = rows
+        .into_iter()
+        .map(|row| {
+            row.into_iter()
+                .map(|field| field.replace("\r\n", "\\n").replace('\n', "\\n"))
+                .collect()
+        })
+        .collect();
+
+    let column_count = rows.iter().map(Vec::len).max().unwrap_or(0);
+    let mut widths = vec![0; column_count];
+    for row in &rows {
+        for (width, field) in widths.iter_mut().zip(row) {
+            *width = (*width).max(field.chars().count());
+        }
+    }
+
+    let mut output = String::new();
+    for (index, row) in rows.iter().enumerate() {
+        // Trailing empty fields are left out rather than padded
+        let filled = row
+            .iter()
+            .rposition(|field| !field.is_empty())
+            .map_or(0, |last| last + 1);
+        let line = row[..filled]
+            .iter()
+            .zip(&widths)
+            .map(|(field, &width)| format!("{:<width$}", field, width = width))
+            .collect::<Vec<_>>()
+            .join(COLUMN_SEPARATOR);
+        output.push_str(line.trim_end());
+        output.push('\n');
+
+        if index == 0 {
+            let separator = widths
+                .iter()
+                .map(|&width| "-".repeat(width))
+                .collect::<Vec<_>>()
+                .join("-+-");
+            output.push_str(&separator);
+            output.push('\n');
+        }
+    }
+
+    Ok(output)
+}
+
+/// Parses CSV text into records of fields.
+///
+/// Fields may be quoted with `"`, in which case they can contain commas,
+/// line breaks, and doubled quotes (`""`) standing for a literal quote.
+/// Records end at `\n` or `\r\n`; blank lines are skipped.
+///
+/// # Arguments
+///
+/// * `csv` - CSV text to parse
+///
+/// # Returns
+///
+/// `Ok(Vec<Vec<String>>)` with one entry per record, or
+/// `Err(FormatError::CsvError)` if a quoted field is never closed.
+pub fn parse_csv(csv: &str) -> Result<Vec<Vec<String>>, FormatError> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut field_started = false;
+    let mut chars = csv.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => in_quotes = false,
+                _ => field.push(c),
+            }
+            continue;
+        }
+
+        match c {
+            '"' if field.is_empty() => {
+                in_quotes = true;
+                field_started = true;
+            }
+            ',' => {
+                record.push(std::mem::take(&mut field));
+                field_started = true;
+            }
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' => {
+                if field_started || !field.is_empty() {
+                    record.push(std::mem::take(&mut field));
+                    records.push(std::mem::take(&mut record));
+                }
+                field_started = false;
+            }
+            _ => field.push(c),
+        }
+    }
+
+    if in_quotes {
+        return Err(FormatError::CsvError(
+            "Unterminated quoted field".to_string(),
+        ));
+    }
+    if field_started || !field.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+
+    Ok(records)
+}
+
+/// Checks whether text looks like CSV when no Content-Type says so.
+///
+/// Requires a header and at least one more record, at least two columns,
+/// and the same number of fields in every record, so prose that happens to
+/// contain commas isn't mistaken for CSV.
+///
+/// # Arguments
+///
+/// * `text` - Response body text
+///
+/// # Returns
+///
+/// `true` if the text appears to be CSV, `false` otherwise.
+pub fn looks_like_csv(text: &str) -> bool {
+    let Ok(records) = parse_csv(text) else {
+        return false;
+    };
+
+    let columns = match records.first() {
+        Some(header) if header.len() >= 2 => header.len(),
+        _ => return false,
+    };
+
+    records.len() >= 2 && records.iter().all(|record| record.len() == columns)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_csv_pretty() {
+        let csv = "id,name,city\n1,Alice,Paris\n22,Bob,New York\n";
+
+        assert_eq!(
+            format_csv_pretty(csv).unwrap(),
+            "id | name  | city\n\
+             ---+-------+---------\n\
+             1  | Alice | Paris\n\
+             22 | Bob   | New York\n"
+        );
+    }
+
+    #[test]
+    fn test_quoted_fields() {
+        let csv = "name,quote\r\n\"Smith, John\",\"He said \"\"hi\"\"\"\r\n\"Doe\",\"line one\nline two\"\r\n";
+
+        let records = parse_csv(csv).unwrap();
+        assert_eq!(
+            records,
+            vec![
+                vec!["name", "quote"],
+                vec!["Smith, John", "He said \"hi\""],
+                vec!["Doe", "line one\nline two"],
+            ]
+        );
+
+        assert_eq!(
+            format_csv_pretty(csv).unwrap(),
+            "name        | quote\n\
+             ------------+-------------------\n\
+             Smith, John | He said \"hi\"\n\
+             Doe         | line one\\nline two\n"
+        );
+    }
+
+    #[test]
+    fn test_ragged_rows() {
+        let csv = "a,b,c\n1\n2,3,4,5\n,,\n";
+
+        assert_eq!(
+            format_csv_pretty(csv).unwrap(),
+            "a | b | c\n\
+             --+---+---+--\n\
+             1\n\
+             2 | 3 | 4 | 5\n\
+             \n"
+        );
+    }
+
+    #[test]
+    fn test_invalid_csv() {
+        assert!(matches!(
+            format_csv_pretty("a,b\n\"unterminated,1\n"),
+            Err(FormatError::CsvError(_))
+        ));
+        assert!(matches!(
+            format_csv_pretty("\n\n"),
+            Err(FormatError::CsvError(_))
+        ));
+    }
+
+    #[test]
+    fn test_looks_like_csv() {
+        assert!(looks_like_csv("id,name\n1,Alice\n2,Bob"));
+        assert!(looks_like_csv("id,note\n1,\"a, b\"\n"));
+
+        assert!(!looks_like_csv("id,name"));
+        assert!(!looks_like_csv(
+            "Hello, world.\nThis is plain text, really.\nOk"
+        ));
+        assert!(!looks_like_csv("just text\nmore text\n"));
+        assert!(!looks_like_csv("a,b\n1,2,3\n"));
+    }
+}
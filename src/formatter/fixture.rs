@@ -0,0 +1,391 @@
+//! Comparison of response bodies against saved fixtures.
+//!
+//! This module backs the `/assert-matches` command. JSON bodies are compared
+//! structurally: both sides are parsed and pretty-printed with sorted keys, so
+//! formatting and key order don't matter. Fields listed in a
+//! `# @ignore-fields` directive are blanked on both sides first, so values
+//! that change on every call (IDs, timestamps) don't cause a mismatch. Any
+//! other body is compared as text. Mismatches are reported as a line diff.
+
+use crate::formatter::jsonpath::{blank, JsonPathError};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde_json::Value;
+
+/// Regex for `# @ignore-fields id,timestamp` directives.
+static IGNORE_FIELDS_DIRECTIVE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^(?:#|//)\s*@ignore-fields\s+(.+?)\s*$")
+        .expect("Failed to compile ignore-fields directive regex")
+});
+
+/// Number of unchanged lines shown around each change in a diff.
+const DIFF_CONTEXT_LINES: usize = 3;
+
+/// Largest LCS table, in cells, a line diff is computed with (about 32 MB).
+const MAX_DIFF_CELLS: usize = 4_000_000;
+
+/// Collects the fields named by the `# @ignore-fields` directives of a request block.
+///
+/// Only lines before the body are considered, so a body line that happens to
+/// look like a directive is not treated as one.
+///
+/// # Arguments
+///
+/// * `lines` - Vector of (line_number, line_content) tuples for one request
+///
+/// # Returns
+///
+/// The fields in the order they appear, across all directives.
+///
+/// # Examples
+///
+/// ```
+/// use rest_client::formatter::fixture::parse_ignore_fields_directives;
+///
+/// let lines = vec![(0, "# @ignore-fields id, createdAt"), (1, "GET https://api.example.com/user")];
+/// assert_eq!(parse_ignore_fields_directives(&lines), vec!["id", "createdAt"]);
+/// ```
+pub fn parse_ignore_fields_directives(lines: &[(usize, &str)]) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut past_request_line = false;
+
+    for (_, line) in lines {
+        let trimmed = line.trim();
+
+        if let Some(captures) = IGNORE_FIELDS_DIRECTIVE_REGEX.captures(trimmed) {
+            fields.extend(
+                captures[1]
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|field| !field.is_empty())
+                    .map(str::to_string),
+            );
+            continue;
+        }
+
+        let is_comment = trimmed.starts_with('#') || trimmed.starts_with("//");
+        if trimmed.is_empty() && past_request_line {
+            break;
+        }
+        if !trimmed.is_empty() && !is_comment {
+            past_request_line = true;
+        }
+    }
+
+    fields
+}
+
+/// Compares a response body against a fixture.
+///
+/// When both are JSON, the fields in `ignore_fields` are set to `null` on
+/// both sides and the documents are compared as pretty-printed JSON with
+/// sorted keys. A field starting with `$` is a JSONPath expression; any
+/// other field matches that member at any depth (`id` is `$..id`, and
+/// `user.id` is `$..user.id`).
+/// Otherwise the bodies are compared as text, ignoring trailing whitespace.
+///
+/// # Arguments
+///
+/// * `body` - The response body
+/// * `fixture` - The expected body
+/// * `ignore_fields` - Fields to blank before comparing JSON bodies
+///
+/// # Returns
+///
+/// `Ok(None)` if the body matches, `Ok(Some(diff))` with a line diff of the
+/// fixture (`-`) against the body (`+`) if it doesn't, or a summary of where
+/// they start to differ when too many lines changed to diff them, or
+/// `Err(JsonPathError::InvalidPath)` if an ignored field isn't a valid path.
+///
+/// # Examples
+///
+/// ```
+/// use rest_client::formatter::fixture::compare_with_fixture;
+///
+/// let fixture = r#"{"id": 1, "name": "alice"}"#;
+/// let body = r#"{"name":"alice","id":2}"#;
+/// assert_eq!(compare_with_fixture(body, fixture, &["id".to_string()]).unwrap(), None);
+/// assert!(compare_with_fixture(body, fixture, &[]).unwrap().is_some());
+/// ```
+pub fn compare_with_fixture(
+    body: &str,
+    fixture: &str,
+    ignore_fields: &[String],
+) -> Result<Option<String>, JsonPathError> {
+    let (expected, actual) = match (
+        serde_json::from_str::<Value>(fixture),
+        serde_json::from_str::<Value>(body),
+    ) {
+        (Ok(mut expected), Ok(mut actual)) => {
+            for field in ignore_fields {
+                let path = if field.starts_with('$') {
+                    field.clone()
+                } else {
+                    format!("$..{}", field)
+                };
+                blank(&mut expected, &path)?;
+                blank(&mut actual, &path)?;
+            }
+            (pretty(&expected), pretty(&actual))
+        }
+        _ => (fixture.trim_end().to_string(), body.trim_end().to_string()),
+    };
+
+    if expected == actual {
+        return Ok(None);
+    }
+
+    let expected_lines: Vec<&str> = expected.lines().collect();
+    let actual_lines: Vec<&str> = actual.lines().collect();
+    Ok(Some(match diff_lines(&expected_lines, &actual_lines) {
+        Some(diff) => format_diff(&diff),
+        None => format_diff_summary(&expected_lines, &actual_lines),
+    }))
+}
+
+/// Pretty-prints a JSON value; keys come out sorted.
+fn pretty(value: &Value) -> String {
+    serde_json::to_string_pretty(value).unwrap_or_else(|_| value.to_string())
+}
+
+/// One line of a diff.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DiffLine<'a> {
+    /// A line present in both texts
+    Same(&'a str),
+    /// A line only in the expected text
+    Removed(&'a str),
+    /// A line only in the actual text
+    Added(&'a str),
+}
+
+/// Computes a line diff of two texts.
+///
+/// Lines shared at the start and end are matched directly; the rest are
+/// diffed with a longest common subsequence table. Returns `None` if that
+/// table would exceed `MAX_DIFF_CELLS`.
+fn diff_lines<'a>(expected: &[&'a str], actual: &[&'a str]) -> Option<Vec<DiffLine<'a>>> {
+    let prefix = common_prefix(expected, actual);
+    let suffix = expected[prefix..]
+        .iter()
+        .rev()
+        .zip(actual[prefix..].iter().rev())
+        .take_while(|(expected, actual)| expected == actual)
+        .count();
+    let changed_expected = &expected[prefix..expected.len() - suffix];
+    let changed_actual = &actual[prefix..actual.len() - suffix];
+
+    let cells = (changed_expected.len() + 1).saturating_mul(changed_actual.len() + 1);
+    if cells > MAX_DIFF_CELLS {
+        return None;
+    }
+
+    let mut diff: Vec<DiffLine> = expected[..prefix]
+        .iter()
+        .map(|line| DiffLine::Same(line))
+        .collect();
+    diff.extend(lcs_diff(changed_expected, changed_actual));
+    diff.extend(
+        expected[expected.len() - suffix..]
+            .iter()
+            .map(|line| DiffLine::Same(line)),
+    );
+    Some(diff)
+}
+
+/// Returns the number of lines two texts share at the start.
+fn common_prefix(expected: &[&str], actual: &[&str]) -> usize {
+    expected
+        .iter()
+        .zip(actual)
+        .take_while(|(expected, actual)| expected == actual)
+        .count()
+}
+
+/// Computes a line diff from the longest common subsequence of two texts.
+fn lcs_diff<'a>(expected: &[&'a str], actual: &[&'a str]) -> Vec<DiffLine<'a>> {
+    // lengths[i][j] is the LCS length of expected[i..] and actual[j..]
+    let mut lengths = vec![vec![0usize; actual.len() + 1]; expected.len() + 1];
+    for i in (0..expected.len()).rev() {
+        for j in (0..actual.len()).rev() {
+            lengths[i][j] = if expected[i] == actual[j] {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+
+    let mut diff = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < expected.len() && j < actual.len() {
+        if expected[i] == actual[j] {
+            diff.push(DiffLine::Same(expected[i]));
+            i += 1;
+            j += 1;
+        } else if lengths[i + 1][j] >= lengths[i][j + 1] {
+            diff.push(DiffLine::Removed(expected[i]));
+            i += 1;
+        } else {
+            diff.push(DiffLine::Added(actual[j]));
+            j += 1;
+        }
+    }
+    diff.extend(expected[i..].iter().map(|line| DiffLine::Removed(line)));
+    diff.extend(actual[j..].iter().map(|line| DiffLine::Added(line)));
+    diff
+}
+
+/// Describes where two texts start to differ, for texts too different to diff.
+fn format_diff_summary(expected: &[&str], actual: &[&str]) -> String {
+    let line = common_prefix(expected, actual);
+    let mut output = format!(
+        "Bodies differ from line {} on ({} lines expected, {} received); too many lines changed to show a diff\n",
+        line + 1,
+        expected.len(),
+        actual.len()
+    );
+    if let Some(text) = expected.get(line) {
+        output.push_str(&format!("- {}\n", text));
+    }
+    if let Some(text) = actual.get(line) {
+        output.push_str(&format!("+ {}\n", text));
+    }
+    output
+}
+
+/// Formats a diff, showing only the changes and the lines around them.
+fn format_diff(diff: &[DiffLine]) -> String {
+    let changed: Vec<usize> = diff
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| !matches!(line, DiffLine::Same(_)))
+        .map(|(index, _)| index)
+        .collect();
+    let near_change = |index: usize| {
+        changed
+            .iter()
+            .any(|&change| index.abs_diff(change) <= DIFF_CONTEXT_LINES)
+    };
+
+    let mut output = String::new();
+    let mut skipped = false;
+    for (index, line) in diff.iter().enumerate() {
+        if !near_change(index) {
+            skipped = true;
+            continue;
+        }
+        if skipped {
+            output.push_str("  ...\n");
+        }
+        skipped = false;
+        match line {
+            DiffLine::Same(text) => output.push_str(&format!("  {}\n", text)),
+            DiffLine::Removed(text) => output.push_str(&format!("- {}\n", text)),
+            DiffLine::Added(text) => output.push_str(&format!("+ {}\n", text)),
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ignore_fields_directives() {
+        let lines = vec![
+            (0, "# @name getUser"),
+            (1, "# @ignore-fields id, createdAt"),
+            (2, "// @ignore-fields $.meta.requestId"),
+            (3, "GET https://api.example.com/user"),
+            (4, ""),
+            (5, "# @ignore-fields body"),
+        ];
+
+        assert_eq!(
+            parse_ignore_fields_directives(&lines),
+            vec!["id", "createdAt", "$.meta.requestId"]
+        );
+    }
+
+    #[test]
+    fn test_compare_json_ignores_formatting_and_fields() {
+        let fixture = r#"{
+            "id": 17,
+            "user": {"name": "alice", "createdAt": "2024-01-01T00:00:00Z"},
+            "meta": {"requestId": "abc"}
+        }"#;
+        let body = r#"{"meta":{"requestId":"xyz"},"user":{"createdAt":"2024-06-01T12:00:00Z","name":"alice"},"id":42}"#;
+        let ignored = vec![
+            "id".to_string(),
+            "createdAt".to_string(),
+            "$.meta.requestId".to_string(),
+        ];
+
+        assert_eq!(compare_with_fixture(body, fixture, &ignored).unwrap(), None);
+        assert!(compare_with_fixture(body, fixture, &ignored[..2])
+            .unwrap()
+            .is_some());
+
+        // A dotted name matches below any parent of that name
+        let ignored = vec![
+            "id".to_string(),
+            "user.createdAt".to_string(),
+            "meta.requestId".to_string(),
+        ];
+        assert_eq!(compare_with_fixture(body, fixture, &ignored).unwrap(), None);
+    }
+
+    #[test]
+    fn test_compare_reports_diff() {
+        let fixture = r#"{"a": 1, "b": 2, "c": 3, "d": 4, "e": 5, "f": 6, "g": 7, "h": 8, "i": 9}"#;
+        let body = r#"{"a": 1, "b": 2, "c": 3, "d": 4, "e": 5, "f": 6, "g": 7, "h": 8, "i": 10}"#;
+
+        let diff = compare_with_fixture(body, fixture, &[]).unwrap().unwrap();
+        assert_eq!(
+            diff,
+            "  ...\n    \"f\": 6,\n    \"g\": 7,\n    \"h\": 8,\n-   \"i\": 9\n+   \"i\": 10\n  }\n"
+        );
+    }
+
+    #[test]
+    fn test_compare_text_bodies() {
+        assert_eq!(
+            compare_with_fixture("hello\nworld\n", "hello\nworld", &[]).unwrap(),
+            None
+        );
+        assert_eq!(
+            compare_with_fixture("hello\nthere\n", "hello\nworld\n", &[])
+                .unwrap()
+                .unwrap(),
+            "  hello\n- world\n+ there\n"
+        );
+    }
+
+    #[test]
+    fn test_compare_large_bodies() {
+        let fixture: String = (0..5000).map(|i| format!("line {}\n", i)).collect();
+
+        // Shared lines around a small change don't count towards the limit
+        let body = fixture.replace("line 2500\n", "changed\n");
+        let diff = compare_with_fixture(&body, &fixture, &[]).unwrap().unwrap();
+        assert!(diff.contains("- line 2500\n+ changed\n"), "{}", diff);
+
+        // Too many changed lines fall back to a summary
+        let body: String = (0..5000).map(|i| format!("other {}\n", i)).collect();
+        let summary = compare_with_fixture(&body, &fixture, &[]).unwrap().unwrap();
+        assert_eq!(
+            summary,
+            "Bodies differ from line 1 on (5000 lines expected, 5000 received); too many lines changed to show a diff\n- line 0\n+ other 0\n"
+        );
+    }
+
+    #[test]
+    fn test_compare_invalid_ignore_field() {
+        assert!(matches!(
+            compare_with_fixture("{}", "{}", &["$.items[".to_string()]),
+            Err(JsonPathError::InvalidPath(_))
+        ));
+    }
+}
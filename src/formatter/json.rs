@@ -4,6 +4,8 @@
 //! - Pretty-printing with custom indentation (2 spaces)
 //! - Minification for compact view
 //! - JSON validation
+//! - Newline-delimited JSON (NDJSON/JSONL), one record per line
+//! - Field redaction for sanitizing responses before sharing or comparing
 //! - Graceful error handling for malformed JSON
 
 use crate::formatter::FormatError;
@@ -22,6 +24,16 @@ const STREAMING_THRESHOLD: usize = 1024 * 1024; // 1MB
 /// Maximum lines to format when using preview mode for very large responses.
 const PREVIEW_MAX_LINES: usize = 1000;
 
+/// Number of elements kept at each end of a collapsed array.
+const COLLAPSED_ARRAY_EDGE_ITEMS: usize = 3;
+
+/// Record separator that starts each record of a JSON text sequence
+/// (`application/json-seq`, RFC 7464).
+const RECORD_SEPARATOR: char = '\u{1e}';
+
+/// Value that redacted fields are replaced with.
+pub const REDACTED_PLACEHOLDER: &str = "[REDACTED]";
+
 /// Formats JSON with pretty-printing using 2-space indentation.
 ///
 /// This function parses the JSON string and reformats it with consistent
@@ -78,6 +90,185 @@ pub fn format_json_pretty(json: &str) -> Result<String, FormatError> {
     String::from_utf8(buf).map_err(|e| FormatError::EncodingError(e.to_string()))
 }
 
+/// Formats JSON like [`format_json_pretty`], collapsing arrays longer than
+/// `threshold` elements.
+///
+/// A collapsed array shows its first and last few elements with a
+/// `"... (N more items)"` marker in between, so the output stays readable
+/// (and valid JSON) for responses with huge arrays. Nested arrays are
+/// collapsed too. This is for display only; the response body is unchanged.
+///
+/// # Arguments
+///
+/// * `json` - JSON string to format
+/// * `threshold` - Arrays with more elements than this are collapsed
+///
+/// # Returns
+///
+/// `Ok(String)` with the formatted JSON, or `Err(FormatError)` if the JSON is
+/// malformed or exceeds the maximum size limit.
+///
+/// # Examples
+///
+/// ```
+/// use rest_client::formatter::json::format_json_collapsed;
+///
+/// let json = format!("[{}]", (1..=100).map(|n| n.to_string()).collect::<Vec<_>>().join(","));
+/// let formatted = format_json_collapsed(&json, 10).unwrap();
+/// assert!(formatted.contains("\"... (94 more items)\""));
+/// ```
+pub fn format_json_collapsed(json: &str, threshold: usize) -> Result<String, FormatError> {
+    if json.len() > MAX_JSON_FORMAT_SIZE {
+        return Err(FormatError::ResponseTooLarge(json.len()));
+    }
+
+    let mut value: Value =
+        serde_json::from_str(json).map_err(|e| FormatError::JsonError(e.to_string()))?;
+    collapse_large_arrays(&mut value, threshold);
+
+    let formatter = serde_json::ser::PrettyFormatter::with_indent(b"  ");
+    let mut buf = Vec::new();
+    let mut serializer = serde_json::Serializer::with_formatter(&mut buf, formatter);
+
+    use serde::Serialize;
+    value
+        .serialize(&mut serializer)
+        .map_err(|e| FormatError::JsonError(e.to_string()))?;
+
+    String::from_utf8(buf).map_err(|e| FormatError::EncodingError(e.to_string()))
+}
+
+/// Collapses every array in `value` longer than `threshold` elements.
+///
+/// The first and last [`COLLAPSED_ARRAY_EDGE_ITEMS`] elements are kept, and
+/// the rest are replaced by a single `"... (N more items)"` string.
+fn collapse_large_arrays(value: &mut Value, threshold: usize) {
+    match value {
+        Value::Array(items) => {
+            let keep = COLLAPSED_ARRAY_EDGE_ITEMS * 2;
+            if items.len() > threshold && items.len() > keep + 1 {
+                let hidden = items.len() - keep;
+                let tail = items.split_off(items.len() - COLLAPSED_ARRAY_EDGE_ITEMS);
+                items.truncate(COLLAPSED_ARRAY_EDGE_ITEMS);
+                items.push(Value::String(format!("... ({} more items)", hidden)));
+                items.extend(tail);
+            }
+
+            for item in items {
+                collapse_large_arrays(item, threshold);
+            }
+        }
+        Value::Object(map) => {
+            for item in map.values_mut() {
+                collapse_large_arrays(item, threshold);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Replaces the values of the given fields with a placeholder.
+///
+/// Each field is a dotted path from the top level, such as `user.email`.
+/// Arrays met along the way are walked element by element, so
+/// `items.id` redacts `id` in every element of `items`, and `id` redacts it
+/// in every element of a top-level array. Paths that don't exist are
+/// skipped.
+///
+/// # Arguments
+///
+/// * `value` - The JSON value to modify
+/// * `fields` - Dotted paths of the fields to redact
+/// * `placeholder` - Value to put in place of each redacted field
+///
+/// # Returns
+///
+/// The number of values that were replaced.
+///
+/// # Examples
+///
+/// ```
+/// use rest_client::formatter::json::redact_fields;
+/// use serde_json::json;
+///
+/// let mut value = json!({"users": [{"id": 1, "email": "a@x.io"}, {"id": 2}]});
+/// let redacted = redact_fields(&mut value, &["users.email".to_string()], &json!("***"));
+/// assert_eq!(redacted, 1);
+/// assert_eq!(value, json!({"users": [{"id": 1, "email": "***"}, {"id": 2}]}));
+/// ```
+pub fn redact_fields(value: &mut Value, fields: &[String], placeholder: &Value) -> usize {
+    fields
+        .iter()
+        .map(|field| {
+            let segments: Vec<&str> = field.trim().split('.').collect();
+            redact_path(value, &segments, placeholder)
+        })
+        .sum()
+}
+
+/// Redacts one dotted path, walking every element of arrays along the way.
+fn redact_path(value: &mut Value, segments: &[&str], placeholder: &Value) -> usize {
+    let Some((first, rest)) = segments.split_first() else {
+        return 0;
+    };
+
+    match value {
+        Value::Array(items) => items
+            .iter_mut()
+            .map(|item| redact_path(item, segments, placeholder))
+            .sum(),
+        Value::Object(map) => match map.get_mut(*first) {
+            Some(field) if rest.is_empty() => {
+                *field = placeholder.clone();
+                1
+            }
+            Some(field) => redact_path(field, rest, placeholder),
+            None => 0,
+        },
+        _ => 0,
+    }
+}
+
+/// Redacts fields in a JSON document and pretty-prints the result.
+///
+/// Fields are replaced with [`REDACTED_PLACEHOLDER`]; see [`redact_fields`]
+/// for how paths are matched.
+///
+/// # Arguments
+///
+/// * `json` - JSON string to redact
+/// * `fields` - Dotted paths of the fields to redact
+///
+/// # Returns
+///
+/// `Ok(String)` with the redacted, pretty-printed JSON, or
+/// `Err(FormatError)` if the JSON is malformed or exceeds the maximum size
+/// limit.
+///
+/// # Examples
+///
+/// ```
+/// use rest_client::formatter::json::redact_json;
+///
+/// let redacted = redact_json(r#"{"token":"abc","user":"alice"}"#, &["token".to_string()]).unwrap();
+/// assert_eq!(redacted, "{\n  \"token\": \"[REDACTED]\",\n  \"user\": \"alice\"\n}");
+/// ```
+pub fn redact_json(json: &str, fields: &[String]) -> Result<String, FormatError> {
+    if json.len() > MAX_JSON_FORMAT_SIZE {
+        return Err(FormatError::ResponseTooLarge(json.len()));
+    }
+
+    let mut value: Value =
+        serde_json::from_str(json).map_err(|e| FormatError::JsonError(e.to_string()))?;
+    redact_fields(
+        &mut value,
+        fields,
+        &Value::String(REDACTED_PLACEHOLDER.to_string()),
+    );
+
+    serde_json::to_string_pretty(&value).map_err(|e| FormatError::JsonError(e.to_string()))
+}
+
 /// Formats large JSON using streaming approach to avoid memory spikes.
 ///
 /// For responses larger than 1MB, this formats only a preview portion
@@ -253,10 +444,136 @@ pub fn format_json_preview(json: &str, max_lines: usize) -> Result<String, Forma
     }
 }
 
+/// Formats newline-delimited JSON (NDJSON/JSONL) record by record.
+///
+/// Each non-empty line is parsed as its own JSON value and pretty-printed;
+/// records are separated by `---` lines. Blank lines, including a trailing
+/// newline, are skipped, and the leading record separators of a JSON text
+/// sequence are removed.
+///
+/// # Arguments
+///
+/// * `ndjson` - Newline-delimited JSON text
+///
+/// # Returns
+///
+/// `Ok(String)` with the formatted records, or `Err(FormatError)` if:
+/// - There are no records
+/// - A line is not valid JSON (the error names the line)
+/// - The text exceeds the maximum size limit
+///
+/// # Examples
+///
+/// ```
+/// use rest_client::formatter::json::format_ndjson;
+///
+/// let formatted = format_ndjson("{\"id\":1}\n{\"id\":2}\n").unwrap();
+/// assert_eq!(formatted, "{\n  \"id\": 1\n}\n---\n{\n  \"id\": 2\n}");
+/// ```
+pub fn format_ndjson(ndjson: &str) -> Result<String, FormatError> {
+    // Check size limit
+    if ndjson.len() > MAX_JSON_FORMAT_SIZE {
+        return Err(FormatError::ResponseTooLarge(ndjson.len()));
+    }
+
+    let mut records = Vec::new();
+    for (index, line) in ndjson.lines().enumerate() {
+        let line = ndjson_record(line);
+        if line.is_empty() {
+            continue;
+        }
+
+        let value: Value = serde_json::from_str(line)
+            .map_err(|e| FormatError::JsonError(format!("Line {}: {}", index + 1, e)))?;
+        records.push(
+            serde_json::to_string_pretty(&value)
+                .map_err(|e| FormatError::JsonError(e.to_string()))?,
+        );
+    }
+
+    if records.is_empty() {
+        return Err(FormatError::JsonError("Empty NDJSON content".to_string()));
+    }
+
+    Ok(records.join("\n---\n"))
+}
+
+/// Checks whether text looks like newline-delimited JSON.
+///
+/// Requires at least two non-empty lines, each of which parses as JSON on
+/// its own, so a single JSON document (pretty-printed or not) isn't mistaken
+/// for NDJSON.
+///
+/// # Arguments
+///
+/// * `text` - Response body text
+///
+/// # Returns
+///
+/// `true` if the text appears to be NDJSON, `false` otherwise.
+pub fn looks_like_ndjson(text: &str) -> bool {
+    let mut records = 0;
+    for line in text
+        .lines()
+        .map(ndjson_record)
+        .filter(|line| !line.is_empty())
+    {
+        if !validate_json(line) {
+            return false;
+        }
+        records += 1;
+    }
+
+    records >= 2
+}
+
+/// Trims one line of NDJSON, dropping any leading record separators.
+fn ndjson_record(line: &str) -> &str {
+    line.trim()
+        .trim_start_matches(RECORD_SEPARATOR)
+        .trim_start()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_format_json_collapsed_large_array() {
+        let items: Vec<String> = (1..=1000).map(|n| format!(r#"{{"id":{}}}"#, n)).collect();
+        let json = format!(r#"{{"total":1000,"items":[{}]}}"#, items.join(","));
+
+        let formatted = format_json_collapsed(&json, 100).unwrap();
+        let value: Value = serde_json::from_str(&formatted).unwrap();
+        let collapsed = value["items"].as_array().unwrap();
+
+        assert_eq!(collapsed.len(), 7);
+        assert_eq!(collapsed[0]["id"], 1);
+        assert_eq!(collapsed[2]["id"], 3);
+        assert_eq!(collapsed[3], "... (994 more items)");
+        assert_eq!(collapsed[4]["id"], 998);
+        assert_eq!(collapsed[6]["id"], 1000);
+        assert_eq!(value["total"], 1000);
+    }
+
+    #[test]
+    fn test_format_json_collapsed_keeps_small_and_nested_arrays() {
+        let nested: Vec<String> = (0..1000).map(|n| n.to_string()).collect();
+        let json = format!(r#"{{"small":[1,2,3],"outer":[[{}]]}}"#, nested.join(","));
+
+        let formatted = format_json_collapsed(&json, 10).unwrap();
+        let value: Value = serde_json::from_str(&formatted).unwrap();
+
+        assert_eq!(value["small"], serde_json::json!([1, 2, 3]));
+        assert_eq!(value["outer"][0][3], "... (994 more items)");
+        assert_eq!(value["outer"][0][6], 999);
+    }
+
+    #[test]
+    fn test_format_json_collapsed_invalid_json() {
+        assert!(format_json_collapsed("[1, 2", 10).is_err());
+    }
+
     #[test]
     fn test_format_json_pretty_simple() {
         let json = r#"{"name":"John","age":30}"#;
@@ -411,4 +728,106 @@ mod tests {
         assert!(formatted.contains("{}"));
         assert!(formatted.contains("[]"));
     }
+
+    #[test]
+    fn test_format_ndjson() {
+        let ndjson = "{\"level\":\"info\",\"msg\":\"started\"}\n[1,2]\n\"done\"\n";
+
+        assert_eq!(
+            format_ndjson(ndjson).unwrap(),
+            "{\n  \"level\": \"info\",\n  \"msg\": \"started\"\n}\n---\n[\n  1,\n  2\n]\n---\n\"done\""
+        );
+    }
+
+    #[test]
+    fn test_format_ndjson_skips_blank_lines() {
+        let ndjson = "\n{\"id\":1}\n\n   \n{\"id\":2}\r\n\n";
+
+        assert_eq!(
+            format_ndjson(ndjson).unwrap(),
+            "{\n  \"id\": 1\n}\n---\n{\n  \"id\": 2\n}"
+        );
+    }
+
+    #[test]
+    fn test_format_ndjson_json_seq() {
+        let json_seq = "\u{1e}{\"id\":1}\n\u{1e}\u{1e}{\"id\":2}\n";
+
+        assert_eq!(
+            format_ndjson(json_seq).unwrap(),
+            "{\n  \"id\": 1\n}\n---\n{\n  \"id\": 2\n}"
+        );
+        assert!(looks_like_ndjson(json_seq));
+    }
+
+    #[test]
+    fn test_format_ndjson_errors() {
+        match format_ndjson("{\"id\":1}\n{broken\n") {
+            Err(FormatError::JsonError(msg)) => assert!(msg.starts_with("Line 2:")),
+            other => panic!("expected a JSON error, got {:?}", other),
+        }
+        assert!(matches!(
+            format_ndjson("\n\n"),
+            Err(FormatError::JsonError(_))
+        ));
+    }
+
+    #[test]
+    fn test_redact_fields_walks_arrays() {
+        let mut value: Value = serde_json::from_str(
+            r#"{
+                "token": "secret",
+                "items": [
+                    {"id": 1, "owner": {"email": "a@example.com"}},
+                    {"id": 2, "owner": {"name": "bob"}},
+                    {"id": 3, "owner": [{"email": "c@example.com"}]}
+                ]
+            }"#,
+        )
+        .unwrap();
+        let fields = vec![
+            "token".to_string(),
+            "items.owner.email".to_string(),
+            "items.missing".to_string(),
+            "nothing.here".to_string(),
+        ];
+
+        let redacted = redact_fields(&mut value, &fields, &Value::Null);
+
+        assert_eq!(redacted, 3);
+        assert_eq!(value["token"], Value::Null);
+        assert_eq!(value["items"][0]["owner"]["email"], Value::Null);
+        assert_eq!(value["items"][1]["owner"]["name"], "bob");
+        assert_eq!(value["items"][2]["owner"][0]["email"], Value::Null);
+        assert_eq!(value["items"][0]["id"], 1);
+    }
+
+    #[test]
+    fn test_redact_json() {
+        let redacted = redact_json(
+            r#"[{"id":1,"password":"a"},{"id":2,"password":"b"}]"#,
+            &["password".to_string()],
+        )
+        .unwrap();
+        let value: Value = serde_json::from_str(&redacted).unwrap();
+
+        assert_eq!(value[0]["password"], REDACTED_PLACEHOLDER);
+        assert_eq!(value[1]["password"], REDACTED_PLACEHOLDER);
+        assert_eq!(value[1]["id"], 2);
+
+        assert!(matches!(
+            redact_json("not json", &[]),
+            Err(FormatError::JsonError(_))
+        ));
+    }
+
+    #[test]
+    fn test_looks_like_ndjson() {
+        assert!(looks_like_ndjson("{\"id\":1}\n{\"id\":2}\n"));
+        assert!(looks_like_ndjson("{\"id\":1}\n\n[2]"));
+
+        assert!(!looks_like_ndjson("{\"id\":1}\n"));
+        assert!(!looks_like_ndjson("{\n  \"id\": 1\n}"));
+        assert!(!looks_like_ndjson("{\"id\":1}\nnot json"));
+    }
 }
@@ -0,0 +1,521 @@
+//! JSONPath queries for filtering JSON response bodies.
+//!
+//! This module evaluates the JSONPath expressions used by the `# @filter`
+//! directive. The supported syntax covers the common cases:
+//!
+//! - `$` - the root value
+//! - `.name` or `['name']` - an object member
+//! - `[0]`, `[-1]` - an array element, counting from the end when negative
+//! - `[1:3]`, `[:2]`, `[-2:]` - an array slice
+//! - `.*` or `[*]` - every member or element
+//! - `..name`, `..*` - recursive descent
+//!
+//! A path without wildcards, slices, or recursive descent selects a single
+//! value; any other path selects a list of values.
+
+use serde_json::Value;
+use std::fmt;
+
+/// Errors that can occur while filtering a JSON body.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JsonPathError {
+    /// The expression is not valid JSONPath.
+    InvalidPath(String),
+
+    /// The body is not valid JSON.
+    InvalidJson(String),
+
+    /// A single-value path matched nothing.
+    NoMatch(String),
+}
+
+impl fmt::Display for JsonPathError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JsonPathError::InvalidPath(msg) => write!(f, "Invalid JSONPath: {}", msg),
+            JsonPathError::InvalidJson(msg) => write!(f, "Response is not valid JSON: {}", msg),
+            JsonPathError::NoMatch(path) => write!(f, "No value matches {}", path),
+        }
+    }
+}
+
+impl std::error::Error for JsonPathError {}
+
+/// What a path segment selects from each value it is applied to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Selector {
+    /// An object member by name
+    Name(String),
+    /// An array element; negative indices count from the end
+    Index(i64),
+    /// A range of array elements
+    Slice(Option<i64>, Option<i64>),
+    /// Every object member or array element
+    Wildcard,
+}
+
+/// One step of a JSONPath expression.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Segment {
+    /// Whether the selector applies to all descendants (`..`)
+    recursive: bool,
+    selector: Selector,
+}
+
+impl Segment {
+    /// Returns `true` if the segment can select more than one value.
+    fn is_indefinite(&self) -> bool {
+        self.recursive || matches!(self.selector, Selector::Slice(..) | Selector::Wildcard)
+    }
+}
+
+/// Selects the values matching a JSONPath expression.
+///
+/// # Arguments
+///
+/// * `json` - The JSON value to query
+/// * `path` - JSONPath expression starting with `$`
+///
+/// # Returns
+///
+/// `Ok(Vec<&Value>)` with the matches in document order (possibly empty),
+/// or `Err(JsonPathError::InvalidPath)` if the expression can't be parsed.
+///
+/// # Examples
+///
+/// ```
+/// use rest_client::formatter::jsonpath::select;
+/// use serde_json::json;
+///
+/// let json = json!({"items": [{"id": 1}, {"id": 2}]});
+/// let ids = select(&json, "$.items[*].id").unwrap();
+/// assert_eq!(ids, vec![&json!(1), &json!(2)]);
+/// ```
+pub fn select<'a>(json: &'a Value, path: &str) -> Result<Vec<&'a Value>, JsonPathError> {
+    let segments = parse_path(path)?;
+
+    let mut current = vec![json];
+    for segment in &segments {
+        let mut next = Vec::new();
+        for value in current {
+            if segment.recursive {
+                let mut nodes = Vec::new();
+                collect_descendants(value, &mut nodes);
+                for node in nodes {
+                    apply_selector(node, &segment.selector, &mut next);
+                }
+            } else {
+                apply_selector(value, &segment.selector, &mut next);
+            }
+        }
+        current = next;
+    }
+
+    Ok(current)
+}
+
+/// Filters a JSON document through a JSONPath expression.
+///
+/// A single-value path yields that value; any other path yields an array of
+/// every match.
+///
+/// # Arguments
+///
+/// * `text` - The JSON document
+/// * `path` - JSONPath expression starting with `$`
+///
+/// # Returns
+///
+/// `Ok(String)` with the result as pretty-printed JSON, or
+/// `Err(JsonPathError)` if the path or document is invalid, or a single-value
+/// path matched nothing.
+///
+/// # Examples
+///
+/// ```
+/// use rest_client::formatter::jsonpath::filter_json;
+///
+/// let body = r#"{"data": {"items": [{"id": 1}, {"id": 2}]}}"#;
+/// assert_eq!(filter_json(body, "$.data.items[*].id").unwrap(), "[\n  1,\n  2\n]");
+/// assert_eq!(filter_json(body, "$.data.items[0].id").unwrap(), "1");
+/// ```
+pub fn filter_json(text: &str, path: &str) -> Result<String, JsonPathError> {
+    let json: Value =
+        serde_json::from_str(text).map_err(|e| JsonPathError::InvalidJson(e.to_string()))?;
+    let indefinite = parse_path(path)?.iter().any(Segment::is_indefinite);
+    let matches = select(&json, path)?;
+
+    let result = if indefinite {
+        Value::Array(matches.into_iter().cloned().collect())
+    } else {
+        matches
+            .into_iter()
+            .next()
+            .cloned()
+            .ok_or_else(|| JsonPathError::NoMatch(path.trim().to_string()))?
+    };
+
+    serde_json::to_string_pretty(&result).map_err(|e| JsonPathError::InvalidJson(e.to_string()))
+}
+
+/// Replaces every value matching a JSONPath expression with `null`.
+///
+/// # Arguments
+///
+/// * `json` - The JSON value to modify
+/// * `path` - JSONPath expression starting with `$`
+///
+/// # Returns
+///
+/// `Ok(usize)` with the number of values blanked, or
+/// `Err(JsonPathError::InvalidPath)` if the expression can't be parsed.
+///
+/// # Examples
+///
+/// ```
+/// use rest_client::formatter::jsonpath::blank;
+/// use serde_json::json;
+///
+/// let mut json = json!({"items": [{"id": 1, "name": "a"}, {"id": 2, "name": "b"}]});
+/// assert_eq!(blank(&mut json, "$..id").unwrap(), 2);
+/// assert_eq!(json, json!({"items": [{"id": null, "name": "a"}, {"id": null, "name": "b"}]}));
+/// ```
+pub fn blank(json: &mut Value, path: &str) -> Result<usize, JsonPathError> {
+    // Matches are identified by address, since `select` only borrows
+    let targets: Vec<*const Value> = select(json, path)?
+        .into_iter()
+        .map(|value| value as *const Value)
+        .collect();
+
+    let mut blanked = 0;
+    blank_targets(json, &targets, &mut blanked);
+    Ok(blanked)
+}
+
+/// Sets `value`, or the values nested inside it, to `null` where they are targets.
+fn blank_targets(value: &mut Value, targets: &[*const Value], blanked: &mut usize) {
+    if targets.contains(&(value as *const Value)) {
+        *value = Value::Null;
+        *blanked += 1;
+        return;
+    }
+    match value {
+        Value::Object(map) => map
+            .values_mut()
+            .for_each(|child| blank_targets(child, targets, blanked)),
+        Value::Array(items) => items
+            .iter_mut()
+            .for_each(|child| blank_targets(child, targets, blanked)),
+        _ => {}
+    }
+}
+
+/// Pushes the values `selector` picks out of `value` onto `out`.
+fn apply_selector<'a>(value: &'a Value, selector: &Selector, out: &mut Vec<&'a Value>) {
+    match (selector, value) {
+        (Selector::Name(name), Value::Object(map)) => out.extend(map.get(name)),
+        (Selector::Index(index), Value::Array(items)) => {
+            out.extend(resolve_index(*index, items.len()).map(|i| &items[i]))
+        }
+        (Selector::Slice(start, end), Value::Array(items)) => {
+            let len = items.len() as i64;
+            let clamp = |bound: i64| (if bound < 0 { len + bound } else { bound }).clamp(0, len);
+            let start = start.map_or(0, clamp) as usize;
+            let end = end.map_or(len, clamp) as usize;
+            if start < end {
+                out.extend(&items[start..end]);
+            }
+        }
+        (Selector::Wildcard, Value::Object(map)) => out.extend(map.values()),
+        (Selector::Wildcard, Value::Array(items)) => out.extend(items),
+        _ => {}
+    }
+}
+
+/// Converts a possibly negative index into a position within `len` elements.
+fn resolve_index(index: i64, len: usize) -> Option<usize> {
+    let resolved = if index < 0 { len as i64 + index } else { index };
+    (0..len as i64)
+        .contains(&resolved)
+        .then_some(resolved as usize)
+}
+
+/// Collects `value` and every value nested inside it, in document order.
+fn collect_descendants<'a>(value: &'a Value, out: &mut Vec<&'a Value>) {
+    out.push(value);
+    match value {
+        Value::Object(map) => map
+            .values()
+            .for_each(|child| collect_descendants(child, out)),
+        Value::Array(items) => items
+            .iter()
+            .for_each(|child| collect_descendants(child, out)),
+        _ => {}
+    }
+}
+
+/// Parses a JSONPath expression into segments.
+fn parse_path(path: &str) -> Result<Vec<Segment>, JsonPathError> {
+    let path = path.trim();
+    let rest = path
+        .strip_prefix('$')
+        .ok_or_else(|| JsonPathError::InvalidPath(format!("'{}' must start with $", path)))?;
+
+    let chars: Vec<char> = rest.chars().collect();
+    let mut segments = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        match chars[i] {
+            '.' => {
+                let recursive = chars.get(i + 1) == Some(&'.');
+                i += if recursive { 2 } else { 1 };
+
+                if chars.get(i) == Some(&'[') {
+                    if !recursive {
+                        return Err(JsonPathError::InvalidPath(format!(
+                            "unexpected '[' after '.' in '{}'",
+                            path
+                        )));
+                    }
+                    let (selector, next) = parse_bracket(&chars, i, path)?;
+                    segments.push(Segment {
+                        recursive,
+                        selector,
+                    });
+                    i = next;
+                    continue;
+                }
+
+                let start = i;
+                while i < chars.len() && chars[i] != '.' && chars[i] != '[' {
+                    i += 1;
+                }
+                let name: String = chars[start..i].iter().collect();
+                let selector = match name.as_str() {
+                    "" => {
+                        return Err(JsonPathError::InvalidPath(format!(
+                            "missing member name in '{}'",
+                            path
+                        )))
+                    }
+                    "*" => Selector::Wildcard,
+                    _ => Selector::Name(name),
+                };
+                segments.push(Segment {
+                    recursive,
+                    selector,
+                });
+            }
+            '[' => {
+                let (selector, next) = parse_bracket(&chars, i, path)?;
+                segments.push(Segment {
+                    recursive: false,
+                    selector,
+                });
+                i = next;
+            }
+            c => {
+                return Err(JsonPathError::InvalidPath(format!(
+                    "unexpected '{}' in '{}'",
+                    c, path
+                )))
+            }
+        }
+    }
+
+    Ok(segments)
+}
+
+/// Parses a bracketed selector starting at `chars[start] == '['`.
+///
+/// Returns the selector and the position just past the closing `]`.
+fn parse_bracket(
+    chars: &[char],
+    start: usize,
+    path: &str,
+) -> Result<(Selector, usize), JsonPathError> {
+    let invalid = |reason: &str| JsonPathError::InvalidPath(format!("{} in '{}'", reason, path));
+
+    let mut i = start + 1;
+    if let Some(&quote) = chars.get(i).filter(|&&c| c == '\'' || c == '"') {
+        i += 1;
+        let name_start = i;
+        while i < chars.len() && chars[i] != quote {
+            i += 1;
+        }
+        if i >= chars.len() {
+            return Err(invalid("unterminated quoted name"));
+        }
+        let name: String = chars[name_start..i].iter().collect();
+        if chars.get(i + 1) != Some(&']') {
+            return Err(invalid("expected ']' after quoted name"));
+        }
+        return Ok((Selector::Name(name), i + 2));
+    }
+
+    let content_start = i;
+    while i < chars.len() && chars[i] != ']' {
+        i += 1;
+    }
+    if i >= chars.len() {
+        return Err(invalid("unclosed '['"));
+    }
+    let content: String = chars[content_start..i].iter().collect();
+    let content = content.trim();
+
+    let parse_bound = |bound: &str| -> Result<Option<i64>, JsonPathError> {
+        let bound = bound.trim();
+        if bound.is_empty() {
+            return Ok(None);
+        }
+        bound
+            .parse::<i64>()
+            .map(Some)
+            .map_err(|_| invalid(&format!("invalid array index '{}'", bound)))
+    };
+
+    let selector = if content == "*" {
+        Selector::Wildcard
+    } else if let Some((from, to)) = content.split_once(':') {
+        Selector::Slice(parse_bound(from)?, parse_bound(to)?)
+    } else {
+        match parse_bound(content)? {
+            Some(index) => Selector::Index(index),
+            None => return Err(invalid("empty '[]'")),
+        }
+    };
+
+    Ok((selector, i + 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn sample() -> Value {
+        json!({
+            "data": {
+                "items": [
+                    {"id": 1, "name": "alpha", "tags": ["a"]},
+                    {"id": 2, "name": "beta", "tags": []},
+                    {"id": 3, "name": "gamma", "tags": ["c", "d"]}
+                ],
+                "total": 3
+            },
+            "meta": {"id": "req-42"}
+        })
+    }
+
+    #[test]
+    fn test_select_members_and_indices() {
+        let json = sample();
+
+        assert_eq!(select(&json, "$.data.total").unwrap(), vec![&json!(3)]);
+        assert_eq!(
+            select(&json, "$['data']['items'][1].name").unwrap(),
+            vec![&json!("beta")]
+        );
+        assert_eq!(
+            select(&json, "$.data.items[-1].id").unwrap(),
+            vec![&json!(3)]
+        );
+        assert_eq!(select(&json, "$").unwrap(), vec![&json]);
+        assert!(select(&json, "$.data.missing").unwrap().is_empty());
+        assert!(select(&json, "$.data.items[10]").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_select_wildcards_slices_and_descendants() {
+        let json = sample();
+
+        assert_eq!(
+            select(&json, "$.data.items[*].id").unwrap(),
+            vec![&json!(1), &json!(2), &json!(3)]
+        );
+        assert_eq!(
+            select(&json, "$.data.items[1:].name").unwrap(),
+            vec![&json!("beta"), &json!("gamma")]
+        );
+        assert_eq!(
+            select(&json, "$.data.items[:-2].name").unwrap(),
+            vec![&json!("alpha")]
+        );
+        assert_eq!(
+            select(&json, "$..id").unwrap(),
+            vec![&json!(1), &json!(2), &json!(3), &json!("req-42")]
+        );
+        assert_eq!(
+            select(&json, "$.data.items[*].tags.*").unwrap(),
+            vec![&json!("a"), &json!("c"), &json!("d")]
+        );
+    }
+
+    #[test]
+    fn test_filter_json() {
+        let body = sample().to_string();
+
+        assert_eq!(
+            filter_json(&body, "$.data.items[*].id").unwrap(),
+            "[\n  1,\n  2,\n  3\n]"
+        );
+        assert_eq!(
+            filter_json(&body, "$.data.items[0].name").unwrap(),
+            "\"alpha\""
+        );
+        assert_eq!(filter_json(&body, "$.data.missing[*]").unwrap(), "[]");
+        assert_eq!(
+            filter_json(&body, "$.data.missing"),
+            Err(JsonPathError::NoMatch("$.data.missing".to_string()))
+        );
+        assert!(matches!(
+            filter_json("not json", "$.id"),
+            Err(JsonPathError::InvalidJson(_))
+        ));
+    }
+
+    #[test]
+    fn test_blank() {
+        let mut json = sample();
+
+        assert_eq!(blank(&mut json, "$..id").unwrap(), 4);
+        assert_eq!(blank(&mut json, "$.data.items[1:].tags").unwrap(), 2);
+        assert_eq!(blank(&mut json, "$.data.missing").unwrap(), 0);
+        assert_eq!(
+            json,
+            json!({
+                "data": {
+                    "items": [
+                        {"id": null, "name": "alpha", "tags": ["a"]},
+                        {"id": null, "name": "beta", "tags": null},
+                        {"id": null, "name": "gamma", "tags": null}
+                    ],
+                    "total": 3
+                },
+                "meta": {"id": null}
+            })
+        );
+    }
+
+    #[test]
+    fn test_invalid_paths() {
+        let json = sample();
+
+        for path in [
+            "data.items",
+            "$.data.items[",
+            "$.data.items[abc]",
+            "$.data.items[]",
+            "$.data.",
+            "$['data'",
+            "$data",
+        ] {
+            assert!(
+                matches!(select(&json, path), Err(JsonPathError::InvalidPath(_))),
+                "expected '{}' to be rejected",
+                path
+            );
+        }
+    }
+}
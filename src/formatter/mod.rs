@@ -4,21 +4,36 @@
 //! including content type detection, pretty-printing, and metadata extraction.
 
 pub mod content_type;
+pub mod csv;
+pub mod fixture;
 pub mod graphql;
 pub mod json;
+pub mod jsonpath;
+pub mod problem;
+pub mod rate_limit;
 pub mod syntax;
 pub mod xml;
+pub mod yaml;
 
 pub use content_type::{detect_content_type, ContentType};
+pub use csv::format_csv_pretty;
 pub use graphql::{format_graphql_query, format_graphql_request, format_graphql_response};
-pub use json::{format_json_pretty, format_json_safe, minify_json, validate_json};
+pub use json::{
+    format_json_collapsed, format_json_pretty, format_json_safe, format_ndjson, minify_json,
+    validate_json,
+};
+pub use problem::format_problem_json;
+pub use rate_limit::{detect_rate_limit, format_rate_limit, RateLimitInfo};
 pub use syntax::{apply_syntax_highlighting, detect_language, HighlightInfo, Language};
 pub use xml::{format_xml_pretty, format_xml_safe, minify_xml, validate_xml};
+pub use yaml::format_yaml_pretty;
 
+use crate::config::get_config;
 use crate::executor::timing::format_timing_breakdown;
+use crate::models::headers::Headers;
 use crate::models::response::HttpResponse;
+use chrono::Utc;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
 use std::fmt;
 use std::time::Duration;
 
@@ -39,6 +54,12 @@ pub enum FormatError {
     /// XML formatting error.
     XmlError(String),
 
+    /// CSV parsing error.
+    CsvError(String),
+
+    /// YAML parsing error.
+    YamlError(String),
+
     /// UTF-8 encoding error.
     EncodingError(String),
 
@@ -51,6 +72,8 @@ impl fmt::Display for FormatError {
         match self {
             FormatError::JsonError(msg) => write!(f, "JSON formatting error: {}", msg),
             FormatError::XmlError(msg) => write!(f, "XML formatting error: {}", msg),
+            FormatError::CsvError(msg) => write!(f, "CSV formatting error: {}", msg),
+            FormatError::YamlError(msg) => write!(f, "YAML formatting error: {}", msg),
             FormatError::EncodingError(msg) => write!(f, "Encoding error: {}", msg),
             FormatError::ResponseTooLarge(size) => {
                 write!(f, "Response too large to format: {} bytes", size)
@@ -90,6 +113,41 @@ pub struct ResponseMetadata {
 
     /// Timing breakdown for detailed performance metrics.
     pub timing_breakdown: String,
+
+    /// Rate limit details from `X-RateLimit-*` and `Retry-After` headers.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate_limit: Option<RateLimitInfo>,
+
+    /// Notices from the executor, such as a redirect that wasn't followed.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+
+    /// URL the response came from, if redirects led away from the requested URL.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub final_url: Option<String>,
+
+    /// Download throughput in bytes per second, when the executor timed the
+    /// download of a response large enough for it to be meaningful.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub throughput_bps: Option<u64>,
+
+    /// Number of lines in a text body sent with `Transfer-Encoding: chunked`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chunked_lines: Option<usize>,
+
+    /// The `Content-Encoding` the body was decompressed from, if it was
+    /// received compressed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub original_encoding: Option<String>,
+
+    /// Size of the body as received, for a body that was decompressed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compressed_size: Option<usize>,
+
+    /// Sizes of the headers and the body in bytes as received, when the
+    /// `show_section_sizes` format option is set. They add up to `size`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub section_sizes: Option<(usize, usize)>,
 }
 
 impl ResponseMetadata {
@@ -116,6 +174,18 @@ impl ResponseMetadata {
             is_success: response.is_success(),
             is_truncated,
             timing_breakdown,
+            rate_limit: detect_rate_limit(&response.headers, Utc::now()),
+            warnings: response.warnings.clone(),
+            // A redirect chain that ends where it started isn't worth reporting
+            final_url: response
+                .final_url
+                .clone()
+                .filter(|url| response.redirect_chain.first() != Some(url)),
+            throughput_bps: response.throughput_bps,
+            chunked_lines: chunked_line_count(response, content_type),
+            original_encoding: response.original_encoding.clone(),
+            compressed_size: response.compressed_size,
+            section_sizes: None,
         }
     }
 
@@ -135,17 +205,60 @@ impl ResponseMetadata {
 
     /// Formats the size in a human-readable format.
     ///
+    /// The download throughput follows in parentheses when it is known.
+    ///
     /// # Returns
     ///
-    /// String representation like "1.23 KB" or "456 B".
+    /// String representation like "1.23 KB", "456 B", or
+    /// "2.00 MB (8.00 MB/s)".
     pub fn format_size(&self) -> String {
-        if self.size < 1024 {
-            format!("{} B", self.size)
-        } else if self.size < 1024 * 1024 {
-            format!("{:.2} KB", self.size as f64 / 1024.0)
-        } else {
-            format!("{:.2} MB", self.size as f64 / (1024.0 * 1024.0))
+        let size = format_bytes(self.size as f64);
+        match self.throughput_bps {
+            Some(bps) => format!("{} ({}/s)", size, format_bytes(bps as f64)),
+            None => size,
+        }
+    }
+
+    /// Formats the header and body sizes with the headers' share of the total.
+    ///
+    /// # Returns
+    ///
+    /// String like "Headers: 412 B · Body: 4.10 KB (9% headers)", or `None`
+    /// when section sizes weren't recorded.
+    pub fn format_section_sizes(&self) -> Option<String> {
+        let (headers, body) = self.section_sizes?;
+        let mut line = format!(
+            "Headers: {} · Body: {}",
+            format_bytes(headers as f64),
+            format_bytes(body as f64)
+        );
+        if headers + body > 0 {
+            let share = (headers as f64 * 100.0 / (headers + body) as f64).round();
+            line.push_str(&format!(" ({}% headers)", share));
         }
+        Some(line)
+    }
+}
+
+/// Returns `true` if the headers declare a `Content-Encoding` other than
+/// `identity`.
+fn is_encoded(headers: &Headers) -> bool {
+    headers.get("Content-Encoding").is_some_and(|value| {
+        value
+            .split(',')
+            .map(str::trim)
+            .any(|coding| !coding.is_empty() && !coding.eq_ignore_ascii_case("identity"))
+    })
+}
+
+/// Formats a byte count with a B, KB, or MB unit.
+fn format_bytes(bytes: f64) -> String {
+    if bytes < 1024.0 {
+        format!("{} B", bytes as u64)
+    } else if bytes < 1024.0 * 1024.0 {
+        format!("{:.2} KB", bytes / 1024.0)
+    } else {
+        format!("{:.2} MB", bytes / (1024.0 * 1024.0))
     }
 }
 
@@ -178,6 +291,14 @@ pub struct FormattedResponse {
 
     /// Whether the response is currently showing formatted or raw view.
     pub is_formatted: bool,
+
+    /// JSONPath from the request's `# @filter` directive.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filter: Option<String>,
+
+    /// Why the filter could not be applied, in which case the full body is shown.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filter_warning: Option<String>,
 }
 
 impl FormattedResponse {
@@ -196,6 +317,25 @@ impl FormattedResponse {
         output.push_str(&self.status_line);
         output.push_str("\n\n");
 
+        // A throttled request says up front how long to wait
+        if self.metadata.status_code == 429 {
+            if let Some(retry_after) = self
+                .metadata
+                .rate_limit
+                .as_ref()
+                .and_then(|info| info.retry_after)
+            {
+                output.push_str(&format!(
+                    "⚠️  Rate limited: retry after {}\n\n",
+                    rate_limit::format_wait(retry_after)
+                ));
+            }
+        }
+
+        if let Some(final_url) = &self.metadata.final_url {
+            output.push_str(&format!("Redirected to: {}\n\n", final_url));
+        }
+
         // Headers
         output.push_str("Headers:\n");
         output.push_str(&self.headers_text);
@@ -209,19 +349,65 @@ impl FormattedResponse {
             self.content_type.as_str()
         ));
 
+        if let Some(section_sizes) = self.metadata.format_section_sizes() {
+            output.push_str(&section_sizes);
+            output.push('\n');
+        }
+
         // Timing breakdown
         output.push_str(&format!("Timing: {}\n", self.metadata.timing_breakdown));
 
+        if let Some(rate_limit) = &self.metadata.rate_limit {
+            output.push_str(&format_rate_limit(rate_limit, Utc::now()));
+        }
+
+        if let Some(lines) = self.metadata.chunked_lines {
+            output.push_str(&format!(
+                "Transfer-Encoding: chunked ({} line{})\n",
+                lines,
+                if lines == 1 { "" } else { "s" }
+            ));
+        }
+
+        // The raw view notes that the body it shows was decompressed
+        if !self.is_formatted {
+            if let (Some(encoding), Some(size)) = (
+                &self.metadata.original_encoding,
+                self.metadata.compressed_size,
+            ) {
+                output.push_str(&format!(
+                    "Content-Encoding: {} ({} as received, decompressed for display)\n",
+                    encoding,
+                    format_bytes(size as f64)
+                ));
+            }
+        }
+
         if self.metadata.is_truncated {
             output.push_str("⚠️  Response truncated (exceeds 1MB limit)\n");
         }
 
+        for warning in &self.metadata.warnings {
+            output.push_str(&format!("⚠️  {}\n", warning));
+        }
+
+        if let Some(filter) = &self.filter {
+            if let Some(warning) = &self.filter_warning {
+                output.push_str(&format!("⚠️  Filter {} not applied: {}\n", filter, warning));
+            } else if self.is_formatted {
+                output.push_str(&format!(
+                    "Filter: {} (toggle to raw view for the full body)\n",
+                    filter
+                ));
+            }
+        }
+
         output.push_str("\n---\n\n");
 
         // Body
         output.push_str(&self.formatted_body);
 
-        output
+        get_config().output_newline.apply(&output)
     }
 
     /// Toggles between formatted and raw view.
@@ -235,15 +421,7 @@ impl FormattedResponse {
             self.is_formatted = false;
         } else {
             // Switch back to formatted view by reformatting
-            self.formatted_body = match self.content_type {
-                ContentType::Json => {
-                    format_json_pretty(&self.raw_body).unwrap_or_else(|_| self.raw_body.clone())
-                }
-                ContentType::Xml => {
-                    format_xml_pretty(&self.raw_body).unwrap_or_else(|_| self.raw_body.clone())
-                }
-                _ => self.raw_body.clone(),
-            };
+            self.formatted_body = self.reformat_body();
             self.is_formatted = true;
         }
     }
@@ -265,15 +443,91 @@ impl FormattedResponse {
         if self.is_formatted {
             self.formatted_body.clone()
         } else {
-            match self.content_type {
-                ContentType::Json => {
-                    format_json_pretty(&self.raw_body).unwrap_or_else(|_| self.raw_body.clone())
-                }
-                ContentType::Xml => {
-                    format_xml_pretty(&self.raw_body).unwrap_or_else(|_| self.raw_body.clone())
-                }
-                _ => self.raw_body.clone(),
+            self.reformat_body()
+        }
+    }
+
+    /// Formats the raw body again, applying the `# @filter` JSONPath if set.
+    fn reformat_body(&self) -> String {
+        if let Some(filtered) = self
+            .filter
+            .as_deref()
+            .and_then(|filter| filter_body(self.content_type, &self.raw_body, filter).ok())
+        {
+            return filtered;
+        }
+
+        match self.content_type {
+            ContentType::Json => {
+                format_json_pretty(&self.raw_body).unwrap_or_else(|_| self.raw_body.clone())
             }
+            ContentType::NdJson => {
+                format_ndjson(&self.raw_body).unwrap_or_else(|_| self.raw_body.clone())
+            }
+            ContentType::ProblemJson => format_problem_body(&self.raw_body),
+            ContentType::Xml => {
+                format_xml_pretty(&self.raw_body).unwrap_or_else(|_| self.raw_body.clone())
+            }
+            ContentType::Csv => {
+                format_csv_pretty(&self.raw_body).unwrap_or_else(|_| self.raw_body.clone())
+            }
+            ContentType::Yaml => {
+                format_yaml_pretty(&self.raw_body).unwrap_or_else(|_| self.raw_body.clone())
+            }
+            _ => self.raw_body.clone(),
+        }
+    }
+}
+
+/// Filters a body through a `# @filter` JSONPath expression.
+///
+/// # Returns
+///
+/// `Ok(String)` with the matching values as pretty-printed JSON, or
+/// `Err(String)` explaining why the filter can't be applied.
+fn filter_body(content_type: ContentType, text: &str, filter: &str) -> Result<String, String> {
+    if !content_type.is_json() {
+        return Err(format!(
+            "filters apply only to JSON responses, not {}",
+            content_type
+        ));
+    }
+
+    jsonpath::filter_json(text, filter).map_err(|e| e.to_string())
+}
+
+/// Formats a problem+json body, falling back to pretty JSON if it isn't an object.
+fn format_problem_body(text: &str) -> String {
+    format_problem_json(text)
+        .unwrap_or_else(|| format_json_pretty(text).unwrap_or_else(|_| text.to_string()))
+}
+
+/// Options controlling how response bodies are formatted for display.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FormatOptions {
+    /// Whether to collapse JSON arrays longer than `collapse_array_threshold`.
+    pub collapse_large_arrays: bool,
+
+    /// Number of elements above which a JSON array is collapsed.
+    pub collapse_array_threshold: usize,
+
+    /// Whether to show the header and body sizes next to the total size.
+    pub show_section_sizes: bool,
+
+    /// JSONPath expression from `# @filter` that narrows JSON bodies down to
+    /// the matching values.
+    pub filter: Option<String>,
+}
+
+impl Default for FormatOptions {
+    /// Creates format options from the global RestClientConfig settings.
+    fn default() -> Self {
+        let global_config = get_config();
+        Self {
+            collapse_large_arrays: global_config.collapse_large_arrays,
+            collapse_array_threshold: global_config.collapse_array_threshold,
+            show_section_sizes: global_config.show_section_sizes,
+            filter: None,
         }
     }
 }
@@ -281,7 +535,8 @@ impl FormattedResponse {
 /// Formats an HTTP response for display.
 ///
 /// Detects the content type, applies appropriate formatting, and packages
-/// everything into a FormattedResponse ready for display.
+/// everything into a FormattedResponse ready for display. Formatting options
+/// are read from the global configuration.
 ///
 /// # Arguments
 ///
@@ -302,8 +557,61 @@ impl FormattedResponse {
 /// println!("{}", formatted.to_display_string());
 /// ```
 pub fn format_response(response: &HttpResponse) -> FormattedResponse {
-    // Detect content type
-    let content_type = detect_content_type(&response.headers, &response.body);
+    format_response_with_options(response, &FormatOptions::default())
+}
+
+/// Formats an HTTP response for display, filtering JSON bodies through the
+/// request's `# @filter` JSONPath expression.
+///
+/// The formatted body shows only the values matching `filter`, while the raw
+/// body keeps the full response so `toggle_view` can show it. A filter that
+/// can't be applied leaves the body unfiltered and is reported in
+/// `filter_warning`.
+///
+/// # Arguments
+///
+/// * `response` - The HTTP response to format
+/// * `filter` - JSONPath expression, if the request has one
+///
+/// # Returns
+///
+/// A `FormattedResponse` containing the formatted content and metadata.
+pub fn format_response_with_filter(
+    response: &HttpResponse,
+    filter: Option<&str>,
+) -> FormattedResponse {
+    let options = FormatOptions {
+        filter: filter.map(str::to_string),
+        ..FormatOptions::default()
+    };
+    format_response_with_options(response, &options)
+}
+
+/// Formats an HTTP response for display with explicit formatting options.
+///
+/// Options only change `formatted_body` and the metadata shown; `raw_body`
+/// always holds the body as received.
+///
+/// # Arguments
+///
+/// * `response` - The HTTP response to format
+/// * `options` - Formatting options, e.g. whether to collapse large arrays
+///
+/// # Returns
+///
+/// A `FormattedResponse` containing the formatted content and metadata.
+pub fn format_response_with_options(
+    response: &HttpResponse,
+    options: &FormatOptions,
+) -> FormattedResponse {
+    // Detect content type. A body still carrying a `Content-Encoding` (left
+    // compressed by `decompressResponses: false`, or in an unsupported
+    // coding) can't be read as its declared type, so its bytes are shown.
+    let content_type = if is_encoded(&response.headers) {
+        ContentType::Binary
+    } else {
+        detect_content_type(&response.headers, &response.body)
+    };
 
     // Check if response is too large (use 10MB limit for enhanced formatters)
     let max_size = 10 * 1024 * 1024; // 10MB for enhanced formatters
@@ -357,7 +665,12 @@ pub fn format_response(response: &HttpResponse) -> FormattedResponse {
                     }
                 } else {
                     // Use enhanced JSON formatter with syntax highlighting
-                    let formatted = format_json_pretty(text).unwrap_or_else(|_| text.to_string());
+                    let formatted = if options.collapse_large_arrays {
+                        format_json_collapsed(text, options.collapse_array_threshold)
+                    } else {
+                        format_json_pretty(text)
+                    }
+                    .unwrap_or_else(|_| text.to_string());
                     let info = HighlightInfo::new(Language::Json);
                     (formatted, Some(info))
                 }
@@ -368,6 +681,18 @@ pub fn format_response(response: &HttpResponse) -> FormattedResponse {
                 )
             }
         }
+        ContentType::ProblemJson => {
+            if let Ok(text) = std::str::from_utf8(body_to_format) {
+                // Render RFC 7807 fields as an error summary
+                let info = HighlightInfo::new(Language::Json);
+                (format_problem_body(text), Some(info))
+            } else {
+                (
+                    "[Error: Invalid UTF-8 encoding in problem+json response]".to_string(),
+                    None,
+                )
+            }
+        }
         ContentType::Xml => {
             if let Ok(text) = std::str::from_utf8(body_to_format) {
                 // Use enhanced XML formatter with syntax highlighting
@@ -381,6 +706,44 @@ pub fn format_response(response: &HttpResponse) -> FormattedResponse {
                 )
             }
         }
+        ContentType::NdJson => {
+            if let Ok(text) = std::str::from_utf8(body_to_format) {
+                // Pretty-print each record, falling back to the raw text on a bad line
+                let formatted = format_ndjson(text).unwrap_or_else(|_| text.to_string());
+                let info = HighlightInfo::new(Language::Json);
+                (formatted, Some(info))
+            } else {
+                (
+                    "[Error: Invalid UTF-8 encoding in NDJSON response]".to_string(),
+                    None,
+                )
+            }
+        }
+        ContentType::Csv => {
+            if let Ok(text) = std::str::from_utf8(body_to_format) {
+                // Render as an aligned table, falling back to the raw text
+                let formatted = format_csv_pretty(text).unwrap_or_else(|_| text.to_string());
+                (formatted, None)
+            } else {
+                (
+                    "[Error: Invalid UTF-8 encoding in CSV response]".to_string(),
+                    None,
+                )
+            }
+        }
+        ContentType::Yaml => {
+            if let Ok(text) = std::str::from_utf8(body_to_format) {
+                // Normalize indentation, falling back to the raw text if it doesn't parse
+                let formatted = format_yaml_pretty(text).unwrap_or_else(|_| text.to_string());
+                let info = HighlightInfo::new(Language::Yaml);
+                (formatted, Some(info))
+            } else {
+                (
+                    "[Error: Invalid UTF-8 encoding in YAML response]".to_string(),
+                    None,
+                )
+            }
+        }
         ContentType::Html => {
             if let Ok(text) = std::str::from_utf8(body_to_format) {
                 let info = HighlightInfo::new(Language::Html);
@@ -394,7 +757,12 @@ pub fn format_response(response: &HttpResponse) -> FormattedResponse {
         }
         ContentType::PlainText => {
             if let Ok(text) = std::str::from_utf8(body_to_format) {
-                (text.to_string(), None)
+                // Streamed text is shown one logical line at a time
+                if response.is_chunked() {
+                    (text.lines().collect::<Vec<_>>().join("\n"), None)
+                } else {
+                    (text.to_string(), None)
+                }
             } else {
                 (
                     format!("[Error: Invalid UTF-8 encoding in text response]"),
@@ -406,6 +774,15 @@ pub fn format_response(response: &HttpResponse) -> FormattedResponse {
         ContentType::Image => (format_image_info(body_to_format, response.size), None),
     };
 
+    // A `# @filter` JSONPath narrows JSON bodies down to the matching values
+    let (formatted_body, filter_warning) = match options.filter.as_deref() {
+        Some(filter) => match filter_body(content_type, &raw_body, filter) {
+            Ok(filtered) => (filtered, None),
+            Err(warning) => (formatted_body, Some(warning)),
+        },
+        None => (formatted_body, None),
+    };
+
     // Format status line
     let status_line = format!("HTTP/1.1 {} {}", response.status_code, response.status_text);
 
@@ -413,7 +790,14 @@ pub fn format_response(response: &HttpResponse) -> FormattedResponse {
     let headers_text = format_headers(&response.headers);
 
     // Create metadata
-    let metadata = ResponseMetadata::from_response(response, content_type, is_truncated);
+    let mut metadata = ResponseMetadata::from_response(response, content_type, is_truncated);
+    if options.show_section_sizes {
+        // Both as received, so they add up to the reported size even when
+        // the body was decompressed and `Content-Encoding` removed
+        let body_size = response.compressed_size.unwrap_or(response.body.len());
+        let headers_size = response.size.saturating_sub(body_size);
+        metadata.section_sizes = Some((headers_size, body_size));
+    }
 
     FormattedResponse {
         content_type,
@@ -424,7 +808,24 @@ pub fn format_response(response: &HttpResponse) -> FormattedResponse {
         metadata,
         highlight_info,
         is_formatted: true,
+        filter: options.filter.clone(),
+        filter_warning,
+    }
+}
+
+/// Counts the lines of a chunk-encoded text body, for the response summary.
+///
+/// Returns `None` unless the response used `Transfer-Encoding: chunked` and
+/// its body is plain text or NDJSON.
+fn chunked_line_count(response: &HttpResponse, content_type: ContentType) -> Option<usize> {
+    if !response.is_chunked()
+        || !matches!(content_type, ContentType::PlainText | ContentType::NdJson)
+    {
+        return None;
     }
+    std::str::from_utf8(&response.body)
+        .ok()
+        .map(|text| text.lines().count())
 }
 
 /// Formats JSON with pretty-printing.
@@ -487,25 +888,29 @@ pub fn format_xml(xml: &str) -> Result<String, FormatError> {
 
 /// Formats headers as human-readable text.
 ///
+/// Headers are sorted by name; repeated headers (e.g., `Set-Cookie`) are each
+/// shown on their own line, in the order they were received.
+///
 /// # Arguments
 ///
-/// * `headers` - HTTP headers map
+/// * `headers` - HTTP headers
 ///
 /// # Returns
 ///
 /// Formatted headers string with each header on a new line.
-fn format_headers(headers: &HashMap<String, String>) -> String {
+fn format_headers(headers: &Headers) -> String {
     if headers.is_empty() {
         return "(no headers)".to_string();
     }
 
-    let mut header_lines: Vec<String> = headers
+    let mut sorted: Vec<(&String, &String)> = headers.iter().collect();
+    sorted.sort_by_key(|(name, _)| *name);
+
+    sorted
         .iter()
         .map(|(name, value)| format!("  {}: {}", name, value))
-        .collect();
-
-    header_lines.sort();
-    header_lines.join("\n")
+        .collect::<Vec<_>>()
+        .join("\n")
 }
 
 /// Formats binary content as a hex preview.
@@ -608,6 +1013,7 @@ fn format_image_info(body: &[u8], total_size: usize) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::headers::Headers;
     use crate::models::response::HttpResponse;
 
     #[test]
@@ -647,7 +1053,7 @@ mod tests {
 
     #[test]
     fn test_format_headers() {
-        let mut headers = HashMap::new();
+        let mut headers = Headers::new();
         headers.insert("Content-Type".to_string(), "application/json".to_string());
         headers.insert("Content-Length".to_string(), "123".to_string());
 
@@ -657,9 +1063,22 @@ mod tests {
         assert!(formatted.contains("Content-Length: 123"));
     }
 
+    #[test]
+    fn test_format_headers_keeps_repeated_headers() {
+        let mut headers = Headers::new();
+        headers.append("Set-Cookie".to_string(), "b=2".to_string());
+        headers.append("Content-Type".to_string(), "text/plain".to_string());
+        headers.append("Set-Cookie".to_string(), "a=1".to_string());
+
+        assert_eq!(
+            format_headers(&headers),
+            "  Content-Type: text/plain\n  Set-Cookie: b=2\n  Set-Cookie: a=1"
+        );
+    }
+
     #[test]
     fn test_format_headers_empty() {
-        let headers = HashMap::new();
+        let headers = Headers::new();
         let formatted = format_headers(&headers);
 
         assert_eq!(formatted, "(no headers)");
@@ -684,6 +1103,121 @@ mod tests {
         assert!(formatted.contains("1024 bytes"));
     }
 
+    #[test]
+    fn test_display_string_warnings() {
+        let mut response = HttpResponse::new(302, "Found".to_string());
+        response.warnings.push(
+            "Not following 302 redirect to https://example.com/next: the request body can't be resent"
+                .to_string(),
+        );
+
+        let display = format_response(&response).to_display_string();
+        assert!(display.contains(
+            "⚠️  Not following 302 redirect to https://example.com/next: the request body can't be resent\n"
+        ));
+    }
+
+    #[test]
+    fn test_display_string_rate_limit_section() {
+        let mut response = HttpResponse::new(200, "OK".to_string());
+        assert!(!format_response(&response)
+            .to_display_string()
+            .contains("Rate Limit:"));
+
+        response.add_header("X-RateLimit-Limit".to_string(), "60".to_string());
+        response.add_header("X-RateLimit-Remaining".to_string(), "59".to_string());
+        let display = format_response(&response).to_display_string();
+        assert!(display.contains("Rate Limit:\n  Remaining: 59 of 60\n"));
+        assert!(!display.contains("Rate limited"));
+
+        let mut response = HttpResponse::new(429, "Too Many Requests".to_string());
+        response.add_header("Retry-After".to_string(), "30".to_string());
+        let display = format_response(&response).to_display_string();
+        assert!(display.starts_with(
+            "HTTP/1.1 429 Too Many Requests\n\n⚠️  Rate limited: retry after 30s\n\n"
+        ));
+        assert!(display.contains("  Retry After: 30s\n"));
+    }
+
+    #[test]
+    fn test_display_string_chunked_lines() {
+        let mut response = HttpResponse::new(200, "OK".to_string());
+        response.add_header("Content-Type".to_string(), "text/plain".to_string());
+        response.set_body(b"event one\r\nevent two\r\n".to_vec());
+        assert!(!format_response(&response)
+            .to_display_string()
+            .contains("Transfer-Encoding: chunked"));
+
+        response.add_header("Transfer-Encoding".to_string(), "chunked".to_string());
+        let display = format_response(&response).to_display_string();
+        assert!(display.contains("Transfer-Encoding: chunked (2 lines)\n"));
+        assert!(display.ends_with("---\n\nevent one\nevent two"));
+    }
+
+    #[test]
+    fn test_decompressed_body_noted_in_raw_view() {
+        let mut response = HttpResponse::new(200, "OK".to_string());
+        response.add_header("Content-Type".to_string(), "application/json".to_string());
+        response.set_body(br#"{"id":1}"#.to_vec());
+        response.original_encoding = Some("gzip".to_string());
+        response.compressed_size = Some(28);
+
+        let mut formatted = format_response(&response);
+        assert_eq!(
+            formatted.metadata.original_encoding.as_deref(),
+            Some("gzip")
+        );
+        assert_eq!(formatted.raw_body, r#"{"id":1}"#);
+        assert!(!formatted.to_display_string().contains("Content-Encoding:"));
+
+        formatted.toggle_view();
+        assert!(formatted
+            .to_display_string()
+            .contains("Content-Encoding: gzip (28 B as received, decompressed for display)\n"));
+    }
+
+    #[test]
+    fn test_still_encoded_body_shows_hex_preview() {
+        let mut response = HttpResponse::new(200, "OK".to_string());
+        response.add_header("Content-Type".to_string(), "application/json".to_string());
+        response.add_header("Content-Encoding".to_string(), "gzip".to_string());
+        response.set_body(vec![0x1f, 0x8b, 0x08, 0x00]);
+
+        let formatted = format_response(&response);
+        assert_eq!(formatted.content_type, ContentType::Binary);
+        assert!(formatted
+            .formatted_body
+            .starts_with("[Binary Data - Hex Preview]\n\n00000000  1f 8b 08 00"));
+
+        response.headers.remove("Content-Encoding");
+        response.add_header("Content-Encoding".to_string(), "identity".to_string());
+        response.set_body(br#"{"id":1}"#.to_vec());
+        assert_eq!(format_response(&response).content_type, ContentType::Json);
+    }
+
+    #[test]
+    fn test_display_string_redirected_to() {
+        let mut response = HttpResponse::new(200, "OK".to_string());
+        assert!(!format_response(&response)
+            .to_display_string()
+            .contains("Redirected to:"));
+
+        response.redirect_chain = vec![
+            "https://example.com/a".to_string(),
+            "https://example.com/b".to_string(),
+        ];
+        response.final_url = Some("https://example.com/b".to_string());
+        let display = format_response(&response).to_display_string();
+        assert!(display.starts_with("HTTP/1.1 200 OK\n\nRedirected to: https://example.com/b\n\n"));
+
+        // Redirected back to the requested URL
+        response.redirect_chain[1] = "https://example.com/a".to_string();
+        response.final_url = Some("https://example.com/a".to_string());
+        let formatted = format_response(&response);
+        assert_eq!(formatted.metadata.final_url, None);
+        assert!(!formatted.to_display_string().contains("Redirected to:"));
+    }
+
     #[test]
     fn test_format_response_json() {
         let mut response = HttpResponse::new(200, "OK".to_string());
@@ -697,6 +1231,149 @@ mod tests {
         assert!(formatted.status_line.contains("200 OK"));
     }
 
+    #[test]
+    fn test_format_response_problem_json() {
+        let mut response = HttpResponse::new(404, "Not Found".to_string());
+        response.add_header(
+            "Content-Type".to_string(),
+            "application/problem+json".to_string(),
+        );
+        response.set_body(
+            br#"{"type":"about:blank","title":"Not Found","status":404,"detail":"No user 42"}"#
+                .to_vec(),
+        );
+
+        let mut formatted = format_response(&response);
+
+        assert_eq!(formatted.content_type, ContentType::ProblemJson);
+        assert!(formatted.formatted_body.contains("# Problem Details"));
+        assert!(formatted.formatted_body.contains("Detail:   No user 42"));
+
+        // Toggling to raw and back restores the problem view
+        formatted.toggle_view();
+        assert!(formatted.get_body().starts_with('{'));
+        formatted.toggle_view();
+        assert!(formatted.get_body().contains("# Problem Details"));
+    }
+
+    #[test]
+    fn test_format_response_collapses_large_arrays() {
+        let items: Vec<String> = (1..=1000).map(|n| n.to_string()).collect();
+        let body = format!("[{}]", items.join(","));
+        let mut response = HttpResponse::new(200, "OK".to_string());
+        response.add_header("Content-Type".to_string(), "application/json".to_string());
+        response.set_body(body.clone().into_bytes());
+
+        let options = FormatOptions {
+            collapse_large_arrays: true,
+            collapse_array_threshold: 100,
+            show_section_sizes: false,
+            filter: None,
+        };
+        let formatted = format_response_with_options(&response, &options);
+
+        assert!(formatted
+            .formatted_body
+            .contains("\"... (994 more items)\""));
+        assert_eq!(formatted.formatted_body.lines().count(), 9);
+        assert_eq!(formatted.raw_body, body);
+
+        let options = FormatOptions {
+            collapse_large_arrays: false,
+            ..options
+        };
+        let formatted = format_response_with_options(&response, &options);
+        assert!(!formatted.formatted_body.contains("more items"));
+    }
+
+    #[test]
+    fn test_format_response_with_section_sizes() {
+        let mut response = HttpResponse::new(200, "OK".to_string());
+        response.add_header("Content-Type".to_string(), "text/plain".to_string());
+        response.set_body(vec![b'a'; 3000]);
+
+        let options = FormatOptions {
+            collapse_large_arrays: false,
+            collapse_array_threshold: 100,
+            show_section_sizes: true,
+            filter: None,
+        };
+        let formatted = format_response_with_options(&response, &options);
+
+        // "Content-Type: text/plain\r\n" is 26 bytes
+        assert_eq!(formatted.metadata.section_sizes, Some((26, 3000)));
+        assert!(formatted
+            .to_display_string()
+            .contains("Headers: 26 B · Body: 2.93 KB (1% headers)"));
+
+        let options = FormatOptions {
+            show_section_sizes: false,
+            ..options
+        };
+        let formatted = format_response_with_options(&response, &options);
+        assert_eq!(formatted.metadata.section_sizes, None);
+        assert!(!formatted.to_display_string().contains("Headers: 26 B"));
+
+        // A decompressed body is counted as received, with its headers
+        response.size = 26 + "Content-Encoding: gzip\r\n".len() + 120;
+        response.compressed_size = Some(120);
+        let options = FormatOptions {
+            show_section_sizes: true,
+            ..options
+        };
+        let formatted = format_response_with_options(&response, &options);
+        let (headers, body) = formatted.metadata.section_sizes.unwrap();
+        assert_eq!((headers, body), (50, 120));
+        assert_eq!(headers + body, response.size);
+    }
+
+    #[test]
+    fn test_format_response_with_filter() {
+        let body = r#"{"data":{"items":[{"id":1},{"id":2}]}}"#;
+        let mut response = HttpResponse::new(200, "OK".to_string());
+        response.add_header("Content-Type".to_string(), "application/json".to_string());
+        response.set_body(body.as_bytes().to_vec());
+
+        let mut formatted = format_response_with_filter(&response, Some("$.data.items[*].id"));
+        assert_eq!(formatted.formatted_body, "[\n  1,\n  2\n]");
+        assert_eq!(formatted.filter_warning, None);
+        assert!(formatted
+            .to_display_string()
+            .contains("Filter: $.data.items[*].id"));
+
+        // The raw view shows the full body, and toggling back re-applies the filter
+        formatted.toggle_view();
+        assert_eq!(formatted.formatted_body, body);
+        assert!(!formatted.to_display_string().contains("Filter:"));
+        formatted.toggle_view();
+        assert_eq!(formatted.formatted_body, "[\n  1,\n  2\n]");
+    }
+
+    #[test]
+    fn test_format_response_with_invalid_filter() {
+        let body = r#"{"data":{"items":[]}}"#;
+        let mut response = HttpResponse::new(200, "OK".to_string());
+        response.add_header("Content-Type".to_string(), "application/json".to_string());
+        response.set_body(body.as_bytes().to_vec());
+
+        let formatted = format_response_with_filter(&response, Some("$.data.items[abc"));
+        assert!(formatted.formatted_body.contains("\"items\": []"));
+        assert!(formatted
+            .to_display_string()
+            .contains("⚠️  Filter $.data.items[abc not applied: Invalid JSONPath"));
+
+        let mut response = HttpResponse::new(200, "OK".to_string());
+        response.add_header("Content-Type".to_string(), "text/plain".to_string());
+        response.set_body(b"plain text".to_vec());
+
+        let formatted = format_response_with_filter(&response, Some("$.id"));
+        assert_eq!(formatted.formatted_body, "plain text");
+        assert_eq!(
+            formatted.filter_warning.as_deref(),
+            Some("filters apply only to JSON responses, not Plain Text")
+        );
+    }
+
     #[test]
     fn test_format_response_xml() {
         let mut response = HttpResponse::new(200, "OK".to_string());
@@ -771,6 +1448,30 @@ mod tests {
         assert!(size_str.ends_with(" B") || size_str.ends_with(" KB") || size_str.ends_with(" MB"));
     }
 
+    #[test]
+    fn test_response_metadata_format_size_with_throughput() {
+        let mut response = HttpResponse::new(200, "OK".to_string());
+        response.size = 2 * 1024 * 1024;
+        response.timing.download = Duration::from_millis(250);
+
+        // Without a measured download (the WASM executor) there is no rate
+        let metadata = ResponseMetadata::from_response(&response, ContentType::Binary, false);
+        assert_eq!(metadata.throughput_bps, None);
+        assert_eq!(metadata.format_size(), "2.00 MB");
+
+        response.throughput_bps = response.download_throughput();
+        let metadata = ResponseMetadata::from_response(&response, ContentType::Binary, false);
+        assert_eq!(metadata.throughput_bps, Some(8 * 1024 * 1024));
+        assert_eq!(metadata.format_size(), "2.00 MB (8.00 MB/s)");
+
+        // Small responses show the size alone
+        response.size = 512;
+        response.throughput_bps = response.download_throughput();
+        let metadata = ResponseMetadata::from_response(&response, ContentType::Binary, false);
+        assert_eq!(metadata.throughput_bps, None);
+        assert_eq!(metadata.format_size(), "512 B");
+    }
+
     #[test]
     fn test_formatted_response_to_display_string() {
         let mut response = HttpResponse::new(200, "OK".to_string());
@@ -834,6 +1535,15 @@ mod tests {
         let xml_err = FormatError::XmlError("malformed".to_string());
         assert_eq!(format!("{}", xml_err), "XML formatting error: malformed");
 
+        let csv_err = FormatError::CsvError("unterminated".to_string());
+        assert_eq!(format!("{}", csv_err), "CSV formatting error: unterminated");
+
+        let yaml_err = FormatError::YamlError("bad indentation".to_string());
+        assert_eq!(
+            format!("{}", yaml_err),
+            "YAML formatting error: bad indentation"
+        );
+
         let encoding_err = FormatError::EncodingError("not utf-8".to_string());
         assert_eq!(format!("{}", encoding_err), "Encoding error: not utf-8");
 
@@ -0,0 +1,125 @@
+//! Formatter for RFC 7807 problem details (`application/problem+json`).
+//!
+//! Problem details responses have a known structure (`type`, `title`,
+//! `status`, `detail`, `instance`), so they are rendered as an error summary
+//! followed by any extension members instead of as generic JSON.
+
+use serde_json::{Map, Value};
+
+/// Standard problem details members, in display order.
+const PROBLEM_FIELDS: [(&str, &str); 5] = [
+    ("title", "Title"),
+    ("status", "Status"),
+    ("type", "Type"),
+    ("detail", "Detail"),
+    ("instance", "Instance"),
+];
+
+/// Formats an RFC 7807 problem details document for display.
+///
+/// The standard members are shown first as a summary; any remaining
+/// (extension) members are pretty-printed below them.
+///
+/// # Arguments
+///
+/// * `text` - The response body
+///
+/// # Returns
+///
+/// `Some(String)` with the formatted problem, or `None` if the body is not a
+/// JSON object.
+///
+/// # Examples
+///
+/// ```
+/// use rest_client::formatter::problem::format_problem_json;
+///
+/// let body = r#"{"type": "https://example.com/probs/out-of-credit", "title": "Out of credit", "status": 403}"#;
+/// let formatted = format_problem_json(body).unwrap();
+/// assert!(formatted.contains("Title:    Out of credit"));
+/// assert!(formatted.contains("Status:   403"));
+/// ```
+pub fn format_problem_json(text: &str) -> Option<String> {
+    let object: Map<String, Value> = serde_json::from_str(text).ok()?;
+
+    let mut output = String::from("# Problem Details\n\n");
+
+    for (key, label) in PROBLEM_FIELDS {
+        if let Some(value) = object.get(key) {
+            let value = match value {
+                Value::String(s) => s.clone(),
+                other => other.to_string(),
+            };
+            output.push_str(&format!("{:<9} {}\n", format!("{}:", label), value));
+        }
+    }
+
+    // RFC 7807: "about:blank" is the default type when absent
+    if !object.contains_key("type") {
+        output.push_str(&format!("{:<9} about:blank\n", "Type:"));
+    }
+
+    let extensions: Map<String, Value> = object
+        .into_iter()
+        .filter(|(key, _)| !PROBLEM_FIELDS.iter().any(|(field, _)| field == key))
+        .collect();
+
+    if !extensions.is_empty() {
+        output.push_str("\n# Additional Details\n\n");
+        let extensions = Value::Object(extensions);
+        match serde_json::to_string_pretty(&extensions) {
+            Ok(formatted) => output.push_str(&formatted),
+            Err(_) => output.push_str(&extensions.to_string()),
+        }
+        output.push('\n');
+    }
+
+    Some(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_problem_json_all_fields() {
+        let body = r#"{
+            "type": "https://example.com/probs/out-of-credit",
+            "title": "You do not have enough credit.",
+            "status": 403,
+            "detail": "Your current balance is 30, but that costs 50.",
+            "instance": "/account/12345/msgs/abc",
+            "balance": 30
+        }"#;
+
+        let formatted = format_problem_json(body).unwrap();
+
+        assert!(formatted.starts_with("# Problem Details"));
+        assert!(formatted.contains("Title:    You do not have enough credit."));
+        assert!(formatted.contains("Status:   403"));
+        assert!(formatted.contains("Type:     https://example.com/probs/out-of-credit"));
+        assert!(formatted.contains("Detail:   Your current balance is 30"));
+        assert!(formatted.contains("Instance: /account/12345/msgs/abc"));
+        assert!(formatted.contains("# Additional Details"));
+        assert!(formatted.contains("\"balance\": 30"));
+
+        // Summary fields come in a fixed order
+        let title_pos = formatted.find("Title:").unwrap();
+        let detail_pos = formatted.find("Detail:").unwrap();
+        assert!(title_pos < detail_pos);
+    }
+
+    #[test]
+    fn test_format_problem_json_defaults_type() {
+        let formatted = format_problem_json(r#"{"title": "Not Found", "status": 404}"#).unwrap();
+
+        assert!(formatted.contains("Type:     about:blank"));
+        assert!(!formatted.contains("# Additional Details"));
+    }
+
+    #[test]
+    fn test_format_problem_json_rejects_non_object() {
+        assert!(format_problem_json("[1, 2, 3]").is_none());
+        assert!(format_problem_json("not json").is_none());
+    }
+}
@@ -0,0 +1,262 @@
+//! Rate limit header detection.
+//!
+//! Summarizes the quota an API reports through `X-RateLimit-*` (or the
+//! standard `RateLimit-*`) headers and `Retry-After`, so throttling can be
+//! understood without reading through the raw headers.
+
+use crate::models::headers::Headers;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Reset values at or above this are Unix timestamps rather than seconds
+/// from now (the timestamp is in 2001, long past any relative delay).
+const EPOCH_THRESHOLD: u64 = 1_000_000_000;
+
+/// Rate limit details reported by a response's headers.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RateLimitInfo {
+    /// Requests allowed in the current window.
+    pub limit: Option<u64>,
+
+    /// Requests remaining in the current window.
+    pub remaining: Option<u64>,
+
+    /// When the current window resets.
+    pub reset_at: Option<DateTime<Utc>>,
+
+    /// How long to wait before retrying, from `Retry-After`.
+    pub retry_after: Option<Duration>,
+}
+
+impl RateLimitInfo {
+    /// Returns how long from `now` until the window resets, if known.
+    pub fn reset_in(&self, now: DateTime<Utc>) -> Option<Duration> {
+        self.reset_at
+            .map(|reset_at| (reset_at - now).to_std().unwrap_or_default())
+    }
+}
+
+/// Detects rate limit headers in a response.
+///
+/// Reads `X-RateLimit-Limit`, `X-RateLimit-Remaining`, and
+/// `X-RateLimit-Reset` (or the same headers without the `X-` prefix), and
+/// `Retry-After`. A reset value is read as a Unix timestamp if it is large
+/// enough to be one, and as seconds from now otherwise. `Retry-After` may be
+/// a number of seconds or an HTTP date.
+///
+/// # Arguments
+///
+/// * `headers` - The response headers
+/// * `now` - The time the response was received
+///
+/// # Returns
+///
+/// `Some(RateLimitInfo)` if any of the headers is present with a valid value,
+/// `None` otherwise.
+///
+/// # Examples
+///
+/// ```
+/// use chrono::Utc;
+/// use rest_client::formatter::rate_limit::detect_rate_limit;
+/// use rest_client::models::Headers;
+///
+/// let mut headers = Headers::new();
+/// headers.insert("X-RateLimit-Remaining".to_string(), "0".to_string());
+///
+/// let info = detect_rate_limit(&headers, Utc::now()).unwrap();
+/// assert_eq!(info.remaining, Some(0));
+/// ```
+pub fn detect_rate_limit(headers: &Headers, now: DateTime<Utc>) -> Option<RateLimitInfo> {
+    let number = |name: &str| {
+        headers
+            .get(&format!("X-RateLimit-{}", name))
+            .or_else(|| headers.get(&format!("RateLimit-{}", name)))
+            .and_then(|value| value.trim().parse::<u64>().ok())
+    };
+
+    let reset_at = number("Reset").map(|reset| {
+        if reset >= EPOCH_THRESHOLD {
+            DateTime::from_timestamp(reset as i64, 0).unwrap_or(now)
+        } else {
+            now + chrono::Duration::seconds(reset as i64)
+        }
+    });
+
+    let retry_after = headers
+        .get("Retry-After")
+        .and_then(|value| parse_retry_after(value, now));
+
+    let info = RateLimitInfo {
+        limit: number("Limit"),
+        remaining: number("Remaining"),
+        reset_at,
+        retry_after,
+    };
+
+    if info.limit.is_none()
+        && info.remaining.is_none()
+        && info.reset_at.is_none()
+        && info.retry_after.is_none()
+    {
+        return None;
+    }
+
+    Some(info)
+}
+
+/// Parses a `Retry-After` value: delay seconds or an HTTP date.
+///
+/// A date in the past gives a zero wait. Returns `None` if the value is
+/// neither form.
+pub fn parse_retry_after(value: &str, now: DateTime<Utc>) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    DateTime::parse_from_rfc2822(value).ok().map(|date| {
+        (date.with_timezone(&Utc) - now)
+            .to_std()
+            .unwrap_or_default()
+    })
+}
+
+/// Formats a wait as a short human-readable duration, e.g. "5m 30s".
+pub fn format_wait(wait: Duration) -> String {
+    let seconds = wait.as_secs();
+    let (hours, minutes, seconds) = (seconds / 3600, seconds % 3600 / 60, seconds % 60);
+
+    match (hours, minutes) {
+        (0, 0) => format!("{}s", seconds),
+        (0, _) => format!("{}m {}s", minutes, seconds),
+        _ => format!("{}h {}m", hours, minutes),
+    }
+}
+
+/// Formats the "Rate Limit" section of a response display.
+///
+/// # Arguments
+///
+/// * `info` - The detected rate limit details
+/// * `now` - The time used for "resets in" durations
+///
+/// # Returns
+///
+/// A section such as:
+///
+/// ```text
+/// Rate Limit:
+///   Remaining: 0 of 60
+///   Resets: in 5m 30s (14:05:00 UTC)
+/// ```
+pub fn format_rate_limit(info: &RateLimitInfo, now: DateTime<Utc>) -> String {
+    let mut output = String::from("Rate Limit:\n");
+
+    match (info.remaining, info.limit) {
+        (Some(remaining), Some(limit)) => {
+            output.push_str(&format!("  Remaining: {} of {}\n", remaining, limit))
+        }
+        (Some(remaining), None) => output.push_str(&format!("  Remaining: {}\n", remaining)),
+        (None, Some(limit)) => output.push_str(&format!("  Limit: {}\n", limit)),
+        (None, None) => {}
+    }
+
+    if let (Some(reset_at), Some(reset_in)) = (info.reset_at, info.reset_in(now)) {
+        output.push_str(&format!(
+            "  Resets: in {} ({})\n",
+            format_wait(reset_in),
+            reset_at.format("%H:%M:%S UTC")
+        ));
+    }
+
+    if let Some(retry_after) = info.retry_after {
+        output.push_str(&format!("  Retry After: {}\n", format_wait(retry_after)));
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(pairs: &[(&str, &str)]) -> Headers {
+        let mut headers = Headers::new();
+        for (name, value) in pairs {
+            headers.insert(name.to_string(), value.to_string());
+        }
+        headers
+    }
+
+    fn now() -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2026-10-16T14:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_detect_epoch_reset() {
+        let reset = (now() + chrono::Duration::seconds(330))
+            .timestamp()
+            .to_string();
+        let headers = headers(&[
+            ("x-ratelimit-limit", "60"),
+            ("x-ratelimit-remaining", "0"),
+            ("x-ratelimit-reset", &reset),
+        ]);
+
+        let info = detect_rate_limit(&headers, now()).unwrap();
+
+        assert_eq!(info.limit, Some(60));
+        assert_eq!(info.remaining, Some(0));
+        assert_eq!(
+            format_rate_limit(&info, now()),
+            "Rate Limit:\n  Remaining: 0 of 60\n  Resets: in 5m 30s (14:05:30 UTC)\n"
+        );
+    }
+
+    #[test]
+    fn test_detect_relative_reset_and_unprefixed_headers() {
+        let headers = headers(&[("RateLimit-Remaining", "7"), ("RateLimit-Reset", "42")]);
+
+        let info = detect_rate_limit(&headers, now()).unwrap();
+
+        assert_eq!(info.remaining, Some(7));
+        assert_eq!(info.reset_in(now()), Some(Duration::from_secs(42)));
+    }
+
+    #[test]
+    fn test_detect_retry_after() {
+        let info = detect_rate_limit(&headers(&[("Retry-After", "120")]), now()).unwrap();
+        assert_eq!(info.retry_after, Some(Duration::from_secs(120)));
+        assert_eq!(
+            format_rate_limit(&info, now()),
+            "Rate Limit:\n  Retry After: 2m 0s\n"
+        );
+
+        let info = detect_rate_limit(
+            &headers(&[("Retry-After", "Fri, 16 Oct 2026 14:00:30 GMT")]),
+            now(),
+        )
+        .unwrap();
+        assert_eq!(info.retry_after, Some(Duration::from_secs(30)));
+    }
+
+    #[test]
+    fn test_no_rate_limit_headers() {
+        let headers = headers(&[
+            ("Content-Type", "application/json"),
+            ("Retry-After", "soon"),
+        ]);
+        assert_eq!(detect_rate_limit(&headers, now()), None);
+    }
+
+    #[test]
+    fn test_format_wait() {
+        assert_eq!(format_wait(Duration::from_secs(9)), "9s");
+        assert_eq!(format_wait(Duration::from_secs(330)), "5m 30s");
+        assert_eq!(format_wait(Duration::from_secs(7260)), "2h 1m");
+    }
+}
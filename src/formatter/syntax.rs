@@ -1,6 +1,6 @@
 //! Syntax highlighting support for formatted responses.
 //!
-//! This module provides syntax highlighting capabilities for JSON, XML, and HTML.
+//! This module provides syntax highlighting capabilities for JSON, XML, HTML, and YAML.
 //! Since Zed extensions run in WASM without access to syntect, we use a simple
 //! marker-based system that can be interpreted by the Zed editor.
 
@@ -13,6 +13,8 @@ pub enum Language {
     Xml,
     /// HTML syntax
     Html,
+    /// YAML syntax
+    Yaml,
     /// Plain text (no highlighting)
     PlainText,
 }
@@ -32,6 +34,7 @@ impl Language {
             "json" => Language::Json,
             "xml" => Language::Xml,
             "html" => Language::Html,
+            "yaml" | "yml" => Language::Yaml,
             _ => Language::PlainText,
         }
     }
@@ -42,6 +45,7 @@ impl Language {
             Language::Json => "json",
             Language::Xml => "xml",
             Language::Html => "html",
+            Language::Yaml => "yaml",
             Language::PlainText => "txt",
         }
     }
@@ -52,6 +56,7 @@ impl Language {
             Language::Json => "application/json",
             Language::Xml => "application/xml",
             Language::Html => "text/html",
+            Language::Yaml => "application/yaml",
             Language::PlainText => "text/plain",
         }
     }
@@ -237,6 +242,8 @@ mod tests {
         assert_eq!(Language::from_str("JSON"), Language::Json);
         assert_eq!(Language::from_str("xml"), Language::Xml);
         assert_eq!(Language::from_str("html"), Language::Html);
+        assert_eq!(Language::from_str("yaml"), Language::Yaml);
+        assert_eq!(Language::from_str("yml"), Language::Yaml);
         assert_eq!(Language::from_str("unknown"), Language::PlainText);
     }
 
@@ -245,6 +252,7 @@ mod tests {
         assert_eq!(Language::Json.extension(), "json");
         assert_eq!(Language::Xml.extension(), "xml");
         assert_eq!(Language::Html.extension(), "html");
+        assert_eq!(Language::Yaml.extension(), "yaml");
         assert_eq!(Language::PlainText.extension(), "txt");
     }
 
@@ -253,6 +261,7 @@ mod tests {
         assert_eq!(Language::Json.mime_type(), "application/json");
         assert_eq!(Language::Xml.mime_type(), "application/xml");
         assert_eq!(Language::Html.mime_type(), "text/html");
+        assert_eq!(Language::Yaml.mime_type(), "application/yaml");
         assert_eq!(Language::PlainText.mime_type(), "text/plain");
     }
 
@@ -0,0 +1,108 @@
+//! YAML formatting with validation and normalized indentation.
+//!
+//! This module parses YAML responses with serde_yaml and re-emits them with
+//! consistent two-space indentation. Multi-document streams (separated by
+//! `---`) are formatted document by document. Comments are not preserved;
+//! the raw view shows the body as received.
+
+use crate::formatter::FormatError;
+use serde::Deserialize;
+
+/// Maximum YAML size to format (10MB).
+///
+/// Responses larger than this will not be formatted to avoid performance issues.
+const MAX_YAML_FORMAT_SIZE: usize = 10 * 1024 * 1024; // 10MB
+
+/// Formats YAML with normalized indentation.
+///
+/// # Arguments
+///
+/// * `yaml` - YAML string to format
+///
+/// # Returns
+///
+/// `Ok(String)` with the formatted YAML, or `Err(FormatError)` if:
+/// - The YAML is empty or malformed
+/// - The YAML exceeds the maximum size limit
+///
+/// # Examples
+///
+/// ```
+/// use rest_client::formatter::yaml::format_yaml_pretty;
+///
+/// let yaml = "server:\n    host: localhost\n    port: 8080\n";
+/// let formatted = format_yaml_pretty(yaml).unwrap();
+/// assert_eq!(formatted, "server:\n  host: localhost\n  port: 8080\n");
+/// ```
+pub fn format_yaml_pretty(yaml: &str) -> Result<String, FormatError> {
+    // Check size limit
+    if yaml.len() > MAX_YAML_FORMAT_SIZE {
+        return Err(FormatError::ResponseTooLarge(yaml.len()));
+    }
+
+    if yaml.trim().is_empty() {
+        return Err(FormatError::YamlError("Empty YAML content".to_string()));
+    }
+
+    let mut documents = Vec::new();
+    for document in serde_yaml::Deserializer::from_str(yaml) {
+        let value = serde_yaml::Value::deserialize(document)
+            .map_err(|e| FormatError::YamlError(e.to_string()))?;
+        let formatted =
+            serde_yaml::to_string(&value).map_err(|e| FormatError::YamlError(e.to_string()))?;
+        documents.push(formatted);
+    }
+
+    // Keep the document markers of multi-document streams
+    if documents.len() > 1 {
+        return Ok(documents
+            .iter()
+            .map(|document| format!("---\n{}", document))
+            .collect());
+    }
+
+    Ok(documents.concat())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_yaml_normalizes_indentation() {
+        let yaml = "---\nname:   api\nservers:\n    - host: a.example.com\n      port: 443\n    - host: b.example.com\n      port: 8443\nenabled: true\n";
+
+        assert_eq!(
+            format_yaml_pretty(yaml).unwrap(),
+            "name: api\n\
+             servers:\n\
+             - host: a.example.com\n  \
+               port: 443\n\
+             - host: b.example.com\n  \
+               port: 8443\n\
+             enabled: true\n"
+        );
+    }
+
+    #[test]
+    fn test_format_yaml_multiple_documents() {
+        let yaml = "---\nkind: Service\n---\nkind:    Deployment\n";
+
+        assert_eq!(
+            format_yaml_pretty(yaml).unwrap(),
+            "---\nkind: Service\n---\nkind: Deployment\n"
+        );
+    }
+
+    #[test]
+    fn test_format_yaml_malformed() {
+        let result = format_yaml_pretty("key: [unclosed\nother: value\n");
+        assert!(matches!(result, Err(FormatError::YamlError(_))));
+
+        let result = format_yaml_pretty("parent:\n  child: 1\n bad: 2\n");
+        assert!(matches!(result, Err(FormatError::YamlError(_))));
+
+        let result = format_yaml_pretty("   \n");
+        assert!(matches!(result, Err(FormatError::YamlError(_))));
+    }
+}
@@ -0,0 +1,648 @@
+//! GraphQL schema introspection.
+//!
+//! This module provides the standard introspection query, turns an
+//! introspection response into a compact [`SchemaSummary`] of the schema's
+//! types and fields, and caches summaries per endpoint so `/graphql` can
+//! check queries against them without re-querying the server.
+
+use super::{GraphQLRequest, GraphQLResponse};
+use crate::models::request::{HttpMethod, HttpRequest};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// The standard GraphQL introspection query, as sent by common GraphQL tools.
+pub const INTROSPECTION_QUERY: &str = r#"query IntrospectionQuery {
+  __schema {
+    queryType { name }
+    mutationType { name }
+    subscriptionType { name }
+    types {
+      ...FullType
+    }
+    directives {
+      name
+      description
+      locations
+      args {
+        ...InputValue
+      }
+    }
+  }
+}
+
+fragment FullType on __Type {
+  kind
+  name
+  description
+  fields(includeDeprecated: true) {
+    name
+    description
+    args {
+      ...InputValue
+    }
+    type {
+      ...TypeRef
+    }
+    isDeprecated
+    deprecationReason
+  }
+  inputFields {
+    ...InputValue
+  }
+  interfaces {
+    ...TypeRef
+  }
+  enumValues(includeDeprecated: true) {
+    name
+    description
+    isDeprecated
+    deprecationReason
+  }
+  possibleTypes {
+    ...TypeRef
+  }
+}
+
+fragment InputValue on __InputValue {
+  name
+  description
+  type { ...TypeRef }
+  defaultValue
+}
+
+fragment TypeRef on __Type {
+  kind
+  name
+  ofType {
+    kind
+    name
+    ofType {
+      kind
+      name
+      ofType {
+        kind
+        name
+        ofType {
+          kind
+          name
+          ofType {
+            kind
+            name
+            ofType {
+              kind
+              name
+              ofType {
+                kind
+                name
+              }
+            }
+          }
+        }
+      }
+    }
+  }
+}"#;
+
+/// Schema summaries from previous introspections, keyed by endpoint URL.
+static SCHEMA_CACHE: Lazy<Mutex<HashMap<String, SchemaSummary>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// A summary of a GraphQL schema: its named types and their fields.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SchemaSummary {
+    /// Name of the root query type, if the schema has one.
+    pub query_type: Option<String>,
+
+    /// Name of the root mutation type, if the schema has one.
+    pub mutation_type: Option<String>,
+
+    /// Types defined by the schema, excluding built-in `__` introspection types.
+    pub types: Vec<SchemaType>,
+}
+
+/// A named type in a GraphQL schema.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SchemaType {
+    /// Type name (e.g., "User")
+    pub name: String,
+
+    /// Type kind (e.g., "OBJECT", "ENUM", "INPUT_OBJECT", "SCALAR")
+    pub kind: String,
+
+    /// Fields of object and interface types, or input fields of input types
+    pub fields: Vec<SchemaField>,
+
+    /// Values of enum types
+    pub enum_values: Vec<String>,
+}
+
+/// A field of a GraphQL type.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SchemaField {
+    /// Field name (e.g., "email")
+    pub name: String,
+
+    /// Field type in GraphQL notation (e.g., "[User!]!")
+    pub type_name: String,
+}
+
+impl SchemaSummary {
+    /// Looks up a type by name.
+    pub fn get_type(&self, name: &str) -> Option<&SchemaType> {
+        self.types
+            .iter()
+            .find(|schema_type| schema_type.name == name)
+    }
+
+    /// Returns the top-level fields of a query that its root type lacks.
+    ///
+    /// Only the first operation is checked, and only queries and mutations
+    /// (the summary doesn't record a subscription root). `__` meta fields
+    /// such as `__typename` are always allowed.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The GraphQL document to check
+    ///
+    /// # Returns
+    ///
+    /// The unknown field names, in query order; empty if the query can't be
+    /// checked.
+    pub fn unknown_root_fields(&self, query: &str) -> Vec<String> {
+        let Some((operation, fields)) = root_fields(query) else {
+            return Vec::new();
+        };
+        let root = match operation.as_str() {
+            "query" => &self.query_type,
+            "mutation" => &self.mutation_type,
+            _ => return Vec::new(),
+        };
+        let Some(root_type) = root.as_deref().and_then(|name| self.get_type(name)) else {
+            return Vec::new();
+        };
+
+        fields
+            .into_iter()
+            .filter(|name| {
+                !name.starts_with("__") && !root_type.fields.iter().any(|f| f.name == *name)
+            })
+            .collect()
+    }
+
+    /// Formats the schema as a readable list of types and their fields.
+    ///
+    /// # Returns
+    ///
+    /// One block per type, headed by its kind and name, with a line for each
+    /// field (`name: Type`) or enum value.
+    pub fn to_display_string(&self) -> String {
+        let mut output = format!("GraphQL Schema ({} types)\n", self.types.len());
+
+        for (label, root) in [
+            ("Query", &self.query_type),
+            ("Mutation", &self.mutation_type),
+        ] {
+            if let Some(root) = root {
+                output.push_str(&format!("{} root: {}\n", label, root));
+            }
+        }
+
+        for schema_type in &self.types {
+            output.push_str(&format!("\n{} {}\n", schema_type.kind, schema_type.name));
+            for field in &schema_type.fields {
+                output.push_str(&format!("  {}: {}\n", field.name, field.type_name));
+            }
+            for value in &schema_type.enum_values {
+                output.push_str(&format!("  {}\n", value));
+            }
+        }
+
+        output
+    }
+}
+
+/// Builds a request that runs the introspection query against an endpoint.
+///
+/// # Arguments
+///
+/// * `endpoint` - The GraphQL endpoint URL
+///
+/// # Returns
+///
+/// A POST request with the query as a JSON `{"query": ...}` body.
+pub fn introspection_request(endpoint: &str) -> HttpRequest {
+    let body = GraphQLRequest::new(INTROSPECTION_QUERY.to_string())
+        .to_json()
+        .unwrap_or_default();
+
+    let mut request = HttpRequest::new(
+        "graphql-introspection".to_string(),
+        HttpMethod::POST,
+        endpoint.to_string(),
+    );
+    request.add_header("Content-Type".to_string(), "application/json".to_string());
+    request.body = Some(body);
+    request
+}
+
+/// Parses an introspection response body into a schema summary.
+///
+/// # Arguments
+///
+/// * `body` - The JSON response to the introspection query
+///
+/// # Returns
+///
+/// `Ok(SchemaSummary)` on success, or `Err(String)` if the body is not JSON,
+/// the server returned GraphQL errors, or the response has no `__schema`.
+pub fn parse_introspection_response(body: &str) -> Result<SchemaSummary, String> {
+    let response: GraphQLResponse = serde_json::from_str(body)
+        .map_err(|e| format!("Introspection response is not valid JSON: {}", e))?;
+
+    if let Some(errors) = format_introspection_errors(&response) {
+        return Err(errors);
+    }
+
+    let schema = response
+        .data
+        .as_ref()
+        .and_then(|data| data.get("__schema"))
+        .ok_or("Introspection response has no __schema data. Is introspection disabled?")?;
+
+    let root_name = |key: &str| {
+        schema
+            .get(key)
+            .and_then(|root| root.get("name"))
+            .and_then(Value::as_str)
+            .map(str::to_string)
+    };
+
+    let types = schema
+        .get("types")
+        .and_then(Value::as_array)
+        .map(|types| types.iter().filter_map(parse_type).collect())
+        .unwrap_or_default();
+
+    Ok(SchemaSummary {
+        query_type: root_name("queryType"),
+        mutation_type: root_name("mutationType"),
+        types,
+    })
+}
+
+/// Extracts the GraphQL errors from an introspection response body, if any.
+///
+/// Servers that disable introspection usually reply with an `errors` array,
+/// often alongside a non-2xx status. Checking for it before the status lets
+/// the caller show the server's own explanation instead of a bare status code.
+///
+/// # Arguments
+///
+/// * `body` - The raw response body
+///
+/// # Returns
+///
+/// `Some(String)` with the formatted errors, or `None` if the body is not a
+/// GraphQL response or has no errors.
+pub fn introspection_errors(body: &str) -> Option<String> {
+    let response: GraphQLResponse = serde_json::from_str(body).ok()?;
+    format_introspection_errors(&response)
+}
+
+/// Formats a response's errors with the GraphQL formatter, if it has any.
+fn format_introspection_errors(response: &GraphQLResponse) -> Option<String> {
+    if !response.has_errors() {
+        return None;
+    }
+
+    Some(format!(
+        "Introspection failed. The endpoint may have introspection disabled.\n\n{}",
+        response.format_errors().trim_end()
+    ))
+}
+
+/// Parses one entry of `__schema.types`, skipping built-in `__` types.
+fn parse_type(value: &Value) -> Option<SchemaType> {
+    let name = value.get("name")?.as_str()?;
+    if name.starts_with("__") {
+        return None;
+    }
+
+    let fields = ["fields", "inputFields"]
+        .iter()
+        .filter_map(|key| value.get(*key).and_then(Value::as_array))
+        .flatten()
+        .filter_map(|field| {
+            Some(SchemaField {
+                name: field.get("name")?.as_str()?.to_string(),
+                type_name: field.get("type").map(format_type_ref)?,
+            })
+        })
+        .collect();
+
+    let enum_values = value
+        .get("enumValues")
+        .and_then(Value::as_array)
+        .map(|values| {
+            values
+                .iter()
+                .filter_map(|value| value.get("name")?.as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Some(SchemaType {
+        name: name.to_string(),
+        kind: value.get("kind")?.as_str()?.to_string(),
+        fields,
+        enum_values,
+    })
+}
+
+/// Renders an introspection type reference in GraphQL notation, e.g. `[User!]!`.
+fn format_type_ref(type_ref: &Value) -> String {
+    let of_type = || {
+        type_ref
+            .get("ofType")
+            .map(format_type_ref)
+            .unwrap_or_default()
+    };
+
+    match type_ref.get("kind").and_then(Value::as_str) {
+        Some("NON_NULL") => format!("{}!", of_type()),
+        Some("LIST") => format!("[{}]", of_type()),
+        _ => type_ref
+            .get("name")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string(),
+    }
+}
+
+/// Returns the operation type and top-level field names of the first
+/// operation in a GraphQL document.
+///
+/// Aliases resolve to their field, and arguments, directives, and fragment
+/// spreads are skipped. Returns `None` for documents that start with a
+/// fragment or have no selection set.
+fn root_fields(query: &str) -> Option<(String, Vec<String>)> {
+    let chars: Vec<char> = query.chars().collect();
+    let mut operation = None;
+    let mut fields = Vec::new();
+    let mut depth = 0usize;
+    let mut parens = 0usize;
+    let mut started = false;
+    let mut skip_name = false;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            '#' => {
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+                continue;
+            }
+            '"' => {
+                i += 1;
+                while i < chars.len() && chars[i] != '"' {
+                    if chars[i] == '\\' {
+                        i += 1;
+                    }
+                    i += 1;
+                }
+            }
+            '(' => parens += 1,
+            ')' => parens = parens.saturating_sub(1),
+            '{' if parens == 0 => {
+                depth += 1;
+                started = true;
+            }
+            '}' if parens == 0 => {
+                depth = depth.saturating_sub(1);
+                if started && depth == 0 {
+                    break;
+                }
+            }
+            '.' | '@' => skip_name = true,
+            c if c.is_ascii_alphabetic() || c == '_' => {
+                let start = i;
+                while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '_') {
+                    i += 1;
+                }
+                let name: String = chars[start..i].iter().collect();
+
+                if !started && depth == 0 && parens == 0 && operation.is_none() {
+                    if name == "fragment" {
+                        return None;
+                    }
+                    operation = Some(name);
+                } else if depth == 1 && parens == 0 {
+                    if skip_name {
+                        // `... on Type` skips the type name as well
+                        skip_name = name == "on";
+                        continue;
+                    }
+                    let next = chars[i..].iter().find(|c| !c.is_whitespace());
+                    if next != Some(&':') {
+                        fields.push(name);
+                    }
+                }
+                continue;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+
+    started.then(|| (operation.unwrap_or_else(|| "query".to_string()), fields))
+}
+
+/// Stores a schema summary for an endpoint, replacing any previous one.
+pub fn cache_schema(endpoint: &str, schema: SchemaSummary) {
+    if let Ok(mut cache) = SCHEMA_CACHE.lock() {
+        cache.insert(endpoint.to_string(), schema);
+    }
+}
+
+/// Returns the cached schema summary for an endpoint, if it was introspected.
+pub fn cached_schema(endpoint: &str) -> Option<SchemaSummary> {
+    SCHEMA_CACHE
+        .lock()
+        .ok()
+        .and_then(|cache| cache.get(endpoint).cloned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graphql::parser::parse_graphql_request;
+
+    const RESPONSE: &str = r#"{
+      "data": {
+        "__schema": {
+          "queryType": { "name": "Query" },
+          "mutationType": null,
+          "types": [
+            {
+              "kind": "OBJECT",
+              "name": "Query",
+              "fields": [
+                {
+                  "name": "users",
+                  "type": {
+                    "kind": "NON_NULL", "name": null,
+                    "ofType": {
+                      "kind": "LIST", "name": null,
+                      "ofType": {
+                        "kind": "NON_NULL", "name": null,
+                        "ofType": { "kind": "OBJECT", "name": "User", "ofType": null }
+                      }
+                    }
+                  }
+                }
+              ],
+              "inputFields": null,
+              "enumValues": null
+            },
+            {
+              "kind": "ENUM",
+              "name": "Role",
+              "fields": null,
+              "enumValues": [{ "name": "ADMIN" }, { "name": "MEMBER" }]
+            },
+            { "kind": "OBJECT", "name": "__Schema", "fields": [] }
+          ]
+        }
+      }
+    }"#;
+
+    #[test]
+    fn test_introspection_query_is_valid_graphql() {
+        assert!(parse_graphql_request(INTROSPECTION_QUERY).is_ok());
+
+        let request = introspection_request("https://api.example.com/graphql");
+        assert_eq!(request.method, HttpMethod::POST);
+        let body: Value = serde_json::from_str(request.body.as_deref().unwrap()).unwrap();
+        assert_eq!(body["query"], INTROSPECTION_QUERY);
+    }
+
+    #[test]
+    fn test_parse_introspection_response() {
+        let schema = parse_introspection_response(RESPONSE).unwrap();
+
+        assert_eq!(schema.query_type.as_deref(), Some("Query"));
+        assert_eq!(schema.mutation_type, None);
+        assert_eq!(schema.types.len(), 2);
+        assert_eq!(
+            schema.get_type("Query").unwrap().fields,
+            vec![SchemaField {
+                name: "users".to_string(),
+                type_name: "[User!]!".to_string(),
+            }]
+        );
+        assert_eq!(
+            schema.get_type("Role").unwrap().enum_values,
+            vec!["ADMIN", "MEMBER"]
+        );
+
+        assert_eq!(
+            schema.to_display_string(),
+            "GraphQL Schema (2 types)\nQuery root: Query\n\nOBJECT Query\n  users: [User!]!\n\nENUM Role\n  ADMIN\n  MEMBER\n"
+        );
+    }
+
+    #[test]
+    fn test_parse_introspection_response_errors() {
+        let err = parse_introspection_response(
+            r#"{"errors": [{"message": "Introspection is disabled"}]}"#,
+        )
+        .unwrap_err();
+        assert!(err.contains("Introspection is disabled"), "{}", err);
+        assert!(err.contains("GraphQL Errors:"), "{}", err);
+        assert!(!err.contains("# GraphQL Errors"), "{}", err);
+
+        assert!(parse_introspection_response(r#"{"data": {}}"#).is_err());
+        assert!(parse_introspection_response("<html>").is_err());
+    }
+
+    #[test]
+    fn test_introspection_errors() {
+        let body = r#"{"errors": [{"message": "GraphQL introspection is not allowed"}]}"#;
+        let errors = introspection_errors(body).unwrap();
+        assert!(errors.starts_with("Introspection failed."), "{}", errors);
+        assert!(errors.contains("1. GraphQL introspection is not allowed"));
+
+        assert_eq!(introspection_errors(RESPONSE), None);
+        assert_eq!(introspection_errors("Bad Request"), None);
+    }
+
+    #[test]
+    fn test_introspection_request_body() {
+        let request = introspection_request("https://api.example.com/graphql");
+        let body: Value = serde_json::from_str(request.body.as_deref().unwrap()).unwrap();
+        assert_eq!(body["query"], INTROSPECTION_QUERY);
+    }
+
+    #[test]
+    fn test_root_fields() {
+        let query = r#"# Load the dashboard
+query Dashboard($id: ID!) {
+  me: user(id: $id, filter: { name: "a}b" }) { id }
+  ... on Query { hidden }
+  ...RootFields
+  posts @include(if: true) { title }
+  __typename
+}"#;
+        assert_eq!(
+            root_fields(query),
+            Some((
+                "query".to_string(),
+                vec![
+                    "user".to_string(),
+                    "posts".to_string(),
+                    "__typename".to_string()
+                ]
+            ))
+        );
+        assert_eq!(
+            root_fields("mutation { login { token } }"),
+            Some(("mutation".to_string(), vec!["login".to_string()]))
+        );
+        assert_eq!(
+            root_fields("{ users { id } }"),
+            Some(("query".to_string(), vec!["users".to_string()]))
+        );
+        assert_eq!(root_fields("fragment F on User { id }"), None);
+    }
+
+    #[test]
+    fn test_unknown_root_fields() {
+        let schema = parse_introspection_response(RESPONSE).unwrap();
+
+        assert!(schema
+            .unknown_root_fields("{ users { id } __typename }")
+            .is_empty());
+        assert_eq!(
+            schema.unknown_root_fields("query { users { id } posts { id } }"),
+            vec!["posts"]
+        );
+        // No mutation root is known, so mutations aren't checked
+        assert!(schema.unknown_root_fields("mutation { login }").is_empty());
+    }
+
+    #[test]
+    fn test_schema_cache() {
+        let endpoint = "https://cache-test.example.com/graphql";
+        assert_eq!(cached_schema(endpoint), None);
+
+        let schema = parse_introspection_response(RESPONSE).unwrap();
+        cache_schema(endpoint, schema.clone());
+
+        assert_eq!(cached_schema(endpoint), Some(schema));
+    }
+}
@@ -39,6 +39,7 @@
 //! }
 //! ```
 
+pub mod introspection;
 pub mod parser;
 
 use serde::{Deserialize, Serialize};
@@ -172,6 +173,14 @@ pub enum ParseError {
 
     /// The query contains an unknown GraphQL keyword or construct.
     UnknownConstruct(String),
+
+    /// A required variable declared by the operation has no value.
+    MissingVariable {
+        /// The variable name, without the `$` prefix
+        name: String,
+        /// The declared type, such as `ID!`
+        type_name: String,
+    },
 }
 
 impl fmt::Display for ParseError {
@@ -202,6 +211,13 @@ impl fmt::Display for ParseError {
             ParseError::UnknownConstruct(construct) => {
                 write!(f, "Unknown GraphQL construct: {}", construct)
             }
+            ParseError::MissingVariable { name, type_name } => {
+                write!(
+                    f,
+                    "Missing required GraphQL variable '${}' of type {}",
+                    name, type_name
+                )
+            }
         }
     }
 }
@@ -109,6 +109,233 @@ pub fn parse_graphql_request(body: &str) -> Result<GraphQLRequest, ParseError> {
     Ok(request)
 }
 
+/// A variable declared in an operation's signature, e.g. `$id: ID!`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VariableDefinition {
+    /// The variable name, without the `$` prefix
+    pub name: String,
+
+    /// The declared type, such as `ID!` or `[String!]`
+    pub type_name: String,
+
+    /// Whether the declaration has a default value (`$limit: Int = 10`)
+    pub has_default: bool,
+}
+
+impl VariableDefinition {
+    /// Returns `true` if the variable must be provided.
+    ///
+    /// A variable is required when its type is non-null and it has no default.
+    pub fn is_required(&self) -> bool {
+        self.type_name.ends_with('!') && !self.has_default
+    }
+}
+
+/// Result of checking a GraphQL request's variables against its operation.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GraphQLValidation {
+    /// Problems that will make the request fail, including parse errors and
+    /// missing required variables
+    pub errors: Vec<ParseError>,
+
+    /// Provided variables the operation doesn't declare. The server ignores or
+    /// rejects these, so they are reported as warnings.
+    pub undeclared_variables: Vec<String>,
+}
+
+impl GraphQLValidation {
+    /// Returns `true` if there are no errors or warnings.
+    pub fn is_clean(&self) -> bool {
+        self.errors.is_empty() && self.undeclared_variables.is_empty()
+    }
+}
+
+/// Validates a GraphQL request body, including its variables.
+///
+/// The body is parsed with [`parse_graphql_request`], then the variables
+/// object is compared against the variables declared by the first operation.
+/// Required variables (non-null type, no default) that are missing or `null`
+/// are errors; provided variables that aren't declared are warnings.
+///
+/// # Arguments
+///
+/// * `body` - The request body containing the GraphQL query and optional variables
+///
+/// # Returns
+///
+/// A [`GraphQLValidation`] with the errors and undeclared variables found.
+///
+/// # Examples
+///
+/// ```
+/// use rest_client::graphql::parser::validate_graphql_request;
+///
+/// let body = "query GetUser($id: ID!) {\n  user(id: $id) { name }\n}\n\n{\"name\": \"x\"}";
+///
+/// let validation = validate_graphql_request(body);
+/// assert_eq!(validation.errors.len(), 1);
+/// assert_eq!(validation.undeclared_variables, vec!["name"]);
+/// ```
+pub fn validate_graphql_request(body: &str) -> GraphQLValidation {
+    let mut validation = GraphQLValidation::default();
+
+    let request = match parse_graphql_request(body) {
+        Ok(request) => request,
+        Err(error) => {
+            validation.errors.push(error);
+            return validation;
+        }
+    };
+
+    let definitions = extract_variable_definitions(&request.query);
+    let empty = serde_json::Map::new();
+    let provided = request
+        .variables
+        .as_ref()
+        .and_then(Value::as_object)
+        .unwrap_or(&empty);
+
+    for definition in &definitions {
+        let missing = provided.get(&definition.name).is_none_or(Value::is_null);
+        if definition.is_required() && missing {
+            validation.errors.push(ParseError::MissingVariable {
+                name: definition.name.clone(),
+                type_name: definition.type_name.clone(),
+            });
+        }
+    }
+
+    validation.undeclared_variables = provided
+        .keys()
+        .filter(|key| !definitions.iter().any(|d| &d.name == *key))
+        .cloned()
+        .collect();
+
+    validation
+}
+
+/// Extracts the variables declared by the first operation in a query.
+///
+/// Only the signature of the first `query`, `mutation`, or `subscription` is
+/// read, since that's the operation a single-operation request runs.
+/// Shorthand queries (`{ ... }`) declare no variables.
+///
+/// # Arguments
+///
+/// * `query` - The GraphQL document
+///
+/// # Returns
+///
+/// The declared variables in signature order.
+///
+/// # Examples
+///
+/// ```
+/// use rest_client::graphql::parser::extract_variable_definitions;
+///
+/// let definitions = extract_variable_definitions("query Q($id: ID!, $limit: Int = 10) { a }");
+/// assert_eq!(definitions.len(), 2);
+/// assert_eq!(definitions[0].name, "id");
+/// assert!(definitions[0].is_required());
+/// assert!(!definitions[1].is_required());
+/// ```
+pub fn extract_variable_definitions(query: &str) -> Vec<VariableDefinition> {
+    let Some(signature) = operation_signature(query) else {
+        return Vec::new();
+    };
+
+    let mut definitions: Vec<VariableDefinition> = Vec::new();
+    let mut chars = signature.chars().peekable();
+    let mut in_string = false;
+
+    while let Some(ch) = chars.next() {
+        // Skip string literals in default values
+        if ch == '"' {
+            in_string = !in_string;
+            continue;
+        }
+        if in_string || ch != '$' {
+            continue;
+        }
+
+        let mut name = String::new();
+        while let Some(&c) = chars.peek() {
+            if c.is_alphanumeric() || c == '_' {
+                name.push(c);
+                chars.next();
+            } else {
+                break;
+            }
+        }
+
+        // Skip to the type after the colon
+        while chars.peek().is_some_and(|c| c.is_whitespace() || *c == ':') {
+            chars.next();
+        }
+
+        let mut type_name = String::new();
+        let mut has_default = false;
+        while let Some(&c) = chars.peek() {
+            if c == '=' {
+                has_default = true;
+                break;
+            }
+            if c == ',' || c == '$' || c == '@' {
+                break;
+            }
+            if !c.is_whitespace() {
+                type_name.push(c);
+            }
+            chars.next();
+        }
+
+        if !name.is_empty() && !type_name.is_empty() {
+            definitions.push(VariableDefinition {
+                name,
+                type_name,
+                has_default,
+            });
+        }
+    }
+
+    definitions
+}
+
+/// Returns the text between the parentheses of the first operation's
+/// signature, or `None` if the operation declares no variables.
+fn operation_signature(query: &str) -> Option<&str> {
+    let keywords = ["query", "mutation", "subscription"];
+
+    let mut depth = 0usize;
+    let mut word_start = None;
+    for (i, ch) in query.char_indices() {
+        if ch.is_alphanumeric() || ch == '_' {
+            word_start.get_or_insert(i);
+            continue;
+        }
+
+        if let Some(start) = word_start.take() {
+            if depth == 0 && keywords.contains(&&query[start..i]) {
+                let rest = &query[start..];
+                let open = rest.find(['(', '{'])?;
+                if !rest[open..].starts_with('(') {
+                    return None;
+                }
+                let close = rest[open..].find(')')?;
+                return Some(&rest[open + 1..open + close]);
+            }
+        }
+
+        match ch {
+            '{' => depth += 1,
+            '}' => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+    }
+
+    None
+}
+
 /// Splits the body into query and variables sections.
 ///
 /// The query section ends when we encounter a line that starts with `{` or `[`
@@ -598,4 +825,90 @@ query GetUserPosts($userId: ID!, $limit: Int) {
         assert_eq!(vars["userId"], "user-123");
         assert_eq!(vars["limit"], 10);
     }
+
+    #[test]
+    fn test_extract_variable_definitions() {
+        let query = r#"query Search($term: String!, $tags: [String!]! = ["a,$b"], $first: Int = 10, $after: ID) {
+  search(term: $term) { id }
+}"#;
+
+        let definitions = extract_variable_definitions(query);
+        let summary: Vec<(&str, &str, bool)> = definitions
+            .iter()
+            .map(|d| (d.name.as_str(), d.type_name.as_str(), d.is_required()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("term", "String!", true),
+                ("tags", "[String!]!", false),
+                ("first", "Int", false),
+                ("after", "ID", false),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_extract_variable_definitions_without_signature() {
+        assert!(extract_variable_definitions("{ users { id } }").is_empty());
+        assert!(extract_variable_definitions("query { user(id: $id) { id } }").is_empty());
+        assert!(extract_variable_definitions("query Named { users { id } }").is_empty());
+
+        // Fragments before the operation are skipped
+        let query = "fragment F on User { id }\nquery Q($id: ID!) { user(id: $id) { ...F } }";
+        assert_eq!(extract_variable_definitions(query)[0].name, "id");
+    }
+
+    #[test]
+    fn test_validate_graphql_request_valid() {
+        let body = r#"
+query GetUser($id: ID!, $limit: Int) {
+  user(id: $id) { id }
+}
+
+{
+  "id": "123"
+}
+        "#;
+
+        assert!(validate_graphql_request(body).is_clean());
+    }
+
+    #[test]
+    fn test_validate_graphql_request_missing_variable() {
+        let body = "query GetUser($id: ID!) {\n  user(id: $id) { id }\n}";
+        let validation = validate_graphql_request(body);
+        assert_eq!(
+            validation.errors,
+            vec![ParseError::MissingVariable {
+                name: "id".to_string(),
+                type_name: "ID!".to_string(),
+            }]
+        );
+        assert_eq!(
+            validation.errors[0].to_string(),
+            "Missing required GraphQL variable '$id' of type ID!"
+        );
+
+        // null doesn't satisfy a non-null type
+        let body = "query GetUser($id: ID!) {\n  user(id: $id) { id }\n}\n\n{\"id\": null}";
+        assert_eq!(validate_graphql_request(body).errors.len(), 1);
+    }
+
+    #[test]
+    fn test_validate_graphql_request_undeclared_variable() {
+        let body = "query GetUser($id: ID!) {\n  user(id: $id) { id }\n}\n\n{\"id\": \"1\", \"extra\": true}";
+        let validation = validate_graphql_request(body);
+        assert!(validation.errors.is_empty());
+        assert_eq!(validation.undeclared_variables, vec!["extra"]);
+    }
+
+    #[test]
+    fn test_validate_graphql_request_parse_error() {
+        let validation = validate_graphql_request("query { users { id }");
+        assert!(matches!(
+            validation.errors.as_slice(),
+            [ParseError::UnmatchedDelimiter(_)]
+        ));
+    }
 }
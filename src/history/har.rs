@@ -0,0 +1,423 @@
+//! HAR (HTTP Archive) import.
+//!
+//! Browser developer tools can export captured traffic as a `.har` file. This
+//! module turns each captured entry into a [`HistoryEntry`] so the requests
+//! can be viewed and re-run like ones sent from the editor.
+//!
+//! Only `http` and `https` entries are imported; `data:`, `blob:`,
+//! `chrome-extension:` and similar URLs are skipped and counted in
+//! [`HarImport::skipped`].
+
+use super::models::{HistoryEntry, HistoryError};
+use super::storage::{save_entry_internal, HistoryConfig};
+use crate::models::{HttpMethod, HttpRequest, HttpResponse, RequestTiming};
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Tag added to every imported entry so they can be filtered in history.
+pub const HAR_IMPORT_TAG: &str = "har-import";
+
+/// Result of converting a HAR document.
+#[derive(Debug, Clone)]
+pub struct HarImport {
+    /// Importable entries in capture order
+    pub entries: Vec<HistoryEntry>,
+    /// Number of entries skipped for a non-HTTP URL or unsupported method
+    pub skipped: usize,
+}
+
+/// Top level of a HAR document.
+#[derive(Debug, Deserialize)]
+struct Har {
+    log: HarLog,
+}
+
+#[derive(Debug, Deserialize)]
+struct HarLog {
+    #[serde(default)]
+    entries: Vec<HarEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct HarEntry {
+    #[serde(default)]
+    started_date_time: Option<String>,
+    /// Total time of the request in milliseconds
+    #[serde(default)]
+    time: f64,
+    request: HarRequest,
+    response: HarResponse,
+    #[serde(default)]
+    timings: Option<HarTimings>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct HarRequest {
+    method: String,
+    url: String,
+    #[serde(default)]
+    headers: Vec<HarHeader>,
+    #[serde(default)]
+    post_data: Option<HarPostData>,
+}
+
+#[derive(Debug, Deserialize)]
+struct HarHeader {
+    name: String,
+    value: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct HarPostData {
+    #[serde(default)]
+    text: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct HarResponse {
+    status: u16,
+    #[serde(default)]
+    status_text: String,
+    #[serde(default)]
+    headers: Vec<HarHeader>,
+    #[serde(default)]
+    content: Option<HarContent>,
+}
+
+#[derive(Debug, Deserialize)]
+struct HarContent {
+    #[serde(default)]
+    text: Option<String>,
+    #[serde(default)]
+    encoding: Option<String>,
+}
+
+/// Phase timings in milliseconds; `-1` means the phase doesn't apply.
+#[derive(Debug, Deserialize)]
+struct HarTimings {
+    #[serde(default)]
+    dns: f64,
+    #[serde(default)]
+    connect: f64,
+    #[serde(default)]
+    ssl: f64,
+    #[serde(default)]
+    send: f64,
+    #[serde(default)]
+    wait: f64,
+    #[serde(default)]
+    receive: f64,
+}
+
+/// Parses a HAR document and appends its entries to the history store.
+///
+/// Each entry is saved with [`HistoryConfig::default`] settings, except that
+/// failed (4xx/5xx) responses are kept too, since they were part of the
+/// captured traffic. Sensitive headers are sanitized as for any other entry.
+///
+/// # Arguments
+///
+/// * `json` - The contents of a `.har` file
+///
+/// # Returns
+///
+/// `Ok(HarImport)` with the imported entries in capture order and the number
+/// of entries that were skipped.
+///
+/// # Errors
+///
+/// Returns `HistoryError::SerializationError` if the document isn't a valid
+/// HAR file, or `HistoryError::StorageError` if an entry can't be written.
+pub fn import_har(json: &str) -> Result<HarImport, HistoryError> {
+    import_har_internal(json, None)
+}
+
+fn import_har_internal(
+    json: &str,
+    history_path: Option<PathBuf>,
+) -> Result<HarImport, HistoryError> {
+    let import = parse_har(json)?;
+
+    let config = HistoryConfig {
+        save_failed_requests: true,
+        ..HistoryConfig::default()
+    };
+    for entry in &import.entries {
+        save_entry_internal(entry, &config, history_path.clone())?;
+    }
+
+    Ok(import)
+}
+
+/// Converts a HAR document into history entries without saving them.
+///
+/// Entries keep their original `startedDateTime` as the timestamp and are
+/// tagged with [`HAR_IMPORT_TAG`]. Entries with a non-HTTP URL or an
+/// unsupported method are skipped and counted.
+///
+/// # Arguments
+///
+/// * `json` - The contents of a `.har` file
+///
+/// # Returns
+///
+/// `Ok(HarImport)` with one entry per importable HAR entry, or
+/// `Err(HistoryError::SerializationError)` if the document isn't valid HAR.
+pub fn parse_har(json: &str) -> Result<HarImport, HistoryError> {
+    let har: Har = serde_json::from_str(json)?;
+
+    let total = har.log.entries.len();
+    let entries: Vec<HistoryEntry> = har.log.entries.into_iter().filter_map(to_entry).collect();
+    Ok(HarImport {
+        skipped: total - entries.len(),
+        entries,
+    })
+}
+
+/// Converts one HAR entry, or returns `None` if it can't be replayed.
+fn to_entry(entry: HarEntry) -> Option<HistoryEntry> {
+    if !is_http_url(&entry.request.url) {
+        return None;
+    }
+    let method = HttpMethod::from_str(&entry.request.method)?;
+
+    let mut request = HttpRequest::new(uuid::Uuid::new_v4().to_string(), method, entry.request.url);
+    for header in entry.request.headers {
+        // HTTP/2 pseudo-headers such as `:authority` aren't real headers
+        if !header.name.starts_with(':') {
+            request.headers.append(header.name, header.value);
+        }
+    }
+    request.body = entry
+        .request
+        .post_data
+        .and_then(|post_data| post_data.text)
+        .filter(|text| !text.is_empty());
+
+    let mut response = HttpResponse::new(entry.response.status, entry.response.status_text);
+    for header in entry.response.headers {
+        if !header.name.starts_with(':') {
+            response.add_header(header.name, header.value);
+        }
+    }
+    if let Some(content) = entry.response.content {
+        response.set_body(decode_content(content));
+    }
+    response.duration = millis(entry.time);
+    if let Some(timings) = entry.timings {
+        response.timing = to_timing(&timings);
+    }
+
+    let mut history_entry =
+        HistoryEntry::with_tags(request, response, vec![HAR_IMPORT_TAG.to_string()]);
+    if let Some(timestamp) = entry
+        .started_date_time
+        .and_then(|started| DateTime::parse_from_rfc3339(&started).ok())
+    {
+        history_entry.timestamp = timestamp.with_timezone(&Utc);
+    }
+
+    Some(history_entry)
+}
+
+/// Returns `true` if the URL uses the `http` or `https` scheme.
+fn is_http_url(url: &str) -> bool {
+    url.split_once("://").is_some_and(|(scheme, _)| {
+        scheme.eq_ignore_ascii_case("http") || scheme.eq_ignore_ascii_case("https")
+    })
+}
+
+/// Returns the response body bytes, decoding base64 content.
+fn decode_content(content: HarContent) -> Vec<u8> {
+    let text = content.text.unwrap_or_default();
+    if content.encoding.as_deref() == Some("base64") {
+        if let Ok(bytes) = STANDARD.decode(text.trim()) {
+            return bytes;
+        }
+    }
+    text.into_bytes()
+}
+
+/// Maps HAR phase timings onto a `RequestTiming`.
+///
+/// HAR includes the TLS handshake in `connect`, so it's subtracted out.
+fn to_timing(timings: &HarTimings) -> RequestTiming {
+    let tls = (timings.ssl > 0.0).then(|| millis(timings.ssl));
+    let connect = if timings.ssl > 0.0 {
+        timings.connect - timings.ssl
+    } else {
+        timings.connect
+    };
+
+    RequestTiming {
+        dns_lookup: millis(timings.dns),
+        tcp_connection: millis(connect),
+        tls_handshake: tls,
+        first_byte: millis(timings.send) + millis(timings.wait),
+        download: millis(timings.receive),
+    }
+}
+
+/// Converts HAR milliseconds to a duration, treating `-1` as zero.
+fn millis(ms: f64) -> Duration {
+    Duration::from_secs_f64(ms.max(0.0) / 1000.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::history::storage::load_history_internal;
+
+    const FIXTURE: &str = r#"{
+      "log": {
+        "version": "1.2",
+        "creator": { "name": "WebInspector", "version": "537.36" },
+        "entries": [
+          {
+            "startedDateTime": "2024-03-01T10:15:30.123Z",
+            "time": 152.5,
+            "request": {
+              "method": "POST",
+              "url": "https://api.example.com/users?page=1",
+              "httpVersion": "http/2.0",
+              "headers": [
+                { "name": ":authority", "value": "api.example.com" },
+                { "name": "content-type", "value": "application/json" },
+                { "name": "authorization", "value": "Bearer secret" }
+              ],
+              "queryString": [{ "name": "page", "value": "1" }],
+              "postData": { "mimeType": "application/json", "text": "{\"name\":\"Ada\"}" }
+            },
+            "response": {
+              "status": 201,
+              "statusText": "Created",
+              "headers": [
+                { "name": "content-type", "value": "application/json" },
+                { "name": "set-cookie", "value": "a=1" },
+                { "name": "x-trace", "value": "1" },
+                { "name": "x-trace", "value": "2" }
+              ],
+              "content": { "size": 11, "mimeType": "application/json", "text": "{\"id\":42}" }
+            },
+            "timings": { "blocked": 1, "dns": 10, "connect": 40, "ssl": 25, "send": 2, "wait": 80, "receive": 5 }
+          },
+          {
+            "startedDateTime": "2024-03-01T10:15:31.000+02:00",
+            "time": 20,
+            "request": { "method": "GET", "url": "data:image/png;base64,AAAA", "headers": [] },
+            "response": { "status": 200, "statusText": "OK", "headers": [], "content": {} }
+          },
+          {
+            "startedDateTime": "2024-03-01T10:15:32.000Z",
+            "time": 30,
+            "request": { "method": "GET", "url": "http://localhost:8080/logo.png", "headers": [] },
+            "response": {
+              "status": 404,
+              "statusText": "Not Found",
+              "headers": [],
+              "content": { "text": "aGVsbG8=", "encoding": "base64" }
+            },
+            "timings": { "dns": -1, "connect": -1, "ssl": -1, "send": 1, "wait": 20, "receive": 9 }
+          }
+        ]
+      }
+    }"#;
+
+    #[test]
+    fn test_parse_har() {
+        let import = parse_har(FIXTURE).unwrap();
+        let entries = import.entries;
+
+        // The data: URL is skipped
+        assert_eq!(entries.len(), 2);
+        assert_eq!(import.skipped, 1);
+
+        let first = &entries[0];
+        assert_eq!(first.request.method, HttpMethod::POST);
+        assert_eq!(first.request.url, "https://api.example.com/users?page=1");
+        assert_eq!(
+            first.request.headers.get("Content-Type").unwrap(),
+            "application/json"
+        );
+        assert!(first.request.headers.get(":authority").is_none());
+        assert_eq!(first.request.body.as_deref(), Some(r#"{"name":"Ada"}"#));
+        assert_eq!(
+            first.timestamp,
+            DateTime::parse_from_rfc3339("2024-03-01T10:15:30.123Z").unwrap()
+        );
+        assert!(first.has_tag(HAR_IMPORT_TAG));
+
+        assert_eq!(first.response.status_code, 201);
+        assert_eq!(first.response.status_text, "Created");
+        assert_eq!(first.response.body, br#"{"id":42}"#);
+        let traces: Vec<&String> = first.response.headers.get_all("x-trace").collect();
+        assert_eq!(traces, vec!["1", "2"]);
+        assert_eq!(first.response.duration, Duration::from_micros(152_500));
+        assert_eq!(first.response.timing.dns_lookup, Duration::from_millis(10));
+        assert_eq!(
+            first.response.timing.tcp_connection,
+            Duration::from_millis(15)
+        );
+        assert_eq!(
+            first.response.timing.tls_handshake,
+            Some(Duration::from_millis(25))
+        );
+        assert_eq!(first.response.timing.first_byte, Duration::from_millis(82));
+    }
+
+    #[test]
+    fn test_parse_har_base64_body_and_missing_phases() {
+        let entries = parse_har(FIXTURE).unwrap().entries;
+        let second = &entries[1];
+
+        assert_eq!(second.response.status_code, 404);
+        assert_eq!(second.response.body, b"hello");
+        assert_eq!(second.response.timing.dns_lookup, Duration::ZERO);
+        assert_eq!(second.response.timing.tls_handshake, None);
+        assert!(second.request.body.is_none());
+    }
+
+    #[test]
+    fn test_parse_har_invalid() {
+        assert!(matches!(
+            parse_har("not json"),
+            Err(HistoryError::SerializationError(_))
+        ));
+        assert!(matches!(
+            parse_har(r#"{"entries": []}"#),
+            Err(HistoryError::SerializationError(_))
+        ));
+        assert!(parse_har(r#"{"log": {"entries": []}}"#)
+            .unwrap()
+            .entries
+            .is_empty());
+    }
+
+    #[test]
+    fn test_import_har_saves_entries() {
+        let history_path =
+            std::env::temp_dir().join(format!("test_har_import_{}.json", uuid::Uuid::new_v4()));
+
+        let imported = import_har_internal(FIXTURE, Some(history_path.clone()))
+            .unwrap()
+            .entries;
+        let loaded = load_history_internal(Some(history_path.clone())).unwrap();
+
+        // The 404 is kept since it was part of the capture
+        assert_eq!(loaded.len(), 2);
+        assert_eq!(loaded[0].id, imported[0].id);
+        assert_eq!(loaded[0].timestamp, imported[0].timestamp);
+
+        // Sensitive headers are sanitized on save
+        assert!(loaded[0].request.headers.get("Authorization").is_none());
+        assert!(loaded[0].response.headers.get("Set-Cookie").is_none());
+
+        let _ = std::fs::remove_file(history_path);
+    }
+}
@@ -10,6 +10,7 @@
 //! - Automatic history limit enforcement
 //! - Sensitive data sanitization
 //! - JSONL format for efficient append operations
+//! - Import of browser-captured `.har` files
 //!
 //! # Example
 //!
@@ -24,12 +25,14 @@
 //! let entries = load_history()?;
 //! ```
 
+pub mod har;
 pub mod models;
 pub mod search;
 pub mod storage;
 pub mod ui;
 
 // Re-export commonly used types
+pub use har::{import_har, HarImport};
 pub use models::{HistoryEntry, HistoryError};
 pub use search::{
     filter_by_method, filter_by_status, filter_by_tag, filter_errors, filter_successful,
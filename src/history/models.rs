@@ -65,6 +65,13 @@ pub struct HistoryEntry {
     /// Tags can be used to categorize requests by project, environment,
     /// API endpoint type, etc.
     pub tags: Vec<String>,
+
+    /// Names of request headers removed by sanitization before storage.
+    ///
+    /// Lets a re-run warn that credentials such as `Authorization` need to
+    /// be supplied again.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sanitized_headers: Vec<String>,
 }
 
 impl HistoryEntry {
@@ -85,6 +92,7 @@ impl HistoryEntry {
             request,
             response,
             tags: Vec::new(),
+            sanitized_headers: Vec::new(),
         }
     }
 
@@ -106,6 +114,7 @@ impl HistoryEntry {
             request,
             response,
             tags,
+            sanitized_headers: Vec::new(),
         }
     }
 
@@ -148,12 +157,21 @@ impl HistoryEntry {
         }
 
         let mut sanitized_request = self.request.clone();
+        let mut sanitized_headers = self.sanitized_headers.clone();
 
-        // Remove sensitive headers from request
+        // Remove sensitive headers from request, remembering which ones
         sanitized_request.headers.retain(|key, _| {
-            !SENSITIVE_HEADERS
+            let sensitive = SENSITIVE_HEADERS
                 .iter()
-                .any(|sensitive| key.eq_ignore_ascii_case(sensitive))
+                .any(|sensitive| key.eq_ignore_ascii_case(sensitive));
+            if sensitive
+                && !sanitized_headers
+                    .iter()
+                    .any(|name| name.eq_ignore_ascii_case(key))
+            {
+                sanitized_headers.push(key.to_string());
+            }
+            !sensitive
         });
 
         let mut sanitized_response = self.response.clone();
@@ -171,6 +189,7 @@ impl HistoryEntry {
             request: sanitized_request,
             response: sanitized_response,
             tags: self.tags.clone(),
+            sanitized_headers,
         }
     }
 
@@ -193,6 +212,7 @@ impl HistoryEntry {
             request: self.request.clone(),
             response: truncated_response,
             tags: self.tags.clone(),
+            sanitized_headers: self.sanitized_headers.clone(),
         }
     }
 
@@ -210,6 +230,21 @@ impl HistoryEntry {
             .truncate_large_response()
     }
 
+    /// Rebuilds the request so it can be sent again.
+    ///
+    /// The request gets a fresh ID so it's tracked separately from the
+    /// original. Headers listed in `sanitized_headers` are not restored, since
+    /// their values were never stored.
+    ///
+    /// # Returns
+    ///
+    /// A copy of the stored request with a new ID.
+    pub fn to_request(&self) -> HttpRequest {
+        let mut request = self.request.clone();
+        request.id = uuid::Uuid::new_v4().to_string();
+        request
+    }
+
     /// Adds a tag to this history entry.
     ///
     /// # Arguments
@@ -410,6 +445,8 @@ mod tests {
         assert!(!sanitized.response.headers.contains_key("Set-Cookie"));
         assert!(sanitized.request.headers.contains_key("Content-Type"));
         assert!(sanitized.response.headers.contains_key("Content-Type"));
+        assert_eq!(sanitized.sanitized_headers, vec!["Authorization"]);
+        assert!(unsanitized.sanitized_headers.is_empty());
     }
 
     #[test]
@@ -504,4 +541,41 @@ mod tests {
             entry.response.status_code
         );
     }
+
+    #[test]
+    fn test_to_request_round_trip() {
+        let mut request = create_test_request();
+        request.method = HttpMethod::POST;
+        request.set_body(r#"{"name": "Ada"}"#.to_string());
+        let entry =
+            HistoryEntry::new(request.clone(), create_test_response(201)).prepare_for_storage(true);
+
+        // Stored as JSON and read back, as history does
+        let json = serde_json::to_string(&entry).unwrap();
+        let loaded: HistoryEntry = serde_json::from_str(&json).unwrap();
+        let rebuilt = loaded.to_request();
+
+        assert_ne!(rebuilt.id, request.id);
+        assert_eq!(rebuilt.method, HttpMethod::POST);
+        assert_eq!(rebuilt.url, request.url);
+        assert_eq!(rebuilt.body, request.body);
+        assert_eq!(
+            rebuilt.headers.get("Content-Type").unwrap(),
+            "application/json"
+        );
+
+        // The token wasn't stored, and the entry says so
+        assert!(!rebuilt.headers.contains_key("Authorization"));
+        assert_eq!(loaded.sanitized_headers, vec!["Authorization"]);
+    }
+
+    #[test]
+    fn test_sanitized_headers_default_for_old_entries() {
+        let entry = HistoryEntry::new(create_test_request(), create_test_response(200));
+        let json = serde_json::to_string(&entry).unwrap();
+        assert!(!json.contains("sanitized_headers"));
+
+        let loaded: HistoryEntry = serde_json::from_str(&json).unwrap();
+        assert!(loaded.sanitized_headers.is_empty());
+    }
 }
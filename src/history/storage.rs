@@ -10,7 +10,7 @@
 //! - Efficient parsing: Pre-allocate vectors based on estimated entry count
 
 use super::models::{HistoryEntry, HistoryError};
-use crate::config::get_config;
+use crate::config::{get_config, user_config_dir};
 use std::fs::{self, File, OpenOptions};
 use std::io::{BufRead, BufReader, Write};
 use std::path::PathBuf;
@@ -86,21 +86,12 @@ fn get_history_file_path_internal(override_path: Option<PathBuf>) -> Result<Path
         return Ok(path);
     }
     // Try to get the config directory
-    let config_dir = if let Some(home) = std::env::var_os("HOME") {
-        PathBuf::from(home).join(".config")
-    } else if let Some(user_profile) = std::env::var_os("USERPROFILE") {
-        PathBuf::from(user_profile).join("AppData").join("Roaming")
-    } else {
-        return Err(HistoryError::StorageError(std::io::Error::new(
+    let history_dir = user_config_dir().ok_or_else(|| {
+        HistoryError::StorageError(std::io::Error::new(
             std::io::ErrorKind::NotFound,
             "Could not determine home directory",
-        )));
-    };
-
-    let history_dir = config_dir
-        .join("zed")
-        .join("extensions")
-        .join("rest-client");
+        ))
+    })?;
 
     // Create directory if it doesn't exist
     if !history_dir.exists() {
@@ -142,7 +133,7 @@ pub fn save_entry(entry: &HistoryEntry) -> Result<(), HistoryError> {
     save_entry_with_config(entry, &HistoryConfig::default())
 }
 
-fn save_entry_internal(
+pub(super) fn save_entry_internal(
     entry: &HistoryEntry,
     config: &HistoryConfig,
     history_path: Option<PathBuf>,
@@ -223,7 +214,9 @@ pub fn load_history() -> Result<Vec<HistoryEntry>, HistoryError> {
     load_history_internal(None)
 }
 
-fn load_history_internal(history_path: Option<PathBuf>) -> Result<Vec<HistoryEntry>, HistoryError> {
+pub(super) fn load_history_internal(
+    history_path: Option<PathBuf>,
+) -> Result<Vec<HistoryEntry>, HistoryError> {
     let history_path = get_history_file_path_internal(history_path)?;
 
     // If file doesn't exist, return empty vector
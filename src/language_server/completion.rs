@@ -6,8 +6,12 @@
 //! - Environment variables from the active environment
 //! - Shared variables
 //! - File-level variables
+//!
+//! Typing the first letters of a request line (e.g., `P`) completes the HTTP
+//! method instead.
 
 use crate::environment::Environments;
+use crate::models::request::HttpMethod;
 use std::collections::HashMap;
 
 /// Represents a completion item to be shown to the user
@@ -37,6 +41,8 @@ pub enum CompletionKind {
     SharedVariable,
     /// File-level custom variable
     FileVariable,
+    /// HTTP method at the start of a request line
+    HttpMethod,
 }
 
 impl CompletionItem {
@@ -94,6 +100,16 @@ impl CompletionItem {
             insert_text: format!("{}}}}}", name),
         }
     }
+
+    /// Creates an HTTP method completion
+    pub fn http_method(method: HttpMethod) -> Self {
+        Self {
+            label: method.as_str().to_string(),
+            kind: CompletionKind::HttpMethod,
+            detail: Some(method_description(method).to_string()),
+            insert_text: format!("{} ", method.as_str()),
+        }
+    }
 }
 
 /// Position in a text document (line and column)
@@ -114,6 +130,9 @@ impl Position {
 
 /// Provides completion suggestions for variables at the given position
 ///
+/// At the method position of a request line, the HTTP methods starting
+/// with the typed letters are suggested instead.
+///
 /// # Arguments
 /// * `position` - The cursor position in the document
 /// * `document` - The full text of the document
@@ -141,6 +160,14 @@ pub fn provide_completions(
     environments: &Environments,
     file_variables: &HashMap<String, String>,
 ) -> Vec<CompletionItem> {
+    if let Some(prefix) = method_prefix(position, document) {
+        return HttpMethod::ALL
+            .into_iter()
+            .filter(|method| method.as_str().starts_with(&prefix.to_ascii_uppercase()))
+            .map(CompletionItem::http_method)
+            .collect();
+    }
+
     // Check if we should trigger completions (user just typed {{)
     if !should_trigger_completion(position, document) {
         return Vec::new();
@@ -203,6 +230,52 @@ fn should_trigger_completion(position: Position, document: &str) -> bool {
     text_before.ends_with("{{")
 }
 
+/// Returns the letters typed so far if the cursor is at the method position
+/// of a request line
+///
+/// That is the first word of the first line in a `###` block that isn't
+/// blank, a comment, or an `@` line, with only letters before the cursor.
+fn method_prefix(position: Position, document: &str) -> Option<&str> {
+    let lines: Vec<&str> = document.lines().collect();
+    let line = lines.get(position.line)?;
+    let prefix = line.get(..position.character)?;
+    if prefix.is_empty() || !prefix.chars().all(|c| c.is_ascii_alphabetic()) {
+        return None;
+    }
+
+    let block_head = lines[..position.line]
+        .iter()
+        .rev()
+        .take_while(|line| line.trim() != "###");
+    for line in block_head {
+        let trimmed = line.trim();
+        if !(trimmed.is_empty()
+            || trimmed.starts_with('#')
+            || trimmed.starts_with("//")
+            || trimmed.starts_with('@'))
+        {
+            return None;
+        }
+    }
+
+    Some(prefix)
+}
+
+/// Returns a short description of an HTTP method
+fn method_description(method: HttpMethod) -> &'static str {
+    match method {
+        HttpMethod::GET => "Retrieve a resource",
+        HttpMethod::POST => "Submit data to create a resource",
+        HttpMethod::PUT => "Replace a resource",
+        HttpMethod::DELETE => "Remove a resource",
+        HttpMethod::PATCH => "Partially modify a resource",
+        HttpMethod::OPTIONS => "Describe communication options",
+        HttpMethod::HEAD => "Retrieve headers only",
+        HttpMethod::TRACE => "Perform a message loop-back test",
+        HttpMethod::CONNECT => "Establish a tunnel to the server",
+    }
+}
+
 /// Returns all available system variable completions
 fn get_system_variable_completions() -> Vec<CompletionItem> {
     vec![
@@ -213,20 +286,30 @@ fn get_system_variable_completions() -> Vec<CompletionItem> {
         ),
         CompletionItem::system_variable(
             "datetime",
-            "Formatted datetime (requires format: {{$datetime iso8601}} or {{$datetime rfc1123}})",
+            "Formatted datetime (requires format: {{$datetime iso8601}}, {{$datetime rfc1123}}, or {{$datetime %Y-%m-%d}})",
         ),
         CompletionItem::system_variable(
             "randomInt",
             "Random integer in range (requires min max: {{$randomInt 1 100}})",
         ),
+        CompletionItem::system_variable(
+            "randomEmail",
+            "Random email address on example.com (e.g., sofia4821@example.com)",
+        ),
+        CompletionItem::system_variable("randomFirstName", "Random first name"),
+        CompletionItem::system_variable("randomUuid", "Random UUID v4 for test payloads"),
         CompletionItem::system_variable(
             "processEnv",
-            "Process environment variable (requires name: {{$processEnv PATH}})",
+            "Process environment variable (requires name, optional default: {{$processEnv API_TOKEN fallback}})",
         ),
         CompletionItem::system_variable(
             "dotenv",
             "Variable from .env file (requires name: {{$dotenv API_KEY}})",
         ),
+        CompletionItem::system_variable(
+            "locale",
+            "Locale for Accept-Language (defaultAcceptLanguage setting or system locale)",
+        ),
     ]
 }
 
@@ -266,7 +349,7 @@ mod tests {
     #[test]
     fn test_system_variable_completions() {
         let completions = get_system_variable_completions();
-        assert_eq!(completions.len(), 6);
+        assert_eq!(completions.len(), 10);
 
         let guid = completions.iter().find(|c| c.label == "$guid").unwrap();
         assert_eq!(guid.kind, CompletionKind::SystemVariable);
@@ -406,4 +489,47 @@ mod tests {
         assert_eq!(pos.line, 5);
         assert_eq!(pos.character, 10);
     }
+
+    #[test]
+    fn test_method_completions_at_request_line_start() {
+        let envs = Environments::new();
+        let file_vars = HashMap::new();
+
+        let doc = "GET https://api.example.com/users\n\n###\n\n# @name create\nP";
+        let completions = provide_completions(Position::new(5, 1), doc, &envs, &file_vars);
+        let labels: Vec<&str> = completions.iter().map(|c| c.label.as_str()).collect();
+
+        assert_eq!(labels, vec!["POST", "PUT", "PATCH"]);
+        assert!(completions
+            .iter()
+            .all(|c| c.kind == CompletionKind::HttpMethod));
+        assert_eq!(completions[0].insert_text, "POST ");
+
+        // Lowercase letters at the top of the file match too
+        let completions = provide_completions(Position::new(0, 2), "de", &envs, &file_vars);
+        assert_eq!(completions.len(), 1);
+        assert_eq!(completions[0].label, "DELETE");
+    }
+
+    #[test]
+    fn test_no_method_completions_outside_method_position() {
+        let envs = Environments::new();
+        let file_vars = HashMap::new();
+
+        // Mid-URL
+        let doc = "GET https://api.example.com/P";
+        assert!(provide_completions(Position::new(0, 29), doc, &envs, &file_vars).is_empty());
+
+        // Header line below the request line
+        let doc = "GET https://api.example.com\nP";
+        assert!(provide_completions(Position::new(1, 1), doc, &envs, &file_vars).is_empty());
+
+        // Second word of the line
+        let doc = "POST P";
+        assert!(provide_completions(Position::new(0, 6), doc, &envs, &file_vars).is_empty());
+
+        // Nothing typed yet
+        let doc = "###\n\n";
+        assert!(provide_completions(Position::new(1, 0), doc, &envs, &file_vars).is_empty());
+    }
 }
@@ -0,0 +1,59 @@
+//! Go-to-definition provider for REST Client
+//!
+//! This module finds where the `{{variable}}` under the cursor is declared in
+//! the environment file, so Ctrl-click on `{{baseUrl}}` opens
+//! `.http-client-env.json` at the `"baseUrl"` key.
+
+use super::hover::{find_variable_at_position, Position};
+use crate::environment::{find_variable_definition, VariableLocation};
+use std::path::Path;
+
+/// Provides the declaration of the variable at the given position
+///
+/// System variables (`{{$guid}}`) have no declaration and return `None`, as
+/// do file variables, captured values, and response references.
+///
+/// # Arguments
+/// * `position` - The cursor position in the document
+/// * `document` - The full text of the document
+/// * `workspace_path` - The workspace root, where the environment file is searched for
+/// * `environment` - The active environment, if any
+///
+/// # Returns
+/// The location of the variable's key in the environment file, if found
+pub fn provide_definition(
+    position: Position,
+    document: &str,
+    workspace_path: &Path,
+    environment: Option<&str>,
+) -> Option<VariableLocation> {
+    let (name, _) = find_variable_at_position(position, document)?;
+    if name.starts_with('$') {
+        return None;
+    }
+
+    find_variable_definition(workspace_path, &name, environment)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_provide_definition_for_environment_variable() {
+        let dir = TempDir::new().unwrap();
+        let content = "{\n  \"dev\": {\n    \"baseUrl\": \"http://localhost:3000\"\n  }\n}\n";
+        std::fs::write(dir.path().join(".http-client-env.json"), content).unwrap();
+
+        let doc = "GET {{baseUrl}}/users\nX-Id: {{$guid}}";
+        let location =
+            provide_definition(Position::new(0, 8), doc, dir.path(), Some("dev")).unwrap();
+        assert_eq!(location.offset, content.find("\"baseUrl\"").unwrap());
+        assert_eq!((location.line, location.character), (2, 4));
+
+        // System variables and positions outside a reference have no definition
+        assert!(provide_definition(Position::new(1, 9), doc, dir.path(), Some("dev")).is_none());
+        assert!(provide_definition(Position::new(0, 1), doc, dir.path(), Some("dev")).is_none());
+    }
+}
@@ -7,12 +7,28 @@
 //! - Header name validation (with typo suggestions)
 //! - JSON body validation when Content-Type is application/json
 //! - Missing required headers for POST/PUT/PATCH requests
-
-use crate::models::HttpMethod;
-use crate::parser::{error::ParseError, parse_file};
-use crate::variables::{substitute_variables, VarError, VariableContext};
+//! - Header lines placed after the blank line that starts the body
+//! - Directives disabled with the configured prefix (e.g., `# !@capture`)
+//! - GraphQL variables that are missing or not declared by the operation
+//! - Active environment variables the document never uses
+//! - File variables (`@name = value`) that are never referenced
+//! - Request names (`# @name`) used by more than one request
+
+use crate::config::get_config;
+use crate::graphql::parser::{is_graphql_request, validate_graphql_request};
+use crate::graphql::ParseError as GraphQLParseError;
+use crate::models::{HttpMethod, HttpRequest};
+use crate::parser::{
+    disabled_directive_name, error::ParseError, has_form_directive, misplaced_header_name,
+    parse_body_file_reference, parse_file, parse_name_directive,
+};
+use crate::variables::{
+    analyze_variables, expand_colon_params, find_variable_references, substitute_variables,
+    VarError, VariableContext,
+};
 use regex::Regex;
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
 
 /// Diagnostic severity levels
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -23,6 +39,8 @@ pub enum DiagnosticSeverity {
     Warning,
     /// Info - informational message
     Info,
+    /// Hint - a tidiness suggestion, shown subtly
+    Hint,
 }
 
 /// Position in a text document (line and column)
@@ -122,6 +140,17 @@ impl Diagnostic {
         }
     }
 
+    /// Creates a new hint diagnostic
+    pub fn hint(range: Range, message: impl Into<String>) -> Self {
+        Self {
+            range,
+            severity: DiagnosticSeverity::Hint,
+            message: message.into(),
+            code: None,
+            suggestion: None,
+        }
+    }
+
     /// Sets the diagnostic code
     pub fn with_code(mut self, code: impl Into<String>) -> Self {
         self.code = Some(code.into());
@@ -155,10 +184,31 @@ impl Diagnostic {
 /// let diagnostics = provide_diagnostics(doc, &context);
 /// ```
 pub fn provide_diagnostics(document: &str, context: &VariableContext) -> Vec<Diagnostic> {
+    provide_file_diagnostics(document, None, context)
+}
+
+/// Provides diagnostics for an HTTP document saved at `file_path`
+///
+/// Works like [`provide_diagnostics`], but body files referenced with
+/// `< path` are resolved against the document's directory, so missing ones
+/// are reported. Without a path they can't be checked and are skipped.
+///
+/// # Arguments
+/// * `document` - The full text of the .http file
+/// * `file_path` - Path of the .http file on disk, if it has one
+/// * `context` - Variable context for resolving variables
+///
+/// # Returns
+/// A vector of diagnostics found in the document
+pub fn provide_file_diagnostics(
+    document: &str,
+    file_path: Option<&Path>,
+    context: &VariableContext,
+) -> Vec<Diagnostic> {
     let mut diagnostics = Vec::new();
 
     // 1. Parse the document and collect syntax errors
-    diagnostics.extend(check_syntax_errors(document));
+    diagnostics.extend(check_syntax_errors(document, file_path));
 
     // 2. Check for variable issues
     diagnostics.extend(check_variable_issues(document, context));
@@ -175,17 +225,49 @@ pub fn provide_diagnostics(document: &str, context: &VariableContext) -> Vec<Dia
     // 6. Check for missing required headers
     diagnostics.extend(check_required_headers(document));
 
+    // 7. Report disabled directives
+    diagnostics.extend(check_disabled_directives(
+        document,
+        &get_config().disabled_directive_prefix,
+    ));
+
+    // 8. Flag headers written below the blank line that starts the body
+    diagnostics.extend(check_headers_in_body(document));
+
+    // 9. Compare GraphQL variables against the operation's declarations
+    diagnostics.extend(check_graphql_variables(document, context));
+
+    // 10. Report active environment variables the document doesn't use
+    let colon_params = get_config().enable_colon_params;
+    diagnostics.extend(check_unused_environment_variables(
+        document,
+        context,
+        colon_params,
+    ));
+
+    // 11. Flag file variables that nothing references
+    diagnostics.extend(check_unused_file_variables(document, colon_params));
+
+    // 12. Flag request names that an earlier request already uses
+    diagnostics.extend(check_duplicate_request_names(document));
+
     diagnostics
 }
 
 /// Checks for syntax errors by parsing the document
-fn check_syntax_errors(document: &str) -> Vec<Diagnostic> {
+///
+/// Missing body files are only reported when the document's path is known.
+fn check_syntax_errors(document: &str, file_path: Option<&Path>) -> Vec<Diagnostic> {
     let mut diagnostics = Vec::new();
 
-    // Use a dummy file path for parsing
-    let file_path = std::path::PathBuf::from(".");
+    // Fall back to a dummy file path for unsaved documents
+    let path = file_path.map_or_else(|| PathBuf::from("."), Path::to_path_buf);
 
-    match parse_file(document, &file_path) {
+    match parse_file(document, &path) {
+        // Body file paths can't be resolved against the dummy path
+        Ok(requests) if file_path.is_some() => {
+            diagnostics.extend(check_missing_body_files(document, &requests));
+        }
         Ok(_) => {
             // No syntax errors
         }
@@ -199,6 +281,34 @@ fn check_syntax_errors(document: &str) -> Vec<Diagnostic> {
     diagnostics
 }
 
+/// Reports `< path` and `<@ path` body files that don't exist
+///
+/// A missing file is not a syntax error, since it is only read when the
+/// request is sent, but the request would fail then.
+fn check_missing_body_files(document: &str, requests: &[HttpRequest]) -> Vec<Diagnostic> {
+    let lines: Vec<&str> = document.lines().collect();
+
+    requests
+        .iter()
+        .filter_map(|request| {
+            let path = request.body_file.as_ref().filter(|path| !path.exists())?;
+
+            // The reference is the first `<` line below the request line
+            let line = (request.line_number..lines.len())
+                .find(|&index| parse_body_file_reference(lines[index]).is_some())?;
+
+            Some(
+                Diagnostic::error(
+                    Range::line(line),
+                    format!("Body file '{}' not found", path.display()),
+                )
+                .with_code("body-file-not-found")
+                .with_suggestion("Paths are relative to the .http file"),
+            )
+        })
+        .collect()
+}
+
 /// Converts a ParseError to a Diagnostic
 fn parse_error_to_diagnostic(error: &ParseError) -> Diagnostic {
     // Convert 1-based line number from parser to 0-based for LSP
@@ -214,6 +324,12 @@ fn parse_error_to_diagnostic(error: &ParseError) -> Diagnostic {
             "Expected one of: GET, POST, PUT, DELETE, PATCH, OPTIONS, HEAD, TRACE, CONNECT",
         ),
 
+        ParseError::InvalidUrl { url, .. } if url.contains(char::is_whitespace) => {
+            Diagnostic::error(Range::line(line), format!("URL contains a space '{}'", url))
+                .with_code("invalid-url")
+                .with_suggestion("URL-encode the value (e.g., %20) or check the variables it uses")
+        }
+
         ParseError::InvalidUrl { url, .. } => {
             Diagnostic::error(Range::line(line), format!("Invalid URL format '{}'", url))
                 .with_code("invalid-url")
@@ -243,6 +359,39 @@ fn parse_error_to_diagnostic(error: &ParseError) -> Diagnostic {
         )
         .with_code("invalid-http-version")
         .with_suggestion("Use HTTP/1.1 or HTTP/2"),
+
+        ParseError::InvalidTimeout { value, .. } => {
+            Diagnostic::error(Range::line(line), format!("Invalid timeout '{}'", value))
+                .with_code("invalid-timeout")
+                .with_suggestion("Use a positive number of milliseconds, e.g. '# @timeout 60000'")
+        }
+
+        ParseError::InvalidRetries { value, .. } => Diagnostic::error(
+            Range::line(line),
+            format!("Invalid retry count '{}'", value),
+        )
+        .with_code("invalid-retries")
+        .with_suggestion("Use a whole number of retries, e.g. '# @retries 3'"),
+
+        ParseError::InvalidAwsDirective { directive, .. } => Diagnostic::error(
+            Range::line(line),
+            format!("Invalid AWS directive '{}'", directive),
+        )
+        .with_code("invalid-aws-directive")
+        .with_suggestion("Use '# @aws region service accessKey secretKey [sessionToken]'"),
+
+        ParseError::InvalidMultipartPart { part, .. } => Diagnostic::error(
+            Range::line(line),
+            format!("Invalid multipart part '{}'", part),
+        )
+        .with_code("invalid-multipart-part")
+        .with_suggestion("Start parts with '--field name=<name>' or '--file <name>=<path>'"),
+
+        ParseError::InvalidFormField { field, .. } => {
+            Diagnostic::error(Range::line(line), format!("Invalid form field '{}'", field))
+                .with_code("invalid-form-field")
+                .with_suggestion("Form fields must be in the format 'key: value'")
+        }
     }
 }
 
@@ -271,6 +420,26 @@ fn check_variable_issues(document: &str, context: &VariableContext) -> Vec<Diagn
                 continue;
             }
 
+            // `$processEnv` without a default fails at send time when unset
+            if var_name.starts_with("$processEnv") {
+                if let Err(VarError::EnvVarNotFound(env_var)) =
+                    substitute_variables(&format!("{{{{{}}}}}", var_name), context)
+                {
+                    diagnostics.push(
+                        Diagnostic::warning(
+                            Range::at_line(line_idx, match_start, match_end),
+                            format!("Environment variable '{}' is not set", env_var),
+                        )
+                        .with_code("env-var-not-set")
+                        .with_suggestion(format!(
+                            "Set {0}, add a default ({{{{$processEnv {0} fallback}}}}), or use %{0} to allow it to be empty",
+                            env_var
+                        )),
+                    );
+                }
+                continue;
+            }
+
             // Check if variable is defined (skip system variables)
             if !var_name.starts_with('$') {
                 // Try to resolve the variable
@@ -406,8 +575,13 @@ fn check_header_issues(document: &str) -> Vec<Diagnostic> {
     for (line_idx, line) in document.lines().enumerate() {
         let trimmed = line.trim();
 
-        // Skip empty lines, comments, and request lines
-        if trimmed.is_empty() || trimmed.starts_with('#') || trimmed.starts_with("//") {
+        // Skip empty lines, comments, and query continuation lines
+        if trimmed.is_empty()
+            || trimmed.starts_with('#')
+            || trimmed.starts_with("//")
+            || trimmed.starts_with('?')
+            || trimmed.starts_with('&')
+        {
             continue;
         }
 
@@ -459,6 +633,338 @@ fn check_header_issues(document: &str) -> Vec<Diagnostic> {
     diagnostics
 }
 
+/// Reports directives disabled with the configured prefix (e.g., `# !@capture`)
+fn check_disabled_directives(document: &str, prefix: &str) -> Vec<Diagnostic> {
+    document
+        .lines()
+        .enumerate()
+        .filter_map(|(line_idx, line)| {
+            let name = disabled_directive_name(line, prefix)?;
+            let directive = format!("{}@{}", prefix, name);
+            let start = line.find(&directive).unwrap_or(0);
+            let end = start + directive.len();
+            Some(
+                Diagnostic::info(
+                    Range::at_line(line_idx, start, end),
+                    format!("Directive '@{}' is disabled", name),
+                )
+                .with_code("disabled-directive")
+                .with_suggestion(format!("Remove '{}' to enable it", prefix)),
+            )
+        })
+        .collect()
+}
+
+/// Flags header-like lines at the start of a request body.
+///
+/// Only the leading lines of each body are checked, up to the first line that
+/// doesn't look like a header. `# @form` bodies are skipped since their
+/// `key: value` lines are intentional.
+fn check_headers_in_body(document: &str) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    let mut in_headers = false;
+    let mut in_leading_body = false;
+    let mut is_form = false;
+
+    for (line_idx, line) in document.lines().enumerate() {
+        let trimmed = line.trim();
+
+        if trimmed == "###" {
+            in_headers = false;
+            in_leading_body = false;
+            is_form = false;
+            continue;
+        }
+
+        if in_leading_body {
+            if trimmed.is_empty() {
+                continue;
+            }
+            match misplaced_header_name(line) {
+                Some(name) if !is_form => {
+                    let start = line.find(name).unwrap_or(0);
+                    diagnostics.push(
+                        Diagnostic::info(
+                            Range::at_line(line_idx, start, start + name.len()),
+                            "This looks like a header but it's inside the body \
+                             — did you add a blank line too early?",
+                        )
+                        .with_code("header-in-body")
+                        .with_suggestion(format!(
+                            "Move '{}' above the blank line that starts the body",
+                            name
+                        )),
+                    );
+                }
+                _ => in_leading_body = false,
+            }
+            continue;
+        }
+
+        if is_request_line(trimmed) {
+            in_headers = true;
+            continue;
+        }
+
+        if trimmed.starts_with('#') || trimmed.starts_with("//") {
+            is_form |= has_form_directive(&[(line_idx, trimmed)]);
+            continue;
+        }
+
+        // Empty line after the headers starts the body
+        if in_headers && trimmed.is_empty() {
+            in_headers = false;
+            in_leading_body = true;
+        }
+    }
+
+    diagnostics
+}
+
+/// Checks GraphQL bodies for missing and undeclared variables.
+///
+/// Errors are reported on the operation's first line. Undeclared variables
+/// are reported as warnings on the line where they're set, if it can be found.
+/// `{{variables}}` in the body are substituted first; a variables object
+/// that still holds unresolved ones isn't reported as invalid JSON.
+fn check_graphql_variables(document: &str, context: &VariableContext) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    let lines: Vec<&str> = document.lines().collect();
+    let mut in_headers = false;
+
+    for (line_idx, line) in lines.iter().enumerate() {
+        let trimmed = line.trim();
+
+        if trimmed == "###" {
+            in_headers = false;
+            continue;
+        }
+
+        if is_request_line(trimmed) {
+            in_headers = true;
+            continue;
+        }
+
+        if !in_headers || !trimmed.is_empty() {
+            continue;
+        }
+        in_headers = false;
+
+        let body_start = line_idx + 1;
+        let body_lines = collect_body_lines(&lines, body_start);
+        let body = body_lines.join("\n");
+        if !is_graphql_request(&body, None) {
+            continue;
+        }
+
+        let operation_line = body_start
+            + body_lines
+                .iter()
+                .position(|line| !line.trim().is_empty())
+                .unwrap_or(0);
+
+        let body = substitute_variables(&body, context).unwrap_or(body);
+        let validation = validate_graphql_request(&body);
+        for error in &validation.errors {
+            if matches!(error, GraphQLParseError::InvalidVariables(_)) && body.contains("{{") {
+                continue;
+            }
+            let diagnostic = Diagnostic::error(Range::line(operation_line), error.to_string());
+            diagnostics.push(match error {
+                GraphQLParseError::MissingVariable { name, .. } => diagnostic
+                    .with_code("graphql-missing-variable")
+                    .with_suggestion(format!("Add \"{}\" to the variables object", name)),
+                _ => diagnostic.with_code("invalid-graphql"),
+            });
+        }
+
+        for name in &validation.undeclared_variables {
+            let key = format!("\"{}\"", name);
+            let variable_line = body_lines
+                .iter()
+                .position(|line| line.trim_start().starts_with(&key))
+                .map_or(operation_line, |offset| body_start + offset);
+
+            diagnostics.push(
+                Diagnostic::warning(
+                    Range::line(variable_line),
+                    format!(
+                        "GraphQL variable '{}' is not declared by the operation",
+                        name
+                    ),
+                )
+                .with_code("graphql-undeclared-variable")
+                .with_suggestion(format!(
+                    "Declare ${} in the operation signature or remove it",
+                    name
+                )),
+            );
+        }
+    }
+
+    diagnostics
+}
+
+/// Reports variables of the active environment that the document never uses.
+///
+/// Each `{{variable}}` reference is resolved on its own, so variables used
+/// only through other variables (`@apiUrl = {{host}}/v1`) count as used and
+/// an undefined reference doesn't hide the rest. Documents that don't use
+/// the active environment at all get no diagnostic. With `colon_params`,
+/// `:name` path segments in request URLs count as references too.
+fn check_unused_environment_variables(
+    document: &str,
+    context: &VariableContext,
+    colon_params: bool,
+) -> Vec<Diagnostic> {
+    let Some(environment) = &context.environment else {
+        return Vec::new();
+    };
+
+    let mut used = BTreeSet::new();
+    let references: BTreeSet<String> = document
+        .lines()
+        .flat_map(|line| line_variable_references(line, colon_params))
+        .collect();
+    for name in references {
+        if let Ok(usage) = analyze_variables(&format!("{{{{{}}}}}", name), context) {
+            used.extend(usage.resolved);
+        }
+    }
+
+    let mut unused: Vec<&str> = environment
+        .variables
+        .keys()
+        .filter(|name| !used.contains(*name))
+        .map(String::as_str)
+        .collect();
+    if unused.is_empty() || unused.len() == environment.variables.len() {
+        return Vec::new();
+    }
+    unused.sort_unstable();
+
+    vec![Diagnostic::info(
+        Range::line(0),
+        format!(
+            "Environment '{}' defines {} variable(s) not used in this file: {}",
+            environment.name,
+            unused.len(),
+            unused.join(", ")
+        ),
+    )
+    .with_code("unused-environment-variable")]
+}
+
+/// Splits an `@name = value` file variable declaration into its name and
+/// value, both trimmed.
+///
+/// Returns `None` for any other line, including directives such as
+/// `# @name login`.
+pub(crate) fn parse_file_variable(line: &str) -> Option<(&str, &str)> {
+    let at = line.find('@').filter(|at| line[..*at].trim().is_empty())?;
+    let (name, value) = line[at + 1..].split_once('=')?;
+    let name = name.trim();
+    if name.is_empty() || name.contains(char::is_whitespace) {
+        return None;
+    }
+    Some((name, value.trim()))
+}
+
+/// Flags `@name = value` declarations that no `{{name}}` in the file uses.
+///
+/// References anywhere count: the URL, headers, bodies, and the values of
+/// other file variables (`@apiUrl = {{baseUrl}}/v1` uses `baseUrl`). With
+/// `colon_params`, so do `:name` path segments in request URLs. A variable
+/// that only references itself is still unused.
+fn check_unused_file_variables(document: &str, colon_params: bool) -> Vec<Diagnostic> {
+    let lines: Vec<&str> = document.lines().collect();
+
+    lines
+        .iter()
+        .enumerate()
+        .filter_map(|(line_idx, line)| {
+            let (name, _) = parse_file_variable(line)?;
+            let at = line.find('@').unwrap_or_default();
+
+            let used = lines.iter().enumerate().any(|(other_idx, other)| {
+                other_idx != line_idx
+                    && line_variable_references(other, colon_params)
+                        .iter()
+                        .any(|reference| reference == name)
+            });
+            if used {
+                return None;
+            }
+
+            let name_end = line
+                .find(name)
+                .map_or(line.len(), |start| start + name.len());
+            Some(
+                Diagnostic::hint(
+                    Range::at_line(line_idx, at, name_end),
+                    format!("File variable '{}' is never used", name),
+                )
+                .with_code("unused-file-variable")
+                .with_suggestion(format!("Reference it as {{{{{}}}}} or remove it", name)),
+            )
+        })
+        .collect()
+}
+
+/// Returns the variables a line references.
+///
+/// With `colon_params`, the `:name` path segments of a request line's URL
+/// are included, as they are resolved like `{{name}}` when it is sent.
+fn line_variable_references(line: &str, colon_params: bool) -> Vec<String> {
+    let trimmed = line.trim();
+    match trimmed.split_whitespace().nth(1) {
+        Some(url) if colon_params && is_request_line(trimmed) => {
+            let mut references = find_variable_references(trimmed);
+            references.extend(find_variable_references(&expand_colon_params(url)));
+            references
+        }
+        _ => find_variable_references(line),
+    }
+}
+
+/// Flags `# @name` directives that repeat an earlier request's name.
+///
+/// Requests are referenced by name, so only the first request with a given
+/// name can be reached; each later one gets an error.
+fn check_duplicate_request_names(document: &str) -> Vec<Diagnostic> {
+    let mut first_lines: HashMap<String, usize> = HashMap::new();
+    let mut diagnostics = Vec::new();
+
+    for (line_idx, line) in document.lines().enumerate() {
+        let Some(name) = parse_name_directive(&[(line_idx, line)]) else {
+            continue;
+        };
+
+        match first_lines.get(&name) {
+            Some(first_line) => {
+                let start = line.rfind(name.as_str()).unwrap_or(0);
+                diagnostics.push(
+                    Diagnostic::error(
+                        Range::at_line(line_idx, start, start + name.len()),
+                        format!(
+                            "Request name '{}' is already used on line {}",
+                            name,
+                            first_line + 1
+                        ),
+                    )
+                    .with_code("duplicate-request-name")
+                    .with_suggestion("Give each request a unique @name"),
+                );
+            }
+            None => {
+                first_lines.insert(name, line_idx);
+            }
+        }
+    }
+
+    diagnostics
+}
+
 /// Returns a map of common header typos to their correct forms
 fn get_common_header_typos() -> HashMap<&'static str, &'static str> {
     let mut map = HashMap::new();
@@ -721,13 +1227,27 @@ mod tests {
     #[test]
     fn test_check_syntax_errors() {
         let doc = "INVALID https://example.com\n";
-        let diagnostics = check_syntax_errors(doc);
+        let diagnostics = check_syntax_errors(doc, None);
 
         assert_eq!(diagnostics.len(), 1);
         assert_eq!(diagnostics[0].severity, DiagnosticSeverity::Error);
         assert!(diagnostics[0].message.contains("Invalid HTTP method"));
     }
 
+    #[test]
+    fn test_check_syntax_errors_missing_body_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let file_path = dir.path().join("api.http");
+        let doc = "POST https://example.com/users\n\n< ./missing.json\n";
+
+        assert!(check_syntax_errors(doc, None).is_empty());
+
+        let diagnostics = check_syntax_errors(doc, Some(&file_path));
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code.as_deref(), Some("body-file-not-found"));
+        assert_eq!(diagnostics[0].range, Range::line(2));
+    }
+
     #[test]
     fn test_check_variable_issues_undefined() {
         let doc = "GET https://api.example.com/{{undefinedVar}}\n";
@@ -754,6 +1274,21 @@ mod tests {
         assert!(undefined_diag.is_none());
     }
 
+    #[test]
+    fn test_check_variable_issues_process_env() {
+        let context = VariableContext::new(PathBuf::from("."));
+        let doc = "GET https://api.example.com\n\
+                   Authorization: Bearer {{$processEnv REST_CLIENT_UNSET_TOKEN}}\n\
+                   X-Default: {{$processEnv REST_CLIENT_UNSET_TOKEN none}}\n\
+                   X-Optional: {{$processEnv %REST_CLIENT_UNSET_TOKEN}}";
+        let diagnostics = check_variable_issues(doc, &context);
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code.as_deref(), Some("env-var-not-set"));
+        assert_eq!(diagnostics[0].range.start.line, 1);
+        assert!(diagnostics[0].message.contains("REST_CLIENT_UNSET_TOKEN"));
+    }
+
     #[test]
     fn test_check_url_format() {
         let doc = "GET api.example.com/users\n";
@@ -763,6 +1298,40 @@ mod tests {
         assert!(diagnostics[0].message.contains("should start with"));
     }
 
+    #[test]
+    fn test_check_disabled_directives() {
+        let doc = "# !@capture token = $.token\n# @capture id = $.id\nGET https://api.example.com";
+        let diagnostics = check_disabled_directives(doc, "!");
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, DiagnosticSeverity::Info);
+        assert_eq!(diagnostics[0].message, "Directive '@capture' is disabled");
+        assert_eq!(diagnostics[0].range, Range::at_line(0, 2, 11));
+    }
+
+    #[test]
+    fn test_check_headers_in_body() {
+        let doc = "POST https://api.example.com/users\nContent-Type: application/json\n\nAuthorization: Bearer abc\n\nX-Request-Id: 42\n{\"name\": \"test\"}\nAccept: */*\n";
+        let diagnostics = check_headers_in_body(doc);
+
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].severity, DiagnosticSeverity::Info);
+        assert_eq!(diagnostics[0].code.as_deref(), Some("header-in-body"));
+        assert_eq!(diagnostics[0].range, Range::at_line(3, 0, 13));
+        assert_eq!(diagnostics[1].range, Range::at_line(5, 0, 12));
+        assert!(diagnostics[1]
+            .suggestion
+            .as_deref()
+            .unwrap()
+            .contains("X-Request-Id"));
+    }
+
+    #[test]
+    fn test_check_headers_in_body_ignores_form_and_headers() {
+        let doc = "# @form\nPOST https://api.example.com/login\nAccept: */*\n\nUser-Name: alice\n\n###\n\nGET https://api.example.com\nX-Api-Key: abc\n";
+        assert!(check_headers_in_body(doc).is_empty());
+    }
+
     #[test]
     fn test_check_header_typo() {
         let doc = "GET https://example.com\nConten-Type: application/json\n";
@@ -859,4 +1428,186 @@ Conten-Type: application/json
             .iter()
             .any(|d| d.code.as_deref() == Some("header-typo")));
     }
+
+    #[test]
+    fn test_check_graphql_variables() {
+        let doc = r#"POST https://api.example.com/graphql
+Content-Type: application/graphql
+
+query GetUser($id: ID!) {
+  user(id: $id) { id }
+}
+
+{
+  "userId": "123"
+}
+"#;
+
+        let diagnostics = check_graphql_variables(doc, &VariableContext::new(PathBuf::new()));
+        assert_eq!(diagnostics.len(), 2);
+
+        assert_eq!(diagnostics[0].severity, DiagnosticSeverity::Error);
+        assert_eq!(
+            diagnostics[0].code.as_deref(),
+            Some("graphql-missing-variable")
+        );
+        assert_eq!(diagnostics[0].range.start.line, 3);
+
+        assert_eq!(diagnostics[1].severity, DiagnosticSeverity::Warning);
+        assert_eq!(
+            diagnostics[1].code.as_deref(),
+            Some("graphql-undeclared-variable")
+        );
+        assert_eq!(diagnostics[1].range.start.line, 8);
+    }
+
+    #[test]
+    fn test_check_graphql_variables_ignores_other_bodies() {
+        let doc = "POST https://api.example.com/users\nContent-Type: application/json\n\n{\"id\": \"$id\"}\n\n###\n\nPOST https://api.example.com/graphql\n\nquery Q($id: ID!) { user(id: $id) { id } }\n\n{\"id\": \"1\"}\n";
+        assert!(check_graphql_variables(doc, &VariableContext::new(PathBuf::new())).is_empty());
+    }
+
+    #[test]
+    fn test_check_graphql_variables_substitutes_variables() {
+        let doc = r#"POST https://api.example.com/graphql
+
+query GetUser($id: ID!, $limit: Int) {
+  user(id: $id) { id }
+}
+
+{
+  "id": {{userId}},
+  "limit": {{limit}}
+}
+"#;
+
+        // Unresolved variables leave the object unparseable; that's not an error
+        let context = VariableContext::new(PathBuf::new());
+        assert!(check_graphql_variables(doc, &context).is_empty());
+
+        // Resolved ones are checked like any other value
+        let mut context = environment_context();
+        context
+            .file_variables
+            .insert("userId".to_string(), "null".to_string());
+        context
+            .file_variables
+            .insert("limit".to_string(), "10".to_string());
+        let diagnostics = check_graphql_variables(doc, &context);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].code.as_deref(),
+            Some("graphql-missing-variable")
+        );
+    }
+
+    fn environment_context() -> VariableContext {
+        let mut env = crate::environment::Environment::new("dev");
+        env.set("host", "localhost:3000");
+        env.set("token", "abc123");
+        env.set("timeout", "30");
+        VariableContext::with_environment(PathBuf::from("."), Some(env), HashMap::new())
+    }
+
+    #[test]
+    fn test_check_unused_environment_variables() {
+        let doc =
+            "@apiUrl = http://{{host}}/v1\n\nGET {{apiUrl}}/users\nX-Missing: {{undefinedVar}}\n";
+        let diagnostics = check_unused_environment_variables(doc, &environment_context(), false);
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, DiagnosticSeverity::Info);
+        assert_eq!(
+            diagnostics[0].code.as_deref(),
+            Some("unused-environment-variable")
+        );
+        assert_eq!(
+            diagnostics[0].message,
+            "Environment 'dev' defines 2 variable(s) not used in this file: timeout, token"
+        );
+    }
+
+    #[test]
+    fn test_check_unused_environment_variables_all_used_or_none() {
+        let context = environment_context();
+
+        let doc = "GET http://{{host}}/users?timeout={{timeout}}\nAuthorization: Basic {{base64(user:{{token}})}}\n";
+        assert!(check_unused_environment_variables(doc, &context, false).is_empty());
+
+        // A file that doesn't use the environment isn't reported
+        let doc = "GET https://example.com/{{$guid}}\n";
+        assert!(check_unused_environment_variables(doc, &context, false).is_empty());
+
+        // Nor is anything reported without an active environment
+        let doc = "GET http://{{host}}/users\n";
+        let context = VariableContext::new(PathBuf::from("."));
+        assert!(check_unused_environment_variables(doc, &context, false).is_empty());
+    }
+
+    #[test]
+    fn test_check_unused_file_variables() {
+        let doc = r#"@baseUrl = https://api.example.com
+@apiUrl = {{baseUrl}}/v1
+@token = abc123
+@unused = 42
+@loop = {{loop}}
+
+POST {{apiUrl}}/users
+Content-Type: application/json
+
+{"token": "{{ token }}"}
+"#;
+        let diagnostics = check_unused_file_variables(doc, false);
+
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].severity, DiagnosticSeverity::Hint);
+        assert_eq!(diagnostics[0].code.as_deref(), Some("unused-file-variable"));
+        assert_eq!(
+            diagnostics[0].message,
+            "File variable 'unused' is never used"
+        );
+        assert_eq!(diagnostics[0].range, Range::at_line(3, 0, 7));
+        assert_eq!(diagnostics[1].message, "File variable 'loop' is never used");
+    }
+
+    #[test]
+    fn test_check_unused_variables_with_colon_params() {
+        let doc = "@userId = 42\n\nGET http://{{host}}/users/:userId/:timeout\n";
+        assert_eq!(check_unused_file_variables(doc, false).len(), 1);
+        assert!(check_unused_file_variables(doc, true).is_empty());
+
+        let context = environment_context();
+        let diagnostics = check_unused_environment_variables(doc, &context, true);
+        assert_eq!(
+            diagnostics[0].message,
+            "Environment 'dev' defines 1 variable(s) not used in this file: token"
+        );
+    }
+
+    #[test]
+    fn test_check_unused_file_variables_ignores_other_at_lines() {
+        let doc = "@use ./common.http\n# @name login\n# @capture token = $.token\nGET https://api.example.com\n";
+        assert!(check_unused_file_variables(doc, false).is_empty());
+    }
+
+    #[test]
+    fn test_check_duplicate_request_names() {
+        let doc = "# @name Login\nPOST https://api.example.com/login\n\n###\n# @name Login\nPOST https://api.example.com/v2/login\n";
+        let diagnostics = check_duplicate_request_names(doc);
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, DiagnosticSeverity::Error);
+        assert_eq!(
+            diagnostics[0].code.as_deref(),
+            Some("duplicate-request-name")
+        );
+        assert_eq!(
+            diagnostics[0].message,
+            "Request name 'Login' is already used on line 1"
+        );
+        assert_eq!(diagnostics[0].range, Range::at_line(4, 8, 13));
+
+        let doc = "# @name Login\nPOST https://api.example.com/login\n\n###\n# @name Profile\nGET https://api.example.com/me\n";
+        assert!(check_duplicate_request_names(doc).is_empty());
+    }
 }
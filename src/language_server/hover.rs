@@ -146,7 +146,10 @@ pub fn provide_hover(
 /// Finds a variable reference at the given position
 ///
 /// Returns the variable name and its range in the document
-fn find_variable_at_position(position: Position, document: &str) -> Option<(String, Range)> {
+pub(crate) fn find_variable_at_position(
+    position: Position,
+    document: &str,
+) -> Option<(String, Range)> {
     let lines: Vec<&str> = document.lines().collect();
 
     if position.line >= lines.len() {
@@ -245,10 +248,17 @@ fn get_system_variable_description(name: &str) -> String {
     match name {
         "guid" => "generates a new UUID v4".to_string(),
         "timestamp" => "current Unix timestamp (can use offset like -1 d)".to_string(),
-        "datetime" => "formatted datetime (requires format: iso8601 or rfc1123)".to_string(),
+        "datetime" => {
+            "formatted datetime (requires format: iso8601, rfc1123, or a strftime pattern)"
+                .to_string()
+        }
         "randomInt" => "random integer (requires min and max)".to_string(),
+        "randomEmail" => "random email address on example.com".to_string(),
+        "randomFirstName" => "random first name".to_string(),
+        "randomUuid" => "random UUID v4".to_string(),
         "processEnv" => "process environment variable".to_string(),
         "dotenv" => "variable from .env file".to_string(),
+        "locale" => "locale for Accept-Language testing".to_string(),
         _ => "system variable".to_string(),
     }
 }
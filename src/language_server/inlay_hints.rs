@@ -0,0 +1,179 @@
+//! Inlay hint provider for REST Client
+//!
+//! This module shows what each `{{variable}}` in a .http file resolves to, as
+//! a hint right after the reference: `{{baseUrl}}ⓘhttp://localhost:3000`.
+//! Values come from the file's `@name = value` variables, the active
+//! environment, the shared variables, and system variables, with nested
+//! references resolved. References that don't resolve get no hint, since
+//! diagnostics already report them, and neither do values that change on
+//! every request, such as `{{$guid}}`.
+
+use super::diagnostics::{parse_file_variable, Position};
+use crate::environment::Environments;
+use crate::variables::substitution::analyze_variables;
+use crate::variables::VariableContext;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::path::PathBuf;
+
+/// Longest value shown in a hint, in characters; longer values are cut short
+const MAX_HINT_LENGTH: usize = 40;
+
+/// System variables that resolve to the same value every time; any other
+/// system variable is random or time-based, so a hint would be misleading
+const DETERMINISTIC_SYSTEM_VARIABLES: &[&str] = &["$processEnv", "$dotenv", "$locale"];
+
+/// Matches a `{{variable}}` reference without nested braces
+static REFERENCE_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\{\{([^{}]+)\}\}").expect("Failed to compile reference regex"));
+
+/// A resolved value shown after a variable reference
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InlayHint {
+    /// Where the hint is shown: just after the closing `}}`
+    pub position: Position,
+    /// The hint text, `ⓘ` followed by the resolved value
+    pub label: String,
+    /// The full resolved value, shown as a tooltip when the label is cut short
+    pub tooltip: Option<String>,
+}
+
+/// Provides an inlay hint for every variable reference that resolves
+///
+/// # Arguments
+/// * `document` - The full text of the .http file
+/// * `environments` - Available environments and variables
+///
+/// # Returns
+/// A vector of inlay hints, in document order
+///
+/// # Examples
+/// ```
+/// use rest_client::environment::{Environment, Environments};
+/// use rest_client::language_server::inlay_hints::provide_inlay_hints;
+///
+/// let mut dev = Environment::new("dev");
+/// dev.set("baseUrl", "http://localhost:3000");
+/// let mut envs = Environments::new();
+/// envs.add_environment(dev);
+/// envs.set_active("dev");
+///
+/// let hints = provide_inlay_hints("GET {{baseUrl}}/users", &envs);
+/// assert_eq!(hints[0].label, "ⓘhttp://localhost:3000");
+/// assert_eq!(hints[0].position.character, 15);
+/// ```
+pub fn provide_inlay_hints(document: &str, environments: &Environments) -> Vec<InlayHint> {
+    let mut context = VariableContext::with_environment(
+        PathBuf::from("."),
+        environments.get_active().cloned(),
+        environments.shared.clone(),
+    );
+    for line in document.lines() {
+        if let Some((name, value)) = parse_file_variable(line) {
+            context
+                .file_variables
+                .insert(name.to_string(), value.to_string());
+        }
+    }
+
+    let mut hints = Vec::new();
+    for (line_idx, line) in document.lines().enumerate() {
+        for reference in REFERENCE_REGEX.find_iter(line) {
+            let Ok(usage) = analyze_variables(reference.as_str(), &context) else {
+                continue;
+            };
+            let non_deterministic = usage.resolved.iter().any(|name| {
+                let name = name.split_whitespace().next().unwrap_or_default();
+                name.starts_with('$') && !DETERMINISTIC_SYSTEM_VARIABLES.contains(&name)
+            });
+            if non_deterministic {
+                continue;
+            }
+            let value = usage.text;
+
+            let shown: String = value.chars().take(MAX_HINT_LENGTH).collect();
+            let truncated = shown.len() < value.len();
+            hints.push(InlayHint {
+                position: Position::new(line_idx, reference.end()),
+                label: format!("ⓘ{}{}", shown, if truncated { "…" } else { "" }),
+                tooltip: truncated.then_some(value),
+            });
+        }
+    }
+
+    hints
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::environment::Environment;
+
+    fn test_environments() -> Environments {
+        let mut dev = Environment::new("dev");
+        dev.set("host", "localhost:3000");
+        dev.set("baseUrl", "http://{{host}}/api");
+        dev.set("token", "x".repeat(50).as_str());
+
+        let mut envs = Environments::new();
+        envs.add_environment(dev);
+        envs.shared
+            .insert("apiVersion".to_string(), "v2".to_string());
+        envs.set_active("dev");
+        envs
+    }
+
+    #[test]
+    fn test_provide_inlay_hints_environment_and_shared() {
+        let doc = "GET {{baseUrl}}/{{ apiVersion }}/users\nX-Api-Version: {{apiVersion}}";
+        let hints = provide_inlay_hints(doc, &test_environments());
+
+        assert_eq!(hints.len(), 3);
+        assert_eq!(hints[0].label, "ⓘhttp://localhost:3000/api");
+        assert_eq!(hints[0].position, Position::new(0, 15));
+        assert_eq!(hints[0].tooltip, None);
+        assert_eq!(hints[1].label, "ⓘv2");
+        assert_eq!(hints[1].position, Position::new(0, 32));
+        assert_eq!(hints[2].position, Position::new(1, 29));
+    }
+
+    #[test]
+    fn test_provide_inlay_hints_skips_undefined_and_truncates() {
+        let doc = "GET {{missing}}/users\nAuthorization: Bearer {{token}}";
+        let hints = provide_inlay_hints(doc, &test_environments());
+
+        assert_eq!(hints.len(), 1);
+        assert_eq!(hints[0].position.line, 1);
+        assert_eq!(hints[0].label, format!("ⓘ{}…", "x".repeat(40)));
+        assert_eq!(hints[0].tooltip, Some("x".repeat(50)));
+    }
+
+    #[test]
+    fn test_provide_inlay_hints_file_variables_and_system_variables() {
+        let doc = "@userId = 42\n\
+            @userUrl = {{baseUrl}}/users/{{userId}}\n\
+            GET {{userUrl}}?id={{$guid}}&n={{$randomInt 5 5}}&t={{$timestamp}}\n\
+            X-Request: {{userId}}-{{$guid}}";
+        let hints = provide_inlay_hints(doc, &test_environments());
+
+        // References on the definition line resolve too; random and
+        // time-based values get no hint, even when nested
+        let labels: Vec<_> = hints.iter().map(|hint| hint.label.as_str()).collect();
+        assert_eq!(
+            labels,
+            vec![
+                "ⓘhttp://localhost:3000/api",
+                "ⓘ42",
+                "ⓘhttp://localhost:3000/api/users/42",
+                "ⓘ42",
+            ]
+        );
+        assert_eq!(hints[2].position.line, 2);
+    }
+
+    #[test]
+    fn test_provide_inlay_hints_without_active_environment() {
+        let hints = provide_inlay_hints("GET {{host}}/users", &Environments::new());
+        assert!(hints.is_empty());
+    }
+}
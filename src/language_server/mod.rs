@@ -5,15 +5,24 @@
 //! - Hover tooltips showing variable values
 //! - Real-time diagnostics for syntax errors, undefined variables, and validation
 //! - CodeLens for clickable "Send Request" actions above each request
+//! - Document symbols listing each request for the outline view
+//! - Inlay hints showing the resolved value of each variable
+//! - Go-to-definition from a variable to its environment file declaration
 //!
 //! These are helper functions designed to be integrated into a full LSP server later.
 
 pub mod codelens;
 pub mod completion;
+pub mod definition;
 pub mod diagnostics;
 pub mod hover;
+pub mod inlay_hints;
+pub mod symbols;
 
 pub use codelens::{provide_code_lens, CodeLens, Command};
 pub use completion::{provide_completions, CompletionItem, CompletionKind};
+pub use definition::provide_definition;
 pub use diagnostics::{provide_diagnostics, Diagnostic, DiagnosticSeverity, Position, Range};
 pub use hover::{provide_hover, Hover};
+pub use inlay_hints::{provide_inlay_hints, InlayHint};
+pub use symbols::{provide_document_symbols, RequestSymbol};
@@ -0,0 +1,157 @@
+//! Document symbol provider for REST Client
+//!
+//! This module lists the requests in a .http file as document symbols, which
+//! editors show in their outline and breadcrumb views. Each request is named
+//! by its `# @name` directive, or by its method and URL when it has none.
+//!
+//! The document is scanned block by block rather than fully parsed, so the
+//! outline stays available while a request further down the file is being
+//! edited and doesn't parse yet.
+
+use super::diagnostics::{Position, Range};
+use crate::models::request::HttpMethod;
+use crate::parser::parse_name_directive;
+
+/// A request in the document outline
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestSymbol {
+    /// The `@name` of the request, or `METHOD URL`
+    pub name: String,
+    /// `METHOD URL` for named requests, shown next to the name
+    pub detail: Option<String>,
+    /// The whole request block, from its first to its last non-blank line
+    pub range: Range,
+    /// The request line
+    pub selection_range: Range,
+}
+
+/// Provides a document symbol for every request in a document
+///
+/// Blocks (separated by `###`) without a request line, such as a block of
+/// file variables, produce no symbol.
+///
+/// # Arguments
+/// * `document` - The full text of the .http file
+///
+/// # Returns
+/// A vector of request symbols, in document order
+///
+/// # Examples
+/// ```
+/// use rest_client::language_server::symbols::provide_document_symbols;
+///
+/// let doc = "# @name listUsers\nGET https://api.example.com/users\n###\nDELETE https://api.example.com/users/1";
+/// let symbols = provide_document_symbols(doc);
+/// assert_eq!(symbols[0].name, "listUsers");
+/// assert_eq!(symbols[1].name, "DELETE https://api.example.com/users/1");
+/// ```
+pub fn provide_document_symbols(document: &str) -> Vec<RequestSymbol> {
+    let mut symbols = Vec::new();
+    let mut block: Vec<(usize, &str)> = Vec::new();
+
+    for (line_num, line) in document.lines().enumerate() {
+        if line.trim() == "###" {
+            symbols.extend(block_symbol(&block));
+            block.clear();
+        } else {
+            block.push((line_num, line));
+        }
+    }
+    symbols.extend(block_symbol(&block));
+
+    symbols
+}
+
+/// Builds the symbol for one request block, if it has a request line.
+fn block_symbol(block: &[(usize, &str)]) -> Option<RequestSymbol> {
+    let request_index = block.iter().position(|(_, line)| {
+        let trimmed = line.trim();
+        !(trimmed.is_empty()
+            || trimmed.starts_with('#')
+            || trimmed.starts_with("//")
+            || trimmed.starts_with('@'))
+    })?;
+    let (request_line_num, request_line) = block[request_index];
+
+    let mut parts = request_line.split_whitespace();
+    let method = HttpMethod::from_str(parts.next()?)?;
+    let url = parts.next()?;
+    let request_label = format!("{} {}", method.as_str(), url);
+
+    let (name, detail) = match parse_name_directive(&block[..request_index]) {
+        Some(name) => (name, Some(request_label)),
+        None => (request_label, None),
+    };
+
+    let mut non_blank = block.iter().filter(|(_, line)| !line.trim().is_empty());
+    let (first_line, _) = non_blank.next()?;
+    let (last_line, last_text) = non_blank.next_back().unwrap_or(&block[request_index]);
+
+    Some(RequestSymbol {
+        name,
+        detail,
+        range: Range::new(
+            Position::new(*first_line, 0),
+            Position::new(*last_line, last_text.len()),
+        ),
+        selection_range: Range::at_line(request_line_num, 0, request_line.len()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_provide_document_symbols_multiple_requests() {
+        let doc = r#"@baseUrl = https://api.example.com
+
+###
+
+# @name listUsers
+GET https://api.example.com/users
+
+###
+
+POST https://api.example.com/users HTTP/1.1
+Content-Type: application/json
+
+{"name": "John"}
+
+###
+"#;
+        let symbols = provide_document_symbols(doc);
+
+        assert_eq!(symbols.len(), 2);
+
+        assert_eq!(symbols[0].name, "listUsers");
+        assert_eq!(
+            symbols[0].detail.as_deref(),
+            Some("GET https://api.example.com/users")
+        );
+        assert_eq!(symbols[0].range.start, Position::new(4, 0));
+        assert_eq!(symbols[0].range.end, Position::new(5, 33));
+        assert_eq!(symbols[0].selection_range, Range::at_line(5, 0, 33));
+
+        assert_eq!(symbols[1].name, "POST https://api.example.com/users");
+        assert_eq!(symbols[1].detail, None);
+        assert_eq!(symbols[1].range.start, Position::new(9, 0));
+        assert_eq!(symbols[1].range.end, Position::new(12, 16));
+        assert_eq!(symbols[1].selection_range.start.line, 9);
+    }
+
+    #[test]
+    fn test_provide_document_symbols_skips_blocks_without_request() {
+        let doc = "# just notes\n\n###\nnot a request line\n###\nget {{baseUrl}}/health";
+        let symbols = provide_document_symbols(doc);
+
+        assert_eq!(symbols.len(), 1);
+        assert_eq!(symbols[0].name, "GET {{baseUrl}}/health");
+        assert_eq!(symbols[0].range.start.line, 5);
+    }
+
+    #[test]
+    fn test_provide_document_symbols_empty_document() {
+        assert!(provide_document_symbols("").is_empty());
+    }
+}
@@ -82,8 +82,9 @@ pub mod parser;
 pub mod ui;
 pub mod variables;
 
+use executor::matrix::{parse_matrix_directives, run_matrix, MatrixResult};
 use executor::{execute_request, ExecutionConfig};
-use formatter::format_response;
+use formatter::{format_response, format_response_with_filter};
 use parser::parse_request;
 
 /// REST Client extension for Zed.
@@ -98,12 +99,28 @@ struct RestClientExtension {
     /// Session for managing environment state across requests
     /// Wrapped in Arc<Mutex> for thread-safe mutable access
     environment_session: Arc<Mutex<Option<environment::EnvironmentSession>>>,
+
+    /// The most recently sent request, as parsed (before variable resolution)
+    /// so that `/resend` can re-resolve variables against the current environment
+    last_request: Arc<Mutex<Option<models::HttpRequest>>>,
+
+    /// Responses sent in this session, kept as virtual tabs for `/tabs`,
+    /// `/tab`, and `/close-tab`
+    response_tabs: Arc<Mutex<ui::LayoutManager>>,
+
+    /// The error from the most recent request, if it failed, for `/explain-error`
+    last_error: Arc<Mutex<Option<executor::RequestError>>>,
 }
 
 impl zed::Extension for RestClientExtension {
     fn new() -> Self {
         Self {
             environment_session: Arc::new(Mutex::new(None)),
+            last_request: Arc::new(Mutex::new(None)),
+            response_tabs: Arc::new(Mutex::new(ui::LayoutManager::new(
+                ui::LayoutConfig::from_settings(),
+            ))),
+            last_error: Arc::new(Mutex::new(None)),
         }
     }
 
@@ -145,9 +162,17 @@ impl zed::Extension for RestClientExtension {
     fn language_server_initialization_options(
         &mut self,
         _language_server_id: &zed::LanguageServerId,
-        _worktree: &zed::Worktree,
+        worktree: &zed::Worktree,
+    ) -> zed::Result<Option<zed::serde_json::Value>> {
+        Ok(worktree_settings(worktree))
+    }
+
+    fn language_server_workspace_configuration(
+        &mut self,
+        _language_server_id: &zed::LanguageServerId,
+        worktree: &zed::Worktree,
     ) -> zed::Result<Option<zed::serde_json::Value>> {
-        Ok(None)
+        Ok(worktree_settings(worktree))
     }
 
     fn run_slash_command(
@@ -156,11 +181,36 @@ impl zed::Extension for RestClientExtension {
         args: Vec<String>,
         worktree: Option<&zed::Worktree>,
     ) -> Result<zed::SlashCommandOutput, String> {
+        // Pick up settings changed since the last command
+        if let Some(worktree) = worktree {
+            let _ = config::load_config(worktree_settings(worktree));
+        }
+
         match command.name.as_str() {
             "switch-environment" => self.handle_switch_environment(args, worktree),
+            "reload-environments" => self.handle_reload_environments(worktree),
+            "init-environment" => self.handle_init_environment(worktree),
+            "check-variables" => self.handle_check_variables(args, worktree),
+            "scaffold-env" => self.handle_scaffold_env(args),
             "generate-code" => self.handle_generate_code(args, worktree),
             "paste-curl" => self.handle_paste_curl(args),
             "copy-as-curl" => self.handle_copy_as_curl(args),
+            "resend" => self.handle_resend(worktree),
+            "run-all" => self.handle_run_all(args, worktree),
+            "run-from-here" => self.handle_run_from_here(args, worktree),
+            "copy-response-body" => self.handle_copy_response_body(args),
+            "fold-response" => self.handle_fold_response(args),
+            "import-history" => self.handle_import_history(args, worktree),
+            "history-run" => self.handle_history_run(args),
+            "graphql" => self.handle_graphql(args),
+            "graphql-introspect" => self.handle_graphql_introspect(args),
+            "assert-matches" => self.handle_assert_matches(args, worktree),
+            "redact" => self.handle_redact(args),
+            "tabs" => self.handle_tabs(),
+            "tab" => self.handle_tab(args),
+            "close-tab" => self.handle_close_tab(args),
+            "toggle-headers" => self.handle_toggle_headers(),
+            "explain-error" => self.handle_explain_error(),
             "send-request" => {
                 // Argument patterns supported:
                 // 1 arg: selection-only (HTTP request text)
@@ -200,17 +250,61 @@ impl zed::Extension for RestClientExtension {
                     .map(|(i, s)| (i, s.as_str()))
                     .collect();
                 let file_path = std::path::PathBuf::from("slash-command");
-                let request = parse_request(&indexed_lines, 0, &file_path)
+                let parsed_request = parse_request(&indexed_lines, 0, &file_path)
                     .map_err(|e| format!("Failed to parse request: {}", e))?;
 
-                // Execute the request
+                // Remember the unresolved request so /resend can re-resolve it later
+                if let Ok(mut last_request) = self.last_request.lock() {
+                    *last_request = Some(parsed_request.clone());
+                }
+
+                // Resolve variables against the active environment
+                let request = commands::resolve_request_variables(
+                    &parsed_request,
+                    self.get_environment_session().as_ref(),
+                    &workspace_path(worktree),
+                )
+                .map_err(|e| format!("Failed to resolve variables: {}", e))?;
+
                 let config = ExecutionConfig::default();
-                let response = execute_request(&request, &config)
-                    .map_err(|e| format!("Failed to execute request: {}", e))?;
+
+                // A `# @matrix` directive sends one request per header value
+                let directives = parse_matrix_directives(&indexed_lines);
+                if !directives.is_empty() {
+                    let results = run_matrix(&request, &directives, |request| {
+                        execute_request(request, &config)
+                    });
+                    return Ok(format_matrix_output(&request, &results));
+                }
+
+                // Execute the request, keeping any error for /explain-error
+                let result = execute_request(&request, &config);
+                let response = match result {
+                    Ok(response) => {
+                        self.set_last_error(None);
+                        response
+                    }
+                    Err(e) => {
+                        let message = format!(
+                            "Failed to execute request: {}. Run /explain-error for help.",
+                            e
+                        );
+                        self.set_last_error(Some(e));
+                        return Err(message);
+                    }
+                };
 
                 // Format the response
-                let formatted = format_response(&response);
-                let output_text = formatted.to_display_string();
+                let formatted = format_response_with_filter(&response, request.filter.as_deref());
+                let mut output_text = formatted.to_display_string();
+
+                // Keep the response as a tab so /tab can show it again
+                if let Ok(mut tabs) = self.response_tabs.lock() {
+                    tabs.record_response(formatted, request.clone(), &request.id);
+                    if let Some(note) = tabs.eviction_note() {
+                        output_text.push_str(&format!("\n{}\n", note));
+                    }
+                }
 
                 // Return as slash command output
                 Ok(zed::SlashCommandOutput {
@@ -238,11 +332,7 @@ impl RestClientExtension {
         worktree: Option<&zed::Worktree>,
     ) -> Result<zed::SlashCommandOutput, String> {
         // Get workspace path from worktree
-        let workspace_path = worktree
-            .map(|w| std::path::PathBuf::from(w.root_path()))
-            .unwrap_or_else(|| {
-                std::env::current_dir().unwrap_or_else(|_| std::path::PathBuf::from("."))
-            });
+        let workspace_path = workspace_path(worktree);
 
         // Try to load or get existing environment session
         let mut session_lock = self
@@ -265,7 +355,8 @@ impl RestClientExtension {
                         }],
                         text: format!(
                             "No environment configuration found.\n\n\
-                            To use environments, create a `.http-client-env.json` file in your workspace:\n\n\
+                            Run /init-environment to create a starter file, or create a\n\
+                            `.http-client-env.json` file in your workspace:\n\n\
                             ```json\n{{\n  \"$shared\": {{\n    \"apiVersion\": \"v1\"\n  }},\n  \
                             \"dev\": {{\n    \"baseUrl\": \"http://localhost:3000\"\n  }},\n  \
                             \"production\": {{\n    \"baseUrl\": \"https://api.example.com\"\n  }},\n  \
@@ -353,6 +444,626 @@ impl RestClientExtension {
         }
     }
 
+    /// Handles the reload-environments slash command
+    ///
+    /// Discards the cached environment session and reloads it from the
+    /// workspace, keeping the active environment if it still exists.
+    /// Usage: /reload-environments
+    fn handle_reload_environments(
+        &self,
+        worktree: Option<&zed::Worktree>,
+    ) -> Result<zed::SlashCommandOutput, String> {
+        let mut session_lock = self
+            .environment_session
+            .lock()
+            .map_err(|e| format!("Failed to acquire session lock: {}", e))?;
+
+        let previous_active = session_lock
+            .as_ref()
+            .and_then(|session| session.get_active_environment_name());
+
+        // Keep the current session if the reload fails
+        let result =
+            commands::reload_environments_command(&workspace_path(worktree), previous_active)?;
+        *session_lock = Some(result.session);
+
+        Ok(zed::SlashCommandOutput {
+            sections: vec![zed::SlashCommandOutputSection {
+                range: (0..result.message.len()).into(),
+                label: "Environments Reloaded".to_string(),
+            }],
+            text: result.message,
+        })
+    }
+
+    /// Handles the init-environment slash command
+    ///
+    /// Writes a starter environment file to the workspace root, unless one
+    /// already exists.
+    /// Usage: /init-environment
+    fn handle_init_environment(
+        &self,
+        worktree: Option<&zed::Worktree>,
+    ) -> Result<zed::SlashCommandOutput, String> {
+        let output_text = commands::init_environment_command(&workspace_path(worktree))?;
+
+        // Load the new file the next time environments are used
+        if let Ok(mut session) = self.environment_session.lock() {
+            *session = None;
+        }
+
+        Ok(zed::SlashCommandOutput {
+            sections: vec![zed::SlashCommandOutputSection {
+                range: (0..output_text.len()).into(),
+                label: "Environment File Created".to_string(),
+            }],
+            text: output_text,
+        })
+    }
+
+    /// Handles the check-variables slash command
+    ///
+    /// Reports, for each environment, the variables used in the file that
+    /// would not resolve.
+    /// Usage: /check-variables (with the .http file content as argument)
+    fn handle_check_variables(
+        &self,
+        args: Vec<String>,
+        worktree: Option<&zed::Worktree>,
+    ) -> Result<zed::SlashCommandOutput, String> {
+        if args.is_empty() {
+            return Err(
+                "No file content provided. Use /check-variables with the .http file content"
+                    .to_string(),
+            );
+        }
+
+        // Arguments arrive split on whitespace; any line breaks left inside
+        // them are kept
+        let file_content = args.join(" ");
+
+        let environments = match self
+            .get_environment_session()
+            .and_then(|session| session.get_environments())
+        {
+            Some(envs) => envs,
+            None => environment::load_environments(&workspace_path(worktree))
+                .map_err(|e| format!("Failed to load environments: {}", e))?,
+        };
+
+        let result = commands::check_variables_command(&file_content, &environments);
+        let label = if result.all_resolved() {
+            "All Variables Resolved".to_string()
+        } else {
+            "Missing Variables".to_string()
+        };
+
+        Ok(zed::SlashCommandOutput {
+            sections: vec![zed::SlashCommandOutputSection {
+                range: (0..result.message.len()).into(),
+                label,
+            }],
+            text: result.message,
+        })
+    }
+
+    /// Handles the scaffold-env slash command
+    ///
+    /// Outputs a `.http-client-env.json` skeleton listing the variables the
+    /// file needs from an environment.
+    /// Usage: /scaffold-env (with the .http file content as argument)
+    fn handle_scaffold_env(&self, args: Vec<String>) -> Result<zed::SlashCommandOutput, String> {
+        if args.is_empty() {
+            return Err(
+                "No file content provided. Use /scaffold-env with the .http file content"
+                    .to_string(),
+            );
+        }
+
+        let result = commands::scaffold_environment_command(&args.join(" "));
+        let label = if result.skeleton.is_some() {
+            format!(
+                "Environment Skeleton ({} variables)",
+                result.variables.len()
+            )
+        } else {
+            "No Environment Variables".to_string()
+        };
+
+        Ok(zed::SlashCommandOutput {
+            sections: vec![zed::SlashCommandOutputSection {
+                range: (0..result.message.len()).into(),
+                label,
+            }],
+            text: result.message,
+        })
+    }
+
+    /// Handles the resend slash command
+    ///
+    /// Re-executes the most recently sent request, re-resolving its variables
+    /// against the currently active environment.
+    /// Usage: /resend
+    fn handle_resend(
+        &self,
+        worktree: Option<&zed::Worktree>,
+    ) -> Result<zed::SlashCommandOutput, String> {
+        let last_request = self
+            .last_request
+            .lock()
+            .map_err(|e| format!("Failed to acquire request lock: {}", e))?
+            .clone();
+
+        let result = commands::resend_last_request_command(
+            last_request,
+            self.get_environment_session(),
+            &workspace_path(worktree),
+        )?;
+
+        let output_text = format!("{}\n\n{}", result.status_message, result.formatted_response);
+
+        Ok(zed::SlashCommandOutput {
+            sections: vec![zed::SlashCommandOutputSection {
+                range: (0..output_text.len()).into(),
+                label: format!("Resent {} {}", result.request.method, result.request.url),
+            }],
+            text: output_text,
+        })
+    }
+
+    /// Handles the run-all slash command
+    ///
+    /// Sends every request in the file concurrently and shows a summary of
+    /// each request's status and duration.
+    /// Usage: /run-all (with the .http file content as argument)
+    fn handle_run_all(
+        &self,
+        args: Vec<String>,
+        worktree: Option<&zed::Worktree>,
+    ) -> Result<zed::SlashCommandOutput, String> {
+        if args.is_empty() {
+            return Err(
+                "No file content provided. Use /run-all with the .http file content".to_string(),
+            );
+        }
+
+        let file_content = args.join("\n");
+        let output_text = commands::run_all_command(
+            &file_content,
+            self.get_environment_session().as_ref(),
+            &workspace_path(worktree),
+        )?;
+
+        Ok(zed::SlashCommandOutput {
+            sections: vec![zed::SlashCommandOutputSection {
+                range: (0..output_text.len()).into(),
+                label: "Run All Requests".to_string(),
+            }],
+            text: output_text,
+        })
+    }
+
+    /// Handles the run-from-here slash command
+    ///
+    /// Sends the request at the cursor and every request after it, in order,
+    /// with captures chained from one request to the next.
+    /// Usage: /run-from-here <file content> <cursor byte offset>
+    fn handle_run_from_here(
+        &self,
+        args: Vec<String>,
+        worktree: Option<&zed::Worktree>,
+    ) -> Result<zed::SlashCommandOutput, String> {
+        let (editor_text, cursor_position) = match args.as_slice() {
+            [editor_text, cursor, ..] => (
+                editor_text,
+                cursor
+                    .parse::<usize>()
+                    .map_err(|_| format!("Invalid cursor position: {}", cursor))?,
+            ),
+            _ => {
+                return Err("Usage: /run-from-here <file content> <cursor byte offset>".to_string())
+            }
+        };
+
+        let output_text = commands::run_from_here_command(
+            editor_text,
+            cursor_position,
+            self.get_environment_session().as_ref(),
+            &workspace_path(worktree),
+        )?;
+
+        Ok(zed::SlashCommandOutput {
+            sections: vec![zed::SlashCommandOutputSection {
+                range: (0..output_text.len()).into(),
+                label: "Run From Here".to_string(),
+            }],
+            text: output_text,
+        })
+    }
+
+    /// Handles the copy-response-body slash command
+    ///
+    /// Returns the response body of a history entry for copying to the
+    /// clipboard. Bodies over the configured size limit are not returned.
+    /// Usage: /copy-response-body <history-id> [--raw]
+    fn handle_copy_response_body(
+        &self,
+        args: Vec<String>,
+    ) -> Result<zed::SlashCommandOutput, String> {
+        let raw = args.iter().any(|arg| arg == "--raw");
+        let entry_id = args
+            .iter()
+            .find(|arg| !arg.starts_with("--"))
+            .ok_or_else(|| {
+                "No history ID provided. Usage: /copy-response-body <history-id> [--raw]"
+                    .to_string()
+            })?;
+
+        let result = commands::copy_response_body_command(entry_id, raw)?;
+
+        let (output_text, label) = if result.success {
+            (result.content, result.message)
+        } else {
+            (result.message, "Response Body Not Copied".to_string())
+        };
+
+        Ok(zed::SlashCommandOutput {
+            sections: vec![zed::SlashCommandOutputSection {
+                range: (0..output_text.len()).into(),
+                label,
+            }],
+            text: output_text,
+        })
+    }
+
+    /// Handles the fold-response slash command
+    ///
+    /// Folds the response of a history entry. With `keys`, only top-level
+    /// JSON keys are shown with a summary of each value; otherwise large
+    /// sections are collapsed.
+    /// Usage: /fold-response <history-id> [keys]
+    fn handle_fold_response(&self, args: Vec<String>) -> Result<zed::SlashCommandOutput, String> {
+        use crate::ui::response_actions::FoldMode;
+
+        let Some((entry_id, rest)) = args.split_first() else {
+            return Err(
+                "No history ID provided. Usage: /fold-response <history-id> [keys]".to_string(),
+            );
+        };
+        let mode = match rest.first().map(String::as_str) {
+            None => FoldMode::Sections,
+            Some("keys") => FoldMode::TopLevelKeys,
+            Some(other) => {
+                return Err(format!(
+                    "Unknown fold mode '{}'. Usage: /fold-response <history-id> [keys]",
+                    other
+                ))
+            }
+        };
+
+        let result = commands::fold_history_response_command(entry_id, mode)?;
+        let label = match mode {
+            FoldMode::Sections => format!("Folded Response ({} sections)", result.sections_folded),
+            FoldMode::TopLevelKeys => "Folded Response (top-level keys)".to_string(),
+        };
+        let output_text = result.folded_response;
+
+        Ok(zed::SlashCommandOutput {
+            sections: vec![zed::SlashCommandOutputSection {
+                range: (0..output_text.len()).into(),
+                label,
+            }],
+            text: output_text,
+        })
+    }
+
+    /// Handles the tabs slash command
+    ///
+    /// Lists the responses kept as tabs in this session.
+    /// Usage: /tabs
+    fn handle_tabs(&self) -> Result<zed::SlashCommandOutput, String> {
+        let output_text = self
+            .response_tabs
+            .lock()
+            .map_err(|e| format!("Failed to acquire tabs lock: {}", e))?
+            .list_open_tabs();
+
+        Ok(zed::SlashCommandOutput {
+            sections: vec![zed::SlashCommandOutputSection {
+                range: (0..output_text.len()).into(),
+                label: "Response Tabs".to_string(),
+            }],
+            text: output_text,
+        })
+    }
+
+    /// Handles the tab slash command
+    ///
+    /// Shows the response of a tab again and makes it the active one.
+    /// Usage: /tab <tab-id> (a unique prefix of the ID is enough)
+    fn handle_tab(&self, args: Vec<String>) -> Result<zed::SlashCommandOutput, String> {
+        let Some(tab_id) = args.first().map(|id| id.trim()) else {
+            return Err("No tab ID provided. Usage: /tab <tab-id>".to_string());
+        };
+
+        let output_text = self
+            .response_tabs
+            .lock()
+            .map_err(|e| format!("Failed to acquire tabs lock: {}", e))?
+            .switch_tab(tab_id);
+
+        Ok(zed::SlashCommandOutput {
+            sections: vec![zed::SlashCommandOutputSection {
+                range: (0..output_text.len()).into(),
+                label: format!("Response Tab {}", tab_id),
+            }],
+            text: output_text,
+        })
+    }
+
+    /// Handles the close-tab slash command
+    ///
+    /// Closes a response tab.
+    /// Usage: /close-tab <tab-id> (a unique prefix of the ID is enough)
+    fn handle_close_tab(&self, args: Vec<String>) -> Result<zed::SlashCommandOutput, String> {
+        let Some(tab_id) = args.first().map(|id| id.trim()) else {
+            return Err("No tab ID provided. Usage: /close-tab <tab-id>".to_string());
+        };
+
+        let output_text = self
+            .response_tabs
+            .lock()
+            .map_err(|e| format!("Failed to acquire tabs lock: {}", e))?
+            .close_tab(tab_id);
+
+        Ok(zed::SlashCommandOutput {
+            sections: vec![zed::SlashCommandOutputSection {
+                range: (0..output_text.len()).into(),
+                label: "Close Response Tab".to_string(),
+            }],
+            text: output_text,
+        })
+    }
+
+    /// Handles the toggle-headers slash command
+    ///
+    /// Expands or collapses the headers hidden by `compactHeaders` and shows
+    /// the active tab again.
+    /// Usage: /toggle-headers
+    fn handle_toggle_headers(&self) -> Result<zed::SlashCommandOutput, String> {
+        let output_text = self
+            .response_tabs
+            .lock()
+            .map_err(|e| format!("Failed to acquire tabs lock: {}", e))?
+            .toggle_headers();
+
+        Ok(zed::SlashCommandOutput {
+            sections: vec![zed::SlashCommandOutputSection {
+                range: (0..output_text.len()).into(),
+                label: "Response Headers".to_string(),
+            }],
+            text: output_text,
+        })
+    }
+
+    /// Handles the graphql slash command
+    ///
+    /// Builds a ready-to-send GraphQL request block from an endpoint and the
+    /// selected query. The query is validated before the block is built.
+    /// Usage: /graphql <endpoint> (with a GraphQL query in selection)
+    fn handle_graphql(&self, args: Vec<String>) -> Result<zed::SlashCommandOutput, String> {
+        let Some((endpoint, query)) = args.split_first() else {
+            return Err(
+                "No endpoint provided. Usage: /graphql <endpoint> (with a GraphQL query selected)"
+                    .to_string(),
+            );
+        };
+        if query.is_empty() {
+            return Err(
+                "No GraphQL query provided. Please select a query and use /graphql <endpoint>"
+                    .to_string(),
+            );
+        }
+
+        let output_text = commands::graphql_scaffold_command(endpoint, &query.join("\n"))?;
+
+        Ok(zed::SlashCommandOutput {
+            sections: vec![zed::SlashCommandOutputSection {
+                range: (0..output_text.len()).into(),
+                label: format!("GraphQL Request ({})", endpoint),
+            }],
+            text: output_text,
+        })
+    }
+
+    /// Handles the graphql-introspect slash command
+    ///
+    /// Generates the introspection query for an endpoint. With `--run`, the
+    /// query is executed and the schema's types and fields are summarized.
+    /// Usage: /graphql-introspect <endpoint> [--run]
+    fn handle_graphql_introspect(
+        &self,
+        args: Vec<String>,
+    ) -> Result<zed::SlashCommandOutput, String> {
+        let Some(endpoint) = args.first() else {
+            return Err(
+                "No endpoint provided. Usage: /graphql-introspect <endpoint> [--run]".to_string(),
+            );
+        };
+        let run = args[1..].iter().any(|arg| arg == "--run");
+
+        let output_text = commands::graphql_introspect_command(endpoint, run)?;
+        let label = if run {
+            format!("GraphQL Schema ({})", endpoint)
+        } else {
+            format!("GraphQL Introspection ({})", endpoint)
+        };
+
+        Ok(zed::SlashCommandOutput {
+            sections: vec![zed::SlashCommandOutputSection {
+                range: (0..output_text.len()).into(),
+                label,
+            }],
+            text: output_text,
+        })
+    }
+
+    /// Remembers the outcome of the last request for /explain-error
+    fn set_last_error(&self, error: Option<executor::RequestError>) {
+        if let Ok(mut last_error) = self.last_error.lock() {
+            *last_error = error;
+        }
+    }
+
+    /// Handles the explain-error slash command
+    ///
+    /// Explains why the last request sent with /send-request failed and
+    /// suggests fixes.
+    /// Usage: /explain-error
+    fn handle_explain_error(&self) -> Result<zed::SlashCommandOutput, String> {
+        let output_text = commands::explain_error_command(
+            self.last_error
+                .lock()
+                .map_err(|e| format!("Failed to acquire error lock: {}", e))?
+                .as_ref(),
+        );
+
+        Ok(zed::SlashCommandOutput {
+            sections: vec![zed::SlashCommandOutputSection {
+                range: (0..output_text.len()).into(),
+                label: "Explain Error".to_string(),
+            }],
+            text: output_text,
+        })
+    }
+
+    /// Handles the history-run slash command
+    ///
+    /// Re-runs a request from history and shows the fresh response.
+    /// Usage: /history-run <n>, where 1 is the most recent request
+    fn handle_history_run(&self, args: Vec<String>) -> Result<zed::SlashCommandOutput, String> {
+        let Some(index) = args.first() else {
+            return Err("No history index provided. Usage: /history-run <n>".to_string());
+        };
+        let index: usize = index.parse().map_err(|_| {
+            format!(
+                "Invalid history index '{}'. Usage: /history-run <n>, where 1 is the most recent request",
+                index
+            )
+        })?;
+
+        let output_text = commands::history_run_command(index)?;
+
+        Ok(zed::SlashCommandOutput {
+            sections: vec![zed::SlashCommandOutputSection {
+                range: (0..output_text.len()).into(),
+                label: format!("History #{}", index),
+            }],
+            text: output_text,
+        })
+    }
+
+    /// Handles the import-history slash command
+    ///
+    /// Imports the requests captured in a browser-exported `.har` file into
+    /// history so they can be viewed and re-run.
+    /// Usage: /import-history <path>
+    fn handle_import_history(
+        &self,
+        args: Vec<String>,
+        worktree: Option<&zed::Worktree>,
+    ) -> Result<zed::SlashCommandOutput, String> {
+        let Some(har_path) = args.first() else {
+            return Err("No HAR file provided. Usage: /import-history <path>".to_string());
+        };
+
+        let output_text = commands::import_history_command(har_path, &workspace_path(worktree))?;
+
+        Ok(zed::SlashCommandOutput {
+            sections: vec![zed::SlashCommandOutputSection {
+                range: (0..output_text.len()).into(),
+                label: format!("Imported History ({})", har_path),
+            }],
+            text: output_text,
+        })
+    }
+
+    /// Handles the assert-matches slash command
+    ///
+    /// Sends the selected request and compares its response body against a
+    /// fixture file, reporting a pass or a diff of the differences.
+    /// Usage: /assert-matches <fixture-path> (with a request selected)
+    fn handle_assert_matches(
+        &self,
+        args: Vec<String>,
+        worktree: Option<&zed::Worktree>,
+    ) -> Result<zed::SlashCommandOutput, String> {
+        let Some((fixture_path, request)) = args.split_first() else {
+            return Err(
+                "No fixture provided. Usage: /assert-matches <fixture-path> (with a request selected)"
+                    .to_string(),
+            );
+        };
+        if request.is_empty() {
+            return Err(
+                "No request provided. Please select a request and use /assert-matches <fixture-path>"
+                    .to_string(),
+            );
+        }
+
+        let output_text = commands::assert_matches_command(
+            &request.join("\n"),
+            fixture_path,
+            self.get_environment_session().as_ref(),
+            &workspace_path(worktree),
+        )?;
+
+        Ok(zed::SlashCommandOutput {
+            sections: vec![zed::SlashCommandOutputSection {
+                range: (0..output_text.len()).into(),
+                label: format!("Assert Matches ({})", fixture_path),
+            }],
+            text: output_text,
+        })
+    }
+
+    /// Handles the redact slash command
+    ///
+    /// Replaces the given fields of the selected JSON response with
+    /// `"[REDACTED]"`, for sharing responses without their secrets.
+    /// Usage: /redact <field1,field2,...> (with a JSON response selected)
+    fn handle_redact(&self, args: Vec<String>) -> Result<zed::SlashCommandOutput, String> {
+        // Arguments arrive split on whitespace, so `a, b` is two arguments;
+        // the field list runs up to the first argument that opens the JSON
+        let json_start = args
+            .iter()
+            .position(|arg| arg.starts_with('{') || arg.starts_with('['))
+            .unwrap_or(args.len());
+        let fields = args[..json_start].join(" ");
+        if fields.trim().is_empty() {
+            return Err(
+                "No fields provided. Usage: /redact <field1,field2,...> (with a JSON response selected)"
+                    .to_string(),
+            );
+        }
+        if json_start == args.len() {
+            return Err(
+                "No JSON provided. Please select a JSON response and use /redact <field1,field2,...>"
+                    .to_string(),
+            );
+        }
+
+        let output_text = commands::redact_command(&fields, &args[json_start..].join(" "))?;
+
+        Ok(zed::SlashCommandOutput {
+            sections: vec![zed::SlashCommandOutputSection {
+                range: (0..output_text.len()).into(),
+                label: format!("Redacted Response ({})", fields),
+            }],
+            text: output_text,
+        })
+    }
+
     /// Handles the generate-code slash command
     ///
     /// Generates executable code from an HTTP request in the specified language.
@@ -451,8 +1162,14 @@ impl RestClientExtension {
     /// Handles the copy-as-curl slash command
     ///
     /// Converts an HTTP request to a cURL command.
-    /// Usage: /copy-as-curl (with HTTP request text in selection)
+    /// Usage: /copy-as-curl [--get] (with HTTP request text in selection)
     fn handle_copy_as_curl(&self, args: Vec<String>) -> Result<zed::SlashCommandOutput, String> {
+        // --get sends a GET's query string as -G data
+        let (get, args) = match args.split_first() {
+            Some((first, rest)) if first == "--get" || first == "-G" => (true, rest.to_vec()),
+            _ => (false, args),
+        };
+
         if args.is_empty() {
             return Err(
                 "No HTTP request provided. Please select an HTTP request and use /copy-as-curl"
@@ -475,7 +1192,11 @@ impl RestClientExtension {
             .map_err(|e| format!("Failed to parse request: {}", e))?;
 
         // Generate cURL command
-        let result = curl::ui::copy_as_curl_command(&request);
+        let options = curl::CurlOptions {
+            get,
+            ..Default::default()
+        };
+        let result = curl::ui::copy_as_curl_command_with_options(&request, &options);
 
         if !result.success {
             return Err(result.message);
@@ -501,4 +1222,58 @@ impl RestClientExtension {
     }
 }
 
+/// Formats matrix results as one output section per varied header set.
+fn format_matrix_output(
+    request: &models::request::HttpRequest,
+    results: &[MatrixResult],
+) -> zed::SlashCommandOutput {
+    let mut text = String::new();
+    let mut sections = Vec::new();
+
+    for result in results {
+        let start = text.len();
+        text.push_str(&format!("# {}\n\n", result.label));
+        match &result.result {
+            Ok(response) => text.push_str(&format_response(response).to_display_string()),
+            Err(e) => text.push_str(&format!("Failed to execute request: {}", e)),
+        }
+        text.push_str("\n\n");
+
+        sections.push(zed::SlashCommandOutputSection {
+            range: (start..text.len()).into(),
+            label: format!("{} {} [{}]", request.method, request.url, result.label),
+        });
+    }
+
+    zed::SlashCommandOutput { sections, text }
+}
+
+/// Returns the user's REST Client settings for a worktree, wrapped in the
+/// `"rest-client"` key that [`config::load_config`] reads.
+///
+/// Zed has no settings section for extensions, so they are read from the
+/// language server's entry: `"lsp": { "rest-client-lsp": { "settings": {...} } }`.
+/// The language server receives these same settings, so settings that only
+/// the user may change are dropped here when the project sets them.
+fn worktree_settings(worktree: &zed::Worktree) -> Option<zed::serde_json::Value> {
+    let mut settings = zed::settings::LspSettings::for_worktree("rest-client-lsp", worktree)
+        .ok()?
+        .settings?;
+    let project_settings = worktree
+        .read_text_file(".zed/settings.json")
+        .unwrap_or_default();
+    config::strip_user_only_settings(&mut settings, &project_settings);
+    Some(zed::serde_json::json!({ "rest-client": settings }))
+}
+
+/// Returns the workspace root for the given worktree, falling back to the
+/// current directory when no worktree is available.
+fn workspace_path(worktree: Option<&zed::Worktree>) -> std::path::PathBuf {
+    worktree
+        .map(|w| std::path::PathBuf::from(w.root_path()))
+        .unwrap_or_else(|| {
+            std::env::current_dir().unwrap_or_else(|_| std::path::PathBuf::from("."))
+        })
+}
+
 zed::register_extension!(RestClientExtension);
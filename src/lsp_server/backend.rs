@@ -12,20 +12,26 @@ use tower_lsp::lsp_types::{
     CompletionItem as LspCompletionItem, CompletionItemKind, CompletionOptions, CompletionParams,
     CompletionResponse, Diagnostic as LspDiagnostic, DiagnosticOptions,
     DiagnosticRelatedInformation, DiagnosticServerCapabilities,
-    DiagnosticSeverity as LspDiagnosticSeverity, DidChangeTextDocumentParams,
-    DidCloseTextDocumentParams, DidOpenTextDocumentParams, DocumentDiagnosticParams,
-    DocumentDiagnosticReportResult, Documentation, ExecuteCommandParams,
-    FullDocumentDiagnosticReport, Hover as LspHover, HoverContents, HoverParams,
-    HoverProviderCapability, InitializeParams, InitializeResult, MarkupContent, MarkupKind,
-    MessageType, Position as LspPosition, Range as LspRange, RelatedFullDocumentDiagnosticReport,
-    ServerCapabilities, TextDocumentSyncCapability, TextDocumentSyncKind, Url,
+    DiagnosticSeverity as LspDiagnosticSeverity, DidChangeConfigurationParams,
+    DidChangeTextDocumentParams, DidCloseTextDocumentParams, DidOpenTextDocumentParams,
+    DocumentDiagnosticParams, DocumentDiagnosticReportResult, DocumentSymbol, DocumentSymbolParams,
+    DocumentSymbolResponse, Documentation, ExecuteCommandParams, FullDocumentDiagnosticReport,
+    GotoDefinitionParams, GotoDefinitionResponse, Hover as LspHover, HoverContents, HoverParams,
+    HoverProviderCapability, InitializeParams, InitializeResult, InlayHint as LspInlayHint,
+    InlayHintLabel, InlayHintParams, InlayHintTooltip, MarkupContent, MarkupKind, MessageType,
+    OneOf, Position as LspPosition, Range as LspRange, RelatedFullDocumentDiagnosticReport,
+    ServerCapabilities, SymbolKind, TextDocumentSyncCapability, TextDocumentSyncKind, Url,
 };
 use tower_lsp::{Client, LanguageServer};
 
 use super::document::DocumentManager;
 use super::executor_bridge::ExecutorBridge;
+use crate::config::load_config;
 use crate::environment::{load_environments, EnvError, EnvironmentSession, Environments};
-use crate::language_server::{codelens, completion, diagnostics, hover};
+use crate::executor::batch::format_batch_summary;
+use crate::language_server::{
+    codelens, completion, definition, diagnostics, hover, inlay_hints, symbols,
+};
 use crate::variables::VariableContext;
 
 /// LSP Backend for REST Client extension
@@ -138,6 +144,22 @@ impl Backend {
         self.log_message(MessageType::ERROR, message).await;
     }
 
+    /// Applies the client's settings to the global configuration
+    ///
+    /// The settings are expected under a `"rest-client"` key, as sent by the
+    /// extension in the initialization options and workspace configuration.
+    /// Settings that fail validation are logged and the previous
+    /// configuration is kept.
+    ///
+    /// # Arguments
+    ///
+    /// * `settings` - The settings sent by the client
+    pub async fn apply_settings(&self, settings: serde_json::Value) {
+        if let Err(e) = load_config(Some(settings)) {
+            self.log_warn(e).await;
+        }
+    }
+
     /// Loads environment configurations from workspace
     ///
     /// Searches for .http-client-env.json or http-client.env.json files
@@ -292,6 +314,59 @@ impl Backend {
             }
         }
     }
+
+    /// Handles the "rest-client.runAll" command
+    ///
+    /// Sends every request in the document given as the only argument and
+    /// shows a summary of the results. Cancelling the command cancels the
+    /// requests that haven't finished.
+    async fn execute_run_all(
+        &self,
+        params: ExecuteCommandParams,
+    ) -> Result<Option<serde_json::Value>> {
+        let uri_str = params
+            .arguments
+            .first()
+            .and_then(|value| value.as_str())
+            .ok_or_else(|| tower_lsp::jsonrpc::Error::invalid_params("Expected 1 argument: uri"))?;
+        let uri = Url::parse(uri_str).map_err(|e| {
+            tower_lsp::jsonrpc::Error::invalid_params(format!("Invalid URI: {}", e))
+        })?;
+
+        let document = match self.documents.get(&uri) {
+            Some(doc) => doc,
+            None => {
+                self.log_error(format!("Document not found: {}", uri)).await;
+                return Err(tower_lsp::jsonrpc::Error::invalid_params(format!(
+                    "Document not found: {}",
+                    uri
+                )));
+            }
+        };
+
+        let active_env = self.environment_session.get_active_environment();
+        match self
+            .executor
+            .execute_all_requests(&document, active_env)
+            .await
+        {
+            Ok(results) => {
+                let summary = format_batch_summary(&results);
+                self.log_info(format!("Ran all requests in {}", uri)).await;
+                self.client.show_message(MessageType::INFO, summary).await;
+                Ok(None)
+            }
+            Err(e) => {
+                let error_msg = format!("Failed to run requests: {}", e);
+                self.log_error(&error_msg).await;
+                self.client
+                    .show_message(MessageType::ERROR, &error_msg)
+                    .await;
+
+                Err(tower_lsp::jsonrpc::Error::internal_error())
+            }
+        }
+    }
 }
 
 #[tower_lsp::async_trait]
@@ -304,6 +379,7 @@ impl LanguageServer for Backend {
     /// - Completion provider (triggered by "{")
     /// - Hover provider
     /// - Diagnostic provider
+    /// - Document symbol provider
     async fn initialize(&self, params: InitializeParams) -> Result<InitializeResult> {
         self.log_info(format!(
             "Initializing REST Client Language Server (process ID: {:?})",
@@ -311,6 +387,11 @@ impl LanguageServer for Backend {
         ))
         .await;
 
+        // Apply the user's settings passed as initialization options
+        if let Some(options) = params.initialization_options {
+            self.apply_settings(options).await;
+        }
+
         // Extract workspace root from initialization parameters
         if let Some(root_uri) = params.root_uri {
             if let Ok(root_path) = root_uri.to_file_path() {
@@ -368,9 +449,21 @@ impl LanguageServer for Backend {
                 work_done_progress_options: Default::default(),
             })),
 
-            // Execute command provider - handle "rest-client.send" command
+            // Document symbol provider - list requests in the outline view
+            document_symbol_provider: Some(OneOf::Left(true)),
+
+            // Inlay hint provider - show resolved values after variables
+            inlay_hint_provider: Some(OneOf::Left(true)),
+
+            // Definition provider - jump from a variable to its environment file entry
+            definition_provider: Some(OneOf::Left(true)),
+
+            // Execute command provider - handle "rest-client.send" and "rest-client.runAll"
             execute_command_provider: Some(tower_lsp::lsp_types::ExecuteCommandOptions {
-                commands: vec!["rest-client.send".to_string()],
+                commands: vec![
+                    "rest-client.send".to_string(),
+                    "rest-client.runAll".to_string(),
+                ],
                 work_done_progress_options: Default::default(),
             }),
 
@@ -395,6 +488,13 @@ impl LanguageServer for Backend {
             .await;
     }
 
+    /// Called when the user's settings change
+    ///
+    /// Reloads the configuration from the new settings.
+    async fn did_change_configuration(&self, params: DidChangeConfigurationParams) {
+        self.apply_settings(params.settings).await;
+    }
+
     /// Shutdown the language server
     ///
     /// Called before the server exits to allow cleanup.
@@ -500,7 +600,7 @@ impl LanguageServer for Backend {
         let internal_lenses = codelens::provide_code_lens(&document);
 
         // Convert internal CodeLens to LSP CodeLens
-        let lsp_lenses: Vec<LspCodeLens> = internal_lenses
+        let mut lsp_lenses: Vec<LspCodeLens> = internal_lenses
             .into_iter()
             .map(|internal_lens| {
                 // Convert internal Range to LSP Range
@@ -533,6 +633,20 @@ impl LanguageServer for Backend {
             })
             .collect();
 
+        // A file with several requests also gets "Run All" above the first one
+        if lsp_lenses.len() > 1 {
+            let run_all = LspCodeLens {
+                range: lsp_lenses[0].range,
+                command: Some(LspCommand {
+                    title: "▶ Run All Requests".to_string(),
+                    command: "rest-client.runAll".to_string(),
+                    arguments: Some(vec![serde_json::json!(uri.to_string())]),
+                }),
+                data: None,
+            };
+            lsp_lenses.insert(0, run_all);
+        }
+
         self.log_info(format!(
             "Provided {} code lens(es) for: {}",
             lsp_lenses.len(),
@@ -607,6 +721,7 @@ impl LanguageServer for Backend {
                         Some(CompletionItemKind::VARIABLE)
                     }
                     completion::CompletionKind::FileVariable => Some(CompletionItemKind::VARIABLE),
+                    completion::CompletionKind::HttpMethod => Some(CompletionItemKind::KEYWORD),
                 };
 
                 // Create documentation from detail if available
@@ -711,6 +826,173 @@ impl LanguageServer for Backend {
         Ok(Some(lsp_hover))
     }
 
+    /// Handle textDocument/documentSymbol request
+    ///
+    /// Lists each request in the document for the outline and breadcrumb
+    /// views, named by its `@name` or by its method and URL.
+    async fn document_symbol(
+        &self,
+        params: DocumentSymbolParams,
+    ) -> Result<Option<DocumentSymbolResponse>> {
+        let uri = params.text_document.uri;
+
+        let document = match self.documents.get(&uri) {
+            Some(content) => content,
+            None => {
+                self.log_warn(format!("Document not found for document symbols: {}", uri))
+                    .await;
+                return Ok(None);
+            }
+        };
+
+        let to_lsp_range = |range: &diagnostics::Range| LspRange {
+            start: LspPosition {
+                line: range.start.line as u32,
+                character: range.start.character as u32,
+            },
+            end: LspPosition {
+                line: range.end.line as u32,
+                character: range.end.character as u32,
+            },
+        };
+
+        // `deprecated` is superseded by `tags`, but the struct still requires it
+        #[allow(deprecated)]
+        let lsp_symbols: Vec<DocumentSymbol> = symbols::provide_document_symbols(&document)
+            .into_iter()
+            .map(|symbol| DocumentSymbol {
+                name: symbol.name,
+                detail: symbol.detail,
+                kind: SymbolKind::FUNCTION,
+                tags: None,
+                deprecated: None,
+                range: to_lsp_range(&symbol.range),
+                selection_range: to_lsp_range(&symbol.selection_range),
+                children: None,
+            })
+            .collect();
+
+        self.log_info(format!(
+            "Provided {} document symbol(s) for: {}",
+            lsp_symbols.len(),
+            uri
+        ))
+        .await;
+
+        Ok(Some(DocumentSymbolResponse::Nested(lsp_symbols)))
+    }
+
+    /// Handle textDocument/definition request
+    ///
+    /// Jumps from the `{{variable}}` under the cursor to its key in the
+    /// environment file, preferring the active environment over `$shared`.
+    async fn goto_definition(
+        &self,
+        params: GotoDefinitionParams,
+    ) -> Result<Option<GotoDefinitionResponse>> {
+        let uri = params.text_document_position_params.text_document.uri;
+        let lsp_position = params.text_document_position_params.position;
+
+        let document = match self.documents.get(&uri) {
+            Some(content) => content,
+            None => {
+                self.log_warn(format!("Document not found for definition: {}", uri))
+                    .await;
+                return Ok(None);
+            }
+        };
+
+        let workspace_path = self
+            .workspace_root
+            .read()
+            .ok()
+            .and_then(|root| root.clone())
+            .unwrap_or_else(|| std::env::current_dir().unwrap_or_default());
+        let active_environment = self.environment_session.get_active_environment_name();
+
+        let position =
+            hover::Position::new(lsp_position.line as usize, lsp_position.character as usize);
+        let Some(location) = definition::provide_definition(
+            position,
+            &document,
+            &workspace_path,
+            active_environment.as_deref(),
+        ) else {
+            return Ok(None);
+        };
+
+        let Ok(target) = Url::from_file_path(&location.path) else {
+            self.log_warn(format!(
+                "Cannot convert environment file path to a URI: {}",
+                location.path.display()
+            ))
+            .await;
+            return Ok(None);
+        };
+
+        let start = LspPosition {
+            line: location.line as u32,
+            character: location.character as u32,
+        };
+        Ok(Some(GotoDefinitionResponse::Scalar(
+            tower_lsp::lsp_types::Location {
+                uri: target,
+                range: LspRange { start, end: start },
+            },
+        )))
+    }
+
+    /// Handle textDocument/inlayHint request
+    ///
+    /// Shows the resolved value after each `{{variable}}` in the requested
+    /// range, using the active environment.
+    async fn inlay_hint(&self, params: InlayHintParams) -> Result<Option<Vec<LspInlayHint>>> {
+        let uri = params.text_document.uri;
+
+        let document = match self.documents.get(&uri) {
+            Some(content) => content,
+            None => {
+                self.log_warn(format!("Document not found for inlay hints: {}", uri))
+                    .await;
+                return Ok(None);
+            }
+        };
+
+        let environments = self
+            .environment_session
+            .get_environments()
+            .unwrap_or_default();
+
+        let lines = params.range.start.line as usize..=params.range.end.line as usize;
+        let lsp_hints: Vec<LspInlayHint> =
+            inlay_hints::provide_inlay_hints(&document, &environments)
+                .into_iter()
+                .filter(|hint| lines.contains(&hint.position.line))
+                .map(|hint| LspInlayHint {
+                    position: LspPosition {
+                        line: hint.position.line as u32,
+                        character: hint.position.character as u32,
+                    },
+                    label: InlayHintLabel::String(hint.label),
+                    kind: None,
+                    text_edits: None,
+                    tooltip: hint.tooltip.map(InlayHintTooltip::String),
+                    padding_left: Some(false),
+                    padding_right: None,
+                    data: None,
+                })
+                .collect();
+
+        self.log_info(format!(
+            "Provided {} inlay hint(s) for: {}",
+            lsp_hints.len(),
+            uri
+        ))
+        .await;
+
+        Ok(Some(lsp_hints))
+    }
+
     async fn diagnostic(
         &self,
         params: DocumentDiagnosticParams,
@@ -762,8 +1044,13 @@ impl LanguageServer for Backend {
             shared_variables,
         );
 
-        // Call existing provide_diagnostics from language_server::diagnostics module
-        let internal_diagnostics = diagnostics::provide_diagnostics(&document, &variable_context);
+        // Call existing provide_file_diagnostics from language_server::diagnostics module
+        let file_path = uri.to_file_path().ok();
+        let internal_diagnostics = diagnostics::provide_file_diagnostics(
+            &document,
+            file_path.as_deref(),
+            &variable_context,
+        );
 
         // Convert internal Diagnostics to lsp_types::Diagnostic
         let lsp_diagnostics: Vec<LspDiagnostic> = internal_diagnostics
@@ -774,6 +1061,7 @@ impl LanguageServer for Backend {
                     diagnostics::DiagnosticSeverity::Error => LspDiagnosticSeverity::ERROR,
                     diagnostics::DiagnosticSeverity::Warning => LspDiagnosticSeverity::WARNING,
                     diagnostics::DiagnosticSeverity::Info => LspDiagnosticSeverity::INFORMATION,
+                    diagnostics::DiagnosticSeverity::Hint => LspDiagnosticSeverity::HINT,
                 };
 
                 // Convert internal range to LSP range
@@ -864,7 +1152,11 @@ impl LanguageServer for Backend {
         ))
         .await;
 
-        // Only handle "rest-client.send" command
+        if params.command == "rest-client.runAll" {
+            return self.execute_run_all(params).await;
+        }
+
+        // Otherwise only handle "rest-client.send" command
         if params.command != "rest-client.send" {
             self.log_warn(format!("Unknown command: {}", params.command))
                 .await;
@@ -928,12 +1220,27 @@ impl LanguageServer for Backend {
         // Get active environment (if any)
         let active_env = self.environment_session.get_active_environment();
 
+        // Lines of a streamed (chunked) body are logged as they arrive, before
+        // the full response is shown
+        let (line_tx, mut line_rx) = tokio::sync::mpsc::unbounded_channel::<String>();
+        let client = self.client.clone();
+        let forward_lines = tokio::spawn(async move {
+            while let Some(body_line) = line_rx.recv().await {
+                client.log_message(MessageType::LOG, body_line).await;
+            }
+        });
+
         // Execute request at specified line using native HTTP client (reqwest)
-        match self
+        let result = self
             .executor
-            .execute_request_at_line(&document, line, active_env)
-            .await
-        {
+            .execute_request_at_line_with_progress(&document, line, active_env, &mut |body_line| {
+                let _ = line_tx.send(body_line.to_string());
+            })
+            .await;
+        drop(line_tx);
+        let _ = forward_lines.await;
+
+        match result {
             Ok(response) => {
                 // Format response for display
                 let formatted = ExecutorBridge::format_response_pretty(&response);
@@ -954,6 +1261,9 @@ impl LanguageServer for Backend {
                     response.status_code, response.status_text
                 ))
                 .await;
+                for warning in &response.warnings {
+                    self.log_warn(warning).await;
+                }
 
                 Ok(None)
             }
@@ -1242,6 +1552,44 @@ DELETE https://api.example.com/users/1"#;
         assert_eq!(lenses[2].command.as_ref().unwrap().title, "▶ Send Request");
     }
 
+    #[tokio::test]
+    async fn test_code_lens_run_all_for_multiple_requests() {
+        let backend = Backend::new(create_test_client());
+        let uri = tower_lsp::lsp_types::Url::parse("file:///test.http").unwrap();
+        let lenses_for = |doc: &str| {
+            backend
+                .documents
+                .insert(uri.clone(), doc.to_string())
+                .unwrap();
+            backend.code_lens(CodeLensParams {
+                text_document: tower_lsp::lsp_types::TextDocumentIdentifier { uri: uri.clone() },
+                work_done_progress_params: Default::default(),
+                partial_result_params: Default::default(),
+            })
+        };
+
+        let lenses = lenses_for("GET https://api.example.com/users")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(lenses.len(), 1);
+
+        let lenses = lenses_for(
+            "GET https://api.example.com/users\n\n###\n\nDELETE https://api.example.com/users/1",
+        )
+        .await
+        .unwrap()
+        .unwrap();
+        assert_eq!(lenses.len(), 3);
+        let run_all = lenses[0].command.as_ref().unwrap();
+        assert_eq!(run_all.command, "rest-client.runAll");
+        assert_eq!(
+            run_all.arguments,
+            Some(vec![serde_json::json!("file:///test.http")])
+        );
+        assert_eq!(lenses[0].range, lenses[1].range);
+    }
+
     #[tokio::test]
     async fn test_code_lens_empty_document() {
         // Test code lens for empty document
@@ -1274,6 +1622,116 @@ DELETE https://api.example.com/users/1"#;
         assert_eq!(lenses.len(), 0);
     }
 
+    #[tokio::test]
+    async fn test_document_symbols_multiple_requests() {
+        let documents = create_test_documents();
+        let uri = tower_lsp::lsp_types::Url::parse("file:///test.http").unwrap();
+
+        let doc = r#"GET https://api.example.com/users
+
+###
+
+# @name CreateUser
+POST https://api.example.com/users
+Content-Type: application/json
+
+{"name": "John"}"#;
+
+        documents.insert(uri.clone(), doc.to_string()).unwrap();
+
+        let content = documents.get(&uri).unwrap();
+        let symbols = symbols::provide_document_symbols(&content);
+
+        assert_eq!(symbols.len(), 2);
+        assert_eq!(symbols[0].name, "GET https://api.example.com/users");
+        assert_eq!(symbols[1].name, "CreateUser");
+        assert_eq!(symbols[1].range.start.line, 4);
+        assert_eq!(symbols[1].range.end.line, 8);
+        assert_eq!(symbols[1].selection_range.start.line, 5);
+    }
+
+    #[tokio::test]
+    async fn test_inlay_hints_with_active_environment() {
+        let mut environments = Environments::new();
+        let mut dev = crate::environment::Environment::new("dev");
+        dev.set("baseUrl", "http://localhost:3000");
+        environments.add_environment(dev);
+        environments.set_active("dev");
+
+        let backend = Backend {
+            client: create_test_client(),
+            documents: Arc::new(DocumentManager::new()),
+            executor: Arc::new(ExecutorBridge::new()),
+            environment_session: Arc::new(EnvironmentSession::new(environments)),
+            workspace_root: Arc::new(std::sync::RwLock::new(None)),
+        };
+
+        let uri = tower_lsp::lsp_types::Url::parse("file:///test.http").unwrap();
+        backend
+            .documents
+            .insert(
+                uri.clone(),
+                "GET {{baseUrl}}/users\n\
+                Authorization: Bearer {{undefinedToken}}\n\
+                \n\
+                ###\n\
+                GET {{baseUrl}}/orders"
+                    .to_string(),
+            )
+            .unwrap();
+
+        let hints_in = |start: u32, end: u32| InlayHintParams {
+            text_document: tower_lsp::lsp_types::TextDocumentIdentifier { uri: uri.clone() },
+            range: tower_lsp::lsp_types::Range {
+                start: LspPosition {
+                    line: start,
+                    character: 0,
+                },
+                end: LspPosition {
+                    line: end,
+                    character: 0,
+                },
+            },
+            work_done_progress_params: Default::default(),
+        };
+
+        let hints = backend.inlay_hint(hints_in(0, 4)).await.unwrap().unwrap();
+        assert_eq!(hints.len(), 2);
+        assert_eq!(hints[0].position, LspPosition::new(0, 15));
+        assert!(matches!(
+            &hints[0].label,
+            InlayHintLabel::String(label) if label == "ⓘhttp://localhost:3000"
+        ));
+        assert_eq!(hints[1].position, LspPosition::new(4, 15));
+
+        // Only hints inside the requested lines are returned
+        let hints = backend.inlay_hint(hints_in(1, 3)).await.unwrap().unwrap();
+        assert!(hints.is_empty());
+        let hints = backend.inlay_hint(hints_in(4, 4)).await.unwrap().unwrap();
+        assert_eq!(hints.len(), 1);
+        assert_eq!(hints[0].position.line, 4);
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_did_change_configuration_updates_config() {
+        let backend = Backend::new(create_test_client());
+
+        backend
+            .did_change_configuration(DidChangeConfigurationParams {
+                settings: serde_json::json!({
+                    "rest-client": { "allowPipeCommands": true, "enableColonParams": true }
+                }),
+            })
+            .await;
+        let config = crate::config::get_config();
+        crate::config::reset_config();
+
+        assert!(config.allow_pipe_commands);
+        assert!(config.enable_colon_params);
+        assert!(!config.show_section_sizes);
+    }
+
     #[test]
     fn test_range_conversion() {
         // Test internal Range to LSP Range conversion
@@ -1963,4 +2421,25 @@ DELETE https://api.example.com/users/1"#;
         let result = backend.execute_command(params).await;
         assert!(result.is_err());
     }
+
+    #[tokio::test]
+    async fn test_execute_command_run_all_requires_open_document() {
+        let backend = Backend::new(create_test_client());
+
+        let params = ExecuteCommandParams {
+            command: "rest-client.runAll".to_string(),
+            arguments: vec![serde_json::Value::String(
+                "file:///nonexistent.http".to_string(),
+            )],
+            work_done_progress_params: Default::default(),
+        };
+        assert!(backend.execute_command(params).await.is_err());
+
+        let params = ExecuteCommandParams {
+            command: "rest-client.runAll".to_string(),
+            arguments: vec![],
+            work_done_progress_params: Default::default(),
+        };
+        assert!(backend.execute_command(params).await.is_err());
+    }
 }
@@ -3,15 +3,21 @@
 //! This module bridges the LSP server with the existing parser and executor
 //! modules, enabling execution of HTTP requests from .http file content.
 
+use crate::auth::append_query_param;
+use crate::config::get_config;
 use crate::environment::Environment;
+use crate::executor::batch::{run_batch_native, BatchResult};
 #[cfg(feature = "lsp")]
-use crate::executor::execute_request_native;
-use crate::executor::ExecutionConfig;
-use crate::models::{HttpRequest, HttpResponse};
+use crate::executor::execute_request_native_with_progress;
+use crate::executor::pipe::{run_pipe_command, PipeError};
+use crate::executor::{ExecutionConfig, RequestError};
+use crate::models::{Headers, HttpRequest, HttpResponse, MultipartPart};
 use crate::parser::{error::ParseError, parse_file};
+use crate::variables::capture::{store_captures, CapturedVariables};
 use crate::variables::substitution::VariableContext;
 use std::collections::HashMap;
 use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
 
 /// Error types for executor bridge operations
 #[derive(Debug)]
@@ -24,6 +30,8 @@ pub enum BridgeError {
     ExecutionError(String),
     /// Error during variable substitution
     VariableError(String),
+    /// The `# @pipe` command was disabled or failed
+    PipeError(PipeError),
 }
 
 impl std::fmt::Display for BridgeError {
@@ -35,6 +43,7 @@ impl std::fmt::Display for BridgeError {
             }
             BridgeError::ExecutionError(e) => write!(f, "Execution error: {}", e),
             BridgeError::VariableError(e) => write!(f, "Variable error: {}", e),
+            BridgeError::PipeError(e) => write!(f, "{}", e),
         }
     }
 }
@@ -55,6 +64,10 @@ impl From<ParseError> for BridgeError {
 pub struct ExecutorBridge {
     /// Execution configuration
     config: ExecutionConfig,
+
+    /// Values captured with `# @capture` by earlier requests, scoped by
+    /// request name
+    captured_variables: Arc<Mutex<CapturedVariables>>,
 }
 
 impl ExecutorBridge {
@@ -70,6 +83,7 @@ impl ExecutorBridge {
     pub fn new() -> Self {
         Self {
             config: ExecutionConfig::default(),
+            captured_variables: Arc::new(Mutex::new(CapturedVariables::new())),
         }
     }
 
@@ -89,7 +103,10 @@ impl ExecutorBridge {
     /// let bridge = ExecutorBridge::with_config(config);
     /// ```
     pub fn with_config(config: ExecutionConfig) -> Self {
-        Self { config }
+        Self {
+            config,
+            captured_variables: Arc::new(Mutex::new(CapturedVariables::new())),
+        }
     }
 
     /// Executes the HTTP request at the specified line in a document
@@ -97,8 +114,12 @@ impl ExecutorBridge {
     /// This method:
     /// 1. Parses the entire document to extract all requests
     /// 2. Finds the request that contains the specified line
-    /// 3. Resolves variables using the provided environment
+    /// 3. Resolves variables using the provided environment and values
+    ///    captured by earlier requests
     /// 4. Executes the request and returns the response
+    /// 5. Stores the values of the request's `# @capture` directives
+    /// 6. Pipes the body through the request's `# @pipe` command, if any,
+    ///    replacing it with the command's output
     ///
     /// # Arguments
     ///
@@ -128,6 +149,30 @@ impl ExecutorBridge {
         document: &str,
         line: usize,
         env: Option<Environment>,
+    ) -> Result<HttpResponse, BridgeError> {
+        self.execute_request_at_line_with_progress(document, line, env, &mut |_| {})
+            .await
+    }
+
+    /// Executes the HTTP request at the specified line, reporting a
+    /// streamed body as it arrives
+    ///
+    /// Behaves like [`ExecutorBridge::execute_request_at_line`], but each
+    /// line of a `Transfer-Encoding: chunked` body is passed to `on_line` as
+    /// soon as it has been received.
+    ///
+    /// # Arguments
+    ///
+    /// * `document` - The full content of the .http file
+    /// * `line` - The line number (1-based) where the cursor is positioned
+    /// * `env` - Optional environment for variable resolution
+    /// * `on_line` - Called with each line of a chunked response body
+    pub async fn execute_request_at_line_with_progress(
+        &self,
+        document: &str,
+        line: usize,
+        env: Option<Environment>,
+        on_line: &mut (dyn FnMut(&str) + Send),
     ) -> Result<HttpResponse, BridgeError> {
         // Parse the document to get all requests
         let file_path = PathBuf::from("untitled.http");
@@ -136,6 +181,14 @@ impl ExecutorBridge {
         // Find the request that contains the specified line
         let request = self.find_request_at_line(&requests, line)?;
 
+        // Refuse before sending, so a disabled pipe doesn't leave a request
+        // sent with nothing to show for it
+        if let Some(command) = &request.pipe {
+            if !get_config().allow_pipe_commands {
+                return Err(BridgeError::PipeError(PipeError::Disabled(command.clone())));
+            }
+        }
+
         // Clone the request for variable substitution
         let mut resolved_request = request.clone();
 
@@ -146,7 +199,7 @@ impl ExecutorBridge {
         // Execute the request using native HTTP client (reqwest)
         // This is available because we're in the LSP server with the "lsp" feature
         #[cfg(feature = "lsp")]
-        let response = execute_request_native(&resolved_request)
+        let mut response = execute_request_native_with_progress(&resolved_request, on_line)
             .await
             .map_err(|e| BridgeError::ExecutionError(e.to_string()))?;
 
@@ -158,9 +211,78 @@ impl ExecutorBridge {
             ));
         };
 
+        self.record_captures(request, &response);
+
+        // Captures above still see the body as received; the size is kept too
+        if let Some(command) = &request.pipe {
+            let timeout = ExecutionConfig::from_global_config().timeout_for(request);
+            let output = run_pipe_command(command, &response.body, timeout)
+                .await
+                .map_err(BridgeError::PipeError)?;
+            response.body = output.into_bytes();
+        }
+
         Ok(response)
     }
 
+    /// Sends every request in a document, at most `runAllConcurrency` at a
+    /// time
+    ///
+    /// Variables are resolved like for a single request; a request whose
+    /// variables can't be resolved is reported as failed and the others are
+    /// still sent. Captures are not recorded and `# @pipe` commands are not
+    /// run. Dropping the returned future cancels the requests that haven't
+    /// finished.
+    ///
+    /// # Arguments
+    ///
+    /// * `document` - The full content of the .http file
+    /// * `env` - Optional environment for variable resolution
+    ///
+    /// # Returns
+    ///
+    /// One result per request, in document order, or `Err(BridgeError)` if
+    /// the document can't be parsed
+    pub async fn execute_all_requests(
+        &self,
+        document: &str,
+        env: Option<Environment>,
+    ) -> Result<Vec<BatchResult>, BridgeError> {
+        let requests = parse_file(document, &PathBuf::from("untitled.http"))?;
+        let context = self.create_variable_context(env);
+
+        let resolved: Vec<Result<HttpRequest, BridgeError>> = requests
+            .iter()
+            .map(|request| {
+                let mut resolved = request.clone();
+                self.resolve_request_variables(&mut resolved, &context)
+                    .map(|_| resolved)
+            })
+            .collect();
+        let sendable: Vec<HttpRequest> = resolved
+            .iter()
+            .filter_map(|request| request.as_ref().ok().cloned())
+            .collect();
+
+        let mut sent = run_batch_native(sendable, get_config().run_all_concurrency)
+            .await
+            .into_iter();
+
+        // Put requests that couldn't be resolved back in document order
+        Ok(requests
+            .iter()
+            .zip(resolved)
+            .filter_map(|(request, resolved)| match resolved {
+                Ok(_) => sent.next(),
+                Err(e) => Some(BatchResult {
+                    method: request.method,
+                    url: request.url.clone(),
+                    result: Err(RequestError::BuildError(e.to_string())),
+                }),
+            })
+            .collect())
+    }
+
     /// Finds the request that contains the specified line number
     ///
     /// Requests can span multiple lines (method, headers, body), so we need
@@ -198,40 +320,117 @@ impl ExecutorBridge {
             shared_variables: HashMap::new(),
             file_variables: HashMap::new(),
             request_variables: HashMap::new(),
+            captured_variables: self.captured_variables.lock().unwrap().clone(),
+            named_responses: HashMap::new(),
             workspace_path: PathBuf::from("."),
         }
     }
 
+    /// Stores the values of a request's `# @capture` directives for later
+    /// requests, reporting directives that don't match the response
+    fn record_captures(&self, request: &HttpRequest, response: &HttpResponse) {
+        let mut captured = self.captured_variables.lock().unwrap();
+        for (variable, err) in store_captures(request, response, &mut captured) {
+            eprintln!("Warning: Failed to capture '{}': {}", variable, err);
+        }
+    }
+
     /// Resolves variables in a request using the variable context
     fn resolve_request_variables(
         &self,
         request: &mut HttpRequest,
         context: &VariableContext,
     ) -> Result<(), BridgeError> {
-        use crate::variables::substitution::substitute_variables;
+        use crate::variables::substitution::{
+            read_template_body, substitute_url, substitute_variables,
+        };
 
         // Resolve URL variables
-        request.url = substitute_variables(&request.url, context)
+        request.url = substitute_url(&request.url, context)
             .map_err(|e| BridgeError::VariableError(e.to_string()))?;
 
+        // Append the `# @apikey query` key once it is resolved
+        if let Some((name, value)) = &request.query_api_key {
+            let name = substitute_variables(name, context)
+                .map_err(|e| BridgeError::VariableError(e.to_string()))?;
+            let value = substitute_variables(value, context)
+                .map_err(|e| BridgeError::VariableError(e.to_string()))?;
+            request.url = append_query_param(&request.url, &name, &value);
+        }
+
         // Resolve header variables
-        let mut resolved_headers = HashMap::new();
+        let mut resolved_headers = Headers::new();
         for (key, value) in &request.headers {
             let resolved_key = substitute_variables(key, context)
                 .map_err(|e| BridgeError::VariableError(e.to_string()))?;
             let resolved_value = substitute_variables(value, context)
                 .map_err(|e| BridgeError::VariableError(e.to_string()))?;
-            resolved_headers.insert(resolved_key, resolved_value);
+            resolved_headers.append(resolved_key, resolved_value);
+        }
+
+        // Add `# @header-if` headers for the active environment
+        let environment = context.environment.as_ref().map(|env| env.name.as_str());
+        for header in request.conditional_headers_for(environment) {
+            let resolved_value = substitute_variables(&header.value, context)
+                .map_err(|e| BridgeError::VariableError(e.to_string()))?;
+            resolved_headers.insert(header.name.clone(), resolved_value);
         }
         request.headers = resolved_headers;
 
-        // Resolve body variables if present
-        if let Some(body) = &request.body {
+        // Resolve body variables if present (raw file bodies are sent as-is)
+        if let Some(body) = request.body.as_ref().filter(|_| !request.raw_body) {
             request.body = Some(
                 substitute_variables(body, context)
                     .map_err(|e| BridgeError::VariableError(e.to_string()))?,
             );
         }
+        if let Some(path) = request.body_file.as_ref().filter(|_| !request.raw_body) {
+            let template =
+                read_template_body(path).map_err(|e| BridgeError::VariableError(e.to_string()))?;
+            if let Some(body) = template {
+                request.body = Some(
+                    substitute_variables(&body, context)
+                        .map_err(|e| BridgeError::VariableError(e.to_string()))?,
+                );
+                request.body_file = None;
+            }
+        }
+
+        // Resolve `@digest` and `@aws` credentials
+        let resolve = |value: &str| {
+            substitute_variables(value, context)
+                .map_err(|e| BridgeError::VariableError(e.to_string()))
+        };
+        if let Some(credentials) = request.digest_auth.as_mut() {
+            credentials.username = resolve(&credentials.username)?;
+            credentials.password = resolve(&credentials.password)?;
+        }
+        if let Some(aws) = request.aws_sigv4.as_mut() {
+            aws.region = resolve(&aws.region)?;
+            aws.service = resolve(&aws.service)?;
+            aws.access_key = resolve(&aws.access_key)?;
+            aws.secret_key = resolve(&aws.secret_key)?;
+            if let Some(token) = aws.session_token.as_mut() {
+                *token = resolve(token)?;
+            }
+        }
+
+        // Resolve multipart field values and file paths
+        if let Some(multipart) = request.multipart.as_mut() {
+            for part in &mut multipart.parts {
+                match part {
+                    MultipartPart::Field { value, .. } => {
+                        *value = substitute_variables(value, context)
+                            .map_err(|e| BridgeError::VariableError(e.to_string()))?;
+                    }
+                    MultipartPart::File { path, .. } => {
+                        *path = substitute_variables(&path.to_string_lossy(), context)
+                            .map_err(|e| BridgeError::VariableError(e.to_string()))?
+                            .into();
+                    }
+                }
+            }
+        }
 
         Ok(())
     }
@@ -412,11 +611,28 @@ mod tests {
         let bridge = ExecutorBridge::new();
         let request = HttpRequest {
             id: "test-1".to_string(),
+            name: None,
             method: HttpMethod::GET,
             url: "https://example.com".to_string(),
             http_version: None,
-            headers: HashMap::new(),
+            headers: Headers::new(),
             body: None,
+            raw_body: false,
+            body_encoding: None,
+            timeout_override: None,
+            retries_override: None,
+            no_cookies: false,
+            filter: None,
+            pipe: None,
+            body_file: None,
+            conditional_headers: Vec::new(),
+            captures: Vec::new(),
+            digest_auth: None,
+            aws_sigv4: None,
+            multipart: None,
+            form_fields: Vec::new(),
+            query_api_key: None,
+            api_key_header: None,
             line_number: 1,
             file_path: PathBuf::from("test.http"),
         };
@@ -441,22 +657,56 @@ mod tests {
         let bridge = ExecutorBridge::new();
         let request1 = HttpRequest {
             id: "test-1".to_string(),
+            name: None,
             method: HttpMethod::GET,
             url: "https://example.com/1".to_string(),
             http_version: None,
-            headers: HashMap::new(),
+            headers: Headers::new(),
             body: None,
+            raw_body: false,
+            body_encoding: None,
+            timeout_override: None,
+            retries_override: None,
+            no_cookies: false,
+            filter: None,
+            pipe: None,
+            body_file: None,
+            conditional_headers: Vec::new(),
+            captures: Vec::new(),
+            digest_auth: None,
+            aws_sigv4: None,
+            multipart: None,
+            form_fields: Vec::new(),
+            query_api_key: None,
+            api_key_header: None,
             line_number: 1,
             file_path: PathBuf::from("test.http"),
         };
 
         let request2 = HttpRequest {
             id: "test-2".to_string(),
+            name: None,
             method: HttpMethod::POST,
             url: "https://example.com/2".to_string(),
             http_version: None,
-            headers: HashMap::new(),
+            headers: Headers::new(),
             body: Some("data".to_string()),
+            raw_body: false,
+            body_encoding: None,
+            timeout_override: None,
+            retries_override: None,
+            no_cookies: false,
+            filter: None,
+            pipe: None,
+            body_file: None,
+            conditional_headers: Vec::new(),
+            captures: Vec::new(),
+            digest_auth: None,
+            aws_sigv4: None,
+            multipart: None,
+            form_fields: Vec::new(),
+            query_api_key: None,
+            api_key_header: None,
             line_number: 10,
             file_path: PathBuf::from("test.http"),
         };
@@ -486,7 +736,7 @@ mod tests {
 
     #[test]
     fn test_format_response_basic() {
-        let mut headers = HashMap::new();
+        let mut headers = Headers::new();
         headers.insert("Content-Type".to_string(), "text/plain".to_string());
         headers.insert("Content-Length".to_string(), "13".to_string());
 
@@ -504,6 +754,12 @@ mod tests {
                 download: std::time::Duration::from_millis(20),
             },
             size: 13,
+            redirect_chain: Vec::new(),
+            final_url: None,
+            original_encoding: None,
+            compressed_size: None,
+            warnings: Vec::new(),
+            throughput_bps: None,
         };
 
         let formatted = ExecutorBridge::format_response(&response);
@@ -515,7 +771,7 @@ mod tests {
 
     #[test]
     fn test_format_response_with_json() {
-        let mut headers = HashMap::new();
+        let mut headers = Headers::new();
         headers.insert("Content-Type".to_string(), "application/json".to_string());
 
         let response = HttpResponse {
@@ -532,6 +788,12 @@ mod tests {
                 download: std::time::Duration::from_millis(15),
             },
             size: 23,
+            redirect_chain: Vec::new(),
+            final_url: None,
+            original_encoding: None,
+            compressed_size: None,
+            warnings: Vec::new(),
+            throughput_bps: None,
         };
 
         let formatted = ExecutorBridge::format_response_pretty(&response);
@@ -561,11 +823,28 @@ mod tests {
 
         let mut request = HttpRequest {
             id: "test-1".to_string(),
+            name: None,
             method: HttpMethod::GET,
             url: "https://example.com/api".to_string(),
             http_version: None,
-            headers: HashMap::new(),
+            headers: Headers::new(),
             body: None,
+            raw_body: false,
+            body_encoding: None,
+            timeout_override: None,
+            retries_override: None,
+            no_cookies: false,
+            filter: None,
+            pipe: None,
+            body_file: None,
+            conditional_headers: Vec::new(),
+            captures: Vec::new(),
+            digest_auth: None,
+            aws_sigv4: None,
+            multipart: None,
+            form_fields: Vec::new(),
+            query_api_key: None,
+            api_key_header: None,
             line_number: 1,
             file_path: PathBuf::from("test.http"),
         };
@@ -574,4 +853,67 @@ mod tests {
         assert!(result.is_ok());
         assert_eq!(request.url, "https://example.com/api");
     }
+
+    #[test]
+    fn test_captured_values_resolve_in_later_requests() {
+        let bridge = ExecutorBridge::new();
+        let document = "# @name login\n\
+                        POST https://example.com/login\n\
+                        \n\
+                        {\"user\": \"alice\"}\n\
+                        \n\
+                        # @capture token = $.auth.token\n\
+                        \n\
+                        ###\n\
+                        GET https://example.com/me\n\
+                        Authorization: Bearer {{login.token}}\n";
+        let requests = parse_file(document, &PathBuf::from("test.http")).unwrap();
+
+        let mut response = HttpResponse::new(200, "OK".to_string());
+        response.set_body(br#"{"auth": {"token": "abc123"}}"#.to_vec());
+        bridge.record_captures(&requests[0], &response);
+
+        // Clones share the captured values
+        let context = bridge.clone().create_variable_context(None);
+        let mut request = requests[1].clone();
+        bridge
+            .resolve_request_variables(&mut request, &context)
+            .unwrap();
+        assert_eq!(request.headers["Authorization"], "Bearer abc123");
+    }
+
+    #[test]
+    fn test_resolve_request_variables_appends_query_api_key() {
+        let bridge = ExecutorBridge::new();
+        let mut context = bridge.create_variable_context(None);
+        context
+            .file_variables
+            .insert("KEY".to_string(), "a&b".to_string());
+
+        let document = "# @apikey query api_key {{KEY}}\nGET https://example.com/items?page=2\n";
+        let mut request = parse_file(document, &PathBuf::from("test.http")).unwrap()[0].clone();
+        bridge
+            .resolve_request_variables(&mut request, &context)
+            .unwrap();
+        assert_eq!(
+            request.url,
+            "https://example.com/items?page=2&api_key=a%26b"
+        );
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_pipe_directive_requires_opt_in() {
+        let bridge = ExecutorBridge::new();
+        let document = "# @pipe jq '.data'\nGET https://example.com/items\n";
+
+        // allowPipeCommands is off by default, so nothing is sent or run
+        let result = bridge.execute_request_at_line(document, 2, None).await;
+        match result {
+            Err(BridgeError::PipeError(PipeError::Disabled(command))) => {
+                assert_eq!(command, "jq '.data'")
+            }
+            other => panic!("expected a disabled pipe error, got {:?}", other),
+        }
+    }
 }
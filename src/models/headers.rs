@@ -0,0 +1,291 @@
+//! Ordered HTTP header collection.
+//!
+//! HTTP allows a header to appear more than once (e.g., `Set-Cookie` or a
+//! repeated `X-Custom`), so headers are stored as an ordered list of
+//! name/value pairs instead of a map. Lookups compare names
+//! case-insensitively, as header names are case-insensitive.
+
+use serde::de::{MapAccess, Visitor};
+use serde::ser::SerializeMap;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::Index;
+
+/// An ordered list of HTTP headers that preserves repeated names.
+///
+/// # Examples
+///
+/// ```
+/// use rest_client::models::Headers;
+///
+/// let mut headers = Headers::new();
+/// headers.append("X-Custom".to_string(), "a".to_string());
+/// headers.append("x-custom".to_string(), "b".to_string());
+///
+/// assert_eq!(headers.len(), 2);
+/// assert_eq!(headers.get("X-CUSTOM"), Some(&"a".to_string()));
+/// assert_eq!(headers.get_all("X-Custom").collect::<Vec<_>>(), vec!["a", "b"]);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Headers {
+    entries: Vec<(String, String)>,
+}
+
+impl Headers {
+    /// Creates an empty header list.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a header, keeping any existing headers with the same name.
+    pub fn append(&mut self, name: String, value: String) {
+        self.entries.push((name, value));
+    }
+
+    /// Sets a header, replacing every existing header with the same name.
+    ///
+    /// The new header takes the position of the first replaced one.
+    ///
+    /// # Returns
+    ///
+    /// The first replaced value, if any.
+    pub fn insert(&mut self, name: String, value: String) -> Option<String> {
+        let Some(index) = self.position(&name) else {
+            self.entries.push((name, value));
+            return None;
+        };
+
+        let previous = std::mem::replace(&mut self.entries[index], (name, value)).1;
+        let name = self.entries[index].0.clone();
+        let mut position = 0;
+        self.entries.retain(|(existing, _)| {
+            let keep = position <= index || !existing.eq_ignore_ascii_case(&name);
+            position += 1;
+            keep
+        });
+
+        Some(previous)
+    }
+
+    /// Returns the value of the first header with the given name.
+    pub fn get(&self, name: &str) -> Option<&String> {
+        self.position(name).map(|index| &self.entries[index].1)
+    }
+
+    /// Returns the values of every header with the given name, in order.
+    pub fn get_all<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a String> + 'a {
+        self.entries
+            .iter()
+            .filter(move |(existing, _)| existing.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value)
+    }
+
+    /// Returns `true` if a header with the given name is present.
+    pub fn contains_key(&self, name: &str) -> bool {
+        self.position(name).is_some()
+    }
+
+    /// Removes every header with the given name.
+    ///
+    /// # Returns
+    ///
+    /// The first removed value, if any.
+    pub fn remove(&mut self, name: &str) -> Option<String> {
+        let first = self.get(name).cloned();
+        self.entries
+            .retain(|(existing, _)| !existing.eq_ignore_ascii_case(name));
+        first
+    }
+
+    /// Keeps only the headers for which `keep` returns `true`.
+    pub fn retain<F>(&mut self, mut keep: F)
+    where
+        F: FnMut(&String, &mut String) -> bool,
+    {
+        self.entries.retain_mut(|(name, value)| keep(name, value));
+    }
+
+    /// Returns the number of headers, counting repeated names separately.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if there are no headers.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Removes all headers.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Iterates over every header as `(name, value)`, in order.
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &String)> {
+        self.entries.iter().map(as_pair)
+    }
+
+    /// Iterates over header names, in order.
+    pub fn keys(&self) -> impl Iterator<Item = &String> {
+        self.entries.iter().map(|(name, _)| name)
+    }
+
+    /// Iterates over header values, in order.
+    pub fn values(&self) -> impl Iterator<Item = &String> {
+        self.entries.iter().map(|(_, value)| value)
+    }
+
+    fn position(&self, name: &str) -> Option<usize> {
+        self.entries
+            .iter()
+            .position(|(existing, _)| existing.eq_ignore_ascii_case(name))
+    }
+}
+
+/// Borrows an entry as a `(name, value)` pair, like `HashMap::iter`.
+fn as_pair((name, value): &(String, String)) -> (&String, &String) {
+    (name, value)
+}
+
+impl Index<&str> for Headers {
+    type Output = String;
+
+    /// Returns the first value for `name`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no header with that name is present.
+    fn index(&self, name: &str) -> &String {
+        self.get(name)
+            .unwrap_or_else(|| panic!("header '{}' not present", name))
+    }
+}
+
+impl<'a> IntoIterator for &'a Headers {
+    type Item = (&'a String, &'a String);
+    type IntoIter = std::iter::Map<
+        std::slice::Iter<'a, (String, String)>,
+        fn(&'a (String, String)) -> (&'a String, &'a String),
+    >;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.iter().map(as_pair)
+    }
+}
+
+impl IntoIterator for Headers {
+    type Item = (String, String);
+    type IntoIter = std::vec::IntoIter<(String, String)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.into_iter()
+    }
+}
+
+impl FromIterator<(String, String)> for Headers {
+    /// Collects headers, keeping repeated names.
+    fn from_iter<I: IntoIterator<Item = (String, String)>>(iter: I) -> Self {
+        Self {
+            entries: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl Extend<(String, String)> for Headers {
+    /// Appends headers, keeping repeated names.
+    fn extend<I: IntoIterator<Item = (String, String)>>(&mut self, iter: I) {
+        self.entries.extend(iter);
+    }
+}
+
+impl From<HashMap<String, String>> for Headers {
+    fn from(map: HashMap<String, String>) -> Self {
+        map.into_iter().collect()
+    }
+}
+
+impl Serialize for Headers {
+    /// Serializes as a map; repeated names become repeated keys.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.entries.len()))?;
+        for (name, value) in &self.entries {
+            map.serialize_entry(name, value)?;
+        }
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for Headers {
+    /// Deserializes from a map, keeping repeated keys in order.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct HeadersVisitor;
+
+        impl<'de> Visitor<'de> for HeadersVisitor {
+            type Value = Headers;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a map of header names to values")
+            }
+
+            fn visit_map<M: MapAccess<'de>>(self, mut access: M) -> Result<Headers, M::Error> {
+                let mut headers = Headers::new();
+                while let Some((name, value)) = access.next_entry()? {
+                    headers.append(name, value);
+                }
+                Ok(headers)
+            }
+        }
+
+        deserializer.deserialize_map(HeadersVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(pairs: &[(&str, &str)]) -> Headers {
+        pairs
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_insert_replaces_all_occurrences() {
+        let mut headers = headers(&[("Accept", "a"), ("X-Id", "1"), ("accept", "b")]);
+
+        assert_eq!(
+            headers.insert("ACCEPT".to_string(), "c".to_string()),
+            Some("a".to_string())
+        );
+
+        let pairs: Vec<_> = headers.iter().collect();
+        assert_eq!(pairs.len(), 2);
+        assert_eq!(pairs[0], (&"ACCEPT".to_string(), &"c".to_string()));
+        assert_eq!(headers["x-id"], "1");
+    }
+
+    #[test]
+    fn test_remove_and_retain() {
+        let mut headers = headers(&[("Set-Cookie", "a=1"), ("Set-Cookie", "b=2"), ("X-Id", "1")]);
+
+        assert_eq!(headers.remove("set-cookie"), Some("a=1".to_string()));
+        assert_eq!(headers.len(), 1);
+
+        headers.retain(|name, _| name != "X-Id");
+        assert!(headers.is_empty());
+    }
+
+    #[test]
+    fn test_serde_round_trip_keeps_duplicates() {
+        let headers = headers(&[("Set-Cookie", "a=1"), ("Set-Cookie", "b=2")]);
+
+        let json = serde_json::to_string(&headers).unwrap();
+        assert_eq!(json, r#"{"Set-Cookie":"a=1","Set-Cookie":"b=2"}"#);
+
+        let parsed: Headers = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, headers);
+    }
+}
@@ -3,8 +3,14 @@
 //! This module contains the core data structures used throughout the REST Client extension
 //! for representing HTTP requests, responses, and related metadata.
 
+pub mod headers;
+pub mod multipart;
 pub mod request;
 pub mod response;
 
-pub use request::{HttpMethod, HttpRequest};
+pub use headers::Headers;
+pub use multipart::{MultipartBody, MultipartPart};
+pub use request::{
+    ConditionalHeader, HttpMethod, HttpRequest, HttpRequestBuilder, InvalidHeaderError,
+};
 pub use response::{HttpResponse, RequestTiming};
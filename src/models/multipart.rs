@@ -0,0 +1,112 @@
+//! Multipart/form-data request bodies.
+//!
+//! A multipart body is authored as a list of parts after `# @multipart` (or
+//! a `Content-Type: multipart/form-data` header without a boundary):
+//!
+//! ```http
+//! # @multipart
+//! POST https://api.example.com/upload
+//!
+//! --field name=title
+//! Hello
+//! --file photo=./photo.png
+//! --file avatar=./avatar.bin;type=image/webp
+//! ```
+//!
+//! The executor assembles the parts into a `multipart/form-data` payload with
+//! a generated boundary.
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// A multipart/form-data request body.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MultipartBody {
+    /// The parts, in the order they are sent.
+    pub parts: Vec<MultipartPart>,
+}
+
+/// A single part of a multipart body.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum MultipartPart {
+    /// A text field, declared with `--field name=<name>` followed by its value.
+    Field {
+        /// Form field name
+        name: String,
+        /// Field value; may span several lines
+        value: String,
+    },
+
+    /// A file upload, declared with `--file <name>=<path>`, optionally
+    /// followed by `;type=<content type>`.
+    File {
+        /// Form field name
+        name: String,
+        /// Path of the file to upload, resolved against the request file
+        path: PathBuf,
+        /// Content type from `;type=`; inferred from the extension when absent
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        content_type: Option<String>,
+    },
+}
+
+impl MultipartPart {
+    /// Returns the form field name of the part.
+    pub fn name(&self) -> &str {
+        match self {
+            MultipartPart::Field { name, .. } | MultipartPart::File { name, .. } => name,
+        }
+    }
+}
+
+/// Infers the content type of a file part from its extension.
+///
+/// # Arguments
+///
+/// * `path` - Path of the uploaded file
+///
+/// # Returns
+///
+/// The MIME type for well-known extensions (compared case-insensitively), or
+/// `application/octet-stream` otherwise.
+///
+/// # Examples
+///
+/// ```
+/// use rest_client::models::multipart::content_type_for_path;
+/// use std::path::Path;
+///
+/// assert_eq!(content_type_for_path(Path::new("photo.PNG")), "image/png");
+/// assert_eq!(content_type_for_path(Path::new("data.bin")), "application/octet-stream");
+/// ```
+pub fn content_type_for_path(path: &Path) -> &'static str {
+    let extension = path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .map(str::to_ascii_lowercase)
+        .unwrap_or_default();
+
+    match extension.as_str() {
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "svg" => "image/svg+xml",
+        "ico" => "image/x-icon",
+        "pdf" => "application/pdf",
+        "json" => "application/json",
+        "xml" => "application/xml",
+        "zip" => "application/zip",
+        "gz" => "application/gzip",
+        "txt" => "text/plain",
+        "csv" => "text/csv",
+        "html" | "htm" => "text/html",
+        "css" => "text/css",
+        "js" => "text/javascript",
+        "mp3" => "audio/mpeg",
+        "wav" => "audio/wav",
+        "mp4" => "video/mp4",
+        _ => "application/octet-stream",
+    }
+}
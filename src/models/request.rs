@@ -3,8 +3,13 @@
 //! This module defines the core data structures for representing HTTP requests,
 //! including the request method, headers, body, and metadata.
 
+use super::headers::Headers;
+use super::multipart::MultipartBody;
+use crate::auth::digest::DigestAuth;
+use crate::auth::sigv4::AwsSigV4;
+use crate::variables::capture::CaptureDirective;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::fmt;
 use std::path::PathBuf;
 
 /// HTTP request method.
@@ -33,6 +38,19 @@ pub enum HttpMethod {
 }
 
 impl HttpMethod {
+    /// Every HTTP method, in declaration order.
+    pub const ALL: [HttpMethod; 9] = [
+        HttpMethod::GET,
+        HttpMethod::POST,
+        HttpMethod::PUT,
+        HttpMethod::DELETE,
+        HttpMethod::PATCH,
+        HttpMethod::OPTIONS,
+        HttpMethod::HEAD,
+        HttpMethod::TRACE,
+        HttpMethod::CONNECT,
+    ];
+
     /// Returns the string representation of the HTTP method.
     pub fn as_str(&self) -> &'static str {
         match self {
@@ -79,6 +97,20 @@ impl std::fmt::Display for HttpMethod {
     }
 }
 
+/// A header sent only when a given environment is active.
+///
+/// Declared with `# @header-if <environment> Name: Value`, e.g.
+/// `# @header-if dev X-Debug: true`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConditionalHeader {
+    /// Name of the environment the header applies to.
+    pub environment: String,
+    /// Header name.
+    pub name: String,
+    /// Header value; may contain variables.
+    pub value: String,
+}
+
 /// Represents an HTTP request parsed from a `.http` or `.rest` file.
 ///
 /// This structure contains all the information needed to execute an HTTP request,
@@ -91,6 +123,13 @@ pub struct HttpRequest {
     /// Used for request history, cancellation, and correlation with responses.
     pub id: String,
 
+    /// Optional request name from a `# @name <ident>` comment.
+    ///
+    /// Lets later requests reference this one's response (e.g.,
+    /// `{{login.response.$.token}}`) and history be searched by name.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
     /// HTTP method (GET, POST, PUT, DELETE, etc.).
     pub method: HttpMethod,
 
@@ -108,8 +147,9 @@ pub struct HttpRequest {
     /// Request headers as key-value pairs.
     ///
     /// Header names are case-insensitive but are stored as provided in the
-    /// source file. Common headers include Content-Type, Authorization, etc.
-    pub headers: HashMap<String, String>,
+    /// source file. Repeated headers are kept in source order. Common headers
+    /// include Content-Type, Authorization, etc.
+    pub headers: Headers,
 
     /// Optional request body.
     ///
@@ -117,6 +157,122 @@ pub struct HttpRequest {
     /// plain text depending on the Content-Type header.
     pub body: Option<String>,
 
+    /// Whether the body is sent as-is, without variable substitution.
+    ///
+    /// Set for bodies read from a file with `< path`; bodies read with
+    /// `<@ path` and inline bodies have their variables resolved.
+    #[serde(default)]
+    pub raw_body: bool,
+
+    /// File the body is read from, for `< path` and `<@ path` bodies.
+    ///
+    /// Only the path is recorded when parsing; the file is read when the
+    /// request is sent. Files larger than the configured
+    /// `large_body_threshold` and files that aren't valid UTF-8 are sent
+    /// as-is without being read into memory. `body` is `None` while set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body_file: Option<PathBuf>,
+
+    /// Charset to encode the body in before sending, set with `# @encoding`.
+    ///
+    /// The body is authored as UTF-8; when set (e.g., "iso-8859-1") it is
+    /// converted to this charset by the executor. Defaults to none (UTF-8).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body_encoding: Option<String>,
+
+    /// Multipart/form-data parts, declared with `--field` and `--file` lines.
+    ///
+    /// When present, the request is sent as `multipart/form-data` built from
+    /// these parts and `body` is `None`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub multipart: Option<MultipartBody>,
+
+    /// Fields of a `# @form` body, as written in the source file.
+    ///
+    /// The body sent is encoded from these after variable substitution, so
+    /// resolved values are percent-encoded too.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub form_fields: Vec<(String, String)>,
+
+    /// API key name and value for the query string, set with
+    /// `# @apikey query <name> <value>`.
+    ///
+    /// Appended to the URL after variable substitution, so the resolved key
+    /// is percent-encoded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub query_api_key: Option<(String, String)>,
+
+    /// Name of the header set with `# @apikey header <name> <value>`.
+    ///
+    /// The header itself is in `headers`; the name is kept so the key can be
+    /// redacted from the audit log.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_key_header: Option<String>,
+
+    /// Timeout for this request in milliseconds, set with `# @timeout`.
+    ///
+    /// Takes precedence over the global timeout when present.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_override: Option<u64>,
+
+    /// Number of retries for this request, set with `# @retries`.
+    ///
+    /// Takes precedence over the configured `max_retries` when present.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retries_override: Option<u32>,
+
+    /// Whether the session cookie jar is bypassed, set with `# @no-cookies`.
+    ///
+    /// When set, stored cookies are not sent and `Set-Cookie` headers in the
+    /// response are not stored.
+    #[serde(default)]
+    pub no_cookies: bool,
+
+    /// JSONPath expression applied to JSON responses, set with `# @filter`.
+    ///
+    /// Only the matching values are shown in the formatted view; the raw
+    /// view keeps the full body.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filter: Option<String>,
+
+    /// Shell command the response body is piped through, set with `# @pipe`.
+    ///
+    /// Only run by the native executor when `allowPipeCommands` is enabled;
+    /// the command's output is shown instead of the body.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pipe: Option<String>,
+
+    /// Headers added only in a given environment, set with `# @header-if`.
+    ///
+    /// Evaluated when the request is sent: headers for the active
+    /// environment replace any header of the same name in `headers`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub conditional_headers: Vec<ConditionalHeader>,
+
+    /// Values to extract from the response, set with `# @capture`.
+    ///
+    /// Applied after the request is sent; captured values are available to
+    /// later requests as `{{variable}}` and, for named requests, as
+    /// `{{name.variable}}`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub captures: Vec<CaptureDirective>,
+
+    /// Credentials for Digest authentication, set with `# @digest user pass`.
+    ///
+    /// The request is sent without them first; if the server answers with a
+    /// Digest challenge, the request is resent with an Authorization header
+    /// computed from it. Like other credentials, these aren't saved to history.
+    #[serde(skip)]
+    pub digest_auth: Option<DigestAuth>,
+
+    /// AWS credentials and scope to sign the request with, set with `# @aws`.
+    ///
+    /// The executor adds the SigV4 `Authorization` and `X-Amz-Date` headers
+    /// just before sending. Like other credentials, these aren't saved to
+    /// history.
+    #[serde(skip)]
+    pub aws_sigv4: Option<AwsSigV4>,
+
     /// Line number in the source file where this request starts.
     ///
     /// Used for error reporting and diagnostics to help users locate issues
@@ -144,16 +300,58 @@ impl HttpRequest {
     pub fn new(id: String, method: HttpMethod, url: String) -> Self {
         Self {
             id,
+            name: None,
             method,
             url,
             http_version: None,
-            headers: HashMap::new(),
+            headers: Headers::new(),
             body: None,
+            raw_body: false,
+            body_encoding: None,
+            timeout_override: None,
+            retries_override: None,
+            no_cookies: false,
+            filter: None,
+            pipe: None,
+            body_file: None,
+            conditional_headers: Vec::new(),
+            captures: Vec::new(),
+            digest_auth: None,
+            aws_sigv4: None,
+            multipart: None,
+            form_fields: Vec::new(),
+            query_api_key: None,
+            api_key_header: None,
             line_number: 0,
             file_path: PathBuf::new(),
         }
     }
 
+    /// Returns a builder for constructing a request programmatically.
+    ///
+    /// The builder fills in editor-only fields (`id`, `line_number`,
+    /// `file_path`) with sensible defaults, so callers outside the editor
+    /// don't have to fabricate them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rest_client::models::request::{HttpMethod, HttpRequest};
+    ///
+    /// let request = HttpRequest::builder()
+    ///     .method(HttpMethod::POST)
+    ///     .url("https://api.example.com/users")
+    ///     .header("Content-Type", "application/json")
+    ///     .body(r#"{"name": "Alice"}"#)
+    ///     .build();
+    ///
+    /// assert_eq!(request.method, HttpMethod::POST);
+    /// assert_eq!(request.content_type(), Some("application/json"));
+    /// ```
+    pub fn builder() -> HttpRequestBuilder {
+        HttpRequestBuilder::default()
+    }
+
     /// Adds a header to the request.
     ///
     /// # Arguments
@@ -179,7 +377,7 @@ impl HttpRequest {
     ///
     /// `true` if the request has a non-empty body, `false` otherwise.
     pub fn has_body(&self) -> bool {
-        self.body.as_ref().map_or(false, |b| !b.is_empty())
+        self.body.as_ref().is_some_and(|b| !b.is_empty()) || self.body_file.is_some()
     }
 
     /// Gets the Content-Type header value if present.
@@ -193,6 +391,231 @@ impl HttpRequest {
             .find(|(k, _)| k.eq_ignore_ascii_case("content-type"))
             .map(|(_, v)| v.as_str())
     }
+
+    /// Returns the conditional headers that apply in the given environment.
+    ///
+    /// # Arguments
+    ///
+    /// * `environment` - Name of the active environment, if any
+    pub fn conditional_headers_for<'a>(
+        &'a self,
+        environment: Option<&'a str>,
+    ) -> impl Iterator<Item = &'a ConditionalHeader> + 'a {
+        self.conditional_headers
+            .iter()
+            .filter(move |header| Some(header.environment.as_str()) == environment)
+    }
+
+    /// Validates the request headers before sending.
+    ///
+    /// Header names must be non-empty RFC 7230 tokens and header values must
+    /// not contain control characters other than horizontal tab. In particular,
+    /// a value that resolved to something containing CR or LF is rejected so it
+    /// cannot inject additional headers or split the request.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` if all headers are valid, or `Err(InvalidHeaderError)`
+    /// describing the first invalid header.
+    pub fn validate(&self) -> Result<(), InvalidHeaderError> {
+        for (name, value) in &self.headers {
+            validate_header(name, value)?;
+        }
+        Ok(())
+    }
+}
+
+/// Fluent builder for [`HttpRequest`].
+///
+/// Created with [`HttpRequest::builder`]. Defaults to a `GET` request with a
+/// random UUID as its id, no headers, no body, line number 0, and an empty
+/// file path.
+#[derive(Debug, Clone)]
+pub struct HttpRequestBuilder {
+    request: HttpRequest,
+}
+
+impl Default for HttpRequestBuilder {
+    fn default() -> Self {
+        Self {
+            request: HttpRequest::new(
+                uuid::Uuid::new_v4().to_string(),
+                HttpMethod::GET,
+                String::new(),
+            ),
+        }
+    }
+}
+
+impl HttpRequestBuilder {
+    /// Sets the request id.
+    pub fn id(mut self, id: impl Into<String>) -> Self {
+        self.request.id = id.into();
+        self
+    }
+
+    /// Sets the request name, as given by `# @name`.
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.request.name = Some(name.into());
+        self
+    }
+
+    /// Sets the request timeout in milliseconds, as given by `# @timeout`.
+    pub fn timeout_override(mut self, timeout_ms: u64) -> Self {
+        self.request.timeout_override = Some(timeout_ms);
+        self
+    }
+
+    /// Sets the number of retries, as given by `# @retries`.
+    pub fn retries_override(mut self, retries: u32) -> Self {
+        self.request.retries_override = Some(retries);
+        self
+    }
+
+    /// Bypasses the session cookie jar, as given by `# @no-cookies`.
+    pub fn no_cookies(mut self) -> Self {
+        self.request.no_cookies = true;
+        self
+    }
+
+    /// Sets the JSONPath filter for the response, as given by `# @filter`.
+    pub fn filter(mut self, path: impl Into<String>) -> Self {
+        self.request.filter = Some(path.into());
+        self
+    }
+
+    /// Sets the shell command the response is piped through, as given by `# @pipe`.
+    pub fn pipe(mut self, command: impl Into<String>) -> Self {
+        self.request.pipe = Some(command.into());
+        self
+    }
+
+    /// Adds a header sent only in `environment`, as given by `# @header-if`.
+    pub fn header_if(
+        mut self,
+        environment: impl Into<String>,
+        name: impl Into<String>,
+        value: impl Into<String>,
+    ) -> Self {
+        self.request.conditional_headers.push(ConditionalHeader {
+            environment: environment.into(),
+            name: name.into(),
+            value: value.into(),
+        });
+        self
+    }
+
+    /// Sets the charset to encode the body in, as given by `# @encoding`.
+    pub fn body_encoding(mut self, encoding: impl Into<String>) -> Self {
+        self.request.body_encoding = Some(encoding.into());
+        self
+    }
+
+    /// Sets the HTTP method.
+    pub fn method(mut self, method: HttpMethod) -> Self {
+        self.request.method = method;
+        self
+    }
+
+    /// Sets the target URL.
+    pub fn url(mut self, url: impl Into<String>) -> Self {
+        self.request.url = url.into();
+        self
+    }
+
+    /// Sets the HTTP version (e.g., "HTTP/1.1").
+    pub fn http_version(mut self, version: impl Into<String>) -> Self {
+        self.request.http_version = Some(version.into());
+        self
+    }
+
+    /// Adds a header, replacing any previous value with the same name.
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.request.add_header(name.into(), value.into());
+        self
+    }
+
+    /// Sets the request body.
+    pub fn body(mut self, body: impl Into<String>) -> Self {
+        self.request.set_body(body.into());
+        self
+    }
+
+    /// Sets the source location used for diagnostics.
+    pub fn source(mut self, file_path: impl Into<PathBuf>, line_number: usize) -> Self {
+        self.request.file_path = file_path.into();
+        self.request.line_number = line_number;
+        self
+    }
+
+    /// Builds the request.
+    pub fn build(self) -> HttpRequest {
+        self.request
+    }
+}
+
+/// A header that can't be sent, reported by [`HttpRequest::validate`].
+///
+/// The message names the header but never includes its value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidHeaderError(pub String);
+
+impl fmt::Display for InvalidHeaderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Invalid header: {}", self.0)
+    }
+}
+
+impl std::error::Error for InvalidHeaderError {}
+
+/// Validates a single header name and value.
+///
+/// The error message names the header but never includes its value, since
+/// values frequently carry credentials.
+///
+/// # Arguments
+///
+/// * `name` - Header name
+/// * `value` - Header value (after variable substitution)
+///
+/// # Returns
+///
+/// `Ok(())` if the header is valid, or `Err(InvalidHeaderError)`.
+pub fn validate_header(name: &str, value: &str) -> Result<(), InvalidHeaderError> {
+    if name.is_empty() {
+        return Err(InvalidHeaderError(
+            "header name must not be empty".to_string(),
+        ));
+    }
+
+    if let Some(c) = name.chars().find(|&c| !is_token_char(c)) {
+        return Err(InvalidHeaderError(format!(
+            "header name '{}' contains invalid character {:?}",
+            name.escape_debug(),
+            c
+        )));
+    }
+
+    if value.contains(['\r', '\n']) {
+        return Err(InvalidHeaderError(format!(
+            "value of '{}' contains a line break (CR/LF)",
+            name
+        )));
+    }
+
+    if let Some(c) = value.chars().find(|&c| c != '\t' && c.is_ascii_control()) {
+        return Err(InvalidHeaderError(format!(
+            "value of '{}' contains control character {:?}",
+            name, c
+        )));
+    }
+
+    Ok(())
+}
+
+/// Returns true if `c` is allowed in an RFC 7230 header field name (token).
+fn is_token_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || "!#$%&'*+-.^_`|~".contains(c)
 }
 
 #[cfg(test)]
@@ -284,6 +707,94 @@ mod tests {
         assert_eq!(request.content_type(), Some("text/plain"));
     }
 
+    #[test]
+    fn test_builder_defaults() {
+        let request = HttpRequest::builder().url("https://example.com").build();
+
+        assert_eq!(request.method, HttpMethod::GET);
+        assert_eq!(request.url, "https://example.com");
+        assert!(!request.id.is_empty());
+        assert!(request.headers.is_empty());
+        assert_eq!(request.body, None);
+        assert_eq!(request.line_number, 0);
+        assert_eq!(request.file_path, PathBuf::new());
+    }
+
+    #[test]
+    fn test_builder_all_fields() {
+        let request = HttpRequest::builder()
+            .id("req-1")
+            .method(HttpMethod::PUT)
+            .url("https://example.com/users/1")
+            .http_version("HTTP/2")
+            .header("Content-Type", "application/json")
+            .header("Accept", "application/json")
+            .body("{}")
+            .source("/workspace/api.http", 12)
+            .build();
+
+        assert_eq!(request.id, "req-1");
+        assert_eq!(request.method, HttpMethod::PUT);
+        assert_eq!(request.http_version.as_deref(), Some("HTTP/2"));
+        assert_eq!(request.headers.len(), 2);
+        assert_eq!(request.body.as_deref(), Some("{}"));
+        assert_eq!(request.file_path, PathBuf::from("/workspace/api.http"));
+        assert_eq!(request.line_number, 12);
+    }
+
+    #[test]
+    fn test_validate_accepts_normal_headers() {
+        let mut request = HttpRequest::new(
+            "test".to_string(),
+            HttpMethod::GET,
+            "https://example.com".to_string(),
+        );
+        request.add_header("Authorization".to_string(), "Bearer abc\t123".to_string());
+        request.add_header("X-Custom_Header".to_string(), "ünïcode value".to_string());
+
+        assert!(request.validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate_rejects_crlf_injection() {
+        for value in [
+            "abc\r\nX-Injected: 1",
+            "abc\nX-Injected: 1",
+            "abc\r",
+            "abc\r\n\r\nGET /admin HTTP/1.1",
+        ] {
+            let mut request = HttpRequest::new(
+                "test".to_string(),
+                HttpMethod::GET,
+                "https://example.com".to_string(),
+            );
+            request.add_header("X-User".to_string(), value.to_string());
+
+            match request.validate() {
+                Err(InvalidHeaderError(msg)) => {
+                    assert!(msg.contains("X-User"));
+                    assert!(!msg.contains("X-Injected"));
+                }
+                other => panic!("Expected InvalidHeader for {:?}, got {:?}", value, other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_validate_rejects_control_characters() {
+        assert!(validate_header("X-Test", "a\0b").is_err());
+        assert!(validate_header("X-Test", "a\x7fb").is_err());
+        assert!(validate_header("X-Test", "a\tb").is_ok());
+    }
+
+    #[test]
+    fn test_validate_rejects_invalid_header_names() {
+        assert!(validate_header("", "value").is_err());
+        assert!(validate_header("X Header", "value").is_err());
+        assert!(validate_header("X-Header:", "value").is_err());
+        assert!(validate_header("X-Header\r\nX-Injected", "value").is_err());
+    }
+
     #[test]
     fn test_serialization() {
         let request = HttpRequest::new(
@@ -3,10 +3,16 @@
 //! This module defines the core data structures for representing HTTP responses,
 //! including status information, headers, body, and performance timing metrics.
 
+use super::headers::Headers;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
 use std::time::Duration;
 
+/// Smallest response for which download throughput is reported (100 KB).
+///
+/// Smaller bodies download in a few milliseconds at most, so the computed
+/// rate is dominated by timer resolution and not worth showing.
+pub const MIN_THROUGHPUT_SIZE: usize = 100 * 1024;
+
 /// Performance timing breakdown for an HTTP request.
 ///
 /// Tracks the duration of each phase of the HTTP request/response cycle
@@ -97,8 +103,9 @@ pub struct HttpResponse {
     /// Response headers as key-value pairs.
     ///
     /// Contains all HTTP headers returned by the server, such as
-    /// Content-Type, Content-Length, Set-Cookie, etc.
-    pub headers: HashMap<String, String>,
+    /// Content-Type, Content-Length, Set-Cookie, etc. Repeated headers are
+    /// kept in the order they were received.
+    pub headers: Headers,
 
     /// Response body as raw bytes.
     ///
@@ -122,6 +129,40 @@ pub struct HttpResponse {
     ///
     /// Includes headers and body. Useful for tracking bandwidth usage.
     pub size: usize,
+
+    /// URLs visited while following redirects, in order.
+    ///
+    /// Starts with the requested URL and ends with the final URL the
+    /// response came from. Empty if the request was not redirected.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub redirect_chain: Vec<String>,
+
+    /// The URL the response came from after following redirects.
+    ///
+    /// The last entry of `redirect_chain`, or `None` if the request was not
+    /// redirected.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub final_url: Option<String>,
+
+    /// The `Content-Encoding` the body was decompressed from.
+    ///
+    /// `None` if the body was received uncompressed or left compressed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub original_encoding: Option<String>,
+
+    /// Size of the body as received, before decompression, in bytes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compressed_size: Option<usize>,
+
+    /// Notices about how the response was obtained, such as a redirect
+    /// that wasn't followed.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+
+    /// Download throughput in bytes per second, set by executors that time
+    /// the body download (see [`HttpResponse::download_throughput`]).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub throughput_bps: Option<u64>,
 }
 
 impl HttpResponse {
@@ -139,11 +180,17 @@ impl HttpResponse {
         Self {
             status_code,
             status_text,
-            headers: HashMap::new(),
+            headers: Headers::new(),
             body: Vec::new(),
             duration: Duration::from_secs(0),
             timing: RequestTiming::new(),
             size: 0,
+            redirect_chain: Vec::new(),
+            final_url: None,
+            original_encoding: None,
+            compressed_size: None,
+            warnings: Vec::new(),
+            throughput_bps: None,
         }
     }
 
@@ -183,6 +230,31 @@ impl HttpResponse {
         (300..400).contains(&self.status_code)
     }
 
+    /// Computes the download throughput in bytes per second.
+    ///
+    /// Uses the response size and the download phase of the timing. Returns
+    /// `None` for responses smaller than [`MIN_THROUGHPUT_SIZE`] or without a
+    /// measured download time, where the figure would be noise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rest_client::models::response::HttpResponse;
+    /// use std::time::Duration;
+    ///
+    /// let mut response = HttpResponse::new(200, "OK".to_string());
+    /// response.size = 2 * 1024 * 1024;
+    /// response.timing.download = Duration::from_millis(500);
+    /// assert_eq!(response.download_throughput(), Some(4 * 1024 * 1024));
+    /// ```
+    pub fn download_throughput(&self) -> Option<u64> {
+        let seconds = self.timing.download.as_secs_f64();
+        if self.size < MIN_THROUGHPUT_SIZE || seconds <= 0.0 {
+            return None;
+        }
+        Some((self.size as f64 / seconds) as u64)
+    }
+
     /// Gets the Content-Type header value if present.
     ///
     /// # Returns
@@ -195,6 +267,15 @@ impl HttpResponse {
             .map(|(_, v)| v.as_str())
     }
 
+    /// Checks whether the body was sent with `Transfer-Encoding: chunked`.
+    ///
+    /// # Returns
+    ///
+    /// `true` if any `Transfer-Encoding` header lists the `chunked` coding.
+    pub fn is_chunked(&self) -> bool {
+        is_chunked(&self.headers)
+    }
+
     /// Attempts to parse the response body as UTF-8 text.
     ///
     /// # Returns
@@ -204,14 +285,15 @@ impl HttpResponse {
         String::from_utf8(self.body.clone())
     }
 
-    /// Adds a header to the response.
+    /// Adds a header to the response, keeping earlier headers with the same
+    /// name (e.g., multiple `Set-Cookie` headers).
     ///
     /// # Arguments
     ///
     /// * `name` - Header name
     /// * `value` - Header value
     pub fn add_header(&mut self, name: String, value: String) {
-        self.headers.insert(name, value);
+        self.headers.append(name, value);
     }
 
     /// Sets the response body.
@@ -220,7 +302,7 @@ impl HttpResponse {
     ///
     /// * `body` - The body content as bytes
     pub fn set_body(&mut self, body: Vec<u8>) {
-        self.size = self.calculate_headers_size() + body.len();
+        self.size = self.headers_size() + body.len();
         self.body = body;
     }
 
@@ -228,8 +310,9 @@ impl HttpResponse {
     ///
     /// # Returns
     ///
-    /// Estimated size of all headers combined.
-    fn calculate_headers_size(&self) -> usize {
+    /// Estimated size of all headers combined, counting each as
+    /// `Name: value\r\n`.
+    pub fn headers_size(&self) -> usize {
         self.headers
             .iter()
             .map(|(k, v)| k.len() + v.len() + 4) // +4 for ": " and "\r\n"
@@ -237,10 +320,51 @@ impl HttpResponse {
     }
 }
 
+/// Checks whether `headers` list the `chunked` transfer coding.
+///
+/// Used by the native executor to decide how to read a body before the
+/// response is built.
+pub(crate) fn is_chunked(headers: &Headers) -> bool {
+    headers.get_all("transfer-encoding").any(|value| {
+        value
+            .split(',')
+            .any(|coding| coding.trim().eq_ignore_ascii_case("chunked"))
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_is_chunked() {
+        let mut response = HttpResponse::new(200, "OK".to_string());
+        assert!(!response.is_chunked());
+
+        response.add_header("Transfer-Encoding".to_string(), "gzip, Chunked".to_string());
+        assert!(response.is_chunked());
+    }
+
+    #[test]
+    fn test_download_throughput() {
+        let mut response = HttpResponse::new(200, "OK".to_string());
+        response.size = MIN_THROUGHPUT_SIZE;
+        response.timing.download = Duration::from_millis(250);
+        assert_eq!(
+            response.download_throughput(),
+            Some(MIN_THROUGHPUT_SIZE as u64 * 4)
+        );
+
+        // Too small to be meaningful
+        response.size = MIN_THROUGHPUT_SIZE - 1;
+        assert_eq!(response.download_throughput(), None);
+
+        // No measured download time
+        response.size = MIN_THROUGHPUT_SIZE;
+        response.timing.download = Duration::ZERO;
+        assert_eq!(response.download_throughput(), None);
+    }
+
     #[test]
     fn test_request_timing_new() {
         let timing = RequestTiming::new();
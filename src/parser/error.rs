@@ -65,6 +65,57 @@ pub enum ParseError {
         /// Line number in the source file (1-based)
         line: usize,
     },
+
+    /// Invalid `# @timeout` value.
+    ///
+    /// The timeout must be a positive whole number of milliseconds.
+    InvalidTimeout {
+        /// The invalid timeout value
+        value: String,
+        /// Line number in the source file (1-based)
+        line: usize,
+    },
+
+    /// Invalid `# @retries` value.
+    ///
+    /// The retry count must be a whole number.
+    InvalidRetries {
+        /// The invalid retry count
+        value: String,
+        /// Line number in the source file (1-based)
+        line: usize,
+    },
+
+    /// Invalid `# @aws` directive.
+    ///
+    /// The directive needs a region, service, access key, and secret key,
+    /// optionally followed by a session token.
+    InvalidAwsDirective {
+        /// The invalid directive line
+        directive: String,
+        /// Line number in the source file (1-based)
+        line: usize,
+    },
+
+    /// Invalid line in a multipart body.
+    ///
+    /// Parts must start with `--field name=<name>` or `--file <name>=<path>`.
+    InvalidMultipartPart {
+        /// The invalid line
+        part: String,
+        /// Line number in the source file (1-based)
+        line: usize,
+    },
+
+    /// Invalid field in a `# @form` body.
+    ///
+    /// Form fields must be in the format "key: value".
+    InvalidFormField {
+        /// The invalid field line
+        field: String,
+        /// Line number in the source file (1-based)
+        line: usize,
+    },
 }
 
 impl ParseError {
@@ -77,6 +128,11 @@ impl ParseError {
             ParseError::MissingUrl { line } => *line,
             ParseError::EmptyRequest { line } => *line,
             ParseError::InvalidHttpVersion { line, .. } => *line,
+            ParseError::InvalidTimeout { line, .. } => *line,
+            ParseError::InvalidRetries { line, .. } => *line,
+            ParseError::InvalidAwsDirective { line, .. } => *line,
+            ParseError::InvalidFormField { line, .. } => *line,
+            ParseError::InvalidMultipartPart { line, .. } => *line,
         }
     }
 }
@@ -91,6 +147,13 @@ impl fmt::Display for ParseError {
                     method, line
                 )
             }
+            ParseError::InvalidUrl { url, line } if url.contains(char::is_whitespace) => {
+                write!(
+                    f,
+                    "Invalid URL '{}' at line {}. The URL contains a space; URL-encode the value (e.g., %20) or check the variables it uses",
+                    url, line
+                )
+            }
             ParseError::InvalidUrl { url, line } => {
                 write!(
                     f,
@@ -122,6 +185,41 @@ impl fmt::Display for ParseError {
                     version, line
                 )
             }
+            ParseError::InvalidTimeout { value, line } => {
+                write!(
+                    f,
+                    "Invalid timeout '{}' at line {}. Expected a positive number of milliseconds",
+                    value, line
+                )
+            }
+            ParseError::InvalidRetries { value, line } => {
+                write!(
+                    f,
+                    "Invalid retry count '{}' at line {}. Expected a whole number",
+                    value, line
+                )
+            }
+            ParseError::InvalidAwsDirective { directive, line } => {
+                write!(
+                    f,
+                    "Invalid AWS directive '{}' at line {}. Expected '# @aws region service accessKey secretKey [sessionToken]'",
+                    directive, line
+                )
+            }
+            ParseError::InvalidMultipartPart { part, line } => {
+                write!(
+                    f,
+                    "Invalid multipart part '{}' at line {}. Expected '--field name=<name>' or '--file <name>=<path>'",
+                    part, line
+                )
+            }
+            ParseError::InvalidFormField { field, line } => {
+                write!(
+                    f,
+                    "Invalid form field '{}' at line {}. Expected format: 'key: value'",
+                    field, line
+                )
+            }
         }
     }
 }
@@ -159,6 +257,15 @@ mod tests {
         let msg = format!("{}", err);
         assert!(msg.contains("Missing URL"));
         assert!(msg.contains("line 3"));
+
+        let err = ParseError::InvalidTimeout {
+            value: "abc".to_string(),
+            line: 2,
+        };
+        assert_eq!(
+            err.to_string(),
+            "Invalid timeout 'abc' at line 2. Expected a positive number of milliseconds"
+        );
     }
 
     #[test]
@@ -6,25 +6,146 @@
 
 pub mod error;
 
-use crate::models::{HttpMethod, HttpRequest};
+use crate::auth::digest::DigestAuth;
+use crate::auth::sigv4::AwsSigV4;
+use crate::auth::{parse_auth_comment, ApiKeyLocation, AuthScheme};
+use crate::config::get_config;
+use crate::models::request::validate_header;
+use crate::models::{
+    ConditionalHeader, Headers, HttpMethod, HttpRequest, MultipartBody, MultipartPart,
+};
+use crate::variables::capture::parse_capture_directive;
 use error::ParseError;
 use once_cell::sync::Lazy;
 use regex::Regex;
-use std::collections::HashMap;
-use std::path::PathBuf;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
 
 /// Cached regex pattern for parsing request lines (METHOD URL [HTTP/VERSION]).
+/// The method may be in any case (e.g., `get` pasted from logs).
 /// This is compiled once and reused to avoid repeated regex compilation overhead.
 static REQUEST_LINE_REGEX: Lazy<Regex> = Lazy::new(|| {
-    Regex::new(r"^([A-Z]+)\s+(\S+)(?:\s+(HTTP/\d+(?:\.\d+)?))?$")
+    Regex::new(r"^([A-Za-z]+)\s+(\S+)(?:\s+(HTTP/\d+(?:\.\d+)?))?$")
         .expect("Failed to compile request line regex")
 });
 
+/// Cached regex pattern for the `# @locale <tag>` directive.
+static LOCALE_DIRECTIVE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^(?:#|//)\s*@locale\s+(\S+)\s*$")
+        .expect("Failed to compile locale directive regex")
+});
+
+/// Cached regex pattern for the `# @name <ident>` directive.
+static NAME_DIRECTIVE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^(?:#|//)\s*@name\s+([A-Za-z_][\w-]*)\s*$")
+        .expect("Failed to compile name directive regex")
+});
+
+/// Cached regex pattern for the `# @timeout <milliseconds>` directive.
+static TIMEOUT_DIRECTIVE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^(?:#|//)\s*@timeout(?:\s+(.*?))?\s*$")
+        .expect("Failed to compile timeout directive regex")
+});
+
+/// Cached regex pattern for the `# @aws <region> <service> <accessKey> <secretKey>` directive.
+static AWS_DIRECTIVE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^(?:#|//)\s*@aws(?:\s+(.*?))?\s*$").expect("Failed to compile aws directive regex")
+});
+
+/// Cached regex pattern for the `# @retries <count>` directive.
+static RETRIES_DIRECTIVE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^(?:#|//)\s*@retries(?:\s+(.*?))?\s*$")
+        .expect("Failed to compile retries directive regex")
+});
+
+/// Cached regex pattern for the `# @encoding <charset>` directive.
+static ENCODING_DIRECTIVE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^(?:#|//)\s*@encoding\s+(\S+)\s*$")
+        .expect("Failed to compile encoding directive regex")
+});
+
+/// Cached regex pattern for the `# @filter <jsonpath>` directive.
+static FILTER_DIRECTIVE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^(?:#|//)\s*@filter\s+(\S.*?)\s*$")
+        .expect("Failed to compile filter directive regex")
+});
+
+/// Cached regex pattern for the `# @pipe <command>` directive.
+static PIPE_DIRECTIVE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^(?:#|//)\s*@pipe\s+(\S.*?)\s*$").expect("Failed to compile pipe directive regex")
+});
+
+/// Cached regex pattern for the `# @header-if <environment> Name: Value` directive.
+static HEADER_IF_DIRECTIVE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^(?:#|//)\s*@header-if(?:\s+(.*?))?\s*$")
+        .expect("Failed to compile header-if directive regex")
+});
+
+/// Cached regex pattern for the file-level `@use <path>` directive, written
+/// bare or as a comment (`# @use <path>`).
+static USE_DIRECTIVE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^(?:(?:#|//)\s*)?@use\s+([^\s=].*?)\s*$")
+        .expect("Failed to compile use directive regex")
+});
+
+/// Cached regex pattern for the `# @form` directive.
+static FORM_DIRECTIVE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^(?:#|//)\s*@form\s*$").expect("Failed to compile form directive regex")
+});
+
+/// Cached regex pattern for the `# @no-cookies` directive.
+static NO_COOKIES_DIRECTIVE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^(?:#|//)\s*@no-cookies\s*$")
+        .expect("Failed to compile no-cookies directive regex")
+});
+
+/// Cached regex pattern for the `# @multipart` directive.
+static MULTIPART_DIRECTIVE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^(?:#|//)\s*@multipart\s*$").expect("Failed to compile multipart directive regex")
+});
+
+/// Cached regex pattern for multipart part lines (`--field name=<name>` or
+/// `--file <name>=<path>[;type=<content type>]`).
+static MULTIPART_PART_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^--(field|file)\s+([^=\s]+)\s*=\s*(\S.*?)\s*$")
+        .expect("Failed to compile multipart part regex")
+});
+
+/// Cached regex pattern for body file references (`< path` or `<@ path`).
+static BODY_FILE_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^<(@?)\s+(\S.*?)\s*$").expect("Failed to compile body file regex"));
+
+/// Cached regex pattern for a `Name: Value` line written in header style
+/// (capitalized, hyphen-separated name).
+static HEADER_LIKE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^([A-Z][A-Za-z0-9]*(?:-[A-Za-z0-9]+)*)\s*:\s*\S")
+        .expect("Failed to compile header-like line regex")
+});
+
+/// Single-word header names recognized by `misplaced_header_name`.
+/// Hyphenated names (e.g., `Content-Type`, `X-Api-Key`) match by shape alone.
+const SINGLE_WORD_HEADERS: &[&str] = &[
+    "Accept",
+    "Authorization",
+    "Connection",
+    "Cookie",
+    "Expect",
+    "Forwarded",
+    "From",
+    "Host",
+    "Origin",
+    "Pragma",
+    "Prefer",
+    "Range",
+    "Referer",
+    "Upgrade",
+];
+
 /// Parses the content of an HTTP request file into a vector of requests.
 ///
 /// Requests are separated by lines containing only `###`. Comments (lines
-/// starting with `#` or `//`) are ignored. Each request block is parsed
-/// independently.
+/// starting with `#` or `//`) are ignored before the body; body lines are kept
+/// verbatim. Each request block is parsed independently.
 ///
 /// # Arguments
 ///
@@ -48,41 +169,165 @@ static REQUEST_LINE_REGEX: Lazy<Regex> = Lazy::new(|| {
 /// assert_eq!(requests.len(), 2);
 /// ```
 pub fn parse_file(content: &str, file_path: &PathBuf) -> Result<Vec<HttpRequest>, ParseError> {
+    Ok(parse_file_with_positions(content, file_path)?
+        .into_iter()
+        .map(|(request, _)| request)
+        .collect())
+}
+
+/// Parses an HTTP request file, keeping the byte range each request spans.
+///
+/// Works like [`parse_file`], but pairs every request with the range of
+/// `content` its block covers: from the first line after the preceding `###`
+/// (or the start of the file) up to the next `###` line (or the end of the
+/// file). Ranges are byte offsets into the original `content`, so they can be
+/// used for cursor lookups and editor edits even when the file uses `\r\n`
+/// line endings.
+///
+/// # Arguments
+///
+/// * `content` - The full content of the HTTP request file
+/// * `file_path` - Path to the file being parsed (for error reporting)
+///
+/// # Returns
+///
+/// A `Result` containing each request with its byte range, in file order, or
+/// a `ParseError` if parsing fails.
+///
+/// # Examples
+///
+/// ```
+/// use rest_client::parser::parse_file_with_positions;
+/// use std::path::PathBuf;
+///
+/// let content = "GET https://api.example.com/users\n###\nGET https://api.example.com/posts\n";
+///
+/// let requests = parse_file_with_positions(content, &PathBuf::from("test.http")).unwrap();
+/// assert_eq!(requests[0].1, 0..34);
+/// assert_eq!(requests[1].1, 38..72);
+/// ```
+pub fn parse_file_with_positions(
+    content: &str,
+    file_path: &PathBuf,
+) -> Result<Vec<(HttpRequest, Range<usize>)>, ParseError> {
     // Pre-allocate with estimated capacity for better performance
     let estimated_requests = content.matches("###").count().max(1);
     let mut requests = Vec::with_capacity(estimated_requests);
     let mut current_block = Vec::new();
     let mut block_start_line = 1;
-    let mut current_line = 1;
+    let mut block_start_offset = 0;
+    let mut offset = 0;
 
-    // Normalize line endings (handle both \r\n and \n)
-    let normalized_content = content.replace("\r\n", "\n");
+    for (index, raw_line) in content.split_inclusive('\n').enumerate() {
+        let current_line = index + 1;
+        // Strip the line ending (handle both \r\n and \n)
+        let line = raw_line
+            .strip_suffix('\n')
+            .map(|line| line.strip_suffix('\r').unwrap_or(line))
+            .unwrap_or(raw_line);
 
-    for line in normalized_content.lines() {
         // Check if this is a request delimiter
         if line.trim() == "###" {
             // Parse the accumulated block if it's not empty
             if !current_block.is_empty() {
                 let request = parse_request(&current_block, block_start_line, file_path)?;
-                requests.push(request);
+                requests.push((request, block_start_offset..offset));
                 current_block.clear();
             }
             block_start_line = current_line + 1;
+            block_start_offset = offset + raw_line.len();
+        } else if line.trim_start().starts_with('@')
+            && parse_use_directive(line).is_some()
+            && current_block
+                .iter()
+                .all(|(_, line)| is_comment_or_blank(line))
+        {
+            // A bare `@use` line before the request line imports another
+            // file; it isn't part of the request
         } else {
             current_block.push((current_line, line));
         }
-        current_line += 1;
+        offset += raw_line.len();
     }
 
     // Parse the last block if it exists
     if !current_block.is_empty() {
         let request = parse_request(&current_block, block_start_line, file_path)?;
-        requests.push(request);
+        requests.push((request, block_start_offset..offset));
     }
 
     Ok(requests)
 }
 
+/// Parses a file-level `@use <path>` directive, which imports the requests of
+/// another `.http` file.
+///
+/// The directive may be written bare (`@use ./auth.http`) or as a comment
+/// (`# @use ./auth.http`). A file variable named `use` (`@use = value`) is
+/// not a directive.
+///
+/// # Arguments
+///
+/// * `line` - The line to parse
+///
+/// # Returns
+///
+/// `Some(&str)` with the referenced path, or `None` if the line isn't a
+/// `@use` directive.
+///
+/// # Examples
+///
+/// ```
+/// use rest_client::parser::parse_use_directive;
+///
+/// assert_eq!(parse_use_directive("@use ./auth.http"), Some("./auth.http"));
+/// assert_eq!(parse_use_directive("# @use shared/auth.http"), Some("shared/auth.http"));
+/// assert_eq!(parse_use_directive("@use = ./auth.http"), None);
+/// assert_eq!(parse_use_directive("GET https://example.com"), None);
+/// ```
+pub fn parse_use_directive(line: &str) -> Option<&str> {
+    USE_DIRECTIVE_REGEX
+        .captures(line.trim())
+        .and_then(|captures| captures.get(1))
+        .map(|path| path.as_str())
+}
+
+/// Returns `true` if a line is blank or a `#` / `//` comment.
+fn is_comment_or_blank(line: &str) -> bool {
+    let trimmed = line.trim();
+    trimmed.is_empty() || trimmed.starts_with('#') || trimmed.starts_with("//")
+}
+
+/// Finds the request whose byte range contains a cursor position.
+///
+/// Ranges are half-open, so a cursor at the start of a `###` line belongs to
+/// no request; a cursor at the very end of the file belongs to the last one.
+///
+/// # Arguments
+///
+/// * `requests` - Requests with their byte ranges, as returned by
+///   [`parse_file_with_positions`]
+/// * `cursor_position` - Byte offset of the cursor in the file content
+///
+/// # Returns
+///
+/// The index of the request containing the cursor, or `None` if the cursor
+/// is on a `###` delimiter line or outside every request.
+pub fn request_index_at_cursor(
+    requests: &[(HttpRequest, Range<usize>)],
+    cursor_position: usize,
+) -> Option<usize> {
+    requests
+        .iter()
+        .position(|(_, range)| range.contains(&cursor_position))
+        .or_else(|| {
+            requests
+                .last()
+                .filter(|(_, range)| range.end == cursor_position)
+                .map(|_| requests.len() - 1)
+        })
+}
+
 /// Parses a single HTTP request block into an `HttpRequest` object.
 ///
 /// # Arguments
@@ -114,8 +359,24 @@ pub fn parse_request(
 
     let (request_line_num, request_line) = request_line_data;
 
+    // `# @name` must appear in the comments before the request line
+    let leading_comments = lines
+        .iter()
+        .position(|(num, _)| num == request_line_num)
+        .unwrap_or(0);
+    let name = parse_name_directive(&lines[..leading_comments]);
+
+    // Query parameters may continue on the lines right after the request line
+    let continuations = lines[leading_comments + 1..]
+        .iter()
+        .take_while(|(_, line)| is_query_continuation(line))
+        .count();
+    let last_url_idx = leading_comments + continuations;
+    let request_line =
+        fold_query_continuations(request_line, &lines[leading_comments + 1..=last_url_idx]);
+
     // Parse the request line (METHOD URL [HTTP_VERSION])
-    let (method, url, http_version) = parse_request_line(request_line, *request_line_num)?;
+    let (method, url, http_version) = parse_request_line(&request_line, *request_line_num)?;
 
     // Find where headers start (after request line) and where body starts (after blank line)
     let mut header_lines = Vec::new();
@@ -125,13 +386,16 @@ pub fn parse_request(
     for (idx, (line_num, line)) in lines.iter().enumerate() {
         let trimmed = line.trim();
 
-        // Skip until we pass the request line
+        // Skip until we pass the request line and its query continuations
         if !past_request_line {
             if line_num == request_line_num {
                 past_request_line = true;
             }
             continue;
         }
+        if idx <= last_url_idx {
+            continue;
+        }
 
         // Skip comments
         if trimmed.starts_with('#') || trimmed.starts_with("//") {
@@ -149,113 +413,283 @@ pub fn parse_request(
     }
 
     // Extract headers
-    let headers = extract_headers(&header_lines)?;
+    let mut headers = extract_headers(&header_lines)?;
+
+    // Apply `# @locale` directive unless Accept-Language is set explicitly
+    let directive_lines = &lines[..body_start_idx.unwrap_or(lines.len())];
+    if let Some(locale) = parse_locale_directive(directive_lines) {
+        if !headers
+            .keys()
+            .any(|name| name.eq_ignore_ascii_case("accept-language"))
+        {
+            headers.insert("Accept-Language".to_string(), locale);
+        }
+    }
 
-    // Extract body if present
-    let body = if let Some(start_idx) = body_start_idx {
-        let body_lines: Vec<&str> = lines[start_idx..]
-            .iter()
-            .map(|(_, line)| *line)
-            .filter(|line| {
-                let trimmed = line.trim();
-                !trimmed.starts_with('#') && !trimmed.starts_with("//")
-            })
-            .collect();
-        extract_body(&body_lines)
+    // `# @timeout` overrides the global timeout for this request
+    let timeout_override = parse_timeout_directive(directive_lines)?;
+
+    // `# @retries` overrides the configured retry count for this request
+    let retries_override = parse_retries_directive(directive_lines)?;
+
+    // `# @no-cookies` keeps the session cookie jar out of this request
+    let no_cookies = has_no_cookies_directive(directive_lines);
+
+    // `# @encoding` selects the charset the body is sent in
+    let body_encoding = parse_encoding_directive(directive_lines);
+
+    // `# @filter` narrows the displayed JSON response with a JSONPath
+    let filter = parse_filter_directive(directive_lines);
+
+    // `# @pipe` runs the response body through a shell command
+    let pipe = parse_pipe_directive(directive_lines);
+
+    // `# @header-if` adds headers only when a given environment is active
+    let conditional_headers = parse_header_if_directives(directive_lines)?;
+
+    // `# @digest` answers a Digest challenge with these credentials
+    let digest_auth = parse_digest_directive(directive_lines);
+
+    // `# @aws` signs the request with AWS SigV4 when it is sent
+    let aws_sigv4 = parse_aws_directive(directive_lines)?;
+
+    // `# @apikey` adds a key to the query string or headers
+    let api_key = parse_apikey_directive(directive_lines)?;
+
+    // `# @capture` directives may sit before the body or in the block that
+    // follows it; capture-like lines inside the body are part of the payload
+    let body_lines: Vec<&str> = lines[body_start_idx.unwrap_or(lines.len())..]
+        .iter()
+        .map(|(_, line)| *line)
+        .collect();
+    let capture_block = &body_lines[strip_trailing_captures(&body_lines).len()..];
+    let captures = directive_lines
+        .iter()
+        .map(|(_, line)| *line)
+        .chain(capture_block.iter().copied())
+        .filter_map(parse_capture_directive)
+        .collect();
+
+    // `# @form` builds a URL-encoded body from `key: value` lines
+    let is_form = has_form_directive(directive_lines);
+
+    // `# @multipart` builds a multipart/form-data body from `--field` and
+    // `--file` parts
+    let multipart = match body_start_idx {
+        Some(start_idx) if is_multipart_body(directive_lines, &headers, &lines[start_idx..]) => {
+            Some(parse_multipart_body(&lines[start_idx..], file_path)?)
+        }
+        _ => None,
+    };
+
+    let form_fields = match body_start_idx {
+        Some(start_idx) if is_form && multipart.is_none() => {
+            parse_form_fields(&lines[start_idx..])?
+        }
+        _ => Vec::new(),
+    };
+
+    // Extract body if present. Comment stripping only applies to the header
+    // region: body lines such as `#hashtag` or `// note` are sent verbatim.
+    let body = if multipart.is_some() {
+        None
+    } else if let Some(start_idx) = body_start_idx {
+        if is_form {
+            encode_form_body(&form_fields)
+        } else {
+            let body_lines: Vec<&str> = lines[start_idx..].iter().map(|(_, line)| *line).collect();
+            extract_body(&body_lines)
+        }
     } else {
         None
     };
 
+    if is_form && body.is_some() && !headers.contains_key("Content-Type") {
+        headers.append(
+            "Content-Type".to_string(),
+            "application/x-www-form-urlencoded".to_string(),
+        );
+    }
+
+    // A body consisting of `< path` or `<@ path` is read from disk when the
+    // request is sent; only the path is kept here
+    let mut raw_body = false;
+    let mut body_file = None;
+    let body = match body {
+        Some(body) => match parse_body_file_reference(&body) {
+            Some((path, substitute)) => {
+                raw_body = !substitute;
+                body_file = Some(resolve_relative_path(path, file_path));
+                None
+            }
+            None => Some(body),
+        },
+        None => None,
+    };
+
     // Generate a unique ID for the request
     let id = generate_request_id(file_path, *request_line_num);
 
-    Ok(HttpRequest {
+    let mut request = HttpRequest {
         id,
+        name,
         method,
         url,
         http_version,
         headers,
         body,
+        raw_body,
+        body_file,
+        body_encoding,
+        timeout_override,
+        retries_override,
+        no_cookies,
+        filter,
+        pipe,
+        conditional_headers,
+        captures,
+        digest_auth,
+        aws_sigv4,
+        multipart,
+        form_fields,
+        query_api_key: None,
+        api_key_header: None,
         line_number: *request_line_num,
         file_path: file_path.clone(),
-    })
+    };
+
+    // A query key is appended once its variables are resolved, so it can be
+    // percent-encoded
+    if let Some(AuthScheme::ApiKey {
+        name,
+        value,
+        location,
+    }) = api_key
+    {
+        match location {
+            ApiKeyLocation::Query => request.query_api_key = Some((name, value)),
+            ApiKeyLocation::Header => {
+                request.headers.insert(name.clone(), value);
+                request.api_key_header = Some(name);
+            }
+        }
+    }
+
+    Ok(request)
 }
 
-/// Parses the request line to extract method, URL, and optional HTTP version.
+/// Finds a `# @locale <tag>` directive in the given lines.
 ///
-/// Supports both formats:
-/// - Simple: `GET https://example.com`
-/// - Full: `GET https://example.com HTTP/1.1`
+/// The directive sets the request's `Accept-Language` header, e.g.
+/// `# @locale fr-FR`. If several are present, the last one wins.
 ///
 /// # Arguments
 ///
-/// * `line` - The request line text
-/// * `line_num` - Line number for error reporting
+/// * `lines` - Vector of (line_number, line_content) tuples
 ///
 /// # Returns
 ///
-/// A tuple of (method, url, optional_http_version) on success, or a `ParseError`.
-pub fn parse_request_line(
-    line: &str,
-    line_num: usize,
-) -> Result<(HttpMethod, String, Option<String>), ParseError> {
-    let trimmed = line.trim();
-
-    // Use cached regex to avoid repeated compilations (performance optimization)
-    if let Some(captures) = REQUEST_LINE_REGEX.captures(trimmed) {
-        // Extract method
-        let method_str = captures.get(1).unwrap().as_str();
-        let method = HttpMethod::from_str(method_str).ok_or(ParseError::InvalidMethod {
-            method: method_str.to_string(),
-            line: line_num,
-        })?;
-
-        // Extract URL
-        let url = captures.get(2).unwrap().as_str();
-
-        // Validate URL format (must start with http:// or https://)
-        if !url.starts_with("http://") && !url.starts_with("https://") {
-            return Err(ParseError::InvalidUrl {
-                url: url.to_string(),
-                line: line_num,
-            });
-        }
-
-        // Extract optional HTTP version
-        let http_version = captures.get(3).map(|m| m.as_str().to_string());
+/// `Some(String)` with the locale tag, or `None` if no directive is present.
+pub fn parse_locale_directive(lines: &[(usize, &str)]) -> Option<String> {
+    lines
+        .iter()
+        .rev()
+        .find_map(|(_, line)| LOCALE_DIRECTIVE_REGEX.captures(line.trim()))
+        .map(|captures| captures[1].to_string())
+}
 
-        Ok((method, url.to_string(), http_version))
-    } else {
-        // Try to extract just the method to give better error
-        let parts: Vec<&str> = trimmed.split_whitespace().collect();
-        if parts.is_empty() {
-            return Err(ParseError::MissingUrl { line: line_num });
-        }
+/// Finds a `# @name <ident>` directive in the given lines.
+///
+/// The name must start with a letter or underscore and may contain letters,
+/// digits, `_`, and `-`. A directive without a valid name is ignored. If
+/// several are present, the last one wins.
+///
+/// # Arguments
+///
+/// * `lines` - The comment lines preceding the request line
+///
+/// # Returns
+///
+/// `Some(String)` with the request name, or `None` if there is none.
+pub fn parse_name_directive(lines: &[(usize, &str)]) -> Option<String> {
+    lines
+        .iter()
+        .rev()
+        .find_map(|(_, line)| NAME_DIRECTIVE_REGEX.captures(line.trim()))
+        .map(|captures| captures[1].to_string())
+}
 
-        if parts.len() == 1 {
-            return Err(ParseError::MissingUrl { line: line_num });
-        }
+/// Finds a `# @encoding <charset>` directive in the given lines.
+///
+/// The charset (e.g., `iso-8859-1`) is the encoding the request body is
+/// converted to before sending. It is not validated here; an unknown charset
+/// is reported when the request is executed. If several are present, the
+/// last one wins.
+///
+/// # Arguments
+///
+/// * `lines` - Vector of (line_number, line_content) tuples
+///
+/// # Returns
+///
+/// `Some(String)` with the charset label, or `None` if no directive is present.
+pub fn parse_encoding_directive(lines: &[(usize, &str)]) -> Option<String> {
+    lines
+        .iter()
+        .rev()
+        .find_map(|(_, line)| ENCODING_DIRECTIVE_REGEX.captures(line.trim()))
+        .map(|captures| captures[1].to_string())
+}
 
-        // If we have method but invalid format, check if method is valid
-        if HttpMethod::from_str(parts[0]).is_none() {
-            return Err(ParseError::InvalidMethod {
-                method: parts[0].to_string(),
-                line: line_num,
-            });
-        }
+/// Finds a `# @filter <jsonpath>` directive in the given lines.
+///
+/// The expression (e.g., `$.data.items[*].id`) selects the part of a JSON
+/// response to display. It is not validated here; an invalid expression is
+/// reported alongside the unfiltered response. If several are present, the
+/// last one wins.
+///
+/// # Arguments
+///
+/// * `lines` - Vector of (line_number, line_content) tuples
+///
+/// # Returns
+///
+/// `Some(String)` with the JSONPath expression, or `None` if no directive is
+/// present.
+pub fn parse_filter_directive(lines: &[(usize, &str)]) -> Option<String> {
+    lines
+        .iter()
+        .rev()
+        .find_map(|(_, line)| FILTER_DIRECTIVE_REGEX.captures(line.trim()))
+        .map(|captures| captures[1].to_string())
+}
 
-        // Otherwise it's likely an invalid URL
-        Err(ParseError::InvalidUrl {
-            url: parts.get(1).unwrap_or(&"").to_string(),
-            line: line_num,
-        })
-    }
+/// Finds a `# @pipe <command>` directive in the given lines.
+///
+/// The command (e.g., `jq '.data'`) receives the response body on stdin and
+/// its output is displayed in place of the body. It is only run when
+/// `allowPipeCommands` is enabled. If several are present, the last one wins.
+///
+/// # Arguments
+///
+/// * `lines` - Vector of (line_number, line_content) tuples
+///
+/// # Returns
+///
+/// `Some(String)` with the command, or `None` if no directive is present.
+pub fn parse_pipe_directive(lines: &[(usize, &str)]) -> Option<String> {
+    lines
+        .iter()
+        .rev()
+        .find_map(|(_, line)| PIPE_DIRECTIVE_REGEX.captures(line.trim()))
+        .map(|captures| captures[1].to_string())
 }
 
-/// Extracts headers from header lines.
+/// Collects `# @header-if <environment> Name: Value` directives in the given lines.
 ///
-/// Headers must be in the format "Name: Value". Lines that don't match this
-/// format will result in an error.
+/// Each directive adds a header that is only sent while the named
+/// environment is active, e.g. `# @header-if dev X-Debug: true`. All
+/// directives are kept, in source order.
 ///
 /// # Arguments
 ///
@@ -263,55 +697,743 @@ pub fn parse_request_line(
 ///
 /// # Returns
 ///
-/// A `HashMap` of header names to values on success, or a `ParseError`.
-pub fn extract_headers(lines: &[(usize, &str)]) -> Result<HashMap<String, String>, ParseError> {
-    let mut headers = HashMap::new();
+/// `Ok(Vec<ConditionalHeader>)` with the declared headers, or
+/// `ParseError::InvalidHeader` if a directive is missing the environment or
+/// has no `Name: Value` header.
+pub fn parse_header_if_directives(
+    lines: &[(usize, &str)],
+) -> Result<Vec<ConditionalHeader>, ParseError> {
+    let mut headers = Vec::new();
 
     for (line_num, line) in lines {
-        let trimmed = line.trim();
-
-        // Skip empty lines
-        if trimmed.is_empty() {
+        let Some(captures) = HEADER_IF_DIRECTIVE_REGEX.captures(line.trim()) else {
             continue;
-        }
-
-        // Headers must contain a colon
-        if let Some(colon_pos) = trimmed.find(':') {
-            let name = trimmed[..colon_pos].trim().to_string();
-            let value = trimmed[colon_pos + 1..].trim().to_string();
-
-            if name.is_empty() {
-                return Err(ParseError::InvalidHeader {
-                    header: trimmed.to_string(),
-                    line: *line_num,
-                });
-            }
-
-            headers.insert(name, value);
-        } else {
-            return Err(ParseError::InvalidHeader {
-                header: trimmed.to_string(),
+        };
+        let rest = captures.get(1).map_or("", |rest| rest.as_str());
+
+        let header = rest
+            .split_once(char::is_whitespace)
+            .and_then(|(environment, header)| {
+                let (name, value) = header.split_once(':')?;
+                let name = name.trim();
+                if name.is_empty() || name.contains(char::is_whitespace) {
+                    return None;
+                }
+                Some(ConditionalHeader {
+                    environment: environment.to_string(),
+                    name: name.to_string(),
+                    value: value.trim().to_string(),
+                })
+            })
+            .ok_or_else(|| ParseError::InvalidHeader {
+                header: line.trim().to_string(),
                 line: *line_num,
-            });
-        }
+            })?;
+        headers.push(header);
     }
 
     Ok(headers)
 }
 
-/// Extracts the request body from body lines.
+/// Finds a `# @digest <username> <password>` directive in the given lines.
 ///
-/// The body is everything after the first blank line in the request block.
-/// Comment lines are filtered out.
+/// The password is everything after the username, so it may contain spaces.
+/// If several are present, the last one wins.
 ///
 /// # Arguments
 ///
-/// * `lines` - Slice of body line strings
+/// * `lines` - Vector of (line_number, line_content) tuples
 ///
 /// # Returns
 ///
-/// `Some(String)` if there's a non-empty body, `None` otherwise.
-pub fn extract_body(lines: &[&str]) -> Option<String> {
+/// `Some(DigestAuth)` with the credentials, or `None` if no complete
+/// directive is present.
+pub fn parse_digest_directive(lines: &[(usize, &str)]) -> Option<DigestAuth> {
+    lines
+        .iter()
+        .rev()
+        .find_map(|(_, line)| match parse_auth_comment(line) {
+            AuthScheme::Digest { username, password } => Some(DigestAuth::new(username, password)),
+            _ => None,
+        })
+}
+
+/// Finds a `# @apikey <query|header> <name> <value>` directive in the given
+/// lines.
+///
+/// If several are present, the last one wins.
+///
+/// # Arguments
+///
+/// * `lines` - Vector of (line_number, line_content) tuples
+///
+/// # Returns
+///
+/// `Ok(Some(AuthScheme::ApiKey))` with the key's name, value, and location,
+/// `Ok(None)` if no complete directive is present, or
+/// `ParseError::InvalidHeader` if a header key's name isn't a valid header
+/// name.
+pub fn parse_apikey_directive(lines: &[(usize, &str)]) -> Result<Option<AuthScheme>, ParseError> {
+    let Some((line_num, line, scheme)) =
+        lines
+            .iter()
+            .rev()
+            .find_map(|(line_num, line)| match parse_auth_comment(line) {
+                scheme @ AuthScheme::ApiKey { .. } => Some((*line_num, line, scheme)),
+                _ => None,
+            })
+    else {
+        return Ok(None);
+    };
+
+    // Names built from variables can only be checked once they are resolved
+    if let AuthScheme::ApiKey {
+        name,
+        location: ApiKeyLocation::Header,
+        ..
+    } = &scheme
+    {
+        if !name.contains("{{") && validate_header(name, "").is_err() {
+            return Err(ParseError::InvalidHeader {
+                header: line.trim().to_string(),
+                line: line_num,
+            });
+        }
+    }
+
+    Ok(Some(scheme))
+}
+
+/// Finds a `# @aws <region> <service> <accessKey> <secretKey> [sessionToken]`
+/// directive in the given lines.
+///
+/// If several are present, the last one wins.
+///
+/// # Arguments
+///
+/// * `lines` - Vector of (line_number, line_content) tuples
+///
+/// # Returns
+///
+/// `Ok(Some(AwsSigV4))` with the credentials, `Ok(None)` if no directive is
+/// present, or `ParseError::InvalidAwsDirective` if it has too few or too
+/// many arguments.
+pub fn parse_aws_directive(lines: &[(usize, &str)]) -> Result<Option<AwsSigV4>, ParseError> {
+    let Some((line_num, line, captures)) = lines.iter().rev().find_map(|(line_num, line)| {
+        AWS_DIRECTIVE_REGEX
+            .captures(line.trim())
+            .map(|captures| (*line_num, line, captures))
+    }) else {
+        return Ok(None);
+    };
+
+    let args = captures.get(1).map_or("", |args| args.as_str());
+    AwsSigV4::parse(args)
+        .map(Some)
+        .ok_or_else(|| ParseError::InvalidAwsDirective {
+            directive: line.trim().to_string(),
+            line: line_num,
+        })
+}
+
+/// Returns `true` if the given lines contain a `# @form` directive.
+///
+/// # Arguments
+///
+/// * `lines` - Vector of (line_number, line_content) tuples
+pub fn has_form_directive(lines: &[(usize, &str)]) -> bool {
+    lines
+        .iter()
+        .any(|(_, line)| FORM_DIRECTIVE_REGEX.is_match(line.trim()))
+}
+
+/// Returns `true` if the given lines contain a `# @no-cookies` directive.
+///
+/// # Arguments
+///
+/// * `lines` - Vector of (line_number, line_content) tuples
+pub fn has_no_cookies_directive(lines: &[(usize, &str)]) -> bool {
+    lines
+        .iter()
+        .any(|(_, line)| NO_COOKIES_DIRECTIVE_REGEX.is_match(line.trim()))
+}
+
+/// Reads `key: value` body lines of a `# @form` request.
+///
+/// Keys and values are trimmed and kept in source order. Blank lines and
+/// comments are skipped.
+///
+/// # Arguments
+///
+/// * `lines` - Vector of (line_number, line_content) tuples for the body
+///
+/// # Returns
+///
+/// `Ok(Vec)` with the fields, or `ParseError::InvalidFormField` if a line
+/// has no key.
+pub fn parse_form_fields(lines: &[(usize, &str)]) -> Result<Vec<(String, String)>, ParseError> {
+    let mut fields = Vec::new();
+
+    for (line_num, line) in lines {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') || trimmed.starts_with("//") {
+            continue;
+        }
+
+        let invalid = || ParseError::InvalidFormField {
+            field: trimmed.to_string(),
+            line: *line_num,
+        };
+        let (key, value) = trimmed.split_once(':').ok_or_else(invalid)?;
+        let key = key.trim();
+        if key.is_empty() {
+            return Err(invalid());
+        }
+
+        fields.push((key.to_string(), value.trim().to_string()));
+    }
+
+    Ok(fields)
+}
+
+/// Serializes form fields into a URL-encoded body, before substitution.
+///
+/// Keys and values are percent-encoded and joined with `&`. `{{variable}}`
+/// references are left unencoded so the body still shows them; the body
+/// that is sent is encoded again from the resolved fields (see
+/// [`HttpRequest::form_fields`]).
+///
+/// # Arguments
+///
+/// * `fields` - The fields read by [`parse_form_fields`]
+///
+/// # Returns
+///
+/// `Some(String)` with the encoded body, or `None` if there are no fields.
+///
+/// # Examples
+///
+/// ```
+/// use rest_client::parser::encode_form_body;
+///
+/// let fields = vec![
+///     ("name".to_string(), "Jane Doe".to_string()),
+///     ("role".to_string(), "a&b".to_string()),
+/// ];
+/// assert_eq!(
+///     encode_form_body(&fields),
+///     Some("name=Jane+Doe&role=a%26b".to_string())
+/// );
+/// ```
+pub fn encode_form_body(fields: &[(String, String)]) -> Option<String> {
+    if fields.is_empty() {
+        return None;
+    }
+
+    let encoded: Vec<String> = fields
+        .iter()
+        .map(|(key, value)| {
+            format!(
+                "{}={}",
+                encode_form_component(key),
+                encode_form_component(value)
+            )
+        })
+        .collect();
+    Some(encoded.join("&"))
+}
+
+/// Percent-encodes a form key or value, keeping `{{variable}}` references.
+fn encode_form_component(text: &str) -> String {
+    let mut encoded = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find("{{") {
+        let Some(end) = rest[start..].find("}}") else {
+            break;
+        };
+        let end = start + end + 2;
+        encoded.extend(url::form_urlencoded::byte_serialize(
+            &rest.as_bytes()[..start],
+        ));
+        encoded.push_str(&rest[start..end]);
+        rest = &rest[end..];
+    }
+
+    encoded.extend(url::form_urlencoded::byte_serialize(rest.as_bytes()));
+    encoded
+}
+
+/// Returns `true` if a request body should be read as multipart parts.
+///
+/// That is the case with a `# @multipart` directive, or with a
+/// `multipart/form-data` Content-Type that has no boundary (so the body can't
+/// be a hand-written payload) and a body starting with a part line.
+fn is_multipart_body(
+    directive_lines: &[(usize, &str)],
+    headers: &Headers,
+    body_lines: &[(usize, &str)],
+) -> bool {
+    if directive_lines
+        .iter()
+        .any(|(_, line)| MULTIPART_DIRECTIVE_REGEX.is_match(line.trim()))
+    {
+        return true;
+    }
+
+    let is_multipart_type = headers.get("Content-Type").is_some_and(|content_type| {
+        let content_type = content_type.to_ascii_lowercase();
+        content_type.starts_with("multipart/form-data") && !content_type.contains("boundary=")
+    });
+
+    is_multipart_type
+        && body_lines
+            .iter()
+            .find(|(_, line)| !line.trim().is_empty())
+            .is_some_and(|(_, line)| MULTIPART_PART_REGEX.is_match(line.trim()))
+}
+
+/// Parses the parts of a multipart body.
+///
+/// Each part starts with `--field name=<name>`, followed by the field value
+/// on the next lines, or `--file <name>=<path>`, optionally followed by
+/// `;type=<content type>` to override the inferred type. File paths are resolved
+/// against the directory of the request file; the file is read when the
+/// request is sent. Blank lines around field values are trimmed.
+///
+/// # Arguments
+///
+/// * `lines` - Vector of (line_number, line_content) tuples for the body
+/// * `file_path` - Path to the request file, for resolving file parts
+///
+/// # Returns
+///
+/// The parsed `MultipartBody`, or `ParseError::InvalidMultipartPart` if the
+/// body has content before the first part, a malformed part line, or a
+/// value after a file part.
+pub fn parse_multipart_body(
+    lines: &[(usize, &str)],
+    file_path: &Path,
+) -> Result<MultipartBody, ParseError> {
+    let text_lines: Vec<&str> = lines.iter().map(|(_, line)| *line).collect();
+    let lines = &lines[..strip_trailing_captures(&text_lines).len()];
+
+    let mut parts = Vec::new();
+    let mut value_lines: Vec<&str> = Vec::new();
+
+    for (line_num, line) in lines {
+        let trimmed = line.trim();
+        let invalid = || ParseError::InvalidMultipartPart {
+            part: trimmed.to_string(),
+            line: *line_num,
+        };
+
+        if trimmed.starts_with("--field") || trimmed.starts_with("--file") {
+            let captures = MULTIPART_PART_REGEX.captures(trimmed).ok_or_else(invalid)?;
+            push_field_value(&mut parts, &mut value_lines);
+
+            let part = if &captures[1] == "field" {
+                if &captures[2] != "name" {
+                    return Err(invalid());
+                }
+                MultipartPart::Field {
+                    name: captures[3].to_string(),
+                    value: String::new(),
+                }
+            } else {
+                let (path, content_type) = split_file_part_type(&captures[3]);
+                MultipartPart::File {
+                    name: captures[2].to_string(),
+                    path: resolve_relative_path(path, file_path),
+                    content_type,
+                }
+            };
+            parts.push(part);
+            continue;
+        }
+
+        match parts.last() {
+            Some(MultipartPart::Field { .. }) => value_lines.push(line),
+            _ if trimmed.is_empty() => {}
+            _ => return Err(invalid()),
+        }
+    }
+    push_field_value(&mut parts, &mut value_lines);
+
+    Ok(MultipartBody { parts })
+}
+
+/// Splits a `;type=<content type>` suffix off a multipart file path.
+///
+/// # Arguments
+///
+/// * `value` - The file part value, e.g. `./photo.png;type=image/webp`
+///
+/// # Returns
+///
+/// The path and the content type, if one was given.
+pub fn split_file_part_type(value: &str) -> (&str, Option<String>) {
+    match value.rsplit_once(";type=") {
+        Some((path, content_type)) if !content_type.trim().is_empty() => {
+            (path.trim(), Some(content_type.trim().to_string()))
+        }
+        _ => (value, None),
+    }
+}
+
+/// Sets the collected value lines on the last field part.
+fn push_field_value(parts: &mut [MultipartPart], value_lines: &mut Vec<&str>) {
+    if let Some(MultipartPart::Field { value, .. }) = parts.last_mut() {
+        *value = value_lines.join("\n").trim_matches('\n').to_string();
+    }
+    value_lines.clear();
+}
+
+/// Finds a `# @timeout <milliseconds>` directive in the given lines.
+///
+/// The timeout overrides the global timeout for a single request, e.g.
+/// `# @timeout 60000` waits up to a minute. If several are present, the last
+/// one wins.
+///
+/// # Arguments
+///
+/// * `lines` - Vector of (line_number, line_content) tuples
+///
+/// # Returns
+///
+/// `Ok(Some(u64))` with the timeout in milliseconds, `Ok(None)` if no
+/// directive is present, or `ParseError::InvalidTimeout` if the value is not a
+/// positive integer.
+pub fn parse_timeout_directive(lines: &[(usize, &str)]) -> Result<Option<u64>, ParseError> {
+    let Some((line_num, captures)) = lines.iter().rev().find_map(|(line_num, line)| {
+        TIMEOUT_DIRECTIVE_REGEX
+            .captures(line.trim())
+            .map(|captures| (*line_num, captures))
+    }) else {
+        return Ok(None);
+    };
+
+    let value = captures.get(1).map_or("", |value| value.as_str());
+    match value.parse::<u64>() {
+        Ok(timeout) if timeout > 0 => Ok(Some(timeout)),
+        _ => Err(ParseError::InvalidTimeout {
+            value: value.to_string(),
+            line: line_num,
+        }),
+    }
+}
+
+/// Finds a `# @retries <count>` directive in the given lines.
+///
+/// The count overrides the configured `maxRetries` for a single request, e.g.
+/// `# @retries 3`; `# @retries 0` disables retries. If several are present,
+/// the last one wins.
+///
+/// # Arguments
+///
+/// * `lines` - Vector of (line_number, line_content) tuples
+///
+/// # Returns
+///
+/// `Ok(Some(u32))` with the retry count, `Ok(None)` if no directive is
+/// present, or `ParseError::InvalidRetries` if the value is not a whole number.
+pub fn parse_retries_directive(lines: &[(usize, &str)]) -> Result<Option<u32>, ParseError> {
+    let Some((line_num, captures)) = lines.iter().rev().find_map(|(line_num, line)| {
+        RETRIES_DIRECTIVE_REGEX
+            .captures(line.trim())
+            .map(|captures| (*line_num, captures))
+    }) else {
+        return Ok(None);
+    };
+
+    let value = captures.get(1).map_or("", |value| value.as_str());
+    value
+        .parse::<u32>()
+        .map(Some)
+        .map_err(|_| ParseError::InvalidRetries {
+            value: value.to_string(),
+            line: line_num,
+        })
+}
+
+/// Returns the name of a disabled directive, such as `capture` for
+/// `# !@capture token = $.token` when `prefix` is `"!"`.
+///
+/// Disabled directives never match a directive pattern, so they are ignored
+/// by the parser; this function only identifies them for reporting.
+///
+/// # Arguments
+///
+/// * `line` - A line from an .http file
+/// * `prefix` - The configured disabled directive prefix
+///
+/// # Returns
+///
+/// `Some(&str)` with the directive name, or `None` if the line is not a
+/// disabled directive.
+///
+/// # Examples
+///
+/// ```
+/// use rest_client::parser::disabled_directive_name;
+///
+/// assert_eq!(disabled_directive_name("# !@capture token = $.token", "!"), Some("capture"));
+/// assert_eq!(disabled_directive_name("// !@locale fr-FR", "!"), Some("locale"));
+/// assert_eq!(disabled_directive_name("# @capture token = $.token", "!"), None);
+/// ```
+pub fn disabled_directive_name<'a>(line: &'a str, prefix: &str) -> Option<&'a str> {
+    if prefix.is_empty() {
+        return None;
+    }
+
+    let trimmed = line.trim_start();
+    let comment = trimmed
+        .strip_prefix('#')
+        .or_else(|| trimmed.strip_prefix("//"))?;
+    let directive = comment
+        .trim_start()
+        .strip_prefix(prefix)?
+        .strip_prefix('@')?;
+
+    let name_end = directive
+        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_' || c == '-'))
+        .unwrap_or(directive.len());
+    let name = &directive[..name_end];
+
+    if name.is_empty() {
+        None
+    } else {
+        Some(name)
+    }
+}
+
+/// Returns the header name of a body line that looks like a misplaced header,
+/// such as `Authorization` for `Authorization: Bearer abc`.
+///
+/// A header added below the blank line that starts the body is silently sent
+/// as body content. Only names written like headers are recognized: a
+/// capitalized name that is hyphenated (`Content-Type`, `X-Api-Key`) or a
+/// common single-word header (`Authorization`, `Accept`), so JSON, form, and
+/// most YAML bodies don't match.
+///
+/// # Arguments
+///
+/// * `line` - A body line from an .http file
+///
+/// # Returns
+///
+/// `Some(&str)` with the header name, or `None` if the line doesn't look like
+/// a header.
+///
+/// # Examples
+///
+/// ```
+/// use rest_client::parser::misplaced_header_name;
+///
+/// assert_eq!(misplaced_header_name("X-Api-Key: abc123"), Some("X-Api-Key"));
+/// assert_eq!(misplaced_header_name("Authorization: Bearer abc"), Some("Authorization"));
+/// assert_eq!(misplaced_header_name("name: John"), None);
+/// assert_eq!(misplaced_header_name("  \"Accept\": \"*/*\","), None);
+/// ```
+pub fn misplaced_header_name(line: &str) -> Option<&str> {
+    let name = HEADER_LIKE_REGEX.captures(line.trim())?.get(1)?.as_str();
+
+    if name.contains('-') || SINGLE_WORD_HEADERS.contains(&name) {
+        Some(name)
+    } else {
+        None
+    }
+}
+
+/// Parses the request line to extract method, URL, and optional HTTP version.
+///
+/// Supports both formats:
+/// - Simple: `GET https://example.com`
+/// - Full: `GET https://example.com HTTP/1.1`
+///
+/// The method is case-insensitive, so `get` and `Get` are read as `GET`.
+///
+/// # Arguments
+///
+/// * `line` - The request line text
+/// * `line_num` - Line number for error reporting
+///
+/// # Returns
+///
+/// A tuple of (method, url, optional_http_version) on success, or a `ParseError`.
+pub fn parse_request_line(
+    line: &str,
+    line_num: usize,
+) -> Result<(HttpMethod, String, Option<String>), ParseError> {
+    let trimmed = line.trim();
+
+    // Use cached regex to avoid repeated compilations (performance optimization)
+    if let Some(captures) = REQUEST_LINE_REGEX.captures(trimmed) {
+        // Extract method
+        let method_str = captures.get(1).unwrap().as_str();
+        let method = HttpMethod::from_str(method_str).ok_or(ParseError::InvalidMethod {
+            method: method_str.to_string(),
+            line: line_num,
+        })?;
+
+        // Extract URL
+        let url = captures.get(2).unwrap().as_str();
+
+        // Validate URL format (must start with http:// or https://)
+        if !url.starts_with("http://") && !url.starts_with("https://") {
+            return Err(ParseError::InvalidUrl {
+                url: url.to_string(),
+                line: line_num,
+            });
+        }
+
+        // Extract optional HTTP version
+        let http_version = captures.get(3).map(|m| m.as_str().to_string());
+
+        Ok((method, url.to_string(), http_version))
+    } else {
+        // Try to extract just the method to give better error
+        let parts: Vec<&str> = trimmed.split_whitespace().collect();
+        if parts.is_empty() {
+            return Err(ParseError::MissingUrl { line: line_num });
+        }
+
+        if parts.len() == 1 {
+            return Err(ParseError::MissingUrl { line: line_num });
+        }
+
+        // If we have method but invalid format, check if method is valid
+        if HttpMethod::from_str(parts[0]).is_none() {
+            return Err(ParseError::InvalidMethod {
+                method: parts[0].to_string(),
+                line: line_num,
+            });
+        }
+
+        // A raw space (often from an unencoded value) splits the URL in two;
+        // report the whole URL so the error points at the space
+        let url = trimmed[parts[0].len()..].trim();
+        let url = match url.rsplit_once(char::is_whitespace) {
+            Some((rest, version)) if version.starts_with("HTTP/") => rest.trim_end(),
+            _ => url,
+        };
+
+        // Otherwise it's likely an invalid URL
+        Err(ParseError::InvalidUrl {
+            url: url.to_string(),
+            line: line_num,
+        })
+    }
+}
+
+/// Returns `true` if a line continues the request URL's query string, i.e.
+/// starts with `?` or `&` (after indentation).
+fn is_query_continuation(line: &str) -> bool {
+    let trimmed = line.trim_start();
+    trimmed.starts_with('?') || trimmed.starts_with('&')
+}
+
+/// Appends query continuation lines to the URL of a request line.
+///
+/// Each continuation is trimmed and the segments are concatenated in order,
+/// so `GET https://example.com/search` followed by `  ?q=rust` and
+/// `  &limit=20` becomes `GET https://example.com/search?q=rust&limit=20`.
+/// A trailing HTTP version stays at the end of the line.
+fn fold_query_continuations(request_line: &str, continuations: &[(usize, &str)]) -> String {
+    let request_line = request_line.trim();
+    let query: String = continuations.iter().map(|(_, line)| line.trim()).collect();
+
+    if query.is_empty() {
+        return request_line.to_string();
+    }
+
+    match request_line.rsplit_once(char::is_whitespace) {
+        Some((start, version)) if version.starts_with("HTTP/") => {
+            format!("{}{} {}", start.trim_end(), query, version)
+        }
+        _ => format!("{}{}", request_line, query),
+    }
+}
+
+/// Extracts headers from header lines.
+///
+/// Headers must be in the format "Name: Value". A line starting with a space
+/// or tab continues the previous header's value (obsolete line folding), and
+/// is joined to it with a single space. Other lines that don't match this
+/// format will result in an error.
+///
+/// # Arguments
+///
+/// * `lines` - Vector of (line_number, line_content) tuples
+///
+/// # Returns
+///
+/// The headers in source order on success, or a `ParseError`. Repeated
+/// headers are kept.
+pub fn extract_headers(lines: &[(usize, &str)]) -> Result<Headers, ParseError> {
+    let mut headers: Vec<(String, String)> = Vec::new();
+
+    for (line_num, line) in lines {
+        let trimmed = line.trim();
+
+        // Skip empty lines
+        if trimmed.is_empty() {
+            continue;
+        }
+
+        // A line starting with whitespace continues the previous header's
+        // value (RFC 7230 obs-fold); the fold is replaced by a single space
+        if line.starts_with([' ', '\t']) {
+            if let Some((_, value)) = headers.last_mut() {
+                if !value.is_empty() {
+                    value.push(' ');
+                }
+                value.push_str(trimmed);
+                continue;
+            }
+        }
+
+        // Headers must contain a colon
+        if let Some(colon_pos) = trimmed.find(':') {
+            let name = trimmed[..colon_pos].trim().to_string();
+            let value = trimmed[colon_pos + 1..].trim().to_string();
+
+            if name.is_empty() {
+                return Err(ParseError::InvalidHeader {
+                    header: trimmed.to_string(),
+                    line: *line_num,
+                });
+            }
+
+            headers.push((name, value));
+        } else {
+            return Err(ParseError::InvalidHeader {
+                header: trimmed.to_string(),
+                line: *line_num,
+            });
+        }
+    }
+
+    Ok(headers.into_iter().collect())
+}
+
+/// Extracts the request body from body lines.
+///
+/// The body is everything after the first blank line in the request block.
+/// Lines are kept verbatim, including ones that look like comments, except
+/// for a trailing run of `# @capture` directives (enabled or disabled)
+/// separated from the content by a blank line. Captures may be placed after
+/// the body and are not part of the payload.
+///
+/// # Arguments
+///
+/// * `lines` - Slice of body line strings
+///
+/// # Returns
+///
+/// `Some(String)` if there's a non-empty body, `None` otherwise.
+pub fn extract_body(lines: &[&str]) -> Option<String> {
+    let lines = strip_trailing_captures(lines);
     if lines.is_empty() {
         return None;
     }
@@ -335,6 +1457,78 @@ pub fn extract_body(lines: &[&str]) -> Option<String> {
     }
 }
 
+/// Recognizes a body that references a file: `< path` or `<@ path`.
+///
+/// # Arguments
+///
+/// * `body` - The extracted request body
+///
+/// # Returns
+///
+/// `Some((path, substitute))` if the body is a single file reference, where
+/// `substitute` is `true` for `<@` (variables in the file are resolved), or
+/// `None` for an inline body.
+///
+/// # Examples
+///
+/// ```
+/// use rest_client::parser::parse_body_file_reference;
+///
+/// assert_eq!(parse_body_file_reference("< ./body.json"), Some(("./body.json", false)));
+/// assert_eq!(parse_body_file_reference("<@ ./body.json\n"), Some(("./body.json", true)));
+/// assert_eq!(parse_body_file_reference("<root/>"), None);
+/// ```
+pub fn parse_body_file_reference(body: &str) -> Option<(&str, bool)> {
+    let trimmed = body.trim();
+    if trimmed.contains('\n') {
+        return None;
+    }
+
+    let captures = BODY_FILE_REGEX.captures(trimmed)?;
+    let substitute = !captures.get(1)?.as_str().is_empty();
+    Some((captures.get(2)?.as_str(), substitute))
+}
+
+/// Resolves a path against the directory of the request file.
+fn resolve_relative_path(path: &str, file_path: &Path) -> PathBuf {
+    let path = Path::new(path);
+    if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        file_path
+            .parent()
+            .map(|dir| dir.join(path))
+            .unwrap_or_else(|| path.to_path_buf())
+    }
+}
+
+/// Drops a trailing block of `# @capture` directives, including disabled
+/// ones, that follows a blank line or makes up the whole body. Any other
+/// comment-looking line belongs to the body.
+fn strip_trailing_captures<'a, 'b>(lines: &'a [&'b str]) -> &'a [&'b str] {
+    let prefix = get_config().disabled_directive_prefix;
+    let is_capture = |line: &str| {
+        parse_capture_directive(line).is_some()
+            || disabled_directive_name(line, &prefix) == Some("capture")
+    };
+
+    // Find where the trailing run of capture and blank lines begins
+    let mut start = lines.len();
+    while start > 0 && (lines[start - 1].trim().is_empty() || is_capture(lines[start - 1])) {
+        start -= 1;
+    }
+
+    let trailer = &lines[start..];
+    let has_capture = trailer.iter().any(|line| is_capture(line));
+    let separated = start == 0 || trailer.first().is_some_and(|line| line.trim().is_empty());
+
+    if has_capture && separated {
+        &lines[..start]
+    } else {
+        lines
+    }
+}
+
 /// Generates a unique ID for a request based on file path and line number.
 ///
 /// # Arguments
@@ -401,6 +1595,32 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_request_line_method_case_insensitive() {
+        for method in ["GET", "get", "Get", "gEt"] {
+            let line = format!("{} https://example.com/users HTTP/1.1", method);
+            let (method, url, version) = parse_request_line(&line, 1).unwrap();
+            assert_eq!(method, HttpMethod::GET);
+            assert_eq!(url, "https://example.com/users");
+            assert_eq!(version, Some("HTTP/1.1".to_string()));
+        }
+
+        let (method, _, _) = parse_request_line("Post https://example.com", 1).unwrap();
+        assert_eq!(method, HttpMethod::POST);
+    }
+
+    #[test]
+    fn test_parse_request_line_unknown_lowercase_method() {
+        let result = parse_request_line("fetch https://example.com", 3);
+        assert_eq!(
+            result.unwrap_err(),
+            ParseError::InvalidMethod {
+                method: "fetch".to_string(),
+                line: 3,
+            }
+        );
+    }
+
     #[test]
     fn test_parse_request_line_missing_url() {
         let result = parse_request_line("GET", 1);
@@ -426,6 +1646,28 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_request_line_url_with_space() {
+        for line in [
+            "GET https://x/search?q=hello world",
+            "GET https://x/search?q=hello world HTTP/1.1",
+        ] {
+            match parse_request_line(line, 3) {
+                Err(err @ ParseError::InvalidUrl { .. }) => {
+                    assert_eq!(
+                        err,
+                        ParseError::InvalidUrl {
+                            url: "https://x/search?q=hello world".to_string(),
+                            line: 3,
+                        }
+                    );
+                    assert!(err.to_string().contains("URL-encode"), "{}", err);
+                }
+                other => panic!("Expected InvalidUrl error, got {:?}", other),
+            }
+        }
+    }
+
     #[test]
     fn test_extract_headers_valid() {
         let lines = vec![
@@ -450,6 +1692,23 @@ mod tests {
         assert_eq!(headers.get("Accept"), Some(&"*/*".to_string()));
     }
 
+    #[test]
+    fn test_extract_headers_keeps_repeated_headers() {
+        let lines = vec![
+            (2, "X-Custom: one"),
+            (3, "Accept: */*"),
+            (4, "x-custom: two"),
+        ];
+
+        let headers = extract_headers(&lines).unwrap();
+        assert_eq!(headers.len(), 3);
+        assert_eq!(headers.get("X-Custom"), Some(&"one".to_string()));
+        assert_eq!(
+            headers.get_all("X-Custom").collect::<Vec<_>>(),
+            vec!["one", "two"]
+        );
+    }
+
     #[test]
     fn test_extract_headers_with_spaces() {
         let lines = vec![(2, "Content-Type:    application/json   ")];
@@ -479,6 +1738,196 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_extract_headers_folds_continuation_lines() {
+        let lines = vec![
+            (2, "User-Agent: Mozilla/5.0"),
+            (3, "    \t(X11; Linux x86_64)"),
+            (4, "Accept: */*"),
+        ];
+
+        let headers = extract_headers(&lines).unwrap();
+
+        assert_eq!(headers.len(), 2);
+        assert_eq!(headers["User-Agent"], "Mozilla/5.0 (X11; Linux x86_64)");
+        assert_eq!(headers["Accept"], "*/*");
+    }
+
+    #[test]
+    fn test_extract_headers_continuation_without_previous_header() {
+        let lines = vec![(2, "  (X11; Linux x86_64)")];
+
+        match extract_headers(&lines) {
+            Err(ParseError::InvalidHeader { header, line }) => {
+                assert_eq!(header, "(X11; Linux x86_64)");
+                assert_eq!(line, 2);
+            }
+            other => panic!("Expected InvalidHeader error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_request_form_directive() {
+        let lines = vec![
+            (1, "# @form"),
+            (2, "POST https://api.example.com/login"),
+            (3, ""),
+            (4, "username: jane doe"),
+            (5, "password: p@ss=w&rd/100%"),
+            (6, "token: {{authToken}}"),
+        ];
+        let request = parse_request(&lines, 1, &PathBuf::from("test.http")).unwrap();
+
+        assert_eq!(
+            request.body,
+            Some(
+                "username=jane+doe&password=p%40ss%3Dw%26rd%2F100%25&token={{authToken}}"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            request.form_fields[2],
+            ("token".to_string(), "{{authToken}}".to_string())
+        );
+        assert_eq!(
+            request.headers["Content-Type"],
+            "application/x-www-form-urlencoded"
+        );
+    }
+
+    #[test]
+    fn test_parse_request_form_keeps_explicit_content_type() {
+        let lines = vec![
+            (1, "// @form"),
+            (2, "POST https://api.example.com/login"),
+            (
+                3,
+                "content-type: application/x-www-form-urlencoded; charset=utf-8",
+            ),
+            (4, ""),
+            (5, "q: a b"),
+        ];
+        let request = parse_request(&lines, 1, &PathBuf::from("test.http")).unwrap();
+
+        assert_eq!(request.body, Some("q=a+b".to_string()));
+        assert_eq!(request.headers.len(), 1);
+    }
+
+    #[test]
+    fn test_parse_form_fields_invalid_field() {
+        let lines = vec![(4, "username: jane"), (5, "no separator")];
+
+        assert_eq!(
+            parse_form_fields(&lines),
+            Err(ParseError::InvalidFormField {
+                field: "no separator".to_string(),
+                line: 5,
+            })
+        );
+        assert!(parse_form_fields(&[(4, ": value")]).is_err());
+    }
+
+    #[test]
+    fn test_parse_request_multipart_text_fields() {
+        let lines = vec![
+            (1, "# @multipart"),
+            (2, "POST https://api.example.com/upload"),
+            (3, ""),
+            (4, "--field name=title"),
+            (5, "Hello"),
+            (6, ""),
+            (7, "--field name=description"),
+            (8, "Line one"),
+            (9, "Line two"),
+        ];
+        let request = parse_request(&lines, 1, &PathBuf::from("test.http")).unwrap();
+
+        assert_eq!(request.body, None);
+        assert_eq!(
+            request.multipart.unwrap().parts,
+            vec![
+                MultipartPart::Field {
+                    name: "title".to_string(),
+                    value: "Hello".to_string(),
+                },
+                MultipartPart::Field {
+                    name: "description".to_string(),
+                    value: "Line one\nLine two".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_request_multipart_file_part_from_content_type() {
+        let lines = vec![
+            (1, "POST https://api.example.com/upload"),
+            (2, "Content-Type: multipart/form-data"),
+            (3, ""),
+            (4, "--field name=title"),
+            (5, "Hello"),
+            (6, "--file file=./photo.png"),
+        ];
+        let request = parse_request(&lines, 1, &PathBuf::from("/requests/upload.http")).unwrap();
+
+        let parts = request.multipart.unwrap().parts;
+        assert_eq!(parts.len(), 2);
+        assert_eq!(
+            parts[1],
+            MultipartPart::File {
+                name: "file".to_string(),
+                path: PathBuf::from("/requests/./photo.png"),
+                content_type: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_request_multipart_file_part_type() {
+        let lines = vec![
+            (1, "# @multipart"),
+            (2, "POST https://api.example.com/upload"),
+            (3, ""),
+            (4, "--file avatar=./avatar.bin;type=image/webp"),
+        ];
+        let request = parse_request(&lines, 1, &PathBuf::from("/requests/upload.http")).unwrap();
+
+        assert_eq!(
+            request.multipart.unwrap().parts,
+            vec![MultipartPart::File {
+                name: "avatar".to_string(),
+                path: PathBuf::from("/requests/./avatar.bin"),
+                content_type: Some("image/webp".to_string()),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_parse_request_multipart_requires_parts() {
+        // A hand-written payload with a boundary is sent as-is
+        let lines = vec![
+            (1, "POST https://api.example.com/upload"),
+            (2, "Content-Type: multipart/form-data; boundary=XYZ"),
+            (3, ""),
+            (4, "--XYZ"),
+        ];
+        let request = parse_request(&lines, 1, &PathBuf::from("test.http")).unwrap();
+        assert!(request.multipart.is_none());
+        assert_eq!(request.body, Some("--XYZ".to_string()));
+
+        let lines = vec![
+            (1, "# @multipart"),
+            (2, "POST https://api.example.com/upload"),
+            (3, ""),
+            (4, "stray text"),
+            (5, "--field name=title"),
+        ];
+        assert!(matches!(
+            parse_request(&lines, 1, &PathBuf::from("test.http")),
+            Err(ParseError::InvalidMultipartPart { line: 4, .. })
+        ));
+    }
+
     #[test]
     fn test_extract_body_simple() {
         let lines = vec![r#"{"name": "John", "age": 30}"#];
@@ -622,6 +2071,632 @@ GET https://api.example.com/users
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_parse_request_body_keeps_hash_lines() {
+        let lines = vec![
+            (1, "POST https://api.example.com/posts"),
+            (2, "Content-Type: application/json"),
+            (3, ""),
+            (4, "{"),
+            (5, "  \"pattern\": \"#hashtag\","),
+            (6, "# not a comment"),
+            (7, "// also not a comment"),
+            (8, "}"),
+        ];
+        let request = parse_request(&lines, 1, &PathBuf::from("test.http")).unwrap();
+        assert_eq!(
+            request.body.unwrap(),
+            "{\n  \"pattern\": \"#hashtag\",\n# not a comment\n// also not a comment\n}"
+        );
+    }
+
+    #[test]
+    fn test_parse_file_with_positions() {
+        let content = "GET https://api.example.com/users\r\n\r\n###\r\n# @name posts\r\nGET https://api.example.com/posts";
+        let requests = parse_file_with_positions(content, &PathBuf::from("test.http")).unwrap();
+
+        assert_eq!(requests.len(), 2);
+        assert_eq!(
+            &content[requests[0].1.clone()],
+            "GET https://api.example.com/users\r\n\r\n"
+        );
+        assert_eq!(
+            &content[requests[1].1.clone()],
+            "# @name posts\r\nGET https://api.example.com/posts"
+        );
+        assert_eq!(requests[1].0.line_number, 5);
+        assert_eq!(requests[1].0.name.as_deref(), Some("posts"));
+    }
+
+    #[test]
+    fn test_parse_file_skips_use_directive() {
+        let content = "@use ./auth.http\n# @use ./setup.http\n\nGET https://api.example.com/me";
+        let requests = parse_file(content, &PathBuf::from("test.http")).unwrap();
+
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].url, "https://api.example.com/me");
+        assert_eq!(requests[0].line_number, 4);
+    }
+
+    #[test]
+    fn test_request_index_at_cursor() {
+        let content = "GET https://a.example.com\n###\nGET https://b.example.com\n";
+        let requests = parse_file_with_positions(content, &PathBuf::from("test.http")).unwrap();
+        let delimiter = content.find("###").unwrap();
+
+        assert_eq!(request_index_at_cursor(&requests, 0), Some(0));
+        assert_eq!(request_index_at_cursor(&requests, delimiter - 1), Some(0));
+        assert_eq!(request_index_at_cursor(&requests, delimiter + 1), None);
+        assert_eq!(request_index_at_cursor(&requests, delimiter + 4), Some(1));
+        assert_eq!(request_index_at_cursor(&requests, content.len()), Some(1));
+        assert_eq!(request_index_at_cursor(&requests, content.len() + 1), None);
+    }
+
+    #[test]
+    fn test_parse_file_shell_script_body_verbatim() {
+        let script =
+            "#!/bin/bash\n# Deploy script\nset -e\n\n# build\nmake build // release\necho done";
+        let content = format!(
+            "# Trigger deploy\nPOST https://hooks.example.com/deploy\nContent-Type: text/x-shellscript\n\n{}\n\n###\n\nGET https://hooks.example.com/status",
+            script
+        );
+
+        let requests = parse_file(&content, &PathBuf::from("test.http")).unwrap();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0].body.as_deref().map(str::trim_end), Some(script));
+    }
+
+    #[test]
+    fn test_parse_request_body_drops_trailing_directives() {
+        let lines = vec![
+            (1, "POST https://api.example.com/login"),
+            (2, "Content-Type: application/json"),
+            (3, ""),
+            (4, "{\"user\": \"admin\"}"),
+            (5, ""),
+            (6, "# @capture token = $.token"),
+            (7, ""),
+        ];
+        let request = parse_request(&lines, 1, &PathBuf::from("test.http")).unwrap();
+        assert_eq!(request.body.unwrap(), "{\"user\": \"admin\"}");
+        assert_eq!(request.captures.len(), 1);
+    }
+
+    #[test]
+    fn test_parse_request_body_keeps_trailing_comments() {
+        let lines = vec![
+            (1, "POST https://hooks.example.com/deploy"),
+            (2, "Content-Type: text/x-shellscript"),
+            (3, ""),
+            (4, "make build"),
+            (5, ""),
+            (6, "# done"),
+            (7, "// end of script"),
+        ];
+        let request = parse_request(&lines, 1, &PathBuf::from("test.http")).unwrap();
+        assert_eq!(
+            request.body.unwrap(),
+            "make build\n\n# done\n// end of script"
+        );
+    }
+
+    #[test]
+    fn test_parse_request_body_file_relative_to_request_file() {
+        let file_path = PathBuf::from("/requests/api.http");
+
+        let content = "POST https://api.example.com/users\nContent-Type: application/json\n\n< ./payloads/user.json\n\n###\n\nPOST https://api.example.com/users\n\n<@ /data/user.json";
+        let requests = parse_file(content, &file_path).unwrap();
+
+        assert_eq!(requests[0].body, None);
+        assert!(requests[0].raw_body);
+        assert_eq!(
+            requests[0].body_file,
+            Some(PathBuf::from("/requests/./payloads/user.json"))
+        );
+        assert_eq!(requests[1].body, None);
+        assert!(!requests[1].raw_body);
+        assert_eq!(
+            requests[1].body_file,
+            Some(PathBuf::from("/data/user.json"))
+        );
+    }
+
+    #[test]
+    fn test_parse_request_missing_body_file_is_not_an_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let lines = vec![
+            (1, "POST https://api.example.com/users"),
+            (2, ""),
+            (3, "< ./missing.json"),
+        ];
+
+        let request = parse_request(&lines, 1, &dir.path().join("api.http")).unwrap();
+        assert_eq!(request.body_file, Some(dir.path().join("./missing.json")));
+    }
+
+    #[test]
+    fn test_parse_body_file_reference_ignores_inline_bodies() {
+        assert_eq!(parse_body_file_reference("<user><id>1</id></user>"), None);
+        assert_eq!(parse_body_file_reference("< a.json\n< b.json"), None);
+        assert_eq!(
+            parse_body_file_reference("< ./my body.txt  "),
+            Some(("./my body.txt", false))
+        );
+    }
+
+    #[test]
+    fn test_parse_request_named() {
+        let lines = vec![
+            (1, "# Log in first"),
+            (2, "# @name login"),
+            (3, "POST https://api.example.com/login"),
+        ];
+        let request = parse_request(&lines, 1, &PathBuf::from("test.http")).unwrap();
+        assert_eq!(request.name.as_deref(), Some("login"));
+
+        let content = "// @name get-user_2\nGET https://api.example.com/users/1";
+        let requests = parse_file(content, &PathBuf::from("test.http")).unwrap();
+        assert_eq!(requests[0].name.as_deref(), Some("get-user_2"));
+    }
+
+    #[test]
+    fn test_parse_request_unnamed() {
+        let lines = vec![
+            (1, "GET https://api.example.com/users"),
+            (2, "# @name tooLate"),
+        ];
+        let request = parse_request(&lines, 1, &PathBuf::from("test.http")).unwrap();
+        assert_eq!(request.name, None);
+    }
+
+    #[test]
+    fn test_parse_request_malformed_name() {
+        for directive in ["# @name", "# @name   ", "# @name 1st", "# @name two words"] {
+            let lines = vec![(1, directive), (2, "GET https://api.example.com/users")];
+            let request = parse_request(&lines, 1, &PathBuf::from("test.http")).unwrap();
+            assert_eq!(request.name, None, "{}", directive);
+        }
+    }
+
+    #[test]
+    fn test_parse_request_locale_directive() {
+        let lines = vec![
+            (1, "# @locale fr-FR"),
+            (2, "GET https://api.example.com/greeting"),
+        ];
+        let request = parse_request(&lines, 1, &PathBuf::from("test.http")).unwrap();
+        assert_eq!(
+            request.headers.get("Accept-Language"),
+            Some(&"fr-FR".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_request_disabled_locale_directive_ignored() {
+        let lines = vec![
+            (1, "# !@locale fr-FR"),
+            (2, "GET https://api.example.com/greeting"),
+        ];
+        let request = parse_request(&lines, 1, &PathBuf::from("test.http")).unwrap();
+        assert!(request.headers.get("Accept-Language").is_none());
+    }
+
+    #[test]
+    fn test_disabled_directive_name() {
+        assert_eq!(
+            disabled_directive_name("  #  !@capture id = $.id", "!"),
+            Some("capture")
+        );
+        assert_eq!(
+            disabled_directive_name("# off@locale fr-FR", "off"),
+            Some("locale")
+        );
+        assert_eq!(disabled_directive_name("# !@locale fr-FR", "off"), None);
+        assert_eq!(disabled_directive_name("# !not a directive", "!"), None);
+        assert_eq!(disabled_directive_name("# !@", "!"), None);
+        assert_eq!(disabled_directive_name("!@capture id = $.id", "!"), None);
+    }
+
+    #[test]
+    fn test_misplaced_header_name() {
+        assert_eq!(
+            misplaced_header_name("Content-Type: application/json"),
+            Some("Content-Type")
+        );
+        assert_eq!(misplaced_header_name("  Accept:*/*"), Some("Accept"));
+
+        assert_eq!(misplaced_header_name("Accept:"), None);
+        assert_eq!(misplaced_header_name("Name: John"), None);
+        assert_eq!(misplaced_header_name("content-type: text/plain"), None);
+        assert_eq!(misplaced_header_name("{\"Authorization\": \"x\"}"), None);
+        assert_eq!(misplaced_header_name("See https://example.com"), None);
+    }
+
+    #[test]
+    fn test_parse_request_locale_directive_keeps_explicit_header() {
+        let lines = vec![
+            (1, "// @locale fr-FR"),
+            (2, "GET https://api.example.com/greeting"),
+            (3, "accept-language: de-DE"),
+        ];
+        let request = parse_request(&lines, 1, &PathBuf::from("test.http")).unwrap();
+        assert_eq!(request.headers.len(), 1);
+        assert_eq!(
+            request.headers.get("accept-language"),
+            Some(&"de-DE".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_request_query_continuation_lines() {
+        let lines = vec![
+            (1, "GET https://api.example.com/search"),
+            (2, "  ?q=rust"),
+            (3, "  &limit=20"),
+            (4, "\t&sort=desc "),
+            (5, "Accept: application/json"),
+        ];
+        let request = parse_request(&lines, 1, &PathBuf::from("test.http")).unwrap();
+
+        assert_eq!(
+            request.url,
+            "https://api.example.com/search?q=rust&limit=20&sort=desc"
+        );
+        assert_eq!(request.headers.len(), 1);
+        assert_eq!(request.headers["Accept"], "application/json");
+    }
+
+    #[test]
+    fn test_parse_request_query_continuation_with_http_version() {
+        let lines = vec![
+            (1, "GET https://api.example.com/search?q=rust HTTP/1.1"),
+            (2, "&limit=20"),
+        ];
+        let request = parse_request(&lines, 1, &PathBuf::from("test.http")).unwrap();
+
+        assert_eq!(
+            request.url,
+            "https://api.example.com/search?q=rust&limit=20"
+        );
+        assert_eq!(request.http_version, Some("HTTP/1.1".to_string()));
+    }
+
+    #[test]
+    fn test_parse_request_query_continuation_only_after_request_line() {
+        let lines = vec![
+            (1, "POST https://api.example.com/search?q=rust"),
+            (2, "Content-Type: application/x-www-form-urlencoded"),
+            (3, "&ignored=1"),
+            (4, ""),
+            (5, "&page=2"),
+        ];
+        let result = parse_request(&lines, 1, &PathBuf::from("test.http"));
+
+        // A `&` line after a header is not a continuation
+        assert!(matches!(
+            result,
+            Err(ParseError::InvalidHeader { line: 3, .. })
+        ));
+
+        let lines = vec![
+            (1, "POST https://api.example.com/search?q=rust"),
+            (2, ""),
+            (3, "&page=2"),
+        ];
+        let request = parse_request(&lines, 1, &PathBuf::from("test.http")).unwrap();
+        assert_eq!(request.url, "https://api.example.com/search?q=rust");
+        assert_eq!(request.body, Some("&page=2".to_string()));
+    }
+
+    #[test]
+    fn test_parse_request_digest_directive() {
+        let lines = vec![
+            (1, "# @digest admin {{password}}"),
+            (2, "GET https://api.example.com/internal"),
+        ];
+        let request = parse_request(&lines, 1, &PathBuf::from("test.http")).unwrap();
+
+        assert_eq!(
+            request.digest_auth,
+            Some(DigestAuth::new(
+                "admin".to_string(),
+                "{{password}}".to_string()
+            ))
+        );
+        assert!(!request.headers.contains_key("Authorization"));
+
+        let lines = vec![(1, "GET https://api.example.com/internal")];
+        let request = parse_request(&lines, 1, &PathBuf::from("test.http")).unwrap();
+        assert_eq!(request.digest_auth, None);
+    }
+
+    #[test]
+    fn test_parse_request_timeout_directive() {
+        let lines = vec![
+            (1, "# @timeout 60000"),
+            (2, "GET https://api.example.com/reports"),
+        ];
+        let request = parse_request(&lines, 1, &PathBuf::from("test.http")).unwrap();
+        assert_eq!(request.timeout_override, Some(60000));
+
+        let lines = vec![(1, "GET https://api.example.com/reports")];
+        let request = parse_request(&lines, 1, &PathBuf::from("test.http")).unwrap();
+        assert_eq!(request.timeout_override, None);
+    }
+
+    #[test]
+    fn test_parse_request_aws_directive() {
+        let lines = vec![
+            (
+                1,
+                "# @aws us-east-1 execute-api {{accessKey}} {{secretKey}}",
+            ),
+            (
+                2,
+                "GET https://abc123.execute-api.us-east-1.amazonaws.com/prod/items",
+            ),
+        ];
+        let request = parse_request(&lines, 1, &PathBuf::from("test.http")).unwrap();
+        let aws = request.aws_sigv4.unwrap();
+        assert_eq!(aws.region, "us-east-1");
+        assert_eq!(aws.service, "execute-api");
+        assert_eq!(aws.access_key, "{{accessKey}}");
+        assert_eq!(aws.session_token, None);
+
+        let lines = vec![
+            (1, "# @aws us-east-1 s3"),
+            (2, "GET https://bucket.s3.amazonaws.com/key"),
+        ];
+        let result = parse_request(&lines, 1, &PathBuf::from("test.http"));
+        assert!(matches!(
+            result,
+            Err(ParseError::InvalidAwsDirective { line: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_request_apikey_directive() {
+        let lines = vec![
+            (1, "# @apikey query api_key {{KEY}}"),
+            (2, "GET https://api.example.com/items?page=2"),
+        ];
+        let request = parse_request(&lines, 1, &PathBuf::from("test.http")).unwrap();
+        assert_eq!(request.url, "https://api.example.com/items?page=2");
+        assert_eq!(
+            request.query_api_key,
+            Some(("api_key".to_string(), "{{KEY}}".to_string()))
+        );
+
+        let lines = vec![
+            (1, "# @apikey header X-API-Key {{KEY}}"),
+            (2, "GET https://api.example.com/items"),
+        ];
+        let request = parse_request(&lines, 1, &PathBuf::from("test.http")).unwrap();
+        assert_eq!(request.url, "https://api.example.com/items");
+        assert_eq!(
+            request.headers.get("X-API-Key").map(String::as_str),
+            Some("{{KEY}}")
+        );
+        assert_eq!(request.api_key_header.as_deref(), Some("X-API-Key"));
+
+        let lines = vec![
+            (1, "# @apikey header X-API-Key: {{KEY}}"),
+            (2, "GET https://api.example.com/items"),
+        ];
+        assert_eq!(
+            parse_request(&lines, 1, &PathBuf::from("test.http")).unwrap_err(),
+            ParseError::InvalidHeader {
+                header: "# @apikey header X-API-Key: {{KEY}}".to_string(),
+                line: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_request_no_cookies_directive() {
+        let lines = vec![
+            (1, "# @no-cookies"),
+            (2, "GET https://api.example.com/public"),
+        ];
+        let request = parse_request(&lines, 1, &PathBuf::from("test.http")).unwrap();
+        assert!(request.no_cookies);
+
+        let lines = vec![(1, "GET https://api.example.com/public")];
+        let request = parse_request(&lines, 1, &PathBuf::from("test.http")).unwrap();
+        assert!(!request.no_cookies);
+    }
+
+    #[test]
+    fn test_parse_request_header_if_directives() {
+        let lines = vec![
+            (1, "# @header-if dev X-Debug: true"),
+            (2, "GET https://api.example.com/items"),
+            (3, "// @header-if staging  Authorization: Bearer {{token}} "),
+            (4, "Accept: application/json"),
+        ];
+        let request = parse_request(&lines, 1, &PathBuf::from("test.http")).unwrap();
+
+        assert_eq!(
+            request.conditional_headers,
+            vec![
+                ConditionalHeader {
+                    environment: "dev".to_string(),
+                    name: "X-Debug".to_string(),
+                    value: "true".to_string(),
+                },
+                ConditionalHeader {
+                    environment: "staging".to_string(),
+                    name: "Authorization".to_string(),
+                    value: "Bearer {{token}}".to_string(),
+                },
+            ]
+        );
+        assert_eq!(request.headers.len(), 1);
+    }
+
+    #[test]
+    fn test_parse_request_capture_directives() {
+        let lines = vec![
+            (1, "# @capture id = $.items[0].id"),
+            (2, "POST https://api.example.com/login"),
+            (3, ""),
+            (4, "{\"user\": \"alice\"}"),
+            (5, ""),
+            (6, "# @capture token = $.auth.token"),
+            (7, "# !@capture refresh = $.auth.refresh"),
+        ];
+        let request = parse_request(&lines, 1, &PathBuf::from("test.http")).unwrap();
+
+        let names: Vec<&str> = request
+            .captures
+            .iter()
+            .map(|capture| capture.variable_name.as_str())
+            .collect();
+        assert_eq!(names, vec!["id", "token"]);
+        assert_eq!(request.body.as_deref(), Some("{\"user\": \"alice\"}"));
+    }
+
+    #[test]
+    fn test_parse_request_capture_line_inside_body_is_payload() {
+        let lines = vec![
+            (1, "POST https://api.example.com/scripts"),
+            (2, ""),
+            (3, "# @capture id = $.id"),
+            (4, "echo hello"),
+        ];
+        let request = parse_request(&lines, 1, &PathBuf::from("test.http")).unwrap();
+
+        assert!(request.captures.is_empty());
+        assert_eq!(
+            request.body.as_deref(),
+            Some("# @capture id = $.id\necho hello")
+        );
+    }
+
+    #[test]
+    fn test_parse_request_captures_without_body() {
+        let lines = vec![
+            (1, "GET https://api.example.com/session"),
+            (2, ""),
+            (3, "# @capture token = $.token"),
+        ];
+        let request = parse_request(&lines, 1, &PathBuf::from("test.http")).unwrap();
+
+        assert_eq!(request.captures.len(), 1);
+        assert_eq!(request.captures[0].variable_name, "token");
+        assert_eq!(request.body, None);
+    }
+
+    #[test]
+    fn test_parse_request_invalid_header_if_directive() {
+        for directive in [
+            "# @header-if",
+            "# @header-if dev",
+            "# @header-if X-Debug: true",
+            "# @header-if dev Bad Name: 1",
+        ] {
+            let lines = vec![(1, directive), (2, "GET https://api.example.com/items")];
+            assert!(
+                matches!(
+                    parse_request(&lines, 1, &PathBuf::from("test.http")),
+                    Err(ParseError::InvalidHeader { line: 1, .. })
+                ),
+                "{} should be rejected",
+                directive
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_request_filter_directive() {
+        let lines = vec![
+            (1, "# @filter $.data.items[*].id"),
+            (2, "GET https://api.example.com/items"),
+        ];
+        let request = parse_request(&lines, 1, &PathBuf::from("test.http")).unwrap();
+        assert_eq!(request.filter, Some("$.data.items[*].id".to_string()));
+
+        let lines = vec![
+            (1, "// @filter   $['total'] "),
+            (2, "GET https://api.example.com/items"),
+        ];
+        let request = parse_request(&lines, 1, &PathBuf::from("test.http")).unwrap();
+        assert_eq!(request.filter, Some("$['total']".to_string()));
+
+        let lines = vec![(1, "GET https://api.example.com/items")];
+        let request = parse_request(&lines, 1, &PathBuf::from("test.http")).unwrap();
+        assert_eq!(request.filter, None);
+    }
+
+    #[test]
+    fn test_parse_request_pipe_directive() {
+        let lines = vec![
+            (1, "# @pipe jq '.data | length'"),
+            (2, "GET https://api.example.com/items"),
+        ];
+        let request = parse_request(&lines, 1, &PathBuf::from("test.http")).unwrap();
+        assert_eq!(request.pipe, Some("jq '.data | length'".to_string()));
+
+        let lines = vec![(1, "# @pipe"), (2, "GET https://api.example.com/items")];
+        let request = parse_request(&lines, 1, &PathBuf::from("test.http")).unwrap();
+        assert_eq!(request.pipe, None);
+    }
+
+    #[test]
+    fn test_parse_request_retries_directive() {
+        let lines = vec![
+            (1, "// @retries 3"),
+            (2, "GET https://api.example.com/flaky"),
+        ];
+        let request = parse_request(&lines, 1, &PathBuf::from("test.http")).unwrap();
+        assert_eq!(request.retries_override, Some(3));
+
+        let lines = vec![
+            (1, "# @retries -1"),
+            (2, "GET https://api.example.com/flaky"),
+        ];
+        assert!(matches!(
+            parse_request(&lines, 1, &PathBuf::from("test.http")),
+            Err(ParseError::InvalidRetries { line: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_request_encoding_directive() {
+        let lines = vec![
+            (1, "# @encoding iso-8859-1"),
+            (2, "POST https://legacy.example.com/orders"),
+            (3, "Content-Type: text/plain"),
+            (4, ""),
+            (5, "café"),
+        ];
+        let request = parse_request(&lines, 1, &PathBuf::from("test.http")).unwrap();
+        assert_eq!(request.body_encoding, Some("iso-8859-1".to_string()));
+
+        let lines = vec![(1, "GET https://api.example.com/users")];
+        let request = parse_request(&lines, 1, &PathBuf::from("test.http")).unwrap();
+        assert_eq!(request.body_encoding, None);
+    }
+
+    #[test]
+    fn test_parse_request_invalid_timeout_directive() {
+        for (directive, value) in [
+            ("# @timeout abc", "abc"),
+            ("// @timeout 0", "0"),
+            ("# @timeout", ""),
+        ] {
+            let lines = vec![(1, directive), (2, "GET https://api.example.com/reports")];
+            let result = parse_request(&lines, 1, &PathBuf::from("test.http"));
+            assert_eq!(
+                result.unwrap_err(),
+                ParseError::InvalidTimeout {
+                    value: value.to_string(),
+                    line: 1,
+                }
+            );
+        }
+    }
+
     #[test]
     fn test_generate_request_id() {
         let id = generate_request_id(&PathBuf::from("/path/to/test.http"), 42);
@@ -14,9 +14,13 @@
 
 use super::response_actions::format_action_menu;
 use super::response_pane::{PanePosition, ResponsePane, ResponseTab};
+use crate::config::{get_config, RestClientConfig};
 use crate::formatter::FormattedResponse;
 use crate::models::request::HttpRequest;
 
+/// Headers always shown when `compact_headers` is enabled
+const CURATED_HEADERS: [&str; 2] = ["Content-Type", "Content-Length"];
+
 /// Configuration for response pane layout
 #[derive(Debug, Clone)]
 pub struct LayoutConfig {
@@ -34,6 +38,13 @@ pub struct LayoutConfig {
 
     /// Whether to include timing information
     pub show_timing: bool,
+
+    /// Whether to show only Content-Type, Content-Length, and
+    /// `interesting_headers` instead of every response header
+    pub compact_headers: bool,
+
+    /// Extra headers shown when `compact_headers` is enabled (case-insensitive)
+    pub interesting_headers: Vec<String>,
 }
 
 impl Default for LayoutConfig {
@@ -44,6 +55,8 @@ impl Default for LayoutConfig {
             compact_mode: false,
             show_request_details: true,
             show_timing: true,
+            compact_headers: false,
+            interesting_headers: Vec::new(),
         }
     }
 }
@@ -72,11 +85,42 @@ impl LayoutConfig {
         self
     }
 
-    /// Load configuration from settings (placeholder for future config integration)
+    /// Enable or disable showing only a curated set of headers
+    pub fn with_compact_headers(mut self, compact: bool) -> Self {
+        self.compact_headers = compact;
+        self
+    }
+
+    /// Set the extra headers shown when compact headers are enabled
+    pub fn with_interesting_headers(mut self, headers: Vec<String>) -> Self {
+        self.interesting_headers = headers;
+        self
+    }
+
+    /// Load configuration from the extension settings
+    ///
+    /// Uses `responsePane` for the position, `maxResponseTabs` for the tab limit,
+    /// and `compactHeaders`/`interestingHeaders` for the header view.
     pub fn from_settings() -> Self {
-        // TODO: When Zed adds settings API for extensions, load from user settings
-        // For now, return defaults
+        Self::from_config(&get_config())
+    }
+
+    /// Build a layout configuration from a `RestClientConfig`
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The extension configuration to read
+    ///
+    /// # Returns
+    ///
+    /// A `LayoutConfig` with the configured position, tab limit, and header
+    /// view, and defaults for everything else
+    pub fn from_config(config: &RestClientConfig) -> Self {
         Self::default()
+            .with_position(config.response_pane.into())
+            .with_max_tabs(config.max_response_tabs)
+            .with_compact_headers(config.compact_headers)
+            .with_interesting_headers(config.interesting_headers.clone())
     }
 }
 
@@ -87,6 +131,9 @@ pub struct LayoutManager {
 
     /// Layout configuration
     config: LayoutConfig,
+
+    /// Whether hidden headers are currently expanded in compact headers mode
+    headers_expanded: bool,
 }
 
 impl LayoutManager {
@@ -95,7 +142,11 @@ impl LayoutManager {
         let mut pane = ResponsePane::new(config.position);
         pane.set_max_tabs(config.max_tabs);
 
-        Self { pane, config }
+        Self {
+            pane,
+            config,
+            headers_expanded: false,
+        }
     }
 
     /// Create a new layout manager with default configuration
@@ -126,11 +177,53 @@ impl LayoutManager {
         let tab_id = self.pane.create_response_tab(response, request, request_id);
 
         // Get the newly created tab
-        if let Some(tab) = self.pane.get_tab(&tab_id) {
-            self.format_response_for_display(tab)
-        } else {
-            "Error: Failed to create response tab".to_string()
+        let Some(tab) = self.pane.get_tab(&tab_id) else {
+            return "Error: Failed to create response tab".to_string();
+        };
+
+        let mut output = self.format_response_for_display(tab);
+        if let Some(note) = self.eviction_note() {
+            output.push('\n');
+            output.push_str(&note);
+            output.push('\n');
         }
+        output
+    }
+
+    /// Describe the tab closed by the most recent insert, if the cap was hit
+    ///
+    /// # Returns
+    ///
+    /// A note such as `Closed oldest tab 'abc12345' (max 10 reached)`, or
+    /// `None` if no tab was evicted
+    pub fn eviction_note(&self) -> Option<String> {
+        self.pane.last_evicted().map(|tab| {
+            format!(
+                "Closed oldest tab '{}' (max {} reached)",
+                &tab.id[..8],
+                self.pane.max_tabs()
+            )
+        })
+    }
+
+    /// Store a response as a new tab without rendering it
+    ///
+    /// # Arguments
+    ///
+    /// * `response` - The formatted response to keep
+    /// * `request` - The original HTTP request
+    /// * `request_id` - Unique identifier for the request
+    ///
+    /// # Returns
+    ///
+    /// The ID of the new tab, which becomes the active one
+    pub fn record_response(
+        &mut self,
+        response: FormattedResponse,
+        request: HttpRequest,
+        request_id: &str,
+    ) -> String {
+        self.pane.create_response_tab(response, request, request_id)
     }
 
     /// Format a response tab for display
@@ -197,7 +290,13 @@ impl LayoutManager {
         );
 
         // Response content
-        output.push_str(&tab.response.to_display_string());
+        if self.hides_headers() {
+            let mut response = tab.response.clone();
+            response.headers_text = self.curated_headers(&tab.response.headers_text);
+            output.push_str(&response.to_display_string());
+        } else {
+            output.push_str(&tab.response.to_display_string());
+        }
 
         // Action menu
         output.push_str("\n");
@@ -228,7 +327,13 @@ impl LayoutManager {
             tab.title, tab.response.status_line
         ));
 
-        // Response body only
+        // Curated headers, if enabled
+        if self.hides_headers() {
+            output.push_str(&self.curated_headers(&tab.response.headers_text));
+            output.push('\n');
+        }
+
+        // Response body
         output.push_str(&tab.response.formatted_body);
 
         output.push_str("\n");
@@ -236,6 +341,68 @@ impl LayoutManager {
         output
     }
 
+    /// Expand or collapse the headers hidden by compact headers mode
+    ///
+    /// # Returns
+    ///
+    /// Formatted display of the active tab with the new header view, or a
+    /// message if no tabs are open
+    pub fn toggle_headers(&mut self) -> String {
+        self.headers_expanded = !self.headers_expanded;
+        self.get_active_tab_display()
+    }
+
+    /// Whether headers are currently being curated
+    fn hides_headers(&self) -> bool {
+        self.config.compact_headers && !self.headers_expanded
+    }
+
+    /// Keep only the curated headers, noting how many were hidden
+    ///
+    /// # Arguments
+    ///
+    /// * `headers_text` - Headers as `Name: value` lines
+    ///
+    /// # Returns
+    ///
+    /// The curated header lines, followed by a note if any were hidden
+    fn curated_headers(&self, headers_text: &str) -> String {
+        let is_curated = |name: &str| {
+            CURATED_HEADERS
+                .iter()
+                .copied()
+                .chain(self.config.interesting_headers.iter().map(String::as_str))
+                .any(|header| header.eq_ignore_ascii_case(name.trim()))
+        };
+
+        let mut output = String::new();
+        let mut hidden = 0;
+        for line in headers_text.lines() {
+            // Lines without a colon, like "(no headers)", aren't headers
+            let Some((name, _)) = line.split_once(':') else {
+                output.push_str(line);
+                output.push('\n');
+                continue;
+            };
+            if is_curated(name) {
+                output.push_str(line);
+                output.push('\n');
+            } else {
+                hidden += 1;
+            }
+        }
+
+        if hidden > 0 {
+            output.push_str(&format!(
+                "… {} more header{} hidden (toggle headers to show all)\n",
+                hidden,
+                if hidden == 1 { "" } else { "s" }
+            ));
+        }
+
+        output
+    }
+
     /// Get information about all open tabs
     ///
     /// # Returns
@@ -402,9 +569,10 @@ impl Default for LayoutManager {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::ResponsePanePosition;
     use crate::formatter::{ContentType, ResponseMetadata};
     use crate::models::request::HttpMethod;
-    use std::collections::HashMap;
+    use crate::models::Headers;
     use std::path::PathBuf;
     use std::time::Duration;
     use uuid::Uuid;
@@ -412,11 +580,28 @@ mod tests {
     fn create_test_request(method: HttpMethod, url: &str) -> HttpRequest {
         HttpRequest {
             id: Uuid::new_v4().to_string(),
+            name: None,
             method,
             url: url.to_string(),
             http_version: Some("HTTP/1.1".to_string()),
-            headers: HashMap::new(),
+            headers: Headers::new(),
             body: None,
+            raw_body: false,
+            body_encoding: None,
+            timeout_override: None,
+            retries_override: None,
+            no_cookies: false,
+            filter: None,
+            pipe: None,
+            body_file: None,
+            conditional_headers: Vec::new(),
+            captures: Vec::new(),
+            digest_auth: None,
+            aws_sigv4: None,
+            multipart: None,
+            form_fields: Vec::new(),
+            query_api_key: None,
+            api_key_header: None,
             line_number: 0,
             file_path: PathBuf::from("test.http"),
         }
@@ -438,9 +623,19 @@ mod tests {
                 is_success: true,
                 is_truncated: false,
                 timing_breakdown: "Total: 150ms".to_string(),
+                rate_limit: None,
+                warnings: Vec::new(),
+                final_url: None,
+                throughput_bps: None,
+                chunked_lines: None,
+                original_encoding: None,
+                compressed_size: None,
+                section_sizes: None,
             },
             highlight_info: None,
             is_formatted: true,
+            filter: None,
+            filter_warning: None,
         }
     }
 
@@ -456,6 +651,31 @@ mod tests {
         assert!(config.compact_mode);
     }
 
+    #[test]
+    fn test_layout_config_from_config() {
+        let mut settings = RestClientConfig::default();
+        settings.response_pane = ResponsePanePosition::Below;
+        settings.max_response_tabs = 3;
+
+        let config = LayoutConfig::from_config(&settings);
+        assert_eq!(config.position, PanePosition::Bottom);
+        assert_eq!(config.max_tabs, 3);
+        assert!(!config.compact_mode);
+        assert!(!config.compact_headers);
+
+        settings.compact_headers = true;
+        settings.interesting_headers = vec!["X-Request-Id".to_string()];
+        let config = LayoutConfig::from_config(&settings);
+        assert!(config.compact_headers);
+        assert_eq!(config.interesting_headers, vec!["X-Request-Id".to_string()]);
+
+        settings.response_pane = ResponsePanePosition::Tab;
+        assert_eq!(
+            LayoutConfig::from_config(&settings).position,
+            PanePosition::Right
+        );
+    }
+
     #[test]
     fn test_manage_pane_layout() {
         let mut manager = LayoutManager::with_defaults();
@@ -483,6 +703,98 @@ mod tests {
         assert!(!output.contains("╔═══")); // No fancy borders in compact mode
     }
 
+    #[test]
+    fn test_compact_headers() {
+        let config = LayoutConfig::new()
+            .with_compact_headers(true)
+            .with_interesting_headers(vec!["x-request-id".to_string()]);
+        let mut manager = LayoutManager::new(config);
+
+        let request = create_test_request(HttpMethod::GET, "https://api.example.com/users");
+        let mut response = create_test_response();
+        response.headers_text = "Content-Type: application/json\nContent-Length: 17\nX-Request-Id: abc\nServer: nginx\nDate: today\n".to_string();
+
+        let output = manager.manage_pane_layout(response, request, "req-123");
+        assert!(output
+            .contains("Content-Type: application/json\nContent-Length: 17\nX-Request-Id: abc\n"));
+        assert!(output.contains("… 2 more headers hidden"));
+        assert!(!output.contains("Server: nginx"));
+
+        // Expanding shows every header again
+        let expanded = manager.toggle_headers();
+        assert!(expanded.contains("Server: nginx"));
+        assert!(!expanded.contains("hidden"));
+    }
+
+    #[test]
+    fn test_compact_headers_in_compact_mode() {
+        let config = LayoutConfig::new()
+            .with_compact_mode(true)
+            .with_compact_headers(true);
+        let mut manager = LayoutManager::new(config);
+
+        let request = create_test_request(HttpMethod::GET, "https://api.example.com/users");
+        let mut response = create_test_response();
+        response.headers_text = "Content-Type: application/json\nServer: nginx\n".to_string();
+
+        let output = manager.manage_pane_layout(response, request, "req-123");
+        assert!(output.contains("Content-Type: application/json\n… 1 more header hidden"));
+        assert!(output.contains(r#"{"status": "ok"}"#));
+    }
+
+    #[test]
+    fn test_compact_headers_without_headers() {
+        let config = LayoutConfig::new().with_compact_headers(true);
+        let mut manager = LayoutManager::new(config);
+
+        let request = create_test_request(HttpMethod::GET, "https://api.example.com/users");
+        let mut response = create_test_response();
+        response.headers_text = "(no headers)".to_string();
+
+        let output = manager.manage_pane_layout(response, request, "req-123");
+        assert!(output.contains("(no headers)"));
+        assert!(!output.contains("hidden"));
+    }
+
+    #[test]
+    fn test_record_response() {
+        let mut manager = LayoutManager::with_defaults();
+        let request = create_test_request(HttpMethod::GET, "https://api.example.com/users");
+
+        let tab_id = manager.record_response(create_test_response(), request, "req-123");
+
+        assert_eq!(manager.pane.tab_count(), 1);
+        assert!(manager.switch_tab(&tab_id[..8]).contains("HTTP/1.1 200 OK"));
+    }
+
+    #[test]
+    fn test_eviction_note() {
+        let config = LayoutConfig::default().with_max_tabs(2);
+        let mut manager = LayoutManager::new(config);
+
+        let first = manager.record_response(
+            create_test_response(),
+            create_test_request(HttpMethod::GET, "https://api.example.com/a"),
+            "req-1",
+        );
+        let output = manager.manage_pane_layout(
+            create_test_response(),
+            create_test_request(HttpMethod::GET, "https://api.example.com/b"),
+            "req-2",
+        );
+        assert!(!output.contains("Closed oldest tab"));
+        assert_eq!(manager.eviction_note(), None);
+
+        let output = manager.manage_pane_layout(
+            create_test_response(),
+            create_test_request(HttpMethod::GET, "https://api.example.com/c"),
+            "req-3",
+        );
+        let note = format!("Closed oldest tab '{}' (max 2 reached)", &first[..8]);
+        assert!(output.contains(&note), "{}", output);
+        assert_eq!(manager.eviction_note(), Some(note));
+    }
+
     #[test]
     fn test_list_open_tabs() {
         let mut manager = LayoutManager::with_defaults();
@@ -74,6 +74,23 @@
 //! let mut manager = LayoutManager::new(config);
 //! ```
 //!
+//! ## Compact Headers
+//!
+//! Show only Content-Type, Content-Length, and any headers you care about,
+//! with a note of how many were hidden:
+//!
+//! ```ignore
+//! let config = LayoutConfig::new()
+//!     .with_compact_headers(true)
+//!     .with_interesting_headers(vec!["X-Request-Id".to_string()]);
+//!
+//! let mut manager = LayoutManager::new(config);
+//! let display = manager.manage_pane_layout(response, request, "req-123");
+//!
+//! // Show every header again
+//! let expanded = manager.toggle_headers();
+//! ```
+//!
 //! # Integration with Commands
 //!
 //! The UI module is designed to integrate seamlessly with command handlers:
@@ -105,19 +122,20 @@ pub mod response_pane;
 // Re-export commonly used types for convenience
 pub use layout::{LayoutConfig, LayoutManager};
 pub use response_actions::{
-    copy_response, fold_response, format_action_menu, save_response, suggest_filename,
-    toggle_raw_view, CopyOption, CopyResponseResult, FoldResponseResult, SaveOption,
-    SaveResponseResult,
+    copy_response, copy_response_with_limit, fold_response, fold_response_keys, format_action_menu,
+    save_response, suggest_filename, toggle_raw_view, CopyOption, CopyResponseResult, FoldMode,
+    FoldResponseResult, SaveOption, SaveResponseResult,
 };
 pub use response_pane::{PanePosition, ResponsePane, ResponseTab};
 
 /// Create a default layout manager instance
 ///
-/// This is a convenience function for quick setup.
+/// This is a convenience function for quick setup. The position and tab
+/// limit come from the `responsePane` and `maxResponseTabs` settings.
 ///
 /// # Returns
 ///
-/// A `LayoutManager` with default configuration
+/// A `LayoutManager` configured from the extension settings
 ///
 /// # Example
 ///
@@ -127,7 +145,7 @@ pub use response_pane::{PanePosition, ResponsePane, ResponseTab};
 /// let mut manager = create_default_layout_manager();
 /// ```
 pub fn create_default_layout_manager() -> LayoutManager {
-    LayoutManager::with_defaults()
+    LayoutManager::new(LayoutConfig::from_settings())
 }
 
 /// Create a layout manager with custom configuration
@@ -167,7 +185,7 @@ mod tests {
     use super::*;
     use crate::formatter::{ContentType, FormattedResponse, ResponseMetadata};
     use crate::models::request::{HttpMethod, HttpRequest};
-    use std::collections::HashMap;
+    use crate::models::Headers;
     use std::path::PathBuf;
     use std::time::Duration;
     use uuid::Uuid;
@@ -175,11 +193,28 @@ mod tests {
     fn create_test_request() -> HttpRequest {
         HttpRequest {
             id: Uuid::new_v4().to_string(),
+            name: None,
             method: HttpMethod::GET,
             url: "https://api.example.com/test".to_string(),
             http_version: Some("HTTP/1.1".to_string()),
-            headers: HashMap::new(),
+            headers: Headers::new(),
             body: None,
+            raw_body: false,
+            body_encoding: None,
+            timeout_override: None,
+            retries_override: None,
+            no_cookies: false,
+            filter: None,
+            pipe: None,
+            body_file: None,
+            conditional_headers: Vec::new(),
+            captures: Vec::new(),
+            digest_auth: None,
+            aws_sigv4: None,
+            multipart: None,
+            form_fields: Vec::new(),
+            query_api_key: None,
+            api_key_header: None,
             line_number: 0,
             file_path: PathBuf::from("test.http"),
         }
@@ -201,9 +236,19 @@ mod tests {
                 is_success: true,
                 is_truncated: false,
                 timing_breakdown: "Total: 100ms".to_string(),
+                rate_limit: None,
+                warnings: Vec::new(),
+                final_url: None,
+                throughput_bps: None,
+                chunked_lines: None,
+                original_encoding: None,
+                compressed_size: None,
+                section_sizes: None,
             },
             highlight_info: None,
             is_formatted: true,
+            filter: None,
+            filter_warning: None,
         }
     }
 
@@ -73,6 +73,15 @@ pub struct CopyResponseResult {
     pub content_size: usize,
 }
 
+/// How a response is folded
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FoldMode {
+    /// Collapse large nested sections, keeping small ones
+    Sections,
+    /// Show only top-level JSON keys with a summary of each value
+    TopLevelKeys,
+}
+
 /// Result of a fold response action
 #[derive(Debug, Clone)]
 pub struct FoldResponseResult {
@@ -82,6 +91,8 @@ pub struct FoldResponseResult {
     pub sections_folded: usize,
     /// Whether folding was applied
     pub is_folded: bool,
+    /// The fold mode that was applied
+    pub mode: FoldMode,
 }
 
 /// Generate a suggested filename for saving a response
@@ -103,16 +114,33 @@ pub struct FoldResponseResult {
 /// use rest_client::ui::response_actions::suggest_filename;
 /// use rest_client::models::request::{HttpRequest, HttpMethod};
 /// use rest_client::formatter::ContentType;
-/// use std::collections::HashMap;
+/// use rest_client::models::Headers;
 /// use std::path::PathBuf;
 ///
 /// let request = HttpRequest {
 ///     id: "test".to_string(),
+///     name: None,
 ///     method: HttpMethod::GET,
 ///     url: "https://api.example.com/users".to_string(),
 ///     http_version: Some("HTTP/1.1".to_string()),
-///     headers: HashMap::new(),
+///     headers: Headers::new(),
 ///     body: None,
+///     raw_body: false,
+///     body_encoding: None,
+///     timeout_override: None,
+///     retries_override: None,
+///     no_cookies: false,
+///     filter: None,
+///     pipe: None,
+///     body_file: None,
+///     conditional_headers: Vec::new(),
+///     captures: Vec::new(),
+///     digest_auth: None,
+///     aws_sigv4: None,
+///     multipart: None,
+///     form_fields: Vec::new(),
+///     query_api_key: None,
+///     api_key_header: None,
 ///     line_number: 0,
 ///     file_path: PathBuf::from("test.http"),
 /// };
@@ -146,8 +174,11 @@ pub fn suggest_filename(request: &HttpRequest, content_type: &ContentType) -> Pa
 
     // Determine file extension based on content type
     let extension = match content_type {
-        ContentType::Json => "json",
+        ContentType::Json | ContentType::ProblemJson => "json",
         ContentType::Xml => "xml",
+        ContentType::NdJson => "ndjson",
+        ContentType::Csv => "csv",
+        ContentType::Yaml => "yaml",
         ContentType::Html => "html",
         ContentType::PlainText => "txt",
         ContentType::Image => "png",
@@ -296,12 +327,7 @@ pub fn copy_response(response: &FormattedResponse, option: CopyOption) -> CopyRe
         success: true,
         message: format!(
             "Copied {} ({} bytes) to clipboard",
-            match option {
-                CopyOption::FullResponse => "full response",
-                CopyOption::Body => "response body",
-                CopyOption::Headers => "headers",
-                CopyOption::StatusLine => "status line",
-            },
+            copy_option_label(option),
             content_size
         ),
         content,
@@ -309,6 +335,67 @@ pub fn copy_response(response: &FormattedResponse, option: CopyOption) -> CopyRe
     }
 }
 
+/// Copy response data to clipboard, refusing content over a size limit
+///
+/// Copying very large content can stall the editor, so when the selected
+/// content exceeds `max_size` bytes nothing is copied and the result
+/// suggests saving to a file instead.
+///
+/// # Arguments
+///
+/// * `response` - The formatted response to copy from
+/// * `option` - What part of the response to copy
+/// * `max_size` - Maximum number of bytes to copy
+///
+/// # Returns
+///
+/// A `CopyResponseResult`; `success` is false and `content` is empty if the
+/// content is too large
+///
+/// # Example
+///
+/// ```ignore
+/// use rest_client::ui::response_actions::{copy_response_with_limit, CopyOption};
+///
+/// let result = copy_response_with_limit(&response, CopyOption::Body, 1024 * 1024);
+/// if !result.success {
+///     println!("{}", result.message);
+/// }
+/// ```
+pub fn copy_response_with_limit(
+    response: &FormattedResponse,
+    option: CopyOption,
+    max_size: usize,
+) -> CopyResponseResult {
+    let result = copy_response(response, option);
+
+    if result.content_size <= max_size {
+        return result;
+    }
+
+    CopyResponseResult {
+        success: false,
+        message: format!(
+            "Not copied: {} is {} bytes, which exceeds the clipboard limit of {} bytes. Save it to a file instead.",
+            copy_option_label(option),
+            result.content_size,
+            max_size
+        ),
+        content: String::new(),
+        content_size: result.content_size,
+    }
+}
+
+/// Describes what a copy option copies, for result messages
+fn copy_option_label(option: CopyOption) -> &'static str {
+    match option {
+        CopyOption::FullResponse => "full response",
+        CopyOption::Body => "response body",
+        CopyOption::Headers => "headers",
+        CopyOption::StatusLine => "status line",
+    }
+}
+
 /// Fold large sections in a JSON response body
 ///
 /// Collapses large JSON arrays and objects to make responses more manageable.
@@ -506,6 +593,90 @@ pub fn fold_response(response: &FormattedResponse, fold_threshold: usize) -> Fol
         ),
         sections_folded,
         is_folded: sections_folded > 0,
+        mode: FoldMode::Sections,
+    }
+}
+
+/// Summarizes a JSON value in a single short line
+///
+/// Objects and arrays are collapsed to `{…}` and `[<length>]`, and long
+/// strings are truncated.
+fn summarize_json_value(value: &serde_json::Value) -> String {
+    const MAX_STRING_CHARS: usize = 40;
+
+    match value {
+        serde_json::Value::Object(map) if map.is_empty() => "{}".to_string(),
+        serde_json::Value::Object(_) => "{…}".to_string(),
+        serde_json::Value::Array(items) => format!("[{}]", items.len()),
+        serde_json::Value::String(text) if text.chars().count() > MAX_STRING_CHARS => {
+            let truncated: String = text.chars().take(MAX_STRING_CHARS).collect();
+            format!("\"{}…\"", truncated)
+        }
+        other => other.to_string(),
+    }
+}
+
+/// Fold a JSON response down to its top-level keys
+///
+/// Shows each top-level key with a one-line summary of its value (e.g.
+/// `data: {…}`, `items: [12]`), so the shape of a large response can be seen
+/// at a glance. A top-level array is summarized by its length. Non-JSON
+/// responses are returned unchanged.
+///
+/// # Arguments
+///
+/// * `response` - The formatted response to fold
+///
+/// # Returns
+///
+/// A `FoldResponseResult` whose `sections_folded` counts the collapsed
+/// objects and arrays
+///
+/// # Example
+///
+/// ```ignore
+/// use rest_client::ui::response_actions::fold_response_keys;
+///
+/// let result = fold_response_keys(&response);
+/// println!("{}", result.folded_response);
+/// ```
+pub fn fold_response_keys(response: &FormattedResponse) -> FoldResponseResult {
+    let parsed = match response.content_type {
+        ContentType::Json => serde_json::from_str::<serde_json::Value>(&response.raw_body).ok(),
+        _ => None,
+    };
+
+    let (folded_body, sections_folded) = match parsed {
+        Some(serde_json::Value::Object(map)) => {
+            let lines: Vec<String> = map
+                .iter()
+                .map(|(key, value)| format!("  {}: {}", key, summarize_json_value(value)))
+                .collect();
+            let collapsed = map
+                .values()
+                .filter(|value| value.is_object() || value.is_array())
+                .count();
+            (format!("{{\n{}\n}}", lines.join("\n")), collapsed)
+        }
+        Some(value @ serde_json::Value::Array(_)) => (summarize_json_value(&value), 1),
+        _ => {
+            let body = if response.is_formatted {
+                &response.formatted_body
+            } else {
+                &response.raw_body
+            };
+            (body.to_string(), 0)
+        }
+    };
+
+    FoldResponseResult {
+        folded_response: format!(
+            "{}\n\n{}\n\n{}",
+            response.status_line, response.headers_text, folded_body
+        ),
+        sections_folded,
+        is_folded: sections_folded > 0,
+        mode: FoldMode::TopLevelKeys,
     }
 }
 
@@ -594,17 +765,34 @@ mod tests {
     use super::*;
     use crate::formatter::{ContentType, ResponseMetadata};
     use crate::models::request::{HttpMethod, HttpRequest};
-    use std::collections::HashMap;
+    use crate::models::Headers;
     use std::time::Duration;
 
     fn create_test_request(method: HttpMethod, url: &str) -> HttpRequest {
         HttpRequest {
             id: "test-123".to_string(),
+            name: None,
             method,
             url: url.to_string(),
             http_version: Some("HTTP/1.1".to_string()),
-            headers: HashMap::new(),
+            headers: Headers::new(),
             body: None,
+            raw_body: false,
+            body_encoding: None,
+            timeout_override: None,
+            retries_override: None,
+            no_cookies: false,
+            filter: None,
+            pipe: None,
+            body_file: None,
+            conditional_headers: Vec::new(),
+            captures: Vec::new(),
+            digest_auth: None,
+            aws_sigv4: None,
+            multipart: None,
+            form_fields: Vec::new(),
+            query_api_key: None,
+            api_key_header: None,
             line_number: 0,
             file_path: PathBuf::from("test.http"),
         }
@@ -626,9 +814,19 @@ mod tests {
                 is_success: true,
                 is_truncated: false,
                 timing_breakdown: "Total: 150ms".to_string(),
+                rate_limit: None,
+                warnings: Vec::new(),
+                final_url: None,
+                throughput_bps: None,
+                chunked_lines: None,
+                original_encoding: None,
+                compressed_size: None,
+                section_sizes: None,
             },
             highlight_info: None,
             is_formatted: true,
+            filter: None,
+            filter_warning: None,
         }
     }
 
@@ -712,6 +910,23 @@ mod tests {
         assert!(result.message.contains("response body"));
     }
 
+    #[test]
+    fn test_copy_response_with_limit() {
+        let response = create_test_response(ContentType::Json, r#"{"test": "data"}"#);
+
+        let result = copy_response_with_limit(&response, CopyOption::Body, 16);
+        assert!(result.success);
+        assert_eq!(result.content, r#"{"test": "data"}"#);
+
+        let result = copy_response_with_limit(&response, CopyOption::Body, 15);
+        assert!(!result.success);
+        assert!(result.content.is_empty());
+        assert_eq!(result.content_size, 16);
+        assert!(result
+            .message
+            .contains("exceeds the clipboard limit of 15 bytes"));
+    }
+
     #[test]
     fn test_copy_response_headers() {
         let response = create_test_response(ContentType::Json, r#"{"test": "data"}"#);
@@ -773,6 +988,34 @@ mod tests {
         assert!(!result.is_folded);
     }
 
+    #[test]
+    fn test_fold_response_keys() {
+        let body = r#"{"data": {"id": 1}, "items": [1, 2, 3], "empty": {}, "total": 3, "next": null, "note": "abcdefghijklmnopqrstuvwxyzabcdefghijklmnopqrstuvwxyz"}"#;
+        let response = create_test_response(ContentType::Json, body);
+
+        let result = fold_response_keys(&response);
+
+        assert_eq!(result.mode, FoldMode::TopLevelKeys);
+        assert_eq!(result.sections_folded, 3);
+        assert!(result.is_folded);
+        assert!(result.folded_response.ends_with(
+            "{\n  data: {…}\n  empty: {}\n  items: [3]\n  next: null\n  note: \"abcdefghijklmnopqrstuvwxyzabcdefghijklmn…\"\n  total: 3\n}"
+        ));
+    }
+
+    #[test]
+    fn test_fold_response_keys_non_object() {
+        let response = create_test_response(ContentType::Json, "[1, 2]");
+        assert!(fold_response_keys(&response)
+            .folded_response
+            .ends_with("\n\n[2]"));
+
+        let response = create_test_response(ContentType::PlainText, "plain text");
+        let result = fold_response_keys(&response);
+        assert!(!result.is_folded);
+        assert!(result.folded_response.ends_with("plain text"));
+    }
+
     #[test]
     fn test_format_action_menu() {
         let response = create_test_response(ContentType::Json, r#"{"test": "data"}"#);
@@ -16,6 +16,7 @@
 //!
 //! The actual display happens via `SlashCommandOutput` text sections in `lib.rs`.
 
+use crate::config::ResponsePanePosition;
 use crate::formatter::FormattedResponse;
 use crate::models::request::HttpRequest;
 use std::collections::VecDeque;
@@ -66,6 +67,31 @@ impl PanePosition {
     }
 }
 
+/// Maps the `responsePane` setting onto a layout position.
+///
+/// `Tab` has no split to place, so it falls back to the default pane on the right.
+impl From<ResponsePanePosition> for PanePosition {
+    fn from(position: ResponsePanePosition) -> Self {
+        match position {
+            ResponsePanePosition::Right | ResponsePanePosition::Tab => PanePosition::Right,
+            ResponsePanePosition::Below => PanePosition::Bottom,
+        }
+    }
+}
+
+/// Maps a layout position back onto the closest `responsePane` setting.
+///
+/// The setting only has a horizontal and a vertical split, so `Left` maps to
+/// `Right` and `Top` maps to `Below`.
+impl From<PanePosition> for ResponsePanePosition {
+    fn from(position: PanePosition) -> Self {
+        match position {
+            PanePosition::Right | PanePosition::Left => ResponsePanePosition::Right,
+            PanePosition::Bottom | PanePosition::Top => ResponsePanePosition::Below,
+        }
+    }
+}
+
 /// Metadata for a response tab
 #[derive(Debug, Clone)]
 pub struct ResponseTab {
@@ -194,6 +220,9 @@ pub struct ResponsePane {
 
     /// ID of the currently active tab
     active_tab_id: Option<String>,
+
+    /// Tab removed by the most recent `create_response_tab` to stay under the limit
+    last_evicted: Option<ResponseTab>,
 }
 
 impl ResponsePane {
@@ -212,6 +241,7 @@ impl ResponsePane {
             max_tabs: MAX_RESPONSE_TABS,
             position,
             active_tab_id: None,
+            last_evicted: None,
         }
     }
 
@@ -248,9 +278,11 @@ impl ResponsePane {
         self.tabs.push_back(tab);
 
         // Remove oldest tab if we exceed the limit
-        if self.tabs.len() > self.max_tabs {
-            self.tabs.pop_front();
-        }
+        self.last_evicted = if self.tabs.len() > self.max_tabs {
+            self.tabs.pop_front()
+        } else {
+            None
+        };
 
         // Update active tab ID
         self.active_tab_id = Some(tab_id.clone());
@@ -369,6 +401,16 @@ impl ResponsePane {
         self.position = position;
     }
 
+    /// Get the tab evicted by the most recent `create_response_tab`
+    ///
+    /// # Returns
+    ///
+    /// The oldest tab if it was closed to make room, or `None` if the limit
+    /// wasn't reached
+    pub fn last_evicted(&self) -> Option<&ResponseTab> {
+        self.last_evicted.as_ref()
+    }
+
     /// Get the maximum number of tabs
     pub fn max_tabs(&self) -> usize {
         self.max_tabs
@@ -399,18 +441,35 @@ mod tests {
     use super::*;
     use crate::formatter::{ContentType, ResponseMetadata};
     use crate::models::request::HttpMethod;
-    use std::collections::HashMap;
+    use crate::models::Headers;
     use std::path::PathBuf;
     use std::time::Duration;
 
     fn create_test_request(method: HttpMethod, url: &str) -> HttpRequest {
         HttpRequest {
             id: Uuid::new_v4().to_string(),
+            name: None,
             method,
             url: url.to_string(),
             http_version: Some("HTTP/1.1".to_string()),
-            headers: HashMap::new(),
+            headers: Headers::new(),
             body: None,
+            raw_body: false,
+            body_encoding: None,
+            timeout_override: None,
+            retries_override: None,
+            no_cookies: false,
+            filter: None,
+            pipe: None,
+            body_file: None,
+            conditional_headers: Vec::new(),
+            captures: Vec::new(),
+            digest_auth: None,
+            aws_sigv4: None,
+            multipart: None,
+            form_fields: Vec::new(),
+            query_api_key: None,
+            api_key_header: None,
             line_number: 0,
             file_path: PathBuf::from("test.http"),
         }
@@ -432,9 +491,19 @@ mod tests {
                 is_success: true,
                 is_truncated: false,
                 timing_breakdown: "Total: 150ms".to_string(),
+                rate_limit: None,
+                warnings: Vec::new(),
+                final_url: None,
+                throughput_bps: None,
+                chunked_lines: None,
+                original_encoding: None,
+                compressed_size: None,
+                section_sizes: None,
             },
             highlight_info: None,
             is_formatted: true,
+            filter: None,
+            filter_warning: None,
         }
     }
 
@@ -447,6 +516,46 @@ mod tests {
         assert_eq!(PanePosition::from_str("invalid"), None);
     }
 
+    #[test]
+    fn test_pane_position_from_response_pane_position() {
+        // Listing variants through an exhaustive match makes this test fail
+        // to compile when a variant is added without a mapping
+        let variant = |position: ResponsePanePosition| match position {
+            ResponsePanePosition::Right => PanePosition::Right,
+            ResponsePanePosition::Below => PanePosition::Bottom,
+            ResponsePanePosition::Tab => PanePosition::Right,
+        };
+
+        for position in [
+            ResponsePanePosition::Right,
+            ResponsePanePosition::Below,
+            ResponsePanePosition::Tab,
+        ] {
+            let pane_position = PanePosition::from(position);
+            assert_eq!(pane_position, variant(position));
+            assert!(PanePosition::from_str(pane_position.as_str()).is_some());
+        }
+    }
+
+    #[test]
+    fn test_response_pane_position_from_pane_position() {
+        for position in [
+            PanePosition::Right,
+            PanePosition::Left,
+            PanePosition::Bottom,
+            PanePosition::Top,
+        ] {
+            let setting = ResponsePanePosition::from(position);
+            // Converting back lands on the same axis
+            let round_trip = PanePosition::from(setting);
+            let vertical = |p: PanePosition| matches!(p, PanePosition::Top | PanePosition::Bottom);
+            assert_eq!(vertical(round_trip), vertical(position));
+        }
+
+        let position: ResponsePanePosition = PanePosition::Bottom.into();
+        assert_eq!(position, ResponsePanePosition::Below);
+    }
+
     #[test]
     fn test_generate_title() {
         let req1 = create_test_request(HttpMethod::GET, "https://api.example.com/users");
@@ -500,6 +609,26 @@ mod tests {
 
         let tabs = pane.list_tabs();
         assert_eq!(tabs.len(), 3);
+
+        // The last insert pushed out req-1
+        assert_eq!(pane.last_evicted().unwrap().request_id, "req-1");
+    }
+
+    #[test]
+    fn test_last_evicted_cleared_below_limit() {
+        let mut pane = ResponsePane::new(PanePosition::Right);
+        pane.set_max_tabs(1);
+
+        for i in 0..2 {
+            let request = create_test_request(HttpMethod::GET, "https://api.example.com/users");
+            pane.create_response_tab(create_test_response(), request, &format!("req-{}", i));
+        }
+        assert_eq!(pane.last_evicted().unwrap().request_id, "req-0");
+
+        pane.set_max_tabs(5);
+        let request = create_test_request(HttpMethod::GET, "https://api.example.com/users");
+        pane.create_response_tab(create_test_response(), request, "req-2");
+        assert!(pane.last_evicted().is_none());
     }
 
     #[test]
@@ -25,7 +25,13 @@
 //! # @capture sessionId = headers.X-Session-Id
 //! ```
 
+use super::request::{extract_response_variable, resolve_response_reference, ContentType};
+use super::VarError;
+use crate::models::request::HttpRequest;
+use crate::models::response::HttpResponse;
 use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::sync::LazyLock;
 
 /// Regex pattern for matching capture directives.
@@ -40,7 +46,7 @@ static CAPTURE_DIRECTIVE_REGEX: LazyLock<Regex> = LazyLock::new(|| {
 });
 
 /// Type of extraction path used in a capture directive.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum PathType {
     /// JSONPath expression for extracting from JSON responses.
     ///
@@ -101,7 +107,7 @@ impl PathType {
 ///
 /// Represents a single `@capture` comment that extracts a value from
 /// a response and stores it in a named variable.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct CaptureDirective {
     /// Name of the variable to store the captured value.
     ///
@@ -129,6 +135,162 @@ impl CaptureDirective {
             path,
         }
     }
+
+    /// Extracts this directive's value from a response.
+    ///
+    /// JSONPath expressions (e.g., `$.a.b[0].c`) are evaluated against the
+    /// body, assumed to be JSON when the response has no Content-Type.
+    ///
+    /// # Arguments
+    ///
+    /// * `response` - The response to extract from
+    ///
+    /// # Returns
+    ///
+    /// `Ok(String)` with the extracted value, or `Err(VarError)` if the path
+    /// does not match the response.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rest_client::models::response::HttpResponse;
+    /// use rest_client::variables::capture::parse_capture_directive;
+    ///
+    /// let mut response = HttpResponse::new(200, "OK".to_string());
+    /// response.set_body(br#"{"auth": {"token": "abc123"}}"#.to_vec());
+    ///
+    /// let directive = parse_capture_directive("# @capture token = $.auth.token").unwrap();
+    /// assert_eq!(directive.extract(&response).unwrap(), "abc123");
+    /// ```
+    pub fn extract(&self, response: &HttpResponse) -> Result<String, VarError> {
+        match &self.path {
+            PathType::JsonPath(path) => resolve_response_reference(response, path),
+            PathType::Header(name) => {
+                resolve_response_reference(response, &format!("headers.{}", name))
+            }
+            PathType::XPath(path) => {
+                extract_response_variable(response, path, ContentType::from_response(response))
+            }
+        }
+    }
+}
+
+/// Values captured with `@capture`, scoped by the request that captured them.
+///
+/// Every value is reachable by its bare variable name, which refers to the
+/// most recent capture of that name (last writer wins). Values captured by a
+/// request named with `# @name` are also kept under that name, so
+/// `login.token` and `refresh.token` coexist even though `token` only holds
+/// whichever ran last.
+///
+/// # Examples
+///
+/// ```
+/// use rest_client::variables::capture::CapturedVariables;
+///
+/// let mut captured = CapturedVariables::new();
+/// captured.insert(Some("login"), "token", "abc".to_string());
+/// captured.insert(Some("refresh"), "token", "xyz".to_string());
+///
+/// assert_eq!(captured.get("login.token"), Some("abc"));
+/// assert_eq!(captured.get("refresh.token"), Some("xyz"));
+/// assert_eq!(captured.get("token"), Some("xyz"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CapturedVariables {
+    /// Values keyed by request name, then variable name
+    scoped: HashMap<String, HashMap<String, String>>,
+
+    /// Most recent value of each variable name
+    latest: HashMap<String, String>,
+}
+
+impl CapturedVariables {
+    /// Creates an empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stores a captured value.
+    ///
+    /// # Arguments
+    ///
+    /// * `request_name` - The capturing request's `@name`, if it has one
+    /// * `variable` - The variable name from the directive
+    /// * `value` - The captured value
+    pub fn insert(&mut self, request_name: Option<&str>, variable: &str, value: String) {
+        if let Some(request_name) = request_name {
+            self.scoped
+                .entry(request_name.to_string())
+                .or_default()
+                .insert(variable.to_string(), value.clone());
+        }
+        self.latest.insert(variable.to_string(), value);
+    }
+
+    /// Looks up a captured value.
+    ///
+    /// A name containing a dot (`login.token`) is looked up in the scope of
+    /// the request named before the dot; a bare name (`token`) returns the
+    /// most recent capture of that variable.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - A qualified or bare variable name
+    ///
+    /// # Returns
+    ///
+    /// The captured value, or `None` if nothing was captured under `name`.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        let value = match name.split_once('.') {
+            Some((request_name, variable)) => self.scoped.get(request_name)?.get(variable),
+            None => self.latest.get(name),
+        };
+        value.map(String::as_str)
+    }
+
+    /// Returns true if nothing has been captured.
+    pub fn is_empty(&self) -> bool {
+        self.latest.is_empty()
+    }
+
+    /// Removes all captured values.
+    pub fn clear(&mut self) {
+        self.scoped.clear();
+        self.latest.clear();
+    }
+}
+
+/// Runs a request's `@capture` directives against its response.
+///
+/// Each captured value is stored under the request's `# @name`, if any, and
+/// as the latest value of its variable name (see [`CapturedVariables`]).
+/// Directives that fail are skipped.
+///
+/// # Arguments
+///
+/// * `request` - The executed request, carrying its capture directives
+/// * `response` - The response received for it
+/// * `captured` - Captured variables, updated in place
+///
+/// # Returns
+///
+/// The variable name and error of each directive that could not be applied.
+pub fn store_captures(
+    request: &HttpRequest,
+    response: &HttpResponse,
+    captured: &mut CapturedVariables,
+) -> Vec<(String, VarError)> {
+    let mut failures = Vec::new();
+
+    for directive in &request.captures {
+        match directive.extract(response) {
+            Ok(value) => captured.insert(request.name.as_deref(), &directive.variable_name, value),
+            Err(e) => failures.push((directive.variable_name.clone(), e)),
+        }
+    }
+
+    failures
 }
 
 /// Parses a capture directive from a comment line.
@@ -287,6 +449,33 @@ pub fn validate_header_name(header_name: &str) -> bool {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_captured_variables_scoping() {
+        let mut captured = CapturedVariables::new();
+        assert!(captured.is_empty());
+
+        captured.insert(Some("login"), "token", "first".to_string());
+        captured.insert(Some("refresh"), "token", "second".to_string());
+        captured.insert(None, "id", "42".to_string());
+
+        assert_eq!(captured.get("login.token"), Some("first"));
+        assert_eq!(captured.get("refresh.token"), Some("second"));
+        assert_eq!(captured.get("token"), Some("second"));
+        assert_eq!(captured.get("id"), Some("42"));
+        assert_eq!(captured.get("login.id"), None);
+        assert_eq!(captured.get("other.token"), None);
+
+        // Re-running a request replaces its own value and the latest one
+        captured.insert(Some("login"), "token", "third".to_string());
+        assert_eq!(captured.get("login.token"), Some("third"));
+        assert_eq!(captured.get("refresh.token"), Some("second"));
+        assert_eq!(captured.get("token"), Some("third"));
+
+        captured.clear();
+        assert!(captured.is_empty());
+        assert_eq!(captured.get("login.token"), None);
+    }
+
     #[test]
     fn test_parse_capture_directive_jsonpath() {
         let result = parse_capture_directive("# @capture token = $.access_token");
@@ -343,6 +532,14 @@ mod tests {
         assert!(parse_capture_directive("@capture token = $.path").is_none()); // Missing #
     }
 
+    #[test]
+    fn test_parse_capture_directive_disabled() {
+        assert!(parse_capture_directive("# !@capture token = $.access_token").is_none());
+        assert!(
+            parse_capture_directives("# !@capture token = $.a\n# @capture id = $.id").len() == 1
+        );
+    }
+
     #[test]
     fn test_parse_capture_directive_invalid_variable_name() {
         // Invalid variable names (must start with letter or underscore)
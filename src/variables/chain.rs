@@ -0,0 +1,366 @@
+//! Request chaining with named response references.
+//!
+//! A [`RequestChain`] executes requests in order and retains the response of
+//! every named request. Later requests can reference those responses inline
+//! with `{{name.response.<path>}}`, where the path is a JSONPath expression
+//! evaluated at substitution time (e.g., `{{login.response.$.token}}`).
+//!
+//! Requests can also declare `# @capture` directives. Their values are
+//! stored after the request runs and resolve as `{{variable}}` or, for named
+//! requests, `{{name.variable}}`. References read directly from the stored
+//! response instead, so any field can be used without declaring it up front.
+//! Both are inserted literally: `{{...}}` in a response is not substituted.
+//!
+//! # Examples
+//!
+//! ```
+//! use rest_client::models::request::{HttpMethod, HttpRequest};
+//! use rest_client::models::response::HttpResponse;
+//! use rest_client::variables::chain::RequestChain;
+//! use rest_client::variables::substitution::VariableContext;
+//! use std::path::PathBuf;
+//!
+//! let mut chain = RequestChain::new();
+//! let context = VariableContext::new(PathBuf::from("/workspace"));
+//!
+//! let login = HttpRequest::new(
+//!     "1".to_string(),
+//!     HttpMethod::POST,
+//!     "https://api.example.com/login".to_string(),
+//! );
+//! chain
+//!     .execute(Some("login"), &login, &context, |_| {
+//!         let mut response = HttpResponse::new(200, "OK".to_string());
+//!         response.set_body(br#"{"token": "abc123"}"#.to_vec());
+//!         Ok(response)
+//!     })
+//!     .unwrap();
+//!
+//! let mut profile = HttpRequest::new(
+//!     "2".to_string(),
+//!     HttpMethod::GET,
+//!     "https://api.example.com/me".to_string(),
+//! );
+//! profile.add_header(
+//!     "Authorization".to_string(),
+//!     "Bearer {{login.response.$.token}}".to_string(),
+//! );
+//!
+//! let resolved = chain.resolve(&profile, &context).unwrap();
+//! assert_eq!(resolved.headers["Authorization"], "Bearer abc123");
+//! ```
+
+use super::capture::{store_captures, CapturedVariables};
+use super::substitution::{substitute_request, VariableContext};
+use super::VarError;
+use crate::executor::RequestError;
+use crate::models::request::HttpRequest;
+use crate::models::response::HttpResponse;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+/// Errors that can occur while running a request chain.
+#[derive(Debug)]
+pub enum ChainError {
+    /// A variable or response reference could not be resolved.
+    Variable(VarError),
+
+    /// The resolved request failed to execute.
+    Execution(RequestError),
+}
+
+impl fmt::Display for ChainError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChainError::Variable(err) => write!(f, "Failed to resolve variables: {}", err),
+            ChainError::Execution(err) => write!(f, "Request failed: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for ChainError {}
+
+impl From<VarError> for ChainError {
+    fn from(err: VarError) -> Self {
+        ChainError::Variable(err)
+    }
+}
+
+impl From<RequestError> for ChainError {
+    fn from(err: RequestError) -> Self {
+        ChainError::Execution(err)
+    }
+}
+
+/// Runs requests in sequence, retaining the responses of named requests.
+#[derive(Debug, Clone, Default)]
+pub struct RequestChain {
+    /// Responses keyed by request name
+    responses: HashMap<String, Arc<HttpResponse>>,
+
+    /// Values captured with `# @capture`, scoped by request name
+    captured: CapturedVariables,
+}
+
+impl RequestChain {
+    /// Creates an empty chain with no stored responses.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stores the response of a named request, replacing any previous one.
+    pub fn store_response(&mut self, name: impl Into<String>, response: HttpResponse) {
+        self.responses.insert(name.into(), Arc::new(response));
+    }
+
+    /// Returns the stored response for a named request, if any.
+    pub fn response(&self, name: &str) -> Option<&HttpResponse> {
+        self.responses.get(name).map(Arc::as_ref)
+    }
+
+    /// Returns a value captured with `# @capture`, if any.
+    ///
+    /// `name` is either `<request name>.<variable name>` or a bare variable
+    /// name, which refers to the most recent capture of that variable.
+    pub fn captured(&self, name: &str) -> Option<&str> {
+        self.captured.get(name)
+    }
+
+    /// Resolves a request against `context` plus all stored responses.
+    ///
+    /// Returns an error if the request references a response that has not
+    /// been stored or a path that does not exist in it.
+    pub fn resolve(
+        &self,
+        request: &HttpRequest,
+        context: &VariableContext,
+    ) -> Result<HttpRequest, VarError> {
+        let mut context = context.clone();
+        context.captured_variables = self.captured.clone();
+        context.named_responses.extend(
+            self.responses
+                .iter()
+                .map(|(name, response)| (name.clone(), Arc::clone(response))),
+        );
+
+        substitute_request(request, &context)
+    }
+
+    /// Resolves and executes a request, storing the response under `name`.
+    ///
+    /// The request's `# @capture` directives are applied to the response;
+    /// a directive that doesn't match is reported and skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The request's `@name`, or `None` for anonymous requests
+    /// * `request` - The request as parsed from the `.http` file
+    /// * `context` - Variables available to the request
+    /// * `execute` - Executes the resolved request
+    ///
+    /// # Returns
+    ///
+    /// The response of the executed request.
+    pub fn execute<F>(
+        &mut self,
+        name: Option<&str>,
+        request: &HttpRequest,
+        context: &VariableContext,
+        execute: F,
+    ) -> Result<HttpResponse, ChainError>
+    where
+        F: FnOnce(&HttpRequest) -> Result<HttpResponse, RequestError>,
+    {
+        let resolved = self.resolve(request, context)?;
+        let response = execute(&resolved)?;
+
+        for (variable, err) in store_captures(request, &response, &mut self.captured) {
+            eprintln!("Warning: Failed to capture '{}': {}", variable, err);
+        }
+
+        if let Some(name) = name {
+            self.store_response(name, response.clone());
+        }
+
+        Ok(response)
+    }
+
+    /// Removes all stored responses and captured values.
+    pub fn clear(&mut self) {
+        self.responses.clear();
+        self.captured.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::request::HttpMethod;
+    use crate::variables::capture::parse_capture_directive;
+    use std::path::PathBuf;
+
+    fn json_response(body: &str) -> HttpResponse {
+        let mut response = HttpResponse::new(200, "OK".to_string());
+        response.add_header("Content-Type".to_string(), "application/json".to_string());
+        response.set_body(body.as_bytes().to_vec());
+        response
+    }
+
+    fn request(url: &str) -> HttpRequest {
+        HttpRequest::new("test".to_string(), HttpMethod::GET, url.to_string())
+    }
+
+    #[test]
+    fn test_execute_stores_named_response() {
+        let mut chain = RequestChain::new();
+        let context = VariableContext::new(PathBuf::from("/workspace"));
+
+        chain
+            .execute(
+                Some("login"),
+                &request("https://api.example.com/login"),
+                &context,
+                |_| Ok(json_response(r#"{"token": "abc"}"#)),
+            )
+            .unwrap();
+
+        assert!(chain.response("login").is_some());
+
+        let mut sent_url = String::new();
+        chain
+            .execute(
+                None,
+                &request("https://api.example.com/users?token={{login.response.$.token}}"),
+                &context,
+                |resolved| {
+                    sent_url = resolved.url.clone();
+                    Ok(json_response("{}"))
+                },
+            )
+            .unwrap();
+
+        assert_eq!(sent_url, "https://api.example.com/users?token=abc");
+    }
+
+    #[test]
+    fn test_captured_values_resolve_in_later_requests() {
+        let mut chain = RequestChain::new();
+        let context = VariableContext::new(PathBuf::from("/workspace"));
+
+        let mut login = request("https://api.example.com/login");
+        login.name = Some("login".to_string());
+        login.captures = vec![
+            parse_capture_directive("# @capture token = $.auth.token").unwrap(),
+            parse_capture_directive("# @capture firstId = $.items[0].id").unwrap(),
+            parse_capture_directive("# @capture missing = $.nope").unwrap(),
+        ];
+        chain
+            .execute(Some("login"), &login, &context, |_| {
+                Ok(json_response(
+                    r#"{"auth": {"token": "abc"}, "items": [{"id": 7}]}"#,
+                ))
+            })
+            .unwrap();
+
+        assert_eq!(chain.captured("login.token"), Some("abc"));
+        assert_eq!(chain.captured("firstId"), Some("7"));
+        assert_eq!(chain.captured("missing"), None);
+
+        let resolved = chain
+            .resolve(
+                &request("https://api.example.com/items/{{login.firstId}}?t={{token}}"),
+                &context,
+            )
+            .unwrap();
+        assert_eq!(resolved.url, "https://api.example.com/items/7?t=abc");
+    }
+
+    #[test]
+    fn test_captures_with_the_same_name_are_scoped_by_request() {
+        let mut chain = RequestChain::new();
+        let context = VariableContext::new(PathBuf::from("/workspace"));
+
+        for (name, token) in [("login", "first"), ("refresh", "second")] {
+            let mut named = request("https://api.example.com/auth");
+            named.name = Some(name.to_string());
+            named.captures = vec![parse_capture_directive("# @capture token = $.token").unwrap()];
+            let body = format!(r#"{{"token": "{}"}}"#, token);
+            chain
+                .execute(Some(name), &named, &context, |_| Ok(json_response(&body)))
+                .unwrap();
+        }
+
+        let resolved = chain
+            .resolve(
+                &request(
+                    "https://api.example.com/?a={{login.token}}&b={{refresh.token}}&c={{token}}",
+                ),
+                &context,
+            )
+            .unwrap();
+        assert_eq!(
+            resolved.url,
+            "https://api.example.com/?a=first&b=second&c=second"
+        );
+    }
+
+    #[test]
+    fn test_undefined_reference_errors_before_execution() {
+        let mut chain = RequestChain::new();
+        let context = VariableContext::new(PathBuf::from("/workspace"));
+        let mut executed = false;
+
+        let result = chain.execute(
+            None,
+            &request("https://api.example.com/{{login.response.$.id}}"),
+            &context,
+            |_| {
+                executed = true;
+                Ok(json_response("{}"))
+            },
+        );
+
+        assert!(!executed);
+        let err = result.unwrap_err();
+        assert!(matches!(err, ChainError::Variable(_)));
+        assert!(err.to_string().contains("login"));
+    }
+
+    #[test]
+    fn test_response_values_are_not_substituted_again() {
+        let mut chain = RequestChain::new();
+        let context = VariableContext::new(PathBuf::from("/workspace"));
+
+        let mut login = request("https://api.example.com/login");
+        login.captures = vec![parse_capture_directive("# @capture note = $.note").unwrap()];
+        chain
+            .execute(Some("login"), &login, &context, |_| {
+                Ok(json_response(r#"{"note": "{{$processEnv HOME}}"}"#))
+            })
+            .unwrap();
+
+        let mut echo = request("https://api.example.com/echo");
+        echo.body = Some("{{login.response.$.note}} {{note}}".to_string());
+        let resolved = chain.resolve(&echo, &context).unwrap();
+
+        assert_eq!(
+            resolved.body.as_deref(),
+            Some("{{$processEnv HOME}} {{$processEnv HOME}}")
+        );
+    }
+
+    #[test]
+    fn test_missing_path_in_stored_response() {
+        let mut chain = RequestChain::new();
+        chain.store_response("login", json_response(r#"{"token": "abc"}"#));
+        let context = VariableContext::new(PathBuf::from("/workspace"));
+
+        let result = chain.resolve(
+            &request("https://api.example.com/{{login.response.$.missing}}"),
+            &context,
+        );
+
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,349 @@
+//! Importing requests from other `.http` files with `@use`.
+//!
+//! A file-level `@use <path>` directive (or `# @use <path>`) names another
+//! `.http` file whose requests run before the importing file's own. They run
+//! into the same [`RequestChain`], so their `# @capture` values and named
+//! responses resolve in the importing file, e.g. a token captured by a login
+//! request in a shared `auth.http`.
+//!
+//! Only `@use` lines before the file's first request count. Paths are
+//! relative to the importing file's directory and are normalized lexically,
+//! since the WASM sandbox can't canonicalize them; imported files may import
+//! others in turn. A file that ends up importing itself is
+//! reported as a cycle instead of being run again, and a file imported more
+//! than once in the same run is only run the first time.
+//!
+//! Importing a file sends its requests: whatever they do on the server
+//! (logging in, creating resources) happens again on every run.
+
+use super::chain::{ChainError, RequestChain};
+use super::substitution::VariableContext;
+use crate::executor::RequestError;
+use crate::models::request::HttpRequest;
+use crate::models::response::HttpResponse;
+use crate::parser::{parse_file, parse_use_directive};
+use std::fmt;
+use std::path::{Component, Path, PathBuf};
+
+/// Errors that can occur while running imported files.
+#[derive(Debug)]
+pub enum ImportError {
+    /// A file imports itself, directly or through other files.
+    Cycle(Vec<PathBuf>),
+
+    /// An imported file could not be read.
+    Read { path: PathBuf, message: String },
+
+    /// An imported file could not be parsed.
+    Parse { path: PathBuf, message: String },
+
+    /// A request in an imported file failed to resolve or execute.
+    Request {
+        path: PathBuf,
+        line: usize,
+        error: ChainError,
+    },
+}
+
+impl fmt::Display for ImportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImportError::Cycle(paths) => write!(
+                f,
+                "Import cycle: {}",
+                paths
+                    .iter()
+                    .map(|path| path.display().to_string())
+                    .collect::<Vec<_>>()
+                    .join(" -> ")
+            ),
+            ImportError::Read { path, message } => write!(
+                f,
+                "Failed to read imported file '{}': {}",
+                path.display(),
+                message
+            ),
+            ImportError::Parse { path, message } => write!(
+                f,
+                "Failed to parse imported file '{}': {}",
+                path.display(),
+                message
+            ),
+            ImportError::Request { path, line, error } => write!(
+                f,
+                "Request at line {} of imported file '{}' failed: {}",
+                line,
+                path.display(),
+                error
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ImportError {}
+
+/// Returns the paths named by `@use` directives in a file, in order.
+///
+/// Only the file-level lines before the first request are read, so a `@use`
+/// in a request body or further down the file is not an import.
+///
+/// # Arguments
+///
+/// * `content` - The full content of the `.http` file
+pub fn use_directives(content: &str) -> Vec<&str> {
+    content
+        .lines()
+        .take_while(|line| {
+            let trimmed = line.trim();
+            trimmed.is_empty()
+                || trimmed.starts_with('#')
+                || trimmed.starts_with("//")
+                || trimmed.starts_with('@')
+        })
+        .filter_map(parse_use_directive)
+        .collect()
+}
+
+/// Resolves `.` and `..` components of a path without touching the
+/// filesystem.
+///
+/// A `..` that would climb above the start of a relative path is kept.
+fn normalize_path(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => match normalized.components().next_back() {
+                Some(Component::Normal(_)) => {
+                    normalized.pop();
+                }
+                Some(Component::RootDir) | Some(Component::Prefix(_)) => {}
+                _ => normalized.push(".."),
+            },
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
+/// Runs every file a `.http` file imports with `@use`, in order.
+///
+/// Each imported file's own imports run first, then its requests are resolved
+/// and executed one at a time into `chain`. The first request that fails stops
+/// the run, since the importing file usually depends on it.
+///
+/// # Arguments
+///
+/// * `content` - The full content of the importing file
+/// * `base_dir` - Directory that `@use` paths in `content` are relative to
+/// * `chain` - The chain the imported requests run into
+/// * `context` - Variables available to the imported requests
+/// * `execute` - Executes a resolved request
+///
+/// # Returns
+///
+/// The normalized paths of the files that were run, in the order they ran, or
+/// an `ImportError` if a file can't be read or parsed, a request fails, or
+/// the imports form a cycle.
+pub fn run_imports<F>(
+    content: &str,
+    base_dir: &Path,
+    chain: &mut RequestChain,
+    context: &VariableContext,
+    execute: &F,
+) -> Result<Vec<PathBuf>, ImportError>
+where
+    F: Fn(&HttpRequest) -> Result<HttpResponse, RequestError>,
+{
+    let mut imported = Vec::new();
+    run_imports_from(
+        content,
+        base_dir,
+        chain,
+        context,
+        execute,
+        &mut Vec::new(),
+        &mut imported,
+    )?;
+    Ok(imported)
+}
+
+/// Runs the imports of one file, tracking the files being imported in
+/// `stack` to detect cycles.
+fn run_imports_from<F>(
+    content: &str,
+    base_dir: &Path,
+    chain: &mut RequestChain,
+    context: &VariableContext,
+    execute: &F,
+    stack: &mut Vec<PathBuf>,
+    imported: &mut Vec<PathBuf>,
+) -> Result<(), ImportError>
+where
+    F: Fn(&HttpRequest) -> Result<HttpResponse, RequestError>,
+{
+    for path in use_directives(content) {
+        let path = normalize_path(&base_dir.join(path));
+
+        if stack.contains(&path) {
+            let mut cycle = stack.clone();
+            cycle.push(path);
+            return Err(ImportError::Cycle(cycle));
+        }
+        if imported.contains(&path) {
+            continue;
+        }
+
+        let text = std::fs::read_to_string(&path).map_err(|e| ImportError::Read {
+            path: path.clone(),
+            message: e.to_string(),
+        })?;
+
+        stack.push(path.clone());
+        let dir = path.parent().unwrap_or(base_dir).to_path_buf();
+        run_imports_from(&text, &dir, chain, context, execute, stack, imported)?;
+
+        let requests = parse_file(&text, &path).map_err(|e| ImportError::Parse {
+            path: path.clone(),
+            message: e.to_string(),
+        })?;
+        for request in &requests {
+            chain
+                .execute(request.name.as_deref(), request, context, |resolved| {
+                    execute(resolved)
+                })
+                .map_err(|error| ImportError::Request {
+                    path: path.clone(),
+                    line: request.line_number,
+                    error,
+                })?;
+        }
+
+        stack.pop();
+        imported.push(path);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    fn json_response(body: &str) -> HttpResponse {
+        let mut response = HttpResponse::new(200, "OK".to_string());
+        response.add_header("Content-Type".to_string(), "application/json".to_string());
+        response.set_body(body.as_bytes().to_vec());
+        response
+    }
+
+    #[test]
+    fn test_use_directives() {
+        let content = "@use ./auth.http\n# @use ../shared/setup.http\n\nGET https://example.com";
+        assert_eq!(
+            use_directives(content),
+            vec!["./auth.http", "../shared/setup.http"]
+        );
+    }
+
+    #[test]
+    fn test_use_directives_only_before_first_request() {
+        let content = "@use = not-an-import\n@use ./auth.http\n\nPOST https://example.com\n\n@use ./body.http\n###\n@use ./later.http\nGET https://example.com";
+        assert_eq!(use_directives(content), vec!["./auth.http"]);
+    }
+
+    #[test]
+    fn test_normalize_path() {
+        assert_eq!(
+            normalize_path(Path::new("/work/api/./../shared/auth.http")),
+            PathBuf::from("/work/shared/auth.http")
+        );
+        assert_eq!(
+            normalize_path(Path::new("api/../../auth.http")),
+            PathBuf::from("../auth.http")
+        );
+    }
+
+    #[test]
+    fn test_run_imports_captures_values() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            dir.path().join("auth.http"),
+            "# @name login\nPOST https://api.example.com/login\n# @capture token = $.token\n",
+        )
+        .unwrap();
+
+        let sent = RefCell::new(Vec::new());
+        let execute = |request: &HttpRequest| {
+            sent.borrow_mut().push(request.url.clone());
+            Ok(json_response(r#"{"token": "abc123"}"#))
+        };
+        let mut chain = RequestChain::new();
+        let context = VariableContext::new(dir.path().to_path_buf());
+
+        let imported = run_imports(
+            "@use ./auth.http\nGET https://api.example.com/me",
+            dir.path(),
+            &mut chain,
+            &context,
+            &execute,
+        )
+        .unwrap();
+
+        assert_eq!(imported.len(), 1);
+        assert!(imported[0].ends_with("auth.http"));
+        assert_eq!(*sent.borrow(), vec!["https://api.example.com/login"]);
+        assert_eq!(chain.captured("token"), Some("abc123"));
+        assert!(chain.response("login").is_some());
+    }
+
+    #[test]
+    fn test_run_imports_detects_cycle() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            dir.path().join("a.http"),
+            "@use ./b.http\nGET https://example.com/a\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("b.http"),
+            "@use ./a.http\nGET https://example.com/b\n",
+        )
+        .unwrap();
+
+        let execute = |_: &HttpRequest| Ok(json_response("{}"));
+        let result = run_imports(
+            "@use ./a.http",
+            dir.path(),
+            &mut RequestChain::new(),
+            &VariableContext::new(dir.path().to_path_buf()),
+            &execute,
+        );
+
+        match result {
+            Err(ImportError::Cycle(paths)) => {
+                assert_eq!(paths.len(), 3);
+                assert!(paths[0].ends_with("a.http"));
+                assert!(paths[2].ends_with("a.http"));
+            }
+            other => panic!("Expected an import cycle, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_run_imports_missing_file() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let execute = |_: &HttpRequest| Ok(json_response("{}"));
+
+        let result = run_imports(
+            "@use ./missing.http",
+            dir.path(),
+            &mut RequestChain::new(),
+            &VariableContext::new(dir.path().to_path_buf()),
+            &execute,
+        );
+
+        assert!(matches!(result, Err(ImportError::Read { .. })));
+    }
+}
@@ -4,13 +4,23 @@
 //! including system variables, environment variables, and request variables.
 
 pub mod capture;
+pub mod chain;
 pub mod environment;
+pub mod imports;
 pub mod request;
 pub mod substitution;
 pub mod system;
 
-pub use capture::{parse_capture_directive, parse_capture_directives, CaptureDirective, PathType};
+pub use capture::{
+    parse_capture_directive, parse_capture_directives, store_captures, CaptureDirective,
+    CapturedVariables, PathType,
+};
+pub use chain::{ChainError, RequestChain};
 pub use environment::{resolve_environment_variable, resolve_with_fallback};
-pub use request::{extract_response_variable, ContentType};
-pub use substitution::{substitute_variables, VariableContext};
+pub use imports::{run_imports, ImportError};
+pub use request::{extract_response_variable, resolve_response_reference, ContentType};
+pub use substitution::{
+    analyze_variables, expand_colon_params, find_variable_references, substitute_request,
+    substitute_url, substitute_variables, VariableContext, VariableUsage,
+};
 pub use system::{clear_dotenv_cache, resolve_system_variable, VarError};
@@ -151,6 +151,64 @@ pub fn extract_response_variable(
     }
 }
 
+/// Resolves a `{{name.response.<path>}}` reference against a stored response.
+///
+/// The path is the part after `response.` and may be:
+/// - A JSONPath expression evaluated against the body (e.g., `$.token`)
+/// - `body` for the whole body, or `body.<field>` / `body.$.<path>` for a field
+/// - `headers.<Name>` for a response header
+///
+/// JSONPath references assume a JSON body when the response carries no
+/// Content-Type header.
+///
+/// # Examples
+///
+/// ```
+/// use rest_client::variables::request::resolve_response_reference;
+/// use rest_client::models::response::HttpResponse;
+///
+/// let mut response = HttpResponse::new(200, "OK".to_string());
+/// response.set_body(r#"{"token": "abc123"}"#.as_bytes().to_vec());
+///
+/// assert_eq!(resolve_response_reference(&response, "$.token").unwrap(), "abc123");
+/// assert_eq!(resolve_response_reference(&response, "body.token").unwrap(), "abc123");
+/// ```
+pub fn resolve_response_reference(response: &HttpResponse, path: &str) -> Result<String, VarError> {
+    let path = path.trim();
+
+    if path.starts_with("headers.") {
+        return extract_response_variable(response, path, ContentType::from_response(response));
+    }
+
+    if path == "body" {
+        return response
+            .body_as_string()
+            .map_err(|_| VarError::InvalidSyntax("Response body is not valid UTF-8".to_string()));
+    }
+
+    let jsonpath = if let Some(rest) = path.strip_prefix("body.") {
+        if rest.starts_with('$') {
+            rest.to_string()
+        } else {
+            format!("$.{}", rest)
+        }
+    } else if path.starts_with('$') {
+        path.to_string()
+    } else {
+        return Err(VarError::InvalidSyntax(format!(
+            "Unsupported response reference path '{}': expected a JSONPath, 'body' or 'headers.<name>'",
+            path
+        )));
+    };
+
+    let content_type = response
+        .content_type()
+        .map(ContentType::from_header)
+        .unwrap_or(ContentType::Json);
+
+    extract_response_variable(response, &jsonpath, content_type)
+}
+
 /// Extracts a header value from an HTTP response.
 ///
 /// # Arguments
@@ -578,6 +636,34 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_resolve_response_reference_paths() {
+        let mut response = HttpResponse::new(200, "OK".to_string());
+        response.set_body(r#"{"token": "abc", "user": {"id": 7}}"#.as_bytes().to_vec());
+        response.add_header("X-Request-Id".to_string(), "req-1".to_string());
+
+        assert_eq!(
+            resolve_response_reference(&response, "$.token").unwrap(),
+            "abc"
+        );
+        assert_eq!(
+            resolve_response_reference(&response, "body.user.id").unwrap(),
+            "7"
+        );
+        assert_eq!(
+            resolve_response_reference(&response, "body.$.user.id").unwrap(),
+            "7"
+        );
+        assert_eq!(
+            resolve_response_reference(&response, "headers.X-Request-Id").unwrap(),
+            "req-1"
+        );
+        assert!(resolve_response_reference(&response, "body")
+            .unwrap()
+            .contains("\"token\""));
+        assert!(resolve_response_reference(&response, "status").is_err());
+    }
+
     #[test]
     fn test_parse_jsonpath_segments() {
         let segments = parse_jsonpath_segments("user.name");
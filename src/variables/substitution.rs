@@ -4,12 +4,20 @@
 //! in HTTP request text with their resolved values. It supports nested variables,
 //! circular reference detection, and multiple variable types (system, environment, request, file).
 
+use super::capture::CapturedVariables;
+use super::request::resolve_response_reference;
 use super::{resolve_system_variable, VarError};
+use crate::auth::append_query_param;
+use crate::config::get_config;
 use crate::environment::Environment;
+use crate::models::multipart::MultipartPart;
+use crate::models::request::HttpRequest;
+use crate::models::response::HttpResponse;
 use once_cell::sync::Lazy;
 use regex::Regex;
-use std::collections::{HashMap, HashSet};
-use std::path::PathBuf;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 /// Maximum recursion depth for nested variable substitution
 const MAX_RECURSION_DEPTH: usize = 10;
@@ -19,6 +27,18 @@ const MAX_RECURSION_DEPTH: usize = 10;
 static VARIABLE_REGEX: Lazy<Regex> =
     Lazy::new(|| Regex::new(r"\{\{([^}]+)\}\}").expect("Failed to compile variable regex"));
 
+/// Cached regex pattern for matching `requestName.response.<path>` references.
+static RESPONSE_REFERENCE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^([A-Za-z_][\w-]*)\.response\.(.+)$")
+        .expect("Failed to compile response reference regex")
+});
+
+/// Cached regex pattern for the start of a `{{function(argument)}}` call.
+static FUNCTION_CALL_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\{\{\s*(base64|base64Decode|urlEncode|urlDecode)\(")
+        .expect("Failed to compile function call regex")
+});
+
 /// Context for variable resolution containing all available variable sources
 #[derive(Debug, Clone)]
 pub struct VariableContext {
@@ -34,6 +54,15 @@ pub struct VariableContext {
     /// Request-level variables captured from previous request responses
     pub request_variables: HashMap<String, String>,
 
+    /// Values captured with `# @capture`, referenced as `{{name.variable}}`
+    /// or, for the most recent capture, `{{variable}}`
+    pub captured_variables: CapturedVariables,
+
+    /// Responses of previously executed named requests, referenced as
+    /// `{{name.response.<path>}}`. Shared so contexts can be cloned without
+    /// copying response bodies.
+    pub named_responses: HashMap<String, Arc<HttpResponse>>,
+
     /// Workspace path for resolving relative file paths
     pub workspace_path: PathBuf,
 }
@@ -46,6 +75,8 @@ impl VariableContext {
             shared_variables: HashMap::new(),
             file_variables: HashMap::new(),
             request_variables: HashMap::new(),
+            captured_variables: CapturedVariables::new(),
+            named_responses: HashMap::new(),
             workspace_path,
         }
     }
@@ -61,6 +92,8 @@ impl VariableContext {
             shared_variables,
             file_variables: HashMap::new(),
             request_variables: HashMap::new(),
+            captured_variables: CapturedVariables::new(),
+            named_responses: HashMap::new(),
             workspace_path,
         }
     }
@@ -69,42 +102,75 @@ impl VariableContext {
     ///
     /// Priority order:
     /// 1. System variables ($ prefix)
-    /// 2. Request variables (from previous responses)
-    /// 3. File variables (defined in .http file)
-    /// 4. Environment variables (from active environment)
-    /// 5. Shared variables (fallback from all environments)
-    fn resolve_variable(&self, name: &str) -> Result<String, VarError> {
+    /// 2. Response references (`name.response.<path>`)
+    /// 3. Captured variables: `name.variable` from the request named `name`,
+    ///    or a bare `variable` from whichever request captured it last
+    /// 4. Request variables (from previous responses)
+    /// 5. File variables (defined in .http file)
+    /// 6. Environment variables (from active environment)
+    /// 7. Shared variables (fallback from all environments)
+    ///
+    /// Returns the value and whether it is literal. Values taken from
+    /// responses are data sent by a server, so `{{...}}` inside them must
+    /// not be substituted again.
+    fn resolve_variable(&self, name: &str) -> Result<(String, bool), VarError> {
         // System variables (e.g., {{$guid}}, {{$timestamp}})
         if name.starts_with('$') {
-            return self.resolve_system_variable_with_args(name);
+            return Ok((self.resolve_system_variable_with_args(name)?, false));
+        }
+
+        // Response references (e.g., {{login.response.$.token}})
+        if let Some(caps) = RESPONSE_REFERENCE_REGEX.captures(name) {
+            return Ok((self.resolve_response_reference(&caps[1], &caps[2])?, true));
+        }
+
+        // Captured variables (e.g., {{login.token}} or {{token}})
+        if let Some(value) = self.captured_variables.get(name) {
+            return Ok((value.to_string(), true));
         }
 
-        // Request variables (highest priority for non-system variables)
+        // Request variables
         if let Some(value) = self.request_variables.get(name) {
-            return Ok(value.clone());
+            return Ok((value.clone(), false));
         }
 
         // File-level variables
         if let Some(value) = self.file_variables.get(name) {
-            return Ok(value.clone());
+            return Ok((value.clone(), false));
         }
 
         // Environment variables (active environment takes precedence)
         if let Some(env) = &self.environment {
             if let Some(value) = env.get(name) {
-                return Ok(value.clone());
+                return Ok((value.clone(), false));
             }
         }
 
         // Shared variables (fallback when not in active environment)
         if let Some(value) = self.shared_variables.get(name) {
-            return Ok(value.clone());
+            return Ok((value.clone(), false));
         }
 
         // Variable not found in any source
         Err(VarError::UndefinedVariable(name.to_string()))
     }
 
+    /// Resolves a reference to a stored response of a named request
+    fn resolve_response_reference(
+        &self,
+        request_name: &str,
+        path: &str,
+    ) -> Result<String, VarError> {
+        let response = self.named_responses.get(request_name).ok_or_else(|| {
+            VarError::UndefinedVariable(format!(
+                "{}.response.{} (no response stored for request '{}'; run it first)",
+                request_name, path, request_name
+            ))
+        })?;
+
+        resolve_response_reference(response, path)
+    }
+
     /// Resolves a system variable, parsing arguments if present
     fn resolve_system_variable_with_args(&self, name: &str) -> Result<String, VarError> {
         // Parse system variable name and arguments
@@ -138,6 +204,8 @@ impl VariableContext {
 /// This function:
 /// - Finds all {{variableName}} patterns using regex
 /// - Handles escaped braces (\{{ and \}}) as literal text
+/// - Applies `{{base64(...)}}`, `{{base64Decode(...)}}`, `{{urlEncode(...)}}`,
+///   and `{{urlDecode(...)}}` calls after resolving variables in the argument
 /// - Resolves nested variables recursively (inner-first)
 /// - Detects circular references
 /// - Preserves original formatting and whitespace
@@ -173,7 +241,253 @@ pub fn substitute_variables(text: &str, context: &VariableContext) -> Result<Str
         return Ok(text.to_string());
     }
 
-    substitute_variables_with_depth(text, context, 0, &mut HashSet::new())
+    substitute_variables_with_depth(text, context, 0, &mut HashSet::new(), &mut BTreeSet::new())
+}
+
+/// The result of [`analyze_variables`]: the substituted text and the
+/// variables that were resolved to produce it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VariableUsage {
+    /// The text with all variables substituted
+    pub text: String,
+
+    /// Names of the variables that were resolved, including variables
+    /// referenced from the values of other variables and from the arguments
+    /// of helper calls
+    pub resolved: BTreeSet<String>,
+}
+
+impl VariableUsage {
+    /// Returns `true` if the variable with this name was resolved
+    pub fn uses(&self, name: &str) -> bool {
+        self.resolved.contains(name)
+    }
+}
+
+/// Substitutes variables like [`substitute_variables`] and reports which
+/// variables were resolved
+///
+/// A variable defined in the context but missing from
+/// [`VariableUsage::resolved`] wasn't needed by `text`. Names are recorded
+/// as written between the braces, so system variables keep their `$` prefix
+/// and arguments.
+///
+/// # Arguments
+///
+/// * `text` - The input text containing {{variable}} patterns
+/// * `context` - The VariableContext containing all available variables
+///
+/// # Returns
+///
+/// The substituted text and the resolved variable names, or the same errors
+/// as [`substitute_variables`].
+///
+/// # Examples
+///
+/// ```
+/// use rest_client::variables::substitution::{analyze_variables, VariableContext};
+/// use std::path::PathBuf;
+///
+/// let mut context = VariableContext::new(PathBuf::from("/workspace"));
+/// context.file_variables.insert("host".to_string(), "api.example.com".to_string());
+/// context.file_variables.insert("baseUrl".to_string(), "https://{{host}}".to_string());
+/// context.file_variables.insert("token".to_string(), "abc123".to_string());
+///
+/// let usage = analyze_variables("GET {{baseUrl}}/users", &context).unwrap();
+/// assert_eq!(usage.text, "GET https://api.example.com/users");
+/// assert!(usage.uses("baseUrl"));
+/// assert!(usage.uses("host"));
+/// assert!(!usage.uses("token"));
+/// ```
+pub fn analyze_variables(text: &str, context: &VariableContext) -> Result<VariableUsage, VarError> {
+    let mut resolved = BTreeSet::new();
+    let text = if text.contains("{{") {
+        substitute_variables_with_depth(text, context, 0, &mut HashSet::new(), &mut resolved)?
+    } else {
+        text.to_string()
+    };
+
+    Ok(VariableUsage { text, resolved })
+}
+
+/// Returns the names of all `{{variable}}` references in `text`
+///
+/// Names are trimmed and de-duplicated, in order of first appearance. System
+/// variables keep their `$` prefix and arguments (e.g., `$randomInt 1 10`).
+/// For helper calls such as `{{base64({{user}}:{{pass}})}}`, the variables
+/// in the argument are returned rather than the call itself.
+///
+/// # Examples
+///
+/// ```
+/// use rest_client::variables::substitution::find_variable_references;
+///
+/// let names = find_variable_references("GET {{baseUrl}}/users/{{ id }}?v={{baseUrl}}");
+/// assert_eq!(names, vec!["baseUrl", "id"]);
+/// ```
+pub fn find_variable_references(text: &str) -> Vec<String> {
+    // Look inside `{{base64(...)}}` calls for the variables in the argument
+    let text = FUNCTION_CALL_REGEX.replace_all(text, "");
+    let mut seen = HashSet::new();
+    VARIABLE_REGEX
+        .captures_iter(&text)
+        .map(|cap| cap[1].trim().to_string())
+        .filter(|name| !name.is_empty() && seen.insert(name.clone()))
+        .collect()
+}
+
+/// Rewrites `:name` path segments of a URL as `{{name}}` references
+///
+/// Only whole path segments are rewritten, and only when the name starts
+/// with a letter or underscore, so ports (`localhost:8080`), the scheme, and
+/// anything in the query string or fragment are left alone.
+///
+/// # Examples
+///
+/// ```
+/// use rest_client::variables::substitution::expand_colon_params;
+///
+/// assert_eq!(
+///     expand_colon_params("http://localhost:8080/users/:id/posts/:postId?at=10:30"),
+///     "http://localhost:8080/users/{{id}}/posts/{{postId}}?at=10:30"
+/// );
+/// ```
+pub fn expand_colon_params(url: &str) -> String {
+    let path_end = url.find(['?', '#']).unwrap_or(url.len());
+    let (path, rest) = url.split_at(path_end);
+
+    let segments: Vec<String> = path
+        .split('/')
+        .map(|segment| match segment.strip_prefix(':') {
+            Some(name) if is_colon_param_name(name) => format!("{{{{{}}}}}", name),
+            _ => segment.to_string(),
+        })
+        .collect();
+
+    segments.join("/") + rest
+}
+
+/// Returns `true` if `name` can be a `:name` path parameter
+fn is_colon_param_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+/// Substitutes variables in a request URL
+///
+/// When `enableColonParams` is on, `:name` path segments are resolved from
+/// the context like `{{name}}` (see [`expand_colon_params`]).
+pub fn substitute_url(url: &str, context: &VariableContext) -> Result<String, VarError> {
+    if get_config().enable_colon_params {
+        substitute_variables(&expand_colon_params(url), context)
+    } else {
+        substitute_variables(url, context)
+    }
+}
+
+/// Substitutes variables in the URL, header values, and body of a request
+///
+/// Returns a resolved copy of the request; the original is left untouched so
+/// it can be re-resolved later against a different context. Bodies loaded
+/// with `<@ path` are read here so their variables can be resolved; raw
+/// bodies (`< path`) are left to the executor. Multipart field values and
+/// file paths are, as are `# @digest` and `# @aws` credentials and
+/// `# @apikey query` keys, which are appended to the URL. `# @form`
+/// bodies are encoded from the resolved fields. `# @header-if` headers for
+/// the context's environment are added, replacing headers of the same name.
+pub fn substitute_request(
+    request: &HttpRequest,
+    context: &VariableContext,
+) -> Result<HttpRequest, VarError> {
+    let mut resolved = request.clone();
+    resolved.url = substitute_url(&request.url, context)?;
+    if let Some((name, value)) = &request.query_api_key {
+        resolved.url = append_query_param(
+            &resolved.url,
+            &substitute_variables(name, context)?,
+            &substitute_variables(value, context)?,
+        );
+    }
+
+    resolved.headers.clear();
+    for (name, value) in &request.headers {
+        resolved
+            .headers
+            .append(name.clone(), substitute_variables(value, context)?);
+    }
+
+    let environment = context.environment.as_ref().map(|env| env.name.as_str());
+    for header in request.conditional_headers_for(environment) {
+        resolved.headers.insert(
+            header.name.clone(),
+            substitute_variables(&header.value, context)?,
+        );
+    }
+
+    if let Some(body) = request.body.as_ref().filter(|_| !request.raw_body) {
+        resolved.body = Some(substitute_variables(body, context)?);
+    }
+    if let Some(path) = request.body_file.as_ref().filter(|_| !request.raw_body) {
+        if let Some(body) = read_template_body(path)? {
+            resolved.body = Some(substitute_variables(&body, context)?);
+            resolved.body_file = None;
+        }
+    }
+
+    if !request.form_fields.is_empty() {
+        let mut form = url::form_urlencoded::Serializer::new(String::new());
+        for (key, value) in &request.form_fields {
+            form.append_pair(
+                &substitute_variables(key, context)?,
+                &substitute_variables(value, context)?,
+            );
+        }
+        resolved.body = Some(form.finish());
+    }
+
+    if let Some(credentials) = resolved.digest_auth.as_mut() {
+        credentials.username = substitute_variables(&credentials.username, context)?;
+        credentials.password = substitute_variables(&credentials.password, context)?;
+    }
+
+    if let Some(aws) = resolved.aws_sigv4.as_mut() {
+        aws.region = substitute_variables(&aws.region, context)?;
+        aws.service = substitute_variables(&aws.service, context)?;
+        aws.access_key = substitute_variables(&aws.access_key, context)?;
+        aws.secret_key = substitute_variables(&aws.secret_key, context)?;
+        if let Some(token) = aws.session_token.as_mut() {
+            *token = substitute_variables(token, context)?;
+        }
+    }
+
+    if let Some(multipart) = resolved.multipart.as_mut() {
+        for part in &mut multipart.parts {
+            match part {
+                MultipartPart::Field { value, .. } => {
+                    *value = substitute_variables(value, context)?;
+                }
+                MultipartPart::File { path, .. } => {
+                    *path = substitute_variables(&path.to_string_lossy(), context)?.into();
+                }
+            }
+        }
+    }
+
+    Ok(resolved)
+}
+
+/// Reads a `<@ path` body file so its variables can be resolved
+///
+/// Returns `None` for a file that isn't valid UTF-8; it has no variables to
+/// resolve and is sent byte for byte.
+pub fn read_template_body(path: &Path) -> Result<Option<String>, VarError> {
+    let bytes = std::fs::read(path).map_err(|e| {
+        VarError::BodyFileError(format!("Failed to read '{}': {}", path.display(), e))
+    })?;
+    Ok(String::from_utf8(bytes).ok())
 }
 
 /// Internal recursive substitution function with depth tracking and cycle detection
@@ -182,6 +496,7 @@ fn substitute_variables_with_depth(
     context: &VariableContext,
     depth: usize,
     visiting: &mut HashSet<String>,
+    resolved: &mut BTreeSet<String>,
 ) -> Result<String, VarError> {
     // Check recursion depth limit
     if depth >= MAX_RECURSION_DEPTH {
@@ -193,6 +508,9 @@ fn substitute_variables_with_depth(
     // Handle escaped braces first - replace \{{ and \}} with placeholders
     let text = text.replace("\\{{", "\u{E000}").replace("\\}}", "\u{E001}");
 
+    // Apply `{{function(argument)}}` calls, resolving the argument first
+    let text = apply_function_calls(&text, context, depth, visiting, resolved)?;
+
     // Use cached regex to avoid repeated compilations (performance optimization)
     let re = &*VARIABLE_REGEX;
 
@@ -220,13 +538,23 @@ fn substitute_variables_with_depth(
         visiting.insert(var_name.to_string());
 
         // Resolve the variable
-        let resolved_value = context.resolve_variable(var_name)?;
-
-        // Recursively substitute variables in the resolved value
-        let substituted_value =
-            substitute_variables_with_depth(&resolved_value, context, depth + 1, visiting)?;
-
-        result.push_str(&substituted_value);
+        let (resolved_value, literal) = context.resolve_variable(var_name)?;
+        resolved.insert(var_name.to_string());
+
+        // Recursively substitute variables in the resolved value, unless it
+        // came from a response
+        if literal {
+            result.push_str(&resolved_value);
+        } else {
+            let substituted_value = substitute_variables_with_depth(
+                &resolved_value,
+                context,
+                depth + 1,
+                visiting,
+                resolved,
+            )?;
+            result.push_str(&substituted_value);
+        }
 
         // Unmark this variable after processing
         visiting.remove(var_name);
@@ -243,6 +571,141 @@ fn substitute_variables_with_depth(
     Ok(result)
 }
 
+/// Replaces `{{function(argument)}}` calls with their results
+///
+/// The argument may contain `{{variable}}` references, which are resolved
+/// before the function is applied. Results are protected from further
+/// substitution, so a decoded value containing `{{` is kept literally.
+fn apply_function_calls(
+    text: &str,
+    context: &VariableContext,
+    depth: usize,
+    visiting: &mut HashSet<String>,
+    resolved: &mut BTreeSet<String>,
+) -> Result<String, VarError> {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(cap) = FUNCTION_CALL_REGEX.captures(rest) {
+        let call = cap.get(0).unwrap();
+        let function = cap.get(1).unwrap().as_str();
+        let argument_start = call.end();
+
+        let Some((argument_end, call_end)) = find_call_end(&rest[argument_start..]) else {
+            return Err(VarError::InvalidSyntax(format!(
+                "Unterminated call to '{}': expected ')}}}}'",
+                function
+            )));
+        };
+
+        let argument = &rest[argument_start..argument_start + argument_end];
+        let argument =
+            substitute_variables_with_depth(argument, context, depth + 1, visiting, resolved)?;
+        let value = apply_function(function, &argument)?;
+
+        result.push_str(&rest[..call.start()]);
+        result.push_str(&value.replace("{{", "\u{E000}").replace("}}", "\u{E001}"));
+        rest = &rest[argument_start + call_end..];
+    }
+
+    result.push_str(rest);
+    Ok(result)
+}
+
+/// Finds the `)}}` closing a function call, skipping nested `{{...}}`
+///
+/// Returns the offset where the argument ends and the offset just past the
+/// closing braces, both relative to `text`.
+fn find_call_end(text: &str) -> Option<(usize, usize)> {
+    let bytes = text.as_bytes();
+    let mut nesting = 0;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if bytes[i..].starts_with(b"{{") {
+            nesting += 1;
+            i += 2;
+        } else if nesting > 0 && bytes[i..].starts_with(b"}}") {
+            nesting -= 1;
+            i += 2;
+        } else if nesting == 0 && bytes[i] == b')' {
+            let after = text[i + 1..].trim_start();
+            if after.starts_with("}}") {
+                return Some((i, text.len() - after.len() + 2));
+            }
+            i += 1;
+        } else {
+            i += 1;
+        }
+    }
+
+    None
+}
+
+/// Applies a `{{function(argument)}}` helper to its resolved argument
+fn apply_function(function: &str, argument: &str) -> Result<String, VarError> {
+    use base64::{engine::general_purpose::STANDARD, Engine as _};
+
+    match function {
+        "base64" => Ok(STANDARD.encode(argument)),
+        "base64Decode" => {
+            let bytes = STANDARD.decode(argument.trim()).map_err(|e| {
+                VarError::InvalidSyntax(format!("base64Decode: invalid base64: {}", e))
+            })?;
+            String::from_utf8(bytes).map_err(|_| {
+                VarError::InvalidSyntax(
+                    "base64Decode: decoded value is not valid UTF-8".to_string(),
+                )
+            })
+        }
+        "urlEncode" => Ok(url_encode(argument)),
+        "urlDecode" => url_decode(argument),
+        _ => Err(VarError::UndefinedVariable(format!("{}()", function))),
+    }
+}
+
+/// Percent-encodes everything but RFC 3986 unreserved characters
+pub(crate) fn url_encode(text: &str) -> String {
+    let mut encoded = String::with_capacity(text.len());
+    for byte in text.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~".contains(&byte) {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    encoded
+}
+
+/// Decodes `%XX` escapes; a `%` not followed by two hex digits is kept
+fn url_decode(text: &str) -> Result<String, VarError> {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let escape = bytes
+            .get(i + 1..i + 3)
+            .filter(|hex| bytes[i] == b'%' && hex.iter().all(u8::is_ascii_hexdigit))
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match escape {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+
+    String::from_utf8(decoded).map_err(|_| {
+        VarError::InvalidSyntax("urlDecode: decoded value is not valid UTF-8".to_string())
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -312,6 +775,85 @@ mod tests {
         assert_eq!(result, "User ID: 12345");
     }
 
+    #[test]
+    fn test_captured_variable_priority() {
+        let mut context = create_test_context();
+        context
+            .captured_variables
+            .insert(Some("login"), "userId", "777".to_string());
+
+        // Captured values win over request variables of the same name
+        let result = substitute_variables("{{userId}} {{login.userId}}", &context).unwrap();
+        assert_eq!(result, "777 777");
+
+        let result = substitute_variables("{{refresh.userId}}", &context);
+        assert!(matches!(result, Err(VarError::UndefinedVariable(_))));
+    }
+
+    #[test]
+    fn test_function_calls() {
+        let mut context = create_test_context();
+        context
+            .file_variables
+            .insert("user".to_string(), "alice".to_string());
+        context
+            .file_variables
+            .insert("pass".to_string(), "s3cret".to_string());
+        context
+            .file_variables
+            .insert("query".to_string(), "a b&c".to_string());
+
+        let result = substitute_variables("Basic {{base64({{user}}:{{pass}})}}", &context).unwrap();
+        assert_eq!(result, "Basic YWxpY2U6czNjcmV0");
+
+        let result = substitute_variables("{{ base64(user:pass) }}", &context).unwrap();
+        assert_eq!(result, "dXNlcjpwYXNz");
+
+        let result = substitute_variables("{{base64Decode(YWxpY2U6czNjcmV0)}}", &context).unwrap();
+        assert_eq!(result, "alice:s3cret");
+
+        let result = substitute_variables(
+            "?q={{urlEncode(query with spaces)}}&r={{urlEncode({{query}})}}",
+            &context,
+        )
+        .unwrap();
+        assert_eq!(result, "?q=query%20with%20spaces&r=a%20b%26c");
+
+        let result = substitute_variables("{{urlDecode(a%20b%26c%zz)}}", &context).unwrap();
+        assert_eq!(result, "a b&c%zz");
+
+        // Calls can be nested, and results are not substituted again
+        let result =
+            substitute_variables("{{base64Decode({{base64({{user}})}})}}", &context).unwrap();
+        assert_eq!(result, "alice");
+        let result = substitute_variables("{{urlDecode(%7B%7Buser%7D%7D)}}", &context).unwrap();
+        assert_eq!(result, "{{user}}");
+    }
+
+    #[test]
+    fn test_function_call_errors() {
+        let context = create_test_context();
+
+        assert!(matches!(
+            substitute_variables("{{base64({{missing}})}}", &context),
+            Err(VarError::UndefinedVariable(_))
+        ));
+        assert!(matches!(
+            substitute_variables("{{base64Decode(not base64!)}}", &context),
+            Err(VarError::InvalidSyntax(_))
+        ));
+        assert!(matches!(
+            substitute_variables("{{base64(unterminated", &context),
+            Err(VarError::InvalidSyntax(_))
+        ));
+    }
+
+    #[test]
+    fn test_find_variable_references_in_function_calls() {
+        let names = find_variable_references("{{base64({{user}}:{{pass}})}} {{urlEncode(x)}}");
+        assert_eq!(names, vec!["user", "pass"]);
+    }
+
     #[test]
     fn test_environment_variable() {
         let context = create_test_context();
@@ -620,4 +1162,226 @@ mod tests {
         let result = substitute_variables(text, &context).unwrap();
         assert_eq!(result, "URL: http://localhost:3000/api/v2");
     }
+
+    #[test]
+    fn test_substitute_request_skips_raw_body() {
+        let mut context = VariableContext::new(PathBuf::from("/workspace"));
+        context
+            .file_variables
+            .insert("name".to_string(), "Alice".to_string());
+
+        let mut request = HttpRequest::builder()
+            .url("https://api.example.com/{{name}}")
+            .body("{{name}}")
+            .build();
+        request.raw_body = true;
+
+        let resolved = substitute_request(&request, &context).unwrap();
+        assert_eq!(resolved.url, "https://api.example.com/Alice");
+        assert_eq!(resolved.body.as_deref(), Some("{{name}}"));
+    }
+
+    #[test]
+    fn test_substitute_request_reads_template_body_file() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("user.json"), r#"{"name": "{{name}}"}"#).unwrap();
+        let mut context = VariableContext::new(PathBuf::from("/workspace"));
+        context
+            .file_variables
+            .insert("name".to_string(), "Alice".to_string());
+
+        let mut request = HttpRequest::builder()
+            .url("https://api.example.com/users")
+            .build();
+        request.body_file = Some(dir.path().join("user.json"));
+
+        let resolved = substitute_request(&request, &context).unwrap();
+        assert_eq!(resolved.body.as_deref(), Some(r#"{"name": "Alice"}"#));
+        assert_eq!(resolved.body_file, None);
+
+        // `< path` files are left for the executor to read
+        request.raw_body = true;
+        let resolved = substitute_request(&request, &context).unwrap();
+        assert_eq!(resolved.body, None);
+
+        request.raw_body = false;
+        request.body_file = Some(dir.path().join("missing.json"));
+        assert!(matches!(
+            substitute_request(&request, &context),
+            Err(VarError::BodyFileError(_))
+        ));
+    }
+
+    #[test]
+    fn test_substitute_request_encodes_resolved_form_fields() {
+        let mut context = VariableContext::new(PathBuf::from("/workspace"));
+        context
+            .file_variables
+            .insert("token".to_string(), "a&b=c d".to_string());
+
+        let mut request = HttpRequest::builder()
+            .url("https://api.example.com/login")
+            .body("user=jane&token={{token}}")
+            .build();
+        request.form_fields = vec![
+            ("user".to_string(), "jane".to_string()),
+            ("token".to_string(), "{{token}}".to_string()),
+        ];
+
+        let resolved = substitute_request(&request, &context).unwrap();
+        assert_eq!(
+            resolved.body.as_deref(),
+            Some("user=jane&token=a%26b%3Dc+d")
+        );
+    }
+
+    #[test]
+    fn test_substitute_request_appends_encoded_query_api_key() {
+        let mut context = VariableContext::new(PathBuf::from("/workspace"));
+        context
+            .file_variables
+            .insert("KEY".to_string(), "k+y/=&".to_string());
+
+        let mut request = HttpRequest::builder()
+            .url("https://api.example.com/items?page=2")
+            .build();
+        request.query_api_key = Some(("api_key".to_string(), "{{KEY}}".to_string()));
+
+        let resolved = substitute_request(&request, &context).unwrap();
+        assert_eq!(
+            resolved.url,
+            "https://api.example.com/items?page=2&api_key=k%2By%2F%3D%26"
+        );
+        assert_eq!(request.url, "https://api.example.com/items?page=2");
+    }
+
+    #[test]
+    fn test_substitute_request_conditional_headers() {
+        let mut env = Environment::new("dev");
+        env.set("level", "verbose");
+        let context = VariableContext::with_environment(
+            PathBuf::from("/workspace"),
+            Some(env),
+            HashMap::new(),
+        );
+
+        let request = HttpRequest::builder()
+            .url("https://api.example.com")
+            .header("X-Debug", "false")
+            .header_if("dev", "X-Debug", "{{level}}")
+            .header_if("prod", "X-Trace", "off")
+            .build();
+
+        let resolved = substitute_request(&request, &context).unwrap();
+        assert_eq!(resolved.headers.get("X-Debug").unwrap(), "verbose");
+        assert_eq!(resolved.headers.len(), 1);
+
+        // Without an active environment only the unconditional headers are sent
+        let context = VariableContext::new(PathBuf::from("/workspace"));
+        let resolved = substitute_request(&request, &context).unwrap();
+        assert_eq!(resolved.headers.get("X-Debug").unwrap(), "false");
+        assert!(resolved.headers.get("X-Trace").is_none());
+    }
+
+    #[test]
+    fn test_analyze_variables_used_and_unused() {
+        let context = create_test_context();
+        let usage = analyze_variables("GET https://{{host}}:{{port}}/users/{{ userId }}", &context)
+            .unwrap();
+
+        assert_eq!(
+            usage.text,
+            "GET https://staging.example.com:8080/users/12345"
+        );
+        assert_eq!(
+            usage.resolved.iter().collect::<Vec<_>>(),
+            vec!["host", "port", "userId"]
+        );
+        assert!(!usage.uses("timeout"));
+        assert!(!usage.uses("baseUrl"));
+        assert!(!usage.uses("apiKey"));
+    }
+
+    #[test]
+    fn test_analyze_variables_nested_and_function_arguments() {
+        let mut context = create_test_context();
+        context
+            .file_variables
+            .insert("apiUrl".to_string(), "https://{{host}}/v1".to_string());
+
+        let usage = analyze_variables(
+            "GET {{apiUrl}}\nAuthorization: Basic {{base64({{apiKey}}:x)}}\nX-Id: {{$guid}}",
+            &context,
+        )
+        .unwrap();
+
+        assert!(usage.uses("apiUrl"));
+        assert!(usage.uses("host"));
+        assert!(usage.uses("apiKey"));
+        assert!(usage.uses("$guid"));
+        assert!(!usage.uses("timeout"));
+    }
+
+    #[test]
+    fn test_analyze_variables_without_variables() {
+        let context = create_test_context();
+        let usage = analyze_variables("GET https://example.com", &context).unwrap();
+
+        assert_eq!(usage.text, "GET https://example.com");
+        assert!(usage.resolved.is_empty());
+
+        assert!(analyze_variables("{{missing}}", &context).is_err());
+    }
+
+    #[test]
+    fn test_expand_colon_params() {
+        assert_eq!(
+            expand_colon_params("https://api.example.com/users/:id"),
+            "https://api.example.com/users/{{id}}"
+        );
+        assert_eq!(
+            expand_colon_params("{{baseUrl}}/orgs/:org_id/repos/:repo-name/"),
+            "{{baseUrl}}/orgs/{{org_id}}/repos/{{repo-name}}/"
+        );
+    }
+
+    #[test]
+    fn test_expand_colon_params_leaves_ports_and_queries() {
+        for url in [
+            "http://localhost:8080/users",
+            "http://localhost:8080",
+            "http://[::1]:3000/health",
+            "{{host}}:8080/users",
+            "https://api.example.com/times/:30",
+            "https://api.example.com/search?from=:id&at=10:30",
+            "https://api.example.com/docs#:section",
+        ] {
+            assert_eq!(expand_colon_params(url), url);
+        }
+
+        assert_eq!(
+            expand_colon_params("http://localhost:8080/users/:id?sort=:name"),
+            "http://localhost:8080/users/{{id}}?sort=:name"
+        );
+    }
+
+    #[test]
+    fn test_substitute_colon_params_from_context() {
+        let mut context = create_test_context();
+        context
+            .file_variables
+            .insert("id".to_string(), "42".to_string());
+
+        let url = expand_colon_params("http://{{host}}:8080/users/:id/:userId");
+        assert_eq!(
+            substitute_variables(&url, &context).unwrap(),
+            "http://staging.example.com:8080/users/42/12345"
+        );
+
+        let url = expand_colon_params("http://localhost:8080/items/:missing");
+        assert!(matches!(
+            substitute_variables(&url, &context),
+            Err(VarError::UndefinedVariable(name)) if name == "missing"
+        ));
+    }
 }
@@ -1,8 +1,10 @@
 //! System variable resolution for REST Client
 //!
 //! This module implements system variables like {{$guid}}, {{$timestamp}}, {{$datetime}},
-//! {{$randomInt}}, {{$processEnv}}, and {{$dotenv}} for use in HTTP requests.
+//! {{$randomInt}}, {{$randomEmail}}, {{$randomFirstName}}, {{$randomUuid}}, {{$processEnv}},
+//! {{$dotenv}}, and {{$locale}} for use in HTTP requests.
 
+use chrono::format::{Item, StrftimeItems};
 use chrono::{DateTime, Duration, SecondsFormat, Utc};
 use rand::Rng;
 use std::collections::HashMap;
@@ -27,6 +29,8 @@ pub enum VarError {
     DotenvError(String),
     /// Circular reference detected during variable substitution
     CircularReference(String),
+    /// `<@ path` body file reading failed
+    BodyFileError(String),
 }
 
 impl std::fmt::Display for VarError {
@@ -38,12 +42,19 @@ impl std::fmt::Display for VarError {
             VarError::EnvVarNotFound(name) => write!(f, "Environment variable not found: {}", name),
             VarError::DotenvError(msg) => write!(f, "Dotenv error: {}", msg),
             VarError::CircularReference(msg) => write!(f, "Circular reference: {}", msg),
+            VarError::BodyFileError(msg) => write!(f, "Body file error: {}", msg),
         }
     }
 }
 
 impl std::error::Error for VarError {}
 
+/// First names used by `{{$randomFirstName}}` and `{{$randomEmail}}`
+const FIRST_NAMES: &[&str] = &[
+    "Alice", "Amara", "Ben", "Carlos", "Chloe", "Daniel", "Elena", "Farah", "George", "Hana",
+    "Ivan", "Jade", "Kenji", "Laura", "Mateo", "Nina", "Omar", "Priya", "Quinn", "Sofia",
+];
+
 /// Cache for .env file contents to avoid repeated file reads
 static DOTENV_CACHE: Mutex<Option<HashMap<String, String>>> = Mutex::new(None);
 
@@ -71,6 +82,10 @@ static DOTENV_CACHE: Mutex<Option<HashMap<String, String>>> = Mutex::new(None);
 ///
 /// // {{$randomInt 1 100}}
 /// resolve_system_variable("randomInt", &["1", "100"]).unwrap();
+///
+/// // {{$randomEmail}}
+/// let email = resolve_system_variable("randomEmail", &[]).unwrap();
+/// assert!(email.ends_with("@example.com"));
 /// ```
 pub fn resolve_system_variable(name: &str, args: &[&str]) -> Result<String, VarError> {
     match name {
@@ -78,8 +93,12 @@ pub fn resolve_system_variable(name: &str, args: &[&str]) -> Result<String, VarE
         "timestamp" => resolve_timestamp(args),
         "datetime" => resolve_datetime(args),
         "randomInt" => resolve_random_int(args),
+        "randomEmail" => resolve_random_email(args),
+        "randomFirstName" => resolve_random_first_name(args),
+        "randomUuid" => resolve_random_uuid(args),
         "processEnv" => resolve_process_env(args),
         "dotenv" => resolve_dotenv(args),
+        "locale" => resolve_locale(),
         _ => Err(VarError::UndefinedVariable(name.to_string())),
     }
 }
@@ -114,33 +133,75 @@ fn resolve_timestamp(args: &[&str]) -> Result<String, VarError> {
 /// - {{$datetime rfc1123}} - RFC 1123 format
 /// - {{$datetime iso8601}} - ISO 8601 format
 /// - {{$datetime rfc1123 -1 d}} - RFC 1123 format, 1 day ago
+/// - {{$datetime %Y-%m-%d}} - strftime-style pattern (UTC)
+/// - {{$datetime "%Y-%m-%d %H:%M" -1 h}} - quoted pattern containing spaces
 fn resolve_datetime(args: &[&str]) -> Result<String, VarError> {
     if args.is_empty() {
         return Err(VarError::InvalidSyntax(
-            "datetime requires format argument (rfc1123 or iso8601)".to_string(),
+            "datetime requires format argument (rfc1123, iso8601, or a strftime pattern)"
+                .to_string(),
         ));
     }
 
-    let format = args[0];
+    let (format, offset_args) = split_format_arg(args)?;
     let now = Utc::now();
 
-    // Parse offset if provided (args[1..])
-    let datetime = if args.len() > 1 {
-        parse_offset(now, &args[1..])?
-    } else {
+    // Parse offset if provided (after the format)
+    let datetime = if offset_args.is_empty() {
         now
+    } else {
+        parse_offset(now, offset_args)?
     };
 
-    match format {
+    match format.as_str() {
         "rfc1123" => Ok(datetime.to_rfc2822()),
         "iso8601" => Ok(datetime.to_rfc3339_opts(SecondsFormat::Millis, true)),
+        pattern if pattern.contains('%') => format_custom_datetime(datetime, pattern),
         _ => Err(VarError::InvalidSyntax(format!(
-            "Unknown datetime format: {}. Use 'rfc1123' or 'iso8601'",
+            "Unknown datetime format: {}. Use 'rfc1123', 'iso8601', or a strftime pattern such as '%Y-%m-%d'",
             format
         ))),
     }
 }
 
+/// Splits the format token off the front of `$datetime` arguments
+///
+/// A format wrapped in `"` or `'` may span several arguments, since the
+/// variable text is split on whitespace; the parts are rejoined with single
+/// spaces. Returns the format and the remaining (offset) arguments.
+fn split_format_arg<'a>(args: &'a [&'a str]) -> Result<(String, &'a [&'a str]), VarError> {
+    let first = args[0];
+    let Some(quote) = first.chars().next().filter(|c| *c == '"' || *c == '\'') else {
+        return Ok((first.to_string(), &args[1..]));
+    };
+
+    let end = args
+        .iter()
+        .enumerate()
+        .position(|(idx, arg)| arg.ends_with(quote) && (idx > 0 || arg.len() > 1))
+        .ok_or_else(|| {
+            VarError::InvalidSyntax(format!("Unterminated quoted datetime format: {}", first))
+        })?;
+
+    let joined = args[..=end].join(" ");
+    Ok((joined[1..joined.len() - 1].to_string(), &args[end + 1..]))
+}
+
+/// Formats a datetime with a strftime-style pattern (e.g., `%Y-%m-%d`)
+///
+/// Unknown specifiers are reported as errors instead of being rendered.
+fn format_custom_datetime(datetime: DateTime<Utc>, pattern: &str) -> Result<String, VarError> {
+    let items: Vec<Item> = StrftimeItems::new(pattern).collect();
+    if items.iter().any(|item| matches!(item, Item::Error)) {
+        return Err(VarError::InvalidSyntax(format!(
+            "Invalid datetime format pattern: {}",
+            pattern
+        )));
+    }
+
+    Ok(datetime.format_with_items(items.into_iter()).to_string())
+}
+
 /// Parses time offset from arguments
 ///
 /// Expected format: [sign][number] [unit]
@@ -211,11 +272,61 @@ fn resolve_random_int(args: &[&str]) -> Result<String, VarError> {
     Ok(value.to_string())
 }
 
+/// Generates a random email address on the reserved `example.com` domain
+///
+/// Format: {{$randomEmail}}
+///
+/// The local part is a lowercase first name followed by a number, e.g.
+/// `sofia4821@example.com`.
+fn resolve_random_email(args: &[&str]) -> Result<String, VarError> {
+    expect_no_args("randomEmail", args)?;
+
+    let mut rng = rand::thread_rng();
+    let name = FIRST_NAMES[rng.gen_range(0..FIRST_NAMES.len())].to_lowercase();
+    let number: u32 = rng.gen_range(1..10_000);
+    Ok(format!("{}{}@example.com", name, number))
+}
+
+/// Picks a random first name
+///
+/// Format: {{$randomFirstName}}
+fn resolve_random_first_name(args: &[&str]) -> Result<String, VarError> {
+    expect_no_args("randomFirstName", args)?;
+
+    let mut rng = rand::thread_rng();
+    Ok(FIRST_NAMES[rng.gen_range(0..FIRST_NAMES.len())].to_string())
+}
+
+/// Generates a random UUID v4
+///
+/// Format: {{$randomUuid}}
+///
+/// Produces the same kind of value as `{{$guid}}`; it exists alongside the
+/// other `$random*` variables for test payloads.
+fn resolve_random_uuid(args: &[&str]) -> Result<String, VarError> {
+    expect_no_args("randomUuid", args)?;
+    Ok(Uuid::new_v4().to_string())
+}
+
+/// Rejects arguments passed to a system variable that takes none
+fn expect_no_args(name: &str, args: &[&str]) -> Result<(), VarError> {
+    if args.is_empty() {
+        Ok(())
+    } else {
+        Err(VarError::InvalidSyntax(format!(
+            "{} takes no arguments, got '{}'",
+            name,
+            args.join(" ")
+        )))
+    }
+}
+
 /// Reads a process environment variable
 ///
 /// Formats:
 /// - {{$processEnv VAR_NAME}} - returns error if not found
 /// - {{$processEnv %VAR_NAME}} - returns empty string if not found (optional)
+/// - {{$processEnv VAR_NAME fallback}} - returns `fallback` if not found
 fn resolve_process_env(args: &[&str]) -> Result<String, VarError> {
     if args.is_empty() {
         return Err(VarError::InvalidSyntax(
@@ -232,10 +343,15 @@ fn resolve_process_env(args: &[&str]) -> Result<String, VarError> {
         (false, var_name)
     };
 
+    // Anything after the name is the default value
+    let default = (args.len() > 1).then(|| args[1..].join(" "));
+
     match env::var(clean_name) {
         Ok(value) => Ok(value),
         Err(_) => {
-            if is_optional {
+            if let Some(default) = default {
+                Ok(default)
+            } else if is_optional {
                 Ok(String::new())
             } else {
                 Err(VarError::EnvVarNotFound(clean_name.to_string()))
@@ -244,6 +360,36 @@ fn resolve_process_env(args: &[&str]) -> Result<String, VarError> {
     }
 }
 
+/// Resolves the locale used for `Accept-Language` testing
+///
+/// Format: {{$locale}}
+///
+/// Uses the `defaultAcceptLanguage` setting if configured, otherwise the
+/// process locale (`LC_ALL`, then `LANG`), falling back to `en-US`.
+fn resolve_locale() -> Result<String, VarError> {
+    if let Some(locale) = crate::config::get_config().default_accept_language {
+        return Ok(locale);
+    }
+
+    let process_locale = ["LC_ALL", "LANG"]
+        .iter()
+        .filter_map(|name| env::var(name).ok())
+        .find_map(|value| posix_locale_to_language_tag(&value));
+
+    Ok(process_locale.unwrap_or_else(|| "en-US".to_string()))
+}
+
+/// Converts a POSIX locale such as `fr_FR.UTF-8` into a language tag (`fr-FR`)
+///
+/// Returns `None` for empty values and the `C`/`POSIX` locales.
+fn posix_locale_to_language_tag(locale: &str) -> Option<String> {
+    let base = locale.split(['.', '@']).next().unwrap_or("").trim();
+    if base.is_empty() || base == "C" || base == "POSIX" {
+        return None;
+    }
+    Some(base.replace('_', "-"))
+}
+
 /// Reads a variable from .env file in workspace
 ///
 /// Format: {{$dotenv VAR_NAME}}
@@ -426,6 +572,45 @@ mod tests {
         assert!(result.contains('T'));
     }
 
+    #[test]
+    fn test_resolve_datetime_negative_day_offset() {
+        let result = resolve_system_variable("datetime", &["iso8601", "-1", "d"]).unwrap();
+        let yesterday = DateTime::parse_from_rfc3339(&result).unwrap();
+
+        let elapsed = Utc::now().signed_duration_since(yesterday);
+        assert!(elapsed >= Duration::days(1));
+        assert!(elapsed < Duration::days(1) + Duration::seconds(5));
+    }
+
+    #[test]
+    fn test_resolve_datetime_custom_format() {
+        let result = resolve_system_variable("datetime", &["%Y-%m-%d"]).unwrap();
+        let date = chrono::NaiveDate::parse_from_str(&result, "%Y-%m-%d").unwrap();
+        assert!((Utc::now().date_naive() - date).num_days() <= 1);
+
+        // Quoted pattern with a space, followed by an offset
+        let result =
+            resolve_system_variable("datetime", &["\"%d/%m/%Y", "%H:%M\"", "-2", "d"]).unwrap();
+        let datetime = chrono::NaiveDateTime::parse_from_str(&result, "%d/%m/%Y %H:%M").unwrap();
+        let elapsed = Utc::now().naive_utc() - datetime;
+        assert!(elapsed >= Duration::days(2) && elapsed < Duration::days(2) + Duration::minutes(2));
+
+        let result = resolve_system_variable("datetime", &["'%H:%M'"]).unwrap();
+        assert_eq!(result.len(), 5);
+    }
+
+    #[test]
+    fn test_resolve_datetime_invalid_custom_format() {
+        assert!(matches!(
+            resolve_system_variable("datetime", &["%Y-%Q"]),
+            Err(VarError::InvalidSyntax(_))
+        ));
+        assert!(matches!(
+            resolve_system_variable("datetime", &["\"%Y-%m-%d", "-1", "d"]),
+            Err(VarError::InvalidSyntax(_))
+        ));
+    }
+
     #[test]
     fn test_resolve_datetime_invalid_format() {
         let result = resolve_system_variable("datetime", &["invalid"]);
@@ -453,6 +638,49 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_resolve_random_int_invalid_args() {
+        for args in [&[][..], &["1"], &["one", "10"], &["1", "1.5"]] {
+            assert!(matches!(
+                resolve_system_variable("randomInt", args),
+                Err(VarError::InvalidSyntax(_))
+            ));
+        }
+
+        let result = resolve_system_variable("randomInt", &["-5", "-5"]).unwrap();
+        assert_eq!(result, "-5");
+    }
+
+    #[test]
+    fn test_resolve_random_email() {
+        for _ in 0..20 {
+            let email = resolve_system_variable("randomEmail", &[]).unwrap();
+            let (local, domain) = email.split_once('@').unwrap();
+            assert_eq!(domain, "example.com");
+
+            let digits = local.trim_start_matches(|c: char| c.is_ascii_lowercase());
+            let name = &local[..local.len() - digits.len()];
+            assert!(FIRST_NAMES.iter().any(|n| n.to_lowercase() == name));
+            let number: u32 = digits.parse().unwrap();
+            assert!((1..10_000).contains(&number));
+        }
+
+        assert!(matches!(
+            resolve_system_variable("randomEmail", &["gmail.com"]),
+            Err(VarError::InvalidSyntax(_))
+        ));
+    }
+
+    #[test]
+    fn test_resolve_random_first_name_and_uuid() {
+        let name = resolve_system_variable("randomFirstName", &[]).unwrap();
+        assert!(FIRST_NAMES.contains(&name.as_str()));
+
+        let uuid = resolve_system_variable("randomUuid", &[]).unwrap();
+        assert_eq!(Uuid::parse_str(&uuid).unwrap().get_version_num(), 4);
+        assert_ne!(uuid, resolve_system_variable("randomUuid", &[]).unwrap());
+    }
+
     #[test]
     fn test_resolve_process_env() {
         // Set a test environment variable
@@ -478,12 +706,57 @@ mod tests {
         env::remove_var("TEST_OPTIONAL_VAR");
     }
 
+    #[test]
+    fn test_resolve_process_env_default() {
+        env::set_var("TEST_DEFAULT_VAR_REST_CLIENT", "from_env");
+        let result =
+            resolve_system_variable("processEnv", &["TEST_DEFAULT_VAR_REST_CLIENT", "fallback"])
+                .unwrap();
+        assert_eq!(result, "from_env");
+
+        env::remove_var("TEST_DEFAULT_VAR_REST_CLIENT");
+        let result =
+            resolve_system_variable("processEnv", &["TEST_DEFAULT_VAR_REST_CLIENT", "fallback"])
+                .unwrap();
+        assert_eq!(result, "fallback");
+
+        let result = resolve_system_variable(
+            "processEnv",
+            &["%TEST_DEFAULT_VAR_REST_CLIENT", "two", "words"],
+        )
+        .unwrap();
+        assert_eq!(result, "two words");
+    }
+
     #[test]
     fn test_resolve_process_env_not_found() {
         let result = resolve_system_variable("processEnv", &["DEFINITELY_NOT_SET_VAR_12345"]);
         assert!(matches!(result, Err(VarError::EnvVarNotFound(_))));
     }
 
+    #[test]
+    fn test_resolve_locale() {
+        let locale = resolve_system_variable("locale", &[]).unwrap();
+        assert!(!locale.is_empty());
+        assert!(!locale.contains('_'));
+    }
+
+    #[test]
+    fn test_posix_locale_to_language_tag() {
+        assert_eq!(
+            posix_locale_to_language_tag("fr_FR.UTF-8"),
+            Some("fr-FR".to_string())
+        );
+        assert_eq!(
+            posix_locale_to_language_tag("de_DE@euro"),
+            Some("de-DE".to_string())
+        );
+        assert_eq!(posix_locale_to_language_tag("en"), Some("en".to_string()));
+        assert_eq!(posix_locale_to_language_tag("C.UTF-8"), None);
+        assert_eq!(posix_locale_to_language_tag("POSIX"), None);
+        assert_eq!(posix_locale_to_language_tag(""), None);
+    }
+
     #[test]
     fn test_parse_offset_units() {
         let now = Utc::now();
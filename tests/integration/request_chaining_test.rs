@@ -5,11 +5,10 @@
 
 use rest_client::environment::{Environment, EnvironmentSession, Environments};
 use rest_client::history::HistoryEntry;
-use rest_client::models::{HttpMethod, HttpRequest, HttpResponse, RequestTiming};
+use rest_client::models::{Headers, HttpMethod, HttpRequest, HttpResponse, RequestTiming};
 use rest_client::parser::parse_file;
 
 use serde_json::json;
-use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
 use std::time::Duration;
@@ -361,11 +360,28 @@ fn test_multiple_environments() {
 fn test_history_entry_creation() {
     let request = HttpRequest {
         id: uuid::Uuid::new_v4().to_string(),
+        name: None,
         method: HttpMethod::GET,
         url: "https://example.com/api/test".to_string(),
         http_version: Some("HTTP/1.1".to_string()),
-        headers: HashMap::new(),
+        headers: Headers::new(),
         body: None,
+        raw_body: false,
+        body_encoding: None,
+        timeout_override: None,
+        retries_override: None,
+        no_cookies: false,
+        filter: None,
+        pipe: None,
+        body_file: None,
+        conditional_headers: Vec::new(),
+        captures: Vec::new(),
+        digest_auth: None,
+        aws_sigv4: None,
+        multipart: None,
+        form_fields: Vec::new(),
+        query_api_key: None,
+        api_key_header: None,
         line_number: 1,
         file_path: PathBuf::new(),
     };
@@ -433,3 +449,57 @@ fn test_environment_merged_variables() {
     assert_eq!(active.get("baseUrl").unwrap(), "http://localhost:3000");
     assert_eq!(active.get("apiKey").unwrap(), "dev-key");
 }
+
+#[test]
+fn test_capture_chains_login_into_authorized_request() {
+    use rest_client::variables::substitution::VariableContext;
+    use rest_client::variables::RequestChain;
+
+    let temp_dir = TempDir::new().unwrap();
+    let http_content = r#"# @name login
+POST https://api.example.com/auth/login
+Content-Type: application/json
+
+{"username": "test", "password": "pass"}
+
+# @capture token = $.auth.token
+# @capture userId = $.auth.users[0].id
+
+###
+
+GET https://api.example.com/users/{{login.userId}}
+Authorization: Bearer {{login.token}}
+"#;
+
+    let file_path = create_temp_http_file(&temp_dir, "capture.http", http_content);
+    let requests = parse_file(http_content, &file_path).unwrap();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[0].captures.len(), 2);
+
+    let mut chain = RequestChain::new();
+    let context = VariableContext::new(temp_dir.path().to_path_buf());
+
+    chain
+        .execute(requests[0].name.as_deref(), &requests[0], &context, |_| {
+            Ok(create_mock_response(
+                200,
+                json!({"auth": {"token": "secret-auth-token-xyz", "users": [{"id": 42}]}}),
+            ))
+        })
+        .unwrap();
+
+    let mut sent = None;
+    chain
+        .execute(None, &requests[1], &context, |resolved| {
+            sent = Some(resolved.clone());
+            Ok(create_mock_response(200, json!({"id": 42})))
+        })
+        .unwrap();
+
+    let sent = sent.expect("Authorized request should be executed");
+    assert_eq!(sent.url, "https://api.example.com/users/42");
+    assert_eq!(
+        sent.headers.get("Authorization").unwrap(),
+        "Bearer secret-auth-token-xyz"
+    );
+}